@@ -4,10 +4,11 @@ use std::str::FromStr;
 
 /// Describes the alignment type used by an alignment.
 ///
-/// Alignments can either be DNA, protein, or generic.
+/// Alignments can either be DNA, RNA, protein, or generic.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AlignmentType {
     Dna,
+    Rna,
     Protein,
     Generic,
 }
@@ -16,18 +17,23 @@ impl AlignmentType {
     /// Returns the alphabet size used for conservation calculations.
     pub const fn conservation_alphabet_size(self) -> Option<NonZeroU8> {
         match self {
-            Self::Dna => NonZeroU8::new(4),
+            Self::Dna | Self::Rna => NonZeroU8::new(4),
             Self::Protein => NonZeroU8::new(20),
             Self::Generic => None,
         }
     }
 
+    /// Returns whether this alignment type holds nucleotides, either DNA or RNA.
+    pub const fn is_nucleotide(self) -> bool {
+        matches!(self, Self::Dna | Self::Rna)
+    }
+
     /// Returns whether this alignment type can be translated.
     ///
-    /// Only DNA alignments support translation. Protein and generic alignments
-    /// return `false`.
+    /// Only nucleotide alignments support translation, with `U` read as `T`.
+    /// Protein and generic alignments return `false`.
     pub const fn supports_translation(self) -> bool {
-        matches!(self, Self::Dna)
+        self.is_nucleotide()
     }
 }
 
@@ -35,6 +41,7 @@ impl std::fmt::Display for AlignmentType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Dna => f.write_str("dna"),
+            Self::Rna => f.write_str("rna"),
             Self::Protein => f.write_str("protein"),
             Self::Generic => f.write_str("generic"),
         }
@@ -47,6 +54,7 @@ impl FromStr for AlignmentType {
     fn from_str(value: &str) -> Result<Self, Self::Err> {
         match value {
             "dna" => Ok(Self::Dna),
+            "rna" => Ok(Self::Rna),
             "protein" => Ok(Self::Protein),
             "generic" => Ok(Self::Generic),
             _ => Err(AlignmentError::InvalidAlignmentType),
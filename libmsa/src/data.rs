@@ -5,6 +5,8 @@ use crate::error::AlignmentError;
 pub struct RawSequence {
     pub id: String,
     pub sequence: Vec<u8>,
+    /// Per-base Phred quality scores, when the input carried them.
+    pub quality: Option<Vec<u8>>,
 }
 
 /// Represents a validated sequence in an alignment.
@@ -15,6 +17,7 @@ pub struct RawSequence {
 pub struct Sequence {
     id: String,
     sequence: Box<[u8]>,
+    quality: Option<Box<[u8]>>,
 }
 
 impl Sequence {
@@ -27,6 +30,31 @@ impl Sequence {
     pub fn sequence(&self) -> &[u8] {
         &self.sequence
     }
+
+    /// Returns the per-base Phred quality scores, if any.
+    pub fn quality(&self) -> Option<&[u8]> {
+        self.quality.as_deref()
+    }
+}
+
+impl Sequence {
+    fn from_raw(raw: RawSequence) -> Result<Self, AlignmentError> {
+        if let Some(quality) = &raw.quality
+            && quality.len() != raw.sequence.len()
+        {
+            return Err(AlignmentError::QualityLengthMismatch {
+                expected: raw.sequence.len(),
+                actual: quality.len(),
+                id: raw.id,
+            });
+        }
+
+        Ok(Self {
+            id: raw.id,
+            sequence: raw.sequence.into_boxed_slice(),
+            quality: raw.quality.map(Vec::into_boxed_slice),
+        })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -47,10 +75,7 @@ impl AlignmentData {
         }
 
         let width = first.sequence.len();
-        let first = Sequence {
-            id: first.id,
-            sequence: first.sequence.into_boxed_slice(),
-        };
+        let first = Sequence::from_raw(first)?;
 
         let mut normalised = Vec::with_capacity(1 + raw_iter.len());
         normalised.push(first);
@@ -69,10 +94,7 @@ impl AlignmentData {
                 });
             }
 
-            normalised.push(Sequence {
-                id: raw.id,
-                sequence: raw.sequence.into_boxed_slice(),
-            });
+            normalised.push(Sequence::from_raw(raw)?);
             Ok(normalised)
         })?;
 
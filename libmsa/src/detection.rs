@@ -68,22 +68,29 @@ pub(crate) fn detect_alignment_type(
     options: DetectionOptions,
     rng: &mut impl rand::Rng,
 ) -> AlignmentType {
-    let (protein_count, nucleotide_count, total_count) = alignment
+    let (protein_count, nucleotide_count, uracil_count, total_count) = alignment
         .sequences
         .choose_multiple(rng, options.sample_size())
         .flat_map(|sequence| sequence.sequence().iter().copied())
         .filter(|byte| !matches!(byte, b'-' | b'.'))
         .map(|byte| byte.to_ascii_uppercase())
         .fold(
-            (0usize, 0usize, 0usize),
-            |(protein, nucleotide, total), byte| {
+            (0usize, 0usize, 0isize, 0usize),
+            |(protein, nucleotide, uracil, total), byte| {
                 (
                     protein + usize::from(PROTEIN_BYTES.contains(&byte)),
                     nucleotide + usize::from(NUCLEOTIDE_BYTES.contains(&byte)),
+                    uracil + isize::from(byte == b'U') - isize::from(byte == b'T'),
                     total + 1,
                 )
             },
         );
+    // nucleotide alignments with more U than T are RNA
+    let nucleotide_type = if uracil_count > 0 {
+        AlignmentType::Rna
+    } else {
+        AlignmentType::Dna
+    };
 
     if total_count == 0 {
         return AlignmentType::Generic;
@@ -96,11 +103,11 @@ pub(crate) fn detect_alignment_type(
 
     match (protein_matches, nucleotide_matches) {
         (true, false) => AlignmentType::Protein,
-        (false, true) => AlignmentType::Dna,
+        (false, true) => nucleotide_type,
         (false, false) => AlignmentType::Generic,
         (true, true) => match protein_fraction.total_cmp(&nucleotide_fraction) {
             std::cmp::Ordering::Greater => AlignmentType::Protein,
-            std::cmp::Ordering::Less => AlignmentType::Dna,
+            std::cmp::Ordering::Less => nucleotide_type,
             std::cmp::Ordering::Equal => AlignmentType::Generic,
         },
     }
@@ -120,6 +127,7 @@ mod detect_alignment_type_tests {
         RawSequence {
             id: id.to_string(),
             sequence: sequence.to_vec(),
+            quality: None,
         }
     }
 
@@ -145,6 +153,15 @@ mod detect_alignment_type_tests {
         );
     }
 
+    #[test]
+    fn detect_classifies_rna() {
+        let data = make_data(&[("seq-1", b"ACG---U"), ("seq-2", b"ugc---a")]);
+        assert_eq!(
+            detect_with_seed(&data, DetectionOptions::default(), 1),
+            AlignmentType::Rna
+        );
+    }
+
     #[test]
     fn detect_classifies_protein() {
         let data = make_data(&[
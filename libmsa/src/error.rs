@@ -18,6 +18,13 @@ pub enum AlignmentError {
         actual: usize,
         id: String,
     },
+    /// A sequence's quality scores do not cover every base.
+    #[error("sequence '{id}' has {actual} quality scores, expected {expected}")]
+    QualityLengthMismatch {
+        expected: usize,
+        actual: usize,
+        id: String,
+    },
     /// A requested column index lies outside the alignment.
     #[error("column index {index} is out of bounds for alignment length {length}")]
     ColumnOutOfBounds { index: usize, length: usize },
@@ -3,6 +3,7 @@ use std::sync::Arc;
 
 use regex::Regex;
 
+use crate::data::AlignmentData;
 use crate::error::AlignmentError;
 use crate::metrics;
 use crate::model::Alignment;
@@ -16,13 +17,30 @@ use crate::projection::Projection;
 /// 1. Regex - rows not matching [`Self::with_row_regex`] are removed.
 /// 2. Exclusion - explicit excludes ([`Self::without_rows`]) are removed last.
 ///
-/// Column filters ([`Self::with_max_gap_fraction`]) run over the final row set.
+/// Explicitly excluded columns ([`Self::without_columns`]) are removed first. The remaining
+/// column filters ([`Self::with_max_gap_fraction`], [`Self::with_insert_columns`], then
+/// [`Self::with_variable_columns_only`]) run over the final row set.
 #[derive(Debug, Clone)]
 pub struct FilterBuilder<'a> {
     source: &'a Alignment,
     row_exclude_sets: Vec<Vec<usize>>,
     row_name_regex: Option<String>,
+    column_excludes: Vec<usize>,
     max_gap_fraction: Option<f32>,
+    insert_columns: Option<InsertColumns>,
+    variable_columns_only: bool,
+    variable_reference: Option<usize>,
+}
+
+/// How A2M insert columns are treated by [`FilterBuilder::with_insert_columns`].
+///
+/// An insert column is one where every row holds a lowercase residue or `.`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InsertColumns {
+    /// Removes every insert column.
+    Hide,
+    /// Keeps only the first column of each run of adjacent insert columns.
+    Compress,
 }
 
 impl<'a> FilterBuilder<'a> {
@@ -43,12 +61,40 @@ impl<'a> FilterBuilder<'a> {
         self
     }
 
+    /// Excludes the supplied columns from the filtered view.
+    pub fn without_columns<I>(mut self, column_ids: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Borrow<usize>,
+    {
+        self.column_excludes
+            .extend(column_ids.into_iter().map(|column_id| *column_id.borrow()));
+        self
+    }
+
     /// Keeps only columns whose gap fraction is at most `threshold`.
     pub fn with_max_gap_fraction(mut self, threshold: f32) -> Self {
         self.max_gap_fraction = Some(threshold);
         self
     }
 
+    /// Hides or compresses A2M insert columns.
+    pub fn with_insert_columns(mut self, mode: InsertColumns) -> Self {
+        self.insert_columns = Some(mode);
+        self
+    }
+
+    /// Keeps only columns where the remaining rows do not all agree.
+    ///
+    /// With a `reference` row, a column is kept when any remaining row differs from the
+    /// reference instead. Residues are compared case-insensitively and gaps count as a
+    /// difference.
+    pub fn with_variable_columns_only(mut self, reference: Option<usize>) -> Self {
+        self.variable_columns_only = true;
+        self.variable_reference = reference;
+        self
+    }
+
     /// Resolves all filters and builds a new [`Alignment`]
     pub fn apply(self) -> Result<Alignment, AlignmentError> {
         let row_count = self.source.row_count();
@@ -57,9 +103,22 @@ impl<'a> FilterBuilder<'a> {
         for row_ids in &self.row_exclude_sets {
             validate_row_ids(row_ids, row_count)?;
         }
+        if let Some(&index) = self
+            .column_excludes
+            .iter()
+            .find(|&&column_id| column_id >= column_count)
+        {
+            return Err(AlignmentError::ColumnOutOfBounds {
+                index,
+                length: column_count,
+            });
+        }
         if let Some(max_gap_fraction) = self.max_gap_fraction {
             validate_gap_fraction(max_gap_fraction)?;
         }
+        if let Some(reference) = self.variable_reference {
+            validate_row_ids(&[reference], row_count)?;
+        }
 
         let mut row_ids: Vec<usize> = (0..row_count).collect();
         if let Some(pattern) = &self.row_name_regex {
@@ -79,7 +138,13 @@ impl<'a> FilterBuilder<'a> {
         }
         row_ids.retain(|&row_id| membership[row_id]);
 
-        let mut column_ids: Vec<usize> = (0..column_count).collect();
+        let mut column_membership = vec![true; column_count];
+        for &column_id in &self.column_excludes {
+            column_membership[column_id] = false;
+        }
+        let mut column_ids: Vec<usize> = (0..column_count)
+            .filter(|&column_id| column_membership[column_id])
+            .collect();
         if let Some(max_gap_fraction) = self.max_gap_fraction {
             let temp_rows = Projection::Filtered(Arc::from(row_ids.as_slice()));
             let gap_fractions = metrics::counted_columns_range(
@@ -97,6 +162,26 @@ impl<'a> FilterBuilder<'a> {
             });
         }
 
+        if let Some(mode) = self.insert_columns {
+            let insert_mask = insert_column_mask(&self.source.data, &row_ids);
+            let mut previous_was_insert = false;
+            column_ids.retain(|&column_id| {
+                let is_insert = insert_mask[column_id];
+                let keep = match mode {
+                    InsertColumns::Hide => !is_insert,
+                    InsertColumns::Compress => !(is_insert && previous_was_insert),
+                };
+                previous_was_insert = is_insert;
+                keep
+            });
+        }
+
+        if self.variable_columns_only {
+            let variable_mask =
+                variable_column_mask(&self.source.data, &row_ids, self.variable_reference);
+            column_ids.retain(|&column_id| variable_mask[column_id]);
+        }
+
         let rows_proj = if row_ids.len() == row_count {
             Projection::Full { len: row_count }
         } else {
@@ -125,11 +210,51 @@ impl<'a> FilterBuilder<'a> {
             source,
             row_exclude_sets: Vec::new(),
             row_name_regex: None,
+            column_excludes: Vec::new(),
             max_gap_fraction: None,
+            insert_columns: None,
+            variable_columns_only: false,
+            variable_reference: None,
         }
     }
 }
 
+const fn is_insert_byte(byte: u8) -> bool {
+    byte.is_ascii_lowercase() || byte == b'.'
+}
+
+/// Classifies each absolute column as an insert column over the given rows.
+fn insert_column_mask(data: &AlignmentData, row_ids: &[usize]) -> Vec<bool> {
+    let mut mask = vec![!row_ids.is_empty(); data.length];
+    for &row_id in row_ids {
+        for (is_insert, &byte) in mask.iter_mut().zip(data.sequences[row_id].sequence()) {
+            *is_insert &= is_insert_byte(byte);
+        }
+    }
+    mask
+}
+
+/// Classifies each absolute column as variable over the given rows, comparing every row against
+/// `reference` or, without one, against the first row.
+fn variable_column_mask(
+    data: &AlignmentData,
+    row_ids: &[usize],
+    reference: Option<usize>,
+) -> Vec<bool> {
+    let mut mask = vec![false; data.length];
+    let Some(anchor_row) = reference.or_else(|| row_ids.first().copied()) else {
+        return mask;
+    };
+    let anchor = data.sequences[anchor_row].sequence();
+    for &row_id in row_ids {
+        let sequence = data.sequences[row_id].sequence();
+        for ((is_variable, &byte), &anchor_byte) in mask.iter_mut().zip(sequence).zip(anchor) {
+            *is_variable |= !byte.eq_ignore_ascii_case(&anchor_byte);
+        }
+    }
+    mask
+}
+
 fn compile_regex(pattern: &str) -> Result<Regex, AlignmentError> {
     Regex::new(pattern).map_err(|error| AlignmentError::InvalidRegex {
         pattern: pattern.to_string(),
@@ -164,13 +289,15 @@ fn validate_gap_fraction(threshold: f32) -> Result<(), AlignmentError> {
 #[cfg(test)]
 mod filter_builder_tests {
     use crate::{
-        Alignment, AlignmentError, AlignmentType, ColumnSummary, ConsensusMethod, RawSequence,
+        Alignment, AlignmentError, AlignmentType, ColumnSummary, ConsensusMethod, InsertColumns,
+        RawSequence,
     };
 
     fn raw(id: &str, sequence: &[u8]) -> RawSequence {
         RawSequence {
             id: id.to_string(),
             sequence: sequence.to_vec(),
+            quality: None,
         }
     }
 
@@ -194,6 +321,28 @@ mod filter_builder_tests {
         .unwrap()
     }
 
+    #[test]
+    fn excluded_columns_are_removed_before_column_filters() {
+        let alignment = dna_alignment(&[("a", b"AC-GT"), ("b", b"AC-GA")]);
+
+        let filtered = alignment
+            .filter()
+            .unwrap()
+            .without_columns([1, 4])
+            .with_max_gap_fraction(0.0)
+            .apply()
+            .unwrap();
+
+        assert_eq!(filtered.absolute_column_ids().collect::<Vec<_>>(), [0, 3]);
+        assert!(matches!(
+            alignment.filter().unwrap().without_columns([5]).apply(),
+            Err(AlignmentError::ColumnOutOfBounds {
+                index: 5,
+                length: 5
+            })
+        ));
+    }
+
     #[test]
     fn filter_builder_is_order_insensitive() {
         let alignment = dna_alignment(&[
@@ -225,6 +374,83 @@ mod filter_builder_tests {
         assert_eq!(first_ids, second_ids);
     }
 
+    #[test]
+    fn variable_columns_only_keeps_columns_where_rows_disagree() {
+        let alignment = dna_alignment(&[("a", b"ACGTa"), ("b", b"ACTTA"), ("c", b"A-GTA")]);
+
+        let filtered = alignment
+            .filter()
+            .unwrap()
+            .with_variable_columns_only(None)
+            .apply()
+            .unwrap();
+
+        let columns: Vec<_> = filtered.absolute_column_ids().collect();
+        assert_eq!(columns, vec![1, 2]);
+    }
+
+    #[test]
+    fn variable_columns_only_compares_against_the_reference() {
+        let alignment = dna_alignment(&[("ref", b"ACGT"), ("b", b"ACTT"), ("c", b"ACTT")]);
+
+        let filtered = alignment
+            .filter()
+            .unwrap()
+            .without_rows([0])
+            .with_variable_columns_only(Some(0))
+            .apply()
+            .unwrap();
+
+        let columns: Vec<_> = filtered.absolute_column_ids().collect();
+        assert_eq!(columns, vec![2]);
+    }
+
+    #[test]
+    fn insert_columns_can_be_hidden() {
+        let alignment = dna_alignment(&[("a", b"AcgT-"), ("b", b"A..Tt"), ("c", b"A.gTa")]);
+
+        let filtered = alignment
+            .filter()
+            .unwrap()
+            .with_insert_columns(InsertColumns::Hide)
+            .apply()
+            .unwrap();
+
+        let columns: Vec<_> = filtered.absolute_column_ids().collect();
+        assert_eq!(columns, vec![0, 3, 4]);
+    }
+
+    #[test]
+    fn insert_columns_can_be_compressed() {
+        let alignment = dna_alignment(&[("a", b"Acg.Tt"), ("b", b"A..aTt")]);
+
+        let filtered = alignment
+            .filter()
+            .unwrap()
+            .with_insert_columns(InsertColumns::Compress)
+            .apply()
+            .unwrap();
+
+        let columns: Vec<_> = filtered.absolute_column_ids().collect();
+        assert_eq!(columns, vec![0, 1, 4, 5]);
+    }
+
+    #[test]
+    fn insert_columns_are_classified_over_visible_rows() {
+        let alignment = dna_alignment(&[("a", b"Ac"), ("b", b"AC")]);
+
+        let filtered = alignment
+            .filter()
+            .unwrap()
+            .without_rows([1])
+            .with_insert_columns(InsertColumns::Hide)
+            .apply()
+            .unwrap();
+
+        let columns: Vec<_> = filtered.absolute_column_ids().collect();
+        assert_eq!(columns, vec![0]);
+    }
+
     #[test]
     fn filter_supports_regex_and_index_filters() {
         let alignment = generic_alignment(&[
@@ -339,6 +565,7 @@ mod filtered_alignment_behaviour_tests {
         RawSequence {
             id: id.to_string(),
             sequence: sequence.to_vec(),
+            quality: None,
         }
     }
 
@@ -12,9 +12,9 @@ pub use alignment_type::AlignmentType;
 pub use data::{RawSequence, Sequence};
 pub use detection::DetectionOptions;
 pub use error::AlignmentError;
-pub use filter::FilterBuilder;
-pub use metrics::{ColumnSummary, ConsensusMethod};
+pub use filter::{FilterBuilder, InsertColumns};
+pub use metrics::{ColumnCounts, ColumnSummary, ConsensusMethod};
 pub use model::{Alignment, SequenceView};
 pub use translation::{
-    ReadingFrame, TranslatedAlignment, TranslatedSequenceView, TranslationTable,
+    ReadingFrame, TranslatedAlignment, TranslatedSequenceView, TranslationGapMode, TranslationTable,
 };
@@ -5,7 +5,9 @@ use crate::data::AlignmentData;
 use crate::error::AlignmentError;
 use crate::model::Alignment;
 use crate::projection::Projection;
-use crate::translation::{ReadingFrame, TranslationTable, translated_byte_at};
+use crate::translation::{
+    ReadingFrame, TranslationGapMode, TranslationTable, translated_byte_at, translated_range,
+};
 
 /// Selects how consensus bytes are chosen for alignment columns.
 ///
@@ -56,8 +58,83 @@ impl std::str::FromStr for ConsensusMethod {
 pub struct ColumnSummary {
     pub position: usize,
     pub consensus: Option<u8>,
+    /// Fraction of all rows in the column that carry the consensus byte.
+    pub consensus_support: f32,
     pub conservation: Option<f32>,
     pub gap_fraction: f32,
+    /// Whether every row in the column is a gap.
+    pub gap_only: bool,
+}
+
+/// Byte counts of a single column, holding only the bytes that occur.
+///
+/// Counts can be adjusted as rows enter or leave an alignment's row projection
+/// and turned back into a [`ColumnSummary`] with
+/// [`Alignment::summary_from_counts`], without rescanning the column.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnCounts {
+    /// `(byte, count)` pairs sorted by byte, with no zero counts.
+    counts: Vec<(u8, u32)>,
+}
+
+impl ColumnCounts {
+    pub(crate) fn from_dense(counts: &[u32; 256]) -> Self {
+        Self {
+            counts: (0..=u8::MAX)
+                .zip(counts.iter().copied())
+                .filter(|&(_, count)| count != 0)
+                .collect(),
+        }
+    }
+
+    fn to_dense(&self) -> [u32; 256] {
+        let mut counts = [0u32; 256];
+        for &(byte, count) in &self.counts {
+            counts[usize::from(byte)] = count;
+        }
+        counts
+    }
+
+    /// Returns how many rows carry `byte`.
+    pub fn count(&self, byte: u8) -> u32 {
+        self.counts
+            .binary_search_by_key(&byte, |&(symbol, _)| symbol)
+            .map_or(0, |index| self.counts[index].1)
+    }
+
+    /// Returns how many distinct bytes occur in the column.
+    pub fn distinct(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Returns the number of rows counted.
+    pub fn total(&self) -> u32 {
+        self.counts.iter().map(|&(_, count)| count).sum()
+    }
+
+    /// Counts one more row carrying `byte`.
+    pub fn add(&mut self, byte: u8) {
+        match self
+            .counts
+            .binary_search_by_key(&byte, |&(symbol, _)| symbol)
+        {
+            Ok(index) => self.counts[index].1 += 1,
+            Err(index) => self.counts.insert(index, (byte, 1)),
+        }
+    }
+
+    /// Counts one fewer row carrying `byte`. Removing a byte that is not counted does nothing.
+    pub fn remove(&mut self, byte: u8) {
+        if let Ok(index) = self
+            .counts
+            .binary_search_by_key(&byte, |&(symbol, _)| symbol)
+        {
+            self.counts[index].1 -= 1;
+            if self.counts[index].1 == 0 {
+                self.counts.remove(index);
+            }
+        }
+    }
 }
 
 pub(crate) struct CountedColumn {
@@ -146,6 +223,40 @@ impl Alignment {
         Ok(gap_fraction_from_columns(&columns))
     }
 
+    /// Returns whether each relative column in `range` is a gap in every row.
+    ///
+    /// Each position is resolved against the alignment's current column projection.
+    /// Unlike [`Alignment::gap_fraction_range`] this stops scanning a column at its
+    /// first non-gap byte, so it stays cheap enough to run over the full width.
+    ///
+    /// # Errors
+    ///
+    /// [`AlignmentError::EmptyRange`] if `range` is empty.
+    ///
+    /// [`AlignmentError::ColumnOutOfBounds`] if `range.end` is greater than the
+    /// current column projection width.
+    pub fn gap_only_range(&self, range: Range<usize>) -> Result<Vec<bool>, AlignmentError> {
+        if range.is_empty() {
+            return Err(AlignmentError::EmptyRange);
+        }
+        if range.end > self.columns.len() {
+            return Err(AlignmentError::ColumnOutOfBounds {
+                index: range.end,
+                length: self.columns.len(),
+            });
+        }
+
+        Ok(range
+            .map(|rel_col| {
+                let abs_col = self
+                    .columns
+                    .absolute(rel_col)
+                    .expect("validated range positions map into the projection");
+                column_is_gap_only(&self.data, &self.rows, abs_col)
+            })
+            .collect())
+    }
+
     /// Returns a derived summary for each requested relative column.
     ///
     /// Each position is resolved against the alignment's current column projection.
@@ -205,6 +316,66 @@ impl Alignment {
     }
 }
 
+// column counts
+impl Alignment {
+    /// Returns the byte counts of each column in `range`.
+    ///
+    /// Each position is resolved against the alignment's current column projection and
+    /// counted over the current row projection.
+    ///
+    /// # Errors
+    ///
+    /// [`AlignmentError::EmptyRange`] if `range` is empty.
+    ///
+    /// [`AlignmentError::ColumnOutOfBounds`] if `range.end` is greater than the
+    /// current column projection width.
+    pub fn column_counts_range(
+        &self,
+        range: Range<usize>,
+    ) -> Result<Vec<ColumnCounts>, AlignmentError> {
+        Ok(
+            counted_columns_range(&self.data, &self.rows, &self.columns, range)?
+                .iter()
+                .map(|column| ColumnCounts::from_dense(&column.counts))
+                .collect(),
+        )
+    }
+
+    /// Returns the summary of a column at relative `position` with the given counts.
+    ///
+    /// Conservation uses the alphabet of the alignment's active kind, as in
+    /// [`Alignment::column_summaries_range`].
+    pub fn summary_from_counts(
+        &self,
+        position: usize,
+        counts: &ColumnCounts,
+        method: ConsensusMethod,
+    ) -> ColumnSummary {
+        summary_from_column_counts(
+            position,
+            counts,
+            method,
+            self.active_type().conservation_alphabet_size(),
+        )
+    }
+}
+
+pub(crate) fn summary_from_column_counts(
+    position: usize,
+    counts: &ColumnCounts,
+    method: ConsensusMethod,
+    alphabet_size: Option<NonZeroU8>,
+) -> ColumnSummary {
+    let max_entropy = alphabet_size.map(|value| f64::from(value.get()).log2());
+    summary_from_dense(
+        position,
+        &counts.to_dense(),
+        method,
+        max_entropy,
+        &mut rand::rng(),
+    )
+}
+
 pub(crate) fn counted_columns_positions(
     data: &AlignmentData,
     rows: &Projection,
@@ -267,6 +438,7 @@ pub(crate) fn counted_translated_columns_positions(
     positions: &[usize],
     frame: ReadingFrame,
     table: &TranslationTable,
+    gap_mode: TranslationGapMode,
 ) -> Result<Vec<CountedColumn>, AlignmentError> {
     let translated_len = frame.translated_length(data.length);
 
@@ -283,7 +455,14 @@ pub(crate) fn counted_translated_columns_positions(
 
             Ok(CountedColumn {
                 position: protein_col,
-                counts: translated_column_byte_counts(data, rows, protein_col, frame, table),
+                counts: translated_column_byte_counts(
+                    data,
+                    rows,
+                    protein_col,
+                    frame,
+                    table,
+                    gap_mode,
+                ),
             })
         })
         .collect()
@@ -295,6 +474,7 @@ pub(crate) fn counted_translated_columns_range(
     range: Range<usize>,
     frame: ReadingFrame,
     table: &TranslationTable,
+    gap_mode: TranslationGapMode,
 ) -> Result<Vec<CountedColumn>, AlignmentError> {
     if range.is_empty() {
         return Err(AlignmentError::EmptyRange);
@@ -308,12 +488,27 @@ pub(crate) fn counted_translated_columns_range(
         });
     }
 
-    Ok(range
+    let mut columns: Vec<CountedColumn> = range
+        .clone()
         .map(|protein_col| CountedColumn {
             position: protein_col,
-            counts: translated_column_byte_counts(data, rows, protein_col, frame, table),
+            counts: [0u32; 256],
         })
-        .collect())
+        .collect();
+
+    // rows are translated a range at a time, since skipping gaps needs the bases before it
+    for abs_row in rows.iter() {
+        let sequence = data
+            .sequences
+            .get(abs_row)
+            .expect("selected row must exist");
+        let bytes = translated_range(sequence.sequence(), range.clone(), frame, table, gap_mode);
+        for (column, byte) in columns.iter_mut().zip(bytes) {
+            column.counts[usize::from(byte)] += 1;
+        }
+    }
+
+    Ok(columns)
 }
 
 pub(crate) fn consensus_from_columns(
@@ -373,16 +568,42 @@ pub(crate) fn summaries_from_columns(
 
     columns
         .iter()
-        .map(|column| ColumnSummary {
-            position: column.position,
-            consensus: consensus_from_counts(&column.counts, method, rng),
-            conservation: max_entropy
-                .map(|max_entropy| conservation_from_counts(&column.counts, max_entropy)),
-            gap_fraction: gap_fraction_from_counts(&column.counts),
-        })
+        .map(|column| summary_from_dense(column.position, &column.counts, method, max_entropy, rng))
         .collect()
 }
 
+fn summary_from_dense(
+    position: usize,
+    counts: &[u32; 256],
+    method: ConsensusMethod,
+    max_entropy: Option<f64>,
+    rng: &mut impl rand::Rng,
+) -> ColumnSummary {
+    let consensus = consensus_from_counts(counts, method, rng);
+    ColumnSummary {
+        position,
+        consensus,
+        consensus_support: consensus.map_or(0.0, |byte| support_from_counts(counts, byte)),
+        conservation: max_entropy.map(|max_entropy| conservation_from_counts(counts, max_entropy)),
+        gap_fraction: gap_fraction_from_counts(counts),
+        gap_only: gap_only_from_counts(counts),
+    }
+}
+
+fn support_from_counts(counts: &[u32; 256], byte: u8) -> f32 {
+    let total: u32 = counts.iter().sum();
+    if total == 0 {
+        0.0
+    } else {
+        counts[usize::from(byte)] as f32 / total as f32
+    }
+}
+
+fn gap_only_from_counts(counts: &[u32; 256]) -> bool {
+    let total: u32 = counts.iter().sum();
+    total != 0 && counts[usize::from(b'-')] == total
+}
+
 pub(crate) fn gap_fraction_from_counts(counts: &[u32; 256]) -> f32 {
     let (gap_count, total) = counts
         .iter()
@@ -495,12 +716,24 @@ fn column_byte_counts(data: &AlignmentData, rows: &Projection, abs_col: usize) -
     counts
 }
 
+fn column_is_gap_only(data: &AlignmentData, rows: &Projection, abs_col: usize) -> bool {
+    !rows.is_empty()
+        && rows.iter().all(|abs_row| {
+            let sequence = data
+                .sequences
+                .get(abs_row)
+                .expect("selected row must exist");
+            is_gap_byte(sequence.sequence()[abs_col])
+        })
+}
+
 fn translated_column_byte_counts(
     data: &AlignmentData,
     rows: &Projection,
     protein_col: usize,
     frame: ReadingFrame,
     table: &TranslationTable,
+    gap_mode: TranslationGapMode,
 ) -> [u32; 256] {
     let mut counts = [0u32; 256];
 
@@ -509,7 +742,7 @@ fn translated_column_byte_counts(
             .sequences
             .get(abs_row)
             .expect("selected row must exist");
-        let byte = translated_byte_at(sequence.sequence(), protein_col, frame, table)
+        let byte = translated_byte_at(sequence.sequence(), protein_col, frame, table, gap_mode)
             .expect("validated translated range");
         counts[usize::from(byte)] += 1;
     }
@@ -640,6 +873,22 @@ mod derived_column_tests {
         assert_eq!(summaries[0].consensus, None);
         assert_eq!(summaries[0].conservation, Some(0.0));
         assert_eq!(summaries[0].gap_fraction, 1.0);
+        assert!(summaries[0].gap_only);
+    }
+
+    #[test]
+    fn summaries_from_columns_report_consensus_support_over_all_rows() {
+        let columns = vec![counted_column(0, b"AAC-")];
+        let mut rng = StdRng::seed_from_u64(10);
+        let summaries = summaries_from_columns(
+            &columns,
+            ConsensusMethod::MajorityNonGap,
+            Some(NonZeroU8::new(4).unwrap()),
+            &mut rng,
+        );
+
+        assert_eq!(summaries[0].consensus, Some(b'A'));
+        assert_eq!(summaries[0].consensus_support, 0.5);
     }
 
     #[test]
@@ -658,6 +907,7 @@ mod derived_column_tests {
         assert_eq!(summaries[0].consensus, Some(b'A'));
         assert_eq!(summaries[0].conservation, Some(1.0));
         assert_eq!(summaries[0].gap_fraction, 0.0);
+        assert!(!summaries[0].gap_only);
         assert_eq!(summaries[1].position, 1);
         assert_eq!(summaries[1].consensus, None);
         assert_eq!(summaries[1].conservation, Some(0.0));
@@ -726,6 +976,7 @@ mod tests {
         RawSequence {
             id: id.to_string(),
             sequence: sequence.to_vec(),
+            quality: None,
         }
     }
 
@@ -779,6 +1030,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn translated_counts_rederive_summaries_for_another_method() {
+        let alignment = Alignment::new_with_type(
+            vec![raw("s1", b"GCT"), raw("s2", b"---"), raw("s3", b"---")],
+            AlignmentType::Dna,
+        )
+        .unwrap();
+        let translated = alignment.translated(crate::ReadingFrame::Frame1).unwrap();
+        let counts = translated.column_counts_range(0..1).unwrap();
+
+        for method in [ConsensusMethod::Majority, ConsensusMethod::MajorityNonGap] {
+            assert_eq!(
+                translated.summary_from_counts(0, &counts[0], method),
+                translated
+                    .column_summaries_range(0..1, method)
+                    .unwrap()
+                    .remove(0)
+            );
+        }
+    }
+
     #[test]
     fn translated_column_summaries_positions_return_protein_metrics() {
         let alignment = Alignment::new_with_type(
@@ -820,6 +1092,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn column_counts_track_rows_and_match_scanned_summaries() {
+        let alignment = Alignment::new_with_type(
+            vec![raw("s1", b"AC"), raw("s2", b"AT"), raw("s3", b"GT")],
+            AlignmentType::Dna,
+        )
+        .unwrap();
+        let mut counts = alignment.column_counts_range(0..2).unwrap();
+
+        assert_eq!(counts[0].count(b'A'), 2);
+        assert_eq!(counts[1].total(), 3);
+
+        counts[0].remove(b'G');
+        counts[0].remove(b'X');
+        let without_s3 = alignment
+            .filter()
+            .unwrap()
+            .without_rows([2])
+            .apply()
+            .unwrap();
+
+        assert_eq!(counts[0].total(), 2);
+        assert_eq!(
+            alignment.summary_from_counts(0, &counts[0], ConsensusMethod::MajorityNonGap),
+            without_s3
+                .column_summaries_range(0..1, ConsensusMethod::MajorityNonGap)
+                .unwrap()
+                .remove(0)
+        );
+    }
+
     #[test]
     fn conservation_positions_returns_score() {
         let alignment =
@@ -844,6 +1147,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn gap_only_range_respects_row_filters() {
+        let alignment = Alignment::new_with_type(
+            vec![raw("s1", b"A--"), raw("s2", b"-C-")],
+            AlignmentType::Dna,
+        )
+        .unwrap();
+        let filtered = alignment
+            .filter()
+            .unwrap()
+            .with_row_regex("s1")
+            .apply()
+            .unwrap();
+
+        assert_eq!(
+            alignment.gap_only_range(0..3).unwrap(),
+            vec![false, false, true]
+        );
+        assert_eq!(
+            filtered.gap_only_range(0..3).unwrap(),
+            vec![false, true, true]
+        );
+    }
+
     #[test]
     fn conservation_positions_is_undefined_for_generic() {
         let alignment = Alignment::new_with_type(
@@ -36,6 +36,7 @@ pub struct SequenceView<'a> {
     absolute_row_id: usize,
     id: &'a str,
     data: &'a [u8],
+    quality: Option<&'a [u8]>,
     columns: &'a Projection,
 }
 
@@ -117,6 +118,14 @@ impl Alignment {
         self.columns.len()
     }
 
+    /// Returns `true` if any sequence in the underlying data carries quality scores.
+    pub fn has_quality(&self) -> bool {
+        self.data
+            .sequences
+            .iter()
+            .any(|sequence| sequence.quality().is_some())
+    }
+
     /// Returns the length in characters of the longest visible sequence identifier, or `0` if no sequences are visible.
     pub fn max_id_len(&self) -> usize {
         self.rows
@@ -148,6 +157,7 @@ impl Alignment {
             absolute_row_id: abs_row,
             id: seq.id(),
             data: seq.sequence(),
+            quality: seq.quality(),
             columns: &self.columns,
         })
     }
@@ -166,6 +176,7 @@ impl Alignment {
             absolute_row_id: absolute_row,
             id: seq.id(),
             data: seq.sequence(),
+            quality: seq.quality(),
             columns: &self.columns,
         })
     }
@@ -184,6 +195,7 @@ impl Alignment {
             absolute_row_id: abs_row,
             id: seq.id(),
             data: seq.sequence(),
+            quality: seq.quality(),
             columns: &self.columns,
         })
     }
@@ -211,6 +223,12 @@ impl Alignment {
         self.columns.iter()
     }
 
+    /// Returns `true` if `other` views the same underlying data through the same columns,
+    /// whatever rows either of them shows.
+    pub fn has_same_columns(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.data, &other.data) && self.columns.same_as(&other.columns)
+    }
+
     /// Returns the relative row index for `absolute`, or `None` if that row is not visible.
     pub fn relative_row_id(&self, absolute: usize) -> Option<usize> {
         self.rows.relative(absolute)
@@ -339,6 +357,13 @@ impl<'a> SequenceView<'a> {
         Some(self.data[abs_col])
     }
 
+    /// Returns the Phred quality score at `relative_col`, or `None` if the sequence carries no
+    /// quality scores or the column is out of bounds.
+    pub fn quality_at(&self, relative_col: usize) -> Option<u8> {
+        let abs_col = self.columns.absolute(relative_col)?;
+        Some(self.quality?[abs_col])
+    }
+
     /// Returns an iterator over `(absolute_column, byte)` pairs for the given relative column range.
     ///
     /// The range is relative to this view's column projection. Each yielded pair carries the
@@ -412,6 +437,7 @@ mod alignment_construction_tests {
         RawSequence {
             id: id.to_string(),
             sequence: sequence.to_vec(),
+            quality: None,
         }
     }
 
@@ -423,6 +449,48 @@ mod alignment_construction_tests {
         assert_eq!(alignment.detected_type(), AlignmentType::Dna);
     }
 
+    #[test]
+    fn quality_scores_follow_the_column_projection() {
+        let alignment = Alignment::new(vec![
+            RawSequence {
+                id: "read-1".to_string(),
+                sequence: b"A-GT".to_vec(),
+                quality: Some(vec![30, 0, 12, 40]),
+            },
+            raw("read-2", b"AAGT"),
+        ])
+        .unwrap();
+        let filtered = alignment
+            .filter()
+            .unwrap()
+            .with_max_gap_fraction(0.0)
+            .apply()
+            .unwrap();
+
+        assert!(filtered.has_quality());
+        let read = filtered.sequence(0).unwrap();
+        assert_eq!(read.quality_at(1), Some(12));
+        assert_eq!(filtered.sequence(1).unwrap().quality_at(1), None);
+    }
+
+    #[test]
+    fn rejects_quality_length_mismatch() {
+        let result = Alignment::new(vec![RawSequence {
+            id: "read-1".to_string(),
+            sequence: b"ACGT".to_vec(),
+            quality: Some(vec![30, 30]),
+        }]);
+
+        assert_eq!(
+            result.unwrap_err(),
+            AlignmentError::QualityLengthMismatch {
+                expected: 4,
+                actual: 2,
+                id: "read-1".to_string(),
+            }
+        );
+    }
+
     #[test]
     fn new_with_kind_skips_detection() {
         let alignment = Alignment::new_with_type(
@@ -491,6 +559,7 @@ mod alignment_access_tests {
         RawSequence {
             id: id.to_string(),
             sequence: sequence.to_vec(),
+            quality: None,
         }
     }
 
@@ -620,6 +689,7 @@ mod alignment_projection_tests {
         RawSequence {
             id: id.to_string(),
             sequence: sequence.to_vec(),
+            quality: None,
         }
     }
 
@@ -44,6 +44,15 @@ impl Projection {
         }
     }
 
+    /// whether both projections select the same ids
+    pub(crate) fn same_as(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Full { len }, Self::Full { len: other_len }) => len == other_len,
+            (Self::Filtered(ids), Self::Filtered(other_ids)) if Arc::ptr_eq(ids, other_ids) => true,
+            _ => self.len() == other.len() && self.iter().eq(other.iter()),
+        }
+    }
+
     pub(crate) fn is_full(&self) -> bool {
         matches!(self, Self::Full { .. })
     }
@@ -5,8 +5,8 @@ use crate::alignment_type::AlignmentType;
 use crate::data::{AlignmentData, RawSequence};
 use crate::error::AlignmentError;
 use crate::metrics::{
-    ColumnSummary, ConsensusMethod, counted_translated_columns_positions,
-    counted_translated_columns_range, summaries_from_columns,
+    ColumnCounts, ColumnSummary, ConsensusMethod, counted_translated_columns_positions,
+    counted_translated_columns_range, summaries_from_columns, summary_from_column_counts,
 };
 
 /// Reading frames for translating.
@@ -79,6 +79,52 @@ impl std::str::FromStr for ReadingFrame {
     }
 }
 
+/// How gaps are handled when translating codons.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TranslationGapMode {
+    /// Every three alignment columns form one codon, and codons containing a gap translate
+    /// to `X`.
+    #[default]
+    Columnwise,
+    /// Gaps are removed before translating, and each amino acid is placed in the codon
+    /// column holding its first base. Codon columns where no codon starts show `-`.
+    SkipGaps,
+    /// Codons are read column-wise, but a codon broken by a gap shows `!` and a fully
+    /// gapped codon shows `-`.
+    GapAsFrameshift,
+}
+
+impl TranslationGapMode {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Columnwise => "columnwise",
+            Self::SkipGaps => "skip-gaps",
+            Self::GapAsFrameshift => "gap-as-frameshift",
+        }
+    }
+
+    pub const fn all() -> [Self; 3] {
+        [Self::Columnwise, Self::SkipGaps, Self::GapAsFrameshift]
+    }
+}
+
+impl std::fmt::Display for TranslationGapMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for TranslationGapMode {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|mode| mode.name() == value)
+            .ok_or(())
+    }
+}
+
 /// Translation table for mapping DNA codons to amino-acid bytes.
 ///
 /// The layout is `[first][second][third]`, with nucleotides indexed in `A, T, C, G` order.
@@ -127,7 +173,8 @@ impl TranslationTable {
         Self { codons }
     }
 
-    pub(crate) fn translate_codon(&self, codon: [u8; 3]) -> u8 {
+    /// Translates one uppercase codon, returning `X` if any base is not `A`, `T`, `C` or `G`.
+    pub fn translate_codon(&self, codon: [u8; 3]) -> u8 {
         let Some(first) = index_nucleotide(codon[0]) else {
             return b'X';
         };
@@ -151,6 +198,7 @@ pub struct TranslatedAlignment<'a> {
     source: &'a Alignment,
     frame: ReadingFrame,
     table: TranslationTable,
+    gap_mode: TranslationGapMode,
     translated_column_count: usize,
 }
 
@@ -160,6 +208,17 @@ impl<'a> TranslatedAlignment<'a> {
         self.translated_column_count
     }
 
+    /// Returns this view with gaps handled by `gap_mode`.
+    pub fn with_gap_mode(mut self, gap_mode: TranslationGapMode) -> Self {
+        self.gap_mode = gap_mode;
+        self
+    }
+
+    /// Returns how gaps are handled by this view.
+    pub fn gap_mode(&self) -> TranslationGapMode {
+        self.gap_mode
+    }
+
     /// Returns the translated view for one visible row by absolute row id.
     ///
     /// The row id is resolved against the source alignment's current row
@@ -172,6 +231,7 @@ impl<'a> TranslatedAlignment<'a> {
             data: sequence.sequence(),
             frame: self.frame,
             table: self.table,
+            gap_mode: self.gap_mode,
             translated_len: self.translated_column_count,
         })
     }
@@ -191,6 +251,7 @@ impl<'a> TranslatedAlignment<'a> {
             data: sequence.sequence(),
             frame: self.frame,
             table: self.table,
+            gap_mode: self.gap_mode,
             translated_len: self.translated_column_count,
         })
     }
@@ -219,7 +280,13 @@ impl<'a> TranslatedAlignment<'a> {
                 )?;
                 Ok(RawSequence {
                     id: sequence.id().to_string(),
-                    sequence: translate_sequence(sequence.sequence(), self.frame, &self.table),
+                    sequence: translate_sequence(
+                        sequence.sequence(),
+                        self.frame,
+                        &self.table,
+                        self.gap_mode,
+                    ),
+                    quality: None,
                 })
             })
             .collect::<Result<Vec<_>, _>>()?;
@@ -250,6 +317,7 @@ impl<'a> TranslatedAlignment<'a> {
             positions,
             self.frame,
             &self.table,
+            self.gap_mode,
         )?;
         let mut rng = rand::rng();
         Ok(summaries_from_columns(
@@ -284,6 +352,7 @@ impl<'a> TranslatedAlignment<'a> {
             range,
             self.frame,
             &self.table,
+            self.gap_mode,
         )?;
         let mut rng = rand::rng();
         Ok(summaries_from_columns(
@@ -294,6 +363,49 @@ impl<'a> TranslatedAlignment<'a> {
         ))
     }
 
+    /// Returns the amino acid counts of each protein column in `range`.
+    ///
+    /// Counts cover the visible rows in the source alignment, translated with
+    /// this view's reading frame and translation table.
+    ///
+    /// # Errors
+    ///
+    /// [`AlignmentError::EmptyRange`] if `range` is empty.
+    ///
+    /// [`AlignmentError::ColumnOutOfBounds`] if `range.end` is greater than
+    /// the translated width of this view.
+    pub fn column_counts_range(
+        &self,
+        range: Range<usize>,
+    ) -> Result<Vec<ColumnCounts>, AlignmentError> {
+        Ok(counted_translated_columns_range(
+            &self.source.data,
+            &self.source.rows,
+            range,
+            self.frame,
+            &self.table,
+            self.gap_mode,
+        )?
+        .iter()
+        .map(|column| ColumnCounts::from_dense(&column.counts))
+        .collect())
+    }
+
+    /// Returns the summary of the protein column at `position` with the given counts.
+    pub fn summary_from_counts(
+        &self,
+        position: usize,
+        counts: &ColumnCounts,
+        method: ConsensusMethod,
+    ) -> ColumnSummary {
+        summary_from_column_counts(
+            position,
+            counts,
+            method,
+            AlignmentType::Protein.conservation_alphabet_size(),
+        )
+    }
+
     /// Returns the translated consensus byte for each protein column in `range`.
     ///
     /// The returned positions use protein-column coordinates from the translated
@@ -355,6 +467,7 @@ impl<'a> TranslatedAlignment<'a> {
             source,
             frame,
             table,
+            gap_mode: TranslationGapMode::default(),
             translated_column_count,
         })
     }
@@ -366,6 +479,7 @@ pub struct TranslatedSequenceView<'a> {
     data: &'a [u8],
     frame: ReadingFrame,
     table: TranslationTable,
+    gap_mode: TranslationGapMode,
     translated_len: usize,
 }
 
@@ -375,7 +489,13 @@ impl<'a> TranslatedSequenceView<'a> {
     /// The column index is a protein-column coordinate in this translated sequence.
     /// Returns `None` when `protein_col` is outside the translated length.
     pub fn byte_at(&self, protein_col: usize) -> Option<u8> {
-        translated_byte_at(self.data, protein_col, self.frame, &self.table)
+        translated_byte_at(
+            self.data,
+            protein_col,
+            self.frame,
+            &self.table,
+            self.gap_mode,
+        )
     }
 
     /// Returns translated bytes for a range of protein columns.
@@ -405,18 +525,21 @@ impl<'a> TranslatedSequenceView<'a> {
             });
         }
 
-        let bytes = range
-            .map(|protein_col| {
-                let byte = translated_byte_at(self.data, protein_col, self.frame, &self.table)
-                    .ok_or(AlignmentError::ColumnOutOfBounds {
-                        index: protein_col,
-                        length: self.translated_len,
-                    })?;
-                Ok((protein_col, byte))
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+        let bytes = translated_range(
+            self.data,
+            range.clone(),
+            self.frame,
+            &self.table,
+            self.gap_mode,
+        );
+        if bytes.len() != range.len() {
+            return Err(AlignmentError::ColumnOutOfBounds {
+                index: range.start + bytes.len(),
+                length: self.translated_len,
+            });
+        }
 
-        Ok(bytes.into_iter())
+        Ok(range.zip(bytes))
     }
 }
 
@@ -434,33 +557,15 @@ pub(crate) fn translate_sequence(
     sequence: &[u8],
     frame: ReadingFrame,
     table: &TranslationTable,
+    gap_mode: TranslationGapMode,
 ) -> Vec<u8> {
-    let mut translated = Vec::with_capacity(translated_length(sequence.len(), frame));
-
-    for codon_start in (frame.offset()..sequence.len()).step_by(3) {
-        let codon = [
-            sequence
-                .get(codon_start)
-                .and_then(|&byte| normalise_nucleotide(byte)),
-            sequence
-                .get(codon_start + 1)
-                .and_then(|&byte| normalise_nucleotide(byte)),
-            sequence
-                .get(codon_start + 2)
-                .and_then(|&byte| normalise_nucleotide(byte)),
-        ];
-
-        let amino_acid = match codon {
-            [Some(first), Some(second), Some(third)] => {
-                table.translate_codon([first, second, third])
-            }
-            _ => b'X',
-        };
-
-        translated.push(amino_acid);
-    }
-
-    translated
+    translated_range(
+        sequence,
+        0..translated_length(sequence.len(), frame),
+        frame,
+        table,
+        gap_mode,
+    )
 }
 
 pub(crate) fn translated_byte_at(
@@ -468,24 +573,118 @@ pub(crate) fn translated_byte_at(
     protein_col: usize,
     frame: ReadingFrame,
     table: &TranslationTable,
+    gap_mode: TranslationGapMode,
 ) -> Option<u8> {
     let codon_start = frame.offset().checked_add(protein_col.checked_mul(3)?)?;
-    let first_raw = *sequence.get(codon_start)?;
+    if codon_start >= sequence.len() {
+        return None;
+    }
+
     let codon = [
-        normalise_nucleotide(first_raw),
-        sequence
-            .get(codon_start + 1)
-            .and_then(|&byte| normalise_nucleotide(byte)),
-        sequence
-            .get(codon_start + 2)
-            .and_then(|&byte| normalise_nucleotide(byte)),
+        sequence.get(codon_start).copied(),
+        sequence.get(codon_start + 1).copied(),
+        sequence.get(codon_start + 2).copied(),
     ];
+    match gap_mode {
+        TranslationGapMode::Columnwise => Some(translate_raw_codon(codon, table)),
+        TranslationGapMode::GapAsFrameshift => {
+            let present = codon.iter().flatten().count();
+            let gaps = codon.iter().flatten().filter(|&&byte| byte == b'-').count();
+            Some(match gaps {
+                0 => translate_raw_codon(codon, table),
+                gaps if gaps == present => b'-',
+                _ => b'!',
+            })
+        }
+        TranslationGapMode::SkipGaps => {
+            skip_gaps_range(sequence, protein_col..protein_col + 1, frame, table)
+                .first()
+                .copied()
+        }
+    }
+}
+
+/// Translates the protein columns in `range`, stopping early at the end of `sequence`.
+pub(crate) fn translated_range(
+    sequence: &[u8],
+    range: Range<usize>,
+    frame: ReadingFrame,
+    table: &TranslationTable,
+    gap_mode: TranslationGapMode,
+) -> Vec<u8> {
+    match gap_mode {
+        TranslationGapMode::SkipGaps => skip_gaps_range(sequence, range, frame, table),
+        _ => range
+            .map_while(|protein_col| {
+                translated_byte_at(sequence, protein_col, frame, table, gap_mode)
+            })
+            .collect(),
+    }
+}
+
+/// Reads codons from the ungapped bases of `sequence`, keeping the reading frame of the
+/// first base, and places each amino acid in the protein column of its first base.
+///
+/// Bases before the first full codon and an unfinished final codon translate to `X`.
+fn skip_gaps_range(
+    sequence: &[u8],
+    range: Range<usize>,
+    frame: ReadingFrame,
+    table: &TranslationTable,
+) -> Vec<u8> {
+    let protein_len = translated_length(sequence.len(), frame).min(range.end);
+    let mut translated = vec![b'-'; protein_len.saturating_sub(range.start)];
+    let scan_end = frame.offset() + range.end * 3;
+    let mut place = |column: usize, byte: u8| {
+        if let Some(protein_col) = frame.protein_col(column)
+            && protein_col >= range.start
+            && protein_col < protein_len
+        {
+            translated[protein_col - range.start] = byte;
+        }
+    };
+
+    let mut phase = None;
+    let mut codon: Option<(usize, [Option<u8>; 3], usize)> = None;
+    for (column, &byte) in sequence.iter().enumerate() {
+        if byte == b'-' {
+            continue;
+        }
+
+        let base_phase = phase.unwrap_or((column + 3 - frame.offset()) % 3);
+        if base_phase == 0
+            && let Some((start, _, _)) = codon.take()
+        {
+            place(start, b'X');
+        }
+        if codon.is_none() {
+            if column >= scan_end {
+                break;
+            }
+            codon = Some((column, [None; 3], 0));
+        }
 
-    match codon {
-        [Some(first), Some(second), Some(third)] => {
-            Some(table.translate_codon([first, second, third]))
+        let (start, bases, count) = codon.as_mut().expect("codon was just opened");
+        bases[*count] = Some(byte);
+        *count += 1;
+        if *count == 3 {
+            place(*start, translate_raw_codon(*bases, table));
+            codon = None;
         }
-        _ => Some(b'X'),
+        phase = Some((base_phase + 1) % 3);
+    }
+
+    if let Some((start, _, _)) = codon {
+        place(start, b'X');
+    }
+
+    translated
+}
+
+fn translate_raw_codon(codon: [Option<u8>; 3], table: &TranslationTable) -> u8 {
+    match codon.map(|byte| byte.and_then(normalise_nucleotide)) {
+        [Some(first), Some(second), Some(third)] => table.translate_codon([first, second, third]),
+        _ => b'X',
     }
 }
 
@@ -496,7 +695,7 @@ pub(crate) fn translated_length(sequence_len: usize, frame: ReadingFrame) -> usi
 fn index_nucleotide(base: u8) -> Option<usize> {
     match base {
         b'A' => Some(0),
-        b'T' => Some(1),
+        b'T' | b'U' => Some(1),
         b'C' => Some(2),
         b'G' => Some(3),
         _ => None,
@@ -506,10 +705,12 @@ fn index_nucleotide(base: u8) -> Option<usize> {
 #[cfg(test)]
 mod translation_table_tests {
     use super::{
-        ReadingFrame, TranslationTable, normalise_nucleotide, translate_sequence,
-        translated_byte_at,
+        ReadingFrame, TranslationGapMode, TranslationTable, normalise_nucleotide,
+        translate_sequence, translated_byte_at,
     };
 
+    const COLUMNWISE: TranslationGapMode = TranslationGapMode::Columnwise;
+
     #[test]
     fn normalises_u_to_t() {
         assert_eq!(normalise_nucleotide(b'U'), Some(b'T'));
@@ -594,6 +795,12 @@ mod translation_table_tests {
         }
     }
 
+    #[test]
+    fn rna_codons_translate_like_dna() {
+        assert_eq!(TranslationTable::STANDARD.translate_codon(*b"AUG"), b'M');
+        assert_eq!(TranslationTable::STANDARD.translate_codon(*b"UAA"), b'*');
+    }
+
     #[test]
     fn invalid_codon_translates_to_x() {
         assert_eq!(TranslationTable::STANDARD.translate_codon(*b"ATN"), b'X');
@@ -602,24 +809,34 @@ mod translation_table_tests {
 
     #[test]
     fn translated_sequence_includes_incomplete_terminal_codon() {
-        let translated =
-            translate_sequence(b"ATGA", ReadingFrame::Frame1, &TranslationTable::STANDARD);
+        let translated = translate_sequence(
+            b"ATGA",
+            ReadingFrame::Frame1,
+            &TranslationTable::STANDARD,
+            COLUMNWISE,
+        );
         assert_eq!(translated, b"MX");
     }
 
     #[test]
     fn translated_frame_works() {
-        let translate_frame1 =
-            translate_sequence(b"GTCATT", ReadingFrame::Frame1, &TranslationTable::STANDARD);
+        let translate_frame1 = translate_sequence(
+            b"GTCATT",
+            ReadingFrame::Frame1,
+            &TranslationTable::STANDARD,
+            COLUMNWISE,
+        );
         let translate_frame2 = translate_sequence(
             b"GGAATTG",
             ReadingFrame::Frame2,
             &TranslationTable::STANDARD,
+            COLUMNWISE,
         );
         let translate_frame3 = translate_sequence(
             b"GGGATTTA",
             ReadingFrame::Frame3,
             &TranslationTable::STANDARD,
+            COLUMNWISE,
         );
         assert_eq!(translate_frame1, b"VI");
         assert_eq!(translate_frame2, b"EL");
@@ -629,11 +846,57 @@ mod translation_table_tests {
     #[test]
     fn translated_byte_at_returns_x_for_all_gap() {
         assert_eq!(
-            translated_byte_at(b"---", 0, ReadingFrame::Frame1, &TranslationTable::STANDARD),
+            translated_byte_at(
+                b"---",
+                0,
+                ReadingFrame::Frame1,
+                &TranslationTable::STANDARD,
+                COLUMNWISE,
+            ),
             Some(b'X')
         );
     }
 
+    #[test]
+    fn skip_gaps_reads_codons_across_gaps() {
+        let translate = |sequence: &[u8], frame| {
+            translate_sequence(
+                sequence,
+                frame,
+                &TranslationTable::STANDARD,
+                TranslationGapMode::SkipGaps,
+            )
+        };
+
+        assert_eq!(translate(b"AT-GAAA--TGG", ReadingFrame::Frame1), b"MK-W");
+        assert_eq!(translate(b"---ATGAA", ReadingFrame::Frame1), b"-MX");
+        assert_eq!(translate(b"--CATGTTT", ReadingFrame::Frame1), b"XMF");
+        assert_eq!(translate(b"-CA-TGTTT", ReadingFrame::Frame2), b"HVX");
+        assert_eq!(
+            translated_byte_at(
+                b"AT-GAAA--TGG",
+                2,
+                ReadingFrame::Frame1,
+                &TranslationTable::STANDARD,
+                TranslationGapMode::SkipGaps,
+            ),
+            Some(b'-')
+        );
+    }
+
+    #[test]
+    fn gap_as_frameshift_marks_broken_codons() {
+        assert_eq!(
+            translate_sequence(
+                b"ATG---A-GAAA",
+                ReadingFrame::Frame1,
+                &TranslationTable::STANDARD,
+                TranslationGapMode::GapAsFrameshift,
+            ),
+            b"M-!K"
+        );
+    }
+
     #[test]
     fn custom_translation_table() {
         let mut codons = [
@@ -723,6 +986,7 @@ mod translated_alignment_tests {
         RawSequence {
             id: id.to_string(),
             sequence: sequence.to_vec(),
+            quality: None,
         }
     }
 
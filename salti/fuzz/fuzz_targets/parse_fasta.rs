@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tokio_util::sync::CancellationToken;
+
+// FASTA and FASTQ input, including truncated records, CRLF line endings and non-UTF-8 IDs,
+// must parse or fail with an error but never panic
+fuzz_target!(|data: &[u8]| {
+    let _ = salti::parse_alignment_bytes(data, &CancellationToken::new());
+});
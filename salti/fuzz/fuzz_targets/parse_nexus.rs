@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tokio_util::sync::CancellationToken;
+
+// prefixes the header so every input reaches the NEXUS parser
+fuzz_target!(|data: &[u8]| {
+    let input = [b"#NEXUS\n".as_slice(), data].concat();
+    let _ = salti::parse_alignment_bytes(&input, &CancellationToken::new());
+});
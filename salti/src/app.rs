@@ -1,7 +1,15 @@
-use std::{env, time::Duration};
+use std::{
+    collections::{BTreeMap, HashSet},
+    env, io,
+    ops::Range,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 
 use anyhow::{Result, format_err};
+use crossterm::clipboard::CopyToClipboard;
 use crossterm::event::{Event as TermEvent, EventStream, KeyEvent, MouseEvent};
+use crossterm::execute;
 use ratatui::DefaultTerminal;
 use ratatui::layout::Rect;
 use tokio::{
@@ -14,19 +22,85 @@ use tracing::{debug, error, info, warn};
 
 use crate::cli::StartupState;
 use crate::command::Command;
-use crate::core::model::{AlignmentModel, StatsView};
-use crate::core::parser;
-use crate::core::stats_cache::{ColumnStatsCache, StatsJobRequest, StatsJobResult};
+use crate::core::bgzf;
+use crate::core::cluster::{self, Cluster};
+use crate::core::composition;
+use crate::core::disk_cache::{self, CacheEntry, CacheHeader, DiskCache};
+use crate::core::dnds;
+use crate::core::export::{self, ConsensusExportOptions, ConservationFormat, JobProgress};
+use crate::core::follow::FileFollower;
+use crate::core::html_report::{self, ReportOptions, ReportRegion};
+use crate::core::inspect;
+use crate::core::jobs::WorkerLimit;
+use crate::core::liftover::PositionSource;
+use crate::core::locator::Locator;
+use crate::core::marks::{self, Mark};
+use crate::core::mask;
+use crate::core::memory::{self, MemoryLimit, MemoryProbe};
+use crate::core::model::{AlignmentModel, InsertColumnMode, StatsView};
+use crate::core::orf;
+use crate::core::parser::{self, DuplicateIds, ParsedAlignment};
+use crate::core::partition;
+use crate::core::primer::{self, Primer, PrimerHit};
+use crate::core::realign::{self, Aligner};
+use crate::core::restriction::{self, RestrictionSite};
+use crate::core::selection;
+use crate::core::serializer::{self, AlignmentFormat};
+use crate::core::similarity::{self, BLAST_LITE_TOP_HITS, Query, SimilarityHit};
+use crate::core::stats_cache::{
+    ColumnStatsCache, DifferenceJobResult, GapOnlyJobResult, StatsJobRequest, StatsJobResult,
+    scan_differences, scan_gap_only,
+};
+use crate::core::status::{StatusFifo, ViewStatus};
+use crate::core::sync::{SyncPosition, ViewportSync};
+use crate::core::tools::{self, ToolContext};
+use crate::core::variability;
 use crate::input;
-use crate::input::MouseTracker;
+use crate::input::{InputCoalescer, MouseTracker};
 use crate::overlay::command_palette::CommandPaletteState;
-use crate::ui::layout::{AppLayout, FrameLayout, pinned_section_layout};
+use crate::overlay::confirm::ConfirmState;
+use crate::overlay::context_menu::ContextMenuState;
+use crate::overlay::file_picker::FilePickerState;
+use crate::overlay::overlay_state::ActiveOverlay;
+use crate::overlay::pager::PagerState;
+use crate::overlay::perf_hud::PerfStats;
+use crate::overlay::report::{ReportEntry, ReportState, ReportTarget};
+use crate::terminal::{self, TerminalTitle};
+use crate::ui::frame::TrackedSequence;
+use crate::ui::layout::{
+    AppLayout, FrameLayout, IdPaneWidth, LayoutMode, LayoutOptions, MIN_SEQUENCE_ID_PANE_WIDTH,
+    pinned_section_layout, position_gutter_width,
+};
 use crate::ui::notification::{Notification, NotificationLevel};
 use crate::ui::render::render;
-use crate::ui::ui_state::{LoadingState, UiState};
+use crate::ui::selection::{selection_row_bounds, sequence_at_point};
+use crate::ui::snapshot::SnapshotOptions;
+use crate::ui::ui_state::{Crosshair, EditMode, LoadingState, MouseSelection, UiState};
+use crate::ui::ungapped_pane::UngappedSequence;
+use crate::ui::utils::{format_thousands, truncate_label};
 use crate::update::UpdateResult;
 
-const RENDER_FPS: f32 = 120.0;
+const RENDER_FPS: u16 = 120;
+/// lowest and highest frame rates accepted by `set-fps`
+pub(crate) const MIN_RENDER_FPS: u16 = 1;
+pub(crate) const MAX_RENDER_FPS: u16 = 240;
+/// shown once when a file loads and detection cannot tell DNA from protein
+const AMBIGUOUS_TYPE_NOTICE: &str =
+    "Could not tell whether this is DNA or protein; use set-sequence-type to choose";
+/// export label of `save-as`, whose success clears the unsaved-edits flag
+const SAVE_ALIGNMENT_LABEL: &str = "Saving alignment";
+/// input name shown for an alignment loaded with `paste-alignment`
+const PASTED_INPUT_NAME: &str = "pasted";
+/// how often the process memory usage shown in the status bar is refreshed
+const MEMORY_SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+/// how often `--follow` checks the input for new records
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// how often `--sync-file` is checked for positions written by other instances
+const SYNC_POLL_INTERVAL: Duration = Duration::from_millis(200);
+/// most sequences `pin-matching` pins at once, so a loose regex cannot swamp the pinned group
+const PIN_MATCHING_LIMIT: usize = 100;
+/// most alignments kept for `restore-alignment`, oldest dropped first
+const MAX_PREVIOUS_ALIGNMENTS: usize = 8;
 
 const INSTALLED_VERSION: &str = env!("CARGO_PKG_VERSION");
 const UPDATE_CHECK_ENV_VAR: &str = "SALTI_SKIP_UPDATE_CHECK";
@@ -41,6 +115,117 @@ enum AppEvent {
 struct AsyncJob<T> {
     handle: JoinHandle<T>,
     cancel: CancellationToken,
+    started: Instant,
+}
+
+/// a running export, with the progress shown in the status bar
+#[derive(Debug)]
+struct ExportJob {
+    label: &'static str,
+    progress: JobProgress,
+    job: AsyncJob<Result<String, String>>,
+}
+
+/// a parsed input with the stats cache entry of its contents
+#[derive(Debug)]
+struct LoadedInput {
+    parsed: ParsedAlignment,
+    cache_entry: Option<CacheEntry>,
+    /// picks up records written after the load stopped reading, when following the input
+    follower: Option<FileFollower>,
+}
+
+/// memory `model` is estimated to hold
+fn model_bytes(model: &AlignmentModel) -> u64 {
+    let base = model.base();
+    memory::alignment_bytes((base.row_count() * base.column_count()) as u64)
+}
+
+/// the question asked before running a destructive command
+fn destructive_prompt(command: &Command) -> String {
+    match command {
+        Command::HideSequence(_) => "Hide this sequence? show-hidden brings it back".to_string(),
+        Command::ExtractRegion { start, end } => format!(
+            "Trim the alignment to columns {}-{}? restore-alignment undoes it",
+            start + 1,
+            end + 1
+        ),
+        Command::LoadMask { input } => format!("Replace the mask with {input}?"),
+        Command::ClearMask => "Clear the mask?".to_string(),
+        Command::RealignSelection(_) => {
+            "Realign the selection? restore-alignment undoes it".to_string()
+        }
+        _ => "Continue?".to_string(),
+    }
+}
+
+/// how to undo a change that replaced the alignment, given whether the old one was kept
+fn restore_hint(kept: bool) -> &'static str {
+    if kept {
+        "use restore-alignment to go back"
+    } else {
+        "the previous alignment was not kept, as it would not fit in --max-mem"
+    }
+}
+
+/// summaries of every column, computed or read back from the disk cache
+#[derive(Debug)]
+struct PrecomputedStats {
+    summaries: Vec<libmsa::ColumnSummary>,
+    from_disk: bool,
+}
+
+/// a running `precompute-stats` job, filling the stats cache for the generation it started in
+#[derive(Debug)]
+struct PrecomputeJob {
+    generation: u64,
+    /// what the stats are cached on disk as, when the view can be cached
+    cached: Option<CacheHeader>,
+    progress: JobProgress,
+    job: AsyncJob<Result<PrecomputedStats, String>>,
+}
+
+/// output of a background analysis command
+#[derive(Debug)]
+struct AnalysisResult {
+    report: ReportState,
+    /// regions highlighted in the alignment pane alongside the report
+    highlights: Vec<MouseSelection>,
+    /// replaces the ruler's restriction site markers when set
+    restriction_sites: Option<Vec<RestrictionSite>>,
+    /// replaces the row clusters when set
+    clusters: Option<Vec<Cluster>>,
+    /// position the viewport jumps to when the report opens
+    jump: Option<ReportTarget>,
+    /// text opened in the pager instead of the report when set
+    pager: Option<PagerState>,
+}
+
+impl From<ReportState> for AnalysisResult {
+    fn from(report: ReportState) -> Self {
+        Self {
+            report,
+            highlights: Vec::new(),
+            restriction_sites: None,
+            clusters: None,
+            jump: None,
+            pager: None,
+        }
+    }
+}
+
+/// path of a parsed alignment file and its sequences
+type ParsedFile = (String, Vec<libmsa::RawSequence>);
+
+/// output of `realign-selection`, ready to be stitched back into the alignment
+#[derive(Debug)]
+struct RealignedBlock {
+    aligner: Aligner,
+    /// absolute rows that were realigned, in the order of `aligned`
+    rows: Vec<usize>,
+    /// absolute columns the block replaces
+    columns: Range<usize>,
+    aligned: Vec<Vec<u8>>,
 }
 
 #[derive(Debug)]
@@ -48,12 +233,47 @@ pub(crate) struct App {
     alignment: Option<AlignmentModel>,
     ui: UiState,
     mouse_tracker: MouseTracker,
+    input_coalescer: InputCoalescer,
+    render_fps: u16,
+    memory_probe: MemoryProbe,
     stats_cache: ColumnStatsCache,
     raw_stats_jobs: JoinSet<StatsJobResult>,
     translated_stats_jobs: JoinSet<StatsJobResult>,
-    load_job: Option<AsyncJob<Result<Vec<libmsa::RawSequence>, String>>>,
+    gap_only_jobs: JoinSet<GapOnlyJobResult>,
+    difference_jobs: JoinSet<DifferenceJobResult>,
+    load_job: Option<AsyncJob<Result<LoadedInput, String>>>,
+    export_job: Option<ExportJob>,
+    analysis_job: Option<AsyncJob<Result<AnalysisResult, String>>>,
+    precompute_job: Option<PrecomputeJob>,
+    disk_cache: DiskCache,
+    /// parses the file given to `append-alignment`, returning its path with the sequences
+    append_job: Option<AsyncJob<Result<ParsedFile, String>>>,
+    /// runs the external aligner for `realign-selection`
+    realign_job: Option<AsyncJob<Result<RealignedBlock, String>>>,
+    /// writes the `.fai` and `.gzi` indexes of a BGZF input opened without them
+    index_job: Option<AsyncJob<Result<String, String>>>,
+    /// alignments replaced by `extract-region`, `append-alignment` or `realign-selection`, most
+    /// recent last
+    previous_alignments: Vec<AlignmentModel>,
+    /// tails the input for records written after it loaded, when started with `--follow`
+    follower: Option<FileFollower>,
+    /// shares the viewport position with other instances, when started with `--sync-file`
+    viewport_sync: Option<ViewportSync>,
+    /// receives the view status, when started with `--status-fifo`
+    status_fifo: Option<StatusFifo>,
+    terminal_title: TerminalTitle,
     event_tx: Option<UnboundedSender<AppEvent>>,
     should_quit: bool,
+    /// shared cap on concurrently running blocking jobs
+    workers: WorkerLimit,
+    /// what loading does with repeated sequence IDs
+    duplicate_ids: DuplicateIds,
+    /// inputs estimated to need more memory than this are refused
+    max_memory: Option<MemoryLimit>,
+    /// columns read from the startup input with `--region`; inputs opened later load whole
+    region: Option<Range<usize>>,
+    /// external commands from the `[tools]` config table, keyed by name
+    tools: BTreeMap<String, String>,
     layout_area: Rect,
     frame_layout: FrameLayout,
     app_layout: AppLayout,
@@ -63,17 +283,53 @@ impl App {
     pub(crate) fn new(startup: StartupState) -> Self {
         let layout_area = Rect::default();
         let frame_layout = FrameLayout::new(layout_area);
-        let app_layout = AppLayout::new(frame_layout.content_area);
+        let app_layout = AppLayout::new(frame_layout.content_area, LayoutOptions::default());
+        let viewport_sync = startup.sync_file.clone().map(ViewportSync::new);
+        let status_fifo = startup.status_fifo.clone().map(StatusFifo::new);
+        let workers = startup
+            .threads
+            .map_or_else(WorkerLimit::default, WorkerLimit::new);
+        debug!(threads = workers.threads(), "Background worker limit");
+        let duplicate_ids = startup.duplicate_ids;
+        let max_memory = startup.max_memory;
+        let region = startup.region.clone();
+        let tools = startup.tools.clone();
+        let disk_cache = DiskCache {
+            dir: startup.cache_dir.clone(),
+            entry: None,
+        };
         Self {
             alignment: None,
             ui: UiState::new(startup),
             mouse_tracker: MouseTracker::default(),
+            input_coalescer: InputCoalescer::default(),
+            render_fps: RENDER_FPS,
+            memory_probe: MemoryProbe::default(),
             stats_cache: ColumnStatsCache::default(),
             raw_stats_jobs: JoinSet::new(),
             translated_stats_jobs: JoinSet::new(),
+            gap_only_jobs: JoinSet::new(),
+            difference_jobs: JoinSet::new(),
             load_job: None,
+            export_job: None,
+            analysis_job: None,
+            precompute_job: None,
+            disk_cache,
+            append_job: None,
+            realign_job: None,
+            index_job: None,
+            previous_alignments: Vec::new(),
+            follower: None,
+            viewport_sync,
+            status_fifo,
+            terminal_title: TerminalTitle::default(),
             event_tx: None,
             should_quit: false,
+            workers,
+            duplicate_ids,
+            max_memory,
+            region,
+            tools,
             layout_area,
             frame_layout,
             app_layout,
@@ -101,8 +357,11 @@ impl App {
 
         self.extend_stats_if_needed();
 
-        let period = Duration::from_secs_f32(1.0 / RENDER_FPS);
+        let mut period = self.frame_period();
         let mut interval = tokio::time::interval(period);
+        let mut memory_interval = tokio::time::interval(MEMORY_SAMPLE_INTERVAL);
+        let mut follow_interval = tokio::time::interval(FOLLOW_POLL_INTERVAL);
+        let mut sync_interval = tokio::time::interval(SYNC_POLL_INTERVAL);
         let mut events = EventStream::new();
         let (event_tx, mut event_rx) = unbounded_channel::<AppEvent>();
         self.event_tx = Some(event_tx);
@@ -120,8 +379,27 @@ impl App {
 
         while !self.should_quit {
             tokio::select! {
-                _ = interval.tick() => {
+                deadline = interval.tick() => {
+                    if let Some(command) = self.input_coalescer.flush() {
+                        self.execute_commands([command]);
+                        needs_redraw = true;
+                    }
+                    if self.refresh_export_progress() {
+                        needs_redraw = true;
+                    }
+                    if self.poll_directory_listing() {
+                        needs_redraw = true;
+                    }
+                    if self.ui.flash_until.is_some_and(|until| Instant::now() >= until) {
+                        self.ui.flash_until = None;
+                        needs_redraw = true;
+                    }
+                    if std::mem::take(&mut self.ui.bell_pending) {
+                        terminal::bell();
+                    }
                     if needs_redraw {
+                        self.refresh_perf_stats(deadline.elapsed());
+                        let frame_start = Instant::now();
                         if let Err(error) = terminal.draw(|frame| {
                             self.update_layout(frame.area());
                             render(
@@ -136,9 +414,32 @@ impl App {
                             error!(error = ?error, "terminal draw failed");
                             return Err(error.into());
                         }
+                        if let Some(perf) = self.ui.perf_hud.as_mut() {
+                            perf.frame_time = frame_start.elapsed();
+                        }
+                        self.update_terminal_title();
+                        self.publish_viewport();
+                        self.publish_status();
                         needs_redraw = false;
                     }
                 }
+                _ = follow_interval.tick(), if self.follower.is_some() => {
+                    if self.poll_follower() {
+                        needs_redraw = true;
+                    }
+                }
+                _ = sync_interval.tick(), if self.viewport_sync.is_some() => {
+                    if self.poll_viewport_sync() {
+                        needs_redraw = true;
+                    }
+                }
+                _ = memory_interval.tick() => {
+                    let memory_usage = self.memory_probe.resident_bytes();
+                    if memory_usage != self.ui.meta.memory_usage {
+                        self.ui.meta.memory_usage = memory_usage;
+                        needs_redraw = true;
+                    }
+                }
                 Some(Ok(event)) = events.next() => {
                     match event {
                         TermEvent::Resize(width, height) => {
@@ -151,6 +452,9 @@ impl App {
                         TermEvent::Mouse(mouse) => {
                             self.handle_mouse_event(mouse);
                         }
+                        TermEvent::Paste(text) => {
+                            self.handle_paste_event(&text);
+                        }
                         _ => (),
                     }
 
@@ -168,6 +472,14 @@ impl App {
                     self.handle_stats_result(join_result);
                     needs_redraw = true;
                 }
+                Some(join_result) = self.gap_only_jobs.join_next() => {
+                    self.handle_gap_only_result(join_result);
+                    needs_redraw = true;
+                }
+                Some(join_result) = self.difference_jobs.join_next() => {
+                    self.handle_difference_result(join_result);
+                    needs_redraw = true;
+                }
                 Some(join_result) = async {
                     match self.load_job.as_mut() {
                         Some(job) => Some((&mut job.handle).await),
@@ -176,19 +488,37 @@ impl App {
                 } => {
                     self.load_job = None;
                     match join_result {
-                        Ok(Ok(raw_sequences)) => match libmsa::Alignment::new(raw_sequences)
+                        Ok(Ok(LoadedInput { parsed, cache_entry, follower })) => match load_with_cached_type(parsed.sequences, cache_entry.as_ref())
                             .and_then(AlignmentModel::new) {
-                            Ok(model) => {
-                                self.raw_stats_jobs.abort_all();
-                                self.translated_stats_jobs.abort_all();
-                                self.stats_cache.init(model.view().column_count());
-                                self.alignment = Some(model);
-                                self.ui.meta.loading_state = LoadingState::Loaded;
-                                self.ui.clear_transient_state();
-                                self.mouse_tracker.clear_anchors();
-                                self.refresh_viewport_bounds();
-                                self.ui.viewport.jump_to_position(self.ui.meta.initial_position);
-                                self.try_spawn_stats_jobs();
+                            Ok(mut model) => {
+                                if !parsed.partitions.is_empty()
+                                    && let Err(error) = model.set_partitions(parsed.partitions)
+                                {
+                                    warn!(error = %error, "Ignoring partitions that do not fit the alignment");
+                                }
+                                model.renamed_rows = parsed.renamed_rows.iter().copied().collect();
+                                let id_notice = id_notice(parsed.lossy_ids, parsed.renamed_rows.len());
+                                self.previous_alignments.clear();
+                                self.ui.meta.unsaved_edits = false;
+                                let ambiguous = model.base().detected_type() == libmsa::AlignmentType::Generic
+                                    && cache_entry.as_ref().is_none_or(|entry| entry.type_override.is_none());
+                                self.install_alignment(model, self.ui.meta.initial_position, cache_entry);
+                                let notices: Vec<_> = ambiguous
+                                    .then(|| AMBIGUOUS_TYPE_NOTICE.to_string())
+                                    .into_iter()
+                                    .chain(id_notice)
+                                    .collect();
+                                if !notices.is_empty() {
+                                    self.ui.notify(Notification {
+                                        level: NotificationLevel::Warning,
+                                        message: notices.join(" "),
+                                    });
+                                }
+                                if let Some(locator) = self.ui.meta.locator.take() {
+                                    self.apply_locator(&locator);
+                                }
+                                self.start_following(follower);
+                                self.start_index_job();
                             }
                             Err(error) => {
                                 self.ui.meta.loading_state = LoadingState::Failed(error.to_string());
@@ -205,6 +535,181 @@ impl App {
                     }
                     needs_redraw = true;
                 }
+                Some(join_result) = async {
+                    match self.export_job.as_mut() {
+                        Some(export) => Some((&mut export.job.handle).await),
+                        None => None,
+                    }
+                } => {
+                    let finished = self.export_job.take();
+                    let started = finished.as_ref().map(|export| export.job.started);
+                    self.ui.meta.export_progress = None;
+                    match join_result {
+                        Ok(Ok(message)) => {
+                            if finished.is_some_and(|export| export.label == SAVE_ALIGNMENT_LABEL) {
+                                self.ui.meta.unsaved_edits = false;
+                            }
+                            if started.is_some_and(|started| started.elapsed() >= terminal::NOTIFY_AFTER) {
+                                terminal::notify("salti", &message);
+                            }
+                            self.show_info(message);
+                        }
+                        Ok(Err(error)) => {
+                            self.execute_commands([Command::ShowNotification(Notification {
+                                level: NotificationLevel::Error,
+                                message: error,
+                            })]);
+                        }
+                        Err(join_error) => {
+                            if !join_error.is_cancelled() {
+                                error!(error = ?join_error, "Export task panicked");
+                            }
+                        }
+                    }
+                    needs_redraw = true;
+                }
+                Some(join_result) = async {
+                    match self.precompute_job.as_mut() {
+                        Some(precompute) => Some((&mut precompute.job.handle).await),
+                        None => None,
+                    }
+                } => {
+                    let precompute = self.precompute_job.take();
+                    self.ui.meta.export_progress = None;
+                    if let Some(precompute) = precompute {
+                        self.handle_precompute_result(precompute.generation, precompute.cached, join_result);
+                    }
+                    needs_redraw = true;
+                }
+                Some(join_result) = async {
+                    match self.index_job.as_mut() {
+                        Some(job) => Some((&mut job.handle).await),
+                        None => None,
+                    }
+                } => {
+                    self.index_job = None;
+                    match join_result {
+                        Ok(Ok(message)) => self.show_info(message),
+                        Ok(Err(error)) => self.ui.notify(Notification {
+                            level: NotificationLevel::Warning,
+                            message: error,
+                        }),
+                        Err(join_error) => {
+                            if !join_error.is_cancelled() {
+                                error!(error = ?join_error, "Index task panicked");
+                            }
+                        }
+                    }
+                    needs_redraw = true;
+                }
+                Some(join_result) = async {
+                    match self.append_job.as_mut() {
+                        Some(job) => Some((&mut job.handle).await),
+                        None => None,
+                    }
+                } => {
+                    self.append_job = None;
+                    let appended = match join_result {
+                        Ok(Ok((input, sequences))) => self.append_alignment(&input, sequences),
+                        Ok(Err(error)) => Err(format_err!(error)),
+                        Err(join_error) => {
+                            if !join_error.is_cancelled() {
+                                error!(error = ?join_error, "Append task panicked");
+                            }
+                            Ok(())
+                        }
+                    };
+                    if let Err(error) = appended {
+                        self.execute_commands([Command::ShowNotification(Notification {
+                            level: NotificationLevel::Error,
+                            message: format!("{error:#}"),
+                        })]);
+                    }
+                    needs_redraw = true;
+                }
+                Some(join_result) = async {
+                    match self.realign_job.as_mut() {
+                        Some(job) => Some((&mut job.handle).await),
+                        None => None,
+                    }
+                } => {
+                    self.realign_job = None;
+                    let realigned = match join_result {
+                        Ok(Ok(block)) => self.replace_realigned_block(block),
+                        Ok(Err(error)) => Err(format_err!(error)),
+                        Err(join_error) => {
+                            if !join_error.is_cancelled() {
+                                error!(error = ?join_error, "Realign task panicked");
+                            }
+                            Ok(())
+                        }
+                    };
+                    if let Err(error) = realigned {
+                        self.execute_commands([Command::ShowNotification(Notification {
+                            level: NotificationLevel::Error,
+                            message: format!("{error:#}"),
+                        })]);
+                    }
+                    needs_redraw = true;
+                }
+                Some(join_result) = async {
+                    match self.analysis_job.as_mut() {
+                        Some(job) => Some((&mut job.handle).await),
+                        None => None,
+                    }
+                } => {
+                    let started = self.analysis_job.take().map(|job| job.started);
+                    match join_result {
+                        Ok(Ok(result)) => {
+                            if started.is_some_and(|started| started.elapsed() >= terminal::NOTIFY_AFTER) {
+                                let title = result.pager.as_ref().map_or(result.report.title(), PagerState::title);
+                                terminal::notify("salti", &format!("{title} is ready"));
+                            }
+                            self.ui.notification = None;
+                            self.ui.highlights.extend(result.highlights);
+                            if let (Some(sites), Some(alignment)) =
+                                (result.restriction_sites, self.alignment.as_mut())
+                            {
+                                alignment.restriction_sites = sites;
+                            }
+                            if let Some(clusters) = result.clusters
+                                && let Err(error) = self.install_clusters(clusters)
+                            {
+                                self.show_error(format!("{error:#}"));
+                            }
+                            if let Some(target) = result.jump {
+                                self.execute_commands(
+                                    target
+                                        .abs_row
+                                        .map(Command::JumpToSequence)
+                                        .into_iter()
+                                        .chain([Command::JumpToPosition(target.column)]),
+                                );
+                            }
+                            match result.pager {
+                                Some(pager) => self.ui.overlay.open_pager(pager),
+                                None => self.ui.overlay.open_report(result.report),
+                            }
+                        }
+                        Ok(Err(error)) => {
+                            self.execute_commands([Command::ShowNotification(Notification {
+                                level: NotificationLevel::Error,
+                                message: error,
+                            })]);
+                        }
+                        Err(join_error) => {
+                            if !join_error.is_cancelled() {
+                                error!(error = ?join_error, "Analysis task panicked");
+                            }
+                        }
+                    }
+                    needs_redraw = true;
+                }
+            }
+
+            if self.frame_period() != period {
+                period = self.frame_period();
+                interval = tokio::time::interval(period);
             }
         }
 
@@ -213,23 +718,155 @@ impl App {
             job.cancel.cancel();
             job.handle.abort();
         }
+        self.cancel_export_job();
+        self.cancel_analysis_job();
+        self.cancel_append_job();
+        self.cancel_realign_job();
+        self.cancel_index_job();
         self.raw_stats_jobs.abort_all();
         self.translated_stats_jobs.abort_all();
+        self.gap_only_jobs.abort_all();
+        self.difference_jobs.abort_all();
         Ok(())
     }
 
+    /// names the file and the visible columns in the terminal title
+    fn update_terminal_title(&mut self) {
+        let total_columns = self
+            .alignment
+            .as_ref()
+            .map_or(0, |alignment| alignment.view().column_count());
+        let title = terminal::window_title(
+            self.ui.meta.input_path.as_deref(),
+            self.ui.viewport.window().col_range,
+            total_columns,
+        );
+        self.terminal_title.update(title);
+    }
+
+    fn viewport_position(&self) -> Option<SyncPosition> {
+        let alignment = self.alignment.as_ref()?;
+        let window = self.ui.viewport.window();
+        let view = alignment.view();
+        Some(SyncPosition {
+            column: view.absolute_column_id(window.col_range.start)?,
+            row: view.absolute_row_id(window.row_range.start).unwrap_or(0),
+        })
+    }
+
+    fn publish_viewport(&mut self) {
+        let Some(position) = self.viewport_position() else {
+            return;
+        };
+        let Some(sync) = self.viewport_sync.as_mut() else {
+            return;
+        };
+        if let Err(error) = sync.publish(position) {
+            warn!(error = ?error, "Failed to write viewport sync file");
+        }
+    }
+
+    fn view_status(&self) -> ViewStatus {
+        let window = self.ui.viewport.window();
+        let file = self.ui.meta.input_path.clone();
+        let Some(alignment) = self.alignment.as_ref() else {
+            return ViewStatus {
+                file,
+                position: None,
+                columns: None,
+                total_columns: 0,
+                rows: 0..0,
+                total_rows: 0,
+                selection: None,
+            };
+        };
+        let view = alignment.view();
+        let position = self
+            .ui
+            .crosshair
+            .map_or(window.col_range.start, |crosshair| crosshair.column);
+        let columns = window.col_range.end.checked_sub(1).and_then(|end| {
+            Some(view.absolute_column_id(window.col_range.start)?..=view.absolute_column_id(end)?)
+        });
+        let selection = self.ui.selection.map(|selection| {
+            let (row_min, row_max) = selection_row_bounds(selection);
+            let col_min = selection.column.min(selection.end_column);
+            let col_max = selection.column.max(selection.end_column);
+            (row_min..=row_max, col_min..=col_max)
+        });
+        ViewStatus {
+            file,
+            position: view.absolute_column_id(position),
+            columns,
+            total_columns: alignment.base().column_count(),
+            rows: window.row_range,
+            total_rows: view.row_count(),
+            selection,
+        }
+    }
+
+    fn publish_status(&mut self) {
+        if self.status_fifo.is_none() {
+            return;
+        }
+        let line = self.view_status().to_line();
+        let Some(fifo) = self.status_fifo.as_mut() else {
+            return;
+        };
+        if let Err(error) = fifo.publish(line) {
+            warn!(error = ?error, "Failed to write status FIFO");
+        }
+    }
+
+    /// scrolls to a position written by another instance. returns whether the view moved.
+    fn poll_viewport_sync(&mut self) -> bool {
+        let (Some(sync), Some(alignment)) = (self.viewport_sync.as_mut(), self.alignment.as_ref())
+        else {
+            return false;
+        };
+        let position = match sync.poll() {
+            Ok(Some(position)) => position,
+            Ok(None) => return false,
+            Err(error) => {
+                warn!(error = ?error, "Failed to read viewport sync file");
+                return false;
+            }
+        };
+        let view = alignment.view();
+        let column = view
+            .absolute_column_ids()
+            .position(|visible| visible >= position.column);
+        let row = view.relative_row_id(position.row);
+        if let Some(column) = column {
+            self.ui.viewport.jump_to_position(column);
+        }
+        if let Some(row) = row {
+            self.ui.viewport.jump_to_sequence(row);
+        }
+        column.is_some() || row.is_some()
+    }
+
+    fn frame_period(&self) -> Duration {
+        Duration::from_secs_f32(1.0 / f32::from(self.render_fps))
+    }
+
     fn startup_update_check_enabled() -> bool {
         !matches!(env::var(UPDATE_CHECK_ENV_VAR), Ok(value) if value.eq_ignore_ascii_case("true"))
     }
 
     fn try_file_load(&mut self) {
         let Some(input) = self.ui.meta.input_path.clone() else {
-            info!("No startup file provided; entering idle loading state");
+            info!("No startup file provided; opening the file picker");
             self.ui.meta.loading_state = LoadingState::Idle;
+            self.open_file_picker();
             return;
         };
 
         debug!(input = %input, "Loading startup alignment");
+        if let Err(error) = self.check_memory_limit(&input) {
+            self.ui.meta.loading_state = LoadingState::Failed(error.to_string());
+            return;
+        }
         self.start_load_job(input);
     }
 
@@ -240,24 +877,53 @@ impl App {
 
         self.layout_area = area;
         self.frame_layout = FrameLayout::new(area);
-        self.app_layout = AppLayout::new(self.frame_layout.content_area);
+        self.rebuild_app_layout();
+    }
+
+    fn rebuild_app_layout(&mut self) {
+        let mut options = self.ui.layout_options();
+        if self.ui.position_gutter {
+            options.position_gutter = position_gutter_width(
+                self.alignment
+                    .as_ref()
+                    .map_or(0, |alignment| alignment.base().column_count()),
+            );
+        }
+        self.app_layout = AppLayout::new(self.frame_layout.content_area, options);
+        self.update_viewport_dimensions();
+    }
 
-        let visible_width = self.app_layout.alignment_pane.width.saturating_sub(2) as usize;
-        let available_sequence_rows = self.app_layout.alignment_pane_sequence_rows.height as usize;
+    fn update_viewport_dimensions(&mut self) {
+        let area = self.layout_area;
         let alignment = self.alignment.as_ref();
         let pinned_count = alignment
             .map(|alignment| alignment.rows().pinned().len())
             .unwrap_or(0);
-        let scrollable_height =
-            pinned_section_layout(pinned_count, available_sequence_rows).scrollable_height;
+        let (visible_width, available_sequence_rows) = match self.ui.layout_mode {
+            LayoutMode::Panned => (
+                self.app_layout.alignment_content_width(),
+                self.app_layout.alignment_pane_sequence_rows.height as usize,
+            ),
+            LayoutMode::Wrapped => {
+                let view_rows = alignment
+                    .map(|alignment| alignment.view().row_count())
+                    .unwrap_or(0);
+                let wrapped = self.app_layout.wrapped(pinned_count, view_rows);
+                (
+                    self.app_layout.alignment_content_width() * wrapped.block_count,
+                    wrapped.block_rows,
+                )
+            }
+        };
+        let density = self.ui.effective_density();
+        let visible_width = visible_width * density.cols_per_cell();
+        let scrollable_height = pinned_section_layout(pinned_count, available_sequence_rows)
+            .scrollable_height
+            * density.rows_per_line();
         let row_count = alignment
             .map(|alignment| alignment.base().row_count())
-            .unwrap_or(0)
-            .max(1);
-        let number_width = row_count
-            .checked_ilog10()
-            .map_or(1, |digits| digits as usize + 1);
-        let number_prefix_width = number_width + 1;
+            .unwrap_or(0);
+        let number_prefix_width = self.ui.row_numbers.prefix_width(row_count);
         let name_visible_width = self
             .app_layout
             .sequence_id_pane
@@ -275,6 +941,9 @@ impl App {
             "Terminal resized, viewport updated"
         );
 
+        self.ui
+            .viewport
+            .set_cell_size(density.rows_per_line(), density.cols_per_cell());
         self.ui
             .viewport
             .update_dimensions(visible_width, scrollable_height, name_visible_width);
@@ -284,6 +953,13 @@ impl App {
     fn handle_key_event(&mut self, key: KeyEvent) {
         self.ui.notification = None;
         let commands = input::handle_key_event(&mut self.ui, key);
+        let commands = self.input_coalescer.push(commands);
+        self.execute_commands(commands);
+    }
+
+    fn handle_paste_event(&mut self, text: &str) {
+        self.ui.notification = None;
+        let commands = input::handle_paste_event(&mut self.ui, text);
         self.execute_commands(commands);
     }
 
@@ -296,6 +972,7 @@ impl App {
             &self.app_layout,
             mouse,
         );
+        let commands = self.input_coalescer.push(commands);
         self.execute_commands(commands);
     }
 
@@ -319,18 +996,133 @@ impl App {
     where
         I: IntoIterator<Item = Command>,
     {
+        // a batch such as jump-sequence followed by jump-position is one jump
+        let mut jumped_from = None;
+        let from_menu = matches!(
+            self.ui.overlay.active_overlay,
+            Some(ActiveOverlay::Palette(_) | ActiveOverlay::ContextMenu(_))
+        );
         for command in commands {
+            if command.is_jump() && jumped_from.is_none() {
+                jumped_from = Some(self.jump_position());
+            }
+            // the palette and sequence menu close themselves after the command they ran. a
+            // command that opened a report, prompt or other overlay in their place keeps that open
+            // instead.
+            if from_menu
+                && command == Command::CloseOverlay
+                && !matches!(
+                    self.ui.overlay.active_overlay,
+                    None | Some(ActiveOverlay::Palette(_) | ActiveOverlay::ContextMenu(_))
+                )
+            {
+                continue;
+            }
             if let Err(error) = self.execute_command(command) {
                 warn!(error = ?error, "Command failed");
-                self.ui.notification = Some(Notification {
+                self.ui.notify(Notification {
                     level: NotificationLevel::Error,
                     message: error.to_string(),
                 });
             }
         }
+        if let Some(Some(from)) = jumped_from
+            && self.jump_position() != Some(from)
+        {
+            self.ui.jumps.record(from);
+        }
+    }
+
+    /// the sequence under the crosshair, or under the mouse when the crosshair is off
+    fn sequence_at_cursor(&self) -> Result<usize> {
+        let alignment = self
+            .alignment
+            .as_ref()
+            .ok_or_else(|| format_err!("no alignment is loaded"))?;
+        match self.ui.crosshair {
+            Some(crosshair) => alignment.view().absolute_row_id(crosshair.row),
+            None => self
+                .mouse_tracker
+                .hover()
+                .filter(|_| self.ui.layout_mode == LayoutMode::Panned)
+                .and_then(|(x, y)| {
+                    sequence_at_point(alignment, &self.ui.viewport, &self.app_layout, x, y)
+                }),
+        }
+        .ok_or_else(|| format_err!("no sequence under the cursor"))
+    }
+
+    /// top-left of the viewport as absolute ids, as remembered by marks and the jump history
+    fn jump_position(&self) -> Option<Mark> {
+        let alignment = self.alignment.as_ref()?;
+        let window = self.ui.viewport.window();
+        let view = alignment.view();
+        view.absolute_row_id(window.row_range.start)
+            .zip(view.absolute_column_id(window.col_range.start))
+            .map(|(row, column)| Mark { row, column })
     }
 
+    /// moves the viewport to a position from the jump history
+    fn show_jump_position(&mut self, position: Mark) -> Result<()> {
+        let alignment = self
+            .alignment
+            .as_ref()
+            .ok_or_else(|| format_err!("no alignment is loaded"))?;
+        let view = alignment.view();
+        let (Some(row), Some(column)) = (
+            view.relative_row_id(position.row),
+            view.relative_column_id(position.column),
+        ) else {
+            return Err(format_err!(
+                "column {} is hidden in the current view",
+                position.column + 1
+            ));
+        };
+        self.ui.viewport.jump_to_sequence(row);
+        self.ui.viewport.jump_to_position(column);
+        Ok(())
+    }
+
+    /// runs `command`, first asking for confirmation when it would overwrite an existing file
+    /// or quit with unsaved edits
     fn execute_command(&mut self, command: Command) -> Result<()> {
+        if let Command::Confirmed(command) = command {
+            return self.apply_command(*command);
+        }
+        if let Some(name) = command.edit_name()
+            && self.ui.meta.edit_mode != EditMode::Edit
+        {
+            return Err(format_err!(
+                "{name} changes the alignment; use set-mode edit first"
+            ));
+        }
+        let prompt = match &command {
+            Command::Quit if self.ui.meta.unsaved_edits => {
+                Some("Quit with unsaved edits? save-as keeps them".to_string())
+            }
+            Command::LoadFile { .. } | Command::LoadPastedAlignment(_)
+                if self.ui.meta.unsaved_edits =>
+            {
+                Some("Discard unsaved edits and load another alignment?".to_string())
+            }
+            command if command.is_destructive() => Some(destructive_prompt(command)),
+            command => command
+                .output_path()
+                .filter(|path| Path::new(path).exists())
+                .map(|path| format!("Overwrite {path}?")),
+        };
+        match prompt {
+            Some(prompt) => {
+                self.ui
+                    .overlay
+                    .open_confirm(ConfirmState::new(prompt, command));
+                Ok(())
+            }
+            None => self.apply_command(command),
+        }
+    }
+
+    fn apply_command(&mut self, command: Command) -> Result<()> {
         match command {
             Command::Quit => {
                 self.should_quit = true;
@@ -338,48 +1130,360 @@ impl App {
             Command::OpenCommandPalette => {
                 self.open_command_palette();
             }
+            Command::OpenFilePicker => {
+                self.open_file_picker();
+            }
+            Command::OpenConsensusThreshold => {
+                let threshold = self.alignment_mut()?.consensus_threshold;
+                self.ui.overlay.open_consensus_threshold(threshold);
+            }
+            Command::SetConsensusThreshold(threshold) => {
+                self.alignment_mut()?.consensus_threshold = threshold;
+                self.show_info(format!("Consensus threshold: {:.0}%", threshold * 100.0));
+            }
+            Command::OpenPasteBuffer => {
+                self.ui.overlay.open_paste();
+            }
             Command::CloseOverlay => {
                 self.ui.overlay.close();
             }
             Command::ToggleMinimap => {
                 self.ui.overlay.toggle_minimap();
             }
+            Command::SetFps(fps) => {
+                if !(MIN_RENDER_FPS..=MAX_RENDER_FPS).contains(&fps) {
+                    return Err(format_err!(
+                        "frame rate must be between {MIN_RENDER_FPS} and {MAX_RENDER_FPS}"
+                    ));
+                }
+                self.render_fps = fps;
+                self.show_info(format!("Frame rate: {fps} fps"));
+            }
+            Command::SetInputCoalescing(enabled) => {
+                if let Some(command) = self.input_coalescer.set_enabled(enabled) {
+                    self.execute_command(command)?;
+                }
+                let state = if enabled { "on" } else { "off" };
+                self.show_info(format!("Input coalescing: {state}"));
+            }
+            Command::TogglePerfHud => {
+                self.ui.perf_hud = match self.ui.perf_hud.take() {
+                    Some(_) => None,
+                    None => Some(PerfStats::default()),
+                };
+            }
             Command::SetTheme(theme_id) => {
                 self.ui.set_theme(theme_id);
             }
+            Command::SetSequencePalette(palette) => {
+                self.ui.set_sequence_palette(palette);
+            }
+            Command::SetColorStyle(color_style) => {
+                self.ui.set_color_style(color_style);
+            }
             Command::ShowNotification(notification) => {
-                self.ui.notification = Some(notification);
+                self.ui.notify(notification);
             }
+            Command::Confirmed(command) => return self.apply_command(*command),
+            Command::ShowLastError => match self.ui.last_error.clone() {
+                Some(message) => self.ui.notify(Notification {
+                    level: NotificationLevel::Error,
+                    message,
+                }),
+                None => self.show_info("No errors to show".to_string()),
+            },
             Command::LoadFile { input } => {
+                self.check_memory_limit(&input)?;
                 self.clear_mouse_selection();
+                self.region = None;
                 self.start_load_job(input);
             }
+            Command::LoadPastedAlignment(text) => {
+                self.clear_mouse_selection();
+                self.start_paste_load_job(text);
+            }
             Command::CheckForUpdate {
                 show_success_message,
             } => {
                 self.spawn_update_check(show_success_message);
             }
-
-            Command::ScrollDown { amount } => self.ui.viewport.scroll_down(amount),
-            Command::ScrollUp { amount } => self.ui.viewport.scroll_up(amount),
-            Command::ScrollLeft { amount } => self.ui.viewport.scroll_left(amount),
-            Command::ScrollRight { amount } => self.ui.viewport.scroll_right(amount),
-            Command::ScrollNamesLeft { amount } => self.ui.viewport.scroll_names_left(amount),
-            Command::ScrollNamesRight { amount } => self.ui.viewport.scroll_names_right(amount),
-
-            Command::JumpToPosition(relative_col) => {
-                let has_column = self
-                    .alignment
-                    .as_ref()
-                    .is_some_and(|alignment| relative_col < alignment.view().column_count());
-                if has_column {
-                    self.ui.viewport.jump_to_position(relative_col);
-                }
-            }
-            Command::JumpToSequence(abs_row) => {
-                let Some(alignment) = self.alignment.as_ref() else {
-                    return Ok(());
+            Command::ExportConsensus {
+                path,
+                method,
+                threshold,
+            } => {
+                let alignment = self.alignment_mut()?;
+                let options = ConsensusExportOptions {
+                    method: method.unwrap_or(alignment.consensus_method),
+                    threshold: threshold.unwrap_or(alignment.consensus_threshold),
+                };
+                let view = alignment.view().clone();
+                self.start_export_job("Exporting consensus", view.column_count(), {
+                    move |progress, cancel| {
+                        export::export_consensus(&view, options, Path::new(&path), progress, cancel)
+                            .map(|()| format!("Wrote consensus to {path}"))
+                    }
+                });
+            }
+            Command::ExportConservation { path, format } => {
+                let view = self.alignment_mut()?.view().clone();
+                let format =
+                    format.unwrap_or_else(|| ConservationFormat::from_path(Path::new(&path)));
+                self.start_export_job("Exporting conservation", view.column_count(), {
+                    move |progress, cancel| {
+                        export::export_conservation(
+                            &view,
+                            format,
+                            Path::new(&path),
+                            progress,
+                            cancel,
+                        )
+                        .map(|()| format!("Wrote conservation ({format}) to {path}"))
+                    }
+                });
+            }
+            Command::ExportSnpDists { path, ignore } => {
+                let view = self.alignment_mut()?.view().clone();
+                if view.row_count() < 2 {
+                    return Err(format_err!(
+                        "export-snp-dists needs at least two visible sequences"
+                    ));
+                }
+                let threads = self.workers.threads();
+                self.start_export_job("Exporting SNP distances", view.row_count(), {
+                    move |progress, cancel| {
+                        export::export_snp_dists(
+                            &view,
+                            ignore,
+                            threads,
+                            Path::new(&path),
+                            progress,
+                            cancel,
+                        )
+                        .map(|()| format!("Wrote SNP distances to {path}"))
+                    }
+                });
+            }
+            Command::GenerateReport { path } => {
+                let regions = self.report_regions();
+                let title = self
+                    .ui
+                    .meta
+                    .input_path
+                    .as_deref()
+                    .and_then(|input| Path::new(input).file_name())
+                    .and_then(|name| name.to_str())
+                    .unwrap_or("alignment")
+                    .to_string();
+                let snapshot = SnapshotOptions {
+                    theme: self.ui.theme.id,
+                    palette: self.ui.theme.palette,
+                    color_style: self.ui.theme.color_style,
+                    ..SnapshotOptions::default()
+                };
+                let alignment = self.alignment_mut()?;
+                let options = ReportOptions {
+                    title,
+                    method: alignment.consensus_method,
+                    reference: alignment.rows().reference(),
+                    rows: alignment
+                        .rows()
+                        .pinned()
+                        .iter()
+                        .copied()
+                        .chain(alignment.view().absolute_row_ids())
+                        .collect(),
+                    regions,
+                    snapshot,
+                };
+                let view = alignment.view().clone();
+                let base = alignment.base().clone();
+                self.start_export_job("Generating report", 2 * view.column_count(), {
+                    move |progress, cancel| {
+                        html_report::generate_report(
+                            &view,
+                            &base,
+                            &options,
+                            Path::new(&path),
+                            progress,
+                            cancel,
+                        )
+                        .map(|()| format!("Wrote report to {path}"))
+                    }
+                });
+            }
+            Command::SaveAlignment { path, format, full } => {
+                let alignment = self.alignment_mut()?;
+                let format = format.unwrap_or_else(|| AlignmentFormat::from_path(Path::new(&path)));
+                let (source, abs_rows) = if full {
+                    let base = alignment.base().clone();
+                    let abs_rows = (0..base.row_count()).collect();
+                    (base, abs_rows)
+                } else {
+                    let abs_rows = alignment
+                        .rows()
+                        .reference()
+                        .into_iter()
+                        .chain(alignment.rows().pinned().iter().copied())
+                        .chain(alignment.view().absolute_row_ids())
+                        .collect::<Vec<usize>>();
+                    (alignment.view().clone(), abs_rows)
+                };
+                let partitions = if source.column_count() == alignment.base().column_count() {
+                    alignment.partitions.clone()
+                } else {
+                    Vec::new()
+                };
+                self.start_export_job(SAVE_ALIGNMENT_LABEL, abs_rows.len(), {
+                    move |progress, cancel| {
+                        serializer::save_alignment(
+                            &source,
+                            &abs_rows,
+                            format,
+                            &partitions,
+                            Path::new(&path),
+                            progress,
+                            cancel,
+                        )
+                        .map(|()| {
+                            format!("Saved {} sequences ({format}) to {path}", abs_rows.len())
+                        })
+                    }
+                });
+            }
+            Command::CancelExport => {
+                if self.export_job.is_none() {
+                    return Err(format_err!("no export is running"));
+                }
+                self.cancel_export_job();
+                self.show_info("Export cancelled".to_string());
+            }
+            Command::PrecomputeStats => {
+                self.alignment_mut()?;
+                let (filled, total) = self.stats_cache.raw_coverage();
+                if filled == total {
+                    self.show_info("Stats are already computed for every column".to_string());
+                } else {
+                    self.start_precompute_job();
+                }
+            }
+            Command::ClearCache => {
+                let dir = self
+                    .disk_cache
+                    .dir
+                    .clone()
+                    .ok_or_else(|| format_err!("the stats cache is disabled"))?;
+                let (files, bytes) = disk_cache::clear(&dir)?;
+                if let Some(entry) = self.disk_cache.entry.as_mut() {
+                    entry.cached = None;
+                }
+                self.show_info(format!(
+                    "Removed {files} cached stats files ({})",
+                    memory::format_bytes(bytes)
+                ));
+            }
+            Command::CancelPrecompute => {
+                if self.precompute_job.is_none() {
+                    return Err(format_err!("precompute-stats is not running"));
+                }
+                self.cancel_precompute_job();
+                self.show_info("Precompute cancelled".to_string());
+            }
+
+            Command::ScrollDown { amount } => match self.ui.layout_mode {
+                LayoutMode::Panned => self.ui.viewport.scroll_down(amount),
+                LayoutMode::Wrapped => self
+                    .ui
+                    .viewport
+                    .scroll_right(amount * self.wrapped_block_width()),
+            },
+            Command::ScrollUp { amount } => match self.ui.layout_mode {
+                LayoutMode::Panned => self.ui.viewport.scroll_up(amount),
+                LayoutMode::Wrapped => self
+                    .ui
+                    .viewport
+                    .scroll_left(amount * self.wrapped_block_width()),
+            },
+            Command::ScrollLeft { amount } => self.ui.viewport.scroll_left(amount),
+            Command::ScrollRight { amount } => self.ui.viewport.scroll_right(amount),
+            Command::ScrollNamesLeft { amount } => self.ui.viewport.scroll_names_left(amount),
+            Command::ScrollNamesRight { amount } => self.ui.viewport.scroll_names_right(amount),
+
+            Command::JumpToPosition(relative_col) => {
+                let column_count = self
+                    .alignment
+                    .as_ref()
+                    .ok_or_else(|| format_err!("no alignment is loaded"))?
+                    .view()
+                    .column_count();
+                if relative_col >= column_count {
+                    return Err(format_err!(
+                        "position {} is past the last visible column ({column_count})",
+                        relative_col + 1
+                    ));
+                }
+                self.ui.viewport.jump_to_position(relative_col);
+            }
+            Command::JumpToAlignmentPosition(absolute_col) => {
+                let alignment = self
+                    .alignment
+                    .as_ref()
+                    .ok_or_else(|| format_err!("no alignment is loaded"))?;
+                let relative_col = alignment
+                    .view()
+                    .absolute_column_ids()
+                    .position(|visible| visible >= absolute_col)
+                    .ok_or_else(|| {
+                        format_err!(
+                            "No visible column at or after position {}",
+                            absolute_col + 1
+                        )
+                    })?;
+                self.ui.viewport.jump_to_position(relative_col);
+            }
+            Command::JumpToRange {
+                start,
+                end,
+                highlight,
+            } => {
+                let alignment = self
+                    .alignment
+                    .as_ref()
+                    .ok_or_else(|| format_err!("no alignment is loaded"))?;
+                let view = alignment.view();
+                let first = view
+                    .absolute_column_ids()
+                    .position(|visible| visible >= start);
+                let last = view
+                    .absolute_column_ids()
+                    .take_while(|&visible| visible <= end)
+                    .count()
+                    .checked_sub(1);
+                let columns = match (first, last) {
+                    (Some(first), Some(last)) if first <= last => first..last + 1,
+                    _ => {
+                        return Err(format_err!(
+                            "No visible columns between positions {} and {}",
+                            start + 1,
+                            end + 1
+                        ));
+                    }
                 };
+                if highlight {
+                    self.ui.highlights.push(MouseSelection {
+                        sequence_id: 0,
+                        column: start,
+                        end_sequence_id: alignment.base().row_count().saturating_sub(1),
+                        end_column: end,
+                    });
+                }
+                self.ui.viewport.centre_on_columns(columns);
+            }
+            Command::JumpToSequence(abs_row) => {
+                let alignment = self
+                    .alignment
+                    .as_ref()
+                    .ok_or_else(|| format_err!("no alignment is loaded"))?;
                 if let Some(relative_row) = alignment.view().relative_row_id(abs_row) {
                     self.ui.viewport.jump_to_sequence(relative_row);
                 }
@@ -388,19 +1492,47 @@ impl App {
                 }
             }
             Command::JumpToStart => {
-                let has_columns = self
+                let column_count = self
                     .alignment
                     .as_ref()
-                    .is_some_and(|alignment| alignment.view().column_count() > 0);
-                if has_columns {
+                    .ok_or_else(|| format_err!("no alignment is loaded"))?
+                    .view()
+                    .column_count();
+                if column_count > 0 {
                     self.ui.viewport.jump_to_position(0);
                 }
             }
+            Command::JumpToNextGapColumn => {
+                if self.alignment.is_none() {
+                    return Ok(());
+                }
+                if self.stats_cache.gap_only_columns().is_none() {
+                    return Err(format_err!("gap column scan is still running"));
+                }
+                let current = self
+                    .ui
+                    .crosshair
+                    .as_ref()
+                    .map_or(self.ui.viewport.window().col_range.start, |crosshair| {
+                        crosshair.column
+                    });
+                let Some(target) = self.stats_cache.next_gap_only_after(current) else {
+                    self.show_info("No gap-only columns after the current position".to_string());
+                    return Ok(());
+                };
+                self.ui.viewport.jump_to_position(target);
+                if let Some(crosshair) = self.ui.crosshair.as_mut() {
+                    crosshair.column = target;
+                }
+            }
             Command::JumpToEnd => {
                 let last_col = self
                     .alignment
                     .as_ref()
-                    .and_then(|alignment| alignment.view().column_count().checked_sub(1));
+                    .ok_or_else(|| format_err!("no alignment is loaded"))?
+                    .view()
+                    .column_count()
+                    .checked_sub(1);
                 if let Some(last_col) = last_col {
                     self.ui.viewport.jump_to_position(last_col);
                 }
@@ -418,6 +1550,38 @@ impl App {
                 self.on_view_rebuilt();
                 return Ok(());
             }
+            Command::PinMatching(pattern) => {
+                let alignment = self.alignment_mut()?;
+                let matches = alignment.rows_matching(&pattern)?;
+                if matches.is_empty() {
+                    return Err(format_err!("No visible sequences match '{pattern}'"));
+                }
+                let pinned = matches.len().min(PIN_MATCHING_LIMIT);
+                alignment.pin_rows(&matches[..pinned])?;
+                self.clear_mouse_selection();
+                self.on_view_rebuilt();
+                if pinned < matches.len() {
+                    self.show_info(format!(
+                        "Pinned the first {pinned} of {} matching sequences",
+                        matches.len()
+                    ));
+                } else {
+                    self.show_info(format!("Pinned {pinned} sequences"));
+                }
+                return Ok(());
+            }
+            Command::UnpinAll => {
+                let alignment = self.alignment_mut()?;
+                let unpinned = alignment.rows().pinned().len();
+                if unpinned == 0 {
+                    return Err(format_err!("No sequences are pinned"));
+                }
+                alignment.unpin_all()?;
+                self.clear_mouse_selection();
+                self.on_view_rebuilt();
+                self.show_info(format!("Unpinned {unpinned} sequences"));
+                return Ok(());
+            }
             Command::SetReference(abs_row) => {
                 self.alignment_mut()?.set_reference(abs_row)?;
                 self.clear_mouse_selection();
@@ -430,10 +1594,76 @@ impl App {
                 self.on_view_rebuilt();
                 return Ok(());
             }
+            Command::HideSequence(abs_row) => {
+                let alignment = self.alignment_mut()?;
+                let before = alignment.stats_view().clone();
+                alignment.hide(abs_row)?;
+                self.clear_mouse_selection();
+                self.on_rows_changed(&before);
+                return Ok(());
+            }
+            Command::ShowHiddenSequences => {
+                let alignment = self.alignment_mut()?;
+                let before = alignment.stats_view().clone();
+                alignment.show_hidden()?;
+                self.on_rows_changed(&before);
+                return Ok(());
+            }
+            Command::OpenSequenceMenu { sequence_id, x, y } => {
+                let alignment = self
+                    .alignment
+                    .as_ref()
+                    .ok_or_else(|| format_err!("no alignment is loaded"))?;
+                let sequence = alignment
+                    .base()
+                    .project_absolute_row(sequence_id)
+                    .ok_or_else(|| format_err!("sequence {} does not exist", sequence_id + 1))?;
+                let menu = ContextMenuState::new(
+                    sequence_id,
+                    sequence.id().to_string(),
+                    alignment.rows().is_pinned(sequence_id),
+                    (x, y),
+                );
+                self.ui.overlay.open_context_menu(menu);
+            }
+            Command::CopySelection => {
+                let selection = self.require_selection()?;
+                let fasta = selection::selection_fasta(self.alignment_mut()?, selection);
+                execute!(io::stdout(), CopyToClipboard::to_clipboard_from(&fasta))?;
+                self.show_info("Copied selection to clipboard as FASTA".to_string());
+            }
+            Command::SetEditMode(mode) => {
+                self.ui.meta.edit_mode = mode;
+                self.show_info(format!("Mode: {mode}"));
+            }
+            Command::CopyLocator => {
+                let locator = self.current_locator()?;
+                return self.execute_command(Command::CopyToClipboard(locator.to_string()));
+            }
+            Command::ShowSelectionStats => {
+                let selection = self.require_selection()?;
+                let stats = selection::selection_stats(self.alignment_mut()?, selection)
+                    .ok_or_else(|| format_err!("selection does not cover any visible cells"))?;
+                self.show_info(format!("Selection: {stats}"));
+            }
+            Command::HighlightSelection => {
+                let selection = self.require_selection()?;
+                self.ui.highlights.push(selection);
+                self.clear_mouse_selection();
+            }
+            Command::ClearHighlights => {
+                self.ui.highlights.clear();
+            }
+            Command::CopyToClipboard(text) => {
+                execute!(io::stdout(), CopyToClipboard::to_clipboard_from(&text))?;
+                self.show_info(format!("Copied \"{text}\" to clipboard"));
+            }
 
             Command::SetFilter(pattern) => {
-                self.alignment_mut()?.set_filter(pattern)?;
-                self.on_view_rebuilt();
+                let alignment = self.alignment_mut()?;
+                let before = alignment.stats_view().clone();
+                alignment.set_filter(pattern)?;
+                self.on_rows_changed(&before);
                 return Ok(());
             }
             Command::SetGapFilter(max_gap_fraction) => {
@@ -452,23 +1682,572 @@ impl App {
                 self.on_view_rebuilt();
                 return Ok(());
             }
+            Command::CycleInsertColumns => {
+                let alignment = self.alignment_mut()?;
+                let mode = alignment.filter().insert_columns().next();
+                if mode != InsertColumnMode::Show && alignment.translation().is_some() {
+                    return Err(format_err!(
+                        "toggle-inserts is unavailable while translation is active"
+                    ));
+                }
+                alignment.set_insert_columns(mode)?;
+                self.on_view_rebuilt();
+                self.show_info(format!("Insert columns: {mode}"));
+                return Ok(());
+            }
+            Command::ToggleVariableOnly => {
+                let alignment = self.alignment_mut()?;
+                let enabled = !alignment.filter().variable_only();
+                if enabled && alignment.translation().is_some() {
+                    return Err(format_err!(
+                        "toggle-variable-only is unavailable while translation is active"
+                    ));
+                }
+                alignment.set_variable_only(enabled)?;
+                self.on_view_rebuilt();
+                let state = if enabled { "on" } else { "off" };
+                self.show_info(format!("Variable columns only: {state}"));
+                return Ok(());
+            }
             Command::SetActiveType(kind) => {
                 self.alignment_mut()?.set_active_kind(kind)?;
                 self.on_view_rebuilt();
+                self.remember_active_type(kind);
                 return Ok(());
             }
 
             Command::ToggleTranslationView => {
                 let alignment = self.alignment_mut()?;
                 if alignment.translation().is_none() && alignment.filter().has_column_filter() {
+                    if alignment.filter().max_gap_fraction().is_some() {
+                        return Err(format_err!(
+                            "translation is unavailable while filter-gaps is active"
+                        ));
+                    }
+                    if alignment.filter().variable_only() {
+                        return Err(format_err!(
+                            "translation is unavailable while only variable columns are shown"
+                        ));
+                    }
+                    if !alignment.filter().masked_columns().is_empty() {
+                        return Err(format_err!(
+                            "translation is unavailable while a column mask is loaded"
+                        ));
+                    }
                     return Err(format_err!(
-                        "translation is unavailable while filter-gaps is active"
+                        "translation is unavailable while insert columns are hidden or compressed"
                     ));
                 }
                 alignment.toggle_translation_view()?;
                 self.invalidate_all_stats();
                 return Ok(());
             }
+            Command::ListOrfIssues => {
+                let report = self.orf_issue_report()?;
+                self.ui.overlay.open_report(report);
+            }
+            Command::FindOrfs(min_codons) => {
+                let alignment = self.alignment_mut()?;
+                let code = alignment.genetic_code();
+                let source = SequenceSource::from_model(alignment, "find-orfs")?;
+                self.start_analysis_job("Finding ORFs", move |cancel| {
+                    let (abs_row, label, bytes) = source.resolve(cancel)?;
+                    let orfs = orf::find_orfs(&bytes, min_codons, code, cancel)?;
+                    Ok(orf_report(&label, abs_row, min_codons, &orfs).into())
+                });
+            }
+            Command::ListVariableColumns(top_n) => {
+                let alignment = self.alignment_mut()?;
+                if alignment
+                    .view()
+                    .active_type()
+                    .conservation_alphabet_size()
+                    .is_none()
+                {
+                    return Err(format_err!(
+                        "entropy is not defined for this alignment type"
+                    ));
+                }
+                let view = alignment.view().clone();
+                let method = alignment.consensus_method;
+                self.start_analysis_job("Ranking variable columns", move |cancel| {
+                    let progress = JobProgress::new(view.column_count());
+                    let summaries =
+                        export::full_column_summaries(&view, method, &progress, cancel)?;
+                    let columns = variability::most_variable_columns(&view, &summaries, top_n);
+                    Ok(variable_columns_report(&columns).into())
+                });
+            }
+            Command::ExtractRegion { start, end } => {
+                let model = self.alignment_mut()?.extract_region(start..end + 1)?;
+                let rows = model.base().row_count();
+                let previous = self.alignment.take().expect("alignment checked above");
+                let kept = self.keep_previous_alignment(previous, &model);
+                self.install_alignment(model, 0, None);
+                self.ui.meta.unsaved_edits = true;
+                self.show_info(format!(
+                    "Extracted columns {}-{} of {rows} sequences; {}",
+                    start + 1,
+                    end + 1,
+                    restore_hint(kept)
+                ));
+            }
+            Command::RestoreAlignment => {
+                let previous = self
+                    .previous_alignments
+                    .pop()
+                    .ok_or_else(|| format_err!("no previous alignment to restore"))?;
+                self.install_alignment(previous, 0, None);
+            }
+            Command::AppendAlignment { input } => {
+                self.alignment_mut()?;
+                self.start_append_job(input);
+            }
+            Command::RunTool(name) => {
+                let template = self.tools.get(&name).cloned().ok_or_else(|| {
+                    format_err!("no tool named {name}; add one under [tools] in the config file")
+                })?;
+                let context = self.tool_context()?;
+                self.start_analysis_job("Running tool", move |cancel| {
+                    let output = tools::run_tool(&name, &template, &context, cancel)?;
+                    let title = format!("{name} output");
+                    Ok(AnalysisResult {
+                        pager: Some(PagerState::from_text(&title, &output)),
+                        ..ReportState::new(title, Vec::new()).into()
+                    })
+                });
+            }
+            Command::RealignSelection(aligner) => {
+                let selection = self.require_selection()?;
+                let alignment = self.alignment_mut()?;
+                let rows = selection::selected_rows(alignment, selection);
+                if rows.len() < 2 {
+                    return Err(format_err!("select at least two sequences to realign"));
+                }
+                let columns = selection.column.min(selection.end_column)
+                    ..selection.column.max(selection.end_column) + 1;
+                let blocks = rows
+                    .iter()
+                    .map(|&abs_row| {
+                        alignment
+                            .base()
+                            .project_absolute_row(abs_row)
+                            .map(|sequence| {
+                                columns
+                                    .clone()
+                                    .filter_map(|col| sequence.byte_at(col))
+                                    .collect()
+                            })
+                            .unwrap_or_default()
+                    })
+                    .collect();
+                self.start_realign_job(aligner, rows, columns, blocks);
+            }
+            Command::LoadPartitions { input } => {
+                let text = std::fs::read_to_string(&input)
+                    .map_err(|error| format_err!("could not read {input}: {error}"))?;
+                let partitions = partition::parse_partitions(&text)?;
+                let count = partitions.len();
+                self.alignment_mut()?.set_partitions(partitions)?;
+                self.show_info(format!("Loaded {count} partitions from {input}"));
+            }
+            Command::SaveMask { path } => {
+                let columns = self
+                    .alignment
+                    .as_ref()
+                    .ok_or_else(|| format_err!("no alignment is loaded"))?
+                    .hidden_columns();
+                if columns.is_empty() {
+                    return Err(format_err!("no columns are hidden"));
+                }
+                std::fs::write(&path, mask::format_mask(&columns))
+                    .map_err(|error| format_err!("could not write {path}: {error}"))?;
+                let intervals = mask::column_runs(&columns).len();
+                self.show_info(format!(
+                    "Saved {} hidden columns in {intervals} intervals to {path}",
+                    columns.len()
+                ));
+            }
+            Command::LoadMask { input } => {
+                let text = std::fs::read_to_string(&input)
+                    .map_err(|error| format_err!("could not read {input}: {error}"))?;
+                let columns = mask::parse_mask(&text)?;
+                let alignment = self.alignment_mut()?;
+                if alignment.translation().is_some() {
+                    return Err(format_err!(
+                        "load-mask is unavailable while translation is active"
+                    ));
+                }
+                let length = alignment.base().column_count();
+                if let Some(&last) = columns.last().filter(|&&last| last >= length) {
+                    return Err(format_err!(
+                        "mask column {} is past the end of the alignment ({length} columns)",
+                        last + 1
+                    ));
+                }
+                let count = columns.len();
+                alignment.set_column_mask(columns)?;
+                self.ui.meta.unsaved_edits = true;
+                self.on_view_rebuilt();
+                self.show_info(format!("Masked {count} columns from {input}"));
+            }
+            Command::ClearMask => {
+                self.alignment_mut()?.set_column_mask(Vec::new())?;
+                self.ui.meta.unsaved_edits = true;
+                self.on_view_rebuilt();
+                self.show_info("Column mask cleared".to_string());
+            }
+            Command::ClusterRows(percent) => {
+                let view = self.alignment_mut()?.view().clone();
+                self.start_analysis_job("Clustering sequences", move |cancel| {
+                    let rows: Vec<(usize, Vec<u8>)> = (0..view.row_count())
+                        .filter_map(|relative_row| {
+                            let sequence = view.sequence(relative_row)?;
+                            let bytes = (0..sequence.len())
+                                .filter_map(|col| sequence.byte_at(col))
+                                .collect();
+                            Some((sequence.absolute_row_id(), bytes))
+                        })
+                        .collect();
+                    let clusters = cluster::cluster_rows(&rows, percent / 100.0, cancel)?;
+                    Ok(AnalysisResult {
+                        clusters: Some(clusters.clone()),
+                        ..cluster_report(&view, percent, rows.len(), &clusters).into()
+                    })
+                });
+            }
+            Command::GroupByIndels { visible_only } => {
+                let view = self.alignment_mut()?.view().clone();
+                let columns = visible_only.then(|| self.ui.viewport.window().col_range);
+                self.start_analysis_job("Grouping sequences by indels", move |cancel| {
+                    let rows: Vec<(usize, Vec<u8>)> = (0..view.row_count())
+                        .filter_map(|relative_row| {
+                            let sequence = view.sequence(relative_row)?;
+                            let bytes = (0..sequence.len())
+                                .filter_map(|col| sequence.byte_at(col))
+                                .collect();
+                            Some((sequence.absolute_row_id(), bytes))
+                        })
+                        .collect();
+                    let clusters = cluster::group_by_gap_pattern(&rows, columns, cancel)?;
+                    Ok(AnalysisResult {
+                        clusters: Some(clusters.clone()),
+                        ..indel_group_report(&view, visible_only, rows.len(), &clusters).into()
+                    })
+                });
+            }
+            Command::ToggleCluster(abs_row) => {
+                let expanded = self
+                    .alignment_mut()?
+                    .toggle_cluster(abs_row)?
+                    .ok_or_else(|| {
+                        format_err!("sequence {} is not a cluster representative", abs_row + 1)
+                    })?;
+                self.on_view_rebuilt();
+                let state = if expanded { "expanded" } else { "collapsed" };
+                self.show_info(format!("Cluster {state}"));
+            }
+            Command::ClearClusters => {
+                self.alignment_mut()?.set_clusters(Vec::new())?;
+                self.on_view_rebuilt();
+                self.show_info("Clusters removed".to_string());
+            }
+            Command::JumpToNextPartition => {
+                let Some(alignment) = self.alignment.as_ref() else {
+                    return Ok(());
+                };
+                if alignment.partitions.is_empty() {
+                    return Err(format_err!("alignment has no partitions"));
+                }
+                let current = self
+                    .ui
+                    .crosshair
+                    .as_ref()
+                    .map_or(self.ui.viewport.window().col_range.start, |crosshair| {
+                        crosshair.column
+                    });
+                let Some((index, target)) = alignment.next_partition_column(current) else {
+                    self.show_info("No partitions after the current position".to_string());
+                    return Ok(());
+                };
+                let name = alignment.partitions[index].name.clone();
+                self.ui.viewport.jump_to_position(target);
+                if let Some(crosshair) = self.ui.crosshair.as_mut() {
+                    crosshair.column = target;
+                }
+                self.show_info(format!("Partition {}: {name}", index + 1));
+            }
+            Command::InspectSequence(abs_row) => {
+                let alignment = self.alignment_mut()?;
+                let view = alignment.view().clone();
+                let method = alignment.consensus_method;
+                let row_bytes = |abs_row: usize| {
+                    let sequence = view.project_absolute_row(abs_row)?;
+                    let bytes: Vec<u8> = (0..sequence.len())
+                        .filter_map(|col| sequence.byte_at(col))
+                        .collect();
+                    Some((sequence.id().to_string(), bytes))
+                };
+                let (id, bytes) = row_bytes(abs_row).ok_or_else(|| {
+                    format_err!("sequence {} is not in the current view", abs_row + 1)
+                })?;
+                let reference = alignment
+                    .rows()
+                    .reference()
+                    .filter(|&reference| reference != abs_row)
+                    .and_then(row_bytes)
+                    .map(|(reference_id, bytes)| (format!("reference {reference_id}"), bytes));
+                self.start_analysis_job("Inspecting sequence", move |cancel| {
+                    let compare = match reference {
+                        Some(reference) => reference,
+                        None => {
+                            let progress = JobProgress::new(view.column_count());
+                            let consensus =
+                                export::full_consensus(&view, method, &progress, cancel)?;
+                            ("consensus".to_string(), consensus)
+                        }
+                    };
+                    let stats = inspect::sequence_stats(
+                        &bytes,
+                        view.active_type(),
+                        Some((compare.0, &compare.1)),
+                    );
+                    let entries = stats.lines().into_iter().map(ReportEntry::text).collect();
+                    Ok(ReportState::new(id, entries).into())
+                });
+            }
+            Command::Composition(rows) => {
+                let (view, rows, frame, table) = self.composition_input(rows)?;
+                let title = match rows.as_slice() {
+                    [abs_row] => view
+                        .project_absolute_row(*abs_row)
+                        .map(|sequence| format!("Composition of {}", sequence.id()))
+                        .unwrap_or_default(),
+                    rows => format!("Composition of {} sequences", rows.len()),
+                };
+                self.start_analysis_job("Counting composition", move |cancel| {
+                    let progress = JobProgress::new(rows.len());
+                    let composition = composition::count_composition(
+                        &view, &rows, frame, &table, &progress, cancel,
+                    )?;
+                    let entries = composition
+                        .lines()
+                        .into_iter()
+                        .map(ReportEntry::text)
+                        .collect();
+                    Ok(ReportState::new(title, entries).into())
+                });
+            }
+            Command::ExportComposition { path, rows } => {
+                let (view, rows, frame, table) = self.composition_input(rows)?;
+                self.start_export_job("Exporting composition", rows.len(), {
+                    move |progress, cancel| {
+                        composition::export_composition(
+                            &view,
+                            &rows,
+                            frame,
+                            &table,
+                            Path::new(&path),
+                            progress,
+                            cancel,
+                        )
+                        .map(|()| format!("Wrote composition to {path}"))
+                    }
+                });
+            }
+            Command::EstimateDnDs { sequence, visible } => {
+                let window = self.ui.viewport.window();
+                let alignment = self.alignment_mut()?;
+                if !alignment.base().active_type().is_nucleotide() {
+                    return Err(format_err!("dnds needs a nucleotide alignment"));
+                }
+                let reference = alignment.rows().reference().ok_or_else(|| {
+                    format_err!("dnds compares against the reference; set one first")
+                })?;
+                if reference == sequence {
+                    return Err(format_err!("the sequence is the reference"));
+                }
+                let columns = if visible {
+                    let view = alignment.view();
+                    let first = view.absolute_column_id(window.col_range.start);
+                    let last = view.absolute_column_id(window.col_range.end.saturating_sub(1));
+                    first
+                        .zip(last)
+                        .map(|(first, last)| first..last + 1)
+                        .ok_or_else(|| format_err!("no columns are visible"))?
+                } else {
+                    0..alignment.base().column_count()
+                };
+                let base = alignment.base().clone();
+                let frame = alignment.translation_frame();
+                let table = alignment.genetic_code().table();
+                let row_bytes = |abs_row: usize| {
+                    let sequence = base.sequence_by_absolute(abs_row)?;
+                    let bytes: Vec<u8> = (0..sequence.len())
+                        .filter_map(|col| sequence.byte_at(col))
+                        .collect();
+                    Some((sequence.id().to_string(), bytes))
+                };
+                let (reference_id, reference) =
+                    row_bytes(reference).ok_or_else(|| format_err!("reference not found"))?;
+                let (id, query) = row_bytes(sequence)
+                    .ok_or_else(|| format_err!("sequence {} not found", sequence + 1))?;
+                let region = format!("Columns {}-{}", columns.start + 1, columns.end);
+                self.start_analysis_job("Estimating dN/dS", move |cancel| {
+                    let estimate =
+                        dnds::nei_gojobori(&reference, &query, frame, &table, columns, cancel)?;
+                    let entries = std::iter::once(region)
+                        .chain(estimate.lines())
+                        .map(ReportEntry::text)
+                        .collect();
+                    Ok(
+                        ReportState::new(format!("dN/dS of {id} against {reference_id}"), entries)
+                            .into(),
+                    )
+                });
+            }
+            Command::ShowUngapped(Some(abs_row)) => {
+                let ungapped = UngappedSequence::from_model(self.alignment_mut()?, abs_row)
+                    .ok_or_else(|| format_err!("sequence {} not found", abs_row + 1))?;
+                let message = format!("Showing {} without gaps", ungapped.id());
+                self.ui.ungapped = Some(ungapped);
+                self.rebuild_app_layout();
+                self.show_info(message);
+            }
+            Command::ShowUngapped(None) => {
+                self.ui.ungapped = None;
+                self.rebuild_app_layout();
+            }
+            Command::TrackSequence(Some(abs_row)) => {
+                let tracked = TrackedSequence::from_model(self.alignment_mut()?, abs_row)
+                    .ok_or_else(|| format_err!("sequence {} not found", abs_row + 1))?;
+                let message = format!("Tracking positions in {}", tracked.id());
+                self.ui.tracked = Some(tracked);
+                self.show_info(message);
+            }
+            Command::TrackSequence(None) => {
+                self.ui.tracked = None;
+                self.show_info("Stopped tracking sequence positions".to_string());
+            }
+            Command::ConvertPosition {
+                position,
+                from,
+                sequence,
+            } => {
+                let alignment = self.alignment_mut()?;
+                let reference = alignment.rows().reference();
+                let column = match from {
+                    PositionSource::Column => Some(position - 1)
+                        .filter(|&col| col < alignment.base().column_count())
+                        .ok_or_else(|| format_err!("Column {position} is past the alignment"))?,
+                    PositionSource::Reference => {
+                        let reference =
+                            reference.ok_or_else(|| format_err!("No reference sequence set"))?;
+                        alignment
+                            .residue_index(reference)
+                            .and_then(|index| index.column_of(position))
+                            .ok_or_else(|| {
+                                format_err!("Position {position} is past the reference")
+                            })?
+                    }
+                    PositionSource::Sequence => {
+                        let abs_row = sequence.ok_or_else(|| format_err!("No sequence given"))?;
+                        alignment
+                            .residue_index(abs_row)
+                            .and_then(|index| index.column_of(position))
+                            .ok_or_else(|| {
+                                format_err!("Position {position} is past the sequence")
+                            })?
+                    }
+                };
+
+                let mut parts = vec![format!("Column {}", column + 1)];
+                let rows = reference
+                    .into_iter()
+                    .chain(sequence.filter(|&abs_row| Some(abs_row) != reference));
+                for abs_row in rows {
+                    let Some(id) = alignment
+                        .base()
+                        .project_absolute_row(abs_row)
+                        .map(|sequence| sequence.id().to_string())
+                    else {
+                        continue;
+                    };
+                    let Some(index) = alignment.residue_index(abs_row) else {
+                        continue;
+                    };
+                    let label = if Some(abs_row) == reference {
+                        format!("reference {id}")
+                    } else {
+                        id
+                    };
+                    parts.push(format!("{label} {}", index.position_at(column)));
+                }
+                self.show_info(parts.join(" | "));
+            }
+            Command::ShowRestrictionSites(names) => {
+                if names.is_empty() {
+                    self.alignment_mut()?.restriction_sites.clear();
+                    self.show_info("Cleared restriction sites".to_string());
+                    return Ok(());
+                }
+                let enzymes = names
+                    .iter()
+                    .map(|name| restriction::enzyme(name))
+                    .collect::<Result<Vec<_>>>()?;
+                let source =
+                    SequenceSource::from_model(self.alignment_mut()?, "show-restriction-sites")?;
+                self.start_analysis_job("Finding restriction sites", move |cancel| {
+                    let view = source.view().clone();
+                    let (abs_row, label, bytes) = source.resolve(cancel)?;
+                    let sites = restriction::find_sites(&bytes, &enzymes);
+                    Ok(restriction_report(&view, &label, abs_row, &enzymes, &sites))
+                });
+            }
+            Command::FindPrimer {
+                primer,
+                max_mismatches,
+            } => {
+                let primer = Primer::parse(&primer)?;
+                let alignment = self.alignment_mut()?;
+                if !alignment.view().active_type().is_nucleotide() {
+                    return Err(format_err!("find-primer requires a DNA or RNA alignment"));
+                }
+                let view = alignment.view().clone();
+                self.start_analysis_job("Searching for primer", move |cancel| {
+                    let rows = (0..view.row_count())
+                        .filter_map(|relative_row| {
+                            let absolute_row = view.absolute_row_id(relative_row)?;
+                            let sequence = view.sequence(relative_row)?;
+                            let bytes = (0..sequence.len())
+                                .filter_map(|col| sequence.byte_at(col))
+                                .collect();
+                            Some((absolute_row, sequence.id().to_string(), bytes))
+                        })
+                        .collect::<Vec<_>>();
+                    let hits = primer::search_rows(&rows, &primer, max_mismatches, cancel)?;
+                    Ok(primer_report(&view, &primer, max_mismatches, &rows, &hits))
+                });
+            }
+            Command::BlastLite { query } => {
+                let alignment = self.alignment_mut()?;
+                let query = Query::parse(&query, alignment.view().active_type().is_nucleotide())?;
+                let view = alignment.view().clone();
+                self.start_analysis_job("Searching for similar sequences", move |cancel| {
+                    let rows = (0..view.row_count())
+                        .filter_map(|relative_row| {
+                            let absolute_row = view.absolute_row_id(relative_row)?;
+                            let sequence = view.sequence(relative_row)?;
+                            let bytes = (0..sequence.len())
+                                .filter_map(|col| sequence.byte_at(col))
+                                .collect();
+                            Some((absolute_row, sequence.id().to_string(), bytes))
+                        })
+                        .collect::<Vec<_>>();
+                    let hits = similarity::search_rows(&rows, &query, cancel)?;
+                    Ok(similarity_report(&view, &query, &rows, &hits))
+                });
+            }
             Command::SetTranslationFrame(frame) => {
                 let alignment = self.alignment_mut()?;
                 let was_enabled = alignment.translation().is_some();
@@ -478,361 +2257,2972 @@ impl App {
                 }
                 return Ok(());
             }
+            Command::SetGeneticCode(code) => {
+                let alignment = self.alignment_mut()?;
+                let was_enabled = alignment.translation().is_some();
+                alignment.set_genetic_code(code);
+                if was_enabled {
+                    self.invalidate_translated_stats();
+                }
+                self.show_info(format!("Genetic code: {code}"));
+                return Ok(());
+            }
+            Command::SetTranslationGapMode(gap_mode) => {
+                let alignment = self.alignment_mut()?;
+                let was_enabled = alignment.translation().is_some();
+                alignment.set_translation_gap_mode(gap_mode);
+                if was_enabled {
+                    self.invalidate_translated_stats();
+                }
+                self.show_info(format!("Translation gap mode: {gap_mode}"));
+                return Ok(());
+            }
 
+            Command::SetConsensusScope(scope) => {
+                self.alignment_mut()?.set_consensus_scope(scope)?;
+                self.invalidate_all_stats();
+                self.show_info(format!("Consensus scope: {scope}"));
+                return Ok(());
+            }
             Command::SetConsensusMethod(method) => {
                 self.alignment_mut()?.consensus_method = method;
-                self.invalidate_all_stats();
+                self.rederive_stats();
                 return Ok(());
             }
             Command::SetDiffMode(mode) => {
                 self.alignment_mut()?.diff_mode = mode;
             }
-        }
-
-        self.extend_stats_if_needed();
-        Ok(())
-    }
-
-    fn open_command_palette(&mut self) {
-        let palette = self
-            .alignment
-            .as_ref()
-            .map(CommandPaletteState::from_alignment)
-            .unwrap_or_else(CommandPaletteState::empty);
-        self.ui.overlay.open_palette(palette);
-    }
-
-    fn on_view_rebuilt(&mut self) {
-        self.refresh_viewport_bounds();
-        self.invalidate_all_stats();
-    }
+            Command::SetDiffAmbiguity(ambiguity) => {
+                self.alignment_mut()?.diff_ambiguity = ambiguity;
+                self.show_info(format!("Diff ambiguity: {ambiguity}"));
+            }
+            Command::SetDiffOptions(options) => {
+                self.alignment_mut()?.diff_options = options;
+                self.show_info(format!("Diff options: {options}"));
+            }
+            Command::TogglePositionGutter => {
+                self.ui.position_gutter = !self.ui.position_gutter;
+                self.rebuild_app_layout();
+                let state = if self.ui.position_gutter { "on" } else { "off" };
+                self.show_info(format!("Position gutter: {state}"));
+            }
+            Command::ToggleDepthTrack => {
+                self.ui.depth_track = !self.ui.depth_track;
+                self.rebuild_app_layout();
+                let state = if self.ui.depth_track { "on" } else { "off" };
+                self.show_info(format!("Depth track: {state}"));
+            }
+            Command::ToggleConsensusTranslation => {
+                if !self
+                    .alignment_mut()?
+                    .base()
+                    .active_type()
+                    .supports_translation()
+                {
+                    return Err(format_err!(
+                        "toggle-consensus-translation requires a DNA or RNA alignment"
+                    ));
+                }
+                self.ui.consensus_translation = !self.ui.consensus_translation;
+                self.rebuild_app_layout();
+                let state = if self.ui.consensus_translation {
+                    "on"
+                } else {
+                    "off"
+                };
+                self.show_info(format!("Consensus translation: {state}"));
+            }
+            Command::ToggleQualityShading => {
+                let alignment = self.alignment_mut()?;
+                if !alignment.base().has_quality() {
+                    return Err(format_err!("alignment has no per-base quality scores"));
+                }
+                alignment.quality_shading = !alignment.quality_shading;
+                let state = if alignment.quality_shading {
+                    "on"
+                } else {
+                    "off"
+                };
+                self.show_info(format!("Quality shading: {state}"));
+            }
+            Command::ToggleConservationEmphasis => {
+                let alignment = self.alignment_mut()?;
+                alignment.conservation_emphasis = !alignment.conservation_emphasis;
+                let state = if alignment.conservation_emphasis {
+                    "on"
+                } else {
+                    "off"
+                };
+                self.show_info(format!("Conservation emphasis: {state}"));
+            }
+            Command::SetLayoutMode(mode) => {
+                if self.ui.layout_mode != mode {
+                    self.ui.layout_mode = mode;
+                    self.clear_mouse_selection();
+                    self.ui.crosshair = None;
+                    self.update_viewport_dimensions();
+                }
+                self.show_info(format!("Layout: {mode}"));
+            }
+            Command::SetIdPaneWidth(width) => {
+                self.ui.id_pane_width = width;
+                self.rebuild_app_layout();
+                self.show_info(format!("Sequence name pane width: {width}"));
+            }
+            Command::ResizeIdPane(delta) => {
+                let width = self
+                    .app_layout
+                    .sequence_id_pane
+                    .width
+                    .saturating_add_signed(delta)
+                    .max(MIN_SEQUENCE_ID_PANE_WIDTH);
+                self.ui.id_pane_width = IdPaneWidth::Columns(width);
+                self.rebuild_app_layout();
+                let width = self.app_layout.sequence_id_pane.width;
+                self.show_info(format!("Sequence name pane width: {width}"));
+            }
+            Command::SetRowNumbers(row_numbers) => {
+                self.ui.row_numbers = row_numbers;
+                self.update_viewport_dimensions();
+                self.show_info(format!("Row numbers: {row_numbers}"));
+            }
+            Command::AddIdColor(rule) => {
+                let matching = self.alignment.as_ref().map_or(0, |alignment| {
+                    (0..alignment.base().row_count())
+                        .filter_map(|abs_row| alignment.base().project_absolute_row(abs_row))
+                        .filter(|sequence| rule.matches(sequence.id()))
+                        .count()
+                });
+                let message = format!(
+                    "Colouring {matching} sequence names matching {}",
+                    rule.pattern()
+                );
+                self.ui
+                    .id_colors
+                    .retain(|existing| existing.pattern() != rule.pattern());
+                self.ui.id_colors.insert(0, rule);
+                self.show_info(message);
+            }
+            Command::ClearIdColors => {
+                self.ui.id_colors.clear();
+                self.show_info("Removed sequence name colours".to_string());
+            }
+            Command::SetRuler { ticks, coordinates } => {
+                if let Some(ticks) = ticks {
+                    self.ui.ruler.ticks = ticks;
+                }
+                if let Some(coordinates) = coordinates {
+                    self.ui.ruler.coordinates = coordinates;
+                }
+                self.show_info(format!("Ruler: {}", self.ui.ruler));
+            }
+            Command::SetErrorFeedback(feedback) => {
+                self.ui.error_feedback = feedback;
+                self.show_info(format!("Error feedback: {feedback}"));
+            }
+            Command::SetFastRender(fast_render) => {
+                self.ui.fast_render = fast_render;
+                self.show_info(format!("Fast rendering: {fast_render}"));
+            }
+            Command::SetDensity(density) => {
+                self.ui.density = density;
+                self.update_viewport_dimensions();
+                self.show_info(format!("Density: {density}"));
+            }
+            Command::ZoomOut => {
+                let density = self
+                    .ui
+                    .density
+                    .zoomed_out()
+                    .ok_or_else(|| format_err!("already zoomed out to the overview"))?;
+                return self.execute_command(Command::SetDensity(density));
+            }
+            Command::ZoomIn => {
+                let density = self
+                    .ui
+                    .density
+                    .zoomed_in()
+                    .ok_or_else(|| format_err!("already at normal density"))?;
+                return self.execute_command(Command::SetDensity(density));
+            }
+            Command::SetIdTruncation(truncation) => {
+                self.ui.id_truncation = truncation;
+                self.ui.viewport.offsets.names = 0;
+                self.refresh_viewport_bounds();
+                self.show_info(format!("Sequence name truncation: {truncation}"));
+            }
 
-    fn clear_mouse_selection(&mut self) {
-        self.ui.selection = None;
-        self.mouse_tracker.clear_anchors();
-    }
+            Command::ToggleCrosshair => {
+                if self.ui.crosshair.take().is_none() {
+                    self.alignment_mut()?;
+                    let window = self.ui.viewport.window();
+                    self.ui.crosshair = Some(Crosshair {
+                        row: window.row_range.start,
+                        column: window.col_range.start,
+                    });
+                }
+            }
+            Command::MoveCrosshair { rows, cols } => self.move_crosshair(rows, cols),
+            Command::SetReferenceAtCrosshair => {
+                let crosshair = self
+                    .ui
+                    .crosshair
+                    .ok_or_else(|| format_err!("crosshair is not active"))?;
+                let abs_row = self
+                    .alignment_mut()?
+                    .view()
+                    .absolute_row_id(crosshair.row)
+                    .ok_or_else(|| format_err!("no sequence under the crosshair"))?;
+                return self.execute_command(Command::SetReference(abs_row));
+            }
+            Command::TogglePinAtCursor => {
+                let abs_row = self.sequence_at_cursor()?;
+                let alignment = self
+                    .alignment
+                    .as_ref()
+                    .ok_or_else(|| format_err!("no alignment is loaded"))?;
+                let command = if alignment.rows().is_pinned(abs_row) {
+                    Command::UnpinSequence(abs_row)
+                } else {
+                    Command::PinSequence(abs_row)
+                };
+                return self.execute_command(command);
+            }
+            Command::MovePinned { abs_row, offset } => {
+                let alignment = self.alignment_mut()?;
+                let name = alignment
+                    .base()
+                    .sequence_by_absolute(abs_row)
+                    .map(|sequence| sequence.id().to_string())
+                    .unwrap_or_default();
+                let from = alignment
+                    .rows()
+                    .pinned()
+                    .iter()
+                    .position(|&pinned_row| pinned_row == abs_row)
+                    .ok_or_else(|| format_err!("{name} is not pinned"))?;
+                let to = alignment
+                    .move_pinned(abs_row, offset)
+                    .ok_or_else(|| format_err!("{name} is not pinned"))?;
+                let count = alignment.rows().pinned().len();
+                if to == from {
+                    let end = if offset < 0 { "first" } else { "last" };
+                    return Err(format_err!("{name} is already the {end} pinned sequence"));
+                }
+                self.show_info(format!("Moved {name} to pin {} of {count}", to + 1));
+            }
+            Command::MovePinnedAtCursor(offset) => {
+                let abs_row = self.sequence_at_cursor()?;
+                return self.execute_command(Command::MovePinned { abs_row, offset });
+            }
+            Command::BeginMark(action) => {
+                self.ui.marks.pending = Some(action);
+            }
+            Command::SetMark(letter) => {
+                let alignment = self
+                    .alignment
+                    .as_ref()
+                    .ok_or_else(|| format_err!("no alignment is loaded"))?;
+                let window = self.ui.viewport.window();
+                let view = alignment.view();
+                let mark = view
+                    .absolute_row_id(window.row_range.start)
+                    .zip(view.absolute_column_id(window.col_range.start))
+                    .map(|(row, column)| Mark { row, column })
+                    .ok_or_else(|| format_err!("nothing in view to mark"))?;
+                self.ui.marks.marks.insert(letter, mark);
+                self.save_marks()?;
+                self.show_info(format!(
+                    "Mark '{letter}' set at column {}",
+                    window.col_range.start + 1
+                ));
+            }
+            Command::JumpToMark(letter) => {
+                let alignment = self
+                    .alignment
+                    .as_ref()
+                    .ok_or_else(|| format_err!("no alignment is loaded"))?;
+                let mark = self
+                    .ui
+                    .marks
+                    .marks
+                    .get(&letter)
+                    .ok_or_else(|| format_err!("mark '{letter}' is not set"))?;
+                let view = alignment.view();
+                let (Some(row), Some(column)) = (
+                    view.relative_row_id(mark.row),
+                    view.relative_column_id(mark.column),
+                ) else {
+                    return Err(format_err!("mark '{letter}' is hidden in the current view"));
+                };
+                self.ui.viewport.jump_to_sequence(row);
+                self.ui.viewport.jump_to_position(column);
+            }
+            Command::JumpBack => {
+                let current = self
+                    .jump_position()
+                    .ok_or_else(|| format_err!("no alignment is loaded"))?;
+                let position = self
+                    .ui
+                    .jumps
+                    .back(current)
+                    .ok_or_else(|| format_err!("no earlier jump position"))?;
+                self.show_jump_position(position)?;
+            }
+            Command::JumpForward => {
+                let position = self
+                    .ui
+                    .jumps
+                    .forward()
+                    .ok_or_else(|| format_err!("no later jump position"))?;
+                self.show_jump_position(position)?;
+            }
+            Command::ToggleFollowTail => {
+                self.ui.follow_tail = !self.ui.follow_tail;
+                if self.ui.follow_tail {
+                    self.ui.viewport.jump_to_sequence(usize::MAX);
+                }
+                let state = if self.ui.follow_tail { "on" } else { "off" };
+                self.show_info(format!("Follow newest rows: {state}"));
+            }
+            Command::ListMarks => {
+                let alignment = self
+                    .alignment
+                    .as_ref()
+                    .ok_or_else(|| format_err!("no alignment is loaded"))?;
+                if self.ui.marks.marks.is_empty() {
+                    return Err(format_err!("no marks are set"));
+                }
+                let view = alignment.view();
+                let entries = self
+                    .ui
+                    .marks
+                    .marks
+                    .iter()
+                    .map(|(letter, mark)| {
+                        let label = format!(
+                            "'{letter}  column {}, sequence {}",
+                            mark.column + 1,
+                            mark.row + 1
+                        );
+                        match view.relative_column_id(mark.column) {
+                            Some(column) => ReportEntry::jump(
+                                label,
+                                ReportTarget {
+                                    abs_row: Some(mark.row),
+                                    column,
+                                },
+                            ),
+                            None => ReportEntry::text(format!("{label} (hidden)")),
+                        }
+                    })
+                    .collect();
+                self.ui
+                    .overlay
+                    .open_report(ReportState::new("Marks", entries));
+            }
+        }
 
-    fn show_info(&mut self, message: String) {
-        self.ui.notification = Some(Notification {
-            level: NotificationLevel::Info,
-            message,
-        });
+        self.extend_stats_if_needed();
+        Ok(())
     }
 
-    fn refresh_viewport_bounds(&mut self) {
-        let Some(alignment) = self.alignment.as_ref() else {
+    fn refresh_perf_stats(&mut self, loop_latency: Duration) {
+        let Some(perf) = self.ui.perf_hud.as_mut() else {
             return;
         };
-        self.ui.viewport.set_bounds(
-            alignment.view().row_count(),
-            alignment.view().column_count(),
-            alignment.base().max_id_len(),
-        );
+        perf.loop_latency = loop_latency;
+        perf.pending_jobs = self.raw_stats_jobs.len()
+            + self.translated_stats_jobs.len()
+            + self.gap_only_jobs.len()
+            + self.difference_jobs.len()
+            + usize::from(self.load_job.is_some())
+            + usize::from(self.append_job.is_some())
+            + usize::from(self.realign_job.is_some())
+            + usize::from(self.index_job.is_some());
+        perf.raw_cache = self.stats_cache.raw_coverage();
+        perf.translated_cache = self.stats_cache.translated_coverage();
     }
 
-    fn alignment_mut(&mut self) -> Result<&mut AlignmentModel> {
-        self.alignment
-            .as_mut()
-            .ok_or_else(|| format_err!("no alignment is loaded"))
+    fn open_command_palette(&mut self) {
+        let palette = self
+            .alignment
+            .as_ref()
+            .map(CommandPaletteState::from_alignment)
+            .unwrap_or_else(CommandPaletteState::empty)
+            .with_tools(self.tools.keys().cloned().collect());
+        self.ui.overlay.open_palette(palette);
     }
 
-    fn start_load_job(&mut self, input: String) {
-        if let Some(previous) = self.load_job.take() {
-            debug!("Previous load job found, cancelling");
-            previous.cancel.cancel();
-            previous.handle.abort();
-        }
-
-        self.ui.meta.input_path = Some(input.clone());
-        self.ui.meta.loading_state = LoadingState::Loading;
-
-        let cancel = CancellationToken::new();
-        debug!(input = %input, "Spawning new load job for input");
-        let handle = tokio::task::spawn_blocking({
-            let cancel = cancel.clone();
-            move || parser::parse_fasta_file(&input, &cancel).map_err(|error| error.to_string())
-        });
+    /// swaps in a new alignment and resets everything derived from the previous one
+    /// `cache_entry` is the stats cache entry of the file the model was loaded from, if any
+    fn install_alignment(
+        &mut self,
+        model: AlignmentModel,
+        position: usize,
+        cache_entry: Option<CacheEntry>,
+    ) {
+        self.follower = None;
+        self.disk_cache.entry = cache_entry;
+        self.cancel_analysis_job();
+        self.cancel_append_job();
+        self.cancel_realign_job();
+        self.raw_stats_jobs.abort_all();
+        self.translated_stats_jobs.abort_all();
+        self.gap_only_jobs.abort_all();
+        self.difference_jobs.abort_all();
+        self.stats_cache.init(model.view().column_count());
+        self.alignment = Some(model);
+        self.ui.meta.loading_state = LoadingState::Loaded;
+        self.ui.clear_transient_state();
+        self.mouse_tracker.clear_anchors();
+        self.rebuild_app_layout();
+        self.ui.viewport.jump_to_position(position);
+        self.load_marks();
+        self.try_spawn_stats_jobs();
+        self.restart_precompute();
+    }
 
-        self.load_job = Some(AsyncJob { handle, cancel });
+    /// the loaded input and the columns, rows, reference and filter currently in view
+    fn current_locator(&self) -> Result<Locator> {
+        let input = self
+            .ui
+            .meta
+            .input_path
+            .as_deref()
+            .ok_or_else(|| format_err!("no input is loaded"))?;
+        let alignment = self
+            .alignment
+            .as_ref()
+            .ok_or_else(|| format_err!("no alignment is loaded"))?;
+        let input = std::fs::canonicalize(input)
+            .map_or_else(|_| input.to_string(), |path| path.display().to_string());
+        let window = self.ui.viewport.window();
+        let view = alignment.view();
+        let sequence_id = |abs_row: usize| {
+            alignment
+                .base()
+                .sequence_by_absolute(abs_row)
+                .map(|sequence| sequence.id().to_string())
+        };
+        Ok(Locator {
+            input,
+            position: view
+                .absolute_column_id(window.col_range.start)
+                .map(|col| col + 1),
+            row: view
+                .absolute_row_id(window.row_range.start)
+                .and_then(sequence_id),
+            reference: alignment.rows().reference().and_then(sequence_id),
+            filter: alignment.filter().pattern().map(str::to_string),
+        })
     }
 
-    fn spawn_update_check(&self, show_up_to_date: bool) {
-        let Some(event_tx) = self.event_tx.clone() else {
+    /// restores the filter, reference and scroll position of a locator given on the command line
+    fn apply_locator(&mut self, locator: &Locator) {
+        let Some(alignment) = self.alignment.as_ref() else {
             return;
         };
+        let base = alignment.base();
+        let find_row = |id: &str| {
+            (0..base.row_count())
+                .find(|&abs_row| {
+                    base.sequence_by_absolute(abs_row)
+                        .is_some_and(|sequence| sequence.id() == id)
+                })
+                .ok_or_else(|| format_err!("sequence {id} in the locator was not found"))
+        };
+        let reference = locator.reference.as_deref().map(find_row);
+        let row = locator.row.as_deref().map(find_row);
 
-        tokio::spawn(async move {
-            let Some(result) = crate::update::check_for_update().await else {
-                return;
-            };
-            match result {
-                UpdateResult::UpdateAvailable(latest) => {
-                    let _ = event_tx.send(AppEvent::UpdateAvailable { latest });
-                }
-                UpdateResult::UpToDate => {
-                    if show_up_to_date {
-                        let _ = event_tx.send(AppEvent::UpToDate);
-                    }
-                }
+        let mut commands = Vec::new();
+        if let Some(pattern) = &locator.filter {
+            commands.push(Ok(Command::SetFilter(pattern.clone())));
+        }
+        commands.extend(reference.map(|abs_row| abs_row.map(Command::SetReference)));
+        commands.extend(row.map(|abs_row| abs_row.map(Command::JumpToSequence)));
+        if let Some(position) = locator.position {
+            commands.push(Ok(Command::JumpToAlignmentPosition(position - 1)));
+        }
+        for command in commands {
+            match command {
+                Ok(command) => self.execute_commands([command]),
+                Err(error) => self.show_error(error.to_string()),
             }
-        });
+        }
     }
 
-    fn handle_stats_result(&mut self, join_result: std::result::Result<StatsJobResult, JoinError>) {
-        let Ok(result) = join_result else {
+    /// saves `kind` as the sequence type of the loaded file so reopening it skips detection
+    fn remember_active_type(&mut self, kind: libmsa::AlignmentType) {
+        let Some(entry) = self.disk_cache.entry.as_mut() else {
             return;
         };
-        let error_message = result.summaries.as_ref().err().cloned();
-        if !self.stats_cache.store(result)
-            && let Some(error_message) = error_message
-        {
-            warn!(error = %error_message, "Stats chunk failed");
+        match disk_cache::write_type_override(entry, kind) {
+            Ok(()) => {
+                entry.type_override = Some(kind);
+                self.show_info(format!("Sequence type: {kind}, remembered for this file"));
+            }
+            Err(error) => warn!(error = ?error, "Failed to save sequence type override"),
         }
     }
 
-    fn try_spawn_stats_jobs(&mut self) {
-        let Some(alignment) = self.alignment.as_ref() else {
+    /// tails the loaded input when started with `--follow`, from where its load stopped reading.
+    /// only local uncompressed files can be followed.
+    fn start_following(&mut self, follower: Option<FileFollower>) {
+        let Some(input) = self
+            .ui
+            .meta
+            .input_path
+            .as_deref()
+            .filter(|_| self.ui.meta.follow)
+        else {
             return;
         };
-        let col_range = self.ui.viewport.window().col_range;
-        let generation = self.stats_cache.generation;
+        if self.region.is_some() {
+            self.show_error(format!(
+                "Cannot follow {input}: only part of it was loaded with --region"
+            ));
+            return;
+        }
+        match follower {
+            Some(follower) => {
+                info!(input = %input, "Following input for appended records");
+                self.follower = Some(follower);
+            }
+            None => self.show_error(format!(
+                "Cannot follow {input}: only local uncompressed files can be followed"
+            )),
+        }
+    }
 
-        for chunk_idx in self.stats_cache.raw_chunks_to_spawn(&col_range) {
-            self.stats_cache.mark_raw_pending(chunk_idx);
-            let request = StatsJobRequest {
-                alignment: alignment.view().clone(),
-                view: StatsView::Raw,
-                chunk_idx,
-                range: self.stats_cache.raw_chunk_range(chunk_idx),
-                method: alignment.consensus_method,
-                generation,
-            };
-            self.raw_stats_jobs.spawn_blocking(move || {
-                let StatsJobRequest {
-                    alignment,
-                    view,
-                    chunk_idx,
-                    range,
-                    method,
-                    generation,
-                } = request;
-                let summaries = alignment
-                    .column_summaries_range(range.clone(), method)
-                    .map_err(|error| error.to_string());
-                StatsJobResult {
-                    generation,
-                    chunk_idx,
-                    view,
-                    summaries,
-                }
-            });
+    /// adds records written to the followed input since the last poll. returns whether any were
+    /// added or following stopped.
+    fn poll_follower(&mut self) -> bool {
+        let (Some(follower), Some(alignment)) = (self.follower.as_mut(), self.alignment.as_ref())
+        else {
+            return false;
+        };
+        let polled = follower.poll(alignment.base().column_count());
+        let added = polled.and_then(|sequences| self.append_rows(sequences));
+        match added {
+            Ok(0) => false,
+            Ok(count) => {
+                self.show_info(format!("Added {count} sequences from the followed file"));
+                true
+            }
+            Err(error) => {
+                warn!(error = ?error, "Stopped following input");
+                self.follower = None;
+                self.show_error(format!("Stopped following: {error:#}"));
+                true
+            }
         }
+    }
 
-        let Some(ctx) = alignment.stats_context(col_range) else {
+    fn append_rows(&mut self, sequences: Vec<libmsa::RawSequence>) -> Result<usize> {
+        let count = sequences.len();
+        if count == 0 {
+            return Ok(0);
+        }
+        self.alignment_mut()?.append_rows(sequences)?;
+        self.on_view_rebuilt();
+        if self.ui.follow_tail {
+            self.ui.viewport.jump_to_sequence(usize::MAX);
+        }
+        Ok(count)
+    }
+
+    fn install_clusters(&mut self, clusters: Vec<Cluster>) -> Result<()> {
+        self.alignment_mut()?.set_clusters(clusters)?;
+        self.on_view_rebuilt();
+        Ok(())
+    }
+
+    fn load_marks(&mut self) {
+        self.ui.marks.marks.clear();
+        let (Some(path), Some(input)) = (&self.ui.marks.path, &self.ui.meta.input_path) else {
             return;
         };
-        if let StatsView::Translated(frame) = ctx.view {
-            for chunk_idx in
-                self.stats_cache
-                    .translated_chunks_to_spawn(&ctx.range, frame, ctx.total_columns)
-            {
-                self.stats_cache.mark_translated_pending(chunk_idx);
-                let request = StatsJobRequest {
-                    alignment: alignment.view().clone(),
-                    view: StatsView::Translated(frame),
-                    chunk_idx,
-                    range: self.stats_cache.translated_chunk_range(chunk_idx),
-                    method: alignment.consensus_method,
-                    generation,
-                };
-                self.translated_stats_jobs.spawn_blocking(move || {
-                    let StatsJobRequest {
-                        alignment,
-                        view,
-                        chunk_idx,
-                        range,
-                        method,
-                        generation,
-                    } = request;
-                    let summaries = alignment
-                        .translated(frame)
-                        .and_then(|translated| {
-                            translated.column_summaries_range(range.clone(), method)
-                        })
-                        .map_err(|error| error.to_string());
-                    StatsJobResult {
-                        generation,
-                        chunk_idx,
-                        view,
-                        summaries,
-                    }
-                });
-            }
+        match marks::load_marks(path, input) {
+            Ok(loaded) => self.ui.marks.marks = loaded,
+            Err(error) => warn!(error = ?error, "Failed to load marks"),
         }
     }
 
-    fn extend_stats_if_needed(&mut self) {
-        self.try_spawn_stats_jobs();
+    fn save_marks(&self) -> Result<()> {
+        let (Some(path), Some(input)) = (&self.ui.marks.path, &self.ui.meta.input_path) else {
+            return Ok(());
+        };
+        marks::save_marks(path, input, &self.ui.marks.marks)
     }
 
-    fn invalidate_all_stats(&mut self) {
+    fn on_view_rebuilt(&mut self) {
+        self.refresh_view_bounds();
+        self.invalidate_all_stats();
+    }
+
+    fn refresh_view_bounds(&mut self) {
+        self.refresh_viewport_bounds();
+        if self.ui.layout_mode == LayoutMode::Wrapped {
+            self.update_viewport_dimensions();
+        }
+        self.move_crosshair(0, 0);
+    }
+
+    /// rebuilds after rows are hidden, shown or filtered. `before` is the stats view from before
+    /// the change; while the columns stay the same, cached column counts are adjusted for the
+    /// rows that left or joined it instead of every chunk being computed again.
+    fn on_rows_changed(&mut self, before: &libmsa::Alignment) {
+        self.refresh_view_bounds();
         let Some(alignment) = self.alignment.as_ref() else {
             return;
         };
+        let after = alignment.stats_view().clone();
+        let method = alignment.consensus_method;
+        if !after.has_same_columns(before) {
+            self.invalidate_all_stats();
+            return;
+        }
+        let before_rows: HashSet<usize> = before.absolute_row_ids().collect();
+        let after_rows: HashSet<usize> = after.absolute_row_ids().collect();
+        let left: Vec<_> = before_rows
+            .difference(&after_rows)
+            .filter_map(|&abs_row| before.project_absolute_row(abs_row))
+            .collect();
+        let joined: Vec<_> = after_rows
+            .difference(&before_rows)
+            .filter_map(|&abs_row| after.project_absolute_row(abs_row))
+            .collect();
+        if left.len() + joined.len() >= after.row_count() {
+            self.invalidate_all_stats();
+            return;
+        }
+
+        self.gap_only_jobs.abort_all();
+        self.difference_jobs.abort_all();
+        if left.is_empty() && joined.is_empty() {
+            self.stats_cache.invalidate_view_scans();
+            self.try_spawn_stats_jobs();
+            return;
+        }
         self.raw_stats_jobs.abort_all();
         self.translated_stats_jobs.abort_all();
-        self.stats_cache
-            .invalidate_all(alignment.view().column_count());
+        let emptied = self.stats_cache.update_raw_rows(|col, counts| {
+            for byte in left.iter().filter_map(|row| row.byte_at(col)) {
+                counts.remove(byte);
+            }
+            for byte in joined.iter().filter_map(|row| row.byte_at(col)) {
+                counts.add(byte);
+            }
+            after.summary_from_counts(col, counts, method)
+        });
+        debug!(
+            left = left.len(),
+            joined = joined.len(),
+            emptied,
+            "Updated column stats for changed rows"
+        );
+        let precomputing = self.precompute_job.is_some();
         self.try_spawn_stats_jobs();
+        if emptied || precomputing {
+            self.restart_precompute();
+        }
     }
 
-    fn invalidate_translated_stats(&mut self) {
+    /// recomputes cached stats for a new consensus method from the counts kept alongside them,
+    /// so only chunks cached without counts are scanned again
+    fn rederive_stats(&mut self) {
+        let Some(alignment) = self.alignment.as_ref() else {
+            return;
+        };
+        let method = alignment.consensus_method;
+        let view = alignment.stats_view();
+        self.raw_stats_jobs.abort_all();
         self.translated_stats_jobs.abort_all();
-        self.stats_cache.invalidate_translated();
+        self.difference_jobs.abort_all();
+        let translated = self
+            .stats_cache
+            .translated_frame()
+            .and_then(|frame| view.translated(frame).ok());
+        if translated.is_none() {
+            self.stats_cache.invalidate_translated();
+        }
+        let emptied = self.stats_cache.rederive(
+            |col, counts| view.summary_from_counts(col, counts, method),
+            |col, counts| {
+                translated
+                    .as_ref()
+                    .map(|translated| translated.summary_from_counts(col, counts, method))
+                    .expect("translated stats are only cached for a translatable view")
+            },
+        );
+        debug!(
+            ?method,
+            emptied, "Re-derived column stats for consensus method"
+        );
+        let precomputing = self.precompute_job.is_some();
         self.try_spawn_stats_jobs();
+        if emptied || precomputing {
+            self.restart_precompute();
+        }
     }
-}
 
-#[cfg(test)]
+    fn move_crosshair(&mut self, rows: isize, cols: isize) {
+        let (Some(alignment), Some(crosshair)) =
+            (self.alignment.as_ref(), self.ui.crosshair.as_mut())
+        else {
+            return;
+        };
+        let max_row = alignment.view().row_count().saturating_sub(1);
+        let max_col = alignment.view().column_count().saturating_sub(1);
+        crosshair.row = crosshair.row.saturating_add_signed(rows).min(max_row);
+        crosshair.column = crosshair.column.saturating_add_signed(cols).min(max_col);
+        self.ui
+            .viewport
+            .scroll_to_reveal(crosshair.row, crosshair.column);
+    }
+
+    /// columns skipped when paging through wrapped blocks
+    fn wrapped_block_width(&self) -> usize {
+        self.app_layout.alignment_content_width().max(1)
+    }
+
+    fn require_selection(&self) -> Result<MouseSelection> {
+        self.ui
+            .selection
+            .ok_or_else(|| format_err!("no selection; drag with the mouse to select a region"))
+    }
+
+    fn orf_issue_report(&self) -> Result<ReportState> {
+        let alignment = self
+            .alignment
+            .as_ref()
+            .ok_or_else(|| format_err!("no alignment is loaded"))?;
+        let (Some(frame), Some(translated)) =
+            (alignment.translation(), alignment.translated_view())
+        else {
+            return Err(format_err!("list-orf-issues requires the translation view"));
+        };
+        let view = alignment.view();
+        let scan = orf::scan_range(&self.ui.viewport.window().col_range, view.column_count());
+
+        let mut entries = Vec::new();
+        let mut affected_rows = 0;
+        for relative_row in 0..view.row_count() {
+            let (Some(sequence), Some(absolute_row)) = (
+                view.sequence(relative_row),
+                view.absolute_row_id(relative_row),
+            ) else {
+                continue;
+            };
+            let Some(translated_sequence) = translated.sequence_by_absolute(absolute_row) else {
+                continue;
+            };
+            let issues = orf::row_issues(sequence, translated_sequence, frame, &scan);
+            affected_rows += usize::from(!issues.is_empty());
+            for issue in issues {
+                entries.push(ReportEntry::jump(
+                    format!("{}: {}", sequence.id(), issue.describe(frame)),
+                    ReportTarget {
+                        abs_row: Some(absolute_row),
+                        column: issue.column(frame),
+                    },
+                ));
+            }
+        }
+
+        if !entries.is_empty() {
+            entries.insert(
+                0,
+                ReportEntry::text(format!(
+                    "{} issues in {affected_rows} sequences",
+                    entries.len()
+                )),
+            );
+        }
+        Ok(ReportState::new(
+            format!("ORF issues in columns {}-{}", scan.start + 1, scan.end),
+            entries,
+        ))
+    }
+
+    fn clear_mouse_selection(&mut self) {
+        self.ui.selection = None;
+        self.mouse_tracker.clear_anchors();
+    }
+
+    fn show_info(&mut self, message: String) {
+        self.ui.notification = Some(Notification {
+            level: NotificationLevel::Info,
+            message,
+        });
+    }
+
+    fn show_error(&mut self, message: String) {
+        self.ui.notify(Notification {
+            level: NotificationLevel::Error,
+            message,
+        });
+    }
+
+    fn refresh_viewport_bounds(&mut self) {
+        let Some(alignment) = self.alignment.as_ref() else {
+            return;
+        };
+        // elided names always fit the pane, so there is nothing to scroll to
+        let name_width = if self.ui.id_truncation.scrolls() {
+            alignment.base().max_id_len()
+        } else {
+            alignment
+                .base()
+                .max_id_len()
+                .min(self.ui.viewport.name_visible_width())
+        };
+        self.ui.viewport.set_bounds(
+            alignment.view().row_count(),
+            alignment.view().column_count(),
+            name_width,
+        );
+    }
+
+    /// highlighted and selected blocks for `generate-report`, or the visible window when there
+    /// are none
+    fn report_regions(&self) -> Vec<ReportRegion> {
+        let mut regions: Vec<ReportRegion> = self
+            .ui
+            .highlights
+            .iter()
+            .chain(&self.ui.selection)
+            .map(|&selection| ReportRegion::from(selection))
+            .collect();
+        if regions.is_empty()
+            && let Some(alignment) = &self.alignment
+        {
+            let window = self.ui.viewport.window();
+            let view = alignment.view();
+            let rows: Vec<usize> = window
+                .row_range
+                .filter_map(|row| view.absolute_row_id(row))
+                .collect();
+            let columns: Vec<usize> = window
+                .col_range
+                .filter_map(|col| view.absolute_column_id(col))
+                .collect();
+            if let (Some(rows), Some(columns)) = (
+                rows.first().zip(rows.last()),
+                columns.first().zip(columns.last()),
+            ) {
+                regions.push(ReportRegion {
+                    rows: *rows.0..rows.1 + 1,
+                    columns: *columns.0..columns.1 + 1,
+                });
+            }
+        }
+        regions
+    }
+
+    /// view, rows and codon frame counted by `composition`. without rows every visible row is
+    /// counted; codons are only counted for nucleotide alignments with translation on.
+    fn composition_input(
+        &mut self,
+        rows: Option<Vec<usize>>,
+    ) -> Result<(
+        libmsa::Alignment,
+        Vec<usize>,
+        Option<libmsa::ReadingFrame>,
+        libmsa::TranslationTable,
+    )> {
+        let alignment = self.alignment_mut()?;
+        let rows = rows.unwrap_or_else(|| {
+            alignment
+                .rows()
+                .reference()
+                .into_iter()
+                .chain(alignment.rows().pinned().iter().copied())
+                .chain(alignment.view().absolute_row_ids())
+                .collect()
+        });
+        let frame = alignment
+            .translation()
+            .filter(|_| alignment.base().active_type().supports_translation());
+        Ok((
+            alignment.view().clone(),
+            rows,
+            frame,
+            alignment.genetic_code().table(),
+        ))
+    }
+
+    fn alignment_mut(&mut self) -> Result<&mut AlignmentModel> {
+        self.alignment
+            .as_mut()
+            .ok_or_else(|| format_err!("no alignment is loaded"))
+    }
+
+    /// refuses inputs estimated to need more than `--max-mem`, warning about ones close to it
+    fn check_memory_limit(&mut self, input: &str) -> Result<()> {
+        let Some(limit) = self.max_memory else {
+            return Ok(());
+        };
+        if let Some(warning) = limit.check(input)? {
+            self.ui.notify(Notification {
+                level: NotificationLevel::Warning,
+                message: warning,
+            });
+        }
+        Ok(())
+    }
+
+    fn start_load_job(&mut self, input: String) {
+        let cache_dir = self.disk_cache.dir.clone();
+        let region = self.region.clone();
+        let path = PathBuf::from(&input);
+        let follow = self.ui.meta.follow
+            && region.is_none()
+            && path.is_file()
+            && !memory::is_compressed(&path);
+        self.spawn_load_job(input.clone(), move |cancel| {
+            // stats of part of the alignment are not cached for the whole of it
+            if let Some(columns) = region {
+                return Ok(LoadedInput {
+                    parsed: parser::parse_fasta_region(&input, columns, cancel)?,
+                    cache_entry: None,
+                    follower: None,
+                });
+            }
+            let (parsed, follower) = if follow {
+                let (parsed, follower) = FileFollower::load(path, cancel)?;
+                (parsed, Some(follower))
+            } else {
+                (parser::parse_alignment_file(&input, cancel)?, None)
+            };
+            let cache_entry = cache_dir.and_then(|dir| {
+                disk_cache::open_entry(&dir, &input, cancel)
+                    .inspect_err(|error| warn!(error = ?error, "Failed to open stats cache entry"))
+                    .ok()
+                    .flatten()
+            });
+            Ok(LoadedInput {
+                parsed,
+                cache_entry,
+                follower,
+            })
+        });
+    }
+
+    /// parses pasted text as an alignment. pasted input has no file behind it, so it is never
+    /// read from or written to the stats cache.
+    fn start_paste_load_job(&mut self, text: String) {
+        self.spawn_load_job(PASTED_INPUT_NAME.to_string(), move |cancel| {
+            Ok(LoadedInput {
+                parsed: parser::parse_alignment_bytes(text.as_bytes(), cancel)?,
+                cache_entry: None,
+                follower: None,
+            })
+        });
+    }
+
+    fn spawn_load_job(
+        &mut self,
+        input: String,
+        parse: impl FnOnce(&CancellationToken) -> Result<LoadedInput> + Send + 'static,
+    ) {
+        if let Some(previous) = self.load_job.take() {
+            debug!("Previous load job found, cancelling");
+            previous.cancel.cancel();
+            previous.handle.abort();
+        }
+
+        debug!(input = %input, "Spawning new load job for input");
+        self.ui.meta.input_path = Some(input);
+        self.ui.meta.loading_state = LoadingState::Loading;
+
+        let cancel = CancellationToken::new();
+        let handle = tokio::task::spawn_blocking(self.workers.wrap({
+            let cancel = cancel.clone();
+            let duplicate_ids = self.duplicate_ids;
+            move || {
+                parse(&cancel)
+                    .and_then(|mut loaded| {
+                        loaded.parsed.resolve_duplicate_ids(duplicate_ids)?;
+                        Ok(loaded)
+                    })
+                    .map_err(|error| error.to_string())
+            }
+        }));
+
+        self.load_job = Some(AsyncJob {
+            handle,
+            cancel,
+            started: Instant::now(),
+        });
+    }
+
+    fn start_export_job(
+        &mut self,
+        label: &'static str,
+        total_columns: usize,
+        run: impl FnOnce(&JobProgress, &CancellationToken) -> Result<String> + Send + 'static,
+    ) {
+        self.cancel_export_job();
+
+        let progress = JobProgress::new(total_columns);
+        let cancel = CancellationToken::new();
+        debug!(label, "Spawning export job");
+        let handle = tokio::task::spawn_blocking(self.workers.wrap({
+            let progress = progress.clone();
+            let cancel = cancel.clone();
+            move || run(&progress, &cancel).map_err(|error| format!("{error:#}"))
+        }));
+
+        self.ui.meta.export_progress = Some((label, 0));
+        self.export_job = Some(ExportJob {
+            label,
+            progress,
+            job: AsyncJob {
+                handle,
+                cancel,
+                started: Instant::now(),
+            },
+        });
+    }
+
+    /// runs an analysis in the background and opens its report when it finishes
+    fn start_analysis_job(
+        &mut self,
+        label: &'static str,
+        run: impl FnOnce(&CancellationToken) -> Result<AnalysisResult> + Send + 'static,
+    ) {
+        self.cancel_analysis_job();
+
+        let cancel = CancellationToken::new();
+        debug!(label, "Spawning analysis job");
+        let handle = tokio::task::spawn_blocking(self.workers.wrap({
+            let cancel = cancel.clone();
+            move || run(&cancel).map_err(|error| format!("{error:#}"))
+        }));
+        self.analysis_job = Some(AsyncJob {
+            handle,
+            cancel,
+            started: Instant::now(),
+        });
+        self.show_info(format!("{label}..."));
+    }
+
+    fn start_append_job(&mut self, input: String) {
+        self.cancel_append_job();
+
+        let cancel = CancellationToken::new();
+        debug!(input = %input, "Spawning append job");
+        self.show_info(format!("Appending {input}..."));
+        let handle = tokio::task::spawn_blocking(self.workers.wrap({
+            let cancel = cancel.clone();
+            let duplicate_ids = self.duplicate_ids;
+            move || {
+                parser::parse_alignment_file(&input, &cancel)
+                    .and_then(|mut parsed| {
+                        parsed.resolve_duplicate_ids(duplicate_ids)?;
+                        Ok((input, parsed.sequences))
+                    })
+                    .map_err(|error| format!("{error:#}"))
+            }
+        }));
+        self.append_job = Some(AsyncJob {
+            handle,
+            cancel,
+            started: Instant::now(),
+        });
+    }
+
+    /// indexes a local BGZF input that has no `.fai` and `.gzi` yet, so later opens can read
+    /// any range of it without inflating the blocks before
+    fn start_index_job(&mut self) {
+        let Some(input) = self.ui.meta.input_path.clone() else {
+            return;
+        };
+        let path = PathBuf::from(&input);
+        if !bgzf::needs_index(&path) {
+            return;
+        }
+        self.cancel_index_job();
+        let cancel = CancellationToken::new();
+        debug!(input = %input, "Spawning BGZF index job");
+        let handle = tokio::task::spawn_blocking(self.workers.wrap({
+            let cancel = cancel.clone();
+            move || {
+                bgzf::build_index(&path, &cancel)
+                    .map(|()| format!("Indexed {input} for random access"))
+                    .map_err(|error| format!("Could not index {input}: {error:#}"))
+            }
+        }));
+        self.index_job = Some(AsyncJob {
+            handle,
+            cancel,
+            started: Instant::now(),
+        });
+    }
+
+    fn cancel_index_job(&mut self) {
+        if let Some(previous) = self.index_job.take() {
+            previous.cancel.cancel();
+            previous.handle.abort();
+        }
+    }
+
+    fn cancel_append_job(&mut self) {
+        if let Some(previous) = self.append_job.take() {
+            previous.cancel.cancel();
+            previous.handle.abort();
+        }
+    }
+
+    /// placeholder values for a `[tools]` command: the selection, or every visible sequence over
+    /// the visible columns when nothing is selected
+    fn tool_context(&self) -> Result<ToolContext> {
+        let alignment = self
+            .alignment
+            .as_ref()
+            .ok_or_else(|| format_err!("no alignment is loaded"))?;
+        let selection = match self.ui.selection {
+            Some(selection) => selection,
+            None => {
+                let window = self.ui.viewport.window();
+                let view = alignment.view();
+                let first = view.absolute_column_id(window.col_range.start);
+                let last = view.absolute_column_id(window.col_range.end.saturating_sub(1));
+                let (column, end_column) = first
+                    .zip(last)
+                    .ok_or_else(|| format_err!("no columns are visible"))?;
+                MouseSelection {
+                    sequence_id: 0,
+                    column,
+                    end_sequence_id: alignment.base().row_count().saturating_sub(1),
+                    end_column,
+                }
+            }
+        };
+        Ok(ToolContext {
+            file: self.ui.meta.input_path.clone(),
+            start: selection.column.min(selection.end_column) + 1,
+            end: selection.column.max(selection.end_column) + 1,
+            selection_fasta: selection::selection_fasta(alignment, selection),
+        })
+    }
+
+    fn start_realign_job(
+        &mut self,
+        aligner: Aligner,
+        rows: Vec<usize>,
+        columns: Range<usize>,
+        blocks: Vec<Vec<u8>>,
+    ) {
+        self.cancel_realign_job();
+
+        let cancel = CancellationToken::new();
+        debug!(%aligner, rows = rows.len(), ?columns, "Spawning realign job");
+        self.show_info(format!(
+            "Realigning {} sequences with {aligner}...",
+            rows.len()
+        ));
+        let handle = tokio::task::spawn_blocking(self.workers.wrap({
+            let cancel = cancel.clone();
+            move || {
+                realign::realign_rows(aligner, &blocks, &cancel)
+                    .map(|aligned| RealignedBlock {
+                        aligner,
+                        rows,
+                        columns,
+                        aligned,
+                    })
+                    .map_err(|error| format!("{error:#}"))
+            }
+        }));
+        self.realign_job = Some(AsyncJob {
+            handle,
+            cancel,
+            started: Instant::now(),
+        });
+    }
+
+    fn cancel_realign_job(&mut self) {
+        if let Some(previous) = self.realign_job.take() {
+            previous.cancel.cancel();
+            previous.handle.abort();
+        }
+    }
+
+    /// swaps the realigned block into the loaded alignment, keeping the old one for
+    /// `restore-alignment`
+    fn replace_realigned_block(&mut self, block: RealignedBlock) -> Result<()> {
+        let model = self.alignment_mut()?.replace_block(
+            &block.rows,
+            block.columns.clone(),
+            &block.aligned,
+        )?;
+        let position = self.ui.viewport.window().col_range.start;
+        let previous = self.alignment.take().expect("alignment checked above");
+        let kept = self.keep_previous_alignment(previous, &model);
+        self.install_alignment(model, position, None);
+        self.ui.meta.unsaved_edits = true;
+        self.show_info(format!(
+            "Realigned {} sequences over columns {}-{} with {}; {}",
+            block.rows.len(),
+            block.columns.start + 1,
+            block.columns.end,
+            block.aligner,
+            restore_hint(kept)
+        ));
+        Ok(())
+    }
+
+    /// concatenates parsed sequences onto the loaded alignment as a new partition
+    fn append_alignment(&mut self, input: &str, sequences: Vec<libmsa::RawSequence>) -> Result<()> {
+        let file_name = |path: &str| {
+            Path::new(path).file_name().map_or_else(
+                || path.to_string(),
+                |name| name.to_string_lossy().into_owned(),
+            )
+        };
+        let base_name = self
+            .ui
+            .meta
+            .input_path
+            .as_deref()
+            .map_or_else(|| "alignment".to_string(), file_name);
+        let name = file_name(input);
+        let model = self
+            .alignment_mut()?
+            .append_alignment(sequences, &name, &base_name)?;
+        let position = self.ui.viewport.window().col_range.start;
+        let partition = model.partitions.len();
+        let columns = model.partitions[partition - 1].columns.len();
+        let previous = self.alignment.take().expect("alignment checked above");
+        let kept = self.keep_previous_alignment(previous, &model);
+        self.install_alignment(model, position, None);
+        self.ui.meta.unsaved_edits = true;
+        self.show_info(format!(
+            "Appended {columns} columns from {name} as partition {partition}; {}",
+            restore_hint(kept)
+        ));
+        Ok(())
+    }
+
+    /// keeps `previous` for `restore-alignment` now that `current` replaces it. the oldest kept
+    /// alignments are dropped past `MAX_PREVIOUS_ALIGNMENTS`, and while they would take memory
+    /// over `--max-mem` beside `current`. returns whether `previous` itself was kept.
+    fn keep_previous_alignment(
+        &mut self,
+        previous: AlignmentModel,
+        current: &AlignmentModel,
+    ) -> bool {
+        self.previous_alignments.push(previous);
+        let excess = self
+            .previous_alignments
+            .len()
+            .saturating_sub(MAX_PREVIOUS_ALIGNMENTS);
+        self.previous_alignments.drain(..excess);
+        if let Some(limit) = self.max_memory {
+            let mut held = model_bytes(current)
+                + self
+                    .previous_alignments
+                    .iter()
+                    .map(model_bytes)
+                    .sum::<u64>();
+            while !limit.allows(held) && !self.previous_alignments.is_empty() {
+                held -= model_bytes(&self.previous_alignments.remove(0));
+            }
+        }
+        debug!(
+            kept = self.previous_alignments.len(),
+            "Alignments kept for restore-alignment"
+        );
+        !self.previous_alignments.is_empty()
+    }
+
+    fn cancel_analysis_job(&mut self) {
+        if let Some(previous) = self.analysis_job.take() {
+            previous.cancel.cancel();
+            previous.handle.abort();
+        }
+    }
+
+    /// computes consensus and conservation for every column of the view in one background job
+    fn start_precompute_job(&mut self) {
+        self.cancel_precompute_job();
+        let Some(alignment) = self.alignment.as_ref() else {
+            return;
+        };
+        let view = alignment.stats_view().clone();
+        let method = alignment.consensus_method;
+        let target = self.stats_cache_target();
+        let cached = target.as_ref().map(|(_, header)| *header);
+        let progress = JobProgress::new(view.column_count());
+        let cancel = CancellationToken::new();
+        debug!(columns = view.column_count(), "Spawning precompute job");
+        let handle = tokio::task::spawn_blocking(self.workers.wrap({
+            let progress = progress.clone();
+            let cancel = cancel.clone();
+            move || {
+                if let Some((path, header)) = &target {
+                    match disk_cache::read_summaries(path, header) {
+                        Ok(Some(summaries)) => {
+                            return Ok(PrecomputedStats {
+                                summaries,
+                                from_disk: true,
+                            });
+                        }
+                        Ok(None) => {}
+                        Err(error) => warn!(error = ?error, "Failed to read cached stats"),
+                    }
+                }
+                let summaries = export::full_column_summaries(&view, method, &progress, &cancel)
+                    .map_err(|error| format!("{error:#}"))?;
+                if let Some((path, header)) = &target
+                    && let Err(error) = disk_cache::write_summaries(path, header, &summaries)
+                {
+                    warn!(error = ?error, "Failed to write cached stats");
+                }
+                Ok(PrecomputedStats {
+                    summaries,
+                    from_disk: false,
+                })
+            }
+        }));
+        self.precompute_job = Some(PrecomputeJob {
+            generation: self.stats_cache.generation,
+            cached,
+            progress,
+            job: AsyncJob {
+                handle,
+                cancel,
+                started: Instant::now(),
+            },
+        });
+    }
+
+    fn cancel_precompute_job(&mut self) {
+        if let Some(previous) = self.precompute_job.take() {
+            debug!("Cancelling precompute job");
+            previous.job.cancel.cancel();
+            previous.job.handle.abort();
+        }
+    }
+
+    /// where the column stats are cached on disk. only stats over every row and column of a
+    /// loaded file with its detected type are cached, and not while the file is followed.
+    fn stats_cache_target(&self) -> Option<(PathBuf, CacheHeader)> {
+        let entry = self.disk_cache.entry.as_ref()?;
+        let alignment = self.alignment.as_ref()?;
+        let view = alignment.stats_view();
+        if view.is_filtered()
+            || self.follower.is_some()
+            || alignment.base().active_type() != alignment.base().detected_type()
+        {
+            return None;
+        }
+        let header = CacheHeader {
+            alignment_type: view.active_type(),
+            consensus_method: alignment.consensus_method,
+            rows: view.row_count(),
+            columns: view.column_count(),
+        };
+        Some((entry.path.clone(), header))
+    }
+
+    fn handle_precompute_result(
+        &mut self,
+        generation: u64,
+        cached: Option<CacheHeader>,
+        join_result: std::result::Result<Result<PrecomputedStats, String>, JoinError>,
+    ) {
+        match join_result {
+            Ok(Ok(PrecomputedStats {
+                summaries,
+                from_disk,
+            })) => {
+                if let (Some(entry), Some(cached)) = (self.disk_cache.entry.as_mut(), cached) {
+                    entry.cached = Some(cached);
+                }
+                let columns = summaries.len();
+                if self.stats_cache.store_full(generation, summaries) {
+                    let verb = if from_disk {
+                        "Loaded cached"
+                    } else {
+                        "Precomputed"
+                    };
+                    self.show_info(format!(
+                        "{verb} stats for {} columns ({} in memory)",
+                        format_thousands(columns),
+                        memory::format_bytes(self.stats_cache.raw_size_bytes() as u64)
+                    ));
+                }
+            }
+            Ok(Err(error)) => self.show_error(error),
+            Err(join_error) => {
+                if !join_error.is_cancelled() {
+                    error!(error = ?join_error, "Precompute task panicked");
+                }
+            }
+        }
+    }
+
+    fn cancel_export_job(&mut self) {
+        if let Some(previous) = self.export_job.take() {
+            debug!(label = previous.label, "Cancelling export job");
+            previous.job.cancel.cancel();
+            previous.job.handle.abort();
+        }
+        self.ui.meta.export_progress = None;
+    }
+
+    /// copies the export job progress into the UI state, returning whether it changed
+    /// picks up directory entries read in the background for the palette's filename completions
+    /// or the file picker
+    fn poll_directory_listing(&mut self) -> bool {
+        match self.ui.overlay.active_overlay.as_mut() {
+            Some(ActiveOverlay::Palette(palette)) => palette.poll_directory_listing(),
+            Some(ActiveOverlay::FilePicker(picker)) => picker.poll_listing(),
+            _ => false,
+        }
+    }
+
+    fn open_file_picker(&mut self) {
+        let dir = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        self.ui.overlay.open_file_picker(FilePickerState::new(dir));
+    }
+
+    fn refresh_export_progress(&mut self) -> bool {
+        let progress = self
+            .export_job
+            .as_ref()
+            .map(|export| (export.label, export.progress.percent()))
+            .or_else(|| {
+                self.precompute_job
+                    .as_ref()
+                    .map(|precompute| ("Precomputing stats", precompute.progress.percent()))
+            });
+        if progress == self.ui.meta.export_progress {
+            return false;
+        }
+        self.ui.meta.export_progress = progress;
+        true
+    }
+
+    fn spawn_update_check(&self, show_up_to_date: bool) {
+        let Some(event_tx) = self.event_tx.clone() else {
+            return;
+        };
+
+        tokio::spawn(async move {
+            let Some(result) = crate::update::check_for_update().await else {
+                return;
+            };
+            match result {
+                UpdateResult::UpdateAvailable(latest) => {
+                    let _ = event_tx.send(AppEvent::UpdateAvailable { latest });
+                }
+                UpdateResult::UpToDate => {
+                    if show_up_to_date {
+                        let _ = event_tx.send(AppEvent::UpToDate);
+                    }
+                }
+            }
+        });
+    }
+
+    fn handle_stats_result(&mut self, join_result: std::result::Result<StatsJobResult, JoinError>) {
+        let Ok(result) = join_result else {
+            return;
+        };
+        let error_message = result.summaries.as_ref().err().cloned();
+        if !self.stats_cache.store(result)
+            && let Some(error_message) = error_message
+        {
+            warn!(error = %error_message, "Stats chunk failed");
+        }
+    }
+
+    fn handle_gap_only_result(
+        &mut self,
+        join_result: std::result::Result<GapOnlyJobResult, JoinError>,
+    ) {
+        let Ok(result) = join_result else {
+            return;
+        };
+        let error_message = result.columns.as_ref().err().cloned();
+        if !self.stats_cache.store_gap_only(result)
+            && let Some(error_message) = error_message
+        {
+            warn!(error = %error_message, "Gap column scan failed");
+        }
+    }
+
+    fn handle_difference_result(
+        &mut self,
+        join_result: std::result::Result<DifferenceJobResult, JoinError>,
+    ) {
+        let Ok(result) = join_result else {
+            return;
+        };
+        let error_message = result.fractions.as_ref().err().cloned();
+        if !self.stats_cache.store_differences(result)
+            && let Some(error_message) = error_message
+        {
+            warn!(error = %error_message, "Consensus difference scan failed");
+        }
+    }
+
+    fn try_spawn_stats_jobs(&mut self) {
+        let Some(alignment) = self.alignment.as_ref() else {
+            return;
+        };
+        if self.stats_cache.start_gap_only_scan() {
+            let view = alignment.view().clone();
+            let generation = self.stats_cache.generation;
+            self.gap_only_jobs
+                .spawn_blocking(self.workers.wrap(move || GapOnlyJobResult {
+                    generation,
+                    columns: scan_gap_only(&view),
+                }));
+        }
+        if matches!(
+            self.ui.overlay.active_overlay,
+            Some(ActiveOverlay::Minimap(_))
+        ) && self.stats_cache.start_difference_scan()
+        {
+            let view = alignment.view().clone();
+            let method = alignment.consensus_method;
+            let generation = self.stats_cache.generation;
+            self.difference_jobs
+                .spawn_blocking(self.workers.wrap(move || DifferenceJobResult {
+                    generation,
+                    fractions: scan_differences(&view, method),
+                }));
+        }
+        let col_range = self.ui.viewport.window().col_range;
+        let generation = self.stats_cache.generation;
+
+        for chunk_idx in self.stats_cache.raw_chunks_to_spawn(&col_range) {
+            self.stats_cache.mark_raw_pending(chunk_idx);
+            let request = StatsJobRequest {
+                alignment: alignment.stats_view().clone(),
+                view: StatsView::Raw,
+                chunk_idx,
+                range: self.stats_cache.raw_chunk_range(chunk_idx),
+                method: alignment.consensus_method,
+                generation,
+            };
+            self.raw_stats_jobs
+                .spawn_blocking(self.workers.wrap(move || {
+                    let StatsJobRequest {
+                        alignment,
+                        view,
+                        chunk_idx,
+                        range,
+                        method,
+                        generation,
+                    } = request;
+                    let counts = alignment
+                        .column_counts_range(range.clone())
+                        .map_err(|error| error.to_string());
+                    let summaries = counts.as_ref().map_err(Clone::clone).map(|counts| {
+                        range
+                            .zip(counts)
+                            .map(|(col, counts)| alignment.summary_from_counts(col, counts, method))
+                            .collect()
+                    });
+                    StatsJobResult {
+                        generation,
+                        chunk_idx,
+                        view,
+                        summaries,
+                        counts: counts.ok(),
+                    }
+                }));
+        }
+
+        let Some(ctx) = alignment.stats_context(col_range) else {
+            return;
+        };
+        if let StatsView::Translated(frame) = ctx.view {
+            let gap_mode = alignment.translation_gap_mode();
+            let table = alignment.genetic_code().table();
+            for chunk_idx in
+                self.stats_cache
+                    .translated_chunks_to_spawn(&ctx.range, frame, ctx.total_columns)
+            {
+                self.stats_cache.mark_translated_pending(chunk_idx);
+                let request = StatsJobRequest {
+                    alignment: alignment.stats_view().clone(),
+                    view: StatsView::Translated(frame),
+                    chunk_idx,
+                    range: self.stats_cache.translated_chunk_range(chunk_idx),
+                    method: alignment.consensus_method,
+                    generation,
+                };
+                self.translated_stats_jobs
+                    .spawn_blocking(self.workers.wrap(move || {
+                        let StatsJobRequest {
+                            alignment,
+                            view,
+                            chunk_idx,
+                            range,
+                            method,
+                            generation,
+                        } = request;
+                        let translated = alignment
+                            .translated_with(frame, table)
+                            .map(|translated| translated.with_gap_mode(gap_mode));
+                        let (summaries, counts) = match translated {
+                            Ok(translated) => {
+                                let counts = translated
+                                    .column_counts_range(range.clone())
+                                    .map_err(|error| error.to_string());
+                                let summaries =
+                                    counts.as_ref().map_err(Clone::clone).map(|counts| {
+                                        range
+                                            .zip(counts)
+                                            .map(|(col, counts)| {
+                                                translated.summary_from_counts(col, counts, method)
+                                            })
+                                            .collect()
+                                    });
+                                (summaries, counts.ok())
+                            }
+                            Err(error) => (Err(error.to_string()), None),
+                        };
+                        StatsJobResult {
+                            generation,
+                            chunk_idx,
+                            view,
+                            summaries,
+                            counts,
+                        }
+                    }));
+            }
+        }
+    }
+
+    fn extend_stats_if_needed(&mut self) {
+        self.try_spawn_stats_jobs();
+    }
+
+    fn invalidate_all_stats(&mut self) {
+        let Some(alignment) = self.alignment.as_ref() else {
+            return;
+        };
+        self.raw_stats_jobs.abort_all();
+        self.translated_stats_jobs.abort_all();
+        self.gap_only_jobs.abort_all();
+        self.difference_jobs.abort_all();
+        self.stats_cache
+            .invalidate_all(alignment.view().column_count());
+        self.try_spawn_stats_jobs();
+        self.restart_precompute();
+    }
+
+    /// a running precompute covers a stale view after the stats are reset; `--precompute` or
+    /// stats cached on disk for the view start a fresh one
+    fn restart_precompute(&mut self) {
+        let cached = self.stats_cache_target().is_some_and(|(_, header)| {
+            self.disk_cache
+                .entry
+                .as_ref()
+                .is_some_and(|entry| entry.cached == Some(header))
+        });
+        if self.precompute_job.is_some() || self.ui.meta.precompute || cached {
+            self.start_precompute_job();
+        }
+    }
+
+    fn invalidate_translated_stats(&mut self) {
+        self.translated_stats_jobs.abort_all();
+        self.stats_cache.invalidate_translated();
+        self.try_spawn_stats_jobs();
+    }
+}
+
+/// sequence scanned by DNA annotation commands: the reference if one is set, otherwise the
+/// consensus, which is computed inside the background job
+#[derive(Debug)]
+enum SequenceSource {
+    Reference {
+        view: libmsa::Alignment,
+        abs_row: usize,
+        id: String,
+        bytes: Vec<u8>,
+    },
+    Consensus {
+        view: libmsa::Alignment,
+        method: libmsa::ConsensusMethod,
+    },
+}
+
+impl SequenceSource {
+    fn from_model(alignment: &AlignmentModel, command: &str) -> Result<Self> {
+        if !alignment.view().active_type().is_nucleotide() {
+            return Err(format_err!("{command} requires a DNA or RNA alignment"));
+        }
+        let view = alignment.view().clone();
+        let reference = alignment.rows().reference().and_then(|abs_row| {
+            let sequence = view.project_absolute_row(abs_row)?;
+            let bytes = (0..sequence.len())
+                .filter_map(|col| sequence.byte_at(col))
+                .collect();
+            Some((abs_row, sequence.id().to_string(), bytes))
+        });
+        Ok(match reference {
+            Some((abs_row, id, bytes)) => Self::Reference {
+                view,
+                abs_row,
+                id,
+                bytes,
+            },
+            None => Self::Consensus {
+                view,
+                method: alignment.consensus_method,
+            },
+        })
+    }
+
+    fn view(&self) -> &libmsa::Alignment {
+        match self {
+            Self::Reference { view, .. } | Self::Consensus { view, .. } => view,
+        }
+    }
+
+    /// the source row (if any), its label and its aligned bytes
+    fn resolve(self, cancel: &CancellationToken) -> Result<(Option<usize>, String, Vec<u8>)> {
+        match self {
+            Self::Reference {
+                abs_row, id, bytes, ..
+            } => Ok((Some(abs_row), id, bytes)),
+            Self::Consensus { view, method } => {
+                let progress = JobProgress::new(view.column_count());
+                let consensus = export::full_consensus(&view, method, &progress, cancel)?;
+                Ok((None, "consensus".to_string(), consensus))
+            }
+        }
+    }
+}
+
+/// warning about sequence IDs that were changed while loading
+fn id_notice(lossy_ids: usize, renamed_ids: usize) -> Option<String> {
+    let mut parts = Vec::new();
+    if lossy_ids > 0 {
+        parts.push(format!(
+            "Sequence IDs not valid UTF-8: {lossy_ids}, shown with replacement characters."
+        ));
+    }
+    if renamed_ids > 0 {
+        parts.push(format!(
+            "Duplicate sequence IDs renamed: {renamed_ids}, highlighted in the name pane."
+        ));
+    }
+    (!parts.is_empty()).then(|| parts.join(" "))
+}
+
+/// builds the alignment, reusing the type the user chose for the file, or else the type detected
+/// when its stats were cached, so that a reopened file is read the same way
+fn load_with_cached_type(
+    sequences: Vec<libmsa::RawSequence>,
+    cache_entry: Option<&CacheEntry>,
+) -> Result<libmsa::Alignment, libmsa::AlignmentError> {
+    let cached_type = cache_entry
+        .and_then(|entry| entry.cached)
+        .filter(|header| {
+            header.rows == sequences.len()
+                && sequences
+                    .first()
+                    .is_some_and(|sequence| sequence.sequence.len() == header.columns)
+        })
+        .map(|header| header.alignment_type);
+    match cache_entry
+        .and_then(|entry| entry.type_override)
+        .or(cached_type)
+    {
+        Some(kind) => libmsa::Alignment::new_with_type(sequences, kind),
+        None => libmsa::Alignment::new(sequences),
+    }
+}
+
+fn restriction_report(
+    view: &libmsa::Alignment,
+    source: &str,
+    abs_row: Option<usize>,
+    enzymes: &[restriction::Enzyme],
+    sites: &[RestrictionSite],
+) -> AnalysisResult {
+    let names: Vec<&str> = enzymes.iter().map(|enzyme| enzyme.name).collect();
+    let mut entries = vec![ReportEntry::text(format!(
+        "{} sites for {}",
+        sites.len(),
+        names.join(", ")
+    ))];
+    entries.extend(sites.iter().map(|site| {
+        ReportEntry::jump(
+            format!(
+                "{} {}-{}",
+                site.enzyme,
+                site.first_col + 1,
+                site.last_col + 1
+            ),
+            ReportTarget {
+                abs_row,
+                column: site.first_col,
+            },
+        )
+    }));
+    let restriction_sites = sites
+        .iter()
+        .filter_map(|site| {
+            Some(RestrictionSite {
+                first_col: view.absolute_column_id(site.first_col)?,
+                last_col: view.absolute_column_id(site.last_col)?,
+                ..*site
+            })
+        })
+        .collect();
+
+    AnalysisResult {
+        report: ReportState::new(format!("Restriction sites in {source}"), entries),
+        highlights: Vec::new(),
+        restriction_sites: Some(restriction_sites),
+        clusters: None,
+        jump: None,
+        pager: None,
+    }
+}
+
+fn cluster_report(
+    view: &libmsa::Alignment,
+    percent: f32,
+    row_count: usize,
+    clusters: &[Cluster],
+) -> ReportState {
+    let collapsed: usize = clusters.iter().map(|cluster| cluster.members.len()).sum();
+    let mut entries = vec![ReportEntry::text(format!(
+        "{} clusters collapse {collapsed} of {row_count} sequences",
+        clusters.len()
+    ))];
+    entries.extend(clusters.iter().filter_map(|cluster| {
+        let sequence = view.sequence_by_absolute(cluster.representative)?;
+        Some(ReportEntry::jump(
+            format!("{}{}", cluster.badge(), sequence.id()),
+            ReportTarget {
+                abs_row: Some(cluster.representative),
+                column: 0,
+            },
+        ))
+    }));
+    ReportState::new(format!("Clusters at {percent}% identity"), entries)
+}
+
+fn indel_group_report(
+    view: &libmsa::Alignment,
+    visible_only: bool,
+    row_count: usize,
+    clusters: &[Cluster],
+) -> ReportState {
+    let collapsed: usize = clusters.iter().map(|cluster| cluster.members.len()).sum();
+    let mut entries = vec![ReportEntry::text(format!(
+        "{} indel groups collapse {collapsed} of {row_count} sequences",
+        clusters.len()
+    ))];
+    entries.extend(clusters.iter().filter_map(|cluster| {
+        let sequence = view.sequence_by_absolute(cluster.representative)?;
+        Some(ReportEntry::jump(
+            format!("{}{}", cluster.badge(), sequence.id()),
+            ReportTarget {
+                abs_row: Some(cluster.representative),
+                column: 0,
+            },
+        ))
+    }));
+    let scope = if visible_only {
+        "visible columns"
+    } else {
+        "whole alignment"
+    };
+    ReportState::new(format!("Indel groups ({scope})"), entries)
+}
+
+fn variable_columns_report(columns: &[variability::VariableColumn]) -> ReportState {
+    let mut entries = vec![ReportEntry::text(format!(
+        "{} most variable columns by entropy",
+        columns.len()
+    ))];
+    entries.extend(columns.iter().map(|column| {
+        ReportEntry::jump(
+            column.describe(),
+            ReportTarget {
+                abs_row: None,
+                column: column.column,
+            },
+        )
+    }));
+    ReportState::new("Variable columns", entries)
+}
+
+fn orf_report(
+    source: &str,
+    abs_row: Option<usize>,
+    min_codons: usize,
+    orfs: &[orf::OpenReadingFrame],
+) -> ReportState {
+    let mut entries = vec![ReportEntry::text(format!(
+        "{} ORFs of at least {min_codons} codons across 6 frames",
+        orfs.len()
+    ))];
+    entries.extend(orfs.iter().map(|orf| {
+        ReportEntry::jump(
+            orf.describe(),
+            ReportTarget {
+                abs_row,
+                column: orf.first_col,
+            },
+        )
+    }));
+    ReportState::new(format!("ORFs in {source}"), entries)
+}
+
+fn primer_report(
+    view: &libmsa::Alignment,
+    primer: &Primer,
+    max_mismatches: usize,
+    rows: &[(usize, String, Vec<u8>)],
+    hits: &[Option<PrimerHit>],
+) -> AnalysisResult {
+    let mut matched: Vec<(usize, &str, PrimerHit)> = rows
+        .iter()
+        .zip(hits)
+        .filter_map(|((abs_row, id, _), hit)| Some((*abs_row, id.as_str(), (*hit)?)))
+        .collect();
+    matched.sort_by_key(|(_, _, hit)| hit.mismatches);
+
+    let mut entries = vec![ReportEntry::text(format!(
+        "{} of {} sequences bind with at most {max_mismatches} mismatches",
+        matched.len(),
+        rows.len()
+    ))];
+    entries.extend(matched.iter().map(|&(abs_row, id, hit)| {
+        ReportEntry::jump(
+            format!(
+                "{id}: {} {}-{}, {} mismatches",
+                hit.strand.symbol(),
+                hit.first_col + 1,
+                hit.last_col + 1,
+                hit.mismatches
+            ),
+            ReportTarget {
+                abs_row: Some(abs_row),
+                column: hit.first_col,
+            },
+        )
+    }));
+    entries.extend(
+        rows.iter()
+            .zip(hits)
+            .filter(|(_, hit)| hit.is_none())
+            .map(|((_, id, _), _)| ReportEntry::text(format!("{id}: no binding site"))),
+    );
+
+    let highlights = matched
+        .iter()
+        .filter_map(|&(abs_row, _, hit)| {
+            Some(MouseSelection {
+                sequence_id: abs_row,
+                column: view.absolute_column_id(hit.first_col)?,
+                end_sequence_id: abs_row,
+                end_column: view.absolute_column_id(hit.last_col)?,
+            })
+        })
+        .collect();
+
+    AnalysisResult {
+        report: ReportState::new(
+            format!("Primer {} ({} nt)", primer.sequence(), primer.len()),
+            entries,
+        ),
+        highlights,
+        restriction_sites: None,
+        clusters: None,
+        jump: None,
+        pager: None,
+    }
+}
+
+fn similarity_report(
+    view: &libmsa::Alignment,
+    query: &Query,
+    rows: &[(usize, String, Vec<u8>)],
+    hits: &[Option<SimilarityHit>],
+) -> AnalysisResult {
+    let mut matched: Vec<(usize, &str, SimilarityHit)> = rows
+        .iter()
+        .zip(hits)
+        .filter_map(|((abs_row, id, _), hit)| Some((*abs_row, id.as_str(), (*hit)?)))
+        .collect();
+    matched.sort_by(|(_, _, left), (_, _, right)| {
+        right
+            .identity
+            .total_cmp(&left.identity)
+            .then(right.shared_words.cmp(&left.shared_words))
+    });
+    let listed = &matched[..matched.len().min(BLAST_LITE_TOP_HITS)];
+    let target = |abs_row, hit: &SimilarityHit| ReportTarget {
+        abs_row: Some(abs_row),
+        column: hit.first_col,
+    };
+
+    let mut entries = vec![ReportEntry::text(format!(
+        "{} of {} sequences share a {}-residue word with the query",
+        matched.len(),
+        rows.len(),
+        query.word_len()
+    ))];
+    entries.extend(listed.iter().map(|(abs_row, id, hit)| {
+        ReportEntry::jump(
+            format!(
+                "{id}: {:.1}% identity, {} {}-{}, {} shared words",
+                hit.identity * 100.0,
+                hit.strand.symbol(),
+                hit.first_col + 1,
+                hit.last_col + 1,
+                hit.shared_words
+            ),
+            target(*abs_row, hit),
+        )
+    }));
+    if matched.len() > listed.len() {
+        entries.push(ReportEntry::text(format!(
+            "{} more hits not shown",
+            matched.len() - listed.len()
+        )));
+    }
+
+    let highlights = listed
+        .iter()
+        .filter_map(|&(abs_row, _, hit)| {
+            Some(MouseSelection {
+                sequence_id: abs_row,
+                column: view.absolute_column_id(hit.first_col)?,
+                end_sequence_id: abs_row,
+                end_column: view.absolute_column_id(hit.last_col)?,
+            })
+        })
+        .collect();
+
+    AnalysisResult {
+        report: ReportState::new(
+            format!(
+                "Similar to {} ({} residues)",
+                truncate_label(&query.sequence(), 20),
+                query.len()
+            ),
+            entries,
+        ),
+        highlights,
+        restriction_sites: None,
+        clusters: None,
+        jump: listed
+            .first()
+            .map(|(abs_row, _, hit)| target(*abs_row, hit)),
+        pager: None,
+    }
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::theme::{HIGH_CONTRAST_SEQUENCE, SequencePaletteId, TOKYO_NIGHT, ThemeId};
+    use crate::ui::sequence_id_pane::IdTruncation;
     use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEventKind};
 
-    use crate::ui::ui_state::MouseSelection;
+    fn raw(id: &str, sequence: &[u8]) -> libmsa::RawSequence {
+        libmsa::RawSequence {
+            id: id.to_string(),
+            sequence: sequence.to_vec(),
+            quality: None,
+        }
+    }
+
+    /// a command as it runs once its confirmation prompt is accepted
+    fn confirmed(command: Command) -> Command {
+        Command::Confirmed(Box::new(command))
+    }
+
+    fn app_with_alignment(sequences: Vec<libmsa::RawSequence>) -> App {
+        let startup = StartupState {
+            file_path: None,
+            initial_position: 0,
+            ..StartupState::default()
+        };
+        let mut app = App::new(startup);
+        let alignment = libmsa::Alignment::new(sequences).expect("alignment should load");
+        let model = AlignmentModel::new(alignment).expect("alignment model should build");
+        app.stats_cache.init(model.view().column_count());
+        app.alignment = Some(model);
+        app.ui.meta.loading_state = LoadingState::Loaded;
+        app.refresh_viewport_bounds();
+        app.update_layout(Rect::new(0, 0, 40, 12));
+        app
+    }
+
+    fn left_mouse_event(
+        kind: MouseEventKind,
+        area: Rect,
+        column_offset: u16,
+        row_offset: u16,
+    ) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column: area.x + column_offset,
+            row: area.y + row_offset,
+            modifiers: KeyModifiers::empty(),
+        }
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn set_fps_rejects_out_of_range_rates() {
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGT")]);
+
+        assert!(app.execute_command(Command::SetFps(0)).is_err());
+        assert!(app.execute_command(Command::SetFps(30)).is_ok());
+        assert_eq!(app.frame_period(), Duration::from_secs_f32(1.0 / 30.0));
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn wrapped_layout_spans_every_block_and_pages_by_block() {
+        let sequence = vec![b'A'; 400];
+        let mut app = app_with_alignment(vec![raw("row1", &sequence), raw("row2", &sequence)]);
+        app.update_layout(Rect::new(0, 0, 40, 30));
+        let block_width = app.app_layout.alignment_content_width();
+        let wrapped = app.app_layout.wrapped(0, 2);
+
+        app.execute_commands([Command::SetLayoutMode(LayoutMode::Wrapped)]);
+
+        let window = app.ui.viewport.window();
+        assert!(wrapped.block_count > 1);
+        assert_eq!(window.col_range.len(), block_width * wrapped.block_count);
+        assert_eq!(window.row_range, 0..2);
+
+        app.execute_commands([Command::ScrollDown { amount: 1 }]);
+
+        assert_eq!(app.ui.viewport.window().col_range.start, block_width);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn id_pane_resizes_and_elided_names_stop_scrolling() {
+        let long_id = "a_very_long_sequence_identifier_indeed";
+        let mut app = app_with_alignment(vec![raw(long_id, b"ACGT"), raw("row2", b"ACGT")]);
+        app.update_layout(Rect::new(0, 0, 100, 30));
+
+        app.execute_commands([Command::SetIdPaneWidth(IdPaneWidth::Columns(30))]);
+        assert_eq!(app.app_layout.sequence_id_pane.width, 30);
+        app.execute_commands([Command::ResizeIdPane(-4)]);
+        assert_eq!(app.app_layout.sequence_id_pane.width, 26);
+        let visible = app.ui.viewport.name_visible_width();
+        assert!(visible < long_id.len());
+
+        app.execute_commands([Command::ScrollNamesRight { amount: 5 }]);
+        assert_eq!(app.ui.viewport.window().name_range.start, 5);
+
+        app.execute_commands([Command::SetIdTruncation(IdTruncation::Middle)]);
+        app.execute_commands([Command::ScrollNamesRight { amount: 5 }]);
+        assert_eq!(app.ui.viewport.window().name_range, 0..visible);
+    }
+
+    #[test]
+    fn translated_click_selects_a_full_codon_span() {
+        let mut app =
+            app_with_alignment(vec![raw("row1", b"ATGAAATTT"), raw("row2", b"ATGAAATTT")]);
+        app.alignment
+            .as_mut()
+            .unwrap()
+            .set_translation_frame(libmsa::ReadingFrame::Frame1)
+            .expect("setting translation frame should succeed");
+        app.alignment
+            .as_mut()
+            .unwrap()
+            .toggle_translation_view()
+            .expect("translation should enable");
+
+        let area = app.app_layout.alignment_pane_sequence_rows;
+        app.handle_mouse_event(left_mouse_event(
+            MouseEventKind::Down(MouseButton::Left),
+            area,
+            1,
+            0,
+        ));
+
+        let selection = app.ui.selection.expect("selection should be created");
+        assert_eq!(selection.sequence_id, 0);
+        assert_eq!(selection.column, 0);
+        assert_eq!(selection.end_column, 2);
+    }
+
+    #[test]
+    fn translated_drag_extends_selection_in_whole_codons() {
+        let mut app =
+            app_with_alignment(vec![raw("row1", b"ATGAAATTT"), raw("row2", b"ATGAAATTT")]);
+        app.alignment
+            .as_mut()
+            .unwrap()
+            .set_translation_frame(libmsa::ReadingFrame::Frame1)
+            .expect("setting translation frame should succeed");
+        app.alignment
+            .as_mut()
+            .unwrap()
+            .toggle_translation_view()
+            .expect("translation should enable");
+
+        let area = app.app_layout.alignment_pane_sequence_rows;
+        app.handle_mouse_event(left_mouse_event(
+            MouseEventKind::Down(MouseButton::Left),
+            area,
+            1,
+            0,
+        ));
+        app.handle_mouse_event(left_mouse_event(
+            MouseEventKind::Drag(MouseButton::Left),
+            area,
+            7,
+            0,
+        ));
+        app.handle_mouse_event(left_mouse_event(
+            MouseEventKind::Up(MouseButton::Left),
+            area,
+            7,
+            0,
+        ));
+
+        let selection = app.ui.selection.expect("selection should be created");
+        assert_eq!(selection.sequence_id, 0);
+        assert_eq!(selection.column, 0);
+        assert_eq!(selection.end_sequence_id, 0);
+        assert_eq!(selection.end_column, 8);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn toggling_translation_preserves_the_stored_nucleotide_selection() {
+        let mut app =
+            app_with_alignment(vec![raw("row1", b"ATGAAATTT"), raw("row2", b"ATGAAATTT")]);
+        let selection = MouseSelection {
+            sequence_id: 0,
+            column: 4,
+            end_sequence_id: 0,
+            end_column: 4,
+        };
+        app.ui.selection = Some(selection);
+
+        app.execute_commands([Command::ToggleTranslationView]);
+        assert_eq!(app.ui.selection, Some(selection));
+
+        app.execute_commands([Command::ToggleTranslationView]);
+        assert_eq!(app.ui.selection, Some(selection));
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn filter_gaps_shows_notification_when_translation_is_active() {
+        let mut app =
+            app_with_alignment(vec![raw("row1", b"ATGAAATTT"), raw("row2", b"ATGAAATTT")]);
+        app.execute_commands([Command::ToggleTranslationView]);
+        app.execute_commands([Command::SetGapFilter(Some(0.25))]);
+
+        let notification = app
+            .ui
+            .notification
+            .as_ref()
+            .expect("notification should be created");
+        assert_eq!(
+            notification.message,
+            "filter-gaps is unavailable while translation is active"
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn translation_shows_notification_when_gap_filter_is_active() {
+        let mut app = app_with_alignment(vec![raw("row1", b"ATG---"), raw("row2", b"ATG---")]);
+        app.execute_commands([Command::SetGapFilter(Some(0.0))]);
+        app.execute_commands([Command::ToggleTranslationView]);
+
+        let notification = app
+            .ui
+            .notification
+            .as_ref()
+            .expect("notification should be created");
+        assert_eq!(
+            notification.message,
+            "translation is unavailable while filter-gaps is active"
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn cancelling_an_export_clears_its_progress() {
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGT")]);
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let path = dir.path().join("conservation.csv");
+
+        app.execute_commands([Command::ExportConservation {
+            path: path.display().to_string(),
+            format: None,
+        }]);
+        assert_eq!(
+            app.ui.meta.export_progress,
+            Some(("Exporting conservation", 0))
+        );
+
+        app.execute_commands([Command::CancelExport]);
+
+        assert!(app.export_job.is_none());
+        assert_eq!(app.ui.meta.export_progress, None);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn palette_commands_keep_the_overlays_they_open() {
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGT")]);
+
+        app.ui.overlay.open_palette(CommandPaletteState::empty());
+        app.execute_commands([Command::JumpToPosition(1), Command::CloseOverlay]);
+        assert!(app.ui.overlay.active_overlay.is_none());
+
+        app.ui.overlay.open_palette(CommandPaletteState::empty());
+        app.execute_commands([Command::OpenPasteBuffer, Command::CloseOverlay]);
+        assert!(matches!(
+            app.ui.overlay.active_overlay,
+            Some(ActiveOverlay::Paste(_))
+        ));
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn exporting_over_an_existing_file_waits_for_confirmation() {
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGT")]);
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let path = dir.path().join("conservation.csv");
+        std::fs::write(&path, "old").expect("existing file should be written");
+        let export = Command::ExportConservation {
+            path: path.display().to_string(),
+            format: None,
+        };
+
+        app.execute_commands([export.clone()]);
+        assert!(matches!(
+            app.ui.overlay.active_overlay,
+            Some(ActiveOverlay::Confirm(_))
+        ));
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+        assert!(app.ui.overlay.active_overlay.is_none());
+        assert!(app.export_job.is_none());
+
+        app.execute_commands([export]);
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+        assert_eq!(
+            app.ui.meta.export_progress,
+            Some(("Exporting conservation", 0))
+        );
+        app.execute_commands([Command::CancelExport]);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn generating_a_report_runs_as_an_export_job() {
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGT"), raw("row2", b"ACTT")]);
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let path = dir.path().join("report.html");
+
+        app.execute_commands([Command::GenerateReport {
+            path: path.display().to_string(),
+        }]);
+
+        assert_eq!(app.ui.meta.export_progress, Some(("Generating report", 0)));
+        app.execute_commands([Command::CancelExport]);
+        assert!(app.export_job.is_none());
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn precompute_stats_shows_progress_until_cancelled() {
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGT"), raw("row2", b"ACTT")]);
+
+        app.execute_commands([Command::PrecomputeStats]);
+
+        assert!(app.precompute_job.is_some());
+        app.refresh_export_progress();
+        assert!(matches!(
+            app.ui.meta.export_progress,
+            Some(("Precomputing stats", _))
+        ));
+        app.execute_commands([Command::CancelPrecompute]);
+        assert!(app.precompute_job.is_none());
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn clear_cache_removes_cached_stats_files() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        std::fs::write(dir.path().join("0123.stats"), b"stats")
+            .expect("cache file should be written");
+        std::fs::write(dir.path().join("notes.txt"), b"keep")
+            .expect("other file should be written");
+        let mut app = App::new(StartupState {
+            cache_dir: Some(dir.path().to_path_buf()),
+            ..StartupState::default()
+        });
+
+        app.execute_commands([Command::ClearCache]);
+
+        assert!(!dir.path().join("0123.stats").exists());
+        assert!(dir.path().join("notes.txt").exists());
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn set_sequence_type_is_remembered_for_the_file() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGT")]);
+        app.disk_cache.entry = Some(CacheEntry {
+            path: dir.path().join("0123.stats"),
+            cached: None,
+            type_override: None,
+        });
+
+        app.execute_commands([Command::SetActiveType(libmsa::AlignmentType::Protein)]);
+
+        let entry = app.disk_cache.entry.clone().expect("entry stays set");
+        assert_eq!(entry.type_override, Some(libmsa::AlignmentType::Protein));
+        assert!(dir.path().join("0123.type").exists());
+        let reloaded = load_with_cached_type(vec![raw("row1", b"ACGT")], Some(&entry))
+            .expect("alignment should load");
+        assert_eq!(reloaded.active_type(), libmsa::AlignmentType::Protein);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn hiding_a_sequence_updates_cached_stats_in_place() {
+        let mut app = app_with_alignment(vec![
+            raw("row1", b"AC"),
+            raw("row2", b"AC"),
+            raw("row3", b"GC"),
+        ]);
+        let view = app.alignment.as_ref().unwrap().stats_view().clone();
+        app.stats_cache.mark_raw_pending(0);
+        app.stats_cache.store(StatsJobResult {
+            generation: app.stats_cache.generation,
+            chunk_idx: 0,
+            view: StatsView::Raw,
+            summaries: view
+                .column_summaries_range(0..2, libmsa::ConsensusMethod::default())
+                .map_err(|error| error.to_string()),
+            counts: view.column_counts_range(0..2).ok(),
+        });
+
+        app.execute_commands([confirmed(Command::HideSequence(2))]);
+
+        assert_eq!(app.stats_cache.raw_coverage(), (1, 1));
+        let summary = app
+            .stats_cache
+            .raw_summary_at(0)
+            .expect("stats stay cached");
+        assert_eq!(summary.consensus, Some(b'A'));
+        assert!((summary.consensus_support - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn changing_consensus_method_rederives_cached_stats() {
+        let mut app = app_with_alignment(vec![
+            raw("row1", b"AC"),
+            raw("row2", b"-C"),
+            raw("row3", b"-C"),
+        ]);
+        let view = app.alignment.as_ref().unwrap().stats_view().clone();
+        app.stats_cache.mark_raw_pending(0);
+        app.stats_cache.store(StatsJobResult {
+            generation: app.stats_cache.generation,
+            chunk_idx: 0,
+            view: StatsView::Raw,
+            summaries: view
+                .column_summaries_range(0..2, libmsa::ConsensusMethod::Majority)
+                .map_err(|error| error.to_string()),
+            counts: view.column_counts_range(0..2).ok(),
+        });
+
+        app.execute_commands([
+            Command::SetConsensusMethod(libmsa::ConsensusMethod::Majority),
+            Command::SetConsensusMethod(libmsa::ConsensusMethod::MajorityNonGap),
+        ]);
+
+        assert_eq!(app.stats_cache.raw_coverage(), (1, 1));
+        assert_eq!(
+            app.stats_cache
+                .raw_summary_at(0)
+                .and_then(|summary| summary.consensus),
+            Some(b'A')
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn show_last_error_reopens_the_most_recent_error() {
+        let mut app = app_with_alignment(vec![raw("row1", b"AC")]);
+        app.execute_commands([Command::ShowLastError]);
+        assert_eq!(
+            app.ui.notification.as_ref().unwrap().message,
+            "No errors to show"
+        );
+
+        app.execute_commands([Command::SetFps(0)]);
+        let error = app.ui.notification.take().unwrap().message;
+        app.execute_commands([Command::SetFps(30), Command::ShowLastError]);
+
+        let notification = app.ui.notification.as_ref().unwrap();
+        assert_eq!(notification.level, NotificationLevel::Error);
+        assert_eq!(notification.message, error);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn locators_restore_and_describe_the_view() {
+        let sequence = [b'A'; 60];
+        let mut app = app_with_alignment(vec![
+            raw("alpha", &sequence),
+            raw("beta", &sequence),
+            raw("gamma", &sequence),
+        ]);
+        app.ui.meta.input_path = Some("missing/cov.fasta".to_string());
+
+        app.apply_locator(&Locator {
+            input: "missing/cov.fasta".to_string(),
+            position: Some(11),
+            row: None,
+            reference: Some("gamma".to_string()),
+            filter: Some("^(alpha|gamma)$".to_string()),
+        });
+
+        let locator = app.current_locator().expect("an alignment is loaded");
+        assert_eq!(locator.input, "missing/cov.fasta");
+        assert_eq!(locator.position, Some(11));
+        assert_eq!(locator.reference.as_deref(), Some("gamma"));
+        assert_eq!(locator.filter.as_deref(), Some("^(alpha|gamma)$"));
+
+        app.apply_locator(&Locator {
+            reference: Some("delta".to_string()),
+            ..locator
+        });
+        assert_eq!(
+            app.ui.notification.as_ref().unwrap().message,
+            "sequence delta in the locator was not found"
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn back_and_forward_revisit_jump_positions() {
+        let bases = b"ACGT".repeat(50);
+        let mut app = app_with_alignment(vec![raw("row1", &bases), raw("row2", &bases)]);
+        let column = |app: &App| app.ui.viewport.window().col_range.start;
+
+        app.execute_commands([Command::JumpToPosition(100)]);
+        app.execute_commands([Command::JumpToEnd]);
+        let end = column(&app);
+        assert!(end > 100);
+
+        app.execute_commands([Command::JumpBack]);
+        assert_eq!(column(&app), 100);
+        app.execute_commands([Command::JumpBack]);
+        assert_eq!(column(&app), 0);
+        app.execute_commands([Command::JumpBack]);
+        assert_eq!(
+            app.ui.notification.as_ref().unwrap().message,
+            "no earlier jump position"
+        );
+        app.execute_commands([Command::JumpForward, Command::JumpForward]);
+        assert_eq!(column(&app), end);
+
+        app.execute_commands([Command::ScrollLeft { amount: 3 }, Command::JumpBack]);
+        assert_eq!(column(&app), 100);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn next_gap_column_jumps_to_gap_only_columns() {
+        let mut app = app_with_alignment(vec![raw("row1", b"A-C-G"), raw("row2", b"A-T-G")]);
+        app.execute_commands([Command::ToggleCrosshair]);
+        app.stats_cache.start_gap_only_scan();
+        let columns = scan_gap_only(app.alignment.as_ref().unwrap().view());
+        app.stats_cache.store_gap_only(GapOnlyJobResult {
+            generation: app.stats_cache.generation,
+            columns,
+        });
+
+        app.execute_commands([Command::JumpToNextGapColumn]);
+        assert_eq!(app.ui.crosshair.as_ref().unwrap().column, 1);
+        app.execute_commands([Command::JumpToNextGapColumn]);
+        assert_eq!(app.ui.crosshair.as_ref().unwrap().column, 3);
+        app.execute_commands([Command::JumpToNextGapColumn]);
+
+        assert_eq!(app.ui.crosshair.as_ref().unwrap().column, 3);
+        assert_eq!(
+            app.ui.notification.as_ref().unwrap().message,
+            "No gap-only columns after the current position"
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn pin_matching_caps_large_matches_and_unpin_all_clears_them() {
+        let sequences = (0..PIN_MATCHING_LIMIT + 5)
+            .map(|index| raw(&format!("seq{index}"), b"ACGT"))
+            .collect();
+        let mut app = app_with_alignment(sequences);
+
+        app.execute_commands([Command::PinMatching("^seq".to_string())]);
+
+        let pinned = app.alignment.as_ref().unwrap().rows().pinned().to_vec();
+        assert_eq!(pinned, (0..PIN_MATCHING_LIMIT).collect::<Vec<_>>());
+        assert_eq!(
+            app.ui.notification.as_ref().unwrap().message,
+            format!(
+                "Pinned the first {PIN_MATCHING_LIMIT} of {} matching sequences",
+                PIN_MATCHING_LIMIT + 5
+            )
+        );
+
+        app.execute_commands([Command::UnpinAll]);
+
+        assert!(app.alignment.as_ref().unwrap().rows().pinned().is_empty());
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn orf_issue_report_lists_stops_and_frameshifts() {
+        let mut app = app_with_alignment(vec![
+            raw("clean", b"ATGAAACCCTAA"),
+            raw("broken", b"ATGTAAC-CTAA"),
+        ]);
+
+        app.execute_commands([Command::ListOrfIssues]);
+        assert_eq!(
+            app.ui.notification.as_ref().unwrap().message,
+            "list-orf-issues requires the translation view"
+        );
+
+        app.execute_commands([Command::ToggleTranslationView]);
+        let report = app
+            .orf_issue_report()
+            .expect("translated report should build");
+
+        assert_eq!(
+            report,
+            ReportState::new(
+                "ORF issues in columns 1-12",
+                vec![
+                    ReportEntry::text("2 issues in 1 sequences"),
+                    ReportEntry::jump(
+                        "broken: premature stop codon at 4",
+                        ReportTarget {
+                            abs_row: Some(1),
+                            column: 3,
+                        },
+                    ),
+                    ReportEntry::jump(
+                        "broken: frameshift: 1 bp gap at 8",
+                        ReportTarget {
+                            abs_row: Some(1),
+                            column: 7,
+                        },
+                    ),
+                ],
+            )
+        );
+    }
+
+    #[test]
+    fn primer_report_lists_hits_by_mismatches_and_highlights_them() {
+        let view = libmsa::Alignment::new(vec![
+            raw("exact", b"ACGTAA"),
+            raw("close", b"ACCTAA"),
+            raw("none", b"TTTTTT"),
+        ])
+        .expect("alignment should load");
+        let primer = Primer::parse("ACGT").expect("primer should parse");
+        let rows: Vec<(usize, String, Vec<u8>)> = (0..3)
+            .map(|row| {
+                let sequence = view.sequence(row).unwrap();
+                let bytes = (0..sequence.len())
+                    .filter_map(|col| sequence.byte_at(col))
+                    .collect();
+                (row, sequence.id().to_string(), bytes)
+            })
+            .collect();
+        let hits = primer::search_rows(&rows, &primer, 1, &CancellationToken::new()).unwrap();
 
-    fn raw(id: &str, sequence: &[u8]) -> libmsa::RawSequence {
-        libmsa::RawSequence {
-            id: id.to_string(),
-            sequence: sequence.to_vec(),
-        }
+        let result = primer_report(&view, &primer, 1, &rows, &hits);
+
+        assert_eq!(
+            result.report,
+            ReportState::new(
+                "Primer ACGT (4 nt)",
+                vec![
+                    ReportEntry::text("2 of 3 sequences bind with at most 1 mismatches"),
+                    ReportEntry::jump(
+                        "exact: + 1-4, 0 mismatches",
+                        ReportTarget {
+                            abs_row: Some(0),
+                            column: 0,
+                        },
+                    ),
+                    ReportEntry::jump(
+                        "close: + 1-4, 1 mismatches",
+                        ReportTarget {
+                            abs_row: Some(1),
+                            column: 0,
+                        },
+                    ),
+                    ReportEntry::text("none: no binding site"),
+                ],
+            )
+        );
+        assert_eq!(
+            result.highlights[1],
+            MouseSelection {
+                sequence_id: 1,
+                column: 0,
+                end_sequence_id: 1,
+                end_column: 3,
+            }
+        );
     }
 
-    fn app_with_alignment(sequences: Vec<libmsa::RawSequence>) -> App {
-        let startup = StartupState {
-            file_path: None,
-            initial_position: 0,
-        };
-        let mut app = App::new(startup);
-        let alignment = libmsa::Alignment::new(sequences).expect("alignment should load");
-        let model = AlignmentModel::new(alignment).expect("alignment model should build");
-        app.stats_cache.init(model.view().column_count());
-        app.alignment = Some(model);
-        app.ui.meta.loading_state = LoadingState::Loaded;
-        app.refresh_viewport_bounds();
-        app.update_layout(Rect::new(0, 0, 40, 12));
-        app
+    #[test]
+    fn similarity_report_ranks_hits_and_jumps_to_the_best() {
+        let view = libmsa::Alignment::new(vec![
+            raw("partial", b"ACGTACGTTTTTTTTT"),
+            raw("unrelated", b"CCCCCCCCCCCCCCCC"),
+            raw("exact", b"GGACGTACGTACGTGG"),
+        ])
+        .expect("alignment should load");
+        let query = Query::parse("ACGTACGTACGT", true).expect("query should parse");
+        let rows: Vec<(usize, String, Vec<u8>)> = (0..3)
+            .map(|row| {
+                let sequence = view.sequence(row).unwrap();
+                let bytes = (0..sequence.len())
+                    .filter_map(|col| sequence.byte_at(col))
+                    .collect();
+                (row, sequence.id().to_string(), bytes)
+            })
+            .collect();
+        let hits = similarity::search_rows(&rows, &query, &CancellationToken::new()).unwrap();
+
+        let result = similarity_report(&view, &query, &rows, &hits);
+
+        assert_eq!(
+            result.report,
+            ReportState::new(
+                "Similar to ACGTACGTACGT (12 residues)",
+                vec![
+                    ReportEntry::text("2 of 3 sequences share a 8-residue word with the query"),
+                    ReportEntry::jump(
+                        "exact: 100.0% identity, + 3-14, 5 shared words",
+                        ReportTarget {
+                            abs_row: Some(2),
+                            column: 2,
+                        },
+                    ),
+                    ReportEntry::jump(
+                        "partial: 66.7% identity, + 1-8, 1 shared words",
+                        ReportTarget {
+                            abs_row: Some(0),
+                            column: 0,
+                        },
+                    ),
+                ],
+            )
+        );
+        assert_eq!(
+            result.jump,
+            Some(ReportTarget {
+                abs_row: Some(2),
+                column: 2,
+            })
+        );
+        assert_eq!(result.highlights.len(), 2);
     }
 
-    fn left_mouse_event(
-        kind: MouseEventKind,
-        area: Rect,
-        column_offset: u16,
-        row_offset: u16,
-    ) -> MouseEvent {
-        MouseEvent {
-            kind,
-            column: area.x + column_offset,
-            row: area.y + row_offset,
-            modifiers: KeyModifiers::empty(),
+    #[tokio::test(flavor = "current_thread")]
+    async fn extract_region_can_be_restored() {
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGTACGT"), raw("row2", b"TTTTAAAA")]);
+
+        app.execute_commands([
+            Command::SetEditMode(EditMode::Edit),
+            confirmed(Command::ExtractRegion { start: 2, end: 4 }),
+        ]);
+        assert_eq!(app.alignment.as_ref().unwrap().base().column_count(), 3);
+        assert_eq!(app.previous_alignments.len(), 1);
+
+        app.execute_commands([Command::RestoreAlignment]);
+        assert_eq!(app.alignment.as_ref().unwrap().base().column_count(), 8);
+
+        app.execute_commands([Command::RestoreAlignment]);
+        assert_eq!(
+            app.ui.notification.as_ref().unwrap().message,
+            "no previous alignment to restore"
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn previous_alignments_are_bounded_by_count_and_memory() {
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGTACGT"), raw("row2", b"TTTTAAAA")]);
+        app.execute_commands([Command::SetEditMode(EditMode::Edit)]);
+        for _ in 0..MAX_PREVIOUS_ALIGNMENTS + 2 {
+            app.execute_commands([confirmed(Command::ExtractRegion { start: 0, end: 0 })]);
         }
+        assert_eq!(app.previous_alignments.len(), MAX_PREVIOUS_ALIGNMENTS);
+
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGTACGT"), raw("row2", b"TTTTAAAA")]);
+        // room for the extracted columns but not for the whole alignment beside them
+        app.max_memory = Some(MemoryLimit {
+            bytes: memory::alignment_bytes(16),
+        });
+        app.execute_commands([
+            Command::SetEditMode(EditMode::Edit),
+            confirmed(Command::ExtractRegion { start: 2, end: 4 }),
+        ]);
+        assert!(app.previous_alignments.is_empty());
+        assert!(
+            app.ui
+                .notification
+                .as_ref()
+                .unwrap()
+                .message
+                .ends_with("the previous alignment was not kept, as it would not fit in --max-mem")
+        );
     }
 
-    #[test]
-    fn translated_click_selects_a_full_codon_span() {
-        let mut app =
-            app_with_alignment(vec![raw("row1", b"ATGAAATTT"), raw("row2", b"ATGAAATTT")]);
-        app.alignment
-            .as_mut()
-            .unwrap()
-            .set_translation_frame(libmsa::ReadingFrame::Frame1)
-            .expect("setting translation frame should succeed");
-        app.alignment
-            .as_mut()
-            .unwrap()
-            .toggle_translation_view()
-            .expect("translation should enable");
+    #[tokio::test(flavor = "current_thread")]
+    async fn trimming_columns_waits_for_confirmation() {
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGTACGT"), raw("row2", b"TTTTAAAA")]);
+        app.execute_commands([
+            Command::SetEditMode(EditMode::Edit),
+            Command::ExtractRegion { start: 2, end: 4 },
+        ]);
+        assert!(matches!(
+            app.ui.overlay.active_overlay,
+            Some(ActiveOverlay::Confirm(_))
+        ));
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+        assert_eq!(app.alignment.as_ref().unwrap().base().column_count(), 8);
 
-        let area = app.app_layout.alignment_pane_sequence_rows;
-        app.handle_mouse_event(left_mouse_event(
-            MouseEventKind::Down(MouseButton::Left),
-            area,
+        app.execute_commands([Command::ExtractRegion { start: 2, end: 4 }]);
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+        assert_eq!(app.alignment.as_ref().unwrap().base().column_count(), 3);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn hiding_a_row_from_the_sequence_menu_waits_for_confirmation() {
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGT"), raw("row2", b"TTTT")]);
+        app.ui.overlay.open_context_menu(ContextMenuState::new(
             1,
-            0,
+            "row2".to_string(),
+            false,
+            (0, 0),
         ));
 
-        let selection = app.ui.selection.expect("selection should be created");
-        assert_eq!(selection.sequence_id, 0);
-        assert_eq!(selection.column, 0);
-        assert_eq!(selection.end_column, 2);
+        app.execute_commands([Command::HideSequence(1), Command::CloseOverlay]);
+        assert!(matches!(
+            app.ui.overlay.active_overlay,
+            Some(ActiveOverlay::Confirm(_))
+        ));
+        assert_eq!(app.alignment.as_ref().unwrap().view().row_count(), 2);
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+        assert_eq!(app.alignment.as_ref().unwrap().view().row_count(), 1);
+        assert!(app.ui.overlay.active_overlay.is_none());
     }
 
-    #[test]
-    fn translated_drag_extends_selection_in_whole_codons() {
-        let mut app =
-            app_with_alignment(vec![raw("row1", b"ATGAAATTT"), raw("row2", b"ATGAAATTT")]);
-        app.alignment
-            .as_mut()
-            .unwrap()
-            .set_translation_frame(libmsa::ReadingFrame::Frame1)
-            .expect("setting translation frame should succeed");
-        app.alignment
-            .as_mut()
-            .unwrap()
-            .toggle_translation_view()
-            .expect("translation should enable");
+    #[tokio::test(flavor = "current_thread")]
+    async fn edits_require_edit_mode_and_mark_the_alignment_unsaved() {
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGTACGT"), raw("row2", b"TTTTAAAA")]);
 
-        let area = app.app_layout.alignment_pane_sequence_rows;
-        app.handle_mouse_event(left_mouse_event(
-            MouseEventKind::Down(MouseButton::Left),
-            area,
-            1,
-            0,
+        app.execute_commands([Command::ExtractRegion { start: 2, end: 4 }]);
+        assert_eq!(app.alignment.as_ref().unwrap().base().column_count(), 8);
+        assert_eq!(
+            app.ui.notification.as_ref().unwrap().message,
+            "extract-region changes the alignment; use set-mode edit first"
+        );
+        assert!(!app.ui.meta.unsaved_edits);
+
+        app.execute_commands([
+            Command::SetEditMode(EditMode::Edit),
+            confirmed(Command::ExtractRegion { start: 2, end: 4 }),
+        ]);
+        assert!(app.ui.meta.unsaved_edits);
+
+        app.execute_commands([Command::Quit]);
+        assert!(!app.should_quit);
+        assert!(matches!(
+            app.ui.overlay.active_overlay,
+            Some(ActiveOverlay::Confirm(_))
         ));
-        app.handle_mouse_event(left_mouse_event(
-            MouseEventKind::Drag(MouseButton::Left),
-            area,
-            7,
-            0,
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+        assert!(app.should_quit);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn loading_over_unsaved_edits_waits_for_confirmation() {
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGT"), raw("row2", b"TTTT")]);
+        app.execute_commands([Command::ClearMask]);
+        assert_eq!(
+            app.ui.notification.as_ref().unwrap().message,
+            "clear-mask changes the alignment; use set-mode edit first"
+        );
+
+        app.execute_commands([Command::SetEditMode(EditMode::Edit)]);
+        app.execute_commands([confirmed(Command::ClearMask)]);
+        assert!(app.ui.meta.unsaved_edits);
+
+        app.execute_commands([Command::LoadPastedAlignment(">a\nACGT\n".to_string())]);
+        assert!(matches!(
+            app.ui.overlay.active_overlay,
+            Some(ActiveOverlay::Confirm(_))
         ));
-        app.handle_mouse_event(left_mouse_event(
-            MouseEventKind::Up(MouseButton::Left),
-            area,
-            7,
-            0,
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+        assert!(app.load_job.is_none());
+
+        app.execute_commands([Command::LoadFile {
+            input: "other.fa".to_string(),
+        }]);
+        assert!(matches!(
+            app.ui.overlay.active_overlay,
+            Some(ActiveOverlay::Confirm(_))
         ));
+    }
 
-        let selection = app.ui.selection.expect("selection should be created");
-        assert_eq!(selection.sequence_id, 0);
-        assert_eq!(selection.column, 0);
-        assert_eq!(selection.end_sequence_id, 0);
-        assert_eq!(selection.end_column, 8);
+    #[test]
+    fn id_notice_reports_changed_ids() {
+        assert_eq!(id_notice(0, 0), None);
+        assert_eq!(
+            id_notice(1, 2).as_deref(),
+            Some(
+                "Sequence IDs not valid UTF-8: 1, shown with replacement characters. \
+                 Duplicate sequence IDs renamed: 2, highlighted in the name pane."
+            )
+        );
     }
 
     #[tokio::test(flavor = "current_thread")]
-    async fn toggling_translation_preserves_the_stored_nucleotide_selection() {
-        let mut app =
-            app_with_alignment(vec![raw("row1", b"ATGAAATTT"), raw("row2", b"ATGAAATTT")]);
-        let selection = MouseSelection {
-            sequence_id: 0,
-            column: 4,
-            end_sequence_id: 0,
-            end_column: 4,
-        };
-        app.ui.selection = Some(selection);
+    async fn appended_alignment_becomes_a_partition() {
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGT"), raw("row2", b"TTTT")]);
 
-        app.execute_commands([Command::ToggleTranslationView]);
-        assert_eq!(app.ui.selection, Some(selection));
+        app.append_alignment("genes/b.fa", vec![raw("row2", b"CC"), raw("row1", b"GG")])
+            .expect("IDs match");
 
-        app.execute_commands([Command::ToggleTranslationView]);
-        assert_eq!(app.ui.selection, Some(selection));
+        let alignment = app.alignment.as_ref().unwrap();
+        assert_eq!(alignment.base().column_count(), 6);
+        assert_eq!(alignment.partitions[1].name, "b.fa");
+        assert_eq!(
+            app.ui.notification.as_ref().unwrap().message,
+            "Appended 2 columns from b.fa as partition 2; use restore-alignment to go back"
+        );
+
+        app.execute_commands([Command::RestoreAlignment]);
+        assert!(app.alignment.as_ref().unwrap().partitions.is_empty());
     }
 
     #[tokio::test(flavor = "current_thread")]
-    async fn filter_gaps_shows_notification_when_translation_is_active() {
-        let mut app =
-            app_with_alignment(vec![raw("row1", b"ATGAAATTT"), raw("row2", b"ATGAAATTT")]);
-        app.execute_commands([Command::ToggleTranslationView]);
-        app.execute_commands([Command::SetGapFilter(Some(0.25))]);
+    async fn quality_shading_requires_quality_scores() {
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGT")]);
+
+        app.execute_commands([Command::ToggleQualityShading]);
 
         let notification = app
             .ui
@@ -841,24 +5231,248 @@ mod tests {
             .expect("notification should be created");
         assert_eq!(
             notification.message,
-            "filter-gaps is unavailable while translation is active"
+            "alignment has no per-base quality scores"
         );
     }
 
     #[tokio::test(flavor = "current_thread")]
-    async fn translation_shows_notification_when_gap_filter_is_active() {
-        let mut app = app_with_alignment(vec![raw("row1", b"ATG---"), raw("row2", b"ATG---")]);
-        app.execute_commands([Command::SetGapFilter(Some(0.0))]);
-        app.execute_commands([Command::ToggleTranslationView]);
+    async fn toggling_inserts_cycles_through_modes() {
+        let mut app = app_with_alignment(vec![raw("row1", b"AcgT"), raw("row2", b"A.tT")]);
 
-        let notification = app
-            .ui
-            .notification
-            .as_ref()
-            .expect("notification should be created");
+        app.execute_commands([Command::CycleInsertColumns]);
+        let alignment = app.alignment.as_ref().unwrap();
+        assert_eq!(alignment.filter().insert_columns(), InsertColumnMode::Hide);
+        assert_eq!(alignment.view().column_count(), 2);
+
+        app.execute_commands([Command::CycleInsertColumns]);
+        let alignment = app.alignment.as_ref().unwrap();
         assert_eq!(
-            notification.message,
-            "translation is unavailable while filter-gaps is active"
+            alignment.filter().insert_columns(),
+            InsertColumnMode::Compress
+        );
+        assert_eq!(alignment.view().column_count(), 3);
+
+        app.execute_commands([Command::CycleInsertColumns]);
+        let alignment = app.alignment.as_ref().unwrap();
+        assert_eq!(alignment.filter().insert_columns(), InsertColumnMode::Show);
+        assert_eq!(alignment.view().column_count(), 4);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn crosshair_movement_scrolls_viewport_to_keep_cursor_visible() {
+        let sequence = vec![b'A'; 100];
+        let mut app = app_with_alignment(vec![raw("row1", &sequence), raw("row2", &sequence)]);
+        app.execute_commands([Command::ToggleCrosshair]);
+        let visible_cols = app.ui.viewport.window().col_range.len();
+
+        app.execute_commands([Command::MoveCrosshair {
+            rows: 5,
+            cols: visible_cols as isize,
+        }]);
+
+        assert_eq!(
+            app.ui.crosshair,
+            Some(Crosshair {
+                row: 1,
+                column: visible_cols,
+            })
+        );
+        assert!(app.ui.viewport.window().col_range.contains(&visible_cols));
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn enter_in_crosshair_mode_sets_reference_to_cursor_row() {
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGT"), raw("row2", b"ACGA")]);
+        app.execute_commands([
+            Command::ToggleCrosshair,
+            Command::MoveCrosshair { rows: 1, cols: 0 },
+        ]);
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(app.alignment.as_ref().unwrap().rows().reference(), Some(1));
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn pin_key_toggles_the_sequence_under_the_mouse() {
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGT"), raw("row2", b"ACGA")]);
+        app.update_layout(Rect::new(0, 0, 40, 20));
+        let rows_area = app.app_layout.alignment_pane_sequence_rows;
+
+        app.handle_mouse_event(left_mouse_event(MouseEventKind::Moved, rows_area, 0, 1));
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE));
+        assert_eq!(app.alignment.as_ref().unwrap().rows().pinned(), &[1]);
+
+        app.handle_mouse_event(left_mouse_event(MouseEventKind::Moved, rows_area, 0, 0));
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE));
+        assert!(app.alignment.as_ref().unwrap().rows().pinned().is_empty());
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn marks_return_to_the_viewport_position_they_were_set_at() {
+        let mut app =
+            app_with_alignment(vec![raw("row1", &[b'A'; 200]), raw("row2", &[b'C'; 200])]);
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        app.ui.viewport.jump_to_position(50);
+        app.handle_key_event(key(KeyCode::Char('m')));
+        app.handle_key_event(key(KeyCode::Char('a')));
+        app.ui.viewport.jump_to_position(120);
+        app.handle_key_event(key(KeyCode::Char('\'')));
+        app.handle_key_event(key(KeyCode::Char('a')));
+
+        assert_eq!(app.ui.viewport.offsets.cols, 50);
+        assert_eq!(
+            app.ui.marks.marks.get(&'a'),
+            Some(&Mark { row: 0, column: 50 })
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn followed_rows_are_scrolled_into_view_when_tailing() {
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGT"), raw("row2", b"ACGA")]);
+        app.ui.follow_tail = true;
+
+        let added = app
+            .append_rows(vec![raw("row3", b"ACGC"), raw("row4", b"ACGG")])
+            .unwrap();
+
+        assert_eq!(added, 2);
+        assert_eq!(app.alignment.as_ref().unwrap().view().row_count(), 4);
+        assert_eq!(app.ui.viewport.window().row_range.end, 4);
+        assert!(
+            app.append_rows(vec![raw("row5", b"AC")]).is_err(),
+            "rows of a different length are rejected"
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn loaded_mask_hides_columns_and_saves_back() {
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGTAC"), raw("row2", b"ACGAAC")]);
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("in.mask");
+        let output = dir.path().join("out.mask");
+        std::fs::write(&input, "2-3\n6\n").unwrap();
+
+        app.ui.meta.edit_mode = EditMode::Edit;
+        app.execute_command(confirmed(Command::LoadMask {
+            input: input.display().to_string(),
+        }))
+        .unwrap();
+        assert_eq!(app.alignment.as_ref().unwrap().view().column_count(), 3);
+        app.execute_command(Command::SaveMask {
+            path: output.display().to_string(),
+        })
+        .unwrap();
+
+        let saved = std::fs::read_to_string(&output).unwrap();
+        assert_eq!(saved.lines().skip(1).collect::<Vec<_>>(), ["2-3", "6"]);
+        app.execute_command(confirmed(Command::ClearMask)).unwrap();
+        assert_eq!(app.alignment.as_ref().unwrap().view().column_count(), 6);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn clustered_rows_collapse_until_expanded() {
+        let mut app = app_with_alignment(vec![
+            raw("row1", b"ACGTACGT"),
+            raw("row2", b"ACGTACGA"),
+            raw("row3", b"TTTTTTTT"),
+        ]);
+        let rows: Vec<(usize, Vec<u8>)> = (0..3)
+            .map(|abs_row| {
+                let sequence = app
+                    .alignment
+                    .as_ref()
+                    .unwrap()
+                    .base()
+                    .project_absolute_row(abs_row);
+                let sequence = sequence.unwrap();
+                (
+                    abs_row,
+                    (0..8).filter_map(|col| sequence.byte_at(col)).collect(),
+                )
+            })
+            .collect();
+        let clusters = cluster::cluster_rows(&rows, 0.85, &CancellationToken::new()).unwrap();
+
+        app.install_clusters(clusters).unwrap();
+        assert_eq!(app.alignment.as_ref().unwrap().view().row_count(), 2);
+
+        app.execute_command(Command::ToggleCluster(0)).unwrap();
+        assert_eq!(app.alignment.as_ref().unwrap().view().row_count(), 3);
+        assert!(app.execute_command(Command::ToggleCluster(2)).is_err());
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn sequence_palette_survives_theme_changes() {
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGT")]);
+
+        app.execute_commands([
+            Command::SetSequencePalette(SequencePaletteId::HighContrast),
+            Command::SetTheme(ThemeId::TokyoNight),
+        ]);
+        assert_eq!(
+            app.ui.theme.theme.sequence.dna.a,
+            HIGH_CONTRAST_SEQUENCE.dna.a
+        );
+
+        app.execute_commands([Command::SetSequencePalette(SequencePaletteId::Theme)]);
+        assert_eq!(
+            app.ui.theme.theme.sequence.dna.a,
+            TOKYO_NIGHT.sequence.dna.a
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn jump_to_range_centres_and_highlights_the_columns() {
+        let mut app =
+            app_with_alignment(vec![raw("row1", &[b'A'; 100]), raw("row2", &[b'C'; 100])]);
+        app.ui.viewport.update_dimensions(20, 2, 4);
+
+        app.execute_commands([Command::JumpToRange {
+            start: 49,
+            end: 54,
+            highlight: true,
+        }]);
+
+        assert_eq!(app.ui.viewport.window().col_range, 42..62);
+        assert_eq!(
+            app.ui.highlights,
+            vec![MouseSelection {
+                sequence_id: 0,
+                column: 49,
+                end_sequence_id: 1,
+                end_column: 54,
+            }]
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn highlight_selection_stores_region_and_clears_selection() {
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGT"), raw("row2", b"ACGA")]);
+        let selection = MouseSelection {
+            sequence_id: 0,
+            column: 1,
+            end_sequence_id: 1,
+            end_column: 2,
+        };
+        app.ui.selection = Some(selection);
+
+        app.execute_commands([Command::HighlightSelection]);
+
+        assert_eq!(app.ui.highlights, vec![selection]);
+        assert_eq!(app.ui.selection, None);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn selection_commands_require_a_selection() {
+        let mut app = app_with_alignment(vec![raw("row1", b"ACGT"), raw("row2", b"ACGA")]);
+
+        app.execute_commands([Command::ShowSelectionStats]);
+
+        assert_eq!(
+            app.ui.notification.map(|notification| notification.message),
+            Some("no selection; drag with the mouse to select a region".to_string())
         );
     }
 
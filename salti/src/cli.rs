@@ -1,11 +1,69 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::path::PathBuf;
+
 use clap::Parser;
 
+use crate::config::settings::{Settings, default_cache_dir, default_marks_path};
+use crate::config::theme::ThemeId;
+use crate::core::locator::Locator;
+use crate::core::memory::MemoryLimit;
+use crate::core::parser::DuplicateIds;
+use crate::logging::{LogFormat, LogLevel, LogOptions};
+use crate::ui::alignment_pane::{Density, RulerOptions};
+use crate::ui::frame::StatusBarTemplates;
+use crate::ui::notification::ErrorFeedback;
+use crate::ui::row_cache::FastRender;
+use crate::ui::sequence_id_pane::IdColorRule;
+
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct StartupState {
     /// Input source (file path, URL, or SSH path)
     pub file_path: Option<String>,
     /// Initial position in the file to jump to
     pub initial_position: usize,
+    /// Status bar layouts parsed from the config file
+    pub status_bar: StatusBarTemplates,
+    /// file viewport marks are saved to; `None` keeps them for the session only
+    pub marks_path: Option<PathBuf>,
+    /// directory full-length stats are cached in across sessions; `None` disables the cache
+    pub cache_dir: Option<PathBuf>,
+    /// keep reading records appended to the input after it loads
+    pub follow: bool,
+    /// absolute columns read from an indexed BGZF input instead of the whole alignment
+    pub region: Option<Range<usize>>,
+    /// compute stats for the whole alignment in the background after loading
+    pub precompute: bool,
+    /// theme matching the terminal background, when it could be detected
+    pub theme: Option<ThemeId>,
+    /// file the viewport position is shared through with other instances
+    pub sync_file: Option<PathBuf>,
+    /// file or FIFO a one-line view status is written to for external status bars
+    pub status_fifo: Option<PathBuf>,
+    /// start with the alignment position gutter shown
+    pub position_gutter: bool,
+    /// bell or flash that accompanies error notifications
+    pub error_feedback: ErrorFeedback,
+    /// start with the column depth track shown under the ruler
+    pub depth_track: bool,
+    /// tick spacing and coordinate origin of the alignment ruler
+    pub ruler: RulerOptions,
+    /// when formatted alignment rows are cached between frames
+    pub fast_render: FastRender,
+    /// alignment rows drawn per line of the panned layout
+    pub density: Density,
+    /// view to restore once the input loads, when started from a `salti://` locator
+    pub locator: Option<Locator>,
+    /// most background jobs run at once; `None` uses one per available CPU
+    pub threads: Option<usize>,
+    /// what loading does with repeated sequence IDs
+    pub duplicate_ids: DuplicateIds,
+    /// inputs estimated to need more memory than this are refused
+    pub max_memory: Option<MemoryLimit>,
+    /// external commands from the `[tools]` config table, keyed by name
+    pub tools: BTreeMap<String, String>,
+    /// colours for sequence IDs matching a pattern, from `[[id_colors]]` in the config file
+    pub id_colors: Vec<IdColorRule>,
 }
 
 #[derive(Parser, Debug)]
@@ -15,7 +73,8 @@ pub struct StartupState {
     about = "A modern, fast, multiple sequence alignment browser - built for the terminal."
 )]
 pub struct Cli {
-    /// Input source: file path, URL (http/https), or SSH path (ssh://)
+    /// Input source: file path, URL (http/https), SSH path (ssh://), or a salti:// locator
+    /// copied with `copy-locator`
     #[arg(value_name = "INPUT")]
     pub file: Option<String>,
 
@@ -23,16 +82,230 @@ pub struct Cli {
     #[arg(short, long, default_value_t = 1)]
     pub position: usize,
 
+    /// Watch a local FASTA input and add records written to it after loading
+    #[arg(long)]
+    pub follow: bool,
+
+    /// Load only alignment columns START-END (1-based, inclusive) of a local BGZF input, reading
+    /// just those through its .fai and .gzi indexes
+    #[arg(long, value_name = "START-END", value_parser = parse_region)]
+    pub region: Option<Range<usize>>,
+
+    /// Compute consensus and conservation for the whole alignment in the background after loading
+    #[arg(long)]
+    pub precompute: bool,
+
+    /// Share the viewport position through this file, keeping instances that use it scrolled together
+    #[arg(long, value_name = "PATH")]
+    pub sync_file: Option<PathBuf>,
+
+    /// Write a one-line status (position, visible region, selection, file) to this FIFO or file
+    /// whenever the view moves, for tmux or other status bars
+    #[arg(long, value_name = "PATH")]
+    pub status_fifo: Option<PathBuf>,
+
+    /// Most background workers used for parsing, stats and analyses (defaults to one per CPU)
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    pub threads: Option<u16>,
+
+    /// Refuse to load files estimated to need more than this many GB of memory
+    #[arg(long, value_name = "GB", value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_mem: Option<u32>,
+
+    /// Path to a config file (defaults to `~/.config/salti/config.toml`)
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
     /// Enable debug logging to `salti.log`
     #[arg(long)]
     pub debug: bool,
+
+    /// Write logs to this file, rotated daily
+    #[arg(long, value_name = "PATH")]
+    pub log_file: Option<PathBuf>,
+
+    /// Log verbosity (overrides `RUST_LOG`)
+    #[arg(long, value_enum, value_name = "LEVEL")]
+    pub log_level: Option<LogLevel>,
+
+    /// Log output format
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t = LogFormat::Pretty)]
+    pub log_format: LogFormat,
 }
 
 impl Cli {
-    pub fn load_startup_sate(self) -> StartupState {
-        StartupState {
-            file_path: self.file,
-            initial_position: self.position.saturating_sub(1),
-        }
+    /// logging is enabled by `--debug`, `--log-file` or `--log-level`
+    pub fn log_options(&self) -> Option<LogOptions> {
+        let enabled = self.debug || self.log_file.is_some() || self.log_level.is_some();
+        enabled.then(|| LogOptions {
+            path: self.log_file.clone(),
+            level: self
+                .log_level
+                .or_else(|| self.debug.then_some(LogLevel::Debug)),
+            format: self.log_format,
+        })
+    }
+
+    pub fn load_startup_sate(self, settings: &Settings) -> anyhow::Result<StartupState> {
+        let locator = self
+            .file
+            .as_deref()
+            .filter(|file| Locator::is_locator(file))
+            .map(Locator::parse)
+            .transpose()?;
+        let (file_path, position) = match &locator {
+            Some(locator) => (
+                Some(locator.input.clone()),
+                locator.position.unwrap_or(self.position),
+            ),
+            None => (self.file, self.position),
+        };
+        Ok(StartupState {
+            file_path,
+            initial_position: position.saturating_sub(1),
+            status_bar: StatusBarTemplates::from_settings(&settings.status_bar)?,
+            marks_path: default_marks_path(),
+            cache_dir: default_cache_dir(),
+            follow: self.follow,
+            region: self.region,
+            precompute: self.precompute,
+            theme: None,
+            sync_file: self.sync_file,
+            status_fifo: self.status_fifo,
+            position_gutter: settings.display.position_gutter,
+            depth_track: settings.display.depth_track,
+            error_feedback: settings.notifications.on_error,
+            ruler: RulerOptions {
+                ticks: settings.display.ruler_ticks,
+                coordinates: settings.display.ruler_coordinates,
+            },
+            fast_render: settings.display.fast_render,
+            density: settings.display.density,
+            locator,
+            threads: self
+                .threads
+                .map(usize::from)
+                .or(settings.jobs.threads)
+                .filter(|&threads| threads > 0),
+            duplicate_ids: settings.input.duplicate_ids,
+            max_memory: self
+                .max_mem
+                .or(settings.input.max_mem)
+                .filter(|&gb| gb > 0)
+                .map(MemoryLimit::from_gb),
+            tools: settings.tools.clone(),
+            id_colors: settings
+                .id_colors
+                .iter()
+                .map(|rule| IdColorRule::new(&rule.pattern, &rule.color))
+                .collect::<anyhow::Result<_>>()?,
+        })
+    }
+}
+
+/// 1-based inclusive `START-END` columns, as absolute ones
+fn parse_region(value: &str) -> Result<Range<usize>, String> {
+    let invalid = || "expected <start>-<end>, e.g. 100-250".to_string();
+    let (start, end) = value.split_once('-').ok_or_else(invalid)?;
+    let (Ok(start), Ok(end)) = (start.trim().parse::<usize>(), end.trim().parse::<usize>()) else {
+        return Err(invalid());
+    };
+    if start == 0 || end < start {
+        return Err(invalid());
+    }
+    Ok(start - 1..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logging_is_disabled_by_default() {
+        let cli = Cli::try_parse_from(["salti"]).expect("arguments should parse");
+
+        assert_eq!(cli.log_options(), None);
+    }
+
+    #[test]
+    fn log_file_options_are_collected() {
+        let cli = Cli::try_parse_from([
+            "salti",
+            "--log-file",
+            "logs/salti.log",
+            "--log-level",
+            "warn",
+            "--log-format",
+            "json",
+        ])
+        .expect("arguments should parse");
+
+        assert_eq!(
+            cli.log_options(),
+            Some(LogOptions {
+                path: Some(PathBuf::from("logs/salti.log")),
+                level: Some(LogLevel::Warn),
+                format: LogFormat::Json,
+            })
+        );
+    }
+
+    #[test]
+    fn locator_input_sets_the_file_and_position() {
+        let cli = Cli::try_parse_from(["salti", "salti://cov.fasta?pos=120&ref=MN908947.3"])
+            .expect("arguments should parse");
+
+        let startup = cli
+            .load_startup_sate(&Settings::default())
+            .expect("locator should parse");
+
+        assert_eq!(startup.file_path.as_deref(), Some("cov.fasta"));
+        assert_eq!(startup.initial_position, 119);
+        assert_eq!(
+            startup
+                .locator
+                .and_then(|locator| locator.reference)
+                .as_deref(),
+            Some("MN908947.3")
+        );
+    }
+
+    #[test]
+    fn threads_flag_overrides_the_config() {
+        let mut settings = Settings::default();
+        settings.jobs.threads = Some(8);
+
+        let from_config = Cli::try_parse_from(["salti"])
+            .expect("arguments should parse")
+            .load_startup_sate(&settings)
+            .expect("startup should load");
+        let from_flag = Cli::try_parse_from(["salti", "--threads", "2"])
+            .expect("arguments should parse")
+            .load_startup_sate(&settings)
+            .expect("startup should load");
+
+        assert_eq!(from_config.threads, Some(8));
+        assert_eq!(from_flag.threads, Some(2));
+        assert!(Cli::try_parse_from(["salti", "--threads", "0"]).is_err());
+    }
+
+    #[test]
+    fn region_flag_takes_one_based_inclusive_columns() {
+        let cli = Cli::try_parse_from(["salti", "aln.fa.gz", "--region", "101-250"])
+            .expect("arguments should parse");
+
+        assert_eq!(cli.region, Some(100..250));
+        assert!(Cli::try_parse_from(["salti", "--region", "0-10"]).is_err());
+        assert!(Cli::try_parse_from(["salti", "--region", "20-10"]).is_err());
+    }
+
+    #[test]
+    fn debug_flag_defaults_to_debug_level() {
+        let cli = Cli::try_parse_from(["salti", "--debug"]).expect("arguments should parse");
+
+        assert_eq!(
+            cli.log_options().and_then(|options| options.level),
+            Some(LogLevel::Debug)
+        );
     }
 }
@@ -1,37 +1,310 @@
-use crate::config::theme::ThemeId;
-use crate::core::model::DiffMode;
+use crate::config::theme::{ColorStyle, SequencePaletteId, ThemeId};
+use crate::core::export::{ConservationFormat, SnpIgnore};
+use crate::core::liftover::PositionSource;
+use crate::core::lookups::GeneticCode;
+use crate::core::marks::MarkAction;
+use crate::core::model::{ConsensusScope, DiffAmbiguity, DiffMode, DiffOptions};
+use crate::core::realign::Aligner;
+use crate::core::serializer::AlignmentFormat;
+use crate::ui::alignment_pane::{Coordinates, Density, RulerTicks};
+use crate::ui::layout::{IdPaneWidth, LayoutMode};
+use crate::ui::notification::ErrorFeedback;
 use crate::ui::notification::Notification;
+use crate::ui::row_cache::FastRender;
+use crate::ui::sequence_id_pane::{IdColorRule, IdTruncation, RowNumbers};
+use crate::ui::ui_state::EditMode;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Command {
     Quit,
     OpenCommandPalette,
+    OpenFilePicker,
+    OpenPasteBuffer,
+    OpenConsensusThreshold,
+    /// fraction of rows the consensus residue needs before the pane shows an ambiguity code
+    SetConsensusThreshold(f32),
     CloseOverlay,
     ToggleMinimap,
+    TogglePerfHud,
+    SetFps(u16),
+    SetInputCoalescing(bool),
     SetTheme(ThemeId),
+    SetSequencePalette(SequencePaletteId),
+    SetColorStyle(ColorStyle),
     ShowNotification(Notification),
-    LoadFile { input: String },
-    CheckForUpdate { show_success_message: bool },
-    ScrollDown { amount: usize },
-    ScrollUp { amount: usize },
-    ScrollLeft { amount: usize },
-    ScrollRight { amount: usize },
-    ScrollNamesLeft { amount: usize },
-    ScrollNamesRight { amount: usize },
+    /// runs a command the user confirmed, skipping its prompt
+    Confirmed(Box<Command>),
+    ShowLastError,
+    LoadFile {
+        input: String,
+    },
+    /// parses alignment text pasted into the terminal
+    LoadPastedAlignment(String),
+    CheckForUpdate {
+        show_success_message: bool,
+    },
+    ExportConsensus {
+        path: String,
+        method: Option<libmsa::ConsensusMethod>,
+        /// `None` uses the alignment's consensus threshold
+        threshold: Option<f32>,
+    },
+    ExportConservation {
+        path: String,
+        format: Option<ConservationFormat>,
+    },
+    ExportSnpDists {
+        path: String,
+        ignore: SnpIgnore,
+    },
+    GenerateReport {
+        path: String,
+    },
+    SaveAlignment {
+        path: String,
+        format: Option<AlignmentFormat>,
+        /// every sequence and column rather than only the visible ones
+        full: bool,
+    },
+    CancelExport,
+    PrecomputeStats,
+    CancelPrecompute,
+    SetConsensusScope(ConsensusScope),
+    ClearCache,
+    ScrollDown {
+        amount: usize,
+    },
+    ScrollUp {
+        amount: usize,
+    },
+    ScrollLeft {
+        amount: usize,
+    },
+    ScrollRight {
+        amount: usize,
+    },
+    ScrollNamesLeft {
+        amount: usize,
+    },
+    ScrollNamesRight {
+        amount: usize,
+    },
     JumpToPosition(usize),
+    /// absolute alignment column, shown from the first visible column at or after it
+    JumpToAlignmentPosition(usize),
+    /// absolute alignment columns, inclusive, centred in the view and optionally highlighted
+    JumpToRange {
+        start: usize,
+        end: usize,
+        highlight: bool,
+    },
     JumpToSequence(usize),
     JumpToStart,
     JumpToEnd,
+    JumpToNextGapColumn,
     SetFilter(String),
     SetGapFilter(Option<f32>),
     ClearFilter,
+    CycleInsertColumns,
+    ToggleVariableOnly,
     PinSequence(usize),
     UnpinSequence(usize),
+    PinMatching(String),
+    UnpinAll,
     SetReference(usize),
     ClearReference,
     SetConsensusMethod(libmsa::ConsensusMethod),
     SetActiveType(libmsa::AlignmentType),
     SetTranslationFrame(libmsa::ReadingFrame),
+    SetTranslationGapMode(libmsa::TranslationGapMode),
+    SetGeneticCode(&'static GeneticCode),
     SetDiffMode(DiffMode),
+    SetDiffAmbiguity(DiffAmbiguity),
+    SetDiffOptions(DiffOptions),
+    ToggleQualityShading,
+    ToggleConservationEmphasis,
+    TogglePositionGutter,
+    ToggleDepthTrack,
+    SetLayoutMode(LayoutMode),
+    SetIdPaneWidth(IdPaneWidth),
+    ResizeIdPane(i16),
+    SetIdTruncation(IdTruncation),
+    SetRowNumbers(RowNumbers),
+    /// colours matching sequence IDs ahead of the existing rules
+    AddIdColor(IdColorRule),
+    ClearIdColors,
+    /// changes the ruler settings that are `Some`
+    SetRuler {
+        ticks: Option<RulerTicks>,
+        coordinates: Option<Coordinates>,
+    },
+    SetFastRender(FastRender),
+    SetDensity(Density),
+    /// steps to the next denser density, from normal to compact to overview
+    ZoomOut,
+    /// steps back towards normal density
+    ZoomIn,
+    SetErrorFeedback(ErrorFeedback),
     ToggleTranslationView,
+    ToggleConsensusTranslation,
+    ListOrfIssues,
+    ExtractRegion {
+        start: usize,
+        end: usize,
+    },
+    RestoreAlignment,
+    AppendAlignment {
+        input: String,
+    },
+    /// realigns the selected rows over the selected columns with an external aligner
+    RealignSelection(Aligner),
+    /// runs a command from the `[tools]` config table and shows what it prints
+    RunTool(String),
+    LoadPartitions {
+        input: String,
+    },
+    SaveMask {
+        path: String,
+    },
+    LoadMask {
+        input: String,
+    },
+    ClearMask,
+    /// groups visible rows at or above this identity percentage
+    ClusterRows(f32),
+    /// groups rows sharing a gap pattern in the visible columns, or the whole alignment
+    GroupByIndels {
+        visible_only: bool,
+    },
+    ToggleCluster(usize),
+    ClearClusters,
+    JumpToNextPartition,
+    FindOrfs(usize),
+    /// ranks this many of the most variable columns by entropy
+    ListVariableColumns(usize),
+    InspectSequence(usize),
+    /// residue and codon composition of these absolute rows, or every visible row
+    Composition(Option<Vec<usize>>),
+    ExportComposition {
+        path: String,
+        rows: Option<Vec<usize>>,
+    },
+    /// dN/dS of an absolute row against the reference, over the visible columns or all of them
+    EstimateDnDs {
+        sequence: usize,
+        visible: bool,
+    },
+    ShowUngapped(Option<usize>),
+    /// shows the ungapped position of an absolute row in the status bar, or stops with `None`
+    TrackSequence(Option<usize>),
+    ConvertPosition {
+        position: usize,
+        from: PositionSource,
+        sequence: Option<usize>,
+    },
+    ShowRestrictionSites(Vec<String>),
+    FindPrimer {
+        primer: String,
+        max_mismatches: usize,
+    },
+    /// scores every sequence against a query fragment and lists the best matches
+    BlastLite {
+        query: String,
+    },
+    ToggleCrosshair,
+    MoveCrosshair {
+        rows: isize,
+        cols: isize,
+    },
+    SetReferenceAtCrosshair,
+    TogglePinAtCursor,
+    /// moves a pinned sequence `offset` places through the pin order
+    MovePinned {
+        abs_row: usize,
+        offset: isize,
+    },
+    /// moves the pinned sequence under the crosshair or mouse
+    MovePinnedAtCursor(isize),
+    /// waits for the letter of a mark to set or jump to
+    BeginMark(MarkAction),
+    SetMark(char),
+    JumpToMark(char),
+    /// returns to the position before the last jump
+    JumpBack,
+    /// undoes a `JumpBack`
+    JumpForward,
+    ListMarks,
+    ToggleFollowTail,
+    HideSequence(usize),
+    ShowHiddenSequences,
+    OpenSequenceMenu {
+        sequence_id: usize,
+        x: u16,
+        y: u16,
+    },
+    CopyToClipboard(String),
+    CopySelection,
+    CopyLocator,
+    SetEditMode(EditMode),
+    ShowSelectionStats,
+    HighlightSelection,
+    ClearHighlights,
+}
+
+impl Command {
+    /// palette name of a command that changes the alignment, which needs edit mode
+    pub const fn edit_name(&self) -> Option<&'static str> {
+        match self {
+            Self::ExtractRegion { .. } => Some("extract-region"),
+            Self::AppendAlignment { .. } => Some("append-alignment"),
+            Self::RealignSelection(_) => Some("realign-selection"),
+            Self::LoadMask { .. } => Some("load-mask"),
+            Self::ClearMask => Some("clear-mask"),
+            _ => None,
+        }
+    }
+
+    /// whether the command drops rows or columns from view or replaces the alignment or mask,
+    /// which needs confirming first
+    pub const fn is_destructive(&self) -> bool {
+        matches!(
+            self,
+            Self::HideSequence(_)
+                | Self::ExtractRegion { .. }
+                | Self::LoadMask { .. }
+                | Self::ClearMask
+                | Self::RealignSelection(_)
+        )
+    }
+
+    /// whether the command moves the viewport somewhere else, leaving its position in the jump
+    /// history
+    pub const fn is_jump(&self) -> bool {
+        matches!(
+            self,
+            Self::JumpToPosition(_)
+                | Self::JumpToAlignmentPosition(_)
+                | Self::JumpToRange { .. }
+                | Self::JumpToSequence(_)
+                | Self::JumpToStart
+                | Self::JumpToEnd
+                | Self::JumpToNextGapColumn
+                | Self::JumpToNextPartition
+                | Self::JumpToMark(_)
+        )
+    }
+
+    /// file the command writes to, which needs confirming when it already exists
+    pub fn output_path(&self) -> Option<&str> {
+        match self {
+            Self::ExportConsensus { path, .. }
+            | Self::ExportConservation { path, .. }
+            | Self::ExportSnpDists { path, .. }
+            | Self::GenerateReport { path }
+            | Self::SaveAlignment { path, .. }
+            | Self::SaveMask { path }
+            | Self::ExportComposition { path, .. } => Some(path),
+            _ => None,
+        }
+    }
 }
@@ -1,6 +1,7 @@
 use crossterm::event::{KeyCode, KeyModifiers};
 
 use crate::command::Command;
+use crate::core::marks::MarkAction;
 
 pub struct Binding {
     pub code: KeyCode,
@@ -30,11 +31,47 @@ const KEY_BINDINGS: &[Binding] = &[
         help: "Toggle NT to AA translation view",
     },
     Binding {
-        code: KeyCode::Char('m'),
-        modifiers: KeyModifiers::NONE,
+        code: KeyCode::Char('M'),
+        modifiers: KeyModifiers::SHIFT,
         action: Command::ToggleMinimap,
         help: "Toggle minimap overlay",
     },
+    Binding {
+        code: KeyCode::Char('m'),
+        modifiers: KeyModifiers::NONE,
+        action: Command::BeginMark(MarkAction::Set),
+        help: "Set a mark at the viewport position",
+    },
+    Binding {
+        code: KeyCode::Char('\''),
+        modifiers: KeyModifiers::NONE,
+        action: Command::BeginMark(MarkAction::Jump),
+        help: "Jump to a mark",
+    },
+    Binding {
+        code: KeyCode::Char('x'),
+        modifiers: KeyModifiers::NONE,
+        action: Command::ToggleCrosshair,
+        help: "Toggle crosshair cursor",
+    },
+    Binding {
+        code: KeyCode::Char('p'),
+        modifiers: KeyModifiers::NONE,
+        action: Command::TogglePinAtCursor,
+        help: "Pin or unpin the sequence under the crosshair or mouse",
+    },
+    Binding {
+        code: KeyCode::Up,
+        modifiers: KeyModifiers::ALT,
+        action: Command::MovePinnedAtCursor(-1),
+        help: "Move the pinned sequence under the mouse up the pinned group",
+    },
+    Binding {
+        code: KeyCode::Down,
+        modifiers: KeyModifiers::ALT,
+        action: Command::MovePinnedAtCursor(1),
+        help: "Move the pinned sequence under the mouse down the pinned group",
+    },
     Binding {
         code: KeyCode::Down,
         modifiers: KeyModifiers::NONE,
@@ -95,6 +132,30 @@ const KEY_BINDINGS: &[Binding] = &[
         action: Command::ScrollNamesRight { amount: 1 },
         help: "Scroll names right",
     },
+    Binding {
+        code: KeyCode::Char('['),
+        modifiers: KeyModifiers::NONE,
+        action: Command::ScrollNamesLeft { amount: 4 },
+        help: "Scroll names left by 4",
+    },
+    Binding {
+        code: KeyCode::Char(']'),
+        modifiers: KeyModifiers::NONE,
+        action: Command::ScrollNamesRight { amount: 4 },
+        help: "Scroll names right by 4",
+    },
+    Binding {
+        code: KeyCode::Char('<'),
+        modifiers: KeyModifiers::NONE,
+        action: Command::ResizeIdPane(-2),
+        help: "Narrow the sequence name pane",
+    },
+    Binding {
+        code: KeyCode::Char('>'),
+        modifiers: KeyModifiers::NONE,
+        action: Command::ResizeIdPane(2),
+        help: "Widen the sequence name pane",
+    },
     Binding {
         code: KeyCode::Home,
         modifiers: KeyModifiers::NONE,
@@ -107,11 +168,121 @@ const KEY_BINDINGS: &[Binding] = &[
         action: Command::JumpToEnd,
         help: "Jump to end of alignment",
     },
+    Binding {
+        code: KeyCode::Char('o'),
+        modifiers: KeyModifiers::CONTROL,
+        action: Command::JumpBack,
+        help: "Go back to the position before the last jump",
+    },
+    Binding {
+        code: KeyCode::Char('i'),
+        modifiers: KeyModifiers::CONTROL,
+        action: Command::JumpForward,
+        help: "Go forward in the jump history",
+    },
+    // most terminals send Ctrl+i as Tab
+    Binding {
+        code: KeyCode::Tab,
+        modifiers: KeyModifiers::NONE,
+        action: Command::JumpForward,
+        help: "Go forward in the jump history",
+    },
+    Binding {
+        code: KeyCode::Char('-'),
+        modifiers: KeyModifiers::NONE,
+        action: Command::ZoomOut,
+        help: "Zoom out to compact, then overview density",
+    },
+    Binding {
+        code: KeyCode::Char('+'),
+        modifiers: KeyModifiers::NONE,
+        action: Command::ZoomIn,
+        help: "Zoom back in towards normal density",
+    },
+    // `=` shares a key with `+` on most layouts
+    Binding {
+        code: KeyCode::Char('='),
+        modifiers: KeyModifiers::NONE,
+        action: Command::ZoomIn,
+        help: "Zoom back in towards normal density",
+    },
 ];
 
-pub fn lookup(code: KeyCode, modifiers: KeyModifiers) -> Option<Command> {
-    KEY_BINDINGS
+/// bindings that take precedence over the global table while the crosshair is active
+const CROSSHAIR_BINDINGS: &[Binding] = &[
+    Binding {
+        code: KeyCode::Down,
+        modifiers: KeyModifiers::NONE,
+        action: Command::MoveCrosshair { rows: 1, cols: 0 },
+        help: "Move crosshair down",
+    },
+    Binding {
+        code: KeyCode::Down,
+        modifiers: KeyModifiers::SHIFT,
+        action: Command::MoveCrosshair { rows: 10, cols: 0 },
+        help: "Fast move crosshair down",
+    },
+    Binding {
+        code: KeyCode::Up,
+        modifiers: KeyModifiers::NONE,
+        action: Command::MoveCrosshair { rows: -1, cols: 0 },
+        help: "Move crosshair up",
+    },
+    Binding {
+        code: KeyCode::Up,
+        modifiers: KeyModifiers::SHIFT,
+        action: Command::MoveCrosshair { rows: -10, cols: 0 },
+        help: "Fast move crosshair up",
+    },
+    Binding {
+        code: KeyCode::Left,
+        modifiers: KeyModifiers::NONE,
+        action: Command::MoveCrosshair { rows: 0, cols: -1 },
+        help: "Move crosshair left",
+    },
+    Binding {
+        code: KeyCode::Left,
+        modifiers: KeyModifiers::SHIFT,
+        action: Command::MoveCrosshair { rows: 0, cols: -10 },
+        help: "Fast move crosshair left",
+    },
+    Binding {
+        code: KeyCode::Right,
+        modifiers: KeyModifiers::NONE,
+        action: Command::MoveCrosshair { rows: 0, cols: 1 },
+        help: "Move crosshair right",
+    },
+    Binding {
+        code: KeyCode::Right,
+        modifiers: KeyModifiers::SHIFT,
+        action: Command::MoveCrosshair { rows: 0, cols: 10 },
+        help: "Fast move crosshair right",
+    },
+    Binding {
+        code: KeyCode::Enter,
+        modifiers: KeyModifiers::NONE,
+        action: Command::SetReferenceAtCrosshair,
+        help: "Set reference to the crosshair row",
+    },
+    Binding {
+        code: KeyCode::Esc,
+        modifiers: KeyModifiers::NONE,
+        action: Command::ToggleCrosshair,
+        help: "Leave crosshair mode",
+    },
+];
+
+fn find_binding(bindings: &[Binding], code: KeyCode, modifiers: KeyModifiers) -> Option<Command> {
+    bindings
         .iter()
         .find(|binding| binding.code == code && binding.modifiers == modifiers)
         .map(|binding| binding.action.clone())
 }
+
+pub fn lookup_crosshair(code: KeyCode, modifiers: KeyModifiers) -> Option<Command> {
+    find_binding(CROSSHAIR_BINDINGS, code, modifiers).or_else(|| lookup(code, modifiers))
+}
+
+pub fn lookup(code: KeyCode, modifiers: KeyModifiers) -> Option<Command> {
+    find_binding(KEY_BINDINGS, code, modifiers)
+}
@@ -1,2 +1,3 @@
 pub mod keybindings;
+pub mod settings;
 pub mod theme;
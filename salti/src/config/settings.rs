@@ -0,0 +1,294 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::core::parser::DuplicateIds;
+use crate::ui::alignment_pane::{Coordinates, Density, RulerTicks};
+use crate::ui::notification::ErrorFeedback;
+use crate::ui::row_cache::FastRender;
+
+/// file name looked up inside the salti config directory
+const CONFIG_FILE_NAME: &str = "config.toml";
+/// file inside the salti config directory that viewport marks are saved to
+const MARKS_FILE_NAME: &str = "marks.toml";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    pub status_bar: StatusBarSettings,
+    pub theme: ThemeSettings,
+    pub display: DisplaySettings,
+    pub jobs: JobSettings,
+    pub input: InputSettings,
+    pub notifications: NotificationSettings,
+    /// external commands run with `tool <name>`, keyed by name
+    pub tools: BTreeMap<String, String>,
+    /// colours for sequence IDs in the name pane, in priority order
+    pub id_colors: Vec<IdColorSettings>,
+}
+
+/// one `[[id_colors]]` rule: IDs matching the `pattern` regex are drawn in `color`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IdColorSettings {
+    pub pattern: String,
+    pub color: String,
+}
+
+/// how loaded alignments are checked
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InputSettings {
+    /// rename repeated sequence IDs with a `_2` suffix, or refuse to load the file
+    pub duplicate_ids: DuplicateIds,
+    /// refuse to load files estimated to need more than this many GB of memory
+    pub max_mem: Option<u32>,
+}
+
+/// how rejected actions are signalled
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotificationSettings {
+    /// ring the terminal `bell` or `flash` the input line when an action fails, or `off`
+    pub on_error: ErrorFeedback,
+}
+
+/// background job limits
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct JobSettings {
+    /// most blocking jobs run at once; `None` uses one per available CPU
+    pub threads: Option<usize>,
+}
+
+/// how the alignment is drawn at startup
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DisplaySettings {
+    /// start each alignment row with the position of its first visible column
+    pub position_gutter: bool,
+    /// show a bar per column under the ruler for how many rows hold a residue there
+    pub depth_track: bool,
+    /// columns between ruler ticks: `5`, `10`, `20` or `codon`
+    pub ruler_ticks: RulerTicks,
+    /// number the ruler from `1-based` or `0-based` (BED-style) positions
+    pub ruler_coordinates: Coordinates,
+    /// cache formatted rows between frames: `off`, `on`, or `auto` for panes 300+ columns wide
+    pub fast_render: FastRender,
+    /// alignment rows per line of the panned layout: `normal`, or `compact` half-blocks
+    pub density: Density,
+}
+
+/// how the starting theme is chosen
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeSettings {
+    /// ask the terminal for its background colour and start with a matching light or dark theme
+    pub detect_background: bool,
+}
+
+impl Default for ThemeSettings {
+    fn default() -> Self {
+        Self {
+            detect_background: true,
+        }
+    }
+}
+
+/// status bar templates; `None` keeps the built-in layout
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StatusBarSettings {
+    pub top: Option<String>,
+    pub bottom: Option<String>,
+}
+
+/// `$XDG_CONFIG_HOME/salti`, falling back to `~/.config/salti`
+fn config_dir() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join("salti"))
+}
+
+/// `$XDG_CACHE_HOME/salti`, falling back to `~/.cache/salti`
+pub fn default_cache_dir() -> Option<PathBuf> {
+    let cache_dir = std::env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+    Some(cache_dir.join("salti"))
+}
+
+/// `$XDG_CONFIG_HOME/salti/config.toml`, falling back to `~/.config/salti/config.toml`
+pub fn default_config_path() -> Option<PathBuf> {
+    Some(config_dir()?.join(CONFIG_FILE_NAME))
+}
+
+/// `$XDG_CONFIG_HOME/salti/marks.toml`, falling back to `~/.config/salti/marks.toml`
+pub fn default_marks_path() -> Option<PathBuf> {
+    Some(config_dir()?.join(MARKS_FILE_NAME))
+}
+
+pub fn parse_settings(contents: &str) -> Result<Settings> {
+    Ok(toml::from_str(contents)?)
+}
+
+/// loads the settings file. an explicitly requested path must exist, while a missing
+/// default config file falls back to the built-in settings.
+pub fn load_settings(path: Option<&Path>) -> Result<Settings> {
+    let (path, required) = match path {
+        Some(path) => (path.to_path_buf(), true),
+        None => match default_config_path() {
+            Some(path) => (path, false),
+            None => return Ok(Settings::default()),
+        },
+    };
+
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(error) if !required && error.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(Settings::default());
+        }
+        Err(error) => {
+            return Err(error).with_context(|| format!("failed to read {}", path.display()));
+        }
+    };
+
+    parse_settings(&contents).with_context(|| format!("invalid config file {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_config_uses_defaults() {
+        let settings = parse_settings("").expect("empty config should parse");
+
+        assert_eq!(settings, Settings::default());
+    }
+
+    #[test]
+    fn status_bar_templates_are_read() {
+        let settings = parse_settings("[status_bar]\nbottom = \"{theme} | {memory}\"\n")
+            .expect("config should parse");
+
+        assert_eq!(settings.status_bar.top, None);
+        assert_eq!(
+            settings.status_bar.bottom.as_deref(),
+            Some("{theme} | {memory}")
+        );
+    }
+
+    #[test]
+    fn background_detection_can_be_disabled() {
+        assert!(Settings::default().theme.detect_background);
+
+        let settings =
+            parse_settings("[theme]\ndetect_background = false\n").expect("config should parse");
+
+        assert!(!settings.theme.detect_background);
+    }
+
+    #[test]
+    fn job_threads_are_read() {
+        let settings = parse_settings("[jobs]\nthreads = 4\n").expect("config should parse");
+
+        assert_eq!(settings.jobs.threads, Some(4));
+    }
+
+    #[test]
+    fn duplicate_id_policy_is_read() {
+        assert_eq!(
+            Settings::default().input.duplicate_ids,
+            DuplicateIds::Suffix
+        );
+
+        let settings =
+            parse_settings("[input]\nduplicate_ids = \"error\"\n").expect("config should parse");
+
+        assert_eq!(settings.input.duplicate_ids, DuplicateIds::Error);
+        assert!(parse_settings("[input]\nduplicate_ids = \"keep\"\n").is_err());
+    }
+
+    #[test]
+    fn ruler_options_are_read() {
+        let settings =
+            parse_settings("[display]\nruler_ticks = \"codon\"\nruler_coordinates = \"0-based\"\n")
+                .expect("config should parse");
+
+        assert_eq!(settings.display.ruler_ticks, RulerTicks::Codon);
+        assert_eq!(settings.display.ruler_coordinates, Coordinates::ZeroBased);
+        assert!(parse_settings("[display]\nruler_ticks = \"7\"\n").is_err());
+    }
+
+    #[test]
+    fn fast_render_is_read() {
+        let settings =
+            parse_settings("[display]\nfast_render = \"auto\"\n").expect("config should parse");
+
+        assert_eq!(settings.display.fast_render, FastRender::Auto);
+        assert_eq!(Settings::default().display.fast_render, FastRender::Off);
+        assert!(parse_settings("[display]\nfast_render = \"fast\"\n").is_err());
+    }
+
+    #[test]
+    fn density_is_read() {
+        let settings =
+            parse_settings("[display]\ndensity = \"compact\"\n").expect("config should parse");
+
+        assert_eq!(settings.display.density, Density::Compact);
+        assert_eq!(Settings::default().display.density, Density::Normal);
+    }
+
+    #[test]
+    fn tools_are_read_by_name() {
+        let settings = parse_settings("[tools]\nmytool = \"script.sh {file} {start} {end}\"\n")
+            .expect("config should parse");
+
+        assert_eq!(
+            settings.tools.get("mytool").map(String::as_str),
+            Some("script.sh {file} {start} {end}")
+        );
+    }
+
+    #[test]
+    fn id_colour_rules_are_read_in_order() {
+        let settings = parse_settings(
+            "[[id_colors]]\npattern = \"^hCoV\"\ncolor = \"blue\"\n\n[[id_colors]]\npattern = \"outgroup\"\ncolor = \"red\"\n",
+        )
+        .expect("config should parse");
+
+        assert_eq!(
+            settings.id_colors,
+            vec![
+                IdColorSettings {
+                    pattern: "^hCoV".to_string(),
+                    color: "blue".to_string(),
+                },
+                IdColorSettings {
+                    pattern: "outgroup".to_string(),
+                    color: "red".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        assert!(parse_settings("[status_bar]\nmiddle = \"{file}\"\n").is_err());
+    }
+
+    #[test]
+    fn missing_explicit_config_is_an_error() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+
+        let result = load_settings(Some(&dir.path().join("missing.toml")));
+
+        assert!(result.is_err());
+    }
+}
@@ -140,6 +140,7 @@ pub const EVERFOREST_DARK: Theme = Theme {
             special: Color::from_u32(0x9da9a0),
         },
         diff_match: Color::from_u32(0x7a8478),
+        style: ColorStyle::Background,
     },
 };
 
@@ -183,6 +184,7 @@ pub const SOLARIZED_LIGHT: Theme = Theme {
             special: Color::from_u32(0x93a1a1),
         },
         diff_match: Color::from_u32(0x93a1a1),
+        style: ColorStyle::Background,
     },
 };
 
@@ -226,6 +228,7 @@ pub const TOKYO_NIGHT: Theme = Theme {
             special: Color::from_u32(0x565f89),
         },
         diff_match: Color::from_u32(0x6183bb),
+        style: ColorStyle::Background,
     },
 };
 
@@ -269,9 +272,116 @@ pub const TERMINAL_DEFAULT: Theme = Theme {
             special: Color::Gray,
         },
         diff_match: Color::DarkGray,
+        style: ColorStyle::Background,
     },
 };
 
+/// residue colours from the Okabe-Ito palette, which stay distinct with deuteranopia and
+/// protanopia
+pub const COLORBLIND_SEQUENCE: SequenceTheme = SequenceTheme {
+    foreground: Color::from_u32(0x000000),
+    dna: DnaPalette {
+        a: Color::from_u32(0x009e73),
+        t: Color::from_u32(0xd55e00),
+        c: Color::from_u32(0x56b4e9),
+        g: Color::from_u32(0xf0e442),
+        n: Color::from_u32(0x999999),
+        ambiguity: Color::from_u32(0xcc79a7),
+        gap: Color::from_u32(0x666666),
+    },
+    amino_acid: AminoAcidPalette {
+        hydrophobic: Color::from_u32(0x56b4e9),
+        positive: Color::from_u32(0xd55e00),
+        negative: Color::from_u32(0xcc79a7),
+        polar: Color::from_u32(0x009e73),
+        glycine: Color::from_u32(0xe69f00),
+        proline: Color::from_u32(0xf0e442),
+        aromatic: Color::from_u32(0x0072b2),
+        special: Color::from_u32(0x999999),
+    },
+    diff_match: Color::from_u32(0x666666),
+    style: ColorStyle::Background,
+};
+
+/// fully saturated residue colours under black text
+pub const HIGH_CONTRAST_SEQUENCE: SequenceTheme = SequenceTheme {
+    foreground: Color::from_u32(0x000000),
+    dna: DnaPalette {
+        a: Color::from_u32(0x00ff00),
+        t: Color::from_u32(0xff3030),
+        c: Color::from_u32(0x00ffff),
+        g: Color::from_u32(0xffff00),
+        n: Color::from_u32(0xc0c0c0),
+        ambiguity: Color::from_u32(0xff00ff),
+        gap: Color::from_u32(0x808080),
+    },
+    amino_acid: AminoAcidPalette {
+        hydrophobic: Color::from_u32(0x00ffff),
+        positive: Color::from_u32(0xff3030),
+        negative: Color::from_u32(0xff00ff),
+        polar: Color::from_u32(0x00ff00),
+        glycine: Color::from_u32(0xffa500),
+        proline: Color::from_u32(0xffff00),
+        aromatic: Color::from_u32(0x40a0ff),
+        special: Color::from_u32(0xc0c0c0),
+    },
+    diff_match: Color::from_u32(0x808080),
+    style: ColorStyle::Background,
+};
+
+/// residue colours chosen separately from the UI theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SequencePaletteId {
+    /// the colours of the active theme
+    #[default]
+    Theme,
+    Colorblind,
+    HighContrast,
+}
+
+impl SequencePaletteId {
+    pub const ALL: &[SequencePaletteId] = &[
+        SequencePaletteId::Theme,
+        SequencePaletteId::Colorblind,
+        SequencePaletteId::HighContrast,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SequencePaletteId::Theme => "theme",
+            SequencePaletteId::Colorblind => "colorblind",
+            SequencePaletteId::HighContrast => "high-contrast",
+        }
+    }
+
+    /// residue colours to use alongside `theme`
+    pub fn sequence_theme(self, theme: &Theme) -> SequenceTheme {
+        match self {
+            SequencePaletteId::Theme => theme.sequence,
+            SequencePaletteId::Colorblind => COLORBLIND_SEQUENCE,
+            SequencePaletteId::HighContrast => HIGH_CONTRAST_SEQUENCE,
+        }
+    }
+}
+
+impl std::fmt::Display for SequencePaletteId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for SequencePaletteId {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|palette| palette.name() == value)
+            .ok_or(())
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ThemeId {
     EverforestDark,
@@ -381,25 +491,75 @@ pub struct AminoAcidPalette {
     pub special: Color,
 }
 
+/// how residue colours are painted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorStyle {
+    /// coloured cell behind the residue letter
+    #[default]
+    Background,
+    /// coloured letter on the terminal background, for terminals where filled cells are hard to
+    /// read
+    Foreground,
+    /// coloured bold letter
+    Bold,
+}
+
+impl ColorStyle {
+    pub const ALL: &[ColorStyle] = &[
+        ColorStyle::Background,
+        ColorStyle::Foreground,
+        ColorStyle::Bold,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorStyle::Background => "background",
+            ColorStyle::Foreground => "foreground",
+            ColorStyle::Bold => "bold",
+        }
+    }
+}
+
+impl std::fmt::Display for ColorStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for ColorStyle {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|style| style.name() == value)
+            .ok_or(())
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct SequenceTheme {
     pub foreground: Color,
     pub dna: DnaPalette,
     pub amino_acid: AminoAcidPalette,
     pub diff_match: Color,
+    pub style: ColorStyle,
 }
 
 impl SequenceTheme {
     pub fn style_for(&self, byte: u8, alignment_type: AlignmentType) -> Style {
         self.colour_for(byte, alignment_type)
-            .map_or(Style::new(), |colour| {
-                Style::new().bg(colour).fg(self.foreground)
+            .map_or(Style::new(), |colour| match self.style {
+                ColorStyle::Background => Style::new().bg(colour).fg(self.foreground),
+                ColorStyle::Foreground => Style::new().fg(colour),
+                ColorStyle::Bold => Style::new().fg(colour).bold(),
             })
     }
 
     pub fn colour_for(&self, byte: u8, alignment_type: AlignmentType) -> Option<Color> {
         match alignment_type {
-            AlignmentType::Dna => self.dna_colour(byte),
+            AlignmentType::Dna | AlignmentType::Rna => self.dna_colour(byte),
             AlignmentType::Protein => self.amino_acid_colour(byte),
             AlignmentType::Generic => self.full_colour(byte),
         }
@@ -408,7 +568,7 @@ impl SequenceTheme {
     pub fn dna_colour(&self, byte: u8) -> Option<Color> {
         match byte {
             b'A' | b'a' => Some(self.dna.a),
-            b'T' | b't' => Some(self.dna.t),
+            b'T' | b't' | b'U' | b'u' => Some(self.dna.t),
             b'C' | b'c' => Some(self.dna.c),
             b'G' | b'g' => Some(self.dna.g),
             b'N' | b'n' => Some(self.dna.n),
@@ -444,6 +604,17 @@ impl SequenceTheme {
     }
 }
 
+/// residue colours of a theme with a palette and colour style applied
+pub fn sequence_theme_for(
+    theme_id: ThemeId,
+    palette: SequencePaletteId,
+    color_style: ColorStyle,
+) -> SequenceTheme {
+    let mut sequence = palette.sequence_theme(&theme_from_id(theme_id));
+    sequence.style = color_style;
+    sequence
+}
+
 pub fn theme_from_id(theme_id: ThemeId) -> Theme {
     match theme_id {
         ThemeId::EverforestDark => EVERFOREST_DARK,
@@ -0,0 +1,405 @@
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{Context, Result, format_err};
+use bgzip::index::BGZFIndex;
+use bgzip::read::{BGZFReader, IndexedBGZFReader};
+use tokio_util::sync::CancellationToken;
+
+/// gzip magic, deflate and the FEXTRA flag every BGZF block starts with
+const GZIP_EXTRA_MAGIC: [u8; 4] = [0x1f, 0x8b, 0x08, 0x04];
+/// bytes of a BGZF block header, up to and including the `BSIZE` field
+const BLOCK_HEADER_LEN: usize = 18;
+/// `BC` subfield id that marks a gzip member as a BGZF block
+const BGZF_SUBFIELD: [u8; 2] = *b"BC";
+
+/// whether `path` is a local BGZF file, the seekable gzip written by `bgzip`
+pub fn is_bgzf(path: &Path) -> bool {
+    let mut header = [0; BLOCK_HEADER_LEN];
+    File::open(path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .is_ok_and(|()| header[..4] == GZIP_EXTRA_MAGIC && header[12..14] == BGZF_SUBFIELD)
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// `.fai` and `.gzi` index paths next to `path`, as samtools names them
+pub fn index_paths(path: &Path) -> (PathBuf, PathBuf) {
+    (with_suffix(path, ".fai"), with_suffix(path, ".gzi"))
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// whether `index` exists and was written no earlier than `path`, so it still describes it
+fn is_current(index: &Path, path: &Path) -> bool {
+    modified(index).is_some_and(|index| modified(path).is_none_or(|input| index >= input))
+}
+
+/// whether `path` is BGZF without both of its index files, or with ones older than it
+pub fn needs_index(path: &Path) -> bool {
+    let (fai, gzi) = index_paths(path);
+    is_bgzf(path) && !(is_current(&fai, path) && is_current(&gzi, path))
+}
+
+/// one `.fai` line: where a record's sequence starts in the uncompressed file and how it is
+/// wrapped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaiRecord {
+    pub name: String,
+    /// residues in the sequence
+    pub length: u64,
+    /// uncompressed offset of the first residue
+    pub offset: u64,
+    pub line_bases: u64,
+    /// bytes per line including the line ending
+    pub line_width: u64,
+}
+
+impl FaiRecord {
+    /// uncompressed offset of residue `position`, counting the line endings before it
+    pub fn byte_offset(&self, position: u64) -> u64 {
+        if self.line_bases == 0 {
+            return self.offset;
+        }
+        self.offset + position / self.line_bases * self.line_width + position % self.line_bases
+    }
+}
+
+/// a samtools `.fai` index of a FASTA file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FastaIndex {
+    pub records: Vec<FaiRecord>,
+}
+
+impl FastaIndex {
+    /// the `.fai` next to `path`, or `None` when there is none or it is older than `path`
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        let (fai_path, _) = index_paths(path);
+        if !is_current(&fai_path, path) {
+            return Ok(None);
+        }
+        let text = std::fs::read_to_string(&fai_path)
+            .with_context(|| format!("failed to read {}", fai_path.display()))?;
+        Self::parse(&text).map(Some)
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let records = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(line_number, line)| {
+                let fields: Vec<&str> = line.split('\t').collect();
+                let number = |index: usize| -> Result<u64> {
+                    fields
+                        .get(index)
+                        .and_then(|field| field.trim().parse().ok())
+                        .ok_or_else(|| format_err!("invalid fai line {}", line_number + 1))
+                };
+                Ok(FaiRecord {
+                    name: fields[0].to_string(),
+                    length: number(1)?,
+                    offset: number(2)?,
+                    line_bases: number(3)?,
+                    line_width: number(4)?,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { records })
+    }
+
+    /// residues over every record
+    pub fn residues(&self) -> u64 {
+        self.records.iter().map(|record| record.length).sum()
+    }
+
+    pub fn to_text(&self) -> String {
+        self.records
+            .iter()
+            .map(|record| {
+                format!(
+                    "{}\t{}\t{}\t{}\t{}\n",
+                    record.name, record.length, record.offset, record.line_bases, record.line_width
+                )
+            })
+            .collect()
+    }
+
+    /// scans uncompressed FASTA, failing on records whose lines (besides the last) differ in
+    /// length, as they could not be seeked into
+    pub fn build(reader: impl BufRead, cancel: &CancellationToken) -> Result<Self> {
+        let mut records: Vec<FaiRecord> = Vec::new();
+        // whether the current record had a line shorter than its first, which must be its last
+        let mut short_line = false;
+        let mut offset = 0u64;
+        for line in reader.split(b'\n') {
+            let line = line.context("failed to read the FASTA")?;
+            let width = line.len() as u64 + 1;
+            let line = line.strip_suffix(b"\r").unwrap_or(&line);
+            if let Some(header) = line.strip_prefix(b">") {
+                if cancel.is_cancelled() {
+                    return Err(format_err!("indexing was cancelled"));
+                }
+                let name = String::from_utf8_lossy(header)
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or_default()
+                    .to_string();
+                records.push(FaiRecord {
+                    name,
+                    length: 0,
+                    offset: offset + width,
+                    line_bases: 0,
+                    line_width: 0,
+                });
+                short_line = false;
+            } else if let Some(record) = records.last_mut() {
+                let bases = line.len() as u64;
+                if bases > 0 {
+                    if record.line_bases == 0 {
+                        record.line_bases = bases;
+                        record.line_width = width;
+                    } else if short_line || bases > record.line_bases {
+                        return Err(format_err!(
+                            "{} has lines of different lengths, so it cannot be indexed",
+                            record.name
+                        ));
+                    }
+                    short_line = bases < record.line_bases;
+                    record.length += bases;
+                }
+            }
+            offset += width;
+        }
+        Ok(Self { records })
+    }
+}
+
+/// compressed and uncompressed offsets of each BGZF block after the first, as `.gzi` lists
+/// them. found from the block headers without inflating anything.
+fn gzi_entries(path: &Path) -> Result<Vec<(u64, u64)>> {
+    let mut file = BufReader::new(
+        File::open(path).with_context(|| format!("failed to open {}", path.display()))?,
+    );
+    let total = file.get_ref().metadata()?.len();
+    let mut entries = Vec::new();
+    let (mut compressed, mut uncompressed) = (0u64, 0u64);
+    while compressed < total {
+        let mut header = [0; BLOCK_HEADER_LEN];
+        file.read_exact(&mut header)
+            .with_context(|| format!("truncated BGZF block at byte {compressed}"))?;
+        if header[..4] != GZIP_EXTRA_MAGIC || header[12..14] != BGZF_SUBFIELD {
+            return Err(format_err!("not a BGZF block at byte {compressed}"));
+        }
+        let block_len = u64::from(u16::from_le_bytes([header[16], header[17]])) + 1;
+        // the uncompressed size closes each block
+        file.seek(SeekFrom::Start(compressed + block_len - 4))?;
+        let mut size = [0; 4];
+        file.read_exact(&mut size)?;
+        let size = u64::from(u32::from_le_bytes(size));
+        // the first block starts at 0 and the empty end-of-file marker holds nothing to seek to
+        if compressed > 0 && size > 0 {
+            entries.push((compressed, uncompressed));
+        }
+        compressed += block_len;
+        uncompressed += size;
+    }
+    Ok(entries)
+}
+
+fn write_gzi(entries: &[(u64, u64)], path: &Path) -> Result<()> {
+    let mut bytes = Vec::with_capacity(8 + entries.len() * 16);
+    bytes.extend_from_slice(&(entries.len() as u64).to_le_bytes());
+    for &(compressed, uncompressed) in entries {
+        bytes.extend_from_slice(&compressed.to_le_bytes());
+        bytes.extend_from_slice(&uncompressed.to_le_bytes());
+    }
+    std::fs::write(path, bytes).with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// reads a `.gzi`. samtools lists no blocks for a file that fits in one, and
+/// `IndexedBGZFReader` needs at least one, so the start of the file stands in for them.
+fn load_gzi(path: &Path) -> Result<BGZFIndex> {
+    let bytes =
+        std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let index = BGZFIndex::from_reader(bytes.as_slice())
+        .with_context(|| format!("invalid gzi index {}", path.display()))?;
+    if !index.entries().is_empty() {
+        return Ok(index);
+    }
+    let first_block = [1u64, 0, 0].map(u64::to_le_bytes).concat();
+    Ok(BGZFIndex::from_reader(first_block.as_slice())?)
+}
+
+/// a BGZF FASTA opened through its `.fai` and `.gzi`, so any residue can be read without
+/// inflating the blocks before it
+pub struct IndexedFasta {
+    pub index: FastaIndex,
+    reader: IndexedBGZFReader<File>,
+}
+
+impl IndexedFasta {
+    /// opens `path` with its indexes, or `None` when it is not BGZF or either index is missing
+    /// or older than it
+    pub fn open(path: &Path) -> Result<Option<Self>> {
+        if !is_bgzf(path) || needs_index(path) {
+            return Ok(None);
+        }
+        let Some(index) = FastaIndex::load(path)? else {
+            return Ok(None);
+        };
+        let (_, gzi_path) = index_paths(path);
+        let gzi = load_gzi(&gzi_path)?;
+        let file =
+            File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+        let reader = BGZFReader::new(file)
+            .and_then(|reader| IndexedBGZFReader::new(reader, gzi))
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Ok(Some(Self { index, reader }))
+    }
+
+    /// the whole uncompressed file, from its start
+    pub fn into_reader(self) -> IndexedBGZFReader<File> {
+        self.reader
+    }
+
+    /// residues `residues` of the `record`th sequence, clamped to its length. only the blocks
+    /// holding them are inflated.
+    pub fn fetch(&mut self, record: usize, residues: Range<u64>) -> Result<Vec<u8>> {
+        let record = self
+            .index
+            .records
+            .get(record)
+            .ok_or_else(|| format_err!("no record {record} in the fai index"))?;
+        let end = residues.end.min(record.length);
+        let start = residues.start.min(end);
+        let (from, to) = (record.byte_offset(start), record.byte_offset(end));
+        self.reader
+            .seek(SeekFrom::Start(from))
+            .with_context(|| format!("failed to seek to {}", record.name))?;
+        let mut bytes = Vec::with_capacity((to - from) as usize);
+        (&mut self.reader)
+            .take(to - from)
+            .read_to_end(&mut bytes)
+            .with_context(|| format!("failed to read {}", record.name))?;
+        bytes.retain(|&byte| byte != b'\n' && byte != b'\r');
+        if bytes.len() as u64 != end - start {
+            return Err(format_err!(
+                "{} is shorter than its fai index says, so the index is out of date",
+                record.name
+            ));
+        }
+        Ok(bytes)
+    }
+}
+
+/// writes the `.gzi` and `.fai` indexes of a BGZF FASTA next to it
+pub fn build_index(path: &Path, cancel: &CancellationToken) -> Result<()> {
+    let (fai_path, gzi_path) = index_paths(path);
+    let entries = gzi_entries(path)?;
+    let file = File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let reader =
+        BGZFReader::new(file).with_context(|| format!("failed to read {}", path.display()))?;
+    let fai = FastaIndex::build(BufReader::new(reader), cancel)?;
+    write_gzi(&entries, &gzi_path)?;
+    std::fs::write(&fai_path, fai.to_text())
+        .with_context(|| format!("failed to write {}", fai_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use bgzip::{BGZFWriter, Compression};
+
+    use super::*;
+
+    const FASTA: &str = ">seq1 first\nACGTA\nCGTAC\nGT\n>seq2\nTTTTT\nGG\n";
+
+    fn write_bgzf(path: &Path, text: &str) {
+        // small blocks, so records span several of them
+        let file = File::create(path).unwrap();
+        let mut writer =
+            BGZFWriter::with_compress_unit_size(file, Compression::default(), 8, false).unwrap();
+        writer.write_all(text.as_bytes()).unwrap();
+        writer.close().unwrap();
+    }
+
+    #[test]
+    fn fai_records_find_each_sequence() {
+        let index = FastaIndex::build(FASTA.as_bytes(), &CancellationToken::new()).unwrap();
+
+        assert_eq!(index.to_text(), "seq1\t12\t12\t5\t6\nseq2\t7\t33\t5\t6\n");
+        assert_eq!(FastaIndex::parse(&index.to_text()).unwrap(), index);
+
+        let ragged = ">seq1\nACG\nACGT\n";
+        assert!(FastaIndex::build(ragged.as_bytes(), &CancellationToken::new()).is_err());
+    }
+
+    #[test]
+    fn built_indexes_seek_into_the_compressed_file() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("aln.fa.gz");
+        write_bgzf(&path, FASTA);
+
+        assert!(is_bgzf(&path));
+        assert!(needs_index(&path));
+        assert_eq!(FastaIndex::load(&path).unwrap(), None);
+
+        build_index(&path, &CancellationToken::new()).expect("index should be built");
+        assert!(!needs_index(&path));
+        let fai = FastaIndex::load(&path)
+            .unwrap()
+            .expect("fai should be written");
+        assert_eq!(fai.residues(), 19);
+        assert_eq!(fai.records[1].name, "seq2");
+
+        let mut fasta = IndexedFasta::open(&path)
+            .unwrap()
+            .expect("indexed file should open");
+        assert_eq!(fasta.fetch(1, 0..5).unwrap(), b"TTTTT");
+        // across a line ending and clamped to the record
+        assert_eq!(fasta.fetch(0, 3..8).unwrap(), b"TACGT");
+        assert_eq!(fasta.fetch(1, 4..20).unwrap(), b"TGG");
+    }
+
+    #[test]
+    fn single_block_files_open_with_an_empty_gzi() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("aln.fa.gz");
+        let file = File::create(&path).unwrap();
+        let mut writer = BGZFWriter::new(file, Compression::default());
+        writer.write_all(FASTA.as_bytes()).unwrap();
+        writer.close().unwrap();
+        build_index(&path, &CancellationToken::new()).expect("index should be built");
+
+        let (_, gzi_path) = index_paths(&path);
+        assert_eq!(std::fs::read(gzi_path).unwrap(), 0u64.to_le_bytes());
+        let mut fasta = IndexedFasta::open(&path)
+            .unwrap()
+            .expect("indexed file should open");
+        assert_eq!(fasta.fetch(0, 10..12).unwrap(), b"GT");
+    }
+
+    #[test]
+    fn plain_files_are_not_bgzf() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("aln.fa");
+        std::fs::write(&path, FASTA).unwrap();
+
+        assert!(!is_bgzf(&path));
+        assert!(!needs_index(&path));
+    }
+}
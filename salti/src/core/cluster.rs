@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use anyhow::{Result, format_err};
+use tokio_util::sync::CancellationToken;
+
+const fn is_gap(byte: u8) -> bool {
+    matches!(byte, b'-' | b'.')
+}
+
+/// near-identical rows grouped by `cluster`. while collapsed only the representative is shown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cluster {
+    pub representative: usize,
+    /// absolute rows other than the representative, in alignment order
+    pub members: Vec<usize>,
+    pub expanded: bool,
+}
+
+impl Cluster {
+    /// shown before the representative's name: `[+n]` while collapsed, `[-n]` once expanded
+    pub fn badge(&self) -> String {
+        let sign = if self.expanded { '-' } else { '+' };
+        format!("[{sign}{}] ", self.members.len())
+    }
+}
+
+/// residues shared by two aligned rows as a fraction of the shorter ungapped sequence, like
+/// CD-HIT's global identity
+pub fn identity(a: &[u8], b: &[u8]) -> f32 {
+    let residues = |bytes: &[u8]| bytes.iter().filter(|&&byte| !is_gap(byte)).count();
+    let shorter = residues(a).min(residues(b));
+    if shorter == 0 {
+        return 0.0;
+    }
+    let matches = a
+        .iter()
+        .zip(b)
+        .filter(|&(&x, &y)| !is_gap(x) && x.eq_ignore_ascii_case(&y))
+        .count();
+    matches as f32 / shorter as f32
+}
+
+/// greedily clusters `(abs_row, aligned bytes)` rows. rows are visited longest first and join
+/// the first representative they match with at least `threshold` identity, otherwise they start
+/// a cluster of their own. clusters of one row are left out.
+pub fn cluster_rows(
+    rows: &[(usize, Vec<u8>)],
+    threshold: f32,
+    cancel: &CancellationToken,
+) -> Result<Vec<Cluster>> {
+    let mut order: Vec<&(usize, Vec<u8>)> = rows.iter().collect();
+    order
+        .sort_by_key(|(_, bytes)| std::cmp::Reverse(bytes.iter().filter(|&&b| !is_gap(b)).count()));
+
+    let mut clusters: Vec<(&[u8], Cluster)> = Vec::new();
+    for (abs_row, bytes) in order {
+        if cancel.is_cancelled() {
+            return Err(format_err!("Clustering cancelled"));
+        }
+        match clusters
+            .iter_mut()
+            .find(|(representative, _)| identity(representative, bytes) >= threshold)
+        {
+            Some((_, cluster)) => cluster.members.push(*abs_row),
+            None => clusters.push((
+                bytes,
+                Cluster {
+                    representative: *abs_row,
+                    members: Vec::new(),
+                    expanded: false,
+                },
+            )),
+        }
+    }
+
+    let mut clusters: Vec<Cluster> = clusters
+        .into_iter()
+        .map(|(_, mut cluster)| {
+            cluster.members.sort_unstable();
+            cluster
+        })
+        .filter(|cluster| !cluster.members.is_empty())
+        .collect();
+    clusters.sort_by_key(|cluster| cluster.representative);
+    Ok(clusters)
+}
+
+/// groups `(abs_row, aligned bytes)` rows whose gaps fall in exactly the same columns of
+/// `columns`, or of the whole row when `None`. the first row of each group represents it, and
+/// groups of one row are left out.
+pub fn group_by_gap_pattern(
+    rows: &[(usize, Vec<u8>)],
+    columns: Option<Range<usize>>,
+    cancel: &CancellationToken,
+) -> Result<Vec<Cluster>> {
+    let mut groups: HashMap<Vec<bool>, Cluster> = HashMap::new();
+    for (abs_row, bytes) in rows {
+        if cancel.is_cancelled() {
+            return Err(format_err!("Indel grouping cancelled"));
+        }
+        let window = columns.clone().unwrap_or(0..bytes.len());
+        let pattern = window
+            .map(|col| bytes.get(col).is_none_or(|&byte| is_gap(byte)))
+            .collect();
+        groups
+            .entry(pattern)
+            .and_modify(|cluster| cluster.members.push(*abs_row))
+            .or_insert(Cluster {
+                representative: *abs_row,
+                members: Vec::new(),
+                expanded: false,
+            });
+    }
+
+    let mut clusters: Vec<Cluster> = groups
+        .into_values()
+        .filter(|cluster| !cluster.members.is_empty())
+        .collect();
+    clusters.sort_by_key(|cluster| cluster.representative);
+    Ok(clusters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identity_counts_shared_residues_over_the_shorter_sequence() {
+        assert_eq!(identity(b"ACGT", b"acgt"), 1.0);
+        assert_eq!(identity(b"ACGT", b"ACGA"), 0.75);
+        assert_eq!(identity(b"AC--", b"ACGT"), 1.0);
+        assert_eq!(identity(b"----", b"ACGT"), 0.0);
+    }
+
+    #[test]
+    fn rows_join_the_first_matching_representative() {
+        let rows = vec![
+            (0, b"ACGTACGT".to_vec()),
+            (1, b"ACGTACGA".to_vec()),
+            (2, b"TTTTTTTT".to_vec()),
+            (3, b"ACGTAC--".to_vec()),
+        ];
+
+        let clusters = cluster_rows(&rows, 0.9, &CancellationToken::new()).unwrap();
+
+        assert_eq!(
+            clusters,
+            vec![Cluster {
+                representative: 0,
+                members: vec![3],
+                expanded: false,
+            }]
+        );
+        assert_eq!(clusters[0].badge(), "[+1] ");
+    }
+
+    #[test]
+    fn rows_group_by_where_their_gaps_fall() {
+        let rows = vec![
+            (0, b"AC-GTA".to_vec()),
+            (1, b"TT-CCA".to_vec()),
+            (2, b"ACGGTA".to_vec()),
+            (3, b"GG-AA-".to_vec()),
+        ];
+        let cancel = CancellationToken::new();
+
+        assert_eq!(
+            group_by_gap_pattern(&rows, None, &cancel).unwrap(),
+            vec![Cluster {
+                representative: 0,
+                members: vec![1],
+                expanded: false,
+            }]
+        );
+        assert_eq!(
+            group_by_gap_pattern(&rows, Some(1..4), &cancel).unwrap(),
+            vec![Cluster {
+                representative: 0,
+                members: vec![1, 3],
+                expanded: false,
+            }]
+        );
+    }
+}
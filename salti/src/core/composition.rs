@@ -0,0 +1,256 @@
+use std::fmt::Write as _;
+use std::path::Path;
+
+use anyhow::{Context, Result, format_err};
+use tokio_util::sync::CancellationToken;
+
+use crate::core::export::JobProgress;
+
+const fn is_gap(byte: u8) -> bool {
+    matches!(byte, b'-' | b'.')
+}
+
+fn percent(count: usize, total: usize) -> f32 {
+    if total == 0 {
+        return 0.0;
+    }
+    count as f32 / total as f32 * 100.0
+}
+
+/// a codon, the amino acid it codes for and how often it occurs
+type CodonCount = ([u8; 3], u8, usize);
+
+/// residue and codon counts over a set of rows, shown by `composition`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Composition {
+    pub sequences: usize,
+    pub gaps: usize,
+    /// residue counts, uppercased and sorted by residue
+    pub residues: Vec<(u8, usize)>,
+    /// reading frame and counts of complete, gap-free codons with their amino acid, sorted by
+    /// amino acid then codon. only counted for nucleotide alignments with a translation frame.
+    pub codons: Option<(libmsa::ReadingFrame, Vec<CodonCount>)>,
+}
+
+impl Composition {
+    fn residue_total(&self) -> usize {
+        self.residues.iter().map(|&(_, count)| count).sum()
+    }
+
+    fn codon_total(&self) -> usize {
+        self.codons
+            .iter()
+            .flat_map(|(_, codons)| codons)
+            .map(|&(_, _, count)| count)
+            .sum()
+    }
+
+    /// report lines: a summary, then a table of residues and, when counted, codons
+    pub fn lines(&self) -> Vec<String> {
+        let residues = self.residue_total();
+        let mut lines = vec![
+            format!(
+                "{} sequences, {residues} residues, {} gaps",
+                self.sequences, self.gaps
+            ),
+            "Residue      Count       %".to_string(),
+        ];
+        for &(residue, count) in &self.residues {
+            lines.push(format!(
+                "  {}     {count:>9} {:>6.2}%",
+                char::from(residue),
+                percent(count, residues)
+            ));
+        }
+        if let Some((frame, codons)) = &self.codons {
+            let total = self.codon_total();
+            lines.push(format!("Codon usage (frame {frame}, {total} codons)"));
+            lines.push("Codon  AA    Count       %".to_string());
+            for &(codon, amino_acid, count) in codons {
+                lines.push(format!(
+                    "  {}  {}  {count:>9} {:>6.2}%",
+                    String::from_utf8_lossy(&codon),
+                    char::from(amino_acid),
+                    percent(count, total)
+                ));
+            }
+        }
+        lines
+    }
+
+    /// one row per residue and codon: `type,symbol,amino_acid,count,percent`
+    pub fn to_csv(&self) -> String {
+        let mut csv = "type,symbol,amino_acid,count,percent\n".to_string();
+        let residues = self.residue_total();
+        for &(residue, count) in &self.residues {
+            let _ = writeln!(
+                csv,
+                "residue,{},,{count},{:.4}",
+                char::from(residue),
+                percent(count, residues)
+            );
+        }
+        if let Some((_, codons)) = &self.codons {
+            let total = self.codon_total();
+            for &(codon, amino_acid, count) in codons {
+                let _ = writeln!(
+                    csv,
+                    "codon,{},{},{count},{:.4}",
+                    String::from_utf8_lossy(&codon),
+                    char::from(amino_acid),
+                    percent(count, total)
+                );
+            }
+        }
+        csv
+    }
+}
+
+/// counts residues over `rows` (absolute) of `view`, and codons in `frame` when given.
+/// progress advances by one per row.
+pub fn count_composition(
+    view: &libmsa::Alignment,
+    rows: &[usize],
+    frame: Option<libmsa::ReadingFrame>,
+    table: &libmsa::TranslationTable,
+    progress: &JobProgress,
+    cancel: &CancellationToken,
+) -> Result<Composition> {
+    let mut residues = [0usize; 256];
+    let mut codons = std::collections::BTreeMap::<[u8; 3], usize>::new();
+    let mut gaps = 0;
+    let mut sequences = 0;
+
+    for &row in rows {
+        if cancel.is_cancelled() {
+            return Err(format_err!("Composition cancelled"));
+        }
+        let Some(sequence) = view.project_absolute_row(row) else {
+            continue;
+        };
+        sequences += 1;
+        let bytes: Vec<u8> = (0..sequence.len())
+            .filter_map(|col| sequence.byte_at(col))
+            .map(|byte| byte.to_ascii_uppercase())
+            .collect();
+        for &byte in &bytes {
+            if is_gap(byte) {
+                gaps += 1;
+            } else {
+                residues[usize::from(byte)] += 1;
+            }
+        }
+        if let Some(frame) = frame {
+            for codon in bytes[frame.offset().min(bytes.len())..].chunks_exact(3) {
+                if !codon.iter().copied().any(is_gap) {
+                    *codons.entry([codon[0], codon[1], codon[2]]).or_default() += 1;
+                }
+            }
+        }
+        progress.advance(1);
+    }
+
+    let codons = frame.map(|frame| {
+        let mut codons: Vec<CodonCount> = codons
+            .into_iter()
+            .map(|(codon, count)| (codon, table.translate_codon(codon), count))
+            .collect();
+        codons.sort_by_key(|&(codon, amino_acid, _)| (amino_acid, codon));
+        (frame, codons)
+    });
+    Ok(Composition {
+        sequences,
+        gaps,
+        residues: (0..=u8::MAX)
+            .filter(|&byte| residues[usize::from(byte)] > 0)
+            .map(|byte| (byte, residues[usize::from(byte)]))
+            .collect(),
+        codons,
+    })
+}
+
+/// counts the composition of `rows` and writes it to `path` as CSV
+pub fn export_composition(
+    view: &libmsa::Alignment,
+    rows: &[usize],
+    frame: Option<libmsa::ReadingFrame>,
+    table: &libmsa::TranslationTable,
+    path: &Path,
+    progress: &JobProgress,
+    cancel: &CancellationToken,
+) -> Result<()> {
+    let composition = count_composition(view, rows, frame, table, progress, cancel)?;
+    std::fs::write(path, composition.to_csv())
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw(id: &str, sequence: &[u8]) -> libmsa::RawSequence {
+        libmsa::RawSequence {
+            id: id.to_string(),
+            sequence: sequence.to_vec(),
+            quality: None,
+        }
+    }
+
+    #[test]
+    fn composition_counts_residues_and_in_frame_codons() {
+        let alignment =
+            libmsa::Alignment::new(vec![raw("a", b"ATGaaa-TG"), raw("b", b"ATGAAGTTG")])
+                .expect("alignment should be valid");
+
+        let composition = count_composition(
+            &alignment,
+            &[0, 1],
+            Some(libmsa::ReadingFrame::Frame1),
+            &libmsa::TranslationTable::STANDARD,
+            &JobProgress::new(2),
+            &CancellationToken::new(),
+        )
+        .expect("composition should be counted");
+
+        assert_eq!(composition.sequences, 2);
+        assert_eq!(composition.gaps, 1);
+        assert_eq!(composition.residues, vec![(b'A', 7), (b'G', 5), (b'T', 5)]);
+        let (_, codons) = composition.codons.as_ref().expect("codons are counted");
+        assert_eq!(
+            codons,
+            &vec![
+                (*b"AAA", b'K', 1),
+                (*b"AAG", b'K', 1),
+                (*b"TTG", b'L', 1),
+                (*b"ATG", b'M', 2),
+            ]
+        );
+        let csv = composition.to_csv();
+        assert!(csv.starts_with("type,symbol,amino_acid,count,percent\nresidue,A,,7,41.1765\n"));
+        assert!(csv.contains("codon,ATG,M,2,40.0000\n"));
+    }
+
+    #[test]
+    fn codons_are_skipped_without_a_frame() {
+        let alignment =
+            libmsa::Alignment::new(vec![raw("a", b"ATGAAA")]).expect("alignment should be valid");
+
+        let composition = count_composition(
+            &alignment,
+            &[0],
+            None,
+            &libmsa::TranslationTable::STANDARD,
+            &JobProgress::new(1),
+            &CancellationToken::new(),
+        )
+        .expect("composition should be counted");
+
+        assert_eq!(composition.codons, None);
+        assert!(
+            !composition
+                .lines()
+                .iter()
+                .any(|line| line.starts_with("Codon"))
+        );
+    }
+}
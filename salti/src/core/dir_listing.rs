@@ -0,0 +1,145 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+
+use tokio_util::sync::CancellationToken;
+use tracing::debug;
+
+/// entries read before the listing is published, so completions appear while a huge
+/// directory is still being read
+const LISTING_BATCH_SIZE: usize = 256;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListedEntry {
+    pub name: String,
+    pub is_dir: bool,
+}
+
+#[derive(Debug, Default)]
+struct ListingState {
+    entries: Vec<ListedEntry>,
+    complete: bool,
+}
+
+/// entries of one directory, read on a background thread. dropping the listing stops the read.
+#[derive(Debug)]
+pub struct DirListing {
+    dir: PathBuf,
+    state: Arc<Mutex<ListingState>>,
+    changed: Arc<AtomicBool>,
+    cancel: CancellationToken,
+}
+
+impl DirListing {
+    pub fn start(dir: PathBuf) -> Self {
+        let state = Arc::new(Mutex::new(ListingState::default()));
+        let changed = Arc::new(AtomicBool::new(false));
+        let cancel = CancellationToken::new();
+
+        let worker_dir = dir.clone();
+        let worker_state = Arc::clone(&state);
+        let worker_changed = Arc::clone(&changed);
+        let worker_cancel = cancel.clone();
+        thread::spawn(move || {
+            let publish = |batch: &mut Vec<ListedEntry>, complete: bool| {
+                let mut state = worker_state.lock().unwrap_or_else(PoisonError::into_inner);
+                state.entries.append(batch);
+                state.complete = complete;
+                worker_changed.store(true, Ordering::Release);
+            };
+
+            let mut batch = Vec::with_capacity(LISTING_BATCH_SIZE);
+            match fs::read_dir(&worker_dir) {
+                Ok(entries) => {
+                    for entry in entries.flatten() {
+                        if worker_cancel.is_cancelled() {
+                            return;
+                        }
+                        let Ok(name) = entry.file_name().into_string() else {
+                            continue;
+                        };
+                        let is_dir = entry.path().is_dir();
+                        batch.push(ListedEntry { name, is_dir });
+                        if batch.len() == LISTING_BATCH_SIZE {
+                            publish(&mut batch, false);
+                        }
+                    }
+                }
+                Err(error) => {
+                    debug!(dir = %worker_dir.display(), error = %error, "Cannot list directory");
+                }
+            }
+            publish(&mut batch, true);
+        });
+
+        Self {
+            dir,
+            state,
+            changed,
+            cancel,
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// entries read so far
+    pub fn entries(&self) -> Vec<ListedEntry> {
+        self.lock().entries.clone()
+    }
+
+    #[cfg(test)]
+    pub fn is_complete(&self) -> bool {
+        self.lock().complete
+    }
+
+    /// whether entries arrived since the last call
+    pub fn take_changed(&self) -> bool {
+        self.changed.swap(false, Ordering::AcqRel)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ListingState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Drop for DirListing {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn listing_reads_every_entry_in_the_background() {
+        let dir = std::env::temp_dir().join(format!("salti-listing-{}", std::process::id()));
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        for index in 0..300 {
+            fs::write(dir.join(format!("{index}.fa")), ">a\nA\n").unwrap();
+        }
+
+        let listing = DirListing::start(dir.clone());
+        while !listing.is_complete() {
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        let entries = listing.entries();
+        assert_eq!(entries.len(), 301);
+        assert!(listing.take_changed());
+        assert!(!listing.take_changed());
+        assert!(
+            entries
+                .iter()
+                .any(|entry| entry.name == "nested" && entry.is_dir)
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
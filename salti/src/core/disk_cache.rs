@@ -0,0 +1,394 @@
+use std::hash::Hasher as _;
+use std::io::Read as _;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, format_err};
+use tokio_util::sync::CancellationToken;
+
+/// start of every cache file, followed by the format version
+const MAGIC: &[u8] = b"SALTISTATS";
+const VERSION: u8 = 1;
+const EXTENSION: &str = "stats";
+/// sequence type chosen with `set-sequence-type`, kept beside the stats of the same file
+const TYPE_EXTENSION: &str = "type";
+const HEADER_LEN: usize = MAGIC.len() + 1 + 2 + 2 * 8;
+/// consensus, consensus support, conservation, gap fraction and gap-only flag
+const COLUMN_LEN: usize = 1 + 3 * 4 + 1;
+const HASH_BUFFER_LEN: usize = 1 << 20;
+
+/// what a cache file holds stats for. stats are only reused when all of it matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheHeader {
+    pub alignment_type: libmsa::AlignmentType,
+    pub consensus_method: libmsa::ConsensusMethod,
+    pub rows: usize,
+    pub columns: usize,
+}
+
+/// cache file of a local input, named by a hash of its contents
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheEntry {
+    pub path: PathBuf,
+    /// header of the cache file when one already exists
+    pub cached: Option<CacheHeader>,
+    /// sequence type the user chose for the file, which wins over detection
+    pub type_override: Option<libmsa::AlignmentType>,
+}
+
+/// stats cached across sessions, shared by every input opened
+#[derive(Debug, Default)]
+pub struct DiskCache {
+    /// directory the cache files live in; `None` disables the cache
+    pub dir: Option<PathBuf>,
+    /// entry of the loaded input, when it is a local file
+    pub entry: Option<CacheEntry>,
+}
+
+const fn type_code(alignment_type: libmsa::AlignmentType) -> u8 {
+    match alignment_type {
+        libmsa::AlignmentType::Dna => 0,
+        libmsa::AlignmentType::Protein => 1,
+        libmsa::AlignmentType::Generic => 2,
+        libmsa::AlignmentType::Rna => 3,
+    }
+}
+
+const fn type_from_code(code: u8) -> Option<libmsa::AlignmentType> {
+    match code {
+        0 => Some(libmsa::AlignmentType::Dna),
+        1 => Some(libmsa::AlignmentType::Protein),
+        2 => Some(libmsa::AlignmentType::Generic),
+        3 => Some(libmsa::AlignmentType::Rna),
+        _ => None,
+    }
+}
+
+const fn method_code(method: libmsa::ConsensusMethod) -> u8 {
+    match method {
+        libmsa::ConsensusMethod::Majority => 0,
+        libmsa::ConsensusMethod::MajorityNonGap => 1,
+    }
+}
+
+const fn method_from_code(code: u8) -> Option<libmsa::ConsensusMethod> {
+    match code {
+        0 => Some(libmsa::ConsensusMethod::Majority),
+        1 => Some(libmsa::ConsensusMethod::MajorityNonGap),
+        _ => None,
+    }
+}
+
+/// hash of the whole file. the std hasher is not stable across toolchains, so a new build may
+/// miss entries written by an older one, which only costs a recompute.
+pub fn content_hash(path: &Path, cancel: &CancellationToken) -> Result<u64> {
+    let mut file =
+        std::fs::File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let mut hasher = std::hash::DefaultHasher::new();
+    let mut buffer = vec![0; HASH_BUFFER_LEN];
+    loop {
+        if cancel.is_cancelled() {
+            return Err(format_err!("Cancelled file hash"));
+        }
+        let read = file
+            .read(&mut buffer)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        if read == 0 {
+            return Ok(hasher.finish());
+        }
+        hasher.write(&buffer[..read]);
+    }
+}
+
+/// the cache entry for `input`, or `None` when it is not a local file
+pub fn open_entry(
+    dir: &Path,
+    input: &str,
+    cancel: &CancellationToken,
+) -> Result<Option<CacheEntry>> {
+    let input = Path::new(input);
+    if !input.is_file() {
+        return Ok(None);
+    }
+    let path = dir.join(format!("{:016x}.{EXTENSION}", content_hash(input, cancel)?));
+    let cached = read_header(&path).unwrap_or(None);
+    let type_override = std::fs::read_to_string(path.with_extension(TYPE_EXTENSION))
+        .ok()
+        .and_then(|kind| kind.trim().parse().ok());
+    Ok(Some(CacheEntry {
+        path,
+        cached,
+        type_override,
+    }))
+}
+
+/// remembers `kind` as the sequence type of the file behind `entry`
+pub fn write_type_override(entry: &CacheEntry, kind: libmsa::AlignmentType) -> Result<()> {
+    let path = entry.path.with_extension(TYPE_EXTENSION);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create {}", dir.display()))?;
+    }
+    std::fs::write(&path, kind.to_string())
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+fn parse_header(bytes: &[u8]) -> Option<CacheHeader> {
+    let rest = bytes.strip_prefix(MAGIC)?;
+    let (&[version, alignment_type, method], rest) = rest.split_first_chunk::<3>()?;
+    if version != VERSION {
+        return None;
+    }
+    let (rows, rest) = rest.split_first_chunk::<8>()?;
+    let (columns, _) = rest.split_first_chunk::<8>()?;
+    Some(CacheHeader {
+        alignment_type: type_from_code(alignment_type)?,
+        consensus_method: method_from_code(method)?,
+        rows: usize::try_from(u64::from_le_bytes(*rows)).ok()?,
+        columns: usize::try_from(u64::from_le_bytes(*columns)).ok()?,
+    })
+}
+
+/// header of the cache file at `path`. a missing or unreadable file has none.
+fn read_header(path: &Path) -> Result<Option<CacheHeader>> {
+    let mut header = [0; HEADER_LEN];
+    match std::fs::File::open(path) {
+        Ok(mut file) => match file.read_exact(&mut header) {
+            Ok(()) => Ok(parse_header(&header)),
+            Err(error) if error.kind() == std::io::ErrorKind::UnexpectedEof => Ok(None),
+            Err(error) => Err(error).with_context(|| format!("failed to read {}", path.display())),
+        },
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error).with_context(|| format!("failed to open {}", path.display())),
+    }
+}
+
+fn encode_header(header: &CacheHeader) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEADER_LEN);
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&[
+        VERSION,
+        type_code(header.alignment_type),
+        method_code(header.consensus_method),
+    ]);
+    bytes.extend_from_slice(&(header.rows as u64).to_le_bytes());
+    bytes.extend_from_slice(&(header.columns as u64).to_le_bytes());
+    bytes
+}
+
+/// summaries cached at `path`, or `None` when the file is missing or holds stats for anything
+/// other than `header`
+pub fn read_summaries(
+    path: &Path,
+    header: &CacheHeader,
+) -> Result<Option<Vec<libmsa::ColumnSummary>>> {
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => {
+            return Err(error).with_context(|| format!("failed to read {}", path.display()));
+        }
+    };
+    if parse_header(&bytes).as_ref() != Some(header)
+        || bytes.len() != HEADER_LEN + header.columns * COLUMN_LEN
+    {
+        return Ok(None);
+    }
+    let float = |bytes: &[u8]| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    let summaries = bytes[HEADER_LEN..]
+        .chunks_exact(COLUMN_LEN)
+        .enumerate()
+        .map(|(position, column)| {
+            let conservation = float(&column[5..9]);
+            libmsa::ColumnSummary {
+                position,
+                consensus: (column[0] != 0).then_some(column[0]),
+                consensus_support: float(&column[1..5]),
+                conservation: (!conservation.is_nan()).then_some(conservation),
+                gap_fraction: float(&column[9..13]),
+                gap_only: column[13] != 0,
+            }
+        })
+        .collect();
+    Ok(Some(summaries))
+}
+
+/// writes `summaries` for `header` to `path`, through a temporary file so that a crash never
+/// leaves a truncated entry behind
+pub fn write_summaries(
+    path: &Path,
+    header: &CacheHeader,
+    summaries: &[libmsa::ColumnSummary],
+) -> Result<()> {
+    let mut bytes = encode_header(header);
+    bytes.reserve(summaries.len() * COLUMN_LEN);
+    for summary in summaries {
+        bytes.push(summary.consensus.unwrap_or(0));
+        bytes.extend_from_slice(&summary.consensus_support.to_le_bytes());
+        bytes.extend_from_slice(&summary.conservation.unwrap_or(f32::NAN).to_le_bytes());
+        bytes.extend_from_slice(&summary.gap_fraction.to_le_bytes());
+        bytes.push(u8::from(summary.gap_only));
+    }
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create {}", dir.display()))?;
+    }
+    let temporary = path.with_extension("tmp");
+    std::fs::write(&temporary, bytes)
+        .with_context(|| format!("failed to write {}", temporary.display()))?;
+    std::fs::rename(&temporary, path).with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// removes every cache file in `dir`, returning how many were removed and their total size
+pub fn clear(dir: &Path) -> Result<(usize, u64)> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok((0, 0)),
+        Err(error) => {
+            return Err(error).with_context(|| format!("failed to read {}", dir.display()));
+        }
+    };
+    let mut removed = 0;
+    let mut bytes = 0;
+    for entry in entries {
+        let path = entry
+            .with_context(|| format!("failed to read {}", dir.display()))?
+            .path();
+        if path
+            .extension()
+            .is_none_or(|extension| extension != EXTENSION)
+        {
+            continue;
+        }
+        let size = std::fs::metadata(&path).map_or(0, |metadata| metadata.len());
+        std::fs::remove_file(&path)
+            .with_context(|| format!("failed to remove {}", path.display()))?;
+        removed += 1;
+        bytes += size;
+    }
+    Ok((removed, bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(
+        position: usize,
+        consensus: Option<u8>,
+        conservation: Option<f32>,
+    ) -> libmsa::ColumnSummary {
+        libmsa::ColumnSummary {
+            position,
+            consensus,
+            consensus_support: 0.5,
+            conservation,
+            gap_fraction: 0.25,
+            gap_only: consensus.is_none(),
+        }
+    }
+
+    #[test]
+    fn summaries_round_trip_only_for_a_matching_header() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let input = dir.path().join("input.fasta");
+        std::fs::write(&input, ">a\nACGT\n").expect("input should be written");
+        let cache_dir = dir.path().join("cache");
+        let entry = open_entry(
+            &cache_dir,
+            &input.display().to_string(),
+            &CancellationToken::new(),
+        )
+        .expect("entry should open")
+        .expect("local files have an entry");
+        assert_eq!(entry.cached, None);
+
+        let header = CacheHeader {
+            alignment_type: libmsa::AlignmentType::Dna,
+            consensus_method: libmsa::ConsensusMethod::MajorityNonGap,
+            rows: 1,
+            columns: 2,
+        };
+        let summaries = vec![summary(0, Some(b'A'), Some(0.75)), summary(1, None, None)];
+        write_summaries(&entry.path, &header, &summaries).expect("summaries should be written");
+
+        assert_eq!(
+            read_summaries(&entry.path, &header).expect("cache should read"),
+            Some(summaries)
+        );
+        let other = CacheHeader {
+            consensus_method: libmsa::ConsensusMethod::Majority,
+            ..header
+        };
+        assert_eq!(
+            read_summaries(&entry.path, &other).expect("cache should read"),
+            None
+        );
+        let reopened = open_entry(
+            &cache_dir,
+            &input.display().to_string(),
+            &CancellationToken::new(),
+        )
+        .expect("entry should open")
+        .expect("local files have an entry");
+        assert_eq!(reopened.cached, Some(header));
+
+        assert_eq!(clear(&cache_dir).expect("cache should clear").0, 1);
+        assert_eq!(
+            read_summaries(&entry.path, &header).expect("cache should read"),
+            None
+        );
+    }
+
+    #[test]
+    fn type_overrides_survive_reopening_and_clearing_stats() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let input = dir.path().join("input.fasta");
+        std::fs::write(&input, ">a\nNNNN\n").expect("input should be written");
+        let cache_dir = dir.path().join("cache");
+        let open = || {
+            open_entry(
+                &cache_dir,
+                &input.display().to_string(),
+                &CancellationToken::new(),
+            )
+            .expect("entry should open")
+            .expect("local files have an entry")
+        };
+
+        write_type_override(&open(), libmsa::AlignmentType::Protein)
+            .expect("override should be written");
+        clear(&cache_dir).expect("cache should clear");
+
+        assert_eq!(open().type_override, Some(libmsa::AlignmentType::Protein));
+    }
+
+    #[test]
+    fn changed_contents_use_a_different_entry() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let input = dir.path().join("input.fasta");
+        let open = || {
+            open_entry(
+                dir.path(),
+                &input.display().to_string(),
+                &CancellationToken::new(),
+            )
+            .expect("entry should open")
+            .expect("local files have an entry")
+            .path
+        };
+        std::fs::write(&input, ">a\nACGT\n").expect("input should be written");
+        let before = open();
+        std::fs::write(&input, ">a\nACGA\n").expect("input should be written");
+
+        assert_ne!(open(), before);
+        assert!(
+            open_entry(
+                dir.path(),
+                "https://example.com/a.fasta",
+                &CancellationToken::new()
+            )
+            .expect("entry should open")
+            .is_none()
+        );
+    }
+}
@@ -0,0 +1,268 @@
+use std::ops::Range;
+
+use anyhow::{Result, format_err};
+use tokio_util::sync::CancellationToken;
+
+const NUCLEOTIDES: [u8; 4] = *b"ACGT";
+
+/// synonymous and nonsynonymous sites and differences between two coding sequences, counted
+/// with the Nei–Gojobori (1986) method
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DnDs {
+    pub frame: libmsa::ReadingFrame,
+    /// codons where both sequences have a complete, unambiguous, non-stop codon
+    pub codons: usize,
+    pub synonymous_sites: f64,
+    pub nonsynonymous_sites: f64,
+    pub synonymous_differences: f64,
+    pub nonsynonymous_differences: f64,
+}
+
+/// Jukes–Cantor corrected distance, or `None` once the proportion is saturated
+fn jukes_cantor(proportion: f64) -> Option<f64> {
+    let remaining = 1.0 - 4.0 * proportion / 3.0;
+    (remaining > 0.0).then(|| -0.75 * remaining.ln())
+}
+
+fn proportion(differences: f64, sites: f64) -> Option<f64> {
+    (sites > 0.0).then(|| differences / sites)
+}
+
+impl DnDs {
+    pub fn ds(&self) -> Option<f64> {
+        proportion(self.synonymous_differences, self.synonymous_sites).and_then(jukes_cantor)
+    }
+
+    pub fn dn(&self) -> Option<f64> {
+        proportion(self.nonsynonymous_differences, self.nonsynonymous_sites).and_then(jukes_cantor)
+    }
+
+    pub fn ratio(&self) -> Option<f64> {
+        let ds = self.ds()?;
+        (ds > 0.0).then(|| self.dn().map(|dn| dn / ds)).flatten()
+    }
+
+    /// report lines describing the estimate
+    pub fn lines(&self) -> Vec<String> {
+        let distance = |value: Option<f64>| {
+            value.map_or_else(|| "saturated".to_string(), |value| format!("{value:.4}"))
+        };
+        let ratio = match (self.ratio(), self.ds()) {
+            (Some(ratio), _) => format!("{ratio:.4}"),
+            (None, Some(_)) => "undefined (no synonymous differences)".to_string(),
+            (None, None) => "undefined (synonymous sites saturated)".to_string(),
+        };
+        vec![
+            format!("Codons compared: {} (frame {})", self.codons, self.frame),
+            format!(
+                "Synonymous: {:.1} differences / {:.1} sites",
+                self.synonymous_differences, self.synonymous_sites
+            ),
+            format!(
+                "Nonsynonymous: {:.1} differences / {:.1} sites",
+                self.nonsynonymous_differences, self.nonsynonymous_sites
+            ),
+            format!("dS: {}", distance(self.ds())),
+            format!("dN: {}", distance(self.dn())),
+            format!("dN/dS: {ratio}"),
+        ]
+    }
+}
+
+/// uppercase codon with `U` read as `T`, or `None` when it holds gaps or ambiguity codes
+fn clean_codon(bytes: &[u8]) -> Option<[u8; 3]> {
+    let mut codon = [0; 3];
+    for (slot, &byte) in codon.iter_mut().zip(bytes) {
+        *slot = match byte.to_ascii_uppercase() {
+            b'U' => b'T',
+            byte if NUCLEOTIDES.contains(&byte) => byte,
+            _ => return None,
+        };
+    }
+    Some(codon)
+}
+
+/// synonymous sites of a codon: the share of its single-base changes that keep the amino acid
+fn synonymous_sites(codon: [u8; 3], table: &libmsa::TranslationTable) -> f64 {
+    let amino_acid = table.translate_codon(codon);
+    let mut synonymous = 0;
+    for position in 0..3 {
+        for base in NUCLEOTIDES {
+            if base == codon[position] {
+                continue;
+            }
+            let mut mutated = codon;
+            mutated[position] = base;
+            if table.translate_codon(mutated) == amino_acid {
+                synonymous += 1;
+            }
+        }
+    }
+    f64::from(synonymous) / 3.0
+}
+
+/// every order in which `positions` can change
+fn change_orders(positions: &[usize]) -> Vec<Vec<usize>> {
+    if positions.len() <= 1 {
+        return vec![positions.to_vec()];
+    }
+    positions
+        .iter()
+        .enumerate()
+        .flat_map(|(index, &first)| {
+            let mut rest = positions.to_vec();
+            rest.remove(index);
+            change_orders(&rest).into_iter().map(move |mut order| {
+                order.insert(0, first);
+                order
+            })
+        })
+        .collect()
+}
+
+/// synonymous and nonsynonymous differences between two codons, averaged over the mutation
+/// pathways that do not pass through a stop codon
+fn codon_differences(
+    from: [u8; 3],
+    to: [u8; 3],
+    table: &libmsa::TranslationTable,
+) -> Option<(f64, f64)> {
+    let positions: Vec<usize> = (0..3).filter(|&index| from[index] != to[index]).collect();
+    let mut synonymous = 0;
+    let mut nonsynonymous = 0;
+    let mut pathways = 0;
+    'orders: for order in change_orders(&positions) {
+        let mut current = from;
+        let (mut path_synonymous, mut path_nonsynonymous) = (0, 0);
+        for position in order {
+            let mut next = current;
+            next[position] = to[position];
+            let next_amino_acid = table.translate_codon(next);
+            if next_amino_acid == b'*' {
+                continue 'orders;
+            }
+            if table.translate_codon(current) == next_amino_acid {
+                path_synonymous += 1;
+            } else {
+                path_nonsynonymous += 1;
+            }
+            current = next;
+        }
+        synonymous += path_synonymous;
+        nonsynonymous += path_nonsynonymous;
+        pathways += 1;
+    }
+    (pathways > 0).then(|| {
+        let pathways = f64::from(pathways);
+        (
+            f64::from(synonymous) / pathways,
+            f64::from(nonsynonymous) / pathways,
+        )
+    })
+}
+
+/// estimates dN/dS between two aligned coding sequences over the codons in `frame` that lie
+/// entirely inside `columns`. codons with gaps, ambiguity codes or stops in either sequence are
+/// skipped.
+pub fn nei_gojobori(
+    reference: &[u8],
+    query: &[u8],
+    frame: libmsa::ReadingFrame,
+    table: &libmsa::TranslationTable,
+    columns: Range<usize>,
+    cancel: &CancellationToken,
+) -> Result<DnDs> {
+    let end = columns.end.min(reference.len()).min(query.len());
+    let first = frame.offset() + (columns.start.saturating_sub(frame.offset())).div_ceil(3) * 3;
+    let mut estimate = DnDs {
+        frame,
+        codons: 0,
+        synonymous_sites: 0.0,
+        nonsynonymous_sites: 0.0,
+        synonymous_differences: 0.0,
+        nonsynonymous_differences: 0.0,
+    };
+
+    for start in (first..end.saturating_sub(2)).step_by(3) {
+        if cancel.is_cancelled() {
+            return Err(format_err!("dN/dS estimate cancelled"));
+        }
+        let (Some(from), Some(to)) = (
+            clean_codon(&reference[start..start + 3]),
+            clean_codon(&query[start..start + 3]),
+        ) else {
+            continue;
+        };
+        if table.translate_codon(from) == b'*' || table.translate_codon(to) == b'*' {
+            continue;
+        }
+        let Some((synonymous, nonsynonymous)) = codon_differences(from, to, table) else {
+            continue;
+        };
+        let sites = f64::midpoint(synonymous_sites(from, table), synonymous_sites(to, table));
+        estimate.codons += 1;
+        estimate.synonymous_sites += sites;
+        estimate.nonsynonymous_sites += 3.0 - sites;
+        estimate.synonymous_differences += synonymous;
+        estimate.nonsynonymous_differences += nonsynonymous;
+    }
+    Ok(estimate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(left: f64, right: f64) -> bool {
+        (left - right).abs() < 1e-9
+    }
+
+    #[test]
+    fn sites_and_differences_follow_nei_gojobori() {
+        let estimate = nei_gojobori(
+            b"ATGAAATTT---TAACTGCTGCTG",
+            b"ATGAAGTTAGGGTAACTGCTGCTG",
+            libmsa::ReadingFrame::Frame1,
+            &libmsa::TranslationTable::STANDARD,
+            0..24,
+            &CancellationToken::new(),
+        )
+        .expect("estimate should finish");
+
+        assert_eq!(estimate.codons, 6);
+        assert!(close(estimate.synonymous_differences, 1.0));
+        assert!(close(estimate.nonsynonymous_differences, 1.0));
+        // ATG has no synonymous sites, AAA/AAG 1/3 each, TTT 1/3, TTA 2/3 and CTG 4/3
+        assert!(close(estimate.synonymous_sites, 1.0 / 3.0 + 0.5 + 4.0));
+        assert!(close(estimate.nonsynonymous_sites, 18.0 - 5.0 / 6.0 - 4.0));
+        assert!(estimate.ratio().expect("ratio is defined") < 1.0);
+    }
+
+    #[test]
+    fn multi_base_changes_average_their_pathways() {
+        let table = libmsa::TranslationTable::STANDARD;
+
+        assert_eq!(
+            codon_differences(*b"TTT", *b"CTA", &table),
+            Some((1.0, 1.0))
+        );
+        assert_eq!(change_orders(&[0, 1, 2]).len(), 6);
+    }
+
+    #[test]
+    fn only_codons_inside_the_columns_count() {
+        let estimate = nei_gojobori(
+            b"AAAAAAAAA",
+            b"AAGAAGAAG",
+            libmsa::ReadingFrame::Frame1,
+            &libmsa::TranslationTable::STANDARD,
+            1..9,
+            &CancellationToken::new(),
+        )
+        .expect("estimate should finish");
+
+        assert_eq!(estimate.codons, 2);
+        assert_eq!(estimate.ratio(), None);
+        assert_eq!(estimate.dn(), Some(0.0));
+    }
+}
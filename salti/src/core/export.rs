@@ -0,0 +1,624 @@
+use std::fmt::{self, Write as _};
+use std::ops::Range;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use anyhow::{Context, Result, format_err};
+use tokio_util::sync::CancellationToken;
+
+/// columns summarised per step of a full-length stats job, between progress updates and
+/// cancellation checks
+const FULL_STATS_STEP: usize = 5000;
+/// residues per line in exported FASTA files
+const FASTA_LINE_WIDTH: usize = 60;
+/// FASTA header used for exported consensus sequences
+const CONSENSUS_ID: &str = "consensus";
+/// chromosome name written to wiggle conservation tracks
+const WIGGLE_CHROM: &str = "alignment";
+
+/// column progress shared between a background export job and the UI
+#[derive(Debug, Clone, Default)]
+pub struct JobProgress {
+    done: Arc<AtomicUsize>,
+    total: usize,
+}
+
+impl JobProgress {
+    pub fn new(total: usize) -> Self {
+        Self {
+            done: Arc::default(),
+            total,
+        }
+    }
+
+    pub(crate) fn advance(&self, columns: usize) {
+        self.done.fetch_add(columns, Ordering::Relaxed);
+    }
+
+    pub fn percent(&self) -> usize {
+        if self.total == 0 {
+            return 100;
+        }
+        (self.done.load(Ordering::Relaxed) * 100 / self.total).min(100)
+    }
+}
+
+/// file formats supported by `export-conservation`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConservationFormat {
+    #[default]
+    Csv,
+    Wiggle,
+}
+
+impl ConservationFormat {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Wiggle => "wig",
+        }
+    }
+
+    pub const fn all() -> [Self; 2] {
+        [Self::Csv, Self::Wiggle]
+    }
+
+    /// picks the format from the file extension, defaulting to csv
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("wig") => Self::Wiggle,
+            _ => Self::Csv,
+        }
+    }
+}
+
+impl fmt::Display for ConservationFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ConservationFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|format| format.name() == value)
+            .ok_or_else(|| format_err!("invalid conservation format: {value}"))
+    }
+}
+
+/// characters skipped by `export-snp-dists`. a site is ignored when either sequence holds one
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SnpIgnore {
+    None,
+    Gaps,
+    #[default]
+    GapsAndN,
+}
+
+impl SnpIgnore {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Gaps => "gaps",
+            Self::GapsAndN => "gaps-and-n",
+        }
+    }
+
+    pub const fn all() -> [Self; 3] {
+        [Self::None, Self::Gaps, Self::GapsAndN]
+    }
+
+    fn skips(self, byte: u8, ambiguity: u8) -> bool {
+        match self {
+            Self::None => false,
+            Self::Gaps => byte == b'-',
+            Self::GapsAndN => byte == b'-' || byte == ambiguity,
+        }
+    }
+}
+
+impl fmt::Display for SnpIgnore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for SnpIgnore {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|ignore| ignore.name() == value)
+            .ok_or_else(|| format_err!("invalid snp-dists ignore mode: {value}"))
+    }
+}
+
+/// options for `export-consensus`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConsensusExportOptions {
+    pub method: libmsa::ConsensusMethod,
+    /// minimum fraction of rows that must carry the consensus residue; columns below it are
+    /// written as an ambiguity code
+    pub threshold: f32,
+}
+
+fn step_ranges(total: usize) -> impl Iterator<Item = Range<usize>> {
+    (0..total)
+        .step_by(FULL_STATS_STEP)
+        .map(move |start| start..(start + FULL_STATS_STEP).min(total))
+}
+
+/// summarises every column of `alignment`. unlike the chunk jobs behind the stats cache this
+/// is not tied to the viewport, so exports always cover the whole alignment.
+pub fn full_column_summaries(
+    alignment: &libmsa::Alignment,
+    method: libmsa::ConsensusMethod,
+    progress: &JobProgress,
+    cancel: &CancellationToken,
+) -> Result<Vec<libmsa::ColumnSummary>> {
+    let mut summaries = Vec::with_capacity(alignment.column_count());
+    for range in step_ranges(alignment.column_count()) {
+        if cancel.is_cancelled() {
+            return Err(format_err!("Export cancelled"));
+        }
+        let columns = range.len();
+        summaries.extend(alignment.column_summaries_range(range, method)?);
+        progress.advance(columns);
+    }
+    Ok(summaries)
+}
+
+/// byte written for columns whose consensus does not reach the threshold
+const fn ambiguity_byte(alignment_type: libmsa::AlignmentType) -> u8 {
+    match alignment_type {
+        libmsa::AlignmentType::Dna | libmsa::AlignmentType::Rna => b'N',
+        libmsa::AlignmentType::Protein | libmsa::AlignmentType::Generic => b'X',
+    }
+}
+
+/// consensus byte of one column, or the ambiguity code when its support is below `threshold`.
+/// `None` for columns that are all gaps.
+pub(crate) fn thresholded_consensus(
+    summary: &libmsa::ColumnSummary,
+    threshold: f32,
+    alignment_type: libmsa::AlignmentType,
+) -> Option<u8> {
+    let byte = summary.consensus?;
+    if summary.consensus_support < threshold {
+        Some(ambiguity_byte(alignment_type))
+    } else {
+        Some(byte)
+    }
+}
+
+pub(crate) fn consensus_bytes(
+    summaries: &[libmsa::ColumnSummary],
+    threshold: f32,
+    alignment_type: libmsa::AlignmentType,
+) -> Vec<u8> {
+    summaries
+        .iter()
+        .map(|summary| thresholded_consensus(summary, threshold, alignment_type).unwrap_or(b'-'))
+        .collect()
+}
+
+/// consensus residue of every column, with `-` for columns that are all gaps
+pub fn full_consensus(
+    alignment: &libmsa::Alignment,
+    method: libmsa::ConsensusMethod,
+    progress: &JobProgress,
+    cancel: &CancellationToken,
+) -> Result<Vec<u8>> {
+    let summaries = full_column_summaries(alignment, method, progress, cancel)?;
+    Ok(consensus_bytes(&summaries, 0.0, alignment.active_type()))
+}
+
+pub(crate) fn format_fasta(id: &str, sequence: &[u8]) -> String {
+    let mut fasta = format!(">{id}\n");
+    for line in sequence.chunks(FASTA_LINE_WIDTH) {
+        fasta.push_str(&String::from_utf8_lossy(line));
+        fasta.push('\n');
+    }
+    fasta
+}
+
+/// computes the consensus of the whole alignment and writes it to `path` as FASTA
+pub fn export_consensus(
+    alignment: &libmsa::Alignment,
+    options: ConsensusExportOptions,
+    path: &Path,
+    progress: &JobProgress,
+    cancel: &CancellationToken,
+) -> Result<()> {
+    let summaries = full_column_summaries(alignment, options.method, progress, cancel)?;
+    let consensus = consensus_bytes(&summaries, options.threshold, alignment.active_type());
+    std::fs::write(path, format_fasta(CONSENSUS_ID, &consensus))
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// pairs each summary with its 1-based position in the unfiltered alignment
+fn conservation_rows(
+    alignment: &libmsa::Alignment,
+    summaries: &[libmsa::ColumnSummary],
+) -> Result<Vec<(usize, f32)>> {
+    summaries
+        .iter()
+        .map(|summary| {
+            let conservation = summary.conservation.ok_or_else(|| {
+                format_err!("conservation is not defined for this alignment type")
+            })?;
+            let position = alignment
+                .absolute_column_id(summary.position)
+                .expect("summary positions come from the view");
+            Ok((position + 1, conservation))
+        })
+        .collect()
+}
+
+fn format_conservation(rows: &[(usize, f32)], format: ConservationFormat) -> String {
+    let mut output = match format {
+        ConservationFormat::Csv => "position,conservation\n".to_string(),
+        ConservationFormat::Wiggle => format!(
+            "track type=wiggle_0 name=\"salti conservation\"\nvariableStep chrom={WIGGLE_CHROM}\n"
+        ),
+    };
+    let separator = match format {
+        ConservationFormat::Csv => ',',
+        ConservationFormat::Wiggle => ' ',
+    };
+    for (position, conservation) in rows {
+        let _ = writeln!(output, "{position}{separator}{conservation:.4}");
+    }
+    output
+}
+
+/// computes per-column conservation for the whole alignment and writes it to `path`
+pub fn export_conservation(
+    alignment: &libmsa::Alignment,
+    format: ConservationFormat,
+    path: &Path,
+    progress: &JobProgress,
+    cancel: &CancellationToken,
+) -> Result<()> {
+    if alignment
+        .active_type()
+        .conservation_alphabet_size()
+        .is_none()
+    {
+        return Err(format_err!(
+            "conservation is not defined for this alignment type"
+        ));
+    }
+    let summaries = full_column_summaries(
+        alignment,
+        libmsa::ConsensusMethod::default(),
+        progress,
+        cancel,
+    )?;
+    let rows = conservation_rows(alignment, &summaries)?;
+    std::fs::write(path, format_conservation(&rows, format))
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// visible bytes of every row, uppercased so that distances ignore case
+fn visible_rows(alignment: &libmsa::Alignment) -> Vec<(String, Vec<u8>)> {
+    (0..alignment.row_count())
+        .filter_map(|row| alignment.sequence(row))
+        .map(|sequence| {
+            let bytes = (0..sequence.len())
+                .filter_map(|col| sequence.byte_at(col))
+                .map(|byte| byte.to_ascii_uppercase())
+                .collect();
+            (sequence.id().to_string(), bytes)
+        })
+        .collect()
+}
+
+fn snp_distance(left: &[u8], right: &[u8], ignore: SnpIgnore, ambiguity: u8) -> usize {
+    left.iter()
+        .zip(right)
+        .filter(|&(&a, &b)| a != b && !ignore.skips(a, ambiguity) && !ignore.skips(b, ambiguity))
+        .count()
+}
+
+/// how many threads the distance matrix is split across: the `--threads` limit, but never more
+/// than one per row
+fn snp_workers(threads: usize, rows: usize) -> usize {
+    threads.min(rows).max(1)
+}
+
+/// pairwise distances between every row, split across up to `threads` worker threads. progress
+/// advances by one per finished row.
+fn snp_distance_matrix(
+    rows: &[(String, Vec<u8>)],
+    ignore: SnpIgnore,
+    ambiguity: u8,
+    threads: usize,
+    progress: &JobProgress,
+    cancel: &CancellationToken,
+) -> Result<Vec<Vec<usize>>> {
+    let workers = snp_workers(threads, rows.len());
+    let mut matrix = vec![vec![0; rows.len()]; rows.len()];
+
+    let upper_rows = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|worker| {
+                scope.spawn(move || {
+                    let mut computed = Vec::new();
+                    // striding keeps the shrinking upper triangle balanced between workers
+                    for row in (worker..rows.len()).step_by(workers) {
+                        if cancel.is_cancelled() {
+                            return Err(format_err!("Export cancelled"));
+                        }
+                        let distances: Vec<usize> = rows[row + 1..]
+                            .iter()
+                            .map(|(_, other)| snp_distance(&rows[row].1, other, ignore, ambiguity))
+                            .collect();
+                        computed.push((row, distances));
+                        progress.advance(1);
+                    }
+                    Ok(computed)
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("snp distance worker panicked"))
+            .collect::<Result<Vec<_>>>()
+    })?;
+
+    for (row, distances) in upper_rows.into_iter().flatten() {
+        for (offset, distance) in distances.into_iter().enumerate() {
+            let other = row + 1 + offset;
+            matrix[row][other] = distance;
+            matrix[other][row] = distance;
+        }
+    }
+    Ok(matrix)
+}
+
+fn format_snp_matrix(ids: &[&str], matrix: &[Vec<usize>]) -> String {
+    let mut output = String::from("snp-dists");
+    for id in ids {
+        output.push('\t');
+        output.push_str(id);
+    }
+    output.push('\n');
+    for (id, distances) in ids.iter().zip(matrix) {
+        output.push_str(id);
+        for distance in distances {
+            let _ = write!(output, "\t{distance}");
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// computes pairwise SNP distances between every visible sequence and writes them to `path`
+/// as a TSV matrix in the style of snp-dists, using up to `threads` worker threads
+pub fn export_snp_dists(
+    alignment: &libmsa::Alignment,
+    ignore: SnpIgnore,
+    threads: usize,
+    path: &Path,
+    progress: &JobProgress,
+    cancel: &CancellationToken,
+) -> Result<()> {
+    let rows = visible_rows(alignment);
+    let ambiguity = ambiguity_byte(alignment.active_type());
+    let matrix = snp_distance_matrix(&rows, ignore, ambiguity, threads, progress, cancel)?;
+    let ids: Vec<&str> = rows.iter().map(|(id, _)| id.as_str()).collect();
+    std::fs::write(path, format_snp_matrix(&ids, &matrix))
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw(id: &str, sequence: &[u8]) -> libmsa::RawSequence {
+        libmsa::RawSequence {
+            id: id.to_string(),
+            sequence: sequence.to_vec(),
+            quality: None,
+        }
+    }
+
+    fn alignment(sequences: Vec<libmsa::RawSequence>) -> libmsa::Alignment {
+        libmsa::Alignment::new_with_type(sequences, libmsa::AlignmentType::Dna)
+            .expect("test alignment should be valid")
+    }
+
+    #[test]
+    fn full_summaries_cover_every_column_and_report_progress() {
+        let alignment = alignment(vec![raw("a", &vec![b'A'; 12_000])]);
+        let progress = JobProgress::new(alignment.column_count());
+
+        let summaries = full_column_summaries(
+            &alignment,
+            libmsa::ConsensusMethod::Majority,
+            &progress,
+            &CancellationToken::new(),
+        )
+        .expect("summaries should compute");
+
+        assert_eq!(summaries.len(), 12_000);
+        assert_eq!(progress.percent(), 100);
+    }
+
+    #[test]
+    fn cancelled_jobs_stop_early() {
+        let alignment = alignment(vec![raw("a", b"ACGT")]);
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+
+        let result = full_column_summaries(
+            &alignment,
+            libmsa::ConsensusMethod::Majority,
+            &JobProgress::new(4),
+            &cancel,
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn consensus_below_threshold_is_ambiguous() {
+        let alignment = alignment(vec![raw("a", b"AC-"), raw("b", b"AG-"), raw("c", b"AT-")]);
+        let summaries = alignment
+            .column_summaries_range(0..3, libmsa::ConsensusMethod::MajorityNonGap)
+            .expect("summaries should compute");
+
+        let bytes = consensus_bytes(&summaries, 0.5, libmsa::AlignmentType::Dna);
+
+        assert_eq!(bytes, b"AN-");
+    }
+
+    #[test]
+    fn export_writes_wrapped_fasta() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let path = dir.path().join("consensus.fa");
+        let alignment = alignment(vec![raw("a", &[b'A'; 70])]);
+
+        export_consensus(
+            &alignment,
+            ConsensusExportOptions {
+                method: libmsa::ConsensusMethod::Majority,
+                threshold: 0.0,
+            },
+            &path,
+            &JobProgress::new(70),
+            &CancellationToken::new(),
+        )
+        .expect("export should succeed");
+
+        let written = std::fs::read_to_string(path).expect("export should be readable");
+        assert_eq!(
+            written,
+            format!(">consensus\n{}\n{}\n", "A".repeat(60), "A".repeat(10))
+        );
+    }
+
+    #[test]
+    fn conservation_format_follows_the_extension() {
+        assert_eq!(
+            ConservationFormat::from_path(Path::new("scores.WIG")),
+            ConservationFormat::Wiggle
+        );
+        assert_eq!(
+            ConservationFormat::from_path(Path::new("scores.txt")),
+            ConservationFormat::Csv
+        );
+    }
+
+    #[test]
+    fn conservation_uses_absolute_positions() {
+        let alignment = alignment(vec![raw("a", b"A-C"), raw("b", b"A-C")]);
+        let filtered = alignment
+            .filter()
+            .expect("filter should build")
+            .with_max_gap_fraction(0.0)
+            .apply()
+            .expect("filter should apply");
+        let summaries = filtered
+            .column_summaries_range(0..2, libmsa::ConsensusMethod::Majority)
+            .expect("summaries should compute");
+
+        let rows = conservation_rows(&filtered, &summaries).expect("rows should resolve");
+
+        assert_eq!(rows, vec![(1, 1.0), (3, 1.0)]);
+    }
+
+    #[test]
+    fn conservation_is_written_as_csv_or_wiggle() {
+        let rows = [(1, 1.0), (3, 0.25)];
+
+        assert_eq!(
+            format_conservation(&rows, ConservationFormat::Csv),
+            "position,conservation\n1,1.0000\n3,0.2500\n"
+        );
+        assert_eq!(
+            format_conservation(&rows, ConservationFormat::Wiggle),
+            "track type=wiggle_0 name=\"salti conservation\"\nvariableStep chrom=alignment\n1 1.0000\n3 0.2500\n"
+        );
+    }
+
+    #[test]
+    fn snp_distances_respect_the_ignore_mode() {
+        assert_eq!(snp_distance(b"AC-T", b"AGAN", SnpIgnore::None, b'N'), 3);
+        assert_eq!(snp_distance(b"AC-T", b"AGAN", SnpIgnore::Gaps, b'N'), 2);
+        assert_eq!(snp_distance(b"AC-T", b"AGAN", SnpIgnore::GapsAndN, b'N'), 1);
+    }
+
+    #[test]
+    fn snp_matrix_is_symmetric_and_written_as_tsv() {
+        let alignment = alignment(vec![
+            raw("a", b"ACGT"),
+            raw("b", b"acgA"),
+            raw("c", b"TCGA"),
+        ]);
+        let rows = visible_rows(&alignment);
+        let progress = JobProgress::new(rows.len());
+
+        let matrix = snp_distance_matrix(
+            &rows,
+            SnpIgnore::default(),
+            b'N',
+            4,
+            &progress,
+            &CancellationToken::new(),
+        )
+        .expect("matrix should compute");
+
+        assert_eq!(matrix, vec![vec![0, 1, 2], vec![1, 0, 1], vec![2, 1, 0]]);
+        assert_eq!(progress.percent(), 100);
+        let single = snp_distance_matrix(
+            &rows,
+            SnpIgnore::default(),
+            b'N',
+            1,
+            &JobProgress::new(rows.len()),
+            &CancellationToken::new(),
+        )
+        .expect("matrix should compute on one thread");
+        assert_eq!(single, matrix);
+        assert_eq!(
+            format_snp_matrix(&["a", "b", "c"], &matrix),
+            "snp-dists\ta\tb\tc\na\t0\t1\t2\nb\t1\t0\t1\nc\t2\t1\t0\n"
+        );
+    }
+
+    #[test]
+    fn snp_workers_follow_the_thread_limit() {
+        assert_eq!(snp_workers(1, 100), 1);
+        assert_eq!(snp_workers(8, 3), 3);
+        assert_eq!(snp_workers(8, 0), 1);
+        assert_eq!(snp_workers(0, 10), 1);
+    }
+
+    #[test]
+    fn conservation_export_rejects_generic_alignments() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let alignment = libmsa::Alignment::new_with_type(
+            vec![raw("a", b"ACGT")],
+            libmsa::AlignmentType::Generic,
+        )
+        .expect("test alignment should be valid");
+
+        let result = export_conservation(
+            &alignment,
+            ConservationFormat::Csv,
+            &dir.path().join("scores.csv"),
+            &JobProgress::new(4),
+            &CancellationToken::new(),
+        );
+
+        assert!(result.is_err());
+    }
+}
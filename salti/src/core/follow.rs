@@ -0,0 +1,135 @@
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, format_err};
+use libmsa::RawSequence;
+use tokio_util::sync::CancellationToken;
+
+use crate::core::parser::{self, ParsedAlignment};
+
+/// tails a local FASTA file that is still being written, handing back records added after the
+/// initial load. a record is complete once the next header follows it, or once the file has
+/// stopped growing and ends with a newline.
+#[derive(Debug)]
+pub struct FileFollower {
+    path: PathBuf,
+    offset: u64,
+    /// bytes read that do not yet form complete records
+    pending: Vec<u8>,
+}
+
+impl FileFollower {
+    /// loads the records written to `path` so far and follows it from the last byte read. a
+    /// trailing record that may still be growing is held back until it is complete, so records
+    /// written during the load are neither skipped nor cut in half.
+    pub fn load(path: PathBuf, cancel: &CancellationToken) -> Result<(ParsedAlignment, Self)> {
+        let mut follower = Self {
+            path,
+            offset: 0,
+            pending: Vec::new(),
+        };
+        follower.read_new_bytes()?;
+        let growing = std::fs::metadata(&follower.path)
+            .is_ok_and(|metadata| metadata.len() != follower.offset);
+        let complete = complete_len(&follower.pending, growing);
+        let records: Vec<u8> = follower.pending.drain(..complete).collect();
+        let parsed = parser::parse_alignment_bytes(&records, cancel)?;
+        Ok((parsed, follower))
+    }
+
+    /// records completed since the last poll, each of which must be `expected_length` long
+    pub fn poll(&mut self, expected_length: usize) -> Result<Vec<RawSequence>> {
+        let grew = self.read_new_bytes()?;
+        let complete = complete_len(&self.pending, grew);
+        if complete == 0 || self.pending[..complete].iter().all(u8::is_ascii_whitespace) {
+            return Ok(Vec::new());
+        }
+        let records: Vec<u8> = self.pending.drain(..complete).collect();
+        parser::parse_fasta_bytes(&records, expected_length)
+    }
+
+    fn read_new_bytes(&mut self) -> Result<bool> {
+        let mut file = std::fs::File::open(&self.path)
+            .with_context(|| format!("failed to open {}", self.path.display()))?;
+        let length = file
+            .metadata()
+            .with_context(|| format!("failed to read {}", self.path.display()))?
+            .len();
+        if length < self.offset {
+            return Err(format_err!("{} was truncated", self.path.display()));
+        }
+        if length == self.offset {
+            return Ok(false);
+        }
+        file.seek(SeekFrom::Start(self.offset))?;
+        let read = file
+            .take(length - self.offset)
+            .read_to_end(&mut self.pending)
+            .with_context(|| format!("failed to read {}", self.path.display()))?;
+        self.offset += read as u64;
+        Ok(read > 0)
+    }
+}
+
+/// bytes of `pending` forming complete records: all of them once the file stopped growing and
+/// ends with a newline, otherwise those before the last header
+fn complete_len(pending: &[u8], growing: bool) -> usize {
+    if !growing && pending.ends_with(b"\n") {
+        return pending.len();
+    }
+    pending
+        .windows(2)
+        .rposition(|pair| pair == b"\n>")
+        .map_or(0, |newline| newline + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn records_are_returned_once_complete() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b">a\nACGT\n").unwrap();
+        let (_, mut follower) =
+            FileFollower::load(file.path().to_path_buf(), &CancellationToken::new()).unwrap();
+
+        file.write_all(b">b\nAC").unwrap();
+        assert!(follower.poll(4).unwrap().is_empty());
+
+        file.write_all(b"GA\n>c\nAC").unwrap();
+        let records = follower.poll(4).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].id, "b");
+        assert_eq!(records[0].sequence, b"ACGA");
+
+        file.write_all(b"TT\n").unwrap();
+        assert!(follower.poll(4).unwrap().is_empty());
+        let records = follower.poll(4).unwrap();
+        assert_eq!(records[0].id, "c");
+
+        file.write_all(b">d\nACG\n>e\n").unwrap();
+        assert!(follower.poll(4).is_err());
+    }
+
+    #[test]
+    fn records_written_during_the_load_are_picked_up() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        // the writer was part way through `b` when the load read the file
+        file.write_all(b">a\nACGT\n>b\nAC").unwrap();
+        let (parsed, mut follower) =
+            FileFollower::load(file.path().to_path_buf(), &CancellationToken::new()).unwrap();
+        assert_eq!(parsed.sequences.len(), 1);
+        assert_eq!(parsed.sequences[0].id, "a");
+
+        file.write_all(b"GT\n>c\nTTTT\n").unwrap();
+        let records = follower.poll(4).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].id, "b");
+        assert_eq!(records[0].sequence, b"ACGT");
+        let records = follower.poll(4).unwrap();
+        assert_eq!(records[0].id, "c");
+    }
+}
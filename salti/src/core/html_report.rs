@@ -0,0 +1,462 @@
+use std::fmt::Write as _;
+use std::ops::Range;
+use std::path::Path;
+
+use anyhow::{Context, Result, format_err};
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier};
+use tokio_util::sync::CancellationToken;
+
+use crate::core::export::{JobProgress, consensus_bytes, format_fasta, full_column_summaries};
+use crate::ui::snapshot::{SnapshotOptions, render_alignment_snapshot};
+use crate::ui::ui_state::MouseSelection;
+
+/// columns counted per step of the variant scan, between progress updates and cancellation checks
+const VARIANT_STEP: usize = 2000;
+/// variant table rows written before the rest are only counted
+const MAX_VARIANT_ROWS: usize = 500;
+/// points in the conservation plot; longer alignments are averaged into this many bins
+const PLOT_BINS: usize = 800;
+const PLOT_HEIGHT: usize = 120;
+/// rows and columns drawn per snapshot region
+const MAX_SNAPSHOT_ROWS: usize = 50;
+const MAX_SNAPSHOT_COLUMNS: usize = 240;
+const SNAPSHOT_ID_WIDTH: usize = 16;
+
+const STYLESHEET: &str = "body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; margin-bottom: 1em; }
+th, td { border: 1px solid #ccc; padding: 0.2em 0.6em; text-align: left; }
+pre { font-family: monospace; line-height: 1.2; }
+pre.snapshot { background: #2d353b; padding: 0.5em; overflow-x: auto; }
+svg { border: 1px solid #ccc; }";
+
+/// a block of the alignment drawn as a coloured snapshot, in absolute rows and columns
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportRegion {
+    pub rows: Range<usize>,
+    pub columns: Range<usize>,
+}
+
+impl From<MouseSelection> for ReportRegion {
+    fn from(selection: MouseSelection) -> Self {
+        let rows = selection.sequence_id.min(selection.end_sequence_id)
+            ..selection.sequence_id.max(selection.end_sequence_id) + 1;
+        let columns = selection.column.min(selection.end_column)
+            ..selection.column.max(selection.end_column) + 1;
+        Self { rows, columns }
+    }
+}
+
+/// what `generate-report` puts in the report besides the alignment-wide sections
+#[derive(Debug, Clone)]
+pub struct ReportOptions {
+    pub title: String,
+    pub method: libmsa::ConsensusMethod,
+    /// absolute row the variant table compares against
+    pub reference: Option<usize>,
+    /// absolute rows compared with the reference
+    pub rows: Vec<usize>,
+    pub regions: Vec<ReportRegion>,
+    /// colours of the snapshots; their rows and columns come from `regions`
+    pub snapshot: SnapshotOptions,
+}
+
+/// a column where at least one row differs from the reference
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Variant {
+    /// 1-based position in the unfiltered alignment
+    position: usize,
+    reference: u8,
+    /// differing residues and how many rows carry them, most common first
+    alternatives: Vec<(u8, usize)>,
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// compares every column of `view` in `rows` against the reference row, ignoring case
+fn find_variants(
+    view: &libmsa::Alignment,
+    reference: usize,
+    rows: &[usize],
+    progress: &JobProgress,
+    cancel: &CancellationToken,
+) -> Result<Vec<Variant>> {
+    let reference = view
+        .project_absolute_row(reference)
+        .ok_or_else(|| format_err!("reference sequence is not in the alignment"))?;
+    let sequences: Vec<_> = rows
+        .iter()
+        .filter_map(|&row| view.project_absolute_row(row))
+        .collect();
+    let mut variants = Vec::new();
+
+    for start in (0..view.column_count()).step_by(VARIANT_STEP) {
+        if cancel.is_cancelled() {
+            return Err(format_err!("Export cancelled"));
+        }
+        let range = start..(start + VARIANT_STEP).min(view.column_count());
+        let mut counts = vec![[0usize; 256]; range.len()];
+        for sequence in &sequences {
+            for (column_counts, col) in counts.iter_mut().zip(range.clone()) {
+                if let Some(byte) = sequence.byte_at(col) {
+                    column_counts[usize::from(byte.to_ascii_uppercase())] += 1;
+                }
+            }
+        }
+        for (column_counts, col) in counts.iter().zip(range.clone()) {
+            let reference_byte = reference.byte_at(col).unwrap_or(b'-').to_ascii_uppercase();
+            let mut alternatives: Vec<(u8, usize)> = (0..=u8::MAX)
+                .zip(column_counts.iter().copied())
+                .filter(|&(byte, count)| count > 0 && byte != reference_byte)
+                .collect();
+            if alternatives.is_empty() {
+                continue;
+            }
+            alternatives.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+            let position = view
+                .absolute_column_id(col)
+                .expect("columns come from the view");
+            variants.push(Variant {
+                position: position + 1,
+                reference: reference_byte,
+                alternatives,
+            });
+        }
+        progress.advance(range.len());
+    }
+    Ok(variants)
+}
+
+/// a line plot of conservation along the alignment, averaged into at most `PLOT_BINS` points
+fn conservation_svg(conservation: &[f32]) -> String {
+    let bin_size = conservation.len().div_ceil(PLOT_BINS).max(1);
+    let bins: Vec<f32> = conservation
+        .chunks(bin_size)
+        .map(|bin| bin.iter().sum::<f32>() / bin.len() as f32)
+        .collect();
+    let mut points = String::new();
+    for (index, value) in bins.iter().enumerate() {
+        let x = index * PLOT_BINS / bins.len().max(1);
+        let y = (1.0 - value.clamp(0.0, 1.0)) * PLOT_HEIGHT as f32;
+        let _ = write!(points, "{x},{y:.1} ");
+    }
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{PLOT_BINS}\" height=\"{PLOT_HEIGHT}\" \
+         viewBox=\"0 0 {PLOT_BINS} {PLOT_HEIGHT}\">\
+         <polyline fill=\"none\" stroke=\"#3a94c5\" stroke-width=\"1\" points=\"{}\"/></svg>",
+        points.trim_end()
+    )
+}
+
+fn css_color(color: Option<Color>) -> Option<String> {
+    match color? {
+        Color::Rgb(red, green, blue) => Some(format!("#{red:02x}{green:02x}{blue:02x}")),
+        _ => None,
+    }
+}
+
+/// writes a snapshot as HTML, one `<span>` per run of equally styled cells
+fn snapshot_to_html(buffer: &Buffer) -> String {
+    let mut html = String::new();
+    for y in buffer.area.top()..buffer.area.bottom() {
+        let mut run = String::new();
+        let mut run_style = None;
+        for x in buffer.area.left()..buffer.area.right() {
+            let cell = &buffer[(x, y)];
+            let style = cell.style();
+            if run_style != Some(style) {
+                if let Some(style) = run_style {
+                    push_span(&mut html, &run, style);
+                }
+                run.clear();
+                run_style = Some(style);
+            }
+            run.push_str(cell.symbol());
+        }
+        if let Some(style) = run_style {
+            push_span(&mut html, &run, style);
+        }
+        html.push('\n');
+    }
+    html
+}
+
+fn push_span(html: &mut String, text: &str, style: ratatui::style::Style) {
+    let mut css = String::new();
+    if let Some(color) = css_color(style.fg) {
+        let _ = write!(css, "color:{color};");
+    }
+    if let Some(color) = css_color(style.bg) {
+        let _ = write!(css, "background:{color};");
+    }
+    if style.add_modifier.contains(Modifier::BOLD) {
+        css.push_str("font-weight:bold;");
+    }
+    if css.is_empty() {
+        html.push_str(&escape_html(text));
+    } else {
+        let _ = write!(html, "<span style=\"{css}\">{}</span>", escape_html(text));
+    }
+}
+
+fn write_overview(
+    html: &mut String,
+    view: &libmsa::Alignment,
+    options: &ReportOptions,
+    summaries: &[libmsa::ColumnSummary],
+) {
+    let columns = summaries.len().max(1) as f32;
+    let gap_only = summaries.iter().filter(|summary| summary.gap_only).count();
+    let mean_gaps = summaries
+        .iter()
+        .map(|summary| summary.gap_fraction)
+        .sum::<f32>()
+        / columns;
+    let mut rows = vec![
+        ("Alignment type", view.active_type().to_string()),
+        ("Sequences", options.rows.len().to_string()),
+        ("Columns", view.column_count().to_string()),
+        ("Gap-only columns", gap_only.to_string()),
+        ("Mean gap fraction", format!("{:.1}%", mean_gaps * 100.0)),
+        ("Consensus method", options.method.to_string()),
+    ];
+    let conservation: Vec<f32> = summaries
+        .iter()
+        .filter_map(|summary| summary.conservation)
+        .collect();
+    if !conservation.is_empty() {
+        let mean = conservation.iter().sum::<f32>() / conservation.len() as f32;
+        rows.push(("Mean conservation", format!("{mean:.3}")));
+    }
+
+    html.push_str("<h2>Overview</h2>\n<table>\n");
+    for (label, value) in rows {
+        let _ = writeln!(
+            html,
+            "<tr><th>{label}</th><td>{}</td></tr>",
+            escape_html(&value)
+        );
+    }
+    html.push_str("</table>\n");
+}
+
+fn write_conservation(html: &mut String, summaries: &[libmsa::ColumnSummary]) {
+    html.push_str("<h2>Conservation</h2>\n");
+    let conservation: Option<Vec<f32>> = summaries
+        .iter()
+        .map(|summary| summary.conservation)
+        .collect();
+    match conservation {
+        Some(conservation) if !conservation.is_empty() => {
+            html.push_str(&conservation_svg(&conservation));
+            html.push('\n');
+        }
+        _ => html.push_str("<p>Conservation is not defined for this alignment type.</p>\n"),
+    }
+}
+
+fn write_variants(html: &mut String, reference_id: Option<&str>, variants: Option<&[Variant]>) {
+    html.push_str("<h2>Variants</h2>\n");
+    let (Some(reference_id), Some(variants)) = (reference_id, variants) else {
+        html.push_str(
+            "<p>No reference is set. Use <code>set-reference</code> to list variants.</p>\n",
+        );
+        return;
+    };
+    let _ = writeln!(
+        html,
+        "<p>{} variable columns against {}.</p>",
+        variants.len(),
+        escape_html(reference_id)
+    );
+    html.push_str("<table>\n<tr><th>Position</th><th>Reference</th><th>Alternatives</th></tr>\n");
+    for variant in variants.iter().take(MAX_VARIANT_ROWS) {
+        let alternatives = variant
+            .alternatives
+            .iter()
+            .map(|&(byte, count)| format!("{} ({count})", char::from(byte)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+            variant.position,
+            escape_html(&char::from(variant.reference).to_string()),
+            escape_html(&alternatives)
+        );
+    }
+    html.push_str("</table>\n");
+    if variants.len() > MAX_VARIANT_ROWS {
+        let _ = writeln!(
+            html,
+            "<p>{} more variable columns are not listed.</p>",
+            variants.len() - MAX_VARIANT_ROWS
+        );
+    }
+}
+
+fn write_snapshots(html: &mut String, base: &libmsa::Alignment, options: &ReportOptions) {
+    html.push_str("<h2>Regions</h2>\n");
+    if options.regions.is_empty() {
+        html.push_str("<p>No regions were selected.</p>\n");
+    }
+    for region in &options.regions {
+        let rows = region.rows.start..region.rows.end.min(region.rows.start + MAX_SNAPSHOT_ROWS);
+        let columns = region.columns.start
+            ..region
+                .columns
+                .end
+                .min(region.columns.start + MAX_SNAPSHOT_COLUMNS);
+        let _ = writeln!(
+            html,
+            "<h3>Columns {}-{}</h3>",
+            columns.start + 1,
+            columns.end
+        );
+        let buffer = render_alignment_snapshot(
+            base,
+            &SnapshotOptions {
+                columns: Some(columns),
+                rows: Some(rows),
+                id_width: SNAPSHOT_ID_WIDTH,
+                ..options.snapshot.clone()
+            },
+        );
+        let _ = writeln!(
+            html,
+            "<pre class=\"snapshot\">{}</pre>",
+            snapshot_to_html(&buffer)
+        );
+    }
+}
+
+/// assembles the report as a single HTML page with inline styles and an inline SVG plot
+fn build_report(
+    view: &libmsa::Alignment,
+    base: &libmsa::Alignment,
+    options: &ReportOptions,
+    progress: &JobProgress,
+    cancel: &CancellationToken,
+) -> Result<String> {
+    let summaries = full_column_summaries(view, options.method, progress, cancel)?;
+    let variants = options
+        .reference
+        .map(|reference| find_variants(view, reference, &options.rows, progress, cancel))
+        .transpose()?;
+    let reference_id = options
+        .reference
+        .and_then(|reference| base.sequence_by_absolute(reference))
+        .map(|sequence| sequence.id().to_string());
+    let consensus = consensus_bytes(&summaries, 0.0, view.active_type());
+
+    let title = escape_html(&options.title);
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+         <style>\n{STYLESHEET}\n</style>\n</head>\n<body>\n<h1>{title}</h1>\n"
+    );
+    write_overview(&mut html, view, options, &summaries);
+    write_conservation(&mut html, &summaries);
+    html.push_str("<h2>Consensus</h2>\n<pre>");
+    html.push_str(&escape_html(&format_fasta("consensus", &consensus)));
+    html.push_str("</pre>\n");
+    write_variants(&mut html, reference_id.as_deref(), variants.as_deref());
+    write_snapshots(&mut html, base, options);
+    html.push_str("</body>\n</html>\n");
+    Ok(html)
+}
+
+/// writes an HTML report of the visible alignment to `path`: overview stats, a conservation
+/// plot, the consensus, variants against the reference and snapshots of the chosen regions
+pub fn generate_report(
+    view: &libmsa::Alignment,
+    base: &libmsa::Alignment,
+    options: &ReportOptions,
+    path: &Path,
+    progress: &JobProgress,
+    cancel: &CancellationToken,
+) -> Result<()> {
+    let html = build_report(view, base, options, progress, cancel)?;
+    std::fs::write(path, html).with_context(|| format!("failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw(id: &str, sequence: &[u8]) -> libmsa::RawSequence {
+        libmsa::RawSequence {
+            id: id.to_string(),
+            sequence: sequence.to_vec(),
+            quality: None,
+        }
+    }
+
+    #[test]
+    fn report_lists_variants_and_draws_regions() {
+        let alignment = libmsa::Alignment::new(vec![
+            raw("ref<1>", b"ACGTAC"),
+            raw("a", b"ACTTAC"),
+            raw("b", b"ACTTAG"),
+        ])
+        .expect("alignment should be valid");
+        let options = ReportOptions {
+            title: "demo".to_string(),
+            method: libmsa::ConsensusMethod::default(),
+            reference: Some(0),
+            rows: vec![1, 2],
+            regions: vec![ReportRegion {
+                rows: 0..3,
+                columns: 1..4,
+            }],
+            snapshot: SnapshotOptions::default(),
+        };
+
+        let html = build_report(
+            &alignment,
+            &alignment,
+            &options,
+            &JobProgress::new(12),
+            &CancellationToken::new(),
+        )
+        .expect("report should build");
+
+        assert!(html.contains("<title>demo</title>"));
+        assert!(html.contains("2 variable columns against ref&lt;1&gt;."));
+        assert!(html.contains("<tr><td>3</td><td>G</td><td>T (2)</td></tr>"));
+        assert!(html.contains("<tr><td>6</td><td>C</td><td>G (1)</td></tr>"));
+        assert!(html.contains("<polyline"));
+        assert!(html.contains("&gt;consensus\nACTTAC\n"));
+        assert!(html.contains("<h3>Columns 2-4</h3>"));
+        assert!(html.contains("<span style=\"color:"));
+    }
+
+    #[test]
+    fn selections_become_inclusive_regions() {
+        let region = ReportRegion::from(MouseSelection {
+            sequence_id: 4,
+            column: 20,
+            end_sequence_id: 1,
+            end_column: 10,
+        });
+
+        assert_eq!(
+            region,
+            ReportRegion {
+                rows: 1..5,
+                columns: 10..21,
+            }
+        );
+    }
+}
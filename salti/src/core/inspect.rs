@@ -0,0 +1,187 @@
+use std::fmt::Write as _;
+
+/// widest composition bar, in characters
+const COMPOSITION_BAR_WIDTH: usize = 30;
+
+/// per-sequence statistics shown by `inspect`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequenceStats {
+    pub alignment_type: libmsa::AlignmentType,
+    /// residues, excluding gaps
+    pub length: usize,
+    pub gaps: usize,
+    /// `N` in nucleotide alignments, `X` otherwise
+    pub ambiguous: usize,
+    /// residue counts, uppercased and sorted by residue
+    pub composition: Vec<(u8, usize)>,
+    /// label of the compared sequence and the number of columns that differ from it
+    pub differences: Option<(String, usize)>,
+}
+
+const fn is_gap(byte: u8) -> bool {
+    matches!(byte, b'-' | b'.')
+}
+
+const fn ambiguity_byte(alignment_type: libmsa::AlignmentType) -> u8 {
+    match alignment_type {
+        libmsa::AlignmentType::Dna | libmsa::AlignmentType::Rna => b'N',
+        libmsa::AlignmentType::Protein | libmsa::AlignmentType::Generic => b'X',
+    }
+}
+
+/// summarises an aligned sequence. differences count columns where both sequences have a
+/// residue, neither is ambiguous, and the residues differ ignoring case.
+pub fn sequence_stats(
+    bytes: &[u8],
+    alignment_type: libmsa::AlignmentType,
+    compare: Option<(String, &[u8])>,
+) -> SequenceStats {
+    let ambiguity = ambiguity_byte(alignment_type);
+    let mut counts = [0usize; 256];
+    let mut gaps = 0;
+    for &byte in bytes {
+        if is_gap(byte) {
+            gaps += 1;
+        } else {
+            counts[usize::from(byte.to_ascii_uppercase())] += 1;
+        }
+    }
+
+    let differences = compare.map(|(label, other)| {
+        let differing = bytes
+            .iter()
+            .zip(other)
+            .map(|(left, right)| (left.to_ascii_uppercase(), right.to_ascii_uppercase()))
+            .filter(|&(left, right)| {
+                !is_gap(left)
+                    && !is_gap(right)
+                    && left != ambiguity
+                    && right != ambiguity
+                    && left != right
+            })
+            .count();
+        (label, differing)
+    });
+
+    SequenceStats {
+        alignment_type,
+        length: bytes.len() - gaps,
+        gaps,
+        ambiguous: counts[usize::from(ambiguity)],
+        composition: (0..=u8::MAX)
+            .filter(|&byte| counts[usize::from(byte)] > 0)
+            .map(|byte| (byte, counts[usize::from(byte)]))
+            .collect(),
+        differences,
+    }
+}
+
+fn percent(count: usize, total: usize) -> f32 {
+    if total == 0 {
+        return 0.0;
+    }
+    count as f32 / total as f32 * 100.0
+}
+
+impl SequenceStats {
+    /// GC content of unambiguous bases, for nucleotide sequences
+    pub fn gc_percent(&self) -> Option<f32> {
+        if !self.alignment_type.is_nucleotide() {
+            return None;
+        }
+        let count = |bases: &[u8]| -> usize {
+            self.composition
+                .iter()
+                .filter(|(residue, _)| bases.contains(residue))
+                .map(|&(_, count)| count)
+                .sum()
+        };
+        Some(percent(count(b"GCS"), count(b"ACGTUSW")))
+    }
+
+    /// report lines, ending with an ASCII bar per residue
+    pub fn lines(&self) -> Vec<String> {
+        let columns = self.length + self.gaps;
+        let mut lines = vec![
+            format!("Length: {} residues without gaps", self.length),
+            format!("Gaps: {} ({:.1}%)", self.gaps, percent(self.gaps, columns)),
+            format!(
+                "{}: {} ({:.1}%)",
+                char::from(ambiguity_byte(self.alignment_type)),
+                self.ambiguous,
+                percent(self.ambiguous, self.length)
+            ),
+        ];
+        if let Some(gc) = self.gc_percent() {
+            lines.push(format!("GC content: {gc:.1}%"));
+        }
+        if let Some((label, differences)) = &self.differences {
+            lines.push(format!("Differences from {label}: {differences}"));
+        }
+
+        lines.push("Composition:".to_string());
+        let most = self
+            .composition
+            .iter()
+            .map(|&(_, count)| count)
+            .max()
+            .unwrap_or(0);
+        for &(residue, count) in &self.composition {
+            let width = (count * COMPOSITION_BAR_WIDTH).div_ceil(most.max(1));
+            let mut line = format!("  {} ", char::from(residue));
+            line.push_str(&"█".repeat(width));
+            line.push_str(&" ".repeat(COMPOSITION_BAR_WIDTH - width));
+            let _ = write!(line, " {:5.1}%", percent(count, self.length));
+            lines.push(line);
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nucleotide_stats_count_gaps_n_and_gc() {
+        let stats = sequence_stats(
+            b"ACGG-NNa-t",
+            libmsa::AlignmentType::Dna,
+            Some(("consensus".to_string(), b"ACGTTAAAAA")),
+        );
+
+        assert_eq!(stats.length, 8);
+        assert_eq!(stats.gaps, 2);
+        assert_eq!(stats.ambiguous, 2);
+        assert_eq!(
+            stats.composition,
+            vec![(b'A', 2), (b'C', 1), (b'G', 2), (b'N', 2), (b'T', 1)]
+        );
+        assert_eq!(stats.gc_percent(), Some(50.0));
+        // column 3 (G vs T) and column 9 (t vs A) differ; gaps and N are skipped
+        assert_eq!(stats.differences, Some(("consensus".to_string(), 2)));
+    }
+
+    #[test]
+    fn composition_bars_scale_to_the_most_common_residue() {
+        let stats = sequence_stats(b"AAAC", libmsa::AlignmentType::Dna, None);
+
+        let lines = stats.lines();
+
+        assert_eq!(lines[3], "GC content: 25.0%");
+        assert_eq!(lines[5], format!("  A {} {:5.1}%", "█".repeat(30), 75.0));
+        assert_eq!(
+            lines[6],
+            format!("  C {}{} {:5.1}%", "█".repeat(10), " ".repeat(20), 25.0)
+        );
+    }
+
+    #[test]
+    fn protein_stats_have_no_gc_content() {
+        let stats = sequence_stats(b"MKX-", libmsa::AlignmentType::Protein, None);
+
+        assert_eq!(stats.ambiguous, 1);
+        assert_eq!(stats.gc_percent(), None);
+        assert!(stats.lines().iter().all(|line| !line.starts_with("GC")));
+    }
+}
@@ -0,0 +1,208 @@
+use std::io::Read;
+use std::num::NonZeroUsize;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, format_err};
+use tokio_util::sync::CancellationToken;
+
+/// how often a running subprocess is checked for exit, cancellation and the timeout
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// caps how many blocking background jobs (parsing, column stats, analyses) run at once, so a
+/// shared machine is not flooded with one busy thread per job
+#[derive(Debug, Clone)]
+pub struct WorkerLimit {
+    threads: usize,
+    running: Arc<(Mutex<usize>, Condvar)>,
+}
+
+impl WorkerLimit {
+    pub fn new(threads: usize) -> Self {
+        Self {
+            threads: threads.max(1),
+            running: Arc::new((Mutex::new(0), Condvar::new())),
+        }
+    }
+
+    pub const fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// wraps `job` so it waits for a free worker before running
+    pub fn wrap<R>(
+        &self,
+        job: impl FnOnce() -> R + Send + 'static,
+    ) -> impl FnOnce() -> R + Send + 'static {
+        let limit = self.clone();
+        move || {
+            let _worker = limit.acquire();
+            job()
+        }
+    }
+
+    fn acquire(&self) -> Worker<'_> {
+        let (running, freed) = &*self.running;
+        let mut count = running.lock().unwrap_or_else(|error| error.into_inner());
+        while *count >= self.threads {
+            count = freed.wait(count).unwrap_or_else(|error| error.into_inner());
+        }
+        *count += 1;
+        Worker { limit: self }
+    }
+}
+
+impl Default for WorkerLimit {
+    /// one worker per available CPU
+    fn default() -> Self {
+        Self::new(std::thread::available_parallelism().map_or(1, NonZeroUsize::get))
+    }
+}
+
+/// a running job's claim on a worker, handed back when dropped
+struct Worker<'a> {
+    limit: &'a WorkerLimit,
+}
+
+impl Drop for Worker<'_> {
+    fn drop(&mut self) {
+        let (running, freed) = &*self.limit.running;
+        let mut count = running.lock().unwrap_or_else(|error| error.into_inner());
+        *count -= 1;
+        freed.notify_one();
+    }
+}
+
+/// runs `command` to completion and returns its stdout. the process is killed when `cancel`
+/// fires or it runs past `timeout`, and a failed exit is reported with the last line it wrote
+/// to stderr.
+pub fn run_with_timeout(
+    mut command: Command,
+    timeout: Duration,
+    cancel: &CancellationToken,
+) -> Result<Vec<u8>> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| match error.kind() {
+            std::io::ErrorKind::NotFound => {
+                format_err!("{program} is not installed or not on PATH")
+            }
+            _ => format_err!("could not start {program}: {error}"),
+        })?;
+    // drained on their own threads so a chatty process cannot fill a pipe and stall
+    let drain = |pipe: Option<Box<dyn Read + Send>>| {
+        thread::spawn(move || {
+            let mut bytes = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut bytes);
+            }
+            bytes
+        })
+    };
+    let stdout = drain(child.stdout.take().map(|pipe| Box::new(pipe) as _));
+    let stderr = drain(child.stderr.take().map(|pipe| Box::new(pipe) as _));
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        let stopped = if cancel.is_cancelled() {
+            Some(format_err!("{program} cancelled"))
+        } else if started.elapsed() >= timeout {
+            Some(format_err!("{program} did not finish within {timeout:?}"))
+        } else {
+            None
+        };
+        if let Some(error) = stopped {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(error);
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    if !status.success() {
+        let stderr = String::from_utf8_lossy(&stderr);
+        let detail = stderr
+            .lines()
+            .rev()
+            .find(|line| !line.trim().is_empty())
+            .unwrap_or("no error output");
+        return Err(format_err!(
+            "{program} failed ({status}): {}",
+            detail.trim()
+        ));
+    }
+    Ok(stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[test]
+    fn jobs_never_exceed_the_worker_count() {
+        let limit = WorkerLimit::new(2);
+        let active = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..6)
+            .map(|_| {
+                let active = Arc::clone(&active);
+                let peak = Arc::clone(&peak);
+                std::thread::spawn(limit.wrap(move || {
+                    let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(20));
+                    active.fetch_sub(1, Ordering::SeqCst);
+                }))
+            })
+            .collect();
+        for handle in handles {
+            handle.join().expect("job should finish");
+        }
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn zero_threads_still_allows_one_worker() {
+        let limit = WorkerLimit::new(0);
+
+        assert_eq!(limit.threads(), 1);
+        assert_eq!(limit.wrap(|| 7)(), 7);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn processes_are_stopped_at_the_timeout_and_report_failures() {
+        let cancel = CancellationToken::new();
+        let mut sleep = Command::new("sleep");
+        sleep.arg("5");
+        let error = run_with_timeout(sleep, Duration::from_millis(100), &cancel).unwrap_err();
+        assert_eq!(error.to_string(), "sleep did not finish within 100ms");
+
+        let mut failing = Command::new("sh");
+        failing.args(["-c", "echo bad input >&2; exit 3"]);
+        let error = run_with_timeout(failing, Duration::from_secs(5), &cancel).unwrap_err();
+        assert!(error.to_string().ends_with(": bad input"));
+
+        let missing = Command::new("salti-no-such-tool");
+        let error = run_with_timeout(missing, Duration::from_secs(5), &cancel).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "salti-no-such-tool is not installed or not on PATH"
+        );
+    }
+}
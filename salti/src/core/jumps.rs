@@ -0,0 +1,105 @@
+use crate::core::marks::Mark;
+
+/// most positions the jump history keeps; the oldest are dropped first
+pub const JUMP_HISTORY_LIMIT: usize = 100;
+
+/// viewport positions left by jump commands, stepped through with `back` and `forward` like an
+/// editor's jump list. positions are absolute ids, like marks.
+#[derive(Debug, Default)]
+pub struct JumpHistory {
+    positions: Vec<Mark>,
+    /// index of the position last returned by `back` or `forward`, or the length of `positions`
+    /// when the viewport is past the newest one
+    cursor: usize,
+}
+
+impl JumpHistory {
+    /// remembers `from`, the position a jump left. positions ahead of the cursor are dropped,
+    /// as a new jump starts a new branch.
+    pub fn record(&mut self, from: Mark) {
+        self.positions.truncate(self.cursor);
+        if self.positions.last() != Some(&from) {
+            self.positions.push(from);
+        }
+        let excess = self.positions.len().saturating_sub(JUMP_HISTORY_LIMIT);
+        self.positions.drain(..excess);
+        self.cursor = self.positions.len();
+    }
+
+    /// the position before the current one. `current` is kept so `forward` can return to it.
+    pub fn back(&mut self, current: Mark) -> Option<Mark> {
+        if self.cursor == self.positions.len() {
+            if self.positions.last() != Some(&current) {
+                self.positions.push(current);
+            }
+            self.cursor = self.positions.len() - 1;
+        }
+        self.cursor = self.cursor.checked_sub(1)?;
+        Some(self.positions[self.cursor])
+    }
+
+    /// the position after the current one, when `back` was used since the last jump
+    pub fn forward(&mut self) -> Option<Mark> {
+        if self.cursor + 1 >= self.positions.len() {
+            return None;
+        }
+        self.cursor += 1;
+        Some(self.positions[self.cursor])
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(column: usize) -> Mark {
+        Mark { row: 0, column }
+    }
+
+    #[test]
+    fn back_and_forward_walk_the_jumps() {
+        let mut history = JumpHistory::default();
+        history.record(at(1));
+        history.record(at(2));
+
+        assert_eq!(history.back(at(3)), Some(at(2)));
+        assert_eq!(history.back(at(2)), Some(at(1)));
+        assert_eq!(history.back(at(1)), None);
+        assert_eq!(history.forward(), Some(at(2)));
+        assert_eq!(history.forward(), Some(at(3)));
+        assert_eq!(history.forward(), None);
+    }
+
+    #[test]
+    fn a_new_jump_drops_the_positions_ahead() {
+        let mut history = JumpHistory::default();
+        history.record(at(1));
+        history.record(at(2));
+        assert_eq!(history.back(at(3)), Some(at(2)));
+
+        history.record(at(2));
+        assert_eq!(history.forward(), None);
+        assert_eq!(history.back(at(4)), Some(at(2)));
+        assert_eq!(history.back(at(2)), Some(at(1)));
+    }
+
+    #[test]
+    fn the_oldest_positions_are_dropped_past_the_limit() {
+        let mut history = JumpHistory::default();
+        for column in 0..JUMP_HISTORY_LIMIT + 5 {
+            history.record(at(column));
+        }
+
+        let mut oldest = None;
+        let mut current = at(JUMP_HISTORY_LIMIT + 5);
+        while let Some(position) = history.back(current) {
+            oldest = Some(position);
+            current = position;
+        }
+        assert_eq!(oldest, Some(at(5)));
+    }
+}
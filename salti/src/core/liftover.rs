@@ -0,0 +1,148 @@
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+use anyhow::format_err;
+
+const fn is_gap(byte: u8) -> bool {
+    matches!(byte, b'-' | b'.')
+}
+
+/// coordinate system a position given to `convert-position` is counted in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PositionSource {
+    /// alignment column
+    #[default]
+    Column,
+    /// ungapped position in the reference sequence
+    Reference,
+    /// ungapped position in a named sequence
+    Sequence,
+}
+
+impl PositionSource {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Column => "column",
+            Self::Reference => "reference",
+            Self::Sequence => "sequence",
+        }
+    }
+
+    pub const fn all() -> [Self; 3] {
+        [Self::Column, Self::Reference, Self::Sequence]
+    }
+}
+
+impl fmt::Display for PositionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for PositionSource {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|source| source.name() == value)
+            .ok_or_else(|| format_err!("invalid position source: {value}"))
+    }
+}
+
+/// where an alignment column falls in one sequence. positions are 1-based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequencePosition {
+    Residue(usize),
+    /// a gap, after this many residues
+    Gap {
+        after: usize,
+    },
+}
+
+impl fmt::Display for SequencePosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Residue(position) => write!(f, "{position}"),
+            Self::Gap { after: 0 } => f.write_str("gap before 1"),
+            Self::Gap { after } => write!(f, "gap after {after}"),
+        }
+    }
+}
+
+/// alignment column of every residue of one sequence, for moving between alignment columns and
+/// ungapped positions
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResidueIndex {
+    columns: Vec<usize>,
+}
+
+impl ResidueIndex {
+    pub fn new(bytes: impl IntoIterator<Item = u8>) -> Self {
+        let columns = bytes
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, byte)| !is_gap(byte))
+            .map(|(col, _)| col)
+            .collect();
+        Self { columns }
+    }
+
+    /// alignment column of each residue, in order
+    pub fn columns(&self) -> &[usize] {
+        &self.columns
+    }
+
+    /// 0-based residues that lie within the alignment `columns`
+    pub fn residues_in(&self, columns: Range<usize>) -> Range<usize> {
+        let start = self.columns.partition_point(|&col| col < columns.start);
+        let end = self.columns.partition_point(|&col| col < columns.end);
+        start..end.max(start)
+    }
+
+    /// position of `col` in the ungapped sequence
+    pub fn position_at(&self, col: usize) -> SequencePosition {
+        let before = self
+            .columns
+            .partition_point(|&residue_col| residue_col < col);
+        if self.columns.get(before) == Some(&col) {
+            SequencePosition::Residue(before + 1)
+        } else {
+            SequencePosition::Gap { after: before }
+        }
+    }
+
+    /// alignment column holding the residue at 1-based `position`
+    pub fn column_of(&self, position: usize) -> Option<usize> {
+        self.columns.get(position.checked_sub(1)?).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn columns_and_ungapped_positions_round_trip() {
+        let index = ResidueIndex::new(b"--AC-G.T".iter().copied());
+
+        assert_eq!(index.position_at(0), SequencePosition::Gap { after: 0 });
+        assert_eq!(index.position_at(2), SequencePosition::Residue(1));
+        assert_eq!(index.position_at(4), SequencePosition::Gap { after: 2 });
+        assert_eq!(index.position_at(7), SequencePosition::Residue(4));
+        assert_eq!(index.column_of(3), Some(5));
+        assert_eq!(index.column_of(0), None);
+        assert_eq!(index.column_of(5), None);
+        assert_eq!(index.residues_in(1..5), 0..2);
+        assert_eq!(index.residues_in(4..5), 2..2);
+        assert_eq!(
+            SequencePosition::Gap { after: 0 }.to_string(),
+            "gap before 1"
+        );
+        assert_eq!(
+            SequencePosition::Gap { after: 2 }.to_string(),
+            "gap after 2"
+        );
+    }
+}
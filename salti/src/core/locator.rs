@@ -0,0 +1,170 @@
+use std::fmt;
+
+use anyhow::{Result, format_err};
+
+pub const LOCATOR_SCHEME: &str = "salti://";
+
+/// a view of an input that can be shared as a `salti://` string and restored from the command
+/// line, e.g. `salti://data/cov.fasta?pos=1234&row=seqB&ref=seqA&filter=^MN`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Locator {
+    pub input: String,
+    /// first visible alignment column, 1-based
+    pub position: Option<usize>,
+    /// ID of the first visible sequence
+    pub row: Option<String>,
+    /// ID of the reference sequence
+    pub reference: Option<String>,
+    /// row filter regex
+    pub filter: Option<String>,
+}
+
+impl Locator {
+    pub fn is_locator(input: &str) -> bool {
+        input.starts_with(LOCATOR_SCHEME)
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let rest = text
+            .strip_prefix(LOCATOR_SCHEME)
+            .ok_or_else(|| format_err!("locator must start with {LOCATOR_SCHEME}"))?;
+        let (input, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let input = decode(input)?;
+        if input.is_empty() {
+            return Err(format_err!("locator does not name an input"));
+        }
+
+        let mut locator = Self {
+            input,
+            ..Self::default()
+        };
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format_err!("locator field has no value: {pair}"))?;
+            let value = decode(value)?;
+            match key {
+                "pos" => {
+                    let position = value
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|&position| position > 0)
+                        .ok_or_else(|| format_err!("invalid locator position: {value}"))?;
+                    locator.position = Some(position);
+                }
+                "row" => locator.row = Some(value),
+                "ref" => locator.reference = Some(value),
+                "filter" => locator.filter = Some(value),
+                _ => return Err(format_err!("unknown locator field: {key}")),
+            }
+        }
+        Ok(locator)
+    }
+}
+
+impl fmt::Display for Locator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{LOCATOR_SCHEME}{}", encode(&self.input, "/:"))?;
+        let fields = [
+            ("pos", self.position.map(|position| position.to_string())),
+            ("row", self.row.clone()),
+            ("ref", self.reference.clone()),
+            ("filter", self.filter.clone()),
+        ];
+        let mut separator = '?';
+        for (key, value) in fields {
+            if let Some(value) = value {
+                write!(f, "{separator}{key}={}", encode(&value, ""))?;
+                separator = '&';
+            }
+        }
+        Ok(())
+    }
+}
+
+/// percent-encodes everything but unreserved characters and those in `keep`
+fn encode(value: &str, keep: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric()
+            || b"-._~".contains(&byte)
+            || keep.as_bytes().contains(&byte)
+        {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+fn decode(value: &str) -> Result<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let byte = value
+                .get(index + 1..index + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| format_err!("invalid escape in locator: {value}"))?;
+            decoded.push(byte);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| format_err!("locator is not valid UTF-8"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locators_round_trip_through_their_string_form() {
+        let locator = Locator {
+            input: "/data/my alignment.fasta".to_string(),
+            position: Some(1234),
+            row: Some("seq B".to_string()),
+            reference: Some("MN908947.3".to_string()),
+            filter: Some("^(A|B)&x=1".to_string()),
+        };
+
+        let text = locator.to_string();
+
+        assert_eq!(
+            text,
+            "salti:///data/my%20alignment.fasta?pos=1234&row=seq%20B&ref=MN908947.3\
+             &filter=%5E%28A%7CB%29%26x%3D1"
+        );
+        assert_eq!(
+            Locator::parse(&text).expect("locator should parse"),
+            locator
+        );
+    }
+
+    #[test]
+    fn fields_are_optional() {
+        let locator = Locator::parse("salti://cov.fasta").expect("locator should parse");
+
+        assert_eq!(locator.input, "cov.fasta");
+        assert_eq!(locator.position, None);
+        assert_eq!(locator.to_string(), "salti://cov.fasta");
+    }
+
+    #[test]
+    fn malformed_locators_are_rejected() {
+        for (text, message) in [
+            ("cov.fasta", "locator must start with salti://"),
+            ("salti://?pos=1", "locator does not name an input"),
+            ("salti://a.fa?pos=0", "invalid locator position: 0"),
+            ("salti://a.fa?zoom=2", "unknown locator field: zoom"),
+            ("salti://a.fa?ref=%4", "invalid escape in locator: %4"),
+        ] {
+            let error = Locator::parse(text).expect_err("locator should be rejected");
+            assert_eq!(error.to_string(), message);
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use std::fmt;
+
+/// one NCBI genetic code, as listed at <https://www.ncbi.nlm.nih.gov/Taxonomy/Utils/wprintgc.cgi>
+#[derive(Debug, PartialEq, Eq)]
+pub struct GeneticCode {
+    /// NCBI translation table number
+    pub id: u8,
+    /// short name accepted by `set-genetic-code`
+    pub key: &'static str,
+    pub name: &'static str,
+    /// amino acid for each codon, in NCBI order (`TCAG` at each codon position)
+    amino_acids: &'static [u8; 64],
+    /// `M` for codons that can start translation, in the same order
+    starts: &'static [u8; 64],
+}
+
+/// tables with a fixed meaning for every codon. tables 27, 28 and 31, where a stop codon can
+/// also be read as an amino acid, are left out.
+pub const GENETIC_CODES: &[GeneticCode] = &[
+    GeneticCode {
+        id: 1,
+        key: "standard",
+        name: "Standard",
+        amino_acids: b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"---M------**--*----M---------------M----------------------------",
+    },
+    GeneticCode {
+        id: 2,
+        key: "vertebrate-mito",
+        name: "Vertebrate Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSS**VVVVAAAADDEEGGGG",
+        starts: b"----------**--------------------MMMM----------**---M------------",
+    },
+    GeneticCode {
+        id: 3,
+        key: "yeast-mito",
+        name: "Yeast Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWTTTTPPPPHHQQRRRRIIMMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"----------**----------------------MM---------------M------------",
+    },
+    GeneticCode {
+        id: 4,
+        key: "mold-mito",
+        name: "Mold, Protozoan and Coelenterate Mitochondrial; Mycoplasma and Spiroplasma",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"--MM------**-------M------------MMMM---------------M------------",
+    },
+    GeneticCode {
+        id: 5,
+        key: "invertebrate-mito",
+        name: "Invertebrate Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSSSVVVVAAAADDEEGGGG",
+        starts: b"---M------**--------------------MMMM---------------M------------",
+    },
+    GeneticCode {
+        id: 6,
+        key: "ciliate",
+        name: "Ciliate, Dasycladacean and Hexamita Nuclear",
+        amino_acids: b"FFLLSSSSYYQQCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"--------------*--------------------M----------------------------",
+    },
+    GeneticCode {
+        id: 9,
+        key: "echinoderm-mito",
+        name: "Echinoderm and Flatworm Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+        starts: b"-----------------------------------M---------------M------------",
+    },
+    GeneticCode {
+        id: 10,
+        key: "euplotid",
+        name: "Euplotid Nuclear",
+        amino_acids: b"FFLLSSSSYY**CCCWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"---M------**-----------------------M----------------------------",
+    },
+    GeneticCode {
+        id: 11,
+        key: "bacterial",
+        name: "Bacterial, Archaeal and Plant Plastid",
+        amino_acids: b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"---M------**--*----M------------MMMM---------------M------------",
+    },
+    GeneticCode {
+        id: 12,
+        key: "alt-yeast",
+        name: "Alternative Yeast Nuclear",
+        amino_acids: b"FFLLSSSSYY**CC*WLLLSPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"-------------------M---------------M----------------------------",
+    },
+    GeneticCode {
+        id: 13,
+        key: "ascidian-mito",
+        name: "Ascidian Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSGGVVVVAAAADDEEGGGG",
+        starts: b"---M------------------------------MM---------------M------------",
+    },
+    GeneticCode {
+        id: 14,
+        key: "alt-flatworm-mito",
+        name: "Alternative Flatworm Mitochondrial",
+        amino_acids: b"FFLLSSSSYYY*CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+        starts: b"-----------------------------------M----------------------------",
+    },
+    GeneticCode {
+        id: 16,
+        key: "chlorophycean-mito",
+        name: "Chlorophycean Mitochondrial",
+        amino_acids: b"FFLLSSSSYY*LCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"-----------------------------------M----------------------------",
+    },
+    GeneticCode {
+        id: 21,
+        key: "trematode-mito",
+        name: "Trematode Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+        starts: b"-----------------------------------M---------------M------------",
+    },
+    GeneticCode {
+        id: 22,
+        key: "scenedesmus-mito",
+        name: "Scenedesmus obliquus Mitochondrial",
+        amino_acids: b"FFLLSS*SYY*LCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"-----------------------------------M----------------------------",
+    },
+    GeneticCode {
+        id: 23,
+        key: "thraustochytrium-mito",
+        name: "Thraustochytrium Mitochondrial",
+        amino_acids: b"FF*LSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"--------------------------------M--M---------------M------------",
+    },
+    GeneticCode {
+        id: 24,
+        key: "rhabdopleuridae-mito",
+        name: "Rhabdopleuridae Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSSKVVVVAAAADDEEGGGG",
+        starts: b"---M------**-------M---------------M---------------M------------",
+    },
+    GeneticCode {
+        id: 25,
+        key: "sr1",
+        name: "Candidate Division SR1 and Gracilibacteria",
+        amino_acids: b"FFLLSSSSYY**CCGWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"---M-------------------------------M---------------M------------",
+    },
+    GeneticCode {
+        id: 26,
+        key: "pachysolen",
+        name: "Pachysolen tannophilus Nuclear",
+        amino_acids: b"FFLLSSSSYY**CC*WLLLAPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"---M------**--*----M---------------M----------------------------",
+    },
+    GeneticCode {
+        id: 29,
+        key: "mesodinium",
+        name: "Mesodinium Nuclear",
+        amino_acids: b"FFLLSSSSYYYYCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"--------------*--------------------M----------------------------",
+    },
+    GeneticCode {
+        id: 30,
+        key: "peritrich",
+        name: "Peritrich Nuclear",
+        amino_acids: b"FFLLSSSSYYEECC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"--------------*--------------------M----------------------------",
+    },
+    GeneticCode {
+        id: 33,
+        key: "cephalodiscidae-mito",
+        name: "Cephalodiscidae Mitochondrial",
+        amino_acids: b"FFLLSSSSYYY*CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSSKVVVVAAAADDEEGGGG",
+        starts: b"---M-------*-------M---------------M---------------M------------",
+    },
+];
+
+/// palette candidates for `set-genetic-code`, matching the keys of [`GENETIC_CODES`]
+pub const GENETIC_CODE_KEYS: &[&str] = &[
+    "standard",
+    "vertebrate-mito",
+    "yeast-mito",
+    "mold-mito",
+    "invertebrate-mito",
+    "ciliate",
+    "echinoderm-mito",
+    "euplotid",
+    "bacterial",
+    "alt-yeast",
+    "ascidian-mito",
+    "alt-flatworm-mito",
+    "chlorophycean-mito",
+    "trematode-mito",
+    "scenedesmus-mito",
+    "thraustochytrium-mito",
+    "rhabdopleuridae-mito",
+    "sr1",
+    "pachysolen",
+    "mesodinium",
+    "peritrich",
+    "cephalodiscidae-mito",
+];
+
+/// index of a base in NCBI codon order, accepting `U` for `T`
+const fn ncbi_index(base: u8) -> Option<usize> {
+    match base.to_ascii_uppercase() {
+        b'T' | b'U' => Some(0),
+        b'C' => Some(1),
+        b'A' => Some(2),
+        b'G' => Some(3),
+        _ => None,
+    }
+}
+
+impl GeneticCode {
+    pub fn standard() -> &'static Self {
+        &GENETIC_CODES[0]
+    }
+
+    /// looks a code up by NCBI table number or key
+    pub fn find(query: &str) -> Option<&'static Self> {
+        let query = query.trim();
+        match query.parse::<u8>() {
+            Ok(id) => GENETIC_CODES.iter().find(|code| code.id == id),
+            Err(_) => GENETIC_CODES
+                .iter()
+                .find(|code| code.key.eq_ignore_ascii_case(query)),
+        }
+    }
+
+    /// the code as a libmsa table, which orders bases `A, T, C, G`
+    pub fn table(&self) -> libmsa::TranslationTable {
+        const BASES: [u8; 4] = [b'A', b'T', b'C', b'G'];
+        let mut codons = [[[b'X'; 4]; 4]; 4];
+        for (first, first_base) in BASES.into_iter().enumerate() {
+            for (second, second_base) in BASES.into_iter().enumerate() {
+                for (third, third_base) in BASES.into_iter().enumerate() {
+                    codons[first][second][third] =
+                        self.amino_acids[Self::codon_index([first_base, second_base, third_base])
+                            .expect("table bases are nucleotides")];
+                }
+            }
+        }
+        libmsa::TranslationTable::new(codons)
+    }
+
+    /// whether `codon` can start translation under this code
+    pub fn is_start(&self, codon: [u8; 3]) -> bool {
+        Self::codon_index(codon).is_some_and(|index| self.starts[index] == b'M')
+    }
+
+    fn codon_index(codon: [u8; 3]) -> Option<usize> {
+        Some(ncbi_index(codon[0])? * 16 + ncbi_index(codon[1])? * 4 + ncbi_index(codon[2])?)
+    }
+}
+
+impl fmt::Display for GeneticCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.id, self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_code_matches_libmsa_standard_table() {
+        assert_eq!(
+            GeneticCode::standard().table(),
+            libmsa::TranslationTable::STANDARD
+        );
+    }
+
+    #[test]
+    fn keys_match_codes_and_ids_are_unique() {
+        let keys: Vec<&str> = GENETIC_CODES.iter().map(|code| code.key).collect();
+        assert_eq!(keys, GENETIC_CODE_KEYS);
+
+        let mut ids: Vec<u8> = GENETIC_CODES.iter().map(|code| code.id).collect();
+        ids.dedup();
+        assert_eq!(ids.len(), GENETIC_CODES.len());
+    }
+
+    #[test]
+    fn vertebrate_mito_reads_tga_as_tryptophan() {
+        let code = GeneticCode::find("2").expect("table 2 should exist");
+        let table = code.table();
+
+        assert_eq!(code.key, "vertebrate-mito");
+        assert_eq!(table.translate_codon(*b"TGA"), b'W');
+        assert_eq!(table.translate_codon(*b"AGA"), b'*');
+        assert_eq!(table.translate_codon(*b"ATA"), b'M');
+        assert!(code.is_start(*b"ATT"));
+        assert!(!code.is_start(*b"TTG"));
+    }
+
+    #[test]
+    fn find_accepts_keys_case_insensitively() {
+        assert_eq!(GeneticCode::find("Bacterial").map(|code| code.id), Some(11));
+        assert_eq!(GeneticCode::find("7"), None);
+        assert_eq!(GeneticCode::find("plastid"), None);
+    }
+}
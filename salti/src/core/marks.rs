@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// top-left of the viewport remembered by `m<letter>`, as absolute row and column ids so that it
+/// survives filtering and column masks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Mark {
+    pub row: usize,
+    pub column: usize,
+}
+
+/// what the next letter typed does after `m` or `'`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkAction {
+    Set,
+    Jump,
+}
+
+/// marks of the loaded file, written back to the marks file whenever one is set
+#[derive(Debug, Default)]
+pub struct MarkState {
+    /// marks file shared by every input; `None` keeps marks for this session only
+    pub path: Option<PathBuf>,
+    pub marks: BTreeMap<char, Mark>,
+    pub pending: Option<MarkAction>,
+}
+
+/// marks of every input opened, keyed by input then by letter
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct MarksFile {
+    inputs: BTreeMap<String, BTreeMap<String, Mark>>,
+}
+
+/// local files are keyed by their canonical path so that marks follow them across working
+/// directories. URLs and SSH paths are used as given.
+fn input_key(input: &str) -> String {
+    std::fs::canonicalize(input)
+        .map_or_else(|_| input.to_string(), |path| path.display().to_string())
+}
+
+fn read_marks_file(path: &Path) -> Result<MarksFile> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => toml::from_str(&contents)
+            .with_context(|| format!("invalid marks file {}", path.display())),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(MarksFile::default()),
+        Err(error) => Err(error).with_context(|| format!("failed to read {}", path.display())),
+    }
+}
+
+/// marks saved for `input`. a missing marks file has none.
+pub fn load_marks(path: &Path, input: &str) -> Result<BTreeMap<char, Mark>> {
+    let mut file = read_marks_file(path)?;
+    let marks = file.inputs.remove(&input_key(input)).unwrap_or_default();
+    Ok(marks
+        .into_iter()
+        .filter_map(|(letter, mark)| {
+            let mut chars = letter.chars();
+            match (chars.next(), chars.next()) {
+                (Some(letter), None) if letter.is_ascii_alphabetic() => Some((letter, mark)),
+                _ => None,
+            }
+        })
+        .collect())
+}
+
+/// replaces the marks saved for `input`, keeping those of other inputs
+pub fn save_marks(path: &Path, input: &str, marks: &BTreeMap<char, Mark>) -> Result<()> {
+    let mut file = read_marks_file(path)?;
+    let key = input_key(input);
+    if marks.is_empty() {
+        file.inputs.remove(&key);
+    } else {
+        let marks = marks
+            .iter()
+            .map(|(letter, mark)| (letter.to_string(), *mark))
+            .collect();
+        file.inputs.insert(key, marks);
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let contents = toml::to_string(&file).context("failed to serialise marks")?;
+    std::fs::write(path, contents).with_context(|| format!("failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marks_round_trip_per_input() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("salti").join("marks.toml");
+        let first = BTreeMap::from([(
+            'a',
+            Mark {
+                row: 3,
+                column: 120,
+            },
+        )]);
+        let second = BTreeMap::from([('b', Mark { row: 0, column: 7 })]);
+
+        assert!(load_marks(&path, "one.fa").unwrap().is_empty());
+        save_marks(&path, "one.fa", &first).unwrap();
+        save_marks(&path, "https://example.com/two.fa", &second).unwrap();
+
+        assert_eq!(load_marks(&path, "one.fa").unwrap(), first);
+        assert_eq!(
+            load_marks(&path, "https://example.com/two.fa").unwrap(),
+            second
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use std::fmt::Write as _;
+use std::ops::Range;
+
+use anyhow::{Result, format_err};
+
+/// first line of a mask written by `save-mask`
+const MASK_HEADER: &str = "# salti column mask: 1-based inclusive intervals";
+
+/// sorted absolute columns grouped into runs of adjacent columns
+pub fn column_runs(columns: &[usize]) -> Vec<Range<usize>> {
+    let mut runs: Vec<Range<usize>> = Vec::new();
+    for &col in columns {
+        match runs.last_mut() {
+            Some(run) if run.end == col => run.end += 1,
+            _ => runs.push(col..col + 1),
+        }
+    }
+    runs
+}
+
+/// writes sorted absolute columns as one `start-end` interval per line
+pub fn format_mask(columns: &[usize]) -> String {
+    let mut text = format!("{MASK_HEADER}\n");
+    for run in column_runs(columns) {
+        if run.len() == 1 {
+            let _ = writeln!(text, "{}", run.end);
+        } else {
+            let _ = writeln!(text, "{}-{}", run.start + 1, run.end);
+        }
+    }
+    text
+}
+
+/// reads a mask of 1-based `start-end` intervals or single columns, one per line. blank lines
+/// and `#` comments are skipped. returns sorted, deduplicated absolute columns.
+pub fn parse_mask(text: &str) -> Result<Vec<usize>> {
+    let mut columns = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let invalid = || format_err!("invalid interval '{line}' on line {}", index + 1);
+        let (start, end) = line.split_once('-').unwrap_or((line, line));
+        let (Ok(start), Ok(end)) = (start.trim().parse::<usize>(), end.trim().parse::<usize>())
+        else {
+            return Err(invalid());
+        };
+        if start == 0 || end < start {
+            return Err(invalid());
+        }
+        columns.extend(start - 1..end);
+    }
+    if columns.is_empty() {
+        return Err(format_err!("no intervals found"));
+    }
+    columns.sort_unstable();
+    columns.dedup();
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_round_trip_through_intervals() {
+        let columns = vec![0, 1, 2, 5, 9, 10];
+
+        let text = format_mask(&columns);
+
+        assert_eq!(
+            text.lines().skip(1).collect::<Vec<_>>(),
+            ["1-3", "6", "10-11"]
+        );
+        assert_eq!(parse_mask(&text).unwrap(), columns);
+        assert_eq!(
+            parse_mask("4-6\n# note\n\n5 # overlaps\n").unwrap(),
+            [3, 4, 5]
+        );
+        assert!(parse_mask("0-4").is_err());
+        assert!(parse_mask("6-2").is_err());
+        assert!(parse_mask("# empty\n").is_err());
+    }
+}
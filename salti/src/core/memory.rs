@@ -0,0 +1,183 @@
+use std::path::Path;
+
+use anyhow::{Result, format_err};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+use crate::core::bgzf;
+
+const KIB: f64 = 1024.0;
+const MIB: f64 = KIB * 1024.0;
+const GIB: f64 = MIB * 1024.0;
+
+/// bytes held after loading per byte of uncompressed input: the sequences, the view built over
+/// them and the column stats
+const LOAD_BYTES_PER_INPUT_BYTE: u64 = 3;
+/// rough size of sequence data once decompressed, relative to the compressed file
+const COMPRESSION_RATIO: u64 = 4;
+/// file extensions read through a decompressor
+const COMPRESSED_EXTENSIONS: &[&str] = &["gz", "bgz", "bz2", "xz", "zst"];
+/// share of the limit above which a load is allowed but warned about, in percent
+const MEMORY_WARNING_PERCENT: u64 = 80;
+
+/// most memory a load may be estimated to need, set with `--max-mem`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryLimit {
+    pub bytes: u64,
+}
+
+impl MemoryLimit {
+    pub const fn from_gb(gb: u32) -> Self {
+        Self {
+            bytes: gb as u64 * 1024 * 1024 * 1024,
+        }
+    }
+
+    pub fn allows(self, bytes: u64) -> bool {
+        bytes <= self.bytes
+    }
+
+    /// checks the estimate for loading `input` against the limit. inputs that are too large are
+    /// refused, and ones that come close return a warning. remote inputs have no size to
+    /// estimate from and always pass.
+    pub fn check(self, input: &str) -> Result<Option<String>> {
+        let Some(estimate) = estimate_load_bytes(Path::new(input)) else {
+            return Ok(None);
+        };
+        if estimate > self.bytes {
+            return Err(format_err!(
+                "{input} needs about {} to load, over the {} limit; raise --max-mem to load it anyway",
+                format_bytes(estimate),
+                format_bytes(self.bytes)
+            ));
+        }
+        Ok(
+            (estimate > self.bytes / 100 * MEMORY_WARNING_PERCENT).then(|| {
+                format!(
+                    "{input} needs about {} to load, close to the {} limit",
+                    format_bytes(estimate),
+                    format_bytes(self.bytes)
+                )
+            }),
+        )
+    }
+}
+
+/// memory a local file is expected to take once loaded, from its size on disk
+pub fn estimate_load_bytes(path: &Path) -> Option<u64> {
+    // the `.fai` of an indexed BGZF file counts its residues exactly
+    if let Ok(Some(index)) = bgzf::FastaIndex::load(path) {
+        return Some(estimate_for_size(index.residues(), false));
+    }
+    let size = std::fs::metadata(path).ok()?.len();
+    Some(estimate_for_size(size, is_compressed(path)))
+}
+
+/// memory an alignment of `residues` takes once loaded, estimated as for a load
+pub fn alignment_bytes(residues: u64) -> u64 {
+    estimate_for_size(residues, false)
+}
+
+/// whether `path` has the extension of a compressed format
+pub fn is_compressed(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| COMPRESSED_EXTENSIONS.contains(&extension))
+}
+
+fn estimate_for_size(size: u64, compressed: bool) -> u64 {
+    let uncompressed = if compressed {
+        size.saturating_mul(COMPRESSION_RATIO)
+    } else {
+        size
+    };
+    uncompressed.saturating_mul(LOAD_BYTES_PER_INPUT_BYTE)
+}
+
+/// samples the resident memory of the running salti process
+#[derive(Debug)]
+pub struct MemoryProbe {
+    system: System,
+    pid: Option<Pid>,
+}
+
+impl Default for MemoryProbe {
+    fn default() -> Self {
+        Self {
+            system: System::new(),
+            pid: sysinfo::get_current_pid().ok(),
+        }
+    }
+}
+
+impl MemoryProbe {
+    pub fn resident_bytes(&mut self) -> Option<u64> {
+        let pid = self.pid?;
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[pid]),
+            true,
+            ProcessRefreshKind::nothing().with_memory(),
+        );
+        self.system.process(pid).map(|process| process.memory())
+    }
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    let bytes_f = bytes as f64;
+    if bytes_f >= GIB {
+        format!("{:.1} GiB", bytes_f / GIB)
+    } else if bytes_f >= MIB {
+        format!("{:.1} MiB", bytes_f / MIB)
+    } else if bytes_f >= KIB {
+        format!("{:.0} KiB", bytes_f / KIB)
+    } else {
+        format!("{bytes} B")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_bytes_picks_a_readable_unit() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(4 * 1024), "4 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 / 2), "1.5 MiB");
+        assert_eq!(format_bytes(2 * 1024 * 1024 * 1024), "2.0 GiB");
+    }
+
+    #[test]
+    fn compressed_inputs_are_estimated_larger() {
+        assert_eq!(estimate_for_size(1000, false), 3000);
+        assert_eq!(estimate_for_size(1000, true), 12000);
+    }
+
+    #[test]
+    fn limit_warns_near_and_refuses_over_the_estimate() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, &[b'A'; 1000]).unwrap();
+        let input = file.path().to_str().unwrap();
+
+        assert_eq!(MemoryLimit { bytes: 10_000 }.check(input).unwrap(), None);
+        assert!(
+            MemoryLimit { bytes: 3_500 }
+                .check(input)
+                .unwrap()
+                .is_some_and(|warning| warning.contains("close to"))
+        );
+        assert!(MemoryLimit { bytes: 2_000 }.check(input).is_err());
+        assert_eq!(
+            MemoryLimit { bytes: 1 }
+                .check("https://example.com/big.fasta")
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn probe_reports_current_process_memory() {
+        let mut probe = MemoryProbe::default();
+
+        assert!(probe.resident_bytes().is_some_and(|bytes| bytes > 0));
+    }
+}
@@ -1,7 +1,37 @@
+pub mod bgzf;
+pub mod cluster;
+pub mod composition;
+pub mod dir_listing;
+pub mod disk_cache;
+pub mod dnds;
+pub mod export;
+pub mod follow;
+pub mod html_report;
+pub mod inspect;
+pub mod jobs;
+pub mod jumps;
+pub mod liftover;
+pub mod locator;
+pub mod lookups;
+pub mod marks;
+pub mod mask;
+pub mod memory;
 pub mod model;
+pub mod orf;
 pub mod parser;
+pub mod partition;
+pub mod primer;
+pub mod realign;
+pub mod restriction;
 pub mod search;
+pub mod selection;
+pub mod serializer;
+pub mod similarity;
 pub mod stats_cache;
+pub mod status;
+pub mod sync;
+pub mod tools;
+pub mod variability;
 pub mod viewport;
 
 pub use viewport::Viewport;
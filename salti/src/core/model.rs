@@ -1,4 +1,12 @@
-use std::{fmt, ops::Range, str::FromStr};
+use std::collections::HashSet;
+use std::collections::hash_map::Entry;
+use std::{collections::HashMap, fmt, ops::Range, str::FromStr};
+
+use crate::core::cluster::Cluster;
+use crate::core::liftover::ResidueIndex;
+use crate::core::lookups::GeneticCode;
+use crate::core::partition::Partition;
+use crate::core::restriction::RestrictionSite;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum StatsView {
@@ -52,10 +60,198 @@ impl FromStr for DiffMode {
     }
 }
 
+/// how diff mode compares nucleotides
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DiffAmbiguity {
+    /// only identical bytes match
+    #[default]
+    Strict,
+    /// IUPAC ambiguity codes match the bases they stand for, so `R` matches `A` or `G`
+    Iupac,
+}
+
+impl DiffAmbiguity {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Strict => "strict",
+            Self::Iupac => "iupac",
+        }
+    }
+
+    pub const fn all() -> [Self; 2] {
+        [Self::Strict, Self::Iupac]
+    }
+}
+
+impl fmt::Display for DiffAmbiguity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for DiffAmbiguity {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|ambiguity| ambiguity.name() == value)
+            .ok_or_else(|| anyhow::format_err!("invalid diff ambiguity: {value}"))
+    }
+}
+
+/// differences diff mode treats as matches
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DiffOptions {
+    /// soft-masked lowercase residues match their uppercase form
+    pub ignore_case: bool,
+    /// gaps match any gap, and columns outside the span both rows have residues in match
+    pub ignore_end_gaps: bool,
+}
+
+impl DiffOptions {
+    const IGNORE_CASE: &str = "ignore-case";
+    const IGNORE_END_GAPS: &str = "ignore-end-gaps";
+    const NONE: &str = "none";
+}
+
+impl fmt::Display for DiffOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = [
+            (self.ignore_case, Self::IGNORE_CASE),
+            (self.ignore_end_gaps, Self::IGNORE_END_GAPS),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, name)| enabled.then_some(name))
+        .collect();
+        if names.is_empty() {
+            f.write_str(Self::NONE)
+        } else {
+            f.write_str(&names.join(","))
+        }
+    }
+}
+
+impl FromStr for DiffOptions {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut options = Self::default();
+        if value == Self::NONE {
+            return Ok(options);
+        }
+        for name in value.split(',').map(str::trim) {
+            match name {
+                Self::IGNORE_CASE => options.ignore_case = true,
+                Self::IGNORE_END_GAPS => options.ignore_end_gaps = true,
+                _ => anyhow::bail!("invalid diff option: {name}"),
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// which rows the consensus and conservation are computed over
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ConsensusScope {
+    /// every sequence, shown or not, over the visible columns
+    All,
+    /// only the sequences left after hiding, filtering and collapsing
+    #[default]
+    Visible,
+}
+
+impl ConsensusScope {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Visible => "visible",
+        }
+    }
+
+    pub const fn all() -> [Self; 2] {
+        [Self::All, Self::Visible]
+    }
+}
+
+impl fmt::Display for ConsensusScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ConsensusScope {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|scope| scope.name() == value)
+            .ok_or_else(|| anyhow::format_err!("invalid consensus scope: {value}"))
+    }
+}
+
+/// how A2M insert columns (lowercase residues and `.` in every row) are displayed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum InsertColumnMode {
+    #[default]
+    Show,
+    Hide,
+    Compress,
+}
+
+impl InsertColumnMode {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Show => "show",
+            Self::Hide => "hide",
+            Self::Compress => "compress",
+        }
+    }
+
+    pub const fn all() -> [Self; 3] {
+        [Self::Show, Self::Hide, Self::Compress]
+    }
+
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Show => Self::Hide,
+            Self::Hide => Self::Compress,
+            Self::Compress => Self::Show,
+        }
+    }
+
+    const fn as_filter(self) -> Option<libmsa::InsertColumns> {
+        match self {
+            Self::Show => None,
+            Self::Hide => Some(libmsa::InsertColumns::Hide),
+            Self::Compress => Some(libmsa::InsertColumns::Compress),
+        }
+    }
+}
+
+impl fmt::Display for InsertColumnMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for InsertColumnMode {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|mode| mode.name() == value)
+            .ok_or_else(|| anyhow::format_err!("invalid insert column mode: {value}"))
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct RowPresentationState {
     pinned: Vec<usize>,
     reference: Option<usize>,
+    hidden: Vec<usize>,
 }
 
 impl RowPresentationState {
@@ -75,8 +271,20 @@ impl RowPresentationState {
         self.reference == Some(abs_row)
     }
 
+    pub fn hidden(&self) -> &[usize] {
+        &self.hidden
+    }
+
+    pub fn is_hidden(&self, abs_row: usize) -> bool {
+        self.hidden.contains(&abs_row)
+    }
+
     pub fn excluded_rows(&self) -> impl Iterator<Item = usize> + '_ {
-        self.pinned.iter().copied().chain(self.reference)
+        self.pinned
+            .iter()
+            .copied()
+            .chain(self.reference)
+            .chain(self.hidden.iter().copied())
     }
 
     pub fn pin(&mut self, abs_row: usize, row_count: usize) -> Result<(), libmsa::AlignmentError> {
@@ -84,6 +292,7 @@ impl RowPresentationState {
         if self.reference == Some(abs_row) || self.pinned.contains(&abs_row) {
             return Err(libmsa::AlignmentError::DuplicateRowIndex { index: abs_row });
         }
+        self.hidden.retain(|&hidden_row| hidden_row != abs_row);
         self.pinned.push(abs_row);
         Ok(())
     }
@@ -98,6 +307,21 @@ impl RowPresentationState {
         Ok(())
     }
 
+    /// moves a pinned row `offset` places through the pin order, stopping at either end.
+    /// returns its new index, or `None` when the row is not pinned.
+    pub fn move_pinned(&mut self, abs_row: usize, offset: isize) -> Option<usize> {
+        let from = self
+            .pinned
+            .iter()
+            .position(|&pinned_row| pinned_row == abs_row)?;
+        let to = from
+            .saturating_add_signed(offset)
+            .min(self.pinned.len() - 1);
+        let row = self.pinned.remove(from);
+        self.pinned.insert(to, row);
+        Some(to)
+    }
+
     pub fn set_reference(
         &mut self,
         abs_row: usize,
@@ -105,6 +329,7 @@ impl RowPresentationState {
     ) -> Result<(), libmsa::AlignmentError> {
         validate_row_id(abs_row, row_count)?;
         self.pinned.retain(|&pinned_row| pinned_row != abs_row);
+        self.hidden.retain(|&hidden_row| hidden_row != abs_row);
         self.reference = Some(abs_row);
         Ok(())
     }
@@ -112,12 +337,37 @@ impl RowPresentationState {
     pub fn clear_reference(&mut self) {
         self.reference = None;
     }
+
+    pub fn clear_pinned(&mut self) {
+        self.pinned.clear();
+    }
+
+    pub fn hide(&mut self, abs_row: usize, row_count: usize) -> Result<(), libmsa::AlignmentError> {
+        validate_row_id(abs_row, row_count)?;
+        if self.hidden.contains(&abs_row) {
+            return Err(libmsa::AlignmentError::DuplicateRowIndex { index: abs_row });
+        }
+        self.pinned.retain(|&pinned_row| pinned_row != abs_row);
+        if self.reference == Some(abs_row) {
+            self.reference = None;
+        }
+        self.hidden.push(abs_row);
+        Ok(())
+    }
+
+    pub fn clear_hidden(&mut self) {
+        self.hidden.clear();
+    }
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct FilterState {
     pattern: Option<String>,
     max_gap_fraction: Option<f32>,
+    insert_columns: InsertColumnMode,
+    variable_only: bool,
+    /// absolute columns hidden by `load-mask`, sorted
+    masked_columns: Vec<usize>,
 }
 
 impl FilterState {
@@ -129,12 +379,28 @@ impl FilterState {
         self.max_gap_fraction
     }
 
+    pub fn insert_columns(&self) -> InsertColumnMode {
+        self.insert_columns
+    }
+
+    /// whether columns where every visible row agrees are hidden
+    pub fn variable_only(&self) -> bool {
+        self.variable_only
+    }
+
+    pub fn masked_columns(&self) -> &[usize] {
+        &self.masked_columns
+    }
+
     pub fn has_column_filter(&self) -> bool {
         self.max_gap_fraction.is_some()
+            || self.insert_columns != InsertColumnMode::Show
+            || self.variable_only
+            || !self.masked_columns.is_empty()
     }
 
     pub fn is_active(&self) -> bool {
-        self.pattern.is_some() || self.max_gap_fraction.is_some()
+        self.pattern.is_some() || self.max_gap_fraction.is_some() || !self.masked_columns.is_empty()
     }
 }
 
@@ -142,12 +408,34 @@ impl FilterState {
 pub struct AlignmentModel {
     base: libmsa::Alignment,
     view: libmsa::Alignment,
+    /// rows the column stats are computed over when they differ from the view's
+    stats_view: Option<libmsa::Alignment>,
+    consensus_scope: ConsensusScope,
     rows: RowPresentationState,
     filter: FilterState,
     translation_enabled: bool,
     translation_frame: libmsa::ReadingFrame,
+    translation_gap_mode: libmsa::TranslationGapMode,
+    genetic_code: &'static GeneticCode,
     pub diff_mode: DiffMode,
+    pub diff_ambiguity: DiffAmbiguity,
+    pub diff_options: DiffOptions,
     pub consensus_method: libmsa::ConsensusMethod,
+    /// fraction of rows the consensus residue needs; columns below it show an ambiguity code
+    pub consensus_threshold: f32,
+    pub quality_shading: bool,
+    /// whether residue colours fade in conserved columns so variable ones stand out
+    pub conservation_emphasis: bool,
+    /// restriction sites marked on the ruler, in absolute columns
+    pub restriction_sites: Vec<RestrictionSite>,
+    /// partitions of a concatenated alignment, from `append-alignment` or `load-partitions`
+    pub partitions: Vec<Partition>,
+    /// residue columns of base rows, built the first time a row's positions are converted
+    residue_indices: HashMap<usize, ResidueIndex>,
+    /// groups of near-identical rows from `cluster`, ordered by representative
+    clusters: Vec<Cluster>,
+    /// absolute rows renamed on load because an earlier row had the same ID
+    pub renamed_rows: HashSet<usize>,
 }
 
 impl AlignmentModel {
@@ -161,13 +449,27 @@ impl AlignmentModel {
 
         Ok(Self {
             view: base.clone(),
+            stats_view: None,
+            consensus_scope: ConsensusScope::default(),
+            quality_shading: base.has_quality(),
+            conservation_emphasis: false,
             base,
             rows: RowPresentationState::default(),
             filter: FilterState::default(),
             translation_enabled: false,
             translation_frame: libmsa::ReadingFrame::Frame1,
+            translation_gap_mode: libmsa::TranslationGapMode::default(),
+            genetic_code: GeneticCode::standard(),
             diff_mode: DiffMode::default(),
+            diff_ambiguity: DiffAmbiguity::default(),
+            diff_options: DiffOptions::default(),
             consensus_method: libmsa::ConsensusMethod::default(),
+            consensus_threshold: 0.0,
+            restriction_sites: Vec::new(),
+            partitions: Vec::new(),
+            residue_indices: HashMap::new(),
+            clusters: Vec::new(),
+            renamed_rows: HashSet::new(),
         })
     }
 
@@ -179,6 +481,28 @@ impl AlignmentModel {
         &self.view
     }
 
+    /// the view's columns over the rows of the consensus scope, which column stats are
+    /// computed from
+    pub fn stats_view(&self) -> &libmsa::Alignment {
+        self.stats_view.as_ref().unwrap_or(&self.view)
+    }
+
+    pub fn consensus_scope(&self) -> ConsensusScope {
+        self.consensus_scope
+    }
+
+    pub fn set_consensus_scope(
+        &mut self,
+        scope: ConsensusScope,
+    ) -> Result<(), libmsa::AlignmentError> {
+        let previous = std::mem::replace(&mut self.consensus_scope, scope);
+        if let Err(error) = self.derive_view_from_intent() {
+            self.consensus_scope = previous;
+            return Err(error);
+        }
+        Ok(())
+    }
+
     pub fn rows(&self) -> &RowPresentationState {
         &self.rows
     }
@@ -191,11 +515,26 @@ impl AlignmentModel {
         self.translation_enabled.then_some(self.translation_frame)
     }
 
-    #[cfg(test)]
     pub fn translation_frame(&self) -> libmsa::ReadingFrame {
         self.translation_frame
     }
 
+    pub fn translation_gap_mode(&self) -> libmsa::TranslationGapMode {
+        self.translation_gap_mode
+    }
+
+    pub fn set_translation_gap_mode(&mut self, gap_mode: libmsa::TranslationGapMode) {
+        self.translation_gap_mode = gap_mode;
+    }
+
+    pub fn genetic_code(&self) -> &'static GeneticCode {
+        self.genetic_code
+    }
+
+    pub fn set_genetic_code(&mut self, code: &'static GeneticCode) {
+        self.genetic_code = code;
+    }
+
     pub fn pin(&mut self, abs_row: usize) -> Result<(), libmsa::AlignmentError> {
         self.rows.pin(abs_row, self.base_row_count())?;
         self.derive_view_from_intent()
@@ -206,6 +545,372 @@ impl AlignmentModel {
         self.derive_view_from_intent()
     }
 
+    /// pins every row in `abs_rows`, keeping their order after any existing pins
+    pub fn pin_rows(&mut self, abs_rows: &[usize]) -> Result<(), libmsa::AlignmentError> {
+        let previous = self.rows.clone();
+        for &abs_row in abs_rows {
+            if let Err(error) = self.rows.pin(abs_row, self.base_row_count()) {
+                self.rows = previous;
+                return Err(error);
+            }
+        }
+        if let Err(error) = self.derive_view_from_intent() {
+            self.rows = previous;
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// moves a pinned row through the pin order, which only changes the pinned band
+    pub fn move_pinned(&mut self, abs_row: usize, offset: isize) -> Option<usize> {
+        self.rows.move_pinned(abs_row, offset)
+    }
+
+    pub fn unpin_all(&mut self) -> Result<(), libmsa::AlignmentError> {
+        self.rows.clear_pinned();
+        self.derive_view_from_intent()
+    }
+
+    /// builds a new model holding `columns` (absolute, unfiltered) of the reference, pinned and
+    /// visible rows. the reference, pins, diff mode and consensus method carry over.
+    pub fn extract_region(&self, columns: Range<usize>) -> Result<Self, libmsa::AlignmentError> {
+        if columns.is_empty() {
+            return Err(libmsa::AlignmentError::EmptyRange);
+        }
+        if columns.end > self.base.column_count() {
+            return Err(libmsa::AlignmentError::ColumnOutOfBounds {
+                index: columns.end - 1,
+                length: self.base.column_count(),
+            });
+        }
+
+        let rows: Vec<usize> = self
+            .rows
+            .reference()
+            .into_iter()
+            .chain(self.rows.pinned().iter().copied())
+            .chain(self.view.absolute_row_ids())
+            .collect();
+        let sequences: Vec<libmsa::RawSequence> = rows
+            .iter()
+            .filter_map(|&abs_row| self.base.project_absolute_row(abs_row))
+            .map(|sequence| libmsa::RawSequence {
+                id: sequence.id().to_string(),
+                sequence: columns
+                    .clone()
+                    .filter_map(|col| sequence.byte_at(col))
+                    .collect(),
+                quality: columns
+                    .clone()
+                    .map(|col| sequence.quality_at(col))
+                    .collect(),
+            })
+            .collect();
+
+        let mut region = Self::new(libmsa::Alignment::new_with_type(
+            sequences,
+            self.base.active_type(),
+        )?)?;
+        let reference_rows = usize::from(self.rows.reference().is_some());
+        if reference_rows == 1 {
+            region.rows.set_reference(0, rows.len())?;
+        }
+        for new_row in reference_rows..reference_rows + self.rows.pinned().len() {
+            region.rows.pin(new_row, rows.len())?;
+        }
+        region.diff_mode = self.diff_mode;
+        region.diff_ambiguity = self.diff_ambiguity;
+        region.diff_options = self.diff_options;
+        region.consensus_method = self.consensus_method;
+        region.consensus_threshold = self.consensus_threshold;
+        region.derive_view_from_intent()?;
+        Ok(region)
+    }
+
+    /// replaces the partitions, checking they fit within the alignment
+    pub fn set_partitions(
+        &mut self,
+        partitions: Vec<Partition>,
+    ) -> Result<(), libmsa::AlignmentError> {
+        let length = self.base.column_count();
+        if let Some(partition) = partitions
+            .iter()
+            .find(|partition| partition.columns.end > length)
+        {
+            return Err(libmsa::AlignmentError::ColumnOutOfBounds {
+                index: partition.columns.end - 1,
+                length,
+            });
+        }
+        self.partitions = partitions;
+        Ok(())
+    }
+
+    /// partition index and first visible column of the next partition with visible columns
+    /// after `relative_col`
+    pub fn next_partition_column(&self, relative_col: usize) -> Option<(usize, usize)> {
+        let current = self.view.absolute_column_id(relative_col)?;
+        let next = self
+            .partitions
+            .iter()
+            .position(|partition| partition.columns.start > current)?;
+        self.view
+            .absolute_column_ids()
+            .enumerate()
+            .skip(relative_col + 1)
+            .find_map(|(visible_col, abs_col)| {
+                let index = self.partitions[next..]
+                    .iter()
+                    .position(|partition| partition.columns.contains(&abs_col))?;
+                Some((next + index, visible_col))
+            })
+    }
+
+    /// builds a new model with the columns of `sequences` appended to each row, matched by
+    /// sequence ID. both files must hold exactly the same IDs. `base_name` names the existing
+    /// columns when they are not already partitioned. row presentation, filters and translation
+    /// carry over.
+    pub fn append_alignment(
+        &self,
+        sequences: Vec<libmsa::RawSequence>,
+        name: &str,
+        base_name: &str,
+    ) -> anyhow::Result<Self> {
+        let mut appended: HashMap<&str, &libmsa::RawSequence> = HashMap::new();
+        for sequence in &sequences {
+            if appended.insert(&sequence.id, sequence).is_some() {
+                return Err(anyhow::format_err!(
+                    "sequence '{}' appears more than once in {name}",
+                    sequence.id
+                ));
+            }
+        }
+        let base_rows: Vec<libmsa::SequenceView<'_>> = (0..self.base_row_count())
+            .filter_map(|abs_row| self.base.project_absolute_row(abs_row))
+            .collect();
+        let mut base_ids: HashMap<&str, usize> = HashMap::new();
+        for sequence in &base_rows {
+            *base_ids.entry(sequence.id()).or_default() += 1;
+        }
+        if let Some((id, _)) = base_ids.iter().find(|&(_, &count)| count > 1) {
+            return Err(anyhow::format_err!(
+                "sequence '{id}' appears more than once in the alignment"
+            ));
+        }
+        let missing: Vec<&str> = base_rows
+            .iter()
+            .map(|sequence| sequence.id())
+            .filter(|id| !appended.contains_key(id))
+            .collect();
+        if let Some(first) = missing.first() {
+            return Err(anyhow::format_err!(
+                "{name} is missing {} of the alignment's sequences, including '{first}'",
+                missing.len()
+            ));
+        }
+        let extra: Vec<&str> = sequences
+            .iter()
+            .map(|sequence| sequence.id.as_str())
+            .filter(|id| !base_ids.contains_key(id))
+            .collect();
+        if let Some(first) = extra.first() {
+            return Err(anyhow::format_err!(
+                "{name} has {} sequences that are not in the alignment, including '{first}'",
+                extra.len()
+            ));
+        }
+
+        let base_columns = self.base.column_count();
+        let combined: Vec<libmsa::RawSequence> = base_rows
+            .iter()
+            .map(|sequence| {
+                let other = appended[sequence.id()];
+                let mut bytes: Vec<u8> = (0..base_columns)
+                    .filter_map(|col| sequence.byte_at(col))
+                    .collect();
+                bytes.extend_from_slice(&other.sequence);
+                let quality = other.quality.as_ref().and_then(|other_quality| {
+                    let mut quality: Vec<u8> = (0..base_columns)
+                        .map(|col| sequence.quality_at(col))
+                        .collect::<Option<_>>()?;
+                    quality.extend_from_slice(other_quality);
+                    Some(quality)
+                });
+                libmsa::RawSequence {
+                    id: sequence.id().to_string(),
+                    sequence: bytes,
+                    quality,
+                }
+            })
+            .collect();
+
+        let mut concatenated = Self::new(libmsa::Alignment::new(combined)?)?;
+        concatenated.partitions = if self.partitions.is_empty() {
+            vec![Partition {
+                name: base_name.to_string(),
+                columns: 0..base_columns,
+            }]
+        } else {
+            self.partitions.clone()
+        };
+        concatenated.partitions.push(Partition {
+            name: name.to_string(),
+            columns: base_columns..concatenated.base.column_count(),
+        });
+        self.carry_settings_to(&mut concatenated);
+        concatenated.restriction_sites = self.restriction_sites.clone();
+        concatenated.derive_view_from_intent()?;
+        Ok(concatenated)
+    }
+
+    /// copies the row state and display settings onto `other`, a model rebuilt from the same
+    /// rows with rewritten columns
+    fn carry_settings_to(&self, other: &mut Self) {
+        other.rows = self.rows.clone();
+        other.filter = self.filter.clone();
+        other.translation_enabled =
+            self.translation_enabled && other.base.active_type().supports_translation();
+        other.translation_frame = self.translation_frame;
+        other.translation_gap_mode = self.translation_gap_mode;
+        other.genetic_code = self.genetic_code;
+        other.diff_mode = self.diff_mode;
+        other.diff_ambiguity = self.diff_ambiguity;
+        other.diff_options = self.diff_options;
+        other.consensus_method = self.consensus_method;
+        other.consensus_threshold = self.consensus_threshold;
+        other.quality_shading &= self.quality_shading;
+        other.conservation_emphasis = self.conservation_emphasis;
+    }
+
+    /// builds a new model with the absolute `columns` of `rows` replaced by `aligned`, one row of
+    /// bytes per row. the block takes the wider of its old and new widths: other rows keep their
+    /// bytes and every row is padded with trailing gaps. residues keep their quality scores and
+    /// added gaps score 0. partitions after the block shift right and restriction sites are
+    /// cleared.
+    pub fn replace_block(
+        &self,
+        rows: &[usize],
+        columns: Range<usize>,
+        aligned: &[Vec<u8>],
+    ) -> anyhow::Result<Self> {
+        let old_width = columns.len();
+        let width = aligned
+            .iter()
+            .map(Vec::len)
+            .max()
+            .unwrap_or(0)
+            .max(old_width);
+        let shift = width - old_width;
+        let replaced: HashMap<usize, &Vec<u8>> = rows.iter().copied().zip(aligned).collect();
+
+        let sequences: Vec<libmsa::RawSequence> = (0..self.base_row_count())
+            .filter_map(|abs_row| self.base.project_absolute_row(abs_row))
+            .map(|sequence| {
+                let abs_row = sequence.absolute_row_id();
+                let bytes: Vec<u8> = (0..sequence.len())
+                    .filter_map(|col| sequence.byte_at(col))
+                    .collect();
+                let quality: Option<Vec<u8>> = (0..sequence.len())
+                    .map(|col| sequence.quality_at(col))
+                    .collect();
+                let mut block = replaced
+                    .get(&abs_row)
+                    .map_or_else(|| bytes[columns.clone()].to_vec(), |row| row.to_vec());
+                block.resize(width, b'-');
+
+                let block_quality = quality.as_ref().map(|quality| {
+                    let mut residue_quality = columns
+                        .clone()
+                        .filter(|&col| !matches!(bytes[col], b'-' | b'.'))
+                        .map(|col| quality[col]);
+                    block
+                        .iter()
+                        .enumerate()
+                        .map(|(offset, &byte)| match replaced.contains_key(&abs_row) {
+                            true if matches!(byte, b'-' | b'.') => 0,
+                            true => residue_quality.next().unwrap_or(0),
+                            false => quality
+                                .get(columns.start + offset)
+                                .copied()
+                                .filter(|_| offset < old_width)
+                                .unwrap_or(0),
+                        })
+                        .collect::<Vec<u8>>()
+                });
+                let splice = |whole: &[u8], middle: Vec<u8>| {
+                    let mut spliced = whole[..columns.start].to_vec();
+                    spliced.extend(middle);
+                    spliced.extend_from_slice(&whole[columns.end..]);
+                    spliced
+                };
+                libmsa::RawSequence {
+                    id: sequence.id().to_string(),
+                    sequence: splice(&bytes, block),
+                    quality: quality
+                        .as_deref()
+                        .zip(block_quality)
+                        .map(|(quality, block_quality)| splice(quality, block_quality)),
+                }
+            })
+            .collect();
+
+        let mut realigned = Self::new(libmsa::Alignment::new(sequences)?)?;
+        self.carry_settings_to(&mut realigned);
+        realigned.partitions = self
+            .partitions
+            .iter()
+            .map(|partition| {
+                let shifted = |col: usize| if col >= columns.end { col + shift } else { col };
+                Partition {
+                    name: partition.name.clone(),
+                    columns: shifted(partition.columns.start)..shifted(partition.columns.end),
+                }
+            })
+            .collect();
+        realigned.derive_view_from_intent()?;
+        Ok(realigned)
+    }
+
+    /// adds `sequences` as rows after the existing ones, for files that grow while open. existing
+    /// absolute row ids are unchanged, so pins, hidden rows, the reference and filters carry over.
+    pub fn append_rows(
+        &mut self,
+        sequences: Vec<libmsa::RawSequence>,
+    ) -> Result<(), libmsa::AlignmentError> {
+        let columns = self.base.column_count();
+        let rows: Vec<libmsa::RawSequence> = (0..self.base_row_count())
+            .filter_map(|abs_row| self.base.project_absolute_row(abs_row))
+            .map(|sequence| libmsa::RawSequence {
+                id: sequence.id().to_string(),
+                sequence: (0..columns)
+                    .filter_map(|col| sequence.byte_at(col))
+                    .collect(),
+                quality: (0..columns).map(|col| sequence.quality_at(col)).collect(),
+            })
+            .chain(sequences)
+            .collect();
+        let mut base = libmsa::Alignment::new(rows)?;
+        if self.base.active_type() != self.base.detected_type() {
+            base.set_override_type(self.base.active_type());
+        }
+        self.base = base;
+        self.derive_view_from_intent()
+    }
+
+    /// rows in the current view whose names match `pattern`, in alignment order
+    pub fn rows_matching(&self, pattern: &str) -> Result<Vec<usize>, libmsa::AlignmentError> {
+        let matches = self
+            .base
+            .filter()?
+            .without_rows(self.rows.excluded_rows())
+            .with_row_regex(pattern)
+            .apply()?;
+        Ok(matches
+            .absolute_row_ids()
+            .filter(|&abs_row| self.view.relative_row_id(abs_row).is_some())
+            .collect())
+    }
+
     pub fn set_reference(&mut self, abs_row: usize) -> Result<(), libmsa::AlignmentError> {
         self.rows.set_reference(abs_row, self.base_row_count())?;
         self.derive_view_from_intent()
@@ -216,6 +921,21 @@ impl AlignmentModel {
         self.derive_view_from_intent()
     }
 
+    pub fn hide(&mut self, abs_row: usize) -> Result<(), libmsa::AlignmentError> {
+        let previous = self.rows.clone();
+        self.rows.hide(abs_row, self.base_row_count())?;
+        if let Err(error) = self.derive_view_from_intent() {
+            self.rows = previous;
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    pub fn show_hidden(&mut self) -> Result<(), libmsa::AlignmentError> {
+        self.rows.clear_hidden();
+        self.derive_view_from_intent()
+    }
+
     pub fn set_filter(&mut self, pattern: String) -> Result<(), libmsa::AlignmentError> {
         let next_pattern = if pattern.is_empty() {
             None
@@ -243,6 +963,89 @@ impl AlignmentModel {
         Ok(())
     }
 
+    pub fn set_insert_columns(
+        &mut self,
+        mode: InsertColumnMode,
+    ) -> Result<(), libmsa::AlignmentError> {
+        let previous = std::mem::replace(&mut self.filter.insert_columns, mode);
+        if let Err(error) = self.derive_view_from_intent() {
+            self.filter.insert_columns = previous;
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    pub fn set_variable_only(&mut self, enabled: bool) -> Result<(), libmsa::AlignmentError> {
+        let previous = std::mem::replace(&mut self.filter.variable_only, enabled);
+        if let Err(error) = self.derive_view_from_intent() {
+            self.filter.variable_only = previous;
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// hides `columns` (absolute, sorted) in addition to any column filters
+    pub fn set_column_mask(&mut self, columns: Vec<usize>) -> Result<(), libmsa::AlignmentError> {
+        let previous = std::mem::replace(&mut self.filter.masked_columns, columns);
+        if let Err(error) = self.derive_view_from_intent() {
+            self.filter.masked_columns = previous;
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// absolute columns hidden from the current view by masks and column filters
+    pub fn hidden_columns(&self) -> Vec<usize> {
+        let mut visible = self.view.absolute_column_ids().peekable();
+        (0..self.base.column_count())
+            .filter(|&col| {
+                if visible.peek() == Some(&col) {
+                    visible.next();
+                    false
+                } else {
+                    true
+                }
+            })
+            .collect()
+    }
+
+    /// cluster represented by `abs_row`
+    pub fn cluster_of(&self, abs_row: usize) -> Option<&Cluster> {
+        self.clusters
+            .binary_search_by_key(&abs_row, |cluster| cluster.representative)
+            .ok()
+            .map(|index| &self.clusters[index])
+    }
+
+    /// replaces the clusters, collapsing each one to its representative
+    pub fn set_clusters(&mut self, clusters: Vec<Cluster>) -> Result<(), libmsa::AlignmentError> {
+        let previous = std::mem::replace(&mut self.clusters, clusters);
+        if let Err(error) = self.derive_view_from_intent() {
+            self.clusters = previous;
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// expands or collapses the cluster represented by `abs_row`, returning whether it is now
+    /// expanded. `None` when `abs_row` does not represent a cluster.
+    pub fn toggle_cluster(
+        &mut self,
+        abs_row: usize,
+    ) -> Result<Option<bool>, libmsa::AlignmentError> {
+        let Ok(index) = self
+            .clusters
+            .binary_search_by_key(&abs_row, |cluster| cluster.representative)
+        else {
+            return Ok(None);
+        };
+        let cluster = &mut self.clusters[index];
+        cluster.expanded = !cluster.expanded;
+        let expanded = cluster.expanded;
+        self.derive_view_from_intent()?;
+        Ok(Some(expanded))
+    }
+
     pub fn clear_filter(&mut self) -> Result<(), libmsa::AlignmentError> {
         self.filter.pattern = None;
         self.filter.max_gap_fraction = None;
@@ -254,7 +1057,7 @@ impl AlignmentModel {
         kind: libmsa::AlignmentType,
     ) -> Result<(), libmsa::AlignmentError> {
         self.base.set_override_type(kind);
-        if kind != libmsa::AlignmentType::Dna {
+        if !kind.supports_translation() {
             self.translation_enabled = false;
         }
         self.derive_view_from_intent()
@@ -269,7 +1072,7 @@ impl AlignmentModel {
             return Ok(());
         };
 
-        if self.base.active_type() != libmsa::AlignmentType::Dna {
+        if !self.base.active_type().supports_translation() {
             return Err(libmsa::AlignmentError::UnsupportedOperation {
                 operation: "set translation",
                 kind: self.base.active_type(),
@@ -286,7 +1089,7 @@ impl AlignmentModel {
         &mut self,
         frame: libmsa::ReadingFrame,
     ) -> Result<(), libmsa::AlignmentError> {
-        if self.base.active_type() != libmsa::AlignmentType::Dna {
+        if !self.base.active_type().supports_translation() {
             return Err(libmsa::AlignmentError::UnsupportedOperation {
                 operation: "set translation frame",
                 kind: self.base.active_type(),
@@ -308,7 +1111,10 @@ impl AlignmentModel {
 
     pub fn translated_view(&self) -> Option<libmsa::TranslatedAlignment<'_>> {
         let frame = self.translation()?;
-        self.view.translated(frame).ok()
+        self.view
+            .translated_with(frame, self.genetic_code.table())
+            .ok()
+            .map(|translated| translated.with_gap_mode(self.translation_gap_mode))
     }
 
     pub fn stats_context(&self, visible_col_range: Range<usize>) -> Option<StatsContext> {
@@ -347,11 +1153,37 @@ impl AlignmentModel {
             "Sequence is pinned".to_string()
         } else if self.rows().is_reference(abs_row) {
             "Sequence is set as reference".to_string()
+        } else if self.rows().is_hidden(abs_row) {
+            "Sequence is hidden".to_string()
+        } else if self.collapsed_rows().any(|row| row == abs_row) {
+            "Sequence is collapsed into a cluster".to_string()
         } else {
             "Sequence is not visible in the current view".to_string()
         })
     }
 
+    /// residue columns of an absolute row of the base alignment, computed on first use and kept
+    /// for the life of the model
+    pub fn residue_index(&mut self, abs_row: usize) -> Option<&ResidueIndex> {
+        match self.residue_indices.entry(abs_row) {
+            Entry::Occupied(entry) => Some(entry.into_mut()),
+            Entry::Vacant(entry) => {
+                let sequence = self.base.project_absolute_row(abs_row)?;
+                let index =
+                    ResidueIndex::new((0..sequence.len()).filter_map(|col| sequence.byte_at(col)));
+                Some(entry.insert(index))
+            }
+        }
+    }
+
+    /// members of clusters that are not expanded
+    fn collapsed_rows(&self) -> impl Iterator<Item = usize> + '_ {
+        self.clusters
+            .iter()
+            .filter(|cluster| !cluster.expanded)
+            .flat_map(|cluster| cluster.members.iter().copied())
+    }
+
     fn base_row_count(&self) -> usize {
         self.base.row_count()
     }
@@ -359,13 +1191,30 @@ impl AlignmentModel {
     fn derive_view_from_intent(&mut self) -> Result<(), libmsa::AlignmentError> {
         let mut builder = self.base.filter()?;
         builder = builder.without_rows(self.rows.excluded_rows());
+        builder = builder.without_rows(self.collapsed_rows());
+        builder = builder.without_columns(&self.filter.masked_columns);
         if let Some(pattern) = self.filter.pattern() {
             builder = builder.with_row_regex(pattern);
         }
         if let Some(max_gap_fraction) = self.filter.max_gap_fraction() {
             builder = builder.with_max_gap_fraction(max_gap_fraction);
         }
+        if let Some(insert_columns) = self.filter.insert_columns.as_filter() {
+            builder = builder.with_insert_columns(insert_columns);
+        }
+        if self.filter.variable_only {
+            builder = builder.with_variable_columns_only(self.rows.reference());
+        }
         self.view = builder.apply()?;
+        self.stats_view = match self.consensus_scope {
+            ConsensusScope::Visible => None,
+            ConsensusScope::All => Some(
+                self.base
+                    .filter()?
+                    .without_columns(self.hidden_columns())
+                    .apply()?,
+            ),
+        };
         Ok(())
     }
 }
@@ -410,12 +1259,16 @@ fn visible_protein_range(
 
 #[cfg(test)]
 mod tests {
-    use super::{AlignmentModel, DiffMode, RowPresentationState, StatsContext, StatsView};
+    use super::{
+        AlignmentModel, Cluster, ConsensusScope, DiffMode, DiffOptions, InsertColumnMode,
+        Partition, RowPresentationState, StatsContext, StatsView,
+    };
 
     fn raw(id: &str, sequence: &[u8]) -> libmsa::RawSequence {
         libmsa::RawSequence {
             id: id.to_string(),
             sequence: sequence.to_vec(),
+            quality: None,
         }
     }
 
@@ -437,6 +1290,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn move_pinned_stops_at_either_end() {
+        let mut state = RowPresentationState::default();
+        for row in [4, 2, 7] {
+            state.pin(row, 8).unwrap();
+        }
+
+        assert_eq!(state.move_pinned(7, -1), Some(1));
+        assert_eq!(state.pinned(), [4, 7, 2]);
+        assert_eq!(state.move_pinned(4, -1), Some(0));
+        assert_eq!(state.move_pinned(4, 5), Some(2));
+        assert_eq!(state.pinned(), [7, 2, 4]);
+        assert_eq!(state.move_pinned(3, 1), None);
+    }
+
     #[test]
     fn row_presentation_state_rejects_pinning_the_reference() {
         let mut state = RowPresentationState::default();
@@ -481,6 +1349,20 @@ mod tests {
         assert_eq!(excluded_rows, vec![1, 3, 2]);
     }
 
+    #[test]
+    fn row_presentation_state_hide_removes_pin_and_reference() {
+        let mut state = RowPresentationState::default();
+        state.pin(1, 4).unwrap();
+        state.set_reference(2, 4).unwrap();
+
+        state.hide(1, 4).unwrap();
+        state.hide(2, 4).unwrap();
+
+        assert!(state.pinned().is_empty());
+        assert_eq!(state.reference(), None);
+        assert_eq!(state.hidden(), &[1, 2]);
+    }
+
     #[test]
     fn row_presentation_state_rejects_out_of_bounds() {
         let mut state = RowPresentationState::default();
@@ -574,6 +1456,79 @@ mod tests {
         assert_eq!(model.view().relative_row_id(1), Some(1));
     }
 
+    #[test]
+    fn rows_matching_only_returns_visible_rows_in_alignment_order() {
+        let mut model = alignment_model(vec![
+            raw("sample-b", b"ACGT"),
+            raw("other", b"ACGT"),
+            raw("sample-a", b"ACGT"),
+            raw("sample-c", b"ACGT"),
+        ]);
+        model.hide(3).unwrap();
+
+        assert_eq!(model.rows_matching("^sample").unwrap(), vec![0, 2]);
+        assert!(matches!(
+            model.rows_matching("["),
+            Err(libmsa::AlignmentError::InvalidRegex { .. })
+        ));
+    }
+
+    #[test]
+    fn pin_rows_appends_after_existing_pins_and_unpin_all_clears_them() {
+        let mut model = alignment_model(vec![
+            raw("row1", b"ACGT"),
+            raw("row2", b"ACGT"),
+            raw("row3", b"ACGT"),
+        ]);
+        model.pin(2).unwrap();
+
+        model.pin_rows(&[0, 1]).unwrap();
+        assert_eq!(model.rows().pinned(), &[2, 0, 1]);
+        assert_eq!(model.view().row_count(), 0);
+
+        model.unpin_all().unwrap();
+        assert!(model.rows().pinned().is_empty());
+        assert_eq!(model.view().row_count(), 3);
+    }
+
+    #[test]
+    fn extract_region_keeps_visible_rows_and_presentation() {
+        let mut model = alignment_model(vec![
+            raw("row1", b"AACCGGTT"),
+            raw("row2", b"ACGTACGT"),
+            raw("row3", b"TTGGCCAA"),
+            raw("row4", b"GGGGGGGG"),
+        ]);
+        model.set_reference(1).unwrap();
+        model.pin(2).unwrap();
+        model.hide(3).unwrap();
+
+        let region = model.extract_region(2..5).unwrap();
+
+        let ids: Vec<String> = (0..region.base().row_count())
+            .map(|row| {
+                region
+                    .base()
+                    .project_absolute_row(row)
+                    .unwrap()
+                    .id()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(ids, ["row2", "row3", "row1"]);
+        let bytes: Vec<u8> = {
+            let sequence = region.base().project_absolute_row(0).unwrap();
+            (0..sequence.len())
+                .filter_map(|col| sequence.byte_at(col))
+                .collect()
+        };
+        assert_eq!(bytes, b"GTA");
+        assert_eq!(region.rows().reference(), Some(0));
+        assert_eq!(region.rows().pinned(), &[1]);
+        assert_eq!(region.view().row_count(), 1);
+        assert!(model.extract_region(6..9).is_err());
+    }
+
     #[test]
     fn set_reference_hides_row_from_view() {
         let mut model = alignment_model(vec![
@@ -600,6 +1555,74 @@ mod tests {
         assert_eq!(model.view().relative_row_id(1), Some(1));
     }
 
+    #[test]
+    fn hide_removes_row_from_view_until_shown() {
+        let mut model = alignment_model(vec![
+            raw("row1", b"ACGT"),
+            raw("row2", b"ACGT"),
+            raw("row3", b"ACGT"),
+        ]);
+
+        model.hide(1).unwrap();
+        assert_eq!(model.view().row_count(), 2);
+        assert_eq!(model.view().relative_row_id(1), None);
+        assert_eq!(
+            model.jump_to_sequence(1),
+            Some("Sequence is hidden".to_string())
+        );
+
+        model.show_hidden().unwrap();
+        assert_eq!(model.view().row_count(), 3);
+    }
+
+    #[test]
+    fn consensus_scope_all_keeps_hidden_rows_in_the_stats_view() {
+        let mut model = alignment_model(vec![
+            raw("row1", b"ACGT"),
+            raw("row2", b"ACGT"),
+            raw("row3", b"ACGT"),
+        ]);
+        model.hide(1).unwrap();
+        assert_eq!(model.stats_view().row_count(), 2);
+
+        model.set_consensus_scope(ConsensusScope::All).unwrap();
+
+        assert_eq!(model.view().row_count(), 2);
+        assert_eq!(model.stats_view().row_count(), 3);
+        assert!(model.stats_view().has_same_columns(model.view()));
+        assert_eq!(
+            "visible".parse::<ConsensusScope>().unwrap(),
+            ConsensusScope::Visible
+        );
+    }
+
+    #[test]
+    fn diff_options_parse_from_a_comma_list() {
+        let options: DiffOptions = "ignore-case, ignore-end-gaps".parse().unwrap();
+
+        assert!(options.ignore_case && options.ignore_end_gaps);
+        assert_eq!(options.to_string(), "ignore-case,ignore-end-gaps");
+        assert_eq!(
+            "none".parse::<DiffOptions>().unwrap(),
+            DiffOptions::default()
+        );
+        assert_eq!(DiffOptions::default().to_string(), "none");
+        assert!("ignore-gaps".parse::<DiffOptions>().is_err());
+    }
+
+    #[test]
+    fn residue_index_is_built_from_the_base_row_and_cached() {
+        let mut model = alignment_model(vec![raw("row1", b"ACGT"), raw("row2", b"A--T")]);
+        model.hide(1).unwrap();
+
+        let index = model
+            .residue_index(1)
+            .expect("hidden rows keep their positions");
+        assert_eq!(index.column_of(2), Some(3));
+        assert!(model.residue_indices.contains_key(&1));
+        assert!(model.residue_index(5).is_none());
+    }
+
     #[test]
     fn set_filter_applies_row_pattern() {
         let mut model = alignment_model(vec![
@@ -654,6 +1677,46 @@ mod tests {
         assert_eq!(model.view().column_count(), 2);
     }
 
+    #[test]
+    fn insert_columns_can_be_hidden_and_compressed() {
+        let mut model = alignment_model(vec![raw("alpha", b"Aac.T"), raw("beta", b"A.gtT")]);
+
+        model.set_insert_columns(InsertColumnMode::Hide).unwrap();
+        assert_eq!(model.view().column_count(), 2);
+        assert!(model.filter().has_column_filter());
+
+        model
+            .set_insert_columns(InsertColumnMode::Compress)
+            .unwrap();
+        assert_eq!(model.view().column_count(), 3);
+
+        model.set_insert_columns(InsertColumnMode::Show).unwrap();
+        assert_eq!(model.view().column_count(), 5);
+    }
+
+    #[test]
+    fn variable_only_follows_the_reference() {
+        let mut model = alignment_model(vec![
+            raw("alpha", b"ACGT"),
+            raw("beta", b"ACTT"),
+            raw("gamma", b"ACTT"),
+        ]);
+
+        model.set_reference(0).unwrap();
+
+        model.set_variable_only(true).unwrap();
+        let columns: Vec<_> = model.view().absolute_column_ids().collect();
+        assert_eq!(columns, vec![2]);
+
+        model.clear_reference().unwrap();
+        assert_eq!(model.view().column_count(), 1);
+        model.hide(0).unwrap();
+        assert_eq!(model.view().column_count(), 0);
+
+        model.set_variable_only(false).unwrap();
+        assert_eq!(model.view().column_count(), 4);
+    }
+
     #[test]
     fn clear_filter_removes_both_filters() {
         let mut model = alignment_model(vec![
@@ -731,6 +1794,239 @@ mod tests {
         assert_eq!(model.translation_frame(), libmsa::ReadingFrame::Frame3);
     }
 
+    #[test]
+    fn translated_view_uses_translation_gap_mode() {
+        let mut model = alignment_model(vec![raw("dna", b"AT-GAAA--TGG")]);
+        model
+            .set_translation(Some(libmsa::ReadingFrame::Frame1))
+            .unwrap();
+        let translated_row = |model: &AlignmentModel| -> Vec<u8> {
+            let translated = model.translated_view().unwrap();
+            translated
+                .sequence_by_absolute(0)
+                .unwrap()
+                .bytes_range(0..4)
+                .unwrap()
+                .map(|(_, byte)| byte)
+                .collect()
+        };
+
+        assert_eq!(translated_row(&model), b"XEXW");
+
+        model.set_translation_gap_mode(libmsa::TranslationGapMode::SkipGaps);
+
+        assert_eq!(translated_row(&model), b"MK-W");
+    }
+
+    #[test]
+    fn append_alignment_concatenates_rows_by_id() {
+        let mut model = alignment_model(vec![raw("row1", b"AACC"), raw("row2", b"GGTT")]);
+        model.set_reference(1).unwrap();
+
+        let appended = model
+            .append_alignment(
+                vec![raw("row2", b"CA"), raw("row1", b"TG")],
+                "gene2.fa",
+                "gene1.fa",
+            )
+            .unwrap();
+
+        let row = |abs_row: usize| -> Vec<u8> {
+            let sequence = appended.base().project_absolute_row(abs_row).unwrap();
+            (0..sequence.len())
+                .filter_map(|col| sequence.byte_at(col))
+                .collect()
+        };
+        assert_eq!(row(0), b"AACCTG");
+        assert_eq!(row(1), b"GGTTCA");
+        assert_eq!(appended.rows().reference(), Some(1));
+        assert_eq!(
+            appended.partitions,
+            vec![
+                Partition {
+                    name: "gene1.fa".to_string(),
+                    columns: 0..4,
+                },
+                Partition {
+                    name: "gene2.fa".to_string(),
+                    columns: 4..6,
+                },
+            ]
+        );
+
+        let third = appended
+            .append_alignment(
+                vec![raw("row1", b"A"), raw("row2", b"C")],
+                "gene3.fa",
+                "ignored",
+            )
+            .unwrap();
+        assert_eq!(third.partitions.len(), 3);
+        assert_eq!(third.partitions[2].columns, 6..7);
+    }
+
+    #[test]
+    fn replace_block_widens_the_block_and_shifts_partitions() {
+        let mut model = alignment_model(vec![
+            raw("row1", b"AACGTT"),
+            raw("row2", b"AAC-TT"),
+            raw("row3", b"GGGGGG"),
+        ]);
+        model
+            .set_partitions(vec![
+                Partition {
+                    name: "a".to_string(),
+                    columns: 0..4,
+                },
+                Partition {
+                    name: "b".to_string(),
+                    columns: 4..6,
+                },
+            ])
+            .unwrap();
+
+        let realigned = model
+            .replace_block(&[0, 1], 1..4, &[b"ACG-".to_vec(), b"A--C".to_vec()])
+            .unwrap();
+
+        let row = |abs_row: usize| -> Vec<u8> {
+            let sequence = realigned.base().project_absolute_row(abs_row).unwrap();
+            (0..sequence.len())
+                .filter_map(|col| sequence.byte_at(col))
+                .collect()
+        };
+        assert_eq!(row(0), b"AACG-TT");
+        assert_eq!(row(1), b"AA--CTT");
+        assert_eq!(row(2), b"GGGG-GG");
+        assert_eq!(realigned.partitions[0].columns, 0..5);
+        assert_eq!(realigned.partitions[1].columns, 5..7);
+    }
+
+    #[test]
+    fn next_partition_column_skips_filtered_columns() {
+        let mut model = alignment_model(vec![raw("row1", b"ACG-TAC"), raw("row2", b"ACG-TAC")]);
+        model
+            .set_partitions(vec![
+                Partition {
+                    name: "a".to_string(),
+                    columns: 0..3,
+                },
+                Partition {
+                    name: "b".to_string(),
+                    columns: 3..5,
+                },
+                Partition {
+                    name: "c".to_string(),
+                    columns: 5..7,
+                },
+            ])
+            .unwrap();
+        model.set_gap_filter(Some(0.5)).unwrap();
+
+        assert_eq!(model.next_partition_column(0), Some((1, 3)));
+        assert_eq!(model.next_partition_column(3), Some((2, 4)));
+        assert_eq!(model.next_partition_column(5), None);
+        assert!(
+            model
+                .set_partitions(vec![Partition {
+                    name: "long".to_string(),
+                    columns: 0..8,
+                }])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn column_mask_hides_columns_alongside_filters() {
+        let mut model = alignment_model(vec![raw("row1", b"AC-GT"), raw("row2", b"AC-GA")]);
+
+        model.set_column_mask(vec![0, 3]).unwrap();
+        assert_eq!(
+            model.view().absolute_column_ids().collect::<Vec<_>>(),
+            [1, 2, 4]
+        );
+        model.set_gap_filter(Some(0.0)).unwrap();
+
+        assert_eq!(model.hidden_columns(), [0, 2, 3]);
+        assert!(model.filter().has_column_filter());
+        assert!(model.set_column_mask(vec![5]).is_err());
+        assert_eq!(model.filter().masked_columns(), [0, 3]);
+    }
+
+    #[test]
+    fn collapsed_clusters_show_only_their_representative() {
+        let mut model = alignment_model(vec![
+            raw("row1", b"ACGT"),
+            raw("row2", b"ACGT"),
+            raw("row3", b"TTTT"),
+        ]);
+
+        model
+            .set_clusters(vec![Cluster {
+                representative: 0,
+                members: vec![1],
+                expanded: false,
+            }])
+            .unwrap();
+        assert_eq!(model.view().absolute_row_ids().collect::<Vec<_>>(), [0, 2]);
+        assert_eq!(
+            model.jump_to_sequence(1).as_deref(),
+            Some("Sequence is collapsed into a cluster")
+        );
+
+        assert_eq!(model.toggle_cluster(0).unwrap(), Some(true));
+        assert_eq!(model.view().row_count(), 3);
+        assert_eq!(model.toggle_cluster(2).unwrap(), None);
+    }
+
+    #[test]
+    fn append_rows_keeps_existing_row_state() {
+        let mut model = alignment_model(vec![raw("row1", b"AACC"), raw("row2", b"GGTT")]);
+        model.set_reference(1).unwrap();
+        model.pin(0).unwrap();
+
+        model.append_rows(vec![raw("row3", b"ACGT")]).unwrap();
+
+        assert_eq!(model.base().row_count(), 3);
+        assert_eq!(model.rows().reference(), Some(1));
+        assert_eq!(model.rows().pinned(), &[0]);
+        assert_eq!(model.view().absolute_row_ids().collect::<Vec<_>>(), [2]);
+    }
+
+    #[test]
+    fn append_alignment_requires_matching_ids() {
+        let model = alignment_model(vec![raw("row1", b"AC"), raw("row2", b"GT")]);
+
+        let missing = model
+            .append_alignment(vec![raw("row1", b"AA")], "b.fa", "a.fa")
+            .unwrap_err();
+        assert_eq!(
+            missing.to_string(),
+            "b.fa is missing 1 of the alignment's sequences, including 'row2'"
+        );
+        let extra = model
+            .append_alignment(
+                vec![raw("row1", b"AA"), raw("row2", b"AA"), raw("row3", b"AA")],
+                "b.fa",
+                "a.fa",
+            )
+            .unwrap_err();
+        assert_eq!(
+            extra.to_string(),
+            "b.fa has 1 sequences that are not in the alignment, including 'row3'"
+        );
+        assert!(
+            model
+                .append_alignment(vec![raw("row1", b"AA"), raw("row1", b"AA")], "b.fa", "a.fa")
+                .is_err()
+        );
+        assert!(
+            model
+                .append_alignment(vec![raw("row1", b"AA"), raw("row2", b"A")], "b.fa", "a.fa")
+                .is_err()
+        );
+    }
+
     #[test]
     fn set_translation_frame_rejects_non_dna_alignments() {
         let mut model = alignment_model(vec![raw("aa", b"MKF")]);
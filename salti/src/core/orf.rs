@@ -0,0 +1,355 @@
+use std::ops::Range;
+
+use anyhow::{Result, format_err};
+use tokio_util::sync::CancellationToken;
+
+use crate::core::lookups::GeneticCode;
+
+/// columns scanned either side of the visible window, so gap runs that cross its edges are
+/// measured in full
+const ORF_SCAN_BUFFER: usize = 300;
+/// shortest ORF listed by `find-orfs` when no length is given, in codons
+pub const DEFAULT_MIN_ORF_CODONS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrfIssue {
+    /// stop codon with more sequence after it, at the protein column of the codon
+    PrematureStop { protein_col: usize },
+    /// internal gap run whose length is not a multiple of three
+    Frameshift { start: usize, len: usize },
+}
+
+impl OrfIssue {
+    /// first nucleotide column covered by the issue
+    pub const fn column(self, frame: libmsa::ReadingFrame) -> usize {
+        match self {
+            Self::PrematureStop { protein_col } => frame.offset() + protein_col * 3,
+            Self::Frameshift { start, .. } => start,
+        }
+    }
+
+    /// nucleotide columns covered by the issue
+    pub const fn columns(self, frame: libmsa::ReadingFrame) -> Range<usize> {
+        let start = self.column(frame);
+        match self {
+            Self::PrematureStop { .. } => start..start + 3,
+            Self::Frameshift { len, .. } => start..start + len,
+        }
+    }
+
+    pub fn describe(self, frame: libmsa::ReadingFrame) -> String {
+        let position = self.column(frame) + 1;
+        match self {
+            Self::PrematureStop { .. } => format!("premature stop codon at {position}"),
+            Self::Frameshift { len, .. } => format!("frameshift: {len} bp gap at {position}"),
+        }
+    }
+}
+
+/// nucleotide columns scanned for issues around `window`
+pub fn scan_range(window: &Range<usize>, nucleotide_len: usize) -> Range<usize> {
+    window.start.saturating_sub(ORF_SCAN_BUFFER)
+        ..window
+            .end
+            .saturating_add(ORF_SCAN_BUFFER)
+            .min(nucleotide_len)
+}
+
+const fn is_gap(byte: u8) -> bool {
+    matches!(byte, b'-' | b'.')
+}
+
+/// finds premature stops and frameshifting gaps in one row within `scan`. leading and trailing
+/// gaps are ignored, as are gap runs cut off by the edges of the scan.
+pub fn row_issues(
+    sequence: libmsa::SequenceView<'_>,
+    translated: libmsa::TranslatedSequenceView<'_>,
+    frame: libmsa::ReadingFrame,
+    scan: &Range<usize>,
+) -> Vec<OrfIssue> {
+    let len = sequence.len();
+    let residue_at = |col: usize| sequence.byte_at(col).is_some_and(|byte| !is_gap(byte));
+    let (Some(first_residue), Some(last_residue)) = (
+        (0..len).find(|&col| residue_at(col)),
+        (0..len).rev().find(|&col| residue_at(col)),
+    ) else {
+        return Vec::new();
+    };
+    let scan = scan.start..scan.end.min(len);
+    let mut issues = Vec::new();
+
+    let mut protein_col = scan.start.saturating_sub(frame.offset()).div_ceil(3);
+    loop {
+        let codon_start = frame.offset() + protein_col * 3;
+        if codon_start + 3 > scan.end {
+            break;
+        }
+        if translated.byte_at(protein_col) == Some(b'*') && codon_start + 3 <= last_residue {
+            issues.push(OrfIssue::PrematureStop { protein_col });
+        }
+        protein_col += 1;
+    }
+
+    let mut col = scan.start;
+    while col < scan.end {
+        if sequence.byte_at(col).is_none_or(|byte| !is_gap(byte)) {
+            col += 1;
+            continue;
+        }
+        let start = col;
+        while col < scan.end && sequence.byte_at(col).is_some_and(is_gap) {
+            col += 1;
+        }
+        let cut_by_scan = (start == scan.start && start > 0) || (col == scan.end && col < len);
+        let terminal = start < first_residue || col > last_residue;
+        let len = col - start;
+        if !cut_by_scan && !terminal && !len.is_multiple_of(3) {
+            issues.push(OrfIssue::Frameshift { start, len });
+        }
+    }
+
+    issues.sort_by_key(|issue| issue.column(frame));
+    issues
+}
+
+/// ATG-initiated reading frame that ends in a stop codon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpenReadingFrame {
+    /// 1-3 on the forward strand, -1 to -3 on the reverse strand
+    pub frame: i8,
+    /// leftmost alignment column of the ORF, including the stop codon
+    pub first_col: usize,
+    /// rightmost alignment column of the ORF, including the stop codon
+    pub last_col: usize,
+    /// codons before the stop
+    pub codons: usize,
+}
+
+impl OpenReadingFrame {
+    pub fn describe(self) -> String {
+        format!(
+            "{:+} {}-{} ({} aa)",
+            self.frame,
+            self.first_col + 1,
+            self.last_col + 1,
+            self.codons
+        )
+    }
+}
+
+fn complement(base: u8) -> u8 {
+    match base {
+        b'A' => b'T',
+        b'T' | b'U' => b'A',
+        b'C' => b'G',
+        b'G' => b'C',
+        _ => b'N',
+    }
+}
+
+/// ORFs in each frame of `bases` as `(frame offset, start, end)` ranges that end after the stop
+fn strand_orfs(bases: &[u8], min_codons: usize, code: &GeneticCode) -> Vec<(usize, usize, usize)> {
+    let table = code.table();
+    let mut orfs = Vec::new();
+    for offset in 0..3 {
+        let mut open = None;
+        for start in (offset..bases.len().saturating_sub(2)).step_by(3) {
+            let codon = [bases[start], bases[start + 1], bases[start + 2]];
+            match (open, table.translate_codon(codon)) {
+                (None, _) if code.is_start(codon) => open = Some(start),
+                (Some(orf_start), b'*') => {
+                    if (start - orf_start) / 3 >= min_codons {
+                        orfs.push((offset, orf_start, start + 3));
+                    }
+                    open = None;
+                }
+                _ => (),
+            }
+        }
+    }
+    orfs
+}
+
+/// finds ORFs of at least `min_codons` in all six frames of an aligned sequence, starting at
+/// the start codons of `code`. gaps are removed before translating and the ORFs are mapped back
+/// to alignment columns, longest first.
+pub fn find_orfs(
+    aligned: &[u8],
+    min_codons: usize,
+    code: &GeneticCode,
+    cancel: &CancellationToken,
+) -> Result<Vec<OpenReadingFrame>> {
+    let columns: Vec<usize> = (0..aligned.len())
+        .filter(|&col| !is_gap(aligned[col]))
+        .collect();
+    let forward: Vec<u8> = columns
+        .iter()
+        .map(|&col| aligned[col].to_ascii_uppercase())
+        .collect();
+    let reverse: Vec<u8> = forward.iter().rev().map(|&base| complement(base)).collect();
+    let len = forward.len();
+
+    let mut orfs = Vec::new();
+    for (sign, bases) in [(1, &forward), (-1, &reverse)] {
+        if cancel.is_cancelled() {
+            return Err(format_err!("ORF search cancelled"));
+        }
+        for (offset, start, end) in strand_orfs(bases, min_codons, code) {
+            let (first, last) = if sign > 0 {
+                (start, end - 1)
+            } else {
+                (len - end, len - start - 1)
+            };
+            orfs.push(OpenReadingFrame {
+                frame: sign * (offset as i8 + 1),
+                first_col: columns[first],
+                last_col: columns[last],
+                codons: (end - start) / 3 - 1,
+            });
+        }
+    }
+    orfs.sort_by_key(|orf| (std::cmp::Reverse(orf.codons), orf.first_col));
+    Ok(orfs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw(id: &str, sequence: &[u8]) -> libmsa::RawSequence {
+        libmsa::RawSequence {
+            id: id.to_string(),
+            sequence: sequence.to_vec(),
+            quality: None,
+        }
+    }
+
+    fn issues(sequence: &[u8], frame: libmsa::ReadingFrame) -> Vec<OrfIssue> {
+        let alignment =
+            libmsa::Alignment::new_with_type(vec![raw("a", sequence)], libmsa::AlignmentType::Dna)
+                .expect("test alignment should be valid");
+        let translated = alignment
+            .translated(frame)
+            .expect("DNA alignment should translate");
+        row_issues(
+            alignment.sequence(0).expect("row should exist"),
+            translated
+                .sequence_by_absolute(0)
+                .expect("row should exist"),
+            frame,
+            &(0..sequence.len()),
+        )
+    }
+
+    #[test]
+    fn orfs_are_found_on_both_strands_and_mapped_through_gaps() {
+        // forward: ATG AAA TAA in frame 1; reverse complement of TTA GGC CAT is ATG GCC TAA
+        let orfs = find_orfs(
+            b"AT-GAAATAACTTAGGCCAT",
+            2,
+            GeneticCode::standard(),
+            &CancellationToken::new(),
+        )
+        .expect("search should not be cancelled");
+
+        assert_eq!(
+            orfs,
+            vec![
+                OpenReadingFrame {
+                    frame: 1,
+                    first_col: 0,
+                    last_col: 9,
+                    codons: 2,
+                },
+                OpenReadingFrame {
+                    frame: -1,
+                    first_col: 11,
+                    last_col: 19,
+                    codons: 2,
+                },
+            ]
+        );
+        assert_eq!(orfs[1].describe(), "-1 12-20 (2 aa)");
+    }
+
+    #[test]
+    fn orfs_need_a_stop_and_the_minimum_length() {
+        let cancel = CancellationToken::new();
+
+        let standard = GeneticCode::standard();
+
+        assert!(
+            find_orfs(b"ATGAAAAAA", 1, standard, &cancel)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            find_orfs(b"ATGAAATAA", 3, standard, &cancel)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn orfs_follow_the_genetic_code() {
+        // TGA is read through in vertebrate mitochondria, which also start at ATA
+        let cancel = CancellationToken::new();
+        let mito = GeneticCode::find("vertebrate-mito").expect("table 2 should exist");
+
+        assert!(
+            find_orfs(b"ATAAAATGAAAAAGA", 4, GeneticCode::standard(), &cancel)
+                .unwrap()
+                .is_empty()
+        );
+        let orfs = find_orfs(b"ATAAAATGAAAAAGA", 4, mito, &cancel).unwrap();
+        assert_eq!(
+            orfs.iter()
+                .map(|orf| (orf.frame, orf.first_col, orf.codons))
+                .collect::<Vec<_>>(),
+            vec![(1, 0, 4)]
+        );
+    }
+
+    #[test]
+    fn terminal_stop_codons_are_not_premature() {
+        assert_eq!(
+            issues(b"ATGAAATAA---", libmsa::ReadingFrame::Frame1),
+            vec![]
+        );
+        assert_eq!(
+            issues(b"ATGTAAAAA", libmsa::ReadingFrame::Frame1),
+            vec![OrfIssue::PrematureStop { protein_col: 1 }]
+        );
+    }
+
+    #[test]
+    fn internal_gaps_off_frame_are_frameshifts() {
+        assert_eq!(
+            issues(b"--ATG--AAA---CCC-", libmsa::ReadingFrame::Frame1),
+            vec![OrfIssue::Frameshift { start: 5, len: 2 }]
+        );
+    }
+
+    #[test]
+    fn gap_runs_cut_by_the_scan_edge_are_skipped() {
+        let alignment = libmsa::Alignment::new_with_type(
+            vec![raw("a", b"AAA----AAA")],
+            libmsa::AlignmentType::Dna,
+        )
+        .expect("test alignment should be valid");
+        let translated = alignment
+            .translated(libmsa::ReadingFrame::Frame1)
+            .expect("DNA alignment should translate");
+
+        let issues = row_issues(
+            alignment.sequence(0).expect("row should exist"),
+            translated
+                .sequence_by_absolute(0)
+                .expect("row should exist"),
+            libmsa::ReadingFrame::Frame1,
+            &(5..10),
+        );
+
+        assert!(issues.is_empty());
+    }
+}
@@ -1,66 +1,325 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::io::Read;
+use std::ops::Range;
 use std::path::Path;
 
 use anyhow::{Result, format_err};
 use libmsa::RawSequence;
-use paraseq::fasta;
+use paraseq::{Record, fastx};
+use serde::Deserialize;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, info};
 
-pub fn parse_fasta_file(input: &str, cancel: &CancellationToken) -> Result<Vec<RawSequence>> {
+use crate::core::bgzf;
+use crate::core::partition::Partition;
+
+mod nexus;
+
+/// what loading does with a sequence ID already used by an earlier row
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DuplicateIds {
+    /// rename later copies `id_2`, `id_3` and so on
+    #[default]
+    Suffix,
+    /// refuse to load the alignment
+    Error,
+}
+
+/// sequences read from an alignment file, with any partitions it defines
+#[derive(Debug)]
+pub struct ParsedAlignment {
+    pub sequences: Vec<RawSequence>,
+    pub partitions: Vec<Partition>,
+    /// how many IDs were not valid UTF-8 and were decoded lossily
+    pub lossy_ids: usize,
+    /// rows renamed by `resolve_duplicate_ids`
+    pub renamed_rows: Vec<usize>,
+}
+
+impl ParsedAlignment {
+    fn new(sequences: Vec<RawSequence>, partitions: Vec<Partition>) -> Self {
+        Self {
+            sequences,
+            partitions,
+            lossy_ids: 0,
+            renamed_rows: Vec::new(),
+        }
+    }
+
+    /// applies `policy` to every ID already used by an earlier row, so each row can be found by
+    /// name. suffixes skip IDs that are already taken.
+    pub fn resolve_duplicate_ids(&mut self, policy: DuplicateIds) -> Result<()> {
+        let mut taken: HashSet<String> = self
+            .sequences
+            .iter()
+            .map(|sequence| sequence.id.clone())
+            .collect();
+        let mut seen = HashSet::new();
+        for (row, sequence) in self.sequences.iter_mut().enumerate() {
+            if seen.insert(sequence.id.clone()) {
+                continue;
+            }
+            if policy == DuplicateIds::Error {
+                return Err(format_err!(
+                    "Duplicate sequence ID {} in row {}",
+                    sequence.id,
+                    row + 1
+                ));
+            }
+            let renamed = (2..)
+                .map(|suffix| format!("{}_{suffix}", sequence.id))
+                .find(|id| !taken.contains(id))
+                .expect("suffixes are unbounded");
+            taken.insert(renamed.clone());
+            sequence.id = renamed;
+            self.renamed_rows.push(row);
+        }
+        Ok(())
+    }
+}
+
+/// parses a local NEXUS file, or a FASTA/FASTQ file from any supported source. NEXUS is
+/// detected by its `#NEXUS` header.
+pub fn parse_alignment_file(input: &str, cancel: &CancellationToken) -> Result<ParsedAlignment> {
+    let is_local = !is_http_url(input) && !is_ssh_path(input);
+    if is_local && starts_with_nexus(Path::new(input)) {
+        info!(input = %input, "Starting NEXUS parse");
+        let bytes =
+            std::fs::read(input).map_err(|error| format_err!("failed to read {input}: {error}"))?;
+        return parse_nexus_bytes(&bytes, cancel);
+    }
+    parse_fasta_file(input, cancel)
+}
+
+/// how much of a local file is read to look for the `#NEXUS` header
+const NEXUS_SNIFF_BYTES: u64 = 512;
+
+/// whether the file at `path` opens with a `#NEXUS` header, reading only its first few hundred
+/// bytes so large FASTA files are not pulled into memory just to be sniffed
+fn starts_with_nexus(path: &Path) -> bool {
+    let mut head = Vec::new();
+    std::fs::File::open(path)
+        .and_then(|file| file.take(NEXUS_SNIFF_BYTES).read_to_end(&mut head))
+        .is_ok_and(|_| nexus::is_nexus(&head))
+}
+
+/// parses an alignment held in memory: NEXUS when it starts with `#NEXUS`, otherwise FASTA or
+/// FASTQ. malformed input of any kind is reported as an error.
+pub fn parse_alignment_bytes(bytes: &[u8], cancel: &CancellationToken) -> Result<ParsedAlignment> {
+    if nexus::is_nexus(bytes) {
+        return parse_nexus_bytes(bytes, cancel);
+    }
+    let mut reader =
+        fastx::Reader::new(bytes).map_err(|error| format_err!("Error reading records: {error}"))?;
+    let parsed = read_records(&mut reader, None, cancel)?;
+    if parsed.sequences.is_empty() {
+        return Err(format_err!(
+            "No valid FASTA or FASTQ records found in input"
+        ));
+    }
+    Ok(parsed)
+}
+
+fn parse_nexus_bytes(bytes: &[u8], cancel: &CancellationToken) -> Result<ParsedAlignment> {
+    let text = std::str::from_utf8(bytes)
+        .map_err(|error| format_err!("NEXUS file is not valid UTF-8: {error}"))?;
+    nexus::parse_nexus(text, cancel)
+}
+
+pub fn parse_fasta_file(input: &str, cancel: &CancellationToken) -> Result<ParsedAlignment> {
     info!(input = %input, "Starting fasta parse");
+    check_indexed_lengths(input)?;
     let mut reader =
         open_fasta_reader(input).map_err(|error| format_err!("Failed to open input: {error}"))?;
+    let parsed = read_records(&mut reader, None, cancel)?;
+    let sequences = &parsed.sequences;
+
+    if sequences.is_empty() {
+        return Err(format_err!(
+            "No valid FASTA or FASTQ records found in input"
+        ));
+    }
+
+    debug!(
+        input = %input,
+        sequence_count = sequences.len(),
+        expected_length = sequences[0].sequence.len(),
+        lossy_ids = parsed.lossy_ids,
+        "Completed fasta parse"
+    );
+
+    Ok(parsed)
+}
+
+/// reads alignment columns `columns` (0-based) of every record in a local BGZF FASTA, seeking
+/// through its `.fai` and `.gzi` so the rest of the file is never inflated. missing indexes are
+/// built first. IDs are the names the `.fai` lists, which end at the first space.
+pub fn parse_fasta_region(
+    input: &str,
+    columns: Range<usize>,
+    cancel: &CancellationToken,
+) -> Result<ParsedAlignment> {
+    info!(input = %input, start = columns.start, end = columns.end, "Starting ranged fasta parse");
+    let path = Path::new(input);
+    if is_http_url(input) || is_ssh_path(input) || !bgzf::is_bgzf(path) {
+        return Err(format_err!(
+            "Only local BGZF inputs can be loaded by region"
+        ));
+    }
+    if bgzf::needs_index(path) {
+        bgzf::build_index(path, cancel)?;
+    }
+    check_indexed_lengths(input)?;
+    let mut fasta = bgzf::IndexedFasta::open(path)?
+        .ok_or_else(|| format_err!("Failed to open the indexes of {input}"))?;
+    let length = fasta
+        .index
+        .records
+        .first()
+        .map_or(0, |record| record.length);
+    if columns.is_empty() || columns.start as u64 >= length {
+        return Err(format_err!(
+            "Region {}-{} is outside the {length} alignment columns",
+            columns.start + 1,
+            columns.end
+        ));
+    }
+    let residues = columns.start as u64..columns.end as u64;
+
+    let mut sequences = Vec::with_capacity(fasta.index.records.len());
+    for record in 0..fasta.index.records.len() {
+        if cancel.is_cancelled() {
+            return Err(format_err!("Cancelled fasta parse"));
+        }
+        let id = fasta.index.records[record].name.clone();
+        let sequence = fasta.fetch(record, residues.clone())?;
+        sequences.push(RawSequence {
+            id,
+            sequence,
+            quality: None,
+        });
+    }
+
+    debug!(
+        input = %input,
+        sequence_count = sequences.len(),
+        "Completed ranged fasta parse"
+    );
+    Ok(ParsedAlignment::new(sequences, Vec::new()))
+}
+
+/// parses FASTA or FASTQ records held in memory, each of which must be `expected_length` long
+pub fn parse_fasta_bytes(bytes: &[u8], expected_length: usize) -> Result<Vec<RawSequence>> {
+    let mut reader =
+        fastx::Reader::new(bytes).map_err(|error| format_err!("Error reading records: {error}"))?;
+    read_records(
+        &mut reader,
+        Some(expected_length),
+        &CancellationToken::new(),
+    )
+    .map(|parsed| parsed.sequences)
+}
+
+/// reads every record, checking they all share one length. the first record sets the length
+/// unless `expected_length` is given. errors name the line the offending record starts on, and
+/// IDs that are not valid UTF-8 are decoded lossily.
+fn read_records<R: std::io::Read>(
+    reader: &mut fastx::Reader<R>,
+    mut expected_length: Option<usize>,
+    cancel: &CancellationToken,
+) -> Result<ParsedAlignment> {
     let mut record_set = reader.new_record_set();
     let mut sequences = Vec::new();
-    let mut expected_length: Option<usize> = None;
+    let mut lossy_ids = 0;
+    let mut line = 1;
 
     while record_set
-        .fill(&mut reader)
-        .map_err(|error| format_err!("Error reading records: {error}"))?
+        .fill(reader)
+        .map_err(|error| format_err!("Error reading records after line {line}: {error}"))?
     {
         for record in record_set.iter() {
             if cancel.is_cancelled() {
                 return Err(format_err!("Cancelled fasta parse"));
             }
 
-            let record = record.map_err(|error| format_err!("Error reading record: {error}"))?;
-            let id = std::str::from_utf8(record.id())
-                .map_err(|error| format_err!("Invalid sequence ID: {error}"))?
-                .to_string();
-            let sequence = record.seq().to_vec();
+            let record = record
+                .map_err(|error| format_err!("Error reading record at line {line}: {error}"))?;
+            // a record with no sequence is rejected before its ID is read, as paraseq
+            // mis-slices the ID of a header-only record at the end of the input
+            let raw_sequence = record.seq_raw();
+            if raw_sequence.is_empty() {
+                return Err(format_err!("Record at line {line} has no sequence"));
+            }
+            let record_line = line;
+            line += record_line_count(raw_sequence, record.qual().is_some());
+
+            let id = String::from_utf8_lossy(record.id());
+            if matches!(id, Cow::Owned(_)) {
+                lossy_ids += 1;
+            }
+            let id = id.trim_end_matches('\r').to_string();
+            let mut sequence = record.seq().into_owned();
+            sequence.retain(|&byte| byte != b'\r');
             let sequence_length = sequence.len();
+            let quality = record.qual().map(decode_phred);
+            if let Some(quality) = &quality
+                && quality.len() != sequence_length
+            {
+                return Err(format_err!(
+                    "Quality length mismatch: expected {}, found {} for id {} at line {record_line}",
+                    sequence_length,
+                    quality.len(),
+                    id
+                ));
+            }
 
             if let Some(length) = expected_length {
                 if sequence_length != length {
                     return Err(format_err!(
-                        "Sequence length mismatch: expected {}, found {} for id {}",
+                        "Sequence length mismatch: expected {}, found {} for id {} at line {record_line}",
                         length,
                         sequence_length,
                         id
                     ));
                 }
             } else if sequence_length == 0 {
-                return Err(format_err!("Sequence has zero length for id {}", id));
+                return Err(format_err!(
+                    "Sequence has zero length for id {} at line {record_line}",
+                    id
+                ));
             } else {
                 expected_length = Some(sequence_length);
             }
 
-            sequences.push(RawSequence { id, sequence });
+            sequences.push(RawSequence {
+                id,
+                sequence,
+                quality,
+            });
         }
     }
 
-    if sequences.is_empty() {
-        return Err(format_err!("No valid FASTA records found in input"));
-    }
+    Ok(ParsedAlignment {
+        lossy_ids,
+        ..ParsedAlignment::new(sequences, Vec::new())
+    })
+}
 
-    debug!(
-        input = %input,
-        sequence_count = sequences.len(),
-        expected_length = expected_length.unwrap_or(0),
-        "Completed fasta parse"
-    );
+/// lines taken by a record: its header and sequence lines (with any blank lines after them) for
+/// FASTA, or the four lines of a FASTQ record
+fn record_line_count(raw_sequence: &[u8], is_fastq: bool) -> usize {
+    if is_fastq {
+        return 4;
+    }
+    let newlines = raw_sequence.iter().filter(|&&byte| byte == b'\n').count();
+    1 + newlines + usize::from(!raw_sequence.ends_with(b"\n"))
+}
 
-    Ok(sequences)
+/// converts phred+33 encoded quality characters to scores
+fn decode_phred(quality: &[u8]) -> Vec<u8> {
+    quality.iter().map(|byte| byte.saturating_sub(33)).collect()
 }
 
 fn is_http_url(input: &str) -> bool {
@@ -71,14 +330,44 @@ fn is_ssh_path(input: &str) -> bool {
     input.starts_with("ssh://")
 }
 
-fn open_fasta_reader(input: &str) -> Result<fasta::Reader<paraseq::BoxedReader>> {
+/// refuses an indexed BGZF input whose `.fai` lists records of different lengths, before any
+/// of it is inflated
+fn check_indexed_lengths(input: &str) -> Result<()> {
+    let path = Path::new(input);
+    if is_http_url(input) || is_ssh_path(input) || !bgzf::is_bgzf(path) {
+        return Ok(());
+    }
+    let Some(index) = bgzf::FastaIndex::load(path)? else {
+        return Ok(());
+    };
+    let Some((first, rest)) = index.records.split_first() else {
+        return Ok(());
+    };
+    match rest.iter().find(|record| record.length != first.length) {
+        Some(record) => Err(format_err!(
+            "Sequence length mismatch: expected {}, found {} for id {}",
+            first.length,
+            record.length,
+            record.name
+        )),
+        None => Ok(()),
+    }
+}
+
+fn open_fasta_reader(input: &str) -> Result<fastx::Reader<paraseq::BoxedReader>> {
     if is_http_url(input) {
-        return fasta::Reader::from_url(input).map_err(Into::into);
+        return fastx::Reader::from_url(input).map_err(Into::into);
     }
     if is_ssh_path(input) {
-        return fasta::Reader::from_ssh(input).map_err(Into::into);
+        return fastx::Reader::from_ssh(input).map_err(Into::into);
+    }
+    let path = Path::new(input);
+    // an indexed BGZF file is read through its `.gzi`, like ranged loads of it
+    if let Some(fasta) = bgzf::IndexedFasta::open(path)? {
+        return fastx::Reader::new(Box::new(fasta.into_reader()) as paraseq::BoxedReader)
+            .map_err(Into::into);
     }
-    fasta::Reader::from_path(Path::new(input)).map_err(Into::into)
+    fastx::Reader::from_path(path).map_err(Into::into)
 }
 
 #[cfg(test)]
@@ -98,7 +387,7 @@ mod tests {
         let temp_file = create_temp_fasta(content);
         let input = temp_file.path().to_str().unwrap();
         let result = parse_fasta_file(input, &CancellationToken::new());
-        let sequences = result.expect("parse should succeed");
+        let sequences = result.expect("parse should succeed").sequences;
         assert_eq!(sequences.len(), 2);
         assert_eq!(sequences[0].id.as_str(), "seq1");
         assert_eq!(sequences[0].sequence.as_slice(), b"A-CG");
@@ -106,6 +395,136 @@ mod tests {
         assert_eq!(sequences[1].sequence.as_slice(), b"TGCA");
     }
 
+    #[test]
+    fn indexed_bgzf_inputs_parse_and_fail_fast_on_ragged_lengths() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("aln.fa.gz");
+        let write = |content: &str| {
+            let file = std::fs::File::create(&path).unwrap();
+            let mut writer = bgzip::BGZFWriter::new(file, bgzip::Compression::default());
+            writer.write_all(content.as_bytes()).unwrap();
+            writer.close().unwrap();
+            bgzf::build_index(&path, &CancellationToken::new()).unwrap();
+        };
+        let input = path.to_str().unwrap();
+
+        write(">seq1\nA-CG\n>seq2\nTGCA\n");
+        let parsed = parse_fasta_file(input, &CancellationToken::new()).unwrap();
+        assert_eq!(parsed.sequences[1].sequence.as_slice(), b"TGCA");
+
+        write(">seq1\nA-CG\n>seq2\nTGC\n");
+        let error = check_indexed_lengths(input).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Sequence length mismatch: expected 4, found 3 for id seq2"
+        );
+    }
+
+    #[test]
+    fn regions_of_bgzf_inputs_are_read_through_the_index() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("aln.fa.gz");
+        let file = std::fs::File::create(&path).unwrap();
+        // small blocks, so the region starts part way through the file
+        let mut writer = bgzip::BGZFWriter::with_compress_unit_size(
+            file,
+            bgzip::Compression::default(),
+            8,
+            false,
+        )
+        .unwrap();
+        writer
+            .write_all(b">seq1 first\nACGTA\nCGTAC\n>seq2\nTTTTT\nGGGGG\n")
+            .unwrap();
+        writer.close().unwrap();
+        let input = path.to_str().unwrap();
+
+        let parsed =
+            parse_fasta_region(input, 3..7, &CancellationToken::new()).expect("region should load");
+        assert!(!bgzf::needs_index(&path));
+        assert_eq!(parsed.sequences.len(), 2);
+        assert_eq!(parsed.sequences[0].id.as_str(), "seq1");
+        assert_eq!(parsed.sequences[0].sequence.as_slice(), b"TACG");
+        assert_eq!(parsed.sequences[1].sequence.as_slice(), b"TTGG");
+
+        let error = parse_fasta_region(input, 10..12, &CancellationToken::new()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Region 11-12 is outside the 10 alignment columns"
+        );
+    }
+
+    #[test]
+    fn test_parse_preserves_a2m_insert_states() {
+        let content = ">seq1\nAc.T\n>seq2\nA.gT\n";
+        let temp_file = create_temp_fasta(content);
+        let input = temp_file.path().to_str().unwrap();
+        let result = parse_fasta_file(input, &CancellationToken::new());
+        let sequences = result.expect("parse should succeed").sequences;
+        assert_eq!(sequences[0].sequence.as_slice(), b"Ac.T");
+        assert_eq!(sequences[1].sequence.as_slice(), b"A.gT");
+    }
+
+    #[test]
+    fn test_parse_fastq_decodes_quality() {
+        let content = "@read1\nAC-T\n+\nI5!+\n@read2\nACGT\n+\nIIII\n";
+        let temp_file = create_temp_fasta(content);
+        let input = temp_file.path().to_str().unwrap();
+        let result = parse_fasta_file(input, &CancellationToken::new());
+        let sequences = result.expect("parse should succeed").sequences;
+        assert_eq!(sequences[0].sequence.as_slice(), b"AC-T");
+        assert_eq!(sequences[0].quality, Some(vec![40, 20, 0, 10]));
+        assert_eq!(sequences[1].quality, Some(vec![40; 4]));
+    }
+
+    #[test]
+    fn test_parse_fasta_has_no_quality() {
+        let content = ">seq1\nACGT\n";
+        let temp_file = create_temp_fasta(content);
+        let input = temp_file.path().to_str().unwrap();
+        let result = parse_fasta_file(input, &CancellationToken::new());
+        let sequences = result.expect("parse should succeed").sequences;
+        assert_eq!(sequences[0].quality, None);
+    }
+
+    #[test]
+    fn test_parse_detects_nexus() {
+        let content = "#NEXUS\nbegin data;\nmatrix\nseq1 AC-G\nseq2 TGCA\n;\nend;\n";
+        let temp_file = create_temp_fasta(content);
+        let input = temp_file.path().to_str().unwrap();
+        let parsed =
+            parse_alignment_file(input, &CancellationToken::new()).expect("parse should succeed");
+        assert_eq!(parsed.sequences[0].id.as_str(), "seq1");
+        assert_eq!(parsed.sequences[1].sequence.as_slice(), b"TGCA");
+
+        let temp_file = create_temp_fasta(">seq1\nACGT\n");
+        let input = temp_file.path().to_str().unwrap();
+        let parsed =
+            parse_alignment_file(input, &CancellationToken::new()).expect("parse should succeed");
+        assert_eq!(parsed.sequences[0].sequence.as_slice(), b"ACGT");
+    }
+
+    #[test]
+    fn nexus_files_past_the_sniffed_header_are_read_whole() {
+        let rows: String = (0..100).map(|row| format!("seq{row} ACGTACGT\n")).collect();
+        let content = format!("#NEXUS\nbegin data;\nmatrix\n{rows};\nend;\n");
+        assert!(content.len() as u64 > NEXUS_SNIFF_BYTES);
+        let temp_file = create_temp_fasta(&content);
+
+        assert!(starts_with_nexus(temp_file.path()));
+        let parsed = parse_alignment_file(
+            temp_file.path().to_str().unwrap(),
+            &CancellationToken::new(),
+        )
+        .expect("parse should succeed");
+        assert_eq!(parsed.sequences.len(), 100);
+        assert!(!starts_with_nexus(Path::new("idontexist.nex")));
+    }
+
     #[test]
     fn test_parse_nonexistant() {
         let result = parse_fasta_file("idontexist.fasta", &CancellationToken::new());
@@ -139,6 +558,111 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_parse_bytes_detects_format() {
+        let cancel = CancellationToken::new();
+
+        let fasta = parse_alignment_bytes(b">seq1\nAC-G\n>seq2\nTGCA", &cancel)
+            .expect("FASTA should parse");
+        let nexus = parse_alignment_bytes(
+            b"#NEXUS\nbegin data;\nmatrix\nseq1 AC-G\n;\nend;\n",
+            &cancel,
+        )
+        .expect("NEXUS should parse");
+
+        assert_eq!(fasta.sequences[1].sequence.as_slice(), b"TGCA");
+        assert_eq!(nexus.sequences[0].sequence.as_slice(), b"AC-G");
+    }
+
+    #[test]
+    fn test_parse_strips_carriage_returns() {
+        let sequences = parse_alignment_bytes(
+            b">seq1\r\nAC\r\nGT\r\n>seq2\nACGT\n",
+            &CancellationToken::new(),
+        )
+        .expect("mixed line endings should parse")
+        .sequences;
+
+        assert_eq!(sequences[0].id.as_str(), "seq1");
+        assert_eq!(sequences[0].sequence.as_slice(), b"ACGT");
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_record_line() {
+        let content = b">seq1\nACGT\n>seq2\nAC\nGT\n\n>seq3\nACG\n";
+
+        let error = parse_alignment_bytes(content, &CancellationToken::new())
+            .expect_err("length mismatch should fail");
+
+        assert_eq!(
+            error.to_string(),
+            "Sequence length mismatch: expected 4, found 3 for id seq3 at line 7"
+        );
+    }
+
+    #[test]
+    fn test_parse_malformed_bytes_are_errors() {
+        let huge_header = [b">".as_slice(), &[b'x'; 1 << 20], b"\nACGT\n>y"].concat();
+        for content in [
+            b">".as_slice(),
+            b">seq1",
+            b">seq1\nACGT\n>",
+            b">seq1\nACGT\n>seq2\n",
+            b"@read1\nACGT\n+",
+            b"\n\n>seq1\nACGT\n",
+            b"#NEXUS\nbegin data;\nmatrix\n\xff\n;\nend;\n",
+            b"#NEXUS\nbegin data;\ndimensions nchar=x;\nend;\n",
+            &huge_header,
+        ] {
+            let result = parse_alignment_bytes(content, &CancellationToken::new());
+            assert!(result.is_err(), "{:?}", String::from_utf8_lossy(content));
+        }
+    }
+
+    #[test]
+    fn test_parse_decodes_invalid_ids_lossily() {
+        let parsed =
+            parse_alignment_bytes(b">seq\xff1\nACGT\n>seq2\nACGT\n", &CancellationToken::new())
+                .expect("invalid IDs should not abort the load");
+
+        assert_eq!(parsed.sequences[0].id.as_str(), "seq\u{FFFD}1");
+        assert_eq!(parsed.lossy_ids, 1);
+    }
+
+    #[test]
+    fn test_duplicate_ids_are_suffixed_past_taken_names() {
+        let mut parsed = parse_alignment_bytes(
+            b">a\nAC\n>a\nAG\n>a_2\nAT\n>a\nAA\n",
+            &CancellationToken::new(),
+        )
+        .expect("parse should succeed");
+
+        parsed
+            .resolve_duplicate_ids(DuplicateIds::Suffix)
+            .expect("suffixing should not fail");
+
+        let ids: Vec<_> = parsed
+            .sequences
+            .iter()
+            .map(|sequence| sequence.id.as_str())
+            .collect();
+        assert_eq!(ids, ["a", "a_3", "a_2", "a_4"]);
+        assert_eq!(parsed.renamed_rows, [1, 3]);
+    }
+
+    #[test]
+    fn test_duplicate_ids_can_be_rejected() {
+        let mut parsed =
+            parse_alignment_bytes(b">a\nAC\n>b\nAG\n>a\nAT\n", &CancellationToken::new())
+                .expect("parse should succeed");
+
+        let error = parsed
+            .resolve_duplicate_ids(DuplicateIds::Error)
+            .expect_err("duplicates should be rejected");
+
+        assert_eq!(error.to_string(), "Duplicate sequence ID a in row 3");
+    }
+
     #[test]
     fn test_parse_invalid() {
         let content = "imaninvalidfasta\nfile\n";
@@ -0,0 +1,344 @@
+use std::collections::HashMap;
+
+use anyhow::{Result, format_err};
+use libmsa::RawSequence;
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+use super::ParsedAlignment;
+use crate::core::partition;
+
+/// symbols declared by a FORMAT command
+#[derive(Debug, Clone, Copy)]
+struct Format {
+    interleave: bool,
+    gap: u8,
+    match_char: Option<u8>,
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Self {
+            interleave: false,
+            gap: b'-',
+            match_char: None,
+        }
+    }
+}
+
+/// whether `text` starts with the `#NEXUS` header
+pub fn is_nexus(text: &[u8]) -> bool {
+    let start = text.trim_ascii_start();
+    start.len() >= 6 && start[..6].eq_ignore_ascii_case(b"#NEXUS")
+}
+
+/// removes `[...]` comments, which may span lines
+fn strip_comments(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut depth = 0usize;
+    for character in text.chars() {
+        match character {
+            '[' => depth += 1,
+            ']' if depth > 0 => depth -= 1,
+            _ if depth == 0 => stripped.push(character),
+            _ => (),
+        }
+    }
+    stripped
+}
+
+/// `key=value` and bare options of a command, with keys lowercased
+fn options(arguments: &str) -> Vec<(String, Option<String>)> {
+    let normalised = arguments
+        .split('=')
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join("=");
+    normalised
+        .split_whitespace()
+        .map(|option| match option.split_once('=') {
+            Some((key, value)) => (
+                key.to_ascii_lowercase(),
+                Some(value.trim_matches(['\'', '"']).to_string()),
+            ),
+            None => (option.to_ascii_lowercase(), None),
+        })
+        .collect()
+}
+
+fn single_symbol(key: &str, value: Option<&str>) -> Result<u8> {
+    match value.map(str::as_bytes) {
+        Some(&[symbol]) => Ok(symbol),
+        _ => Err(format_err!("NEXUS {key} must be a single character")),
+    }
+}
+
+/// splits a matrix line into its taxon label and the rest. labels may be quoted, with `''` for a
+/// literal quote.
+fn split_label(line: &str) -> Result<(String, &str)> {
+    let Some(quoted) = line.strip_prefix('\'') else {
+        let end = line.find(char::is_whitespace).unwrap_or(line.len());
+        return Ok((line[..end].to_string(), &line[end..]));
+    };
+    let mut label = String::new();
+    let mut chars = quoted.char_indices().peekable();
+    while let Some((index, character)) = chars.next() {
+        if character != '\'' {
+            label.push(character);
+            continue;
+        }
+        if chars.peek().is_some_and(|&(_, next)| next == '\'') {
+            chars.next();
+            label.push('\'');
+            continue;
+        }
+        return Ok((label, &quoted[index + 1..]));
+    }
+    Err(format_err!(
+        "unterminated taxon label in NEXUS matrix: {line}"
+    ))
+}
+
+/// reads a MATRIX into rows in order of first appearance. in sequential matrices a taxon's
+/// characters may wrap over several lines once NCHAR is known; in interleaved matrices every
+/// line starts with a label.
+fn parse_matrix(
+    body: &str,
+    format: Format,
+    nchar: Option<usize>,
+    cancel: &CancellationToken,
+) -> Result<Vec<(String, Vec<u8>)>> {
+    let mut rows: Vec<(String, Vec<u8>)> = Vec::new();
+    let mut row_index: HashMap<String, usize> = HashMap::new();
+    let mut current: Option<usize> = None;
+
+    for line in body.lines() {
+        if cancel.is_cancelled() {
+            return Err(format_err!("Cancelled NEXUS parse"));
+        }
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let continues_row = !format.interleave
+            && current.is_some_and(|index| nchar.is_some_and(|nchar| rows[index].1.len() < nchar));
+        let (index, characters) = if continues_row {
+            (current.expect("checked above"), line)
+        } else {
+            let (label, rest) = split_label(line)?;
+            let index = *row_index.entry(label.clone()).or_insert_with(|| {
+                rows.push((label, Vec::new()));
+                rows.len() - 1
+            });
+            (index, rest)
+        };
+        rows[index].1.extend(
+            characters
+                .bytes()
+                .filter(|byte| !byte.is_ascii_whitespace()),
+        );
+        current = Some(index);
+    }
+    Ok(rows)
+}
+
+/// parses NEXUS text with a DATA or CHARACTERS block. CHARSET commands in any block become
+/// partitions; when they cannot be read as contiguous, non-overlapping ranges they are skipped.
+pub fn parse_nexus(text: &str, cancel: &CancellationToken) -> Result<ParsedAlignment> {
+    let text = strip_comments(text);
+    let text = text.trim_start();
+    let text = if is_nexus(text.as_bytes()) {
+        &text[6..]
+    } else {
+        text
+    };
+    let mut block: Option<String> = None;
+    let mut format = Format::default();
+    let mut ntax: Option<usize> = None;
+    let mut nchar: Option<usize> = None;
+    let mut matrix: Option<Vec<(String, Vec<u8>)>> = None;
+    let mut charsets = Vec::new();
+
+    for statement in text.split(';') {
+        let statement = statement.trim();
+        let (command, arguments) = statement
+            .split_once(char::is_whitespace)
+            .unwrap_or((statement, ""));
+        let command = command.to_ascii_lowercase();
+        let in_characters = block
+            .as_deref()
+            .is_some_and(|block| block == "data" || block == "characters");
+        match command.as_str() {
+            "begin" => block = Some(arguments.trim().to_ascii_lowercase()),
+            "end" | "endblock" => block = None,
+            "charset" => charsets.push(format!("charset {arguments}")),
+            "dimensions" if in_characters => {
+                for (key, value) in options(arguments) {
+                    let parse = || {
+                        value
+                            .as_deref()
+                            .and_then(|value| value.parse::<usize>().ok())
+                            .ok_or_else(|| format_err!("NEXUS {key} must be a number"))
+                    };
+                    match key.as_str() {
+                        "ntax" => ntax = Some(parse()?),
+                        "nchar" => nchar = Some(parse()?),
+                        _ => (),
+                    }
+                }
+            }
+            "format" if in_characters => {
+                for (key, value) in options(arguments) {
+                    match key.as_str() {
+                        "interleave" => {
+                            format.interleave = value
+                                .as_deref()
+                                .is_none_or(|value| !value.eq_ignore_ascii_case("no"));
+                        }
+                        "gap" => format.gap = single_symbol(&key, value.as_deref())?,
+                        "matchchar" => {
+                            format.match_char = Some(single_symbol(&key, value.as_deref())?);
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            "matrix" if in_characters => {
+                matrix = Some(parse_matrix(arguments, format, nchar, cancel)?);
+            }
+            _ => (),
+        }
+    }
+
+    let rows = matrix.ok_or_else(|| format_err!("NEXUS file has no DATA or CHARACTERS matrix"))?;
+    if rows.is_empty() {
+        return Err(format_err!("NEXUS matrix has no taxa"));
+    }
+    if let Some(ntax) = ntax
+        && ntax != rows.len()
+    {
+        return Err(format_err!(
+            "NEXUS matrix has {} taxa, expected {ntax}",
+            rows.len()
+        ));
+    }
+    let first_row = rows[0].1.clone();
+    let sequences = rows
+        .into_iter()
+        .map(|(id, mut sequence)| {
+            if let Some(nchar) = nchar
+                && sequence.len() != nchar
+            {
+                return Err(format_err!(
+                    "taxon '{id}' has {} characters, expected {nchar}",
+                    sequence.len()
+                ));
+            }
+            for (col, byte) in sequence.iter_mut().enumerate() {
+                if Some(*byte) == format.match_char {
+                    *byte = first_row.get(col).copied().unwrap_or(*byte);
+                } else if *byte == format.gap {
+                    *byte = b'-';
+                }
+            }
+            Ok(RawSequence {
+                id,
+                sequence,
+                quality: None,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let partitions = if charsets.is_empty() {
+        Vec::new()
+    } else {
+        partition::parse_partitions(&charsets.join("\n")).unwrap_or_else(|error| {
+            warn!(error = %error, "Skipping NEXUS charsets");
+            Vec::new()
+        })
+    };
+    Ok(ParsedAlignment::new(sequences, partitions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Result<ParsedAlignment> {
+        parse_nexus(text, &CancellationToken::new())
+    }
+
+    #[test]
+    fn interleaved_matrix_with_charsets_is_joined_by_label() {
+        let text = "#NEXUS
+[written by hand]
+BEGIN DATA;
+  DIMENSIONS NTAX=2 NCHAR=8;
+  FORMAT DATATYPE=DNA INTERLEAVE GAP=? MATCHCHAR=.;
+  MATRIX
+    'taxon one' ACGT
+    taxon_2     .?.A
+
+    'taxon one' TTAA
+    taxon_2     ..?C
+  ;
+END;
+BEGIN SETS;
+  CHARSET gene2 = 5-8; CHARSET gene1 = 1-4;
+END;
+";
+
+        let parsed = parse(text).expect("NEXUS should parse");
+
+        let rows: Vec<(&str, &[u8])> = parsed
+            .sequences
+            .iter()
+            .map(|sequence| (sequence.id.as_str(), sequence.sequence.as_slice()))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                ("taxon one", &b"ACGTTTAA"[..]),
+                ("taxon_2", &b"A-GATT-C"[..])
+            ]
+        );
+        let names: Vec<&str> = parsed
+            .partitions
+            .iter()
+            .map(|partition| partition.name.as_str())
+            .collect();
+        assert_eq!(names, ["gene1", "gene2"]);
+    }
+
+    #[test]
+    fn sequential_rows_may_wrap_once_nchar_is_known() {
+        let text = "#NEXUS
+begin characters;
+  dimensions nchar=6;
+  matrix
+    a ACG
+      TAC
+    b ACGTAA
+  ;
+end;
+";
+
+        let parsed = parse(text).expect("NEXUS should parse");
+
+        assert_eq!(parsed.sequences[0].sequence, b"ACGTAC");
+        assert_eq!(parsed.sequences[1].sequence, b"ACGTAA");
+        assert!(parsed.partitions.is_empty());
+    }
+
+    #[test]
+    fn dimensions_are_checked() {
+        let short = "#NEXUS\nbegin data; dimensions ntax=2 nchar=4; matrix\na ACGT\nb ACG\n; end;";
+        let taxa = "#NEXUS\nbegin data; dimensions ntax=3; matrix\na ACGT\nb ACGT\n; end;";
+
+        assert!(parse(short).is_err());
+        assert!(parse(taxa).is_err());
+        assert!(parse("#NEXUS\nbegin taxa; end;").is_err());
+        assert!(is_nexus(b"\n  #nexus\n"));
+        assert!(!is_nexus(b">seq1\nACGT\n"));
+    }
+}
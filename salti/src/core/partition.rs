@@ -0,0 +1,120 @@
+use std::ops::Range;
+
+use anyhow::{Result, format_err};
+
+/// block of columns contributed by one gene or file of a concatenated alignment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Partition {
+    pub name: String,
+    /// absolute columns covered by the partition
+    pub columns: Range<usize>,
+}
+
+/// parses a `start-end` or single 1-based position into absolute columns
+fn parse_range(name: &str, text: &str) -> Result<Range<usize>> {
+    let invalid = || format_err!("partition '{name}' has an invalid range '{text}'");
+    if text.contains(['\\', ',']) || text.split_whitespace().count() > 1 {
+        return Err(format_err!(
+            "partition '{name}' is not a single contiguous range"
+        ));
+    }
+    let (start, end) = text.split_once('-').unwrap_or((text, text));
+    let (Ok(start), Ok(end)) = (start.trim().parse::<usize>(), end.trim().parse::<usize>()) else {
+        return Err(invalid());
+    };
+    if start == 0 || end < start {
+        return Err(invalid());
+    }
+    Ok(start - 1..end)
+}
+
+/// parses a RAxML-style (`DNA, gene1 = 1-500`) or NEXUS charset (`charset gene1 = 1-500;`)
+/// partition file. other NEXUS lines are skipped. partitions are returned in column order and
+/// must not overlap.
+pub fn parse_partitions(text: &str) -> Result<Vec<Partition>> {
+    let mut partitions = Vec::new();
+    for line in text.lines() {
+        let line = line.trim().trim_end_matches(';').trim();
+        let Some((label, range)) = line.split_once('=') else {
+            continue;
+        };
+        let label = label.trim();
+        let name = match label.split_once(char::is_whitespace) {
+            Some((keyword, name)) if keyword.eq_ignore_ascii_case("charset") => name.trim(),
+            _ => match label.split_once(',') {
+                Some((_, name)) => name.trim(),
+                None => continue,
+            },
+        };
+        if name.is_empty() {
+            return Err(format_err!("partition on line '{line}' has no name"));
+        }
+        partitions.push(Partition {
+            name: name.to_string(),
+            columns: parse_range(name, range.trim())?,
+        });
+    }
+    if partitions.is_empty() {
+        return Err(format_err!("no partitions found"));
+    }
+
+    partitions.sort_by_key(|partition| partition.columns.start);
+    if let Some(pair) = partitions
+        .windows(2)
+        .find(|pair| pair[1].columns.start < pair[0].columns.end)
+    {
+        return Err(format_err!(
+            "partitions '{}' and '{}' overlap",
+            pair[0].name,
+            pair[1].name
+        ));
+    }
+    Ok(partitions)
+}
+
+/// index of the partition holding absolute column `abs_col`
+pub fn partition_at(partitions: &[Partition], abs_col: usize) -> Option<usize> {
+    partitions
+        .iter()
+        .position(|partition| partition.columns.contains(&abs_col))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raxml_and_nexus_partitions_parse_in_column_order() {
+        let raxml = "DNA, gene2 = 501-800\nDNA, gene1 = 1-500\n";
+        let nexus =
+            "#NEXUS\nbegin sets;\n  charset gene1 = 1-500;\n  CHARSET gene2 = 501-800;\nend;\n";
+
+        for text in [raxml, nexus] {
+            let partitions = parse_partitions(text).expect("partitions should parse");
+            assert_eq!(
+                partitions,
+                vec![
+                    Partition {
+                        name: "gene1".to_string(),
+                        columns: 0..500,
+                    },
+                    Partition {
+                        name: "gene2".to_string(),
+                        columns: 500..800,
+                    },
+                ]
+            );
+            assert_eq!(partition_at(&partitions, 500), Some(1));
+            assert_eq!(partition_at(&partitions, 800), None);
+        }
+    }
+
+    #[test]
+    fn unsupported_or_overlapping_ranges_are_rejected() {
+        assert!(parse_partitions("DNA, codon = 1-300\\3").is_err());
+        assert!(parse_partitions("DNA, split = 1-10, 20-30").is_err());
+        assert!(parse_partitions("DNA, a = 1-10\nDNA, b = 10-20").is_err());
+        assert!(parse_partitions("DNA, a = 0-10").is_err());
+        assert!(parse_partitions("#NEXUS\nbegin sets;\nend;").is_err());
+    }
+}
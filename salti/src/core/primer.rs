@@ -0,0 +1,277 @@
+use anyhow::{Result, format_err};
+use tokio_util::sync::CancellationToken;
+
+/// mismatches allowed at a binding site when `find-primer` is not given a limit
+pub const DEFAULT_MAX_PRIMER_MISMATCHES: usize = 3;
+
+/// bases matched by an IUPAC code as a bitmask of A, C, G and T. gaps and unknown bytes match
+/// nothing.
+pub const fn iupac_mask(base: u8) -> u8 {
+    const A: u8 = 1;
+    const C: u8 = 2;
+    const G: u8 = 4;
+    const T: u8 = 8;
+    match base.to_ascii_uppercase() {
+        b'A' => A,
+        b'C' => C,
+        b'G' => G,
+        b'T' | b'U' => T,
+        b'R' => A | G,
+        b'Y' => C | T,
+        b'S' => C | G,
+        b'W' => A | T,
+        b'K' => G | T,
+        b'M' => A | C,
+        b'B' => C | G | T,
+        b'D' => A | G | T,
+        b'H' => A | C | T,
+        b'V' => A | C | G,
+        b'N' => A | C | G | T,
+        _ => 0,
+    }
+}
+
+/// complement of an uppercase IUPAC nucleotide code
+pub(crate) const fn complement(base: u8) -> u8 {
+    match base {
+        b'A' => b'T',
+        b'C' => b'G',
+        b'G' => b'C',
+        b'T' => b'A',
+        b'R' => b'Y',
+        b'Y' => b'R',
+        b'K' => b'M',
+        b'M' => b'K',
+        b'B' => b'V',
+        b'V' => b'B',
+        b'D' => b'H',
+        b'H' => b'D',
+        other => other,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strand {
+    Forward,
+    Reverse,
+}
+
+impl Strand {
+    pub const fn symbol(self) -> char {
+        match self {
+            Self::Forward => '+',
+            Self::Reverse => '-',
+        }
+    }
+}
+
+/// primer or probe in both orientations, stored as IUPAC masks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Primer {
+    sequence: String,
+    forward: Vec<u8>,
+    reverse: Vec<u8>,
+}
+
+impl Primer {
+    /// parses a primer written in IUPAC nucleotide codes. `U` is read as `T`.
+    pub fn parse(text: &str) -> Result<Self> {
+        let sequence = text.trim().to_ascii_uppercase().replace('U', "T");
+        if sequence.is_empty() {
+            return Err(format_err!("primer sequence is empty"));
+        }
+        if let Some(invalid) = sequence.bytes().find(|&base| iupac_mask(base) == 0) {
+            return Err(format_err!(
+                "'{}' is not an IUPAC nucleotide code",
+                char::from(invalid)
+            ));
+        }
+        let forward = sequence.bytes().map(iupac_mask).collect();
+        let reverse = sequence
+            .bytes()
+            .rev()
+            .map(|base| iupac_mask(complement(base)))
+            .collect();
+        Ok(Self {
+            sequence,
+            forward,
+            reverse,
+        })
+    }
+
+    pub fn sequence(&self) -> &str {
+        &self.sequence
+    }
+
+    pub fn len(&self) -> usize {
+        self.forward.len()
+    }
+}
+
+/// best binding site of a primer in one aligned sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrimerHit {
+    pub strand: Strand,
+    /// first alignment column covered by the site
+    pub first_col: usize,
+    /// last alignment column covered by the site
+    pub last_col: usize,
+    pub mismatches: usize,
+}
+
+/// mismatches between `probe` and `bases`, or `None` once they exceed `limit`
+fn mismatches_within(probe: &[u8], bases: &[u8], limit: usize) -> Option<usize> {
+    let mut mismatches = 0;
+    for (&probe_mask, &base) in probe.iter().zip(bases) {
+        if probe_mask & iupac_mask(base) == 0 {
+            mismatches += 1;
+            if mismatches > limit {
+                return None;
+            }
+        }
+    }
+    Some(mismatches)
+}
+
+/// non-gap bases of an aligned sequence and the column each came from
+fn ungapped(aligned: &[u8]) -> (Vec<usize>, Vec<u8>) {
+    (0..aligned.len())
+        .filter(|&col| !matches!(aligned[col], b'-' | b'.'))
+        .map(|col| (col, aligned[col]))
+        .unzip()
+}
+
+/// finds the site with the fewest mismatches (at most `max_mismatches`) on either strand. gaps
+/// are removed before matching, so sites may span gapped columns. ties keep the leftmost forward
+/// site.
+pub fn best_hit(aligned: &[u8], primer: &Primer, max_mismatches: usize) -> Option<PrimerHit> {
+    let (columns, bases) = ungapped(aligned);
+    if bases.len() < primer.len() {
+        return None;
+    }
+
+    let mut best: Option<PrimerHit> = None;
+    for start in 0..=bases.len() - primer.len() {
+        let window = &bases[start..start + primer.len()];
+        for (strand, probe) in [
+            (Strand::Forward, &primer.forward),
+            (Strand::Reverse, &primer.reverse),
+        ] {
+            let limit = best.map_or(max_mismatches, |hit| hit.mismatches.saturating_sub(1));
+            if best.is_some_and(|hit| hit.mismatches == 0) {
+                return best;
+            }
+            let Some(mismatches) = mismatches_within(probe, window, limit) else {
+                continue;
+            };
+            best = Some(PrimerHit {
+                strand,
+                first_col: columns[start],
+                last_col: columns[start + primer.len() - 1],
+                mismatches,
+            });
+        }
+    }
+    best
+}
+
+/// every site matching `primer` exactly on either strand, leftmost first. palindromic sites are
+/// reported once, on the forward strand.
+pub fn exact_sites(aligned: &[u8], primer: &Primer) -> Vec<PrimerHit> {
+    let (columns, bases) = ungapped(aligned);
+    if bases.len() < primer.len() {
+        return Vec::new();
+    }
+    (0..=bases.len() - primer.len())
+        .filter_map(|start| {
+            let window = &bases[start..start + primer.len()];
+            let strand = if mismatches_within(&primer.forward, window, 0).is_some() {
+                Strand::Forward
+            } else if mismatches_within(&primer.reverse, window, 0).is_some() {
+                Strand::Reverse
+            } else {
+                return None;
+            };
+            Some(PrimerHit {
+                strand,
+                first_col: columns[start],
+                last_col: columns[start + primer.len() - 1],
+                mismatches: 0,
+            })
+        })
+        .collect()
+}
+
+/// best site in each `(absolute row, id, aligned bytes)` row, in row order
+pub fn search_rows(
+    rows: &[(usize, String, Vec<u8>)],
+    primer: &Primer,
+    max_mismatches: usize,
+    cancel: &CancellationToken,
+) -> Result<Vec<Option<PrimerHit>>> {
+    rows.iter()
+        .map(|(_, _, bytes)| {
+            if cancel.is_cancelled() {
+                return Err(format_err!("Primer search cancelled"));
+            }
+            Ok(best_hit(bytes, primer, max_mismatches))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn primer(text: &str) -> Primer {
+        Primer::parse(text).expect("test primer should parse")
+    }
+
+    #[test]
+    fn degenerate_codes_match_any_of_their_bases() {
+        let hit = best_hit(b"TTACGTTT", &primer("AYGT"), 0).expect("site should match");
+
+        assert_eq!(
+            hit,
+            PrimerHit {
+                strand: Strand::Forward,
+                first_col: 2,
+                last_col: 5,
+                mismatches: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn reverse_strand_sites_span_gaps() {
+        // reverse complement of GGATC is GATCC
+        let hit = best_hit(b"AAGA-TCCAA", &primer("GGATC"), 1).expect("site should match");
+
+        assert_eq!(
+            hit,
+            PrimerHit {
+                strand: Strand::Reverse,
+                first_col: 2,
+                last_col: 7,
+                mismatches: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn fewest_mismatches_win_and_the_limit_is_respected() {
+        let sequence = b"ACCTAAAAACGTAA";
+
+        let hit = best_hit(sequence, &primer("ACGT"), 1).expect("exact site should match");
+        assert_eq!((hit.first_col, hit.mismatches), (8, 0));
+
+        assert_eq!(best_hit(b"TTTTTTTT", &primer("ACGG"), 1), None);
+    }
+
+    #[test]
+    fn primers_must_be_iupac() {
+        assert_eq!(primer("acgu").sequence(), "ACGT");
+        assert!(Primer::parse("ACGZ").is_err());
+        assert!(Primer::parse("AC-G").is_err());
+    }
+}
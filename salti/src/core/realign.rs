@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::process::Command;
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{Result, format_err};
+use tokio_util::sync::CancellationToken;
+
+use crate::core::jobs::run_with_timeout;
+use crate::core::parser;
+
+/// longest an external aligner may run before it is stopped
+pub const ALIGNER_TIMEOUT: Duration = Duration::from_secs(300);
+
+const fn is_gap(byte: u8) -> bool {
+    matches!(byte, b'-' | b'.')
+}
+
+/// external program used by `realign-selection`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aligner {
+    Mafft,
+    Muscle,
+}
+
+impl Aligner {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Mafft => "mafft",
+            Self::Muscle => "muscle",
+        }
+    }
+
+    pub const fn all() -> [Self; 2] {
+        [Self::Mafft, Self::Muscle]
+    }
+
+    /// mafft writes the alignment to stdout, muscle (v5) to `output`
+    fn command(self, input: &Path, output: &Path) -> Command {
+        let mut command = Command::new(self.name());
+        match self {
+            Self::Mafft => {
+                command
+                    .args(["--auto", "--quiet", "--preservecase"])
+                    .arg(input);
+            }
+            Self::Muscle => {
+                command.arg("-align").arg(input).arg("-output").arg(output);
+            }
+        }
+        command
+    }
+}
+
+impl fmt::Display for Aligner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Aligner {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|aligner| aligner.name() == value)
+            .ok_or_else(|| format_err!("invalid aligner: {value}"))
+    }
+}
+
+/// realigns the `rows` of one alignment block with `aligner`. the result has one row per input
+/// row, all the same width; rows without residues come back as gaps.
+pub fn realign_rows(
+    aligner: Aligner,
+    rows: &[Vec<u8>],
+    cancel: &CancellationToken,
+) -> Result<Vec<Vec<u8>>> {
+    let residues: Vec<Vec<u8>> = rows
+        .iter()
+        .map(|row| row.iter().copied().filter(|&byte| !is_gap(byte)).collect())
+        .collect();
+    if residues.iter().filter(|row| !row.is_empty()).count() < 2 {
+        return Err(format_err!(
+            "at least two selected sequences need residues in the selected columns"
+        ));
+    }
+    let mut fasta = String::new();
+    for (index, row) in residues.iter().enumerate() {
+        if !row.is_empty() {
+            fasta.push_str(&format!(">r{index}\n{}\n", String::from_utf8_lossy(row)));
+        }
+    }
+
+    // a private directory per run, so concurrent realigns never share files. it is removed
+    // with everything in it when `dir` drops.
+    let dir = tempfile::Builder::new()
+        .prefix("salti-realign-")
+        .tempdir()
+        .map_err(|error| format_err!("could not create a temporary directory: {error}"))?;
+    let input = dir.path().join("in.fa");
+    let output = dir.path().join("out.fa");
+    std::fs::write(&input, fasta)
+        .map_err(|error| format_err!("could not write {}: {error}", input.display()))?;
+    let result = run_with_timeout(aligner.command(&input, &output), ALIGNER_TIMEOUT, cancel)
+        .and_then(|stdout| match aligner {
+            Aligner::Mafft => Ok(stdout),
+            Aligner::Muscle => std::fs::read(&output)
+                .map_err(|error| format_err!("could not read {aligner} output: {error}")),
+        });
+
+    restitch_output(aligner, &residues, &result?, cancel)
+}
+
+/// matches aligner output back to the input rows by their `r<index>` names, checking that
+/// each row kept its residues
+fn restitch_output(
+    aligner: Aligner,
+    residues: &[Vec<u8>],
+    output: &[u8],
+    cancel: &CancellationToken,
+) -> Result<Vec<Vec<u8>>> {
+    let parsed = parser::parse_alignment_bytes(output, cancel)
+        .map_err(|error| format_err!("could not read {aligner} output: {error:#}"))?;
+    let mut aligned: HashMap<usize, Vec<u8>> = HashMap::new();
+    for sequence in parsed.sequences {
+        let index = sequence
+            .id
+            .strip_prefix('r')
+            .and_then(|index| index.parse().ok())
+            .filter(|index: &usize| *index < residues.len())
+            .ok_or_else(|| {
+                format_err!("{aligner} returned an unknown sequence '{}'", sequence.id)
+            })?;
+        aligned.insert(index, sequence.sequence);
+    }
+    let width = aligned.values().map(Vec::len).max().unwrap_or(0);
+    residues
+        .iter()
+        .enumerate()
+        .map(|(index, expected)| {
+            if expected.is_empty() {
+                return Ok(vec![b'-'; width]);
+            }
+            let row = aligned
+                .remove(&index)
+                .ok_or_else(|| format_err!("{aligner} dropped a sequence from its output"))?;
+            let kept: Vec<u8> = row.iter().copied().filter(|&byte| !is_gap(byte)).collect();
+            if !kept.eq_ignore_ascii_case(expected) || row.len() != width {
+                return Err(format_err!(
+                    "{aligner} changed the residues of a sequence; the alignment was left as it was"
+                ));
+            }
+            Ok(row)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_rows_are_matched_back_to_their_inputs() {
+        let residues = vec![b"ACGT".to_vec(), Vec::new(), b"AGT".to_vec()];
+        let output = b">r2\nA-GT\n>r0\nACGT\n";
+
+        let rows =
+            restitch_output(Aligner::Mafft, &residues, output, &CancellationToken::new()).unwrap();
+
+        assert_eq!(
+            rows,
+            vec![b"ACGT".to_vec(), b"----".to_vec(), b"A-GT".to_vec()]
+        );
+    }
+
+    #[test]
+    fn output_that_changes_residues_is_rejected() {
+        let residues = vec![b"ACGT".to_vec(), b"AGT".to_vec()];
+        let cancel = CancellationToken::new();
+
+        assert!(
+            restitch_output(
+                Aligner::Muscle,
+                &residues,
+                b">r0\nACGT\n>r1\nA-GA\n",
+                &cancel
+            )
+            .is_err()
+        );
+        assert!(restitch_output(Aligner::Muscle, &residues, b">r0\nACGT\n", &cancel).is_err());
+    }
+}
@@ -0,0 +1,139 @@
+use anyhow::{Result, format_err};
+
+use crate::core::primer::{self, Primer};
+
+/// restriction enzyme and its recognition site in IUPAC codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Enzyme {
+    pub name: &'static str,
+    pub site: &'static str,
+}
+
+impl Enzyme {
+    const fn new(name: &'static str, site: &'static str) -> Self {
+        Self { name, site }
+    }
+}
+
+/// common commercially available enzymes, sorted by name
+pub const ENZYMES: &[Enzyme] = &[
+    Enzyme::new("AluI", "AGCT"),
+    Enzyme::new("ApaI", "GGGCCC"),
+    Enzyme::new("AscI", "GGCGCGCC"),
+    Enzyme::new("BamHI", "GGATCC"),
+    Enzyme::new("BglII", "AGATCT"),
+    Enzyme::new("BsaI", "GGTCTC"),
+    Enzyme::new("BsmBI", "CGTCTC"),
+    Enzyme::new("ClaI", "ATCGAT"),
+    Enzyme::new("DpnII", "GATC"),
+    Enzyme::new("EcoRI", "GAATTC"),
+    Enzyme::new("EcoRV", "GATATC"),
+    Enzyme::new("HaeIII", "GGCC"),
+    Enzyme::new("HincII", "GTYRAC"),
+    Enzyme::new("HindIII", "AAGCTT"),
+    Enzyme::new("HinfI", "GANTC"),
+    Enzyme::new("KpnI", "GGTACC"),
+    Enzyme::new("MluI", "ACGCGT"),
+    Enzyme::new("MspI", "CCGG"),
+    Enzyme::new("NcoI", "CCATGG"),
+    Enzyme::new("NdeI", "CATATG"),
+    Enzyme::new("NheI", "GCTAGC"),
+    Enzyme::new("NotI", "GCGGCCGC"),
+    Enzyme::new("PacI", "TTAATTAA"),
+    Enzyme::new("PstI", "CTGCAG"),
+    Enzyme::new("SacI", "GAGCTC"),
+    Enzyme::new("SalI", "GTCGAC"),
+    Enzyme::new("SapI", "GCTCTTC"),
+    Enzyme::new("SfiI", "GGCCNNNNNGGCC"),
+    Enzyme::new("SmaI", "CCCGGG"),
+    Enzyme::new("SpeI", "ACTAGT"),
+    Enzyme::new("TaqI", "TCGA"),
+    Enzyme::new("XbaI", "TCTAGA"),
+    Enzyme::new("XhoI", "CTCGAG"),
+];
+
+/// looks up an enzyme by name, ignoring case
+pub fn enzyme(name: &str) -> Result<Enzyme> {
+    ENZYMES
+        .iter()
+        .find(|enzyme| enzyme.name.eq_ignore_ascii_case(name))
+        .copied()
+        .ok_or_else(|| format_err!("unknown restriction enzyme '{name}'"))
+}
+
+/// recognition site found in the reference or consensus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestrictionSite {
+    pub enzyme: &'static str,
+    /// first alignment column covered by the site
+    pub first_col: usize,
+    /// last alignment column covered by the site
+    pub last_col: usize,
+}
+
+/// sites of every enzyme in an aligned sequence, in column order. columns are relative to the
+/// sequence, and sites may span gapped columns.
+pub fn find_sites(aligned: &[u8], enzymes: &[Enzyme]) -> Vec<RestrictionSite> {
+    let mut sites: Vec<RestrictionSite> = enzymes
+        .iter()
+        .flat_map(|enzyme| {
+            let site = Primer::parse(enzyme.site).expect("built-in sites are valid IUPAC");
+            primer::exact_sites(aligned, &site)
+                .into_iter()
+                .map(|hit| RestrictionSite {
+                    enzyme: enzyme.name,
+                    first_col: hit.first_col,
+                    last_col: hit.last_col,
+                })
+        })
+        .collect();
+    sites.sort_by_key(|site| (site.first_col, site.enzyme));
+    sites
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn built_in_sites_are_valid_and_names_unique() {
+        for (index, enzyme) in ENZYMES.iter().enumerate() {
+            assert!(Primer::parse(enzyme.site).is_ok(), "{}", enzyme.name);
+            assert!(
+                ENZYMES[index + 1..]
+                    .iter()
+                    .all(|other| !other.name.eq_ignore_ascii_case(enzyme.name)),
+                "{} is listed twice",
+                enzyme.name
+            );
+        }
+    }
+
+    #[test]
+    fn sites_are_found_on_both_strands_across_gaps() {
+        let enzymes = [
+            enzyme("ecori").expect("EcoRI is built in"),
+            enzyme("BsaI").expect("BsaI is built in"),
+        ];
+
+        // GAATTC across a gap, then GAGACC (BsaI on the reverse strand)
+        let sites = find_sites(b"AGAA-TTCAGAGACCA", &enzymes);
+
+        assert_eq!(
+            sites,
+            vec![
+                RestrictionSite {
+                    enzyme: "EcoRI",
+                    first_col: 1,
+                    last_col: 7,
+                },
+                RestrictionSite {
+                    enzyme: "BsaI",
+                    first_col: 9,
+                    last_col: 14,
+                },
+            ]
+        );
+        assert!(enzyme("FooI").is_err());
+    }
+}
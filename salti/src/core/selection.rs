@@ -0,0 +1,194 @@
+use crate::core::model::AlignmentModel;
+use crate::ui::ui_state::MouseSelection;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SelectionStats {
+    pub rows: usize,
+    pub columns: usize,
+    /// columns where every selected row has the same non-gap residue
+    pub identical_columns: usize,
+    pub gap_fraction: f32,
+}
+
+impl SelectionStats {
+    pub fn identity(&self) -> f32 {
+        if self.columns == 0 {
+            return 0.0;
+        }
+        self.identical_columns as f32 / self.columns as f32
+    }
+}
+
+impl std::fmt::Display for SelectionStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} rows x {} cols | identity {:.1}% | gaps {:.1}%",
+            self.rows,
+            self.columns,
+            self.identity() * 100.0,
+            self.gap_fraction * 100.0
+        )
+    }
+}
+
+/// absolute rows covered by the selection, in display order (pinned rows first)
+pub fn selected_rows(alignment: &AlignmentModel, selection: MouseSelection) -> Vec<usize> {
+    let row_min = selection.sequence_id.min(selection.end_sequence_id);
+    let row_max = selection.sequence_id.max(selection.end_sequence_id);
+    alignment
+        .rows()
+        .pinned()
+        .iter()
+        .copied()
+        .chain(alignment.view().absolute_row_ids())
+        .filter(|row| (row_min..=row_max).contains(row))
+        .collect()
+}
+
+/// absolute columns covered by the selection that are visible in the current view
+pub fn selected_columns(alignment: &AlignmentModel, selection: MouseSelection) -> Vec<usize> {
+    let col_min = selection.column.min(selection.end_column);
+    let col_max = selection.column.max(selection.end_column);
+    alignment
+        .view()
+        .absolute_column_ids()
+        .filter(|col| (col_min..=col_max).contains(col))
+        .collect()
+}
+
+fn selected_bytes<'a>(
+    alignment: &'a AlignmentModel,
+    abs_row: usize,
+    columns: &'a [usize],
+) -> impl Iterator<Item = u8> + 'a {
+    let sequence = alignment.base().project_absolute_row(abs_row);
+    columns
+        .iter()
+        .filter_map(move |&col| sequence.and_then(|sequence| sequence.byte_at(col)))
+}
+
+pub fn selection_fasta(alignment: &AlignmentModel, selection: MouseSelection) -> String {
+    let columns = selected_columns(alignment, selection);
+    let mut fasta = String::new();
+    for abs_row in selected_rows(alignment, selection) {
+        let Some(sequence) = alignment.base().project_absolute_row(abs_row) else {
+            continue;
+        };
+        let bytes: Vec<u8> = selected_bytes(alignment, abs_row, &columns).collect();
+        fasta.push('>');
+        fasta.push_str(sequence.id());
+        fasta.push('\n');
+        fasta.push_str(&String::from_utf8_lossy(&bytes));
+        fasta.push('\n');
+    }
+    fasta
+}
+
+pub fn selection_stats(
+    alignment: &AlignmentModel,
+    selection: MouseSelection,
+) -> Option<SelectionStats> {
+    let rows = selected_rows(alignment, selection);
+    let columns = selected_columns(alignment, selection);
+    if rows.is_empty() || columns.is_empty() {
+        return None;
+    }
+
+    let row_bytes: Vec<Vec<u8>> = rows
+        .iter()
+        .map(|&abs_row| selected_bytes(alignment, abs_row, &columns).collect())
+        .collect();
+    let mut gaps = 0usize;
+    let mut identical_columns = 0usize;
+    for col_index in 0..columns.len() {
+        let mut first_residue = None;
+        let mut identical = true;
+        for bytes in &row_bytes {
+            let byte = bytes[col_index].to_ascii_uppercase();
+            if byte == b'-' {
+                gaps += 1;
+                identical = false;
+                continue;
+            }
+            match first_residue {
+                None => first_residue = Some(byte),
+                Some(residue) if residue != byte => identical = false,
+                Some(_) => (),
+            }
+        }
+        if identical {
+            identical_columns += 1;
+        }
+    }
+
+    Some(SelectionStats {
+        rows: rows.len(),
+        columns: columns.len(),
+        identical_columns,
+        gap_fraction: gaps as f32 / (rows.len() * columns.len()) as f32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw(id: &str, sequence: &[u8]) -> libmsa::RawSequence {
+        libmsa::RawSequence {
+            id: id.to_string(),
+            sequence: sequence.to_vec(),
+            quality: None,
+        }
+    }
+
+    fn model() -> AlignmentModel {
+        let alignment = libmsa::Alignment::new(vec![
+            raw("row1", b"ACGTA"),
+            raw("row2", b"ACG-A"),
+            raw("row3", b"TCGTA"),
+        ])
+        .expect("alignment should be valid");
+        AlignmentModel::new(alignment).expect("alignment model should build")
+    }
+
+    fn selection(rows: (usize, usize), columns: (usize, usize)) -> MouseSelection {
+        MouseSelection {
+            sequence_id: rows.0,
+            column: columns.0,
+            end_sequence_id: rows.1,
+            end_column: columns.1,
+        }
+    }
+
+    #[test]
+    fn fasta_export_contains_only_the_selected_block() {
+        let model = model();
+
+        let fasta = selection_fasta(&model, selection((1, 0), (3, 1)));
+
+        assert_eq!(fasta, ">row1\nCGT\n>row2\nCG-\n");
+    }
+
+    #[test]
+    fn stats_report_identity_and_gaps() {
+        let model = model();
+
+        let stats = selection_stats(&model, selection((0, 2), (0, 3))).expect("stats should exist");
+
+        assert_eq!(stats.rows, 3);
+        assert_eq!(stats.columns, 4);
+        assert_eq!(stats.identical_columns, 2);
+        assert!((stats.gap_fraction - 1.0 / 12.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn pinned_rows_are_included_before_scrollable_rows() {
+        let mut model = model();
+        model.pin(2).expect("pin should succeed");
+
+        let rows = selected_rows(&model, selection((0, 2), (0, 0)));
+
+        assert_eq!(rows, vec![2, 0, 1]);
+    }
+}
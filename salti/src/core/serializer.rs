@@ -0,0 +1,326 @@
+use std::fmt::{self, Write as _};
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{Context, Result, format_err};
+use tokio_util::sync::CancellationToken;
+
+use crate::core::export::{self, JobProgress};
+use crate::core::partition::Partition;
+
+/// residues per line in Clustal blocks
+const CLUSTAL_LINE_WIDTH: usize = 60;
+
+/// alignment file formats written by `save-as`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AlignmentFormat {
+    #[default]
+    Fasta,
+    Clustal,
+    Phylip,
+    Stockholm,
+    Nexus,
+}
+
+impl AlignmentFormat {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Fasta => "fasta",
+            Self::Clustal => "clustal",
+            Self::Phylip => "phylip",
+            Self::Stockholm => "stockholm",
+            Self::Nexus => "nexus",
+        }
+    }
+
+    pub const fn all() -> [Self; 5] {
+        [
+            Self::Fasta,
+            Self::Clustal,
+            Self::Phylip,
+            Self::Stockholm,
+            Self::Nexus,
+        ]
+    }
+
+    const fn extensions(self) -> &'static [&'static str] {
+        match self {
+            Self::Fasta => &["fasta", "fa", "fas", "fna", "faa", "afa"],
+            Self::Clustal => &["aln", "clustal"],
+            Self::Phylip => &["phy", "phylip"],
+            Self::Stockholm => &["sto", "stk", "stockholm"],
+            Self::Nexus => &["nex", "nexus", "nxs"],
+        }
+    }
+
+    /// picks the format from the file extension, defaulting to fasta
+    pub fn from_path(path: &Path) -> Self {
+        let Some(extension) = path.extension().and_then(|extension| extension.to_str()) else {
+            return Self::Fasta;
+        };
+        Self::all()
+            .into_iter()
+            .find(|format| {
+                format
+                    .extensions()
+                    .iter()
+                    .any(|known| known.eq_ignore_ascii_case(extension))
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for AlignmentFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for AlignmentFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|format| format.name() == value)
+            .ok_or_else(|| format_err!("invalid alignment format: {value}"))
+    }
+}
+
+/// IDs with whitespace replaced, for formats that separate names from residues by spaces
+fn plain_id(id: &str) -> String {
+    id.split_whitespace().collect::<Vec<_>>().join("_")
+}
+
+/// NEXUS label, quoted when it holds whitespace or punctuation
+fn nexus_label(id: &str) -> String {
+    if id
+        .chars()
+        .all(|character| character.is_alphanumeric() || matches!(character, '_' | '.' | '-'))
+    {
+        return id.to_string();
+    }
+    format!("'{}'", id.replace('\'', "''"))
+}
+
+fn padded_rows(rows: &[(String, Vec<u8>)], label: fn(&str) -> String) -> Vec<(String, &[u8])> {
+    let labels: Vec<String> = rows.iter().map(|(id, _)| label(id)).collect();
+    let width = labels.iter().map(String::len).max().unwrap_or(0);
+    labels
+        .into_iter()
+        .zip(rows)
+        .map(|(label, (_, bytes))| (format!("{label:<width$}"), bytes.as_slice()))
+        .collect()
+}
+
+/// clustal conservation marks: `*` where every row holds the same residue
+fn clustal_marks(rows: &[(String, &[u8])], columns: std::ops::Range<usize>) -> String {
+    columns
+        .map(|col| {
+            let mut residues = rows
+                .iter()
+                .map(|(_, bytes)| bytes[col].to_ascii_uppercase());
+            let first = residues.next();
+            let identical = first.is_some_and(|first| {
+                !matches!(first, b'-' | b'.') && residues.all(|residue| residue == first)
+            });
+            if identical { '*' } else { ' ' }
+        })
+        .collect()
+}
+
+fn format_clustal(rows: &[(String, Vec<u8>)]) -> String {
+    let rows = padded_rows(rows, plain_id);
+    let width = rows.first().map_or(0, |(_, bytes)| bytes.len());
+    let label_width = rows.first().map_or(0, |(label, _)| label.len());
+    let mut text = String::from("CLUSTAL W multiple sequence alignment\n\n");
+    for start in (0..width).step_by(CLUSTAL_LINE_WIDTH) {
+        let end = (start + CLUSTAL_LINE_WIDTH).min(width);
+        text.push('\n');
+        for (label, bytes) in &rows {
+            let _ = writeln!(
+                text,
+                "{label}      {}",
+                String::from_utf8_lossy(&bytes[start..end])
+            );
+        }
+        let _ = writeln!(
+            text,
+            "{:label_width$}      {}",
+            "",
+            clustal_marks(&rows, start..end)
+        );
+    }
+    text
+}
+
+fn format_phylip(rows: &[(String, Vec<u8>)]) -> String {
+    let width = rows.first().map_or(0, |(_, bytes)| bytes.len());
+    let mut text = format!("{} {width}\n", rows.len());
+    for (label, bytes) in padded_rows(rows, plain_id) {
+        let _ = writeln!(text, "{label}  {}", String::from_utf8_lossy(bytes));
+    }
+    text
+}
+
+fn format_stockholm(rows: &[(String, Vec<u8>)]) -> String {
+    let mut text = String::from("# STOCKHOLM 1.0\n\n");
+    for (label, bytes) in padded_rows(rows, plain_id) {
+        let _ = writeln!(text, "{label} {}", String::from_utf8_lossy(bytes));
+    }
+    text.push_str("//\n");
+    text
+}
+
+fn format_nexus(
+    rows: &[(String, Vec<u8>)],
+    alignment_type: libmsa::AlignmentType,
+    partitions: &[Partition],
+) -> String {
+    let width = rows.first().map_or(0, |(_, bytes)| bytes.len());
+    let datatype = match alignment_type {
+        libmsa::AlignmentType::Dna => " DATATYPE=DNA",
+        libmsa::AlignmentType::Rna => " DATATYPE=RNA",
+        libmsa::AlignmentType::Protein => " DATATYPE=PROTEIN",
+        libmsa::AlignmentType::Generic => "",
+    };
+    let mut text = String::from("#NEXUS\n\nBEGIN DATA;\n");
+    let _ = writeln!(text, "  DIMENSIONS NTAX={} NCHAR={width};", rows.len());
+    let _ = writeln!(text, "  FORMAT{datatype} GAP=- MISSING=?;");
+    text.push_str("  MATRIX\n");
+    for (label, bytes) in padded_rows(rows, nexus_label) {
+        let _ = writeln!(text, "    {label}  {}", String::from_utf8_lossy(bytes));
+    }
+    text.push_str("  ;\nEND;\n");
+    if !partitions.is_empty() {
+        text.push_str("\nBEGIN SETS;\n");
+        for partition in partitions {
+            let _ = writeln!(
+                text,
+                "  CHARSET {} = {}-{};",
+                nexus_label(&partition.name),
+                partition.columns.start + 1,
+                partition.columns.end
+            );
+        }
+        text.push_str("END;\n");
+    }
+    text
+}
+
+/// renders `(id, aligned bytes)` rows in `format`. partitions are only written to NEXUS.
+pub fn format_alignment(
+    rows: &[(String, Vec<u8>)],
+    format: AlignmentFormat,
+    alignment_type: libmsa::AlignmentType,
+    partitions: &[Partition],
+) -> String {
+    match format {
+        AlignmentFormat::Fasta => rows
+            .iter()
+            .map(|(id, bytes)| export::format_fasta(id, bytes))
+            .collect(),
+        AlignmentFormat::Clustal => format_clustal(rows),
+        AlignmentFormat::Phylip => format_phylip(rows),
+        AlignmentFormat::Stockholm => format_stockholm(rows),
+        AlignmentFormat::Nexus => format_nexus(rows, alignment_type, partitions),
+    }
+}
+
+/// writes the given absolute rows of `alignment`, restricted to its columns, to `path`.
+/// progress advances by one per row.
+pub fn save_alignment(
+    alignment: &libmsa::Alignment,
+    abs_rows: &[usize],
+    format: AlignmentFormat,
+    partitions: &[Partition],
+    path: &Path,
+    progress: &JobProgress,
+    cancel: &CancellationToken,
+) -> Result<()> {
+    let mut rows = Vec::with_capacity(abs_rows.len());
+    for &abs_row in abs_rows {
+        if cancel.is_cancelled() {
+            return Err(format_err!("Save cancelled"));
+        }
+        let Some(sequence) = alignment.project_absolute_row(abs_row) else {
+            continue;
+        };
+        let bytes = (0..sequence.len())
+            .filter_map(|col| sequence.byte_at(col))
+            .collect();
+        rows.push((sequence.id().to_string(), bytes));
+        progress.advance(1);
+    }
+    if rows.is_empty() {
+        return Err(format_err!("no sequences to save"));
+    }
+    let text = format_alignment(&rows, format, alignment.active_type(), partitions);
+    std::fs::write(path, text).with_context(|| format!("failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows() -> Vec<(String, Vec<u8>)> {
+        vec![
+            ("seq one".to_string(), b"ACGT".to_vec()),
+            ("s2".to_string(), b"ACG-".to_vec()),
+        ]
+    }
+
+    #[test]
+    fn formats_follow_extensions_and_names() {
+        assert_eq!(
+            AlignmentFormat::from_path(Path::new("out.PHY")),
+            AlignmentFormat::Phylip
+        );
+        assert_eq!(
+            AlignmentFormat::from_path(Path::new("out.txt")),
+            AlignmentFormat::Fasta
+        );
+        assert_eq!(
+            "stockholm".parse::<AlignmentFormat>().unwrap(),
+            AlignmentFormat::Stockholm
+        );
+        assert!("genbank".parse::<AlignmentFormat>().is_err());
+    }
+
+    #[test]
+    fn text_formats_pad_names_and_replace_spaces() {
+        let dna = libmsa::AlignmentType::Dna;
+
+        assert_eq!(
+            format_alignment(&rows(), AlignmentFormat::Phylip, dna, &[]),
+            "2 4\nseq_one  ACGT\ns2       ACG-\n"
+        );
+        assert_eq!(
+            format_alignment(&rows(), AlignmentFormat::Stockholm, dna, &[]),
+            "# STOCKHOLM 1.0\n\nseq_one ACGT\ns2      ACG-\n//\n"
+        );
+        assert_eq!(
+            format_alignment(&rows(), AlignmentFormat::Clustal, dna, &[]),
+            "CLUSTAL W multiple sequence alignment\n\n\nseq_one      ACGT\ns2           ACG-\n             *** \n"
+        );
+    }
+
+    #[test]
+    fn nexus_output_quotes_labels_and_writes_charsets() {
+        let partitions = [Partition {
+            name: "gene1".to_string(),
+            columns: 0..4,
+        }];
+
+        let text = format_alignment(
+            &rows(),
+            AlignmentFormat::Nexus,
+            libmsa::AlignmentType::Dna,
+            &partitions,
+        );
+
+        assert!(text.contains("DIMENSIONS NTAX=2 NCHAR=4;"));
+        assert!(text.contains("    'seq one'  ACGT\n"));
+        assert!(text.contains("CHARSET gene1 = 1-4;"));
+    }
+}
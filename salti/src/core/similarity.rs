@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+
+use anyhow::{Result, format_err};
+use tokio_util::sync::CancellationToken;
+
+use crate::core::primer::{Strand, complement};
+
+/// most hits listed by `blast-lite`
+pub const BLAST_LITE_TOP_HITS: usize = 25;
+/// word length used to seed nucleotide matches
+const NUCLEOTIDE_WORD_LEN: usize = 8;
+/// word length used to seed protein matches
+const PROTEIN_WORD_LEN: usize = 3;
+
+/// query fragment for `blast-lite`, with its reverse complement when it is a nucleotide query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    forward: Vec<u8>,
+    reverse: Option<Vec<u8>>,
+    word_len: usize,
+}
+
+impl Query {
+    /// parses a query written in residue letters. whitespace and gaps are dropped, and for
+    /// nucleotides `U` is read as `T`.
+    pub fn parse(text: &str, nucleotide: bool) -> Result<Self> {
+        let forward: Vec<u8> = text
+            .bytes()
+            .filter(|&byte| !byte.is_ascii_whitespace() && !matches!(byte, b'-' | b'.'))
+            .map(|byte| normalise(byte, nucleotide))
+            .collect();
+        if forward.is_empty() {
+            return Err(format_err!("query sequence is empty"));
+        }
+        if let Some(invalid) = forward.iter().find(|byte| !byte.is_ascii_alphabetic()) {
+            return Err(format_err!(
+                "'{}' is not a residue code",
+                char::from(*invalid)
+            ));
+        }
+        let reverse =
+            nucleotide.then(|| forward.iter().rev().map(|&base| complement(base)).collect());
+        let word_len = if nucleotide {
+            NUCLEOTIDE_WORD_LEN
+        } else {
+            PROTEIN_WORD_LEN
+        };
+        Ok(Self {
+            word_len: word_len.min(forward.len()),
+            forward,
+            reverse,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.forward.len()
+    }
+
+    pub fn sequence(&self) -> String {
+        String::from_utf8_lossy(&self.forward).into_owned()
+    }
+
+    pub const fn word_len(&self) -> usize {
+        self.word_len
+    }
+
+    fn is_nucleotide(&self) -> bool {
+        self.reverse.is_some()
+    }
+}
+
+/// best local match of a query in one aligned sequence
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimilarityHit {
+    pub strand: Strand,
+    /// query words found along the best diagonal
+    pub shared_words: usize,
+    /// fraction of query residues matched along the best diagonal
+    pub identity: f32,
+    /// first alignment column covered by the match
+    pub first_col: usize,
+    /// last alignment column covered by the match
+    pub last_col: usize,
+}
+
+fn normalise(byte: u8, nucleotide: bool) -> u8 {
+    match byte.to_ascii_uppercase() {
+        b'U' if nucleotide => b'T',
+        upper => upper,
+    }
+}
+
+/// words of `residues` packed into integers, with the position each starts at
+fn words(residues: &[u8], word_len: usize) -> impl Iterator<Item = (usize, u64)> + '_ {
+    residues.windows(word_len).enumerate().map(|(start, word)| {
+        let packed = word
+            .iter()
+            .fold(0u64, |packed, &byte| packed << 8 | u64::from(byte));
+        (start, packed)
+    })
+}
+
+/// the diagonal (sequence position minus query position) sharing the most words with `probe`,
+/// and how many words it shares
+fn best_diagonal(bases: &[u8], probe: &[u8], word_len: usize) -> Option<(isize, usize)> {
+    let mut query_words: HashMap<u64, Vec<usize>> = HashMap::new();
+    for (start, word) in words(probe, word_len) {
+        query_words.entry(word).or_default().push(start);
+    }
+    let mut diagonals: HashMap<isize, usize> = HashMap::new();
+    for (start, word) in words(bases, word_len) {
+        for &query_start in query_words.get(&word).into_iter().flatten() {
+            *diagonals
+                .entry(start as isize - query_start as isize)
+                .or_default() += 1;
+        }
+    }
+    diagonals
+        .into_iter()
+        .max_by_key(|&(diagonal, shared)| (shared, std::cmp::Reverse(diagonal)))
+}
+
+/// scores `aligned` against `query` by seeding word matches on each strand and extending the
+/// diagonal with the most seeds without gaps. gaps are removed first, so matches may span
+/// gapped columns. ties keep the forward strand. `None` when no word is shared.
+pub fn best_hit(aligned: &[u8], query: &Query) -> Option<SimilarityHit> {
+    let nucleotide = query.is_nucleotide();
+    let (columns, bases): (Vec<usize>, Vec<u8>) = aligned
+        .iter()
+        .enumerate()
+        .filter(|(_, byte)| !matches!(byte, b'-' | b'.'))
+        .map(|(col, &byte)| (col, normalise(byte, nucleotide)))
+        .unzip();
+
+    [
+        Some((Strand::Forward, &query.forward)),
+        query
+            .reverse
+            .as_ref()
+            .map(|reverse| (Strand::Reverse, reverse)),
+    ]
+    .into_iter()
+    .flatten()
+    .filter_map(|(strand, probe)| {
+        let (diagonal, shared_words) = best_diagonal(&bases, probe, query.word_len)?;
+        let first = diagonal.max(0) as usize;
+        let last = (diagonal + probe.len() as isize).min(bases.len() as isize) as usize - 1;
+        let matches = (first..=last)
+            .filter(|&position| bases[position] == probe[(position as isize - diagonal) as usize])
+            .count();
+        Some(SimilarityHit {
+            strand,
+            shared_words,
+            identity: matches as f32 / probe.len() as f32,
+            first_col: columns[first],
+            last_col: columns[last],
+        })
+    })
+    .reduce(|best, hit| {
+        let better = hit
+            .identity
+            .total_cmp(&best.identity)
+            .then(hit.shared_words.cmp(&best.shared_words))
+            .is_gt();
+        if better { hit } else { best }
+    })
+}
+
+/// best match in each `(absolute row, id, aligned bytes)` row, in row order
+pub fn search_rows(
+    rows: &[(usize, String, Vec<u8>)],
+    query: &Query,
+    cancel: &CancellationToken,
+) -> Result<Vec<Option<SimilarityHit>>> {
+    rows.iter()
+        .map(|(_, _, bytes)| {
+            if cancel.is_cancelled() {
+                return Err(format_err!("Similarity search cancelled"));
+            }
+            Ok(best_hit(bytes, query))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(text: &str) -> Query {
+        Query::parse(text, true).expect("test query should parse")
+    }
+
+    #[test]
+    fn fragments_are_found_across_gaps() {
+        let hit = best_hit(b"TTTTACGTAC-GTACGGTTTT", &query("acgtacgtacgg")).expect("should match");
+
+        assert_eq!(hit.strand, Strand::Forward);
+        assert!((hit.identity - 1.0).abs() < f32::EPSILON);
+        assert_eq!((hit.first_col, hit.last_col), (4, 16));
+    }
+
+    #[test]
+    fn reverse_complement_matches_score_on_the_reverse_strand() {
+        // reverse complement of AACCGGTTAC is GTAACCGGTT
+        let hit = best_hit(b"CCGTAACCGGTTCC", &query("AACCGGTTAC")).expect("should match");
+
+        assert_eq!(hit.strand, Strand::Reverse);
+        assert_eq!((hit.first_col, hit.last_col), (2, 11));
+    }
+
+    #[test]
+    fn mismatches_lower_identity_and_unrelated_rows_have_no_hit() {
+        let hit = best_hit(b"ACGTACGTACGTTTTT", &query("ACGTACGTACGAAAAA")).expect("should match");
+        assert_eq!(hit.strand, Strand::Forward);
+        assert!((hit.identity - 11.0 / 16.0).abs() < f32::EPSILON);
+
+        assert_eq!(best_hit(b"CCCCCCCCCCCC", &query("ACGTACGTAC")), None);
+    }
+
+    #[test]
+    fn queries_must_be_residue_letters() {
+        assert_eq!(query("ac gu-a").sequence(), "ACGTA");
+        assert_eq!(query("ACG").word_len(), 3);
+        assert!(Query::parse("AC1G", true).is_err());
+        assert!(Query::parse("  ", false).is_err());
+    }
+}
@@ -26,6 +26,93 @@ pub struct StatsJobResult {
     pub chunk_idx: usize,
     pub view: StatsView,
     pub summaries: Result<Vec<libmsa::ColumnSummary>, String>,
+    /// column counts behind the summaries, kept so row and consensus method changes can
+    /// update them in place
+    pub counts: Option<Vec<libmsa::ColumnCounts>>,
+}
+
+/// flags every column of `alignment` that is a gap in all visible rows
+pub fn scan_gap_only(alignment: &libmsa::Alignment) -> Result<Vec<bool>, String> {
+    if alignment.column_count() == 0 {
+        return Ok(Vec::new());
+    }
+    alignment
+        .gap_only_range(0..alignment.column_count())
+        .map_err(|error| error.to_string())
+}
+
+#[derive(Debug)]
+pub struct GapOnlyJobResult {
+    pub generation: u64,
+    pub columns: Result<Vec<bool>, String>,
+}
+
+/// fraction of visible rows in every column of `alignment` that do not carry the consensus
+/// byte, with gaps counting as differences unless the consensus is itself a gap
+pub fn scan_differences(
+    alignment: &libmsa::Alignment,
+    method: libmsa::ConsensusMethod,
+) -> Result<Vec<f32>, String> {
+    if alignment.column_count() == 0 {
+        return Ok(Vec::new());
+    }
+    alignment
+        .column_summaries_range(0..alignment.column_count(), method)
+        .map(|summaries| {
+            summaries
+                .iter()
+                .map(|summary| 1.0 - summary.consensus_support)
+                .collect()
+        })
+        .map_err(|error| error.to_string())
+}
+
+#[derive(Debug)]
+pub struct DifferenceJobResult {
+    pub generation: u64,
+    pub fractions: Result<Vec<f32>, String>,
+}
+
+/// a per-column value scanned across the whole view rather than per chunk, so the minimap and
+/// `next-gap-column` can see past the viewport
+#[derive(Debug)]
+enum ViewScanState<T> {
+    Empty,
+    Pending(u64),
+    Filled(Vec<T>),
+}
+
+impl<T> ViewScanState<T> {
+    fn start(&mut self, generation: u64) -> bool {
+        if !matches!(self, Self::Empty) {
+            return false;
+        }
+        *self = Self::Pending(generation);
+        true
+    }
+
+    fn store(&mut self, generation: u64, values: Result<Vec<T>, String>) -> bool {
+        if !matches!(self, Self::Pending(pending) if *pending == generation) {
+            return false;
+        }
+        match values {
+            Ok(values) => {
+                *self = Self::Filled(values);
+                true
+            }
+            Err(_) => {
+                *self = Self::Empty;
+                false
+            }
+        }
+    }
+
+    fn values(&self) -> Option<&[T]> {
+        match self {
+            Self::Filled(values) => Some(values),
+            Self::Empty | Self::Pending(_) => None,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -34,6 +121,10 @@ pub struct ColumnStatsCache {
     raw: ChunkedCache,
     translated: ChunkedCache,
     translated_frame: Option<libmsa::ReadingFrame>,
+    /// columns that are gaps in every visible row
+    gap_only: ViewScanState<bool>,
+    /// consensus difference density, only scanned while the minimap is open
+    differences: ViewScanState<f32>,
 }
 
 impl Default for ColumnStatsCache {
@@ -43,6 +134,8 @@ impl Default for ColumnStatsCache {
             raw: ChunkedCache::empty(),
             translated: ChunkedCache::empty(),
             translated_frame: None,
+            gap_only: ViewScanState::Empty,
+            differences: ViewScanState::Empty,
         }
     }
 }
@@ -53,6 +146,8 @@ impl ColumnStatsCache {
         self.raw = ChunkedCache::new(nucleotide_cols);
         self.translated = ChunkedCache::empty();
         self.translated_frame = None;
+        self.gap_only = ViewScanState::Empty;
+        self.differences = ViewScanState::Empty;
     }
 
     pub fn raw_summary_at(&self, col: usize) -> Option<&libmsa::ColumnSummary> {
@@ -62,6 +157,15 @@ impl ColumnStatsCache {
             .and_then(|summary| summary.as_ref())
     }
 
+    /// the byte counts behind a raw summary, when the job that filled its chunk kept them
+    pub fn raw_counts_at(&self, col: usize) -> Option<&libmsa::ColumnCounts> {
+        self.raw
+            .counts
+            .get(col / CHUNK_SIZE)?
+            .as_ref()?
+            .get(col % CHUNK_SIZE)
+    }
+
     pub fn translated_summary_at(
         &self,
         frame: libmsa::ReadingFrame,
@@ -133,19 +237,100 @@ impl ColumnStatsCache {
                 &mut self.translated
             }
         };
-        cache.fill_chunk(result.chunk_idx, summaries);
+        cache.fill_chunk(result.chunk_idx, summaries, result.counts);
         true
     }
 
+    /// fills every raw chunk from summaries of the whole view, computed by `precompute-stats`
+    pub fn store_full(&mut self, generation: u64, summaries: Vec<libmsa::ColumnSummary>) -> bool {
+        if generation != self.generation || summaries.len() != self.raw.summaries.len() {
+            return false;
+        }
+        for (slot, summary) in self.raw.summaries.iter_mut().zip(summaries) {
+            *slot = Some(summary);
+        }
+        self.raw.chunks.fill(ChunkState::Filled);
+        self.raw.counts.fill(None);
+        true
+    }
+
+    /// memory held by the raw summaries and their counts, in bytes
+    pub fn raw_size_bytes(&self) -> usize {
+        let counts: usize = self
+            .raw
+            .counts
+            .iter()
+            .flatten()
+            .flatten()
+            .map(|counts| {
+                std::mem::size_of::<libmsa::ColumnCounts>()
+                    + counts.distinct() * std::mem::size_of::<(u8, u32)>()
+            })
+            .sum();
+        self.raw.summaries.len() * std::mem::size_of::<Option<libmsa::ColumnSummary>>() + counts
+    }
+
+    /// starts a new generation so that results of jobs already running are dropped, returning
+    /// their chunks to empty so they are computed again
+    fn next_generation(&mut self) {
+        self.generation += 1;
+        self.raw.clear_pending();
+        self.translated.clear_pending();
+    }
+
+    /// resets the scans over the visible rows after rows are hidden, shown or filtered while
+    /// the rows stats are computed over stay the same
+    pub fn invalidate_view_scans(&mut self) {
+        self.next_generation();
+        self.gap_only = ViewScanState::Empty;
+        self.differences = ViewScanState::Empty;
+    }
+
+    /// applies a change of the rows raw stats are computed over without rescanning them.
+    /// `update` adjusts the counts of a column and returns its new summary; chunks cached
+    /// without counts are emptied to be computed again, along with translated stats and the
+    /// view scans. returns whether any raw chunk was emptied.
+    pub fn update_raw_rows(
+        &mut self,
+        update: impl FnMut(usize, &mut libmsa::ColumnCounts) -> libmsa::ColumnSummary,
+    ) -> bool {
+        self.invalidate_view_scans();
+        self.translated = ChunkedCache::empty();
+        self.translated_frame = None;
+        self.raw.rederive(update)
+    }
+
+    /// recomputes cached summaries from their counts after the consensus method changes.
+    /// `raw` and `translated` summarise a column of either view from its counts; chunks cached
+    /// without counts are emptied to be computed again. returns whether any raw chunk was
+    /// emptied.
+    pub fn rederive(
+        &mut self,
+        raw: impl FnMut(usize, &mut libmsa::ColumnCounts) -> libmsa::ColumnSummary,
+        translated: impl FnMut(usize, &mut libmsa::ColumnCounts) -> libmsa::ColumnSummary,
+    ) -> bool {
+        self.next_generation();
+        self.differences = ViewScanState::Empty;
+        self.translated.rederive(translated);
+        self.raw.rederive(raw)
+    }
+
+    /// reading frame the translated stats are cached for
+    pub fn translated_frame(&self) -> Option<libmsa::ReadingFrame> {
+        self.translated_frame
+    }
+
     pub fn invalidate_all(&mut self, nucleotide_cols: usize) {
         self.generation += 1;
         self.raw.reset(nucleotide_cols);
         self.translated = ChunkedCache::empty();
         self.translated_frame = None;
+        self.gap_only = ViewScanState::Empty;
+        self.differences = ViewScanState::Empty;
     }
 
     pub fn invalidate_translated(&mut self) {
-        self.generation += 1;
+        self.next_generation();
         self.translated = ChunkedCache::empty();
         self.translated_frame = None;
     }
@@ -169,12 +354,69 @@ impl ColumnStatsCache {
     pub fn translated_chunk_range(&self, chunk_idx: usize) -> Range<usize> {
         self.translated.chunk_range(chunk_idx)
     }
+
+    /// marks the gap-only scan as pending, returning false if it is already running or done
+    pub fn start_gap_only_scan(&mut self) -> bool {
+        self.gap_only.start(self.generation)
+    }
+
+    pub fn store_gap_only(&mut self, result: GapOnlyJobResult) -> bool {
+        self.gap_only.store(result.generation, result.columns)
+    }
+
+    /// gap-only flags for every raw view column, once the scan has finished
+    pub fn gap_only_columns(&self) -> Option<&[bool]> {
+        self.gap_only.values()
+    }
+
+    /// marks the difference scan as pending, returning false if it is already running or done
+    pub fn start_difference_scan(&mut self) -> bool {
+        self.differences.start(self.generation)
+    }
+
+    pub fn store_differences(&mut self, result: DifferenceJobResult) -> bool {
+        self.differences.store(result.generation, result.fractions)
+    }
+
+    /// consensus difference fraction for every raw view column, once the scan has finished
+    pub fn difference_fractions(&self) -> Option<&[f32]> {
+        self.differences.values()
+    }
+
+    pub fn is_gap_only(&self, col: usize) -> bool {
+        self.gap_only_columns()
+            .and_then(|columns| columns.get(col))
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// first gap-only column after `col`
+    pub fn next_gap_only_after(&self, col: usize) -> Option<usize> {
+        let columns = self.gap_only_columns()?;
+        columns
+            .iter()
+            .skip(col + 1)
+            .position(|&gap_only| gap_only)
+            .map(|offset| col + 1 + offset)
+    }
+
+    /// filled and total chunk counts for the raw view
+    pub fn raw_coverage(&self) -> (usize, usize) {
+        self.raw.coverage()
+    }
+
+    /// filled and total chunk counts for the active translation frame
+    pub fn translated_coverage(&self) -> (usize, usize) {
+        self.translated.coverage()
+    }
 }
 
 #[derive(Debug)]
 struct ChunkedCache {
     chunks: Vec<ChunkState>,
     summaries: Vec<Option<libmsa::ColumnSummary>>,
+    /// counts of each filled chunk's columns, when the job that filled it kept them
+    counts: Vec<Option<Vec<libmsa::ColumnCounts>>>,
 }
 
 impl ChunkedCache {
@@ -183,6 +425,7 @@ impl ChunkedCache {
         Self {
             chunks: vec![ChunkState::Empty; n_chunks],
             summaries: vec![None; total_columns],
+            counts: vec![None; n_chunks],
         }
     }
 
@@ -190,13 +433,51 @@ impl ChunkedCache {
         Self {
             chunks: Vec::new(),
             summaries: Vec::new(),
+            counts: Vec::new(),
         }
     }
 
     fn reset(&mut self, total_columns: usize) {
-        let n_chunks = total_columns.div_ceil(CHUNK_SIZE);
-        self.chunks = vec![ChunkState::Empty; n_chunks];
-        self.summaries = vec![None; total_columns];
+        *self = Self::new(total_columns);
+    }
+
+    fn clear_pending(&mut self) {
+        for state in &mut self.chunks {
+            if *state == ChunkState::Pending {
+                *state = ChunkState::Empty;
+            }
+        }
+    }
+
+    fn clear_chunk(&mut self, chunk_idx: usize) {
+        let range = self.chunk_range(chunk_idx);
+        self.summaries[range].fill(None);
+        self.counts[chunk_idx] = None;
+        self.chunks[chunk_idx] = ChunkState::Empty;
+    }
+
+    /// replaces the summaries of every filled chunk with `summarise` applied to its counts,
+    /// emptying chunks that have none. returns whether any chunk was emptied.
+    fn rederive(
+        &mut self,
+        mut summarise: impl FnMut(usize, &mut libmsa::ColumnCounts) -> libmsa::ColumnSummary,
+    ) -> bool {
+        let mut emptied = false;
+        for chunk_idx in 0..self.chunks.len() {
+            if self.chunks[chunk_idx] != ChunkState::Filled {
+                continue;
+            }
+            let range = self.chunk_range(chunk_idx);
+            let Some(counts) = self.counts[chunk_idx].as_mut() else {
+                self.clear_chunk(chunk_idx);
+                emptied = true;
+                continue;
+            };
+            for (col, counts) in range.zip(counts.iter_mut()) {
+                self.summaries[col] = Some(summarise(col, counts));
+            }
+        }
+        emptied
     }
 
     fn chunks_for_range(&self, range: &Range<usize>) -> Range<usize> {
@@ -208,13 +489,27 @@ impl ChunkedCache {
         start_chunk..end_chunk
     }
 
+    fn coverage(&self) -> (usize, usize) {
+        let filled = self
+            .chunks
+            .iter()
+            .filter(|&&state| state == ChunkState::Filled)
+            .count();
+        (filled, self.chunks.len())
+    }
+
     fn chunk_range(&self, chunk_idx: usize) -> Range<usize> {
         let start = chunk_idx * CHUNK_SIZE;
         let end = (start + CHUNK_SIZE).min(self.summaries.len());
         start..end
     }
 
-    fn fill_chunk(&mut self, chunk_idx: usize, summaries: Vec<libmsa::ColumnSummary>) {
+    fn fill_chunk(
+        &mut self,
+        chunk_idx: usize,
+        summaries: Vec<libmsa::ColumnSummary>,
+        counts: Option<Vec<libmsa::ColumnCounts>>,
+    ) {
         let range = self.chunk_range(chunk_idx);
         for (offset, summary) in summaries.into_iter().enumerate() {
             let col = range.start + offset;
@@ -224,21 +519,27 @@ impl ChunkedCache {
         }
         if let Some(state) = self.chunks.get_mut(chunk_idx) {
             *state = ChunkState::Filled;
+            self.counts[chunk_idx] = counts;
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{CHUNK_SIZE, ChunkState, ChunkedCache, ColumnStatsCache, StatsJobResult};
+    use super::{
+        CHUNK_SIZE, ChunkState, ChunkedCache, ColumnStatsCache, DifferenceJobResult,
+        GapOnlyJobResult, StatsJobResult, scan_differences,
+    };
     use crate::core::model::StatsView;
 
     fn summary(consensus: u8) -> libmsa::ColumnSummary {
         libmsa::ColumnSummary {
             position: 0,
             consensus: Some(consensus),
+            consensus_support: 1.0,
             conservation: Some(1.0),
             gap_fraction: 0.0,
+            gap_only: false,
         }
     }
 
@@ -261,7 +562,7 @@ mod tests {
     fn fill_chunk_writes_summaries_into_the_chunk_range() {
         let mut cache = ChunkedCache::new(CHUNK_SIZE + 3);
 
-        cache.fill_chunk(1, vec![summary(b'A'), summary(b'C'), summary(b'G')]);
+        cache.fill_chunk(1, vec![summary(b'A'), summary(b'C'), summary(b'G')], None);
 
         assert_eq!(
             cache.summaries[CHUNK_SIZE]
@@ -289,7 +590,9 @@ mod tests {
         let mut cache = ColumnStatsCache::default();
         cache.init(CHUNK_SIZE * 3);
         cache.mark_raw_pending(1);
-        cache.raw.fill_chunk(2, vec![summary(b'A'); CHUNK_SIZE]);
+        cache
+            .raw
+            .fill_chunk(2, vec![summary(b'A'); CHUNK_SIZE], None);
 
         assert_eq!(cache.raw_chunks_to_spawn(&(0..CHUNK_SIZE * 3)), vec![0]);
     }
@@ -304,6 +607,7 @@ mod tests {
             chunk_idx: 0,
             view: StatsView::Raw,
             summaries: Ok(vec![summary(b'A'); 10]),
+            counts: None,
         });
 
         assert!(!stored);
@@ -321,6 +625,7 @@ mod tests {
             chunk_idx: 0,
             view: StatsView::Translated(libmsa::ReadingFrame::Frame2),
             summaries: Ok(vec![summary(b'M'); 2]),
+            counts: None,
         });
 
         assert!(!stored);
@@ -342,6 +647,7 @@ mod tests {
             chunk_idx: 0,
             view: StatsView::Raw,
             summaries: Ok(vec![summary(b'A'); 10]),
+            counts: None,
         });
 
         assert!(stored);
@@ -352,6 +658,24 @@ mod tests {
         assert_eq!(cache.raw.chunks[0], ChunkState::Filled);
     }
 
+    #[test]
+    fn coverage_counts_filled_chunks() {
+        let mut cache = ColumnStatsCache::default();
+        cache.init(CHUNK_SIZE * 2);
+        cache.mark_raw_pending(1);
+
+        cache.store(StatsJobResult {
+            generation: cache.generation,
+            chunk_idx: 1,
+            view: StatsView::Raw,
+            summaries: Ok(vec![summary(b'A'); CHUNK_SIZE]),
+            counts: None,
+        });
+
+        assert_eq!(cache.raw_coverage(), (1, 2));
+        assert_eq!(cache.translated_coverage(), (0, 0));
+    }
+
     #[test]
     fn invalidate_all_resets_both_caches_and_bumps_generation() {
         let mut cache = ColumnStatsCache::default();
@@ -367,6 +691,197 @@ mod tests {
         assert_eq!(cache.translated_frame, None);
     }
 
+    #[test]
+    fn gap_only_scan_survives_translated_invalidation() {
+        let mut cache = ColumnStatsCache::default();
+        cache.init(4);
+        let scan_generation = cache.generation;
+        assert!(cache.start_gap_only_scan());
+        assert!(!cache.start_gap_only_scan());
+
+        cache.invalidate_translated();
+        let stored = cache.store_gap_only(GapOnlyJobResult {
+            generation: scan_generation,
+            columns: Ok(vec![false, true, false, true]),
+        });
+
+        assert!(stored);
+        assert!(cache.is_gap_only(1));
+        assert_eq!(cache.next_gap_only_after(1), Some(3));
+        assert_eq!(cache.next_gap_only_after(3), None);
+    }
+
+    #[test]
+    fn invalidate_all_discards_pending_gap_only_scan() {
+        let mut cache = ColumnStatsCache::default();
+        cache.init(2);
+        let scan_generation = cache.generation;
+        cache.start_gap_only_scan();
+
+        cache.invalidate_all(2);
+        let stored = cache.store_gap_only(GapOnlyJobResult {
+            generation: scan_generation,
+            columns: Ok(vec![true, true]),
+        });
+
+        assert!(!stored);
+        assert!(cache.gap_only_columns().is_none());
+    }
+
+    #[test]
+    fn difference_scan_counts_rows_away_from_the_consensus() {
+        let alignment = libmsa::Alignment::new(
+            [b"AAC", b"AA-", b"ATC", b"AGC"]
+                .into_iter()
+                .enumerate()
+                .map(|(index, sequence)| libmsa::RawSequence {
+                    id: format!("row{index}"),
+                    sequence: sequence.to_vec(),
+                    quality: None,
+                }),
+        )
+        .unwrap();
+        let mut cache = ColumnStatsCache::default();
+        cache.init(3);
+        assert!(cache.start_difference_scan());
+
+        let stored = cache.store_differences(DifferenceJobResult {
+            generation: cache.generation,
+            fractions: scan_differences(&alignment, libmsa::ConsensusMethod::MajorityNonGap),
+        });
+
+        assert!(stored);
+        assert_eq!(
+            cache.difference_fractions(),
+            Some([0.0, 0.5, 0.25].as_slice())
+        );
+        cache.invalidate_translated();
+        assert!(cache.difference_fractions().is_some());
+        cache.invalidate_all(3);
+        assert!(cache.difference_fractions().is_none());
+    }
+
+    #[test]
+    fn store_full_fills_every_raw_chunk_for_the_current_generation() {
+        let mut cache = ColumnStatsCache::default();
+        cache.init(CHUNK_SIZE + 10);
+        let stale = cache.generation;
+        cache.invalidate_all(CHUNK_SIZE + 10);
+
+        assert!(!cache.store_full(stale, vec![summary(b'A'); CHUNK_SIZE + 10]));
+        assert!(!cache.store_full(cache.generation, vec![summary(b'A'); 3]));
+        assert!(cache.store_full(cache.generation, vec![summary(b'C'); CHUNK_SIZE + 10]));
+
+        assert_eq!(cache.raw_coverage(), (2, 2));
+        assert!(cache.raw_chunks_to_spawn(&(0..CHUNK_SIZE + 10)).is_empty());
+        assert_eq!(
+            cache
+                .raw_summary_at(CHUNK_SIZE + 9)
+                .and_then(|it| it.consensus),
+            Some(b'C')
+        );
+    }
+
+    #[test]
+    fn update_raw_rows_adjusts_counted_chunks_and_empties_the_rest() {
+        let alignment = libmsa::Alignment::new(vec![
+            libmsa::RawSequence {
+                id: "a".to_string(),
+                sequence: b"AC".to_vec(),
+                quality: None,
+            },
+            libmsa::RawSequence {
+                id: "b".to_string(),
+                sequence: b"GC".to_vec(),
+                quality: None,
+            },
+        ])
+        .expect("alignment should be valid");
+        let mut cache = ColumnStatsCache::default();
+        cache.init(CHUNK_SIZE + 2);
+        cache.mark_raw_pending(0);
+        cache.raw.fill_chunk(
+            1,
+            vec![summary(b'A'); 2],
+            alignment.column_counts_range(0..2).ok(),
+        );
+        cache
+            .raw
+            .fill_chunk(0, vec![summary(b'A'); CHUNK_SIZE], None);
+        let generation = cache.generation;
+
+        let emptied = cache.update_raw_rows(|col, counts| {
+            counts.remove(b'A');
+            alignment.summary_from_counts(col, counts, libmsa::ConsensusMethod::MajorityNonGap)
+        });
+
+        assert!(emptied);
+        assert!(cache.generation > generation);
+        assert_eq!(cache.raw.chunks[0], ChunkState::Empty);
+        assert!(cache.raw_summary_at(0).is_none());
+        assert_eq!(
+            cache.raw_summary_at(CHUNK_SIZE).and_then(|it| it.consensus),
+            Some(b'G')
+        );
+    }
+
+    #[test]
+    fn rederive_resummarises_counted_chunks_of_both_views() {
+        let alignment = libmsa::Alignment::new(vec![
+            libmsa::RawSequence {
+                id: "a".to_string(),
+                sequence: b"A-".to_vec(),
+                quality: None,
+            },
+            libmsa::RawSequence {
+                id: "b".to_string(),
+                sequence: b"--".to_vec(),
+                quality: None,
+            },
+            libmsa::RawSequence {
+                id: "c".to_string(),
+                sequence: b"--".to_vec(),
+                quality: None,
+            },
+        ])
+        .expect("alignment should be valid");
+        let method = libmsa::ConsensusMethod::Majority;
+        let mut cache = ColumnStatsCache::default();
+        cache.init(2);
+        let _ = cache.translated_chunks_to_spawn(&(0..1), libmsa::ReadingFrame::Frame1, 1);
+        cache.translated.fill_chunk(0, vec![summary(b'A')], None);
+        cache.store(StatsJobResult {
+            generation: cache.generation,
+            chunk_idx: 0,
+            view: StatsView::Raw,
+            summaries: alignment
+                .column_summaries_range(0..2, method)
+                .map_err(|_| String::new()),
+            counts: alignment.column_counts_range(0..2).ok(),
+        });
+        assert_eq!(
+            cache.raw_summary_at(0).and_then(|it| it.consensus),
+            Some(b'-')
+        );
+        let generation = cache.generation;
+
+        let emptied = cache.rederive(
+            |col, counts| {
+                alignment.summary_from_counts(col, counts, libmsa::ConsensusMethod::MajorityNonGap)
+            },
+            |_, _| unreachable!("translated chunk has no counts"),
+        );
+
+        assert!(!emptied);
+        assert!(cache.generation > generation);
+        assert_eq!(
+            cache.raw_summary_at(0).and_then(|it| it.consensus),
+            Some(b'A')
+        );
+        assert_eq!(cache.translated_frame(), Some(libmsa::ReadingFrame::Frame1));
+        assert_eq!(cache.translated_coverage(), (0, 1));
+    }
+
     #[test]
     fn invalidate_translated_preserves_raw_cache_and_bumps_generation() {
         let mut cache = ColumnStatsCache::default();
@@ -376,6 +891,7 @@ mod tests {
             chunk_idx: 0,
             view: StatsView::Raw,
             summaries: Ok(vec![summary(b'A'); 10]),
+            counts: None,
         });
         let _ = cache.translated_chunks_to_spawn(&(0..2), libmsa::ReadingFrame::Frame1, 2);
         let previous_generation = cache.generation;
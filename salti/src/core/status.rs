@@ -0,0 +1,212 @@
+use std::ops::{Range, RangeInclusive};
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+/// `ENXIO`, returned when a FIFO is opened for writing without a reader. the same on every
+/// unix salti builds for.
+#[cfg(unix)]
+const NO_READER_ERROR: i32 = 6;
+
+/// where the view is, written as one line for status bars such as tmux or starship. columns
+/// and selection are absolute 0-based ids, rows are view-relative.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ViewStatus {
+    pub file: Option<String>,
+    /// crosshair column, or the first visible column
+    pub position: Option<usize>,
+    pub columns: Option<RangeInclusive<usize>>,
+    pub total_columns: usize,
+    pub rows: Range<usize>,
+    pub total_rows: usize,
+    /// selected rows and columns
+    pub selection: Option<(RangeInclusive<usize>, RangeInclusive<usize>)>,
+}
+
+impl ViewStatus {
+    /// space separated `key=value` fields with 1-based positions, e.g.
+    /// `position=120 columns=101-220/30000 rows=1-40/200 selection=none file=aln.fasta`.
+    /// `file` comes last and runs to the end of the line, so names may hold spaces.
+    pub fn to_line(&self) -> String {
+        let inclusive =
+            |range: &RangeInclusive<usize>| format!("{}-{}", range.start() + 1, range.end() + 1);
+        let position = self
+            .position
+            .map_or_else(|| "-".to_string(), |column| (column + 1).to_string());
+        let columns = self
+            .columns
+            .as_ref()
+            .map_or_else(|| "-".to_string(), inclusive);
+        let rows = if self.rows.is_empty() {
+            "-".to_string()
+        } else {
+            format!("{}-{}", self.rows.start + 1, self.rows.end)
+        };
+        let selection = self.selection.as_ref().map_or_else(
+            || "none".to_string(),
+            |(rows, columns)| format!("{}:{}", inclusive(rows), inclusive(columns)),
+        );
+        format!(
+            "position={position} columns={columns}/{} rows={rows}/{} selection={selection} file={}",
+            self.total_columns,
+            self.total_rows,
+            self.file.as_deref().unwrap_or("-")
+        )
+    }
+}
+
+/// writes the view status to the path given by `--status-fifo`. a FIFO gets each new line while
+/// something is reading it, and any other path is replaced with the latest line.
+#[derive(Debug)]
+pub struct StatusFifo {
+    path: PathBuf,
+    /// line last written, so unchanged status is not written again
+    last: Option<String>,
+}
+
+impl StatusFifo {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, last: None }
+    }
+
+    /// writes `line` when it changed since the last write. lines a FIFO had no reader for are
+    /// tried again on the next call.
+    pub fn publish(&mut self, line: String) -> Result<()> {
+        if self.last.as_ref() == Some(&line) {
+            return Ok(());
+        }
+        let written = if self.is_fifo() {
+            self.write_fifo(&line)?
+        } else {
+            self.write_file(&line)?;
+            true
+        };
+        if written {
+            self.last = Some(line);
+        }
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    fn is_fifo(&self) -> bool {
+        use std::os::unix::fs::FileTypeExt;
+
+        std::fs::metadata(&self.path).is_ok_and(|metadata| metadata.file_type().is_fifo())
+    }
+
+    #[cfg(not(unix))]
+    fn is_fifo(&self) -> bool {
+        false
+    }
+
+    /// writes without blocking, returning whether a reader took the line
+    #[cfg(unix)]
+    fn write_fifo(&self, line: &str) -> Result<bool> {
+        use std::io::{ErrorKind, Write};
+
+        let sender = match tokio::net::unix::pipe::OpenOptions::new().open_sender(&self.path) {
+            Ok(sender) => sender,
+            Err(error) if error.raw_os_error() == Some(NO_READER_ERROR) => return Ok(false),
+            Err(error) => {
+                return Err(error)
+                    .with_context(|| format!("failed to open {}", self.path.display()));
+            }
+        };
+        // the pipe stays non-blocking, so a full pipe drops the line instead of stalling
+        let mut pipe = std::fs::File::from(
+            sender
+                .into_nonblocking_fd()
+                .with_context(|| format!("failed to open {}", self.path.display()))?,
+        );
+        match pipe.write_all(format!("{line}\n").as_bytes()) {
+            Ok(()) => Ok(true),
+            Err(error) if error.kind() == ErrorKind::WouldBlock => Ok(false),
+            Err(error) => {
+                Err(error).with_context(|| format!("failed to write {}", self.path.display()))
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn write_fifo(&self, _: &str) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// replaces the file in one step so readers never see half a line
+    fn write_file(&self, line: &str) -> Result<()> {
+        let mut staging = self.path.clone().into_os_string();
+        staging.push(format!(".{}.tmp", std::process::id()));
+        std::fs::write(&staging, format!("{line}\n"))
+            .with_context(|| format!("failed to write {}", self.path.display()))?;
+        std::fs::rename(&staging, &self.path)
+            .with_context(|| format!("failed to replace {}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_lines_use_one_based_positions_and_end_with_the_file() {
+        let mut status = ViewStatus {
+            file: Some("run 1.fasta".to_string()),
+            position: Some(119),
+            columns: Some(100..=219),
+            total_columns: 30_000,
+            rows: 0..40,
+            total_rows: 200,
+            selection: Some((4..=6, 99..=119)),
+        };
+        assert_eq!(
+            status.to_line(),
+            "position=120 columns=101-220/30000 rows=1-40/200 selection=5-7:100-120 file=run 1.fasta"
+        );
+
+        status.selection = None;
+        status.file = None;
+        assert!(status.to_line().ends_with("selection=none file=-"));
+    }
+
+    #[test]
+    fn plain_files_hold_the_latest_line() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("status");
+        let mut fifo = StatusFifo::new(path.clone());
+
+        fifo.publish("position=1".to_string())
+            .expect("status should be written");
+        fifo.publish("position=2".to_string())
+            .expect("status should be written");
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "position=2\n");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "current_thread")]
+    async fn fifos_get_lines_only_while_read() {
+        use tokio::io::AsyncReadExt;
+
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("status");
+        let created = std::process::Command::new("mkfifo")
+            .arg(&path)
+            .status()
+            .expect("mkfifo should run");
+        assert!(created.success());
+        let mut fifo = StatusFifo::new(path.clone());
+
+        fifo.publish("position=1".to_string())
+            .expect("a missing reader is not an error");
+        assert_eq!(fifo.last, None);
+
+        let mut reader = tokio::net::unix::pipe::OpenOptions::new()
+            .open_receiver(&path)
+            .expect("fifo should open for reading");
+        fifo.publish("position=2".to_string())
+            .expect("status should be written");
+        let mut text = String::new();
+        reader.read_to_string(&mut text).await.unwrap();
+        assert_eq!(text, "position=2\n");
+    }
+}
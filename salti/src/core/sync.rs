@@ -0,0 +1,123 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+/// first visible column and row of the view, as absolute 0-based indices
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncPosition {
+    pub column: usize,
+    pub row: usize,
+}
+
+impl SyncPosition {
+    /// `column=<n>` and `row=<n>` lines with 1-based positions, so scripts can write them too
+    fn to_text(self) -> String {
+        format!("column={}\nrow={}\n", self.column + 1, self.row + 1)
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        let mut column = None;
+        let mut row = None;
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim().parse::<usize>().ok()?.checked_sub(1)?;
+            match key.trim() {
+                "column" => column = Some(value),
+                "row" => row = Some(value),
+                _ => {}
+            }
+        }
+        Some(Self {
+            column: column?,
+            row: row.unwrap_or(0),
+        })
+    }
+}
+
+/// shares the viewport position with other salti instances through a small file given by
+/// `--sync-file`. each instance writes where it is and follows positions written by the others.
+#[derive(Debug)]
+pub struct ViewportSync {
+    path: PathBuf,
+    /// position last written or read, so an instance does not follow its own writes
+    last: Option<SyncPosition>,
+}
+
+impl ViewportSync {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, last: None }
+    }
+
+    /// writes `position` when it moved since the last write or read. the file is replaced in
+    /// one step so readers never see half a position.
+    pub fn publish(&mut self, position: SyncPosition) -> Result<()> {
+        if self.last == Some(position) {
+            return Ok(());
+        }
+        self.last = Some(position);
+        let mut staging = self.path.clone().into_os_string();
+        staging.push(format!(".{}.tmp", std::process::id()));
+        std::fs::write(&staging, position.to_text())
+            .with_context(|| format!("failed to write {}", self.path.display()))?;
+        std::fs::rename(&staging, &self.path)
+            .with_context(|| format!("failed to replace {}", self.path.display()))
+    }
+
+    /// a position written by another instance since the last write or read
+    pub fn poll(&mut self) -> Result<Option<SyncPosition>> {
+        let text = match std::fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(error) => {
+                return Err(error)
+                    .with_context(|| format!("failed to read {}", self.path.display()));
+            }
+        };
+        let Some(position) = SyncPosition::parse(&text) else {
+            return Ok(None);
+        };
+        if self.last == Some(position) {
+            return Ok(None);
+        }
+        self.last = Some(position);
+        Ok(Some(position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instances_follow_each_others_positions_but_not_their_own() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("position");
+        let mut nucleotides = ViewportSync::new(path.clone());
+        let mut proteins = ViewportSync::new(path.clone());
+        let position = SyncPosition {
+            column: 1_200,
+            row: 4,
+        };
+
+        assert_eq!(proteins.poll().expect("missing file is fine"), None);
+        nucleotides
+            .publish(position)
+            .expect("position should be written");
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "column=1201\nrow=5\n"
+        );
+        assert_eq!(nucleotides.poll().unwrap(), None);
+        assert_eq!(proteins.poll().unwrap(), Some(position));
+        assert_eq!(proteins.poll().unwrap(), None);
+        assert_eq!(
+            SyncPosition::parse("column=10\n"),
+            Some(SyncPosition { column: 9, row: 0 })
+        );
+        assert_eq!(SyncPosition::parse("row=3\n"), None);
+    }
+}
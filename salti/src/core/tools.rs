@@ -0,0 +1,224 @@
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::{Context, Result, format_err};
+use tokio_util::sync::CancellationToken;
+
+use crate::core::jobs::run_with_timeout;
+
+/// longest a configured tool may run before it is stopped
+pub const TOOL_TIMEOUT: Duration = Duration::from_secs(300);
+
+const SELECTION_FASTA: &str = "{selection_fasta}";
+
+/// values substituted into the command of a `[tools]` entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolContext {
+    /// path of the loaded alignment, if it came from a file
+    pub file: Option<String>,
+    /// first and last selected columns, 1-based and inclusive
+    pub start: usize,
+    pub end: usize,
+    /// selected sequences over the selected columns, written to a temporary file for the tool
+    pub selection_fasta: String,
+}
+
+/// quotes `value` as one word for the shell that runs tools
+fn quote(value: &str) -> String {
+    if cfg!(windows) {
+        cmd_quote(value)
+    } else {
+        sh_quote(value)
+    }
+}
+
+/// quotes `value` as one `sh` word
+fn sh_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// characters `cmd.exe` treats specially, even inside double quotes once the quotes themselves
+/// are escaped
+const CMD_METACHARACTERS: &[char] = &['^', '&', '|', '<', '>', '(', ')', '%', '!', '"'];
+
+/// quotes `value` as one argument for a program started by `cmd /C`: quotes are doubled and
+/// backslashes before them or the closing quote are doubled for the program's argument parser,
+/// then every `cmd.exe` metacharacter, the quotes included, is escaped with a caret
+fn cmd_quote(value: &str) -> String {
+    let mut argument = String::from("\"");
+    let mut backslashes = 0;
+    for character in value.chars() {
+        match character {
+            '\\' => backslashes += 1,
+            '"' => {
+                argument.push_str(&"\\".repeat(backslashes * 2));
+                argument.push_str("\"\"");
+                backslashes = 0;
+            }
+            _ => {
+                argument.push_str(&"\\".repeat(backslashes));
+                argument.push(character);
+                backslashes = 0;
+            }
+        }
+    }
+    argument.push_str(&"\\".repeat(backslashes * 2));
+    argument.push('"');
+
+    let mut escaped = String::with_capacity(argument.len() * 2);
+    for character in argument.chars() {
+        if CMD_METACHARACTERS.contains(&character) {
+            escaped.push('^');
+        }
+        escaped.push(character);
+    }
+    escaped
+}
+
+/// fills the `{file}`, `{start}`, `{end}` and `{selection_fasta}` placeholders of `template`.
+/// paths are quoted for the shell; other braces are left as written.
+fn expand_template(template: &str, context: &ToolContext, selection_path: &Path) -> Result<String> {
+    let mut expanded = String::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        expanded.push_str(&rest[..open]);
+        let placeholder = rest[open..]
+            .find('}')
+            .map(|close| &rest[open..=open + close]);
+        let value = match placeholder {
+            Some("{file}") => Some(quote(context.file.as_deref().ok_or_else(|| {
+                format_err!("{{file}} needs an alignment loaded from a file")
+            })?)),
+            Some("{start}") => Some(context.start.to_string()),
+            Some("{end}") => Some(context.end.to_string()),
+            Some(SELECTION_FASTA) => Some(quote(&selection_path.to_string_lossy())),
+            _ => None,
+        };
+        match (placeholder, value) {
+            (Some(placeholder), Some(value)) => {
+                expanded.push_str(&value);
+                rest = &rest[open + placeholder.len()..];
+            }
+            _ => {
+                expanded.push('{');
+                rest = &rest[open + 1..];
+            }
+        }
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+#[cfg(windows)]
+fn shell_command(line: &str) -> Command {
+    use std::os::windows::process::CommandExt;
+
+    // the line is already escaped for cmd.exe, so it is passed on as written
+    let mut command = Command::new("cmd");
+    command.arg("/C").raw_arg(line);
+    command
+}
+
+#[cfg(not(windows))]
+fn shell_command(line: &str) -> Command {
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(line);
+    command
+}
+
+/// runs the `template` of tool `name` through the shell and returns what it printed. the
+/// selection is written to a temporary FASTA file when the template asks for it.
+pub fn run_tool(
+    name: &str,
+    template: &str,
+    context: &ToolContext,
+    cancel: &CancellationToken,
+) -> Result<String> {
+    // a fresh file per run, created without following links and removed once `selection` drops
+    let mut selection = tempfile::Builder::new()
+        .prefix("salti-tool-")
+        .suffix(".fa")
+        .tempfile()
+        .context("could not create the selection file")?;
+    let line = expand_template(template, context, selection.path())
+        .with_context(|| format!("tool {name}"))?;
+    if template.contains(SELECTION_FASTA) {
+        selection
+            .write_all(context.selection_fasta.as_bytes())
+            .and_then(|()| selection.flush())
+            .map_err(|error| {
+                format_err!("could not write {}: {error}", selection.path().display())
+            })?;
+    }
+    let output = run_with_timeout(shell_command(&line), TOOL_TIMEOUT, cancel)
+        .with_context(|| format!("tool {name}"))?;
+    Ok(String::from_utf8_lossy(&output).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> ToolContext {
+        ToolContext {
+            file: Some("it's.fa".to_string()),
+            start: 11,
+            end: 20,
+            selection_fasta: ">row1\nACGT\n".to_string(),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn placeholders_are_filled_and_quoted() {
+        let line = expand_template(
+            "script.sh {file} {start}-{end} {selection_fasta} {other} {",
+            &context(),
+            Path::new("/tmp/sel.fa"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            line,
+            r"script.sh 'it'\''s.fa' 11-20 '/tmp/sel.fa' {other} {"
+        );
+    }
+
+    #[test]
+    fn cmd_quoting_escapes_quotes_and_metacharacters() {
+        assert_eq!(cmd_quote(r"C:\data\aln.fa"), r#"^"C:\data\aln.fa^""#);
+        assert_eq!(cmd_quote(r"C:\data\"), r#"^"C:\data\\^""#);
+        assert_eq!(
+            cmd_quote(r#"a "b" & c|d %PATH% !x! (1)<2>^"#),
+            r#"^"a ^"^"b^"^" ^& c^|d ^%PATH^% ^!x^! ^(1^)^<2^>^^^""#
+        );
+        assert_eq!(cmd_quote(r#"a\"b"#), r#"^"a\\^"^"b^""#);
+    }
+
+    #[test]
+    fn file_placeholder_needs_a_file() {
+        let context = ToolContext {
+            file: None,
+            ..context()
+        };
+
+        assert!(expand_template("wc {file}", &context, Path::new("sel.fa")).is_err());
+        assert!(expand_template("wc {start}", &context, Path::new("sel.fa")).is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn tools_read_the_selection_file() {
+        let output = run_tool(
+            "count",
+            "grep -c '>' {selection_fasta}; echo {start}",
+            &context(),
+            &CancellationToken::new(),
+        )
+        .unwrap();
+
+        assert_eq!(output, "1\n11\n");
+    }
+}
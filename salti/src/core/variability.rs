@@ -0,0 +1,94 @@
+/// ranked entries shown by `list-variable-columns` when no count is given
+pub const DEFAULT_VARIABLE_COLUMNS: usize = 50;
+
+/// a column ranked by `list-variable-columns`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VariableColumn {
+    /// column in the view the summaries were computed over
+    pub column: usize,
+    /// 1-based position in the unfiltered alignment
+    pub position: usize,
+    /// Shannon entropy of the column's residues relative to the alphabet's maximum, 0 to 1
+    pub entropy: f32,
+    pub gap_fraction: f32,
+    pub consensus: Option<u8>,
+}
+
+impl VariableColumn {
+    pub fn describe(&self) -> String {
+        format!(
+            "col {:>7}  entropy {:.3}  gaps {:5.1}%  consensus {}",
+            self.position,
+            self.entropy,
+            self.gap_fraction * 100.0,
+            self.consensus.map_or('-', char::from)
+        )
+    }
+}
+
+/// the `top_n` columns with the highest residue entropy, most variable first. conservation is
+/// `(1 - entropy) * (1 - gap fraction)`, so the entropy is recovered by undoing the gap
+/// weighting; gap-only columns are left out.
+pub fn most_variable_columns(
+    view: &libmsa::Alignment,
+    summaries: &[libmsa::ColumnSummary],
+    top_n: usize,
+) -> Vec<VariableColumn> {
+    let mut columns: Vec<VariableColumn> = summaries
+        .iter()
+        .filter(|summary| !summary.gap_only && summary.gap_fraction < 1.0)
+        .filter_map(|summary| {
+            let conservation = summary.conservation?;
+            let position = view.absolute_column_id(summary.position)?;
+            Some(VariableColumn {
+                column: summary.position,
+                position: position + 1,
+                entropy: (1.0 - conservation / (1.0 - summary.gap_fraction)).clamp(0.0, 1.0),
+                gap_fraction: summary.gap_fraction,
+                consensus: summary.consensus,
+            })
+        })
+        .filter(|column| column.entropy > 0.0)
+        .collect();
+    columns.sort_by(|a, b| {
+        b.entropy
+            .total_cmp(&a.entropy)
+            .then(a.position.cmp(&b.position))
+    });
+    columns.truncate(top_n);
+    columns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw(id: &str, sequence: &[u8]) -> libmsa::RawSequence {
+        libmsa::RawSequence {
+            id: id.to_string(),
+            sequence: sequence.to_vec(),
+            quality: None,
+        }
+    }
+
+    #[test]
+    fn columns_are_ranked_by_entropy_and_skip_conserved_ones() {
+        let alignment = libmsa::Alignment::new(vec![
+            raw("a", b"AACA-"),
+            raw("b", b"ACGA-"),
+            raw("c", b"AGTA-"),
+            raw("d", b"ATAC-"),
+        ])
+        .expect("alignment should be valid");
+        let summaries = alignment
+            .column_summaries_range(0..5, libmsa::ConsensusMethod::default())
+            .expect("summaries should be computed");
+
+        let columns = most_variable_columns(&alignment, &summaries, 2);
+
+        let positions: Vec<usize> = columns.iter().map(|column| column.position).collect();
+        assert_eq!(positions, vec![2, 3]);
+        assert!((columns[0].entropy - 1.0).abs() < 1e-6);
+        assert!(columns[0].entropy > most_variable_columns(&alignment, &summaries, 3)[2].entropy);
+    }
+}
@@ -16,6 +16,10 @@ struct ViewportDims {
     rows: usize,
     cols: usize,
     name_width: usize,
+    /// alignment rows drawn on each terminal line; more than one when rows are packed together
+    rows_per_line: usize,
+    /// alignment columns drawn in each terminal cell
+    cols_per_cell: usize,
 }
 
 // maximum bounds of the data.
@@ -61,6 +65,47 @@ impl Viewport {
         self.clamp_offsets();
     }
 
+    /// how many alignment rows and columns each terminal cell of the sequence rows draws
+    pub fn set_cell_size(&mut self, rows_per_line: usize, cols_per_cell: usize) {
+        self.dims.rows_per_line = rows_per_line;
+        self.dims.cols_per_cell = cols_per_cell;
+    }
+
+    pub fn rows_per_line(&self) -> usize {
+        self.dims.rows_per_line.max(1)
+    }
+
+    pub fn cols_per_cell(&self) -> usize {
+        self.dims.cols_per_cell.max(1)
+    }
+
+    /// terminal cells, counted from the left of the sequence rows, that draw the visible part of
+    /// the view columns
+    pub fn cells_of_columns(&self, cols: Range<usize>) -> Range<usize> {
+        let window = self.window().col_range;
+        let start = cols.start.clamp(window.start, window.end) - window.start;
+        let end = cols.end.clamp(window.start, window.end) - window.start;
+        start / self.cols_per_cell()..end.div_ceil(self.cols_per_cell())
+    }
+
+    /// terminal line of the scrollable rows the view row is drawn on, when it is visible
+    pub fn line_of_row(&self, row: usize) -> Option<usize> {
+        self.window()
+            .row_range
+            .contains(&row)
+            .then(|| (row - self.offsets.rows) / self.rows_per_line())
+    }
+
+    /// characters of a sequence ID the name pane can show
+    pub fn name_visible_width(&self) -> usize {
+        self.dims.name_width
+    }
+
+    /// columns of the view that can be scrolled to
+    pub fn column_count(&self) -> usize {
+        self.max.cols
+    }
+
     pub fn window(&self) -> ViewportWindow {
         let row_end = self
             .offsets
@@ -117,11 +162,35 @@ impl Viewport {
         self.offsets.cols = position.min(max_scroll);
     }
 
+    /// scrolls so the view columns sit in the middle of the window, or start at its left edge
+    /// when they are wider than it
+    pub fn centre_on_columns(&mut self, columns: Range<usize>) {
+        let slack = self.dims.cols.saturating_sub(columns.len());
+        self.jump_to_position(columns.start.saturating_sub(slack / 2));
+    }
+
     pub fn jump_to_sequence(&mut self, sequence_index: usize) {
         let max_scroll = self.max.rows.saturating_sub(self.dims.rows);
         self.offsets.rows = sequence_index.min(max_scroll);
     }
 
+    /// scrolls the minimum amount needed for the given view row and column to be visible
+    pub fn scroll_to_reveal(&mut self, row: usize, col: usize) {
+        if row < self.offsets.rows {
+            self.offsets.rows = row;
+        } else if self.dims.rows > 0 && row >= self.offsets.rows + self.dims.rows {
+            self.offsets.rows = row + 1 - self.dims.rows;
+        }
+
+        if col < self.offsets.cols {
+            self.offsets.cols = col;
+        } else if self.dims.cols > 0 && col >= self.offsets.cols + self.dims.cols {
+            self.offsets.cols = col + 1 - self.dims.cols;
+        }
+
+        self.clamp_offsets();
+    }
+
     fn clamp_offsets(&mut self) {
         let row_max = self.max.rows.saturating_sub(self.dims.rows);
         let col_max = self.max.cols.saturating_sub(self.dims.cols);
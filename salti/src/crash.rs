@@ -0,0 +1,251 @@
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::thread::ThreadId;
+
+use crossterm::event::{DisableBracketedPaste, DisableMouseCapture};
+use crossterm::execute;
+use tracing_subscriber::fmt::MakeWriter;
+
+/// number of log lines kept in memory for crash reports
+const RECENT_LOG_LINES: usize = 200;
+const ISSUES_URL: &str = "https://github.com/Sam-Sims/salti/issues";
+
+static RECENT_LOGS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+/// thread the TUI runs on, recorded when the hook is installed
+static UI_THREAD: OnceLock<ThreadId> = OnceLock::new();
+
+fn push_log_lines(text: &str) {
+    let Ok(mut logs) = RECENT_LOGS.lock() else {
+        return;
+    };
+    for line in text.lines().filter(|line| !line.trim().is_empty()) {
+        if logs.len() == RECENT_LOG_LINES {
+            logs.pop_front();
+        }
+        logs.push_back(line.to_string());
+    }
+}
+
+fn recent_log_lines() -> Vec<String> {
+    RECENT_LOGS
+        .lock()
+        .map(|logs| logs.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// tracing writer factory that keeps the most recent log lines for the crash report
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RecentLogs;
+
+/// buffers partial writes so that only whole lines are recorded
+#[derive(Debug, Default)]
+pub struct RecentLogWriter {
+    pending: String,
+}
+
+impl Write for RecentLogWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.push_str(&String::from_utf8_lossy(buf));
+        if let Some(end) = self.pending.rfind('\n') {
+            push_log_lines(&self.pending[..end]);
+            self.pending.drain(..=end);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        push_log_lines(&self.pending);
+        self.pending.clear();
+        Ok(())
+    }
+}
+
+impl Drop for RecentLogWriter {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+impl<'a> MakeWriter<'a> for RecentLogs {
+    type Writer = RecentLogWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RecentLogWriter::default()
+    }
+}
+
+fn panic_message(info: &PanicHookInfo<'_>) -> String {
+    let payload = info.payload();
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| (*message).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic payload".to_string())
+}
+
+fn build_report(message: &str, location: &str, backtrace: &str, log_lines: &[String]) -> String {
+    let mut report = format!(
+        "salti {} crash report\nos: {} ({})\n\npanic: {message}\nlocation: {location}\n\nbacktrace:\n{backtrace}\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+    );
+
+    report.push_str("\nrecent log lines:\n");
+    if log_lines.is_empty() {
+        report.push_str("(none captured; run with --log-file or --debug to include logs)\n");
+    }
+    for line in log_lines {
+        report.push_str(line);
+        report.push('\n');
+    }
+    report
+}
+
+/// writes the report to a fresh, uniquely named file in the temp directory. the name is not
+/// predictable, so another user cannot plant a file or symlink there ahead of the crash.
+fn write_report(report: &str) -> io::Result<PathBuf> {
+    let mut file = tempfile::Builder::new()
+        .prefix("salti-crash-")
+        .suffix(".log")
+        .tempfile()?;
+    file.write_all(report.as_bytes())?;
+    let (_, path) = file.keep().map_err(|error| error.error)?;
+    Ok(path)
+}
+
+fn restore_terminal() {
+    let _ = execute!(io::stdout(), DisableMouseCapture, DisableBracketedPaste);
+    crate::terminal::pop_title();
+    ratatui::restore();
+}
+
+/// whether a panic on the current thread takes salti down. background jobs run on other
+/// threads and their panics come back as join errors, so the TUI keeps running.
+fn panic_is_fatal() -> bool {
+    UI_THREAD
+        .get()
+        .is_none_or(|&thread| thread == std::thread::current().id())
+}
+
+fn report_panic(info: &PanicHookInfo<'_>, restore: impl FnOnce()) {
+    let location = info.location().map_or_else(
+        || "unknown".to_string(),
+        |location| format!("{}:{}", location.file(), location.line()),
+    );
+    let message = panic_message(info);
+    if !panic_is_fatal() {
+        tracing::error!(panic = %message, location = %location, "Background job panicked");
+        return;
+    }
+    restore();
+
+    let backtrace = std::backtrace::Backtrace::force_capture().to_string();
+    tracing::error!(panic = %message, location = %location, "salti panicked");
+    let report = build_report(&message, &location, &backtrace, &recent_log_lines());
+
+    let mut stderr = io::stderr();
+    let _ = writeln!(stderr, "salti crashed unexpectedly: {message}");
+    match write_report(&report) {
+        Ok(path) => {
+            let _ = writeln!(
+                stderr,
+                "A crash report was written to {}\nPlease open an issue at {ISSUES_URL} and attach it.",
+                path.display()
+            );
+        }
+        Err(error) => {
+            let _ = writeln!(
+                stderr,
+                "Failed to write a crash report ({error}). Please open an issue at {ISSUES_URL}.\n\n{report}"
+            );
+        }
+    }
+}
+
+/// replaces the default panic output with a terminal restore, a crash report file and a
+/// short message pointing at it. only panics on the calling thread, which runs the TUI, are
+/// reported this way; the rest are logged.
+pub fn install_panic_hook() {
+    let _ = UI_THREAD.set(std::thread::current().id());
+    std::panic::set_hook(Box::new(|info| report_panic(info, restore_terminal)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_includes_panic_details_and_logs() {
+        let report = build_report(
+            "index out of bounds",
+            "src/app.rs:10",
+            "0: main",
+            &["INFO salti: loading".to_string()],
+        );
+
+        assert!(report.contains("panic: index out of bounds"));
+        assert!(report.contains("location: src/app.rs:10"));
+        assert!(report.contains("backtrace:\n0: main"));
+        assert!(report.ends_with("recent log lines:\nINFO salti: loading\n"));
+    }
+
+    #[test]
+    fn report_explains_missing_logs() {
+        let report = build_report("boom", "here", "", &[]);
+
+        assert!(report.contains("run with --log-file or --debug"));
+    }
+
+    #[test]
+    fn reports_are_kept_under_unique_names() {
+        let first = write_report("first").expect("report should be written");
+        let second = write_report("second").expect("report should be written");
+
+        assert_ne!(first, second);
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), "first");
+        let name = first.file_name().unwrap().to_str().unwrap();
+        assert!(name.starts_with("salti-crash-") && name.ends_with(".log"));
+        let _ = std::fs::remove_file(first);
+        let _ = std::fs::remove_file(second);
+    }
+
+    #[test]
+    fn job_panics_leave_the_terminal_alone() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let _ = UI_THREAD.set(std::thread::current().id());
+        let restored = Arc::new(AtomicBool::new(false));
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new({
+            let restored = Arc::clone(&restored);
+            move |info| report_panic(info, || restored.store(true, Ordering::SeqCst))
+        }));
+        let job = std::thread::spawn(|| panic!("job failed"));
+        let joined = job.join();
+        std::panic::set_hook(previous);
+
+        assert!(joined.is_err());
+        assert!(!restored.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn recent_logs_keep_only_the_newest_lines() {
+        let mut writer = RecentLogs.make_writer();
+        for index in 0..RECENT_LOG_LINES + 5 {
+            writeln!(writer, "line {index}").expect("writing to memory should succeed");
+        }
+
+        let lines = recent_log_lines();
+
+        assert_eq!(lines.len(), RECENT_LOG_LINES);
+        assert_eq!(
+            lines.last().map(String::as_str),
+            Some(format!("line {}", RECENT_LOG_LINES + 4).as_str())
+        );
+    }
+}
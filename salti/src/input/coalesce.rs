@@ -0,0 +1,171 @@
+use crate::command::Command;
+
+fn merge_scrolls(previous: &Command, next: &Command) -> Option<Command> {
+    let merged = match (previous, next) {
+        (Command::ScrollDown { amount: a }, Command::ScrollDown { amount: b }) => {
+            Command::ScrollDown {
+                amount: a.saturating_add(*b),
+            }
+        }
+        (Command::ScrollUp { amount: a }, Command::ScrollUp { amount: b }) => Command::ScrollUp {
+            amount: a.saturating_add(*b),
+        },
+        (Command::ScrollLeft { amount: a }, Command::ScrollLeft { amount: b }) => {
+            Command::ScrollLeft {
+                amount: a.saturating_add(*b),
+            }
+        }
+        (Command::ScrollRight { amount: a }, Command::ScrollRight { amount: b }) => {
+            Command::ScrollRight {
+                amount: a.saturating_add(*b),
+            }
+        }
+        (Command::ScrollNamesLeft { amount: a }, Command::ScrollNamesLeft { amount: b }) => {
+            Command::ScrollNamesLeft {
+                amount: a.saturating_add(*b),
+            }
+        }
+        (Command::ScrollNamesRight { amount: a }, Command::ScrollNamesRight { amount: b }) => {
+            Command::ScrollNamesRight {
+                amount: a.saturating_add(*b),
+            }
+        }
+        _ => return None,
+    };
+    Some(merged)
+}
+
+const fn is_scroll(command: &Command) -> bool {
+    matches!(
+        command,
+        Command::ScrollDown { .. }
+            | Command::ScrollUp { .. }
+            | Command::ScrollLeft { .. }
+            | Command::ScrollRight { .. }
+            | Command::ScrollNamesLeft { .. }
+            | Command::ScrollNamesRight { .. }
+    )
+}
+
+/// holds back scroll commands until the next frame so that key repeats and wheel bursts
+/// arriving between two redraws are applied as one larger scroll
+#[derive(Debug, Default)]
+pub(crate) struct InputCoalescer {
+    enabled: bool,
+    pending: Option<Command>,
+}
+
+impl InputCoalescer {
+    /// returns any held back scroll so it is not lost when coalescing is switched off
+    pub fn set_enabled(&mut self, enabled: bool) -> Option<Command> {
+        self.enabled = enabled;
+        if enabled { None } else { self.flush() }
+    }
+
+    /// returns the commands that should run now. a non-scroll command first releases the
+    /// pending scroll so command order is preserved.
+    pub fn push(&mut self, commands: Vec<Command>) -> Vec<Command> {
+        if !self.enabled {
+            return commands;
+        }
+
+        let mut ready = Vec::new();
+        for command in commands {
+            if !is_scroll(&command) {
+                ready.extend(self.pending.take());
+                ready.push(command);
+                continue;
+            }
+
+            self.pending = match self.pending.take() {
+                Some(previous) => match merge_scrolls(&previous, &command) {
+                    Some(merged) => Some(merged),
+                    None => {
+                        ready.push(previous);
+                        Some(command)
+                    }
+                },
+                None => Some(command),
+            };
+        }
+        ready
+    }
+
+    pub fn flush(&mut self) -> Option<Command> {
+        self.pending.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coalescer() -> InputCoalescer {
+        let mut coalescer = InputCoalescer::default();
+        coalescer.set_enabled(true);
+        coalescer
+    }
+
+    #[test]
+    fn disabled_coalescer_passes_commands_through() {
+        let mut coalescer = InputCoalescer::default();
+
+        let ready = coalescer.push(vec![Command::ScrollDown { amount: 1 }]);
+
+        assert_eq!(ready, vec![Command::ScrollDown { amount: 1 }]);
+        assert_eq!(coalescer.flush(), None);
+    }
+
+    #[test]
+    fn repeated_scrolls_merge_until_flushed() {
+        let mut coalescer = coalescer();
+
+        assert!(
+            coalescer
+                .push(vec![Command::ScrollDown { amount: 1 }])
+                .is_empty()
+        );
+        assert!(
+            coalescer
+                .push(vec![Command::ScrollDown { amount: 10 }])
+                .is_empty()
+        );
+
+        assert_eq!(coalescer.flush(), Some(Command::ScrollDown { amount: 11 }));
+    }
+
+    #[test]
+    fn direction_change_releases_the_pending_scroll() {
+        let mut coalescer = coalescer();
+        coalescer.push(vec![Command::ScrollDown { amount: 2 }]);
+
+        let ready = coalescer.push(vec![Command::ScrollRight { amount: 1 }]);
+
+        assert_eq!(ready, vec![Command::ScrollDown { amount: 2 }]);
+        assert_eq!(coalescer.flush(), Some(Command::ScrollRight { amount: 1 }));
+    }
+
+    #[test]
+    fn other_commands_run_after_the_pending_scroll() {
+        let mut coalescer = coalescer();
+        coalescer.push(vec![Command::ScrollUp { amount: 3 }]);
+
+        let ready = coalescer.push(vec![Command::OpenCommandPalette]);
+
+        assert_eq!(
+            ready,
+            vec![Command::ScrollUp { amount: 3 }, Command::OpenCommandPalette]
+        );
+    }
+
+    #[test]
+    fn disabling_returns_the_pending_scroll() {
+        let mut coalescer = coalescer();
+        coalescer.push(vec![Command::ScrollLeft { amount: 4 }]);
+
+        assert_eq!(
+            coalescer.set_enabled(false),
+            Some(Command::ScrollLeft { amount: 4 })
+        );
+    }
+}
@@ -1,24 +1,121 @@
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyCode, KeyEvent};
 
 use crate::command::Command;
 use crate::config::keybindings;
+use crate::core::marks::MarkAction;
 use crate::input::route::{KeyRoute, route_key};
 use crate::overlay::overlay_state::ActiveOverlay;
+use crate::ui::notification::{Notification, NotificationLevel};
 use crate::ui::ui_state::UiState;
 
+/// the letter after `m` or `'` names the mark. `Esc` cancels, and any other key is rejected.
+fn complete_mark(action: MarkAction, key: KeyEvent) -> Vec<Command> {
+    match (key.code, action) {
+        (KeyCode::Char(letter), MarkAction::Set) if letter.is_ascii_alphabetic() => {
+            vec![Command::SetMark(letter)]
+        }
+        (KeyCode::Char(letter), MarkAction::Jump) if letter.is_ascii_alphabetic() => {
+            vec![Command::JumpToMark(letter)]
+        }
+        (KeyCode::Esc, _) => Vec::new(),
+        _ => vec![Command::ShowNotification(Notification {
+            level: NotificationLevel::Error,
+            message: "marks are named by a letter".to_string(),
+        })],
+    }
+}
+
 pub(crate) fn handle_key_event(ui: &mut UiState, key: KeyEvent) -> Vec<Command> {
-    match route_key(ui) {
+    let route = route_key(ui);
+    if let Some(action) = ui.marks.pending.take()
+        && matches!(
+            route,
+            KeyRoute::Minimap | KeyRoute::Crosshair | KeyRoute::Global
+        )
+    {
+        return complete_mark(action, key);
+    }
+    match route {
         KeyRoute::Palette => match ui.overlay.active_overlay.as_mut() {
             Some(ActiveOverlay::Palette(palette)) => palette.handle_key_event(key),
             _ => Vec::new(),
         },
-        KeyRoute::Global => match keybindings::lookup(key.code, key.modifiers) {
-            Some(command) => vec![command],
-            None => Vec::new(),
+        KeyRoute::ContextMenu => match ui.overlay.active_overlay.as_mut() {
+            Some(ActiveOverlay::ContextMenu(menu)) => menu.handle_key_event(key),
+            _ => Vec::new(),
+        },
+        KeyRoute::Report => match ui.overlay.active_overlay.as_mut() {
+            Some(ActiveOverlay::Report(report)) => report.handle_key_event(key),
+            _ => Vec::new(),
+        },
+        KeyRoute::Pager => match ui.overlay.active_overlay.as_mut() {
+            Some(ActiveOverlay::Pager(pager)) => pager.handle_key_event(key),
+            _ => Vec::new(),
+        },
+        KeyRoute::FilePicker => match ui.overlay.active_overlay.as_mut() {
+            Some(ActiveOverlay::FilePicker(picker)) => picker.handle_key_event(key),
+            _ => Vec::new(),
+        },
+        KeyRoute::Confirm => match ui.overlay.active_overlay.as_ref() {
+            Some(ActiveOverlay::Confirm(confirm)) => confirm.handle_key_event(key),
+            _ => Vec::new(),
+        },
+        KeyRoute::Paste => match ui.overlay.active_overlay.as_mut() {
+            Some(ActiveOverlay::Paste(paste)) => paste.handle_key_event(key),
+            _ => Vec::new(),
+        },
+        KeyRoute::ConsensusThreshold => match ui.overlay.active_overlay.as_mut() {
+            Some(ActiveOverlay::ConsensusThreshold(slider)) => slider.handle_key_event(key),
+            _ => Vec::new(),
         },
+        KeyRoute::Minimap => {
+            let viewport_col_range = ui.viewport.window().col_range;
+            let total_columns = ui.viewport.column_count();
+            let handled = match ui.overlay.active_overlay.as_mut() {
+                Some(ActiveOverlay::Minimap(minimap)) => {
+                    minimap.handle_key(key, &viewport_col_range, total_columns)
+                }
+                _ => None,
+            };
+            handled.unwrap_or_else(|| binding_commands(ui, key))
+        }
+        KeyRoute::Crosshair | KeyRoute::Global => binding_commands(ui, key),
     }
 }
 
+/// text from a bracketed paste. the paste buffer takes all of it, while the palette and file
+/// picker take its first line as if it had been typed.
+pub(crate) fn handle_paste_event(ui: &mut UiState, text: &str) -> Vec<Command> {
+    let typed = || {
+        text.lines()
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .filter(|character| !character.is_control())
+            .map(|character| KeyEvent::from(KeyCode::Char(character)))
+    };
+    match ui.overlay.active_overlay.as_mut() {
+        Some(ActiveOverlay::Paste(paste)) => paste.handle_paste(text),
+        Some(ActiveOverlay::Palette(palette)) => typed()
+            .flat_map(|key| palette.handle_key_event(key))
+            .collect(),
+        Some(ActiveOverlay::FilePicker(picker)) => typed()
+            .flat_map(|key| picker.handle_key_event(key))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// crosshair bindings while the crosshair is shown, the global bindings otherwise
+fn binding_commands(ui: &UiState, key: KeyEvent) -> Vec<Command> {
+    let command = if ui.crosshair.is_some() {
+        keybindings::lookup_crosshair(key.code, key.modifiers)
+    } else {
+        keybindings::lookup(key.code, key.modifiers)
+    };
+    command.into_iter().collect()
+}
+
 #[cfg(test)]
 mod tests {
     use crossterm::event::{KeyCode, KeyEvent};
@@ -26,11 +123,14 @@ mod tests {
     use super::*;
     use crate::cli::StartupState;
     use crate::overlay::command_palette::CommandPaletteState;
+    use crate::ui::notification::ErrorFeedback;
+    use crate::ui::ui_state::Crosshair;
 
     fn ui_state() -> UiState {
         UiState::new(StartupState {
             file_path: None,
             initial_position: 0,
+            ..StartupState::default()
         })
     }
 
@@ -43,6 +143,105 @@ mod tests {
         assert_eq!(commands, vec![Command::Quit]);
     }
 
+    #[test]
+    fn crosshair_mode_routes_arrows_to_crosshair_movement() {
+        let mut ui = ui_state();
+        ui.crosshair = Some(Crosshair { row: 0, column: 0 });
+
+        let commands = handle_key_event(&mut ui, KeyEvent::from(KeyCode::Right));
+
+        assert_eq!(commands, vec![Command::MoveCrosshair { rows: 0, cols: 1 }]);
+    }
+
+    #[test]
+    fn crosshair_mode_falls_back_to_global_bindings() {
+        let mut ui = ui_state();
+        ui.crosshair = Some(Crosshair { row: 0, column: 0 });
+
+        let commands = handle_key_event(&mut ui, KeyEvent::from(KeyCode::Char('q')));
+
+        assert_eq!(commands, vec![Command::Quit]);
+    }
+
+    #[test]
+    fn mark_keys_wait_for_a_letter() {
+        let mut ui = ui_state();
+
+        ui.marks.pending = Some(MarkAction::Set);
+        let commands = handle_key_event(&mut ui, KeyEvent::from(KeyCode::Char('a')));
+        assert_eq!(commands, vec![Command::SetMark('a')]);
+
+        ui.marks.pending = Some(MarkAction::Jump);
+        let commands = handle_key_event(&mut ui, KeyEvent::from(KeyCode::Char('a')));
+        assert_eq!(commands, vec![Command::JumpToMark('a')]);
+
+        ui.marks.pending = Some(MarkAction::Jump);
+        let commands = handle_key_event(&mut ui, KeyEvent::from(KeyCode::Esc));
+        assert!(commands.is_empty());
+        assert_eq!(ui.marks.pending, None);
+
+        ui.marks.pending = Some(MarkAction::Set);
+        let commands = handle_key_event(&mut ui, KeyEvent::from(KeyCode::Char('1')));
+        assert!(matches!(
+            commands.as_slice(),
+            [Command::ShowNotification(Notification {
+                level: NotificationLevel::Error,
+                ..
+            })]
+        ));
+    }
+
+    #[test]
+    fn errors_ring_the_bell_or_flash_as_configured() {
+        let mut ui = ui_state();
+        let error = Notification {
+            level: NotificationLevel::Error,
+            message: "no sequence under the cursor".to_string(),
+        };
+
+        ui.notify(error.clone());
+        assert!(!ui.bell_pending && ui.flash_until.is_none());
+
+        ui.error_feedback = ErrorFeedback::Bell;
+        ui.notify(Notification {
+            level: NotificationLevel::Info,
+            message: "Pinned 2 sequences".to_string(),
+        });
+        assert!(!ui.bell_pending);
+        ui.notify(error.clone());
+        assert!(ui.bell_pending);
+
+        ui.error_feedback = ErrorFeedback::Flash;
+        ui.notify(error);
+        assert!(ui.flash_until.is_some());
+    }
+
+    #[test]
+    fn minimap_keys_move_the_box_before_jumping() {
+        let mut ui = ui_state();
+        ui.viewport.set_bounds(1, 1000, 10);
+        ui.viewport.update_dimensions(100, 1, 10);
+        ui.overlay.toggle_minimap();
+
+        assert!(handle_key_event(&mut ui, KeyEvent::from(KeyCode::PageDown)).is_empty());
+        assert!(handle_key_event(&mut ui, KeyEvent::from(KeyCode::Right)).is_empty());
+        let commands = handle_key_event(&mut ui, KeyEvent::from(KeyCode::Enter));
+        assert_eq!(commands, vec![Command::JumpToPosition(110)]);
+
+        handle_key_event(&mut ui, KeyEvent::from(KeyCode::Char('4')));
+        handle_key_event(&mut ui, KeyEvent::from(KeyCode::Char('2')));
+        let commands = handle_key_event(&mut ui, KeyEvent::from(KeyCode::Enter));
+        assert_eq!(commands, vec![Command::JumpToAlignmentPosition(41)]);
+
+        let commands = handle_key_event(
+            &mut ui,
+            KeyEvent::new(KeyCode::Char('M'), crossterm::event::KeyModifiers::SHIFT),
+        );
+        assert_eq!(commands, vec![Command::ToggleMinimap]);
+        let commands = handle_key_event(&mut ui, KeyEvent::from(KeyCode::Esc));
+        assert_eq!(commands, vec![Command::CloseOverlay]);
+    }
+
     #[test]
     fn palette_keys_are_routed_to_palette_state() {
         let mut ui = ui_state();
@@ -52,4 +251,24 @@ mod tests {
 
         assert_eq!(commands, vec![Command::CloseOverlay]);
     }
+
+    #[test]
+    fn pastes_fill_the_paste_buffer_or_type_into_the_palette() {
+        let mut ui = ui_state();
+        assert!(handle_paste_event(&mut ui, ">a\nACGT").is_empty());
+
+        ui.overlay.open_paste();
+        assert_eq!(
+            handle_paste_event(&mut ui, ">a\nACGT"),
+            vec![
+                Command::CloseOverlay,
+                Command::LoadPastedAlignment(">a\nACGT".to_string())
+            ]
+        );
+
+        ui.overlay.open_palette(CommandPaletteState::empty());
+        handle_paste_event(&mut ui, "quit\nignored");
+        let commands = handle_key_event(&mut ui, KeyEvent::from(KeyCode::Enter));
+        assert!(commands.contains(&Command::Quit));
+    }
 }
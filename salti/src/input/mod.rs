@@ -1,6 +1,8 @@
+mod coalesce;
 mod key;
 mod mouse;
 mod route;
 
-pub(crate) use key::handle_key_event;
+pub(crate) use coalesce::InputCoalescer;
+pub(crate) use key::{handle_key_event, handle_paste_event};
 pub(crate) use mouse::{MouseTracker, handle_mouse_event};
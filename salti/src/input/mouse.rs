@@ -1,3 +1,5 @@
+use std::time::{Duration, Instant};
+
 use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 
 use crate::command::Command;
@@ -5,10 +7,20 @@ use crate::core::model::AlignmentModel;
 use crate::input::route::{MouseRoute, route_mouse};
 use crate::overlay::minimap::MinimapState;
 use crate::overlay::overlay_state::ActiveOverlay;
-use crate::ui::layout::{AppLayout, FrameLayout};
-use crate::ui::selection::{codon_span_for_absolute_column, selection_point_crosshair};
+use crate::ui::layout::{AppLayout, FrameLayout, LayoutMode};
+use crate::ui::notification::{Notification, NotificationLevel};
+use crate::ui::selection::{
+    codon_span_for_absolute_column, selection_point_crosshair, sequence_id_pane_row,
+};
 use crate::ui::ui_state::{MouseSelection, UiState};
 
+/// rows or columns moved per wheel notch until the step is changed with ctrl+wheel
+const DEFAULT_WHEEL_STEP: usize = 3;
+/// upper bound for the wheel step when growing it with ctrl+wheel
+const MAX_WHEEL_STEP: usize = 96;
+/// maximum time between two clicks on the same cell for them to count as a double-click
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) struct MouseAnchor {
     sequence_id: usize,
@@ -16,18 +28,61 @@ pub(crate) struct MouseAnchor {
     end_column: usize,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub(crate) struct MouseTracker {
     box_anchor: Option<MouseAnchor>,
     pan_anchor: Option<(u16, u16)>,
+    wheel_step: usize,
+    last_click: Option<(Instant, u16, u16)>,
+    /// last cell the mouse was seen over in the alignment area
+    hover: Option<(u16, u16)>,
+}
+
+impl Default for MouseTracker {
+    fn default() -> Self {
+        Self {
+            box_anchor: None,
+            pan_anchor: None,
+            wheel_step: DEFAULT_WHEEL_STEP,
+            last_click: None,
+            hover: None,
+        }
+    }
 }
 
 impl MouseTracker {
+    pub fn hover(&self) -> Option<(u16, u16)> {
+        self.hover
+    }
+
     pub fn clear_anchors(&mut self) {
         self.box_anchor = None;
         self.pan_anchor = None;
     }
 
+    /// records a left click and reports whether it completes a double-click
+    fn register_click(&mut self, column: u16, row: u16) -> bool {
+        let now = Instant::now();
+        let is_double = self.last_click.is_some_and(|(at, last_column, last_row)| {
+            (last_column, last_row) == (column, row)
+                && now.duration_since(at) <= DOUBLE_CLICK_INTERVAL
+        });
+        self.last_click = (!is_double).then_some((now, column, row));
+        is_double
+    }
+
+    fn change_wheel_step(&mut self, grow: bool) -> Command {
+        self.wheel_step = if grow {
+            (self.wheel_step * 2).min(MAX_WHEEL_STEP)
+        } else {
+            (self.wheel_step / 2).max(1)
+        };
+        Command::ShowNotification(Notification {
+            level: NotificationLevel::Info,
+            message: format!("Scroll step: {}", self.wheel_step),
+        })
+    }
+
     fn pan_drag_commands(&mut self, column: u16, row: u16) -> [Option<Command>; 2] {
         let Some((anchor_x, anchor_y)) = self.pan_anchor else {
             return [None, None];
@@ -71,8 +126,33 @@ pub(crate) fn handle_mouse_event(
     mouse: MouseEvent,
 ) -> Vec<Command> {
     let mut commands = Vec::new();
-    match route_mouse(ui, frame_layout, mouse) {
-        MouseRoute::Palette => (),
+    let route = route_mouse(ui, frame_layout, mouse);
+    tracker.hover = (route == MouseRoute::Alignment).then_some((mouse.column, mouse.row));
+    match route {
+        MouseRoute::Palette
+        | MouseRoute::Confirm
+        | MouseRoute::Paste
+        | MouseRoute::ConsensusThreshold => (),
+        MouseRoute::ContextMenu => {
+            if let Some(ActiveOverlay::ContextMenu(menu)) = ui.overlay.active_overlay.as_mut() {
+                commands.extend(menu.handle_mouse(mouse, frame_layout.overlay_area));
+            }
+        }
+        MouseRoute::Report => {
+            if let Some(ActiveOverlay::Report(report)) = ui.overlay.active_overlay.as_mut() {
+                commands.extend(report.handle_mouse(mouse, frame_layout.overlay_area));
+            }
+        }
+        MouseRoute::Pager => {
+            if let Some(ActiveOverlay::Pager(pager)) = ui.overlay.active_overlay.as_mut() {
+                commands.extend(pager.handle_mouse(mouse, frame_layout.overlay_area));
+            }
+        }
+        MouseRoute::FilePicker => {
+            if let Some(ActiveOverlay::FilePicker(picker)) = ui.overlay.active_overlay.as_mut() {
+                commands.extend(picker.handle_mouse(mouse, frame_layout.overlay_area));
+            }
+        }
         MouseRoute::Minimap => {
             if let Some(alignment) = alignment {
                 let viewport_col_range = ui.viewport.window().col_range;
@@ -132,13 +212,29 @@ fn handle_alignment_mouse_event(
     app_layout: &AppLayout,
     mouse: MouseEvent,
 ) {
-    let crosshair = selection_point_crosshair(
-        alignment,
-        &ui.viewport,
-        app_layout.alignment_pane_sequence_rows,
-        mouse.column,
-        mouse.row,
-    );
+    // row and column hit-testing assumes the panned layout, so the wrapped layout
+    // only supports scrolling and panning
+    let panned = ui.layout_mode == LayoutMode::Panned;
+    if panned
+        && let Some(sequence_id) =
+            sequence_id_pane_row(alignment, &ui.viewport, app_layout, mouse.column, mouse.row)
+        && let Some(command) = sequence_id_pane_command(tracker, alignment, sequence_id, mouse)
+    {
+        commands.push(command);
+        return;
+    }
+
+    let crosshair = panned
+        .then(|| {
+            selection_point_crosshair(
+                alignment,
+                &ui.viewport,
+                app_layout.alignment_pane_sequence_rows,
+                mouse.column,
+                mouse.row,
+            )
+        })
+        .flatten();
     let resolved_anchor = crosshair
         .and_then(|(sequence_id, column)| anchor_from_crosshair(alignment, sequence_id, column));
 
@@ -185,10 +281,83 @@ fn handle_alignment_mouse_event(
         MouseEventKind::Up(MouseButton::Middle) => {
             tracker.pan_anchor = None;
         }
+        MouseEventKind::ScrollDown
+        | MouseEventKind::ScrollUp
+        | MouseEventKind::ScrollLeft
+        | MouseEventKind::ScrollRight => {
+            commands.push(wheel_command(
+                tracker,
+                mouse,
+                app_layout
+                    .sequence_id_pane
+                    .contains((mouse.column, mouse.row).into()),
+            ));
+        }
         _ => (),
     }
 }
 
+fn sequence_id_pane_command(
+    tracker: &mut MouseTracker,
+    alignment: &AlignmentModel,
+    sequence_id: usize,
+    mouse: MouseEvent,
+) -> Option<Command> {
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => tracker
+            .register_click(mouse.column, mouse.row)
+            .then_some(Command::SetReference(sequence_id)),
+        MouseEventKind::Down(MouseButton::Middle) => {
+            Some(if alignment.rows().is_pinned(sequence_id) {
+                Command::UnpinSequence(sequence_id)
+            } else {
+                Command::PinSequence(sequence_id)
+            })
+        }
+        MouseEventKind::Down(MouseButton::Right) => Some(Command::OpenSequenceMenu {
+            sequence_id,
+            x: mouse.column,
+            y: mouse.row,
+        }),
+        _ => None,
+    }
+}
+
+fn wheel_command(tracker: &mut MouseTracker, mouse: MouseEvent, over_names: bool) -> Command {
+    let amount = tracker.wheel_step;
+    let towards_start = matches!(
+        mouse.kind,
+        MouseEventKind::ScrollUp | MouseEventKind::ScrollLeft
+    );
+
+    // Ctrl+wheel zooms as in most viewers, leaving Alt+wheel to change the scroll step
+    if mouse.modifiers.contains(KeyModifiers::CONTROL) {
+        return if towards_start {
+            Command::ZoomIn
+        } else {
+            Command::ZoomOut
+        };
+    }
+    if mouse.modifiers.contains(KeyModifiers::ALT) {
+        return tracker.change_wheel_step(towards_start);
+    }
+
+    let horizontal = mouse.modifiers.contains(KeyModifiers::SHIFT)
+        || matches!(
+            mouse.kind,
+            MouseEventKind::ScrollLeft | MouseEventKind::ScrollRight
+        );
+
+    match (horizontal, towards_start, over_names) {
+        (false, true, _) => Command::ScrollUp { amount },
+        (false, false, _) => Command::ScrollDown { amount },
+        (true, true, true) => Command::ScrollNamesLeft { amount },
+        (true, false, true) => Command::ScrollNamesRight { amount },
+        (true, true, false) => Command::ScrollLeft { amount },
+        (true, false, false) => Command::ScrollRight { amount },
+    }
+}
+
 fn anchor_from_crosshair(
     alignment: &AlignmentModel,
     sequence_id: usize,
@@ -233,12 +402,13 @@ mod tests {
     use super::*;
     use crate::cli::StartupState;
     use crate::overlay::command_palette::CommandPaletteState;
-    use crate::ui::layout::{AppLayout, FrameLayout};
+    use crate::ui::layout::{AppLayout, FrameLayout, LayoutMode, LayoutOptions};
 
     fn raw(id: &str, sequence: &[u8]) -> libmsa::RawSequence {
         libmsa::RawSequence {
             id: id.to_string(),
             sequence: sequence.to_vec(),
+            quality: None,
         }
     }
 
@@ -246,6 +416,7 @@ mod tests {
         UiState::new(StartupState {
             file_path: None,
             initial_position: 0,
+            ..StartupState::default()
         })
     }
 
@@ -255,7 +426,7 @@ mod tests {
         let mut ui = ui_state();
         ui.overlay.open_palette(CommandPaletteState::empty());
         let frame_layout = FrameLayout::new(Rect::new(0, 0, 80, 24));
-        let app_layout = AppLayout::new(frame_layout.content_area);
+        let app_layout = AppLayout::new(frame_layout.content_area, LayoutOptions::default());
         let mouse = MouseEvent {
             kind: MouseEventKind::Down(MouseButton::Left),
             column: 10,
@@ -289,7 +460,7 @@ mod tests {
         let mut tracker = MouseTracker::default();
         let mut ui = ui_state();
         let frame_layout = FrameLayout::new(Rect::new(0, 0, 80, 24));
-        let app_layout = AppLayout::new(frame_layout.content_area);
+        let app_layout = AppLayout::new(frame_layout.content_area, LayoutOptions::default());
         ui.viewport.update_dimensions(78, 10, 20);
         ui.viewport.set_bounds(2, 200, 4);
         ui.overlay.toggle_minimap();
@@ -311,4 +482,171 @@ mod tests {
 
         assert!(matches!(commands.as_slice(), [Command::JumpToPosition(_)]));
     }
+
+    fn wheel_event(kind: MouseEventKind, modifiers: KeyModifiers, column: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row: 10,
+            modifiers,
+        }
+    }
+
+    fn commands_for(tracker: &mut MouseTracker, mouse: MouseEvent) -> Vec<Command> {
+        commands_in_layout(tracker, mouse, LayoutMode::Panned)
+    }
+
+    fn commands_in_layout(
+        tracker: &mut MouseTracker,
+        mouse: MouseEvent,
+        layout_mode: LayoutMode,
+    ) -> Vec<Command> {
+        let alignment = libmsa::Alignment::new(vec![raw("row1", b"ACGT"), raw("row2", b"ACGT")])
+            .expect("alignment should be valid");
+        let model = crate::core::model::AlignmentModel::new(alignment)
+            .expect("alignment model should be valid");
+        let mut ui = ui_state();
+        ui.layout_mode = layout_mode;
+        let frame_layout = FrameLayout::new(Rect::new(0, 0, 80, 24));
+        let app_layout = AppLayout::new(frame_layout.content_area, LayoutOptions::default());
+
+        handle_mouse_event(
+            tracker,
+            Some(&model),
+            &mut ui,
+            &frame_layout,
+            &app_layout,
+            mouse,
+        )
+    }
+
+    #[test]
+    fn shift_wheel_scrolls_horizontally() {
+        let mut tracker = MouseTracker::default();
+
+        let commands = commands_for(
+            &mut tracker,
+            wheel_event(MouseEventKind::ScrollDown, KeyModifiers::SHIFT, 40),
+        );
+
+        assert_eq!(
+            commands,
+            vec![Command::ScrollRight {
+                amount: DEFAULT_WHEEL_STEP
+            }]
+        );
+    }
+
+    #[test]
+    fn shift_wheel_over_names_scrolls_the_name_pane() {
+        let mut tracker = MouseTracker::default();
+
+        let commands = commands_for(
+            &mut tracker,
+            wheel_event(MouseEventKind::ScrollUp, KeyModifiers::SHIFT, 2),
+        );
+
+        assert_eq!(
+            commands,
+            vec![Command::ScrollNamesLeft {
+                amount: DEFAULT_WHEEL_STEP
+            }]
+        );
+    }
+
+    #[test]
+    fn ctrl_wheel_zooms() {
+        let mut tracker = MouseTracker::default();
+
+        let zoom_in = commands_for(
+            &mut tracker,
+            wheel_event(MouseEventKind::ScrollUp, KeyModifiers::CONTROL, 40),
+        );
+        let zoom_out = commands_for(
+            &mut tracker,
+            wheel_event(MouseEventKind::ScrollDown, KeyModifiers::CONTROL, 40),
+        );
+
+        assert_eq!(zoom_in, vec![Command::ZoomIn]);
+        assert_eq!(zoom_out, vec![Command::ZoomOut]);
+    }
+
+    #[test]
+    fn alt_wheel_changes_the_scroll_step() {
+        let mut tracker = MouseTracker::default();
+
+        commands_for(
+            &mut tracker,
+            wheel_event(MouseEventKind::ScrollUp, KeyModifiers::ALT, 40),
+        );
+        let commands = commands_for(
+            &mut tracker,
+            wheel_event(MouseEventKind::ScrollDown, KeyModifiers::NONE, 40),
+        );
+
+        assert_eq!(
+            commands,
+            vec![Command::ScrollDown {
+                amount: DEFAULT_WHEEL_STEP * 2
+            }]
+        );
+    }
+
+    fn click_event(button: MouseButton, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind: MouseEventKind::Down(button),
+            column,
+            row,
+            modifiers: KeyModifiers::empty(),
+        }
+    }
+
+    #[test]
+    fn double_click_on_a_name_sets_the_reference() {
+        let mut tracker = MouseTracker::default();
+
+        let first = commands_for(&mut tracker, click_event(MouseButton::Left, 2, 5));
+        let second = commands_for(&mut tracker, click_event(MouseButton::Left, 2, 5));
+
+        assert!(first.is_empty());
+        assert_eq!(second, vec![Command::SetReference(1)]);
+    }
+
+    #[test]
+    fn wrapped_layout_ignores_name_clicks() {
+        let mut tracker = MouseTracker::default();
+
+        let commands = commands_in_layout(
+            &mut tracker,
+            click_event(MouseButton::Middle, 2, 4),
+            LayoutMode::Wrapped,
+        );
+
+        assert!(commands.is_empty());
+    }
+
+    #[test]
+    fn middle_click_on_a_name_pins_it() {
+        let mut tracker = MouseTracker::default();
+
+        let commands = commands_for(&mut tracker, click_event(MouseButton::Middle, 2, 4));
+
+        assert_eq!(commands, vec![Command::PinSequence(0)]);
+    }
+
+    #[test]
+    fn right_click_on_a_name_opens_the_sequence_menu() {
+        let mut tracker = MouseTracker::default();
+
+        let commands = commands_for(&mut tracker, click_event(MouseButton::Right, 2, 4));
+
+        assert_eq!(
+            commands,
+            vec![Command::OpenSequenceMenu {
+                sequence_id: 0,
+                x: 2,
+                y: 4,
+            }]
+        );
+    }
 }
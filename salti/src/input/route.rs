@@ -7,12 +7,28 @@ use crate::ui::ui_state::UiState;
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(super) enum KeyRoute {
     Palette,
+    ContextMenu,
+    Report,
+    Pager,
+    FilePicker,
+    Confirm,
+    Paste,
+    ConsensusThreshold,
+    Minimap,
+    Crosshair,
     Global,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(super) enum MouseRoute {
     Palette,
+    ContextMenu,
+    Report,
+    Pager,
+    FilePicker,
+    Confirm,
+    Paste,
+    ConsensusThreshold,
     Minimap,
     Alignment,
 }
@@ -20,6 +36,15 @@ pub(super) enum MouseRoute {
 pub(super) fn route_key(ui: &UiState) -> KeyRoute {
     match &ui.overlay.active_overlay {
         Some(ActiveOverlay::Palette(_)) => KeyRoute::Palette,
+        Some(ActiveOverlay::ContextMenu(_)) => KeyRoute::ContextMenu,
+        Some(ActiveOverlay::Report(_)) => KeyRoute::Report,
+        Some(ActiveOverlay::Pager(_)) => KeyRoute::Pager,
+        Some(ActiveOverlay::FilePicker(_)) => KeyRoute::FilePicker,
+        Some(ActiveOverlay::Confirm(_)) => KeyRoute::Confirm,
+        Some(ActiveOverlay::Paste(_)) => KeyRoute::Paste,
+        Some(ActiveOverlay::ConsensusThreshold(_)) => KeyRoute::ConsensusThreshold,
+        Some(ActiveOverlay::Minimap(_)) => KeyRoute::Minimap,
+        _ if ui.crosshair.is_some() => KeyRoute::Crosshair,
         _ => KeyRoute::Global,
     }
 }
@@ -31,6 +56,13 @@ pub(super) fn route_mouse(
 ) -> MouseRoute {
     match &ui.overlay.active_overlay {
         Some(ActiveOverlay::Palette(_)) => MouseRoute::Palette,
+        Some(ActiveOverlay::ContextMenu(_)) => MouseRoute::ContextMenu,
+        Some(ActiveOverlay::Report(_)) => MouseRoute::Report,
+        Some(ActiveOverlay::Pager(_)) => MouseRoute::Pager,
+        Some(ActiveOverlay::FilePicker(_)) => MouseRoute::FilePicker,
+        Some(ActiveOverlay::Confirm(_)) => MouseRoute::Confirm,
+        Some(ActiveOverlay::Paste(_)) => MouseRoute::Paste,
+        Some(ActiveOverlay::ConsensusThreshold(_)) => MouseRoute::ConsensusThreshold,
         Some(ActiveOverlay::Minimap(minimap_state)) => {
             let left_mouse = matches!(
                 mouse.kind,
@@ -0,0 +1,55 @@
+//! salti is a terminal browser for multiple sequence alignments. Besides the `salti` binary, the
+//! crate exposes its row formatting and theming so other tools can print coloured alignment
+//! snippets without running the TUI:
+//!
+//! ```
+//! use salti::{Alignment, RawSequence, SnapshotOptions, ThemeId};
+//!
+//! let alignment = Alignment::new(vec![
+//!     RawSequence {
+//!         id: "ref".to_string(),
+//!         sequence: b"ACGTACGT".to_vec(),
+//!         quality: None,
+//!     },
+//!     RawSequence {
+//!         id: "sample".to_string(),
+//!         sequence: b"ACGTTCGT".to_vec(),
+//!         quality: None,
+//!     },
+//! ])
+//! .expect("rows should have equal lengths");
+//! let options = SnapshotOptions {
+//!     columns: Some(2..6),
+//!     theme: ThemeId::TokyoNight,
+//!     id_width: 6,
+//!     ..SnapshotOptions::default()
+//! };
+//!
+//! let buffer = salti::render_alignment_snapshot(&alignment, &options);
+//! assert_eq!(buffer.area.width, 11);
+//! print!("{}", salti::snapshot_to_ansi(&buffer));
+//! ```
+
+mod app;
+mod cli;
+mod command;
+mod config;
+mod core;
+mod crash;
+mod input;
+mod logging;
+mod overlay;
+mod terminal;
+mod tui;
+mod ui;
+mod update;
+
+pub use config::theme::{ColorStyle, SequencePaletteId, ThemeId};
+pub use core::parser::{ParsedAlignment, parse_alignment_bytes};
+pub use core::partition::Partition;
+pub use libmsa::{Alignment, AlignmentType, RawSequence};
+pub use ratatui::buffer::Buffer;
+pub use ui::snapshot::{SnapshotOptions, render_alignment_snapshot, snapshot_to_ansi};
+
+#[doc(hidden)]
+pub use tui::run;
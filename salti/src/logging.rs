@@ -1,17 +1,59 @@
 use std::fs::{File, OpenOptions};
 use std::io::ErrorKind;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use clap::ValueEnum;
 use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_subscriber::EnvFilter;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 
+use crate::crash::RecentLogs;
+
 /// fallback filter used when `RUST_LOG` env is not set.
 const DEFAULT_LOG_LEVEL: &str = "salti=debug";
 /// base filename used for debug log output.
 const LOG_BASENAME: &str = "salti.log";
+/// number of rotated log files kept next to `--log-file`.
+const LOG_FILES_KEPT: usize = 5;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    #[default]
+    Pretty,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Trace => "trace",
+            Self::Debug => "debug",
+            Self::Info => "info",
+            Self::Warn => "warn",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// where and how to write logs. without a path, logs go to `salti.log` in the working directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogOptions {
+    pub path: Option<PathBuf>,
+    pub level: Option<LogLevel>,
+    pub format: LogFormat,
+}
 
 fn create_log() -> Result<File> {
     for index in 0usize.. {
@@ -30,20 +72,66 @@ fn create_log() -> Result<File> {
     unreachable!("file error")
 }
 
-pub fn init_logging() -> Result<WorkerGuard> {
-    let log_file = create_log()?;
-    let (non_blocking, guard) = tracing_appender::non_blocking(log_file);
-    let env_filter =
-        EnvFilter::try_from_default_env().or_else(|_| EnvFilter::try_new(DEFAULT_LOG_LEVEL))?;
+/// daily rotating appender that keeps the last few files next to `path`
+fn create_rolling_log(path: &Path) -> Result<RollingFileAppender> {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .with_context(|| format!("invalid log file path: {}", path.display()))?;
+    let directory = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
 
-    tracing_subscriber::registry()
-        .with(env_filter)
+    RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(file_name)
+        .max_log_files(LOG_FILES_KEPT)
+        .build(directory)
+        .with_context(|| format!("failed to create log file in {}", directory.display()))
+}
+
+/// an explicit `--log-level` wins over `RUST_LOG`, which wins over the default level
+fn env_filter(level: Option<LogLevel>) -> Result<EnvFilter> {
+    let filter = match level {
+        Some(level) => EnvFilter::try_new(format!("salti={0},libmsa={0}", level.name()))?,
+        None => {
+            EnvFilter::try_from_default_env().or_else(|_| EnvFilter::try_new(DEFAULT_LOG_LEVEL))?
+        }
+    };
+    Ok(filter)
+}
+
+pub fn init_logging(options: &LogOptions) -> Result<WorkerGuard> {
+    let (non_blocking, guard) = match options.path.as_deref() {
+        Some(path) => tracing_appender::non_blocking(create_rolling_log(path)?),
+        None => tracing_appender::non_blocking(create_log()?),
+    };
+    // recent lines are also kept in memory so crash reports can include them
+    let registry = tracing_subscriber::registry()
+        .with(env_filter(options.level)?)
         .with(
             tracing_subscriber::fmt::layer()
                 .with_ansi(false)
-                .with_writer(non_blocking),
-        )
-        .try_init()?;
+                .with_writer(RecentLogs),
+        );
+
+    match options.format {
+        LogFormat::Pretty => registry
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .with_ansi(false)
+                    .with_writer(non_blocking),
+            )
+            .try_init()?,
+        LogFormat::Json => registry
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .json()
+                    .with_writer(non_blocking),
+            )
+            .try_init()?,
+    }
 
     Ok(guard)
 }
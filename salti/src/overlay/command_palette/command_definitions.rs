@@ -1,11 +1,34 @@
 use super::command_runners::{
-    run_check_update, run_clear_filter, run_clear_reference, run_consensus_method, run_diff_mode,
-    run_filter_gaps, run_filter_rows, run_jump_position, run_jump_sequence, run_load_alignment,
-    run_pin_sequence, run_quit, run_set_active_type, run_set_reference, run_theme,
-    run_toggle_translation, run_translation_frame, run_unpin_sequence,
+    run_adjust_consensus, run_append_alignment, run_blast_lite, run_browse_files,
+    run_cancel_export, run_check_update, run_clear_cache, run_clear_filter, run_clear_highlights,
+    run_clear_mask, run_clear_reference, run_cluster, run_color_ids, run_color_style,
+    run_composition, run_consensus_method, run_consensus_scope, run_convert_position,
+    run_copy_locator, run_copy_selection, run_diff_ambiguity, run_diff_mode, run_diff_options,
+    run_dnds, run_export_composition, run_export_consensus, run_export_conservation,
+    run_export_snp_dists, run_extract_region, run_filter_gaps, run_filter_rows, run_find_orfs,
+    run_find_primer, run_generate_report, run_group_by_indels, run_hide_sequence,
+    run_highlight_selection, run_input_coalescing, run_inspect, run_jump_back, run_jump_forward,
+    run_jump_partition, run_jump_position, run_jump_sequence, run_list_marks, run_list_orf_issues,
+    run_list_variable_columns, run_load_alignment, run_load_mask, run_load_partitions,
+    run_next_gap_column, run_next_partition, run_paste_alignment, run_pin_matching,
+    run_pin_move_down, run_pin_move_up, run_pin_sequence, run_precompute_stats, run_quit,
+    run_realign_selection, run_restore_alignment, run_save_as, run_save_mask, run_selection_stats,
+    run_sequence_palette, run_set_active_type, run_set_density, run_set_error_feedback,
+    run_set_fast_render, run_set_fps, run_set_genetic_code, run_set_id_truncation,
+    run_set_id_width, run_set_layout, run_set_mode, run_set_reference, run_set_row_numbers,
+    run_set_ruler, run_show_hidden, run_show_last_error, run_show_restriction_sites,
+    run_show_ungapped, run_theme, run_toggle_cluster, run_toggle_consensus_translation,
+    run_toggle_conservation_emphasis, run_toggle_crosshair, run_toggle_depth_track,
+    run_toggle_follow_tail, run_toggle_inserts, run_toggle_perf_hud, run_toggle_position_gutter,
+    run_toggle_quality, run_toggle_translation, run_toggle_variable_only, run_tool,
+    run_track_sequence, run_translation_frame, run_translation_gap_mode, run_uncluster,
+    run_unpin_all, run_unpin_sequence, run_zoom_in, run_zoom_out,
 };
 use super::command_spec::{PaletteCommand, StaticCommand, TypableCommand};
 use super::completers;
+use super::ranges;
+use super::validators;
+use crate::core::lookups::GENETIC_CODE_KEYS;
 
 /// Defines all commands available in the command palette.
 ///
@@ -18,31 +41,111 @@ use super::completers;
 /// - `aliases`: alternative names that can also be used to invoke the command
 /// - `completer`: a function that provides autocompletion suggestions for the command's argument
 ///   These are only used for typable commands.
+/// - `validator`: an optional check run as the argument is typed, shown as an ok/error hint
+///   above the completions. These are defined in `overlay/command_palette/validators`
 /// - `run`: the function that is called to execute the command. These are defined in
 ///   `overlay/command_palette/command_runners`
 pub(super) const COMMAND_SPECS: &[PaletteCommand] = &[
     PaletteCommand::Typable(TypableCommand {
         name: "jump-position",
-        help_text: "Jump to an alignment position (1 based), skipping forward to the next visible column if needed.",
+        help_text: "Jump to an alignment position (1 based), a range to centre (100-200, add 'highlight' to mark it), an offset (+500, -500) or a percentage (50%).",
         aliases: &["jp"],
         completer: None,
         static_candidates: &[],
+        validator: Some(validators::position),
+        range: Some(ranges::position),
         run: run_jump_position,
     }),
     PaletteCommand::Typable(TypableCommand {
         name: "jump-sequence",
-        help_text: "Jump to a sequence by name.",
+        help_text: "Jump to a sequence by name, by number (#42), or to the first name matching a glob or regex.",
         aliases: &["js"],
-        completer: Some(completers::sequences),
+        completer: Some(completers::numbered_sequences),
         static_candidates: &[],
+        validator: Some(validators::jump_sequence),
+        range: None,
         run: run_jump_sequence,
     }),
+    PaletteCommand::Static(StaticCommand {
+        name: "next-gap-column",
+        help_text: "Jump to the next column that is a gap in every visible row.",
+        aliases: &[],
+        run: run_next_gap_column,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "convert-position",
+        help_text: "Convert a position between alignment column, reference and sequence coordinates. Optional arguments: column, reference or sequence for what the position counts, and a sequence to report.",
+        aliases: &[],
+        completer: None,
+        static_candidates: &[],
+        validator: None,
+        range: None,
+        run: run_convert_position,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "show-ungapped",
+        help_text: "Show a sequence without gaps in a pane under the consensus, highlighting the residues in view. Without an argument the pane is closed.",
+        aliases: &[],
+        completer: Some(completers::sequences),
+        static_candidates: &[],
+        validator: None,
+        range: None,
+        run: run_show_ungapped,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "track-sequence",
+        help_text: "Show a sequence's ungapped position at the current column in the status bar. Without an argument tracking stops.",
+        aliases: &[],
+        completer: Some(completers::sequences),
+        static_candidates: &[],
+        validator: None,
+        range: None,
+        run: run_track_sequence,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "list-marks",
+        help_text: "List the marks set with m<letter>. Choosing one jumps to it.",
+        aliases: &["marks"],
+        run: run_list_marks,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "inspect",
+        help_text: "Show length, gap, ambiguity, composition and difference statistics for a sequence.",
+        aliases: &[],
+        completer: Some(completers::sequences),
+        static_candidates: &[],
+        validator: Some(validators::sequence),
+        range: None,
+        run: run_inspect,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "composition",
+        help_text: "Show residue composition of every visible sequence, one sequence, or all sequences matching a glob or regex. Codon usage is added for nucleotide alignments with translation on.",
+        aliases: &[],
+        completer: Some(completers::sequences),
+        static_candidates: &[],
+        validator: None,
+        range: None,
+        run: run_composition,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "dnds",
+        help_text: "Estimate dN/dS (Nei-Gojobori) between the reference and a sequence in the translation frame, over the whole alignment or, with visible, the visible columns.",
+        aliases: &["kaks"],
+        completer: Some(completers::sequences),
+        static_candidates: &[],
+        validator: None,
+        range: None,
+        run: run_dnds,
+    }),
     PaletteCommand::Typable(TypableCommand {
         name: "pin-sequence",
         help_text: "Pin a sequence to the top of the alignment pane.",
         aliases: &[],
         completer: Some(completers::sequences),
         static_candidates: &[],
+        validator: Some(validators::sequence),
+        range: None,
         run: run_pin_sequence,
     }),
     PaletteCommand::Typable(TypableCommand {
@@ -51,36 +154,164 @@ pub(super) const COMMAND_SPECS: &[PaletteCommand] = &[
         aliases: &[],
         completer: Some(completers::pinned_sequences),
         static_candidates: &[],
+        validator: None,
+        range: None,
         run: run_unpin_sequence,
     }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "pin-move-up",
+        help_text: "Move a pinned sequence up one place in the pinned group (Alt+Up over a pinned row).",
+        aliases: &[],
+        completer: Some(completers::pinned_sequences),
+        static_candidates: &[],
+        validator: None,
+        range: None,
+        run: run_pin_move_up,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "pin-move-down",
+        help_text: "Move a pinned sequence down one place in the pinned group (Alt+Down over a pinned row).",
+        aliases: &[],
+        completer: Some(completers::pinned_sequences),
+        static_candidates: &[],
+        validator: None,
+        range: None,
+        run: run_pin_move_down,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "pin-matching",
+        help_text: "Pin every visible sequence whose name matches a regular expression.",
+        aliases: &[],
+        completer: Some(completers::filter_matches),
+        static_candidates: &[],
+        validator: None,
+        range: None,
+        run: run_pin_matching,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "unpin-all",
+        help_text: "Remove every sequence from the pinned group.",
+        aliases: &[],
+        run: run_unpin_all,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "hide-sequence",
+        help_text: "Hide a sequence from the alignment pane.",
+        aliases: &[],
+        completer: Some(completers::sequences),
+        static_candidates: &[],
+        validator: Some(validators::sequence),
+        range: None,
+        run: run_hide_sequence,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "show-hidden",
+        help_text: "Show all hidden sequences again.",
+        aliases: &[],
+        run: run_show_hidden,
+    }),
     PaletteCommand::Typable(TypableCommand {
         name: "filter-rows",
         help_text: "Filter sequences by regular expression.",
         aliases: &[],
         completer: Some(completers::filter_matches),
         static_candidates: &[],
+        validator: Some(validators::regex),
+        range: None,
         run: run_filter_rows,
     }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "cluster",
+        help_text: "Group visible sequences at or above an identity percentage and collapse each group to its longest sequence.",
+        aliases: &[],
+        completer: None,
+        static_candidates: &["90", "95", "97", "99", "100"],
+        validator: Some(validators::percentage),
+        range: Some(ranges::identity),
+        run: run_cluster,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "group-by-indels",
+        help_text: "Group sequences whose gaps fall in the same columns, over the visible columns or the whole alignment.",
+        aliases: &[],
+        completer: None,
+        static_candidates: &["visible", "all"],
+        validator: None,
+        range: None,
+        run: run_group_by_indels,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "toggle-cluster",
+        help_text: "Expand or collapse the cluster represented by a sequence.",
+        aliases: &[],
+        completer: Some(completers::sequences),
+        static_candidates: &[],
+        validator: Some(validators::sequence),
+        range: None,
+        run: run_toggle_cluster,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "uncluster",
+        help_text: "Remove all clusters and show their sequences again.",
+        aliases: &[],
+        run: run_uncluster,
+    }),
     PaletteCommand::Static(StaticCommand {
         name: "clear-filter",
         help_text: "Clear all active filters.",
         aliases: &[],
         run: run_clear_filter,
     }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "save-mask",
+        help_text: "Save the columns hidden by masks and column filters as 1-based start-end intervals.",
+        aliases: &[],
+        completer: Some(completers::filename),
+        static_candidates: &[],
+        validator: None,
+        range: None,
+        run: run_save_mask,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "load-mask",
+        help_text: "Hide the columns listed in an intervals file written by save-mask, replacing any loaded mask.",
+        aliases: &[],
+        completer: Some(completers::filename),
+        static_candidates: &[],
+        validator: None,
+        range: None,
+        run: run_load_mask,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "clear-mask",
+        help_text: "Show the columns hidden by load-mask again.",
+        aliases: &[],
+        run: run_clear_mask,
+    }),
     PaletteCommand::Typable(TypableCommand {
         name: "filter-gaps",
         help_text: "Hide columns with a gap percentage above the given threshold. Use 0 to disable it.",
         aliases: &[],
         completer: None,
         static_candidates: &["0", "5", "10", "25", "50"],
+        validator: Some(validators::percentage),
+        range: Some(ranges::gap_percentage),
         run: run_filter_gaps,
     }),
+    PaletteCommand::Static(StaticCommand {
+        name: "toggle-inserts",
+        help_text: "Cycle A2M insert columns (lowercase or '.' in every row) between shown, hidden and compressed.",
+        aliases: &[],
+        run: run_toggle_inserts,
+    }),
     PaletteCommand::Typable(TypableCommand {
         name: "set-reference",
         help_text: "Set the reference sequence used for diffs.",
         aliases: &[],
         completer: Some(completers::sequences),
         static_candidates: &[],
+        validator: Some(validators::sequence),
+        range: None,
         run: run_set_reference,
     }),
     PaletteCommand::Static(StaticCommand {
@@ -89,12 +320,224 @@ pub(super) const COMMAND_SPECS: &[PaletteCommand] = &[
         aliases: &[],
         run: run_clear_reference,
     }),
+    PaletteCommand::Static(StaticCommand {
+        name: "copy-selection",
+        help_text: "Copy the selected region to the clipboard as a FASTA sub-alignment.",
+        aliases: &[],
+        run: run_copy_selection,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "copy-locator",
+        help_text: "Copy a salti:// link to the current position, reference and filter. Pass it to salti to open the same view.",
+        aliases: &[],
+        run: run_copy_locator,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "selection-stats",
+        help_text: "Show identity and gap statistics for the selected region.",
+        aliases: &[],
+        run: run_selection_stats,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "highlight-selection",
+        help_text: "Keep the selected region highlighted after the selection is cleared.",
+        aliases: &[],
+        run: run_highlight_selection,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "clear-highlights",
+        help_text: "Remove all region highlights.",
+        aliases: &[],
+        run: run_clear_highlights,
+    }),
     PaletteCommand::Static(StaticCommand {
         name: "toggle-translate",
         help_text: "Toggle the translation view.",
         aliases: &[],
         run: run_toggle_translation,
     }),
+    PaletteCommand::Static(StaticCommand {
+        name: "list-orf-issues",
+        help_text: "List premature stop codons and frameshifting gaps near the translated window.",
+        aliases: &[],
+        run: run_list_orf_issues,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "find-orfs",
+        help_text: "List open reading frames in the reference or consensus. Optional minimum length in codons (default 100).",
+        aliases: &[],
+        completer: None,
+        static_candidates: &[],
+        validator: None,
+        range: Some(ranges::orf_codons),
+        run: run_find_orfs,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "list-variable-columns",
+        help_text: "Rank the most variable columns of the whole alignment by entropy; Enter jumps to one. Optional number of columns (default 50).",
+        aliases: &["hotspots"],
+        completer: None,
+        static_candidates: &[],
+        validator: None,
+        range: Some(ranges::variable_columns),
+        run: run_list_variable_columns,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "find-primer",
+        help_text: "Find the best binding site of an IUPAC primer or probe in every sequence, on both strands. Optional maximum mismatches (default 3).",
+        aliases: &[],
+        completer: None,
+        static_candidates: &[],
+        validator: None,
+        range: Some(ranges::primer_mismatches),
+        run: run_find_primer,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "blast-lite",
+        help_text: "Score every sequence against a query fragment by shared words and list the closest matches, jumping to the best one.",
+        aliases: &[],
+        completer: None,
+        static_candidates: &[],
+        validator: None,
+        range: None,
+        run: run_blast_lite,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "show-restriction-sites",
+        help_text: "Mark recognition sites of comma separated enzymes on the ruler, using the reference or consensus. Leave empty to clear them.",
+        aliases: &[],
+        completer: Some(completers::restriction_enzymes),
+        static_candidates: &[],
+        validator: None,
+        range: None,
+        run: run_show_restriction_sites,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "toggle-variable-only",
+        help_text: "Show only columns where the visible sequences disagree, or differ from the reference if one is set.",
+        aliases: &[],
+        run: run_toggle_variable_only,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "toggle-consensus-translation",
+        help_text: "Toggle a consensus pane row with the consensus translated in the current frame (DNA only).",
+        aliases: &[],
+        run: run_toggle_consensus_translation,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "toggle-follow-tail",
+        help_text: "Toggle keeping the newest rows in view while following a growing file with --follow.",
+        aliases: &[],
+        run: run_toggle_follow_tail,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "toggle-quality",
+        help_text: "Toggle shading residue backgrounds by per-base quality (FASTQ input only).",
+        aliases: &[],
+        run: run_toggle_quality,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "toggle-conservation-emphasis",
+        help_text: "Toggle fading residue colours in conserved columns so variable columns stand out.",
+        aliases: &[],
+        run: run_toggle_conservation_emphasis,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "toggle-position-gutter",
+        help_text: "Toggle showing the position of the first visible column at the start of every alignment row.",
+        aliases: &[],
+        run: run_toggle_position_gutter,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "back",
+        help_text: "Return to the position before the last jump (Ctrl+o).",
+        aliases: &[],
+        run: run_jump_back,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "forward",
+        help_text: "Undo a back, returning to the next position in the jump history (Ctrl+i or Tab).",
+        aliases: &[],
+        run: run_jump_forward,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "zoom-out",
+        help_text: "Step to a denser view of the alignment: normal, compact, then overview (-).",
+        aliases: &[],
+        run: run_zoom_out,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "zoom-in",
+        help_text: "Step back towards normal density (+).",
+        aliases: &[],
+        run: run_zoom_in,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "toggle-depth-track",
+        help_text: "Toggle a bar chart under the ruler of how many visible rows hold a residue in each column.",
+        aliases: &[],
+        run: run_toggle_depth_track,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "toggle-crosshair",
+        help_text: "Toggle a keyboard-driven crosshair cursor. Arrow keys move it and Enter sets the reference to its row.",
+        aliases: &[],
+        run: run_toggle_crosshair,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "cancel-export",
+        help_text: "Cancel the running consensus or conservation export.",
+        aliases: &[],
+        run: run_cancel_export,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "precompute-stats",
+        help_text: "Compute consensus and conservation for the whole alignment in the background so scrolling never waits on stats. Pass cancel to stop it.",
+        aliases: &[],
+        completer: None,
+        static_candidates: &["cancel"],
+        validator: None,
+        range: None,
+        run: run_precompute_stats,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "clear-cache",
+        help_text: "Delete the full-length stats cached on disk for every file opened.",
+        aliases: &[],
+        run: run_clear_cache,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "toggle-perf-hud",
+        help_text: "Toggle a HUD showing frame time, event loop latency, memory use, pending jobs and stats cache coverage.",
+        aliases: &[],
+        run: run_toggle_perf_hud,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "set-fps",
+        help_text: "Set the maximum redraw rate in frames per second (1-240).",
+        aliases: &[],
+        completer: None,
+        static_candidates: &[],
+        validator: Some(validators::fps),
+        range: Some(ranges::fps),
+        run: run_set_fps,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "set-input-coalescing",
+        help_text: "Merge repeated scrolls between redraws into a single larger scroll.",
+        aliases: &[],
+        completer: None,
+        static_candidates: &["on", "off"],
+        validator: None,
+        range: None,
+        run: run_input_coalescing,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "show-last-error",
+        help_text: "Show the full text of the most recent error again.",
+        aliases: &[],
+        run: run_show_last_error,
+    }),
     PaletteCommand::Static(StaticCommand {
         name: "check-update",
         help_text: "Check crates.io for a newer salti version.",
@@ -113,32 +556,345 @@ pub(super) const COMMAND_SPECS: &[PaletteCommand] = &[
         aliases: &[],
         completer: None,
         static_candidates: &["off", "reference", "consensus"],
+        validator: None,
+        range: None,
         run: run_diff_mode,
     }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "set-diff-ambiguity",
+        help_text: "Choose whether diff mode treats IUPAC codes as matching their bases (strict or iupac).",
+        aliases: &[],
+        completer: None,
+        static_candidates: &["strict", "iupac"],
+        validator: None,
+        range: None,
+        run: run_diff_ambiguity,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "set-diff-options",
+        help_text: "Treat case or end-gap differences as matches in diff mode (comma-separated, or none).",
+        aliases: &[],
+        completer: None,
+        static_candidates: &[
+            "none",
+            "ignore-case",
+            "ignore-end-gaps",
+            "ignore-case,ignore-end-gaps",
+        ],
+        validator: None,
+        range: None,
+        run: run_diff_options,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "set-layout",
+        help_text: "Switch between panned and wrapped alignment layout.",
+        aliases: &[],
+        completer: None,
+        static_candidates: &["panned", "wrapped"],
+        validator: None,
+        range: None,
+        run: run_set_layout,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "set-mode",
+        help_text: "Switch between read-only and edit mode; edits need edit mode.",
+        aliases: &[],
+        completer: None,
+        static_candidates: &["read-only", "edit"],
+        validator: None,
+        range: None,
+        run: run_set_mode,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "set-id-width",
+        help_text: "Set the sequence name pane width in columns, or auto for a fifth of the screen.",
+        aliases: &[],
+        completer: None,
+        static_candidates: &["auto"],
+        validator: None,
+        range: None,
+        run: run_set_id_width,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "set-id-truncation",
+        help_text: "Choose how long sequence names fit the name pane: clip (scrollable), or an ellipsis at the start, middle or end.",
+        aliases: &[],
+        completer: None,
+        static_candidates: &["clip", "start", "middle", "end"],
+        validator: None,
+        range: None,
+        run: run_set_id_truncation,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "set-row-numbers",
+        help_text: "Show file order, display order, both or no row numbers before sequence names.",
+        aliases: &[],
+        completer: None,
+        static_candidates: &["off", "file", "display", "both"],
+        validator: None,
+        range: None,
+        run: run_set_row_numbers,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "color-ids",
+        help_text: "Colour sequence names matching a regex, e.g. `^hCoV blue` or `outgroup #ff5555`. Leave empty to remove every rule.",
+        aliases: &[],
+        completer: None,
+        static_candidates: &[],
+        validator: None,
+        range: None,
+        run: run_color_ids,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "set-ruler",
+        help_text: "Set the ruler tick spacing (5, 10, 20 or codon) and whether it counts from 1 or 0.",
+        aliases: &[],
+        completer: None,
+        static_candidates: &["5", "10", "20", "codon", "1-based", "0-based"],
+        validator: None,
+        range: None,
+        run: run_set_ruler,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "set-error-feedback",
+        help_text: "Ring the terminal bell or flash the input line when an action fails: off, bell or flash.",
+        aliases: &[],
+        completer: None,
+        static_candidates: &["off", "bell", "flash"],
+        validator: None,
+        range: None,
+        run: run_set_error_feedback,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "set-fast-render",
+        help_text: "Cache formatted alignment rows between frames and only redraw rows that changed, leaving out ORF issue marks: on, off, or auto for panes 300+ columns wide.",
+        aliases: &[],
+        completer: None,
+        static_candidates: &["off", "auto", "on"],
+        validator: None,
+        range: None,
+        run: run_set_fast_render,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "set-density",
+        help_text: "Draw one alignment row per line (normal), two rows per line as coloured half-blocks (compact), or 2x4 blocks per cell as braille coloured by consensus agreement (overview).",
+        aliases: &[],
+        completer: None,
+        static_candidates: &["normal", "compact", "overview"],
+        validator: None,
+        range: None,
+        run: run_set_density,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "export-consensus",
+        help_text: "Write the consensus of the whole alignment to a FASTA file. Optional arguments: a consensus method and a minimum support percentage, below which columns are written as N (or X).",
+        aliases: &[],
+        completer: Some(completers::filename),
+        static_candidates: &[],
+        validator: None,
+        range: Some(ranges::consensus_threshold),
+        run: run_export_consensus,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "export-conservation",
+        help_text: "Write per-column conservation scores for the whole alignment. The format (csv or wig) follows the file extension unless given as a second argument.",
+        aliases: &[],
+        completer: Some(completers::filename),
+        static_candidates: &[],
+        validator: None,
+        range: None,
+        run: run_export_conservation,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "export-snp-dists",
+        help_text: "Write pairwise SNP distances between all visible sequences as a TSV matrix. Sites with a gap or N are skipped unless a second argument of gaps or none is given.",
+        aliases: &[],
+        completer: Some(completers::filename),
+        static_candidates: &[],
+        validator: None,
+        range: None,
+        run: run_export_snp_dists,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "export-composition",
+        help_text: "Write residue composition, and codon usage when translation is on, as CSV. Covers every visible sequence unless a sequence or name pattern is given after the path.",
+        aliases: &[],
+        completer: Some(completers::filename),
+        static_candidates: &[],
+        validator: None,
+        range: None,
+        run: run_export_composition,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "generate-report",
+        help_text: "Write an HTML report with overview stats, a conservation plot, the consensus, variants against the reference and coloured snapshots of the highlighted regions.",
+        aliases: &[],
+        completer: Some(completers::filename),
+        static_candidates: &[],
+        validator: None,
+        range: None,
+        run: run_generate_report,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "save-as",
+        help_text: "Save the visible sequences and columns as fasta, clustal, phylip, stockholm or nexus. The format follows the file extension unless given after the path; add full to save every sequence and column.",
+        aliases: &["write"],
+        completer: Some(completers::filename),
+        static_candidates: &[],
+        validator: None,
+        range: None,
+        run: run_save_as,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "extract-region",
+        help_text: "Replace the alignment with columns <start>-<end> (1 based, inclusive) of the visible sequences.",
+        aliases: &[],
+        completer: None,
+        static_candidates: &[],
+        validator: Some(validators::region),
+        range: Some(ranges::region),
+        run: run_extract_region,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "restore-alignment",
+        help_text: "Return to the alignment that was open before the last extract-region, append-alignment or realign-selection.",
+        aliases: &[],
+        run: run_restore_alignment,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "append-alignment",
+        help_text: "Concatenate the columns of another alignment file with the same sequence IDs.",
+        aliases: &["append"],
+        completer: Some(completers::filename),
+        static_candidates: &[],
+        validator: None,
+        range: None,
+        run: run_append_alignment,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "realign-selection",
+        help_text: "Realign the selected sequences over the selected columns with mafft or muscle, if installed.",
+        aliases: &[],
+        completer: None,
+        static_candidates: &["mafft", "muscle"],
+        validator: None,
+        range: None,
+        run: run_realign_selection,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "tool",
+        help_text: "Run an external command from the [tools] config table on the selection, or the visible columns, and show its output.",
+        aliases: &[],
+        completer: Some(completers::tools),
+        static_candidates: &[],
+        validator: None,
+        range: None,
+        run: run_tool,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "load-partitions",
+        help_text: "Load partition boundaries from a RAxML or NEXUS charset partition file.",
+        aliases: &[],
+        completer: Some(completers::filename),
+        static_candidates: &[],
+        validator: None,
+        range: None,
+        run: run_load_partitions,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "next-partition",
+        help_text: "Jump to the start of the next partition.",
+        aliases: &[],
+        run: run_next_partition,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "jump-partition",
+        help_text: "Jump to the start of a partition by name.",
+        aliases: &[],
+        completer: Some(completers::partitions),
+        static_candidates: &[],
+        validator: None,
+        range: None,
+        run: run_jump_partition,
+    }),
     PaletteCommand::Typable(TypableCommand {
         name: "load-alignment",
         help_text: "Load an alignment file using a file path argument.",
         aliases: &["load"],
         completer: Some(completers::filename),
         static_candidates: &[],
+        validator: None,
+        range: None,
         run: run_load_alignment,
     }),
+    PaletteCommand::Static(StaticCommand {
+        name: "browse-files",
+        help_text: "Open a file browser to pick an alignment from the current directory.",
+        aliases: &["browse"],
+        run: run_browse_files,
+    }),
+    PaletteCommand::Static(StaticCommand {
+        name: "paste-alignment",
+        help_text: "Paste a FASTA or NEXUS alignment from the clipboard and browse it without a file.",
+        aliases: &["paste"],
+        run: run_paste_alignment,
+    }),
     PaletteCommand::Typable(TypableCommand {
         name: "set-consensus-method",
         help_text: "Set the consensus method used for the consensus row.",
         aliases: &[],
         completer: None,
         static_candidates: &["majority", "majority-non-gap"],
+        validator: None,
+        range: None,
         run: run_consensus_method,
     }),
+    PaletteCommand::Static(StaticCommand {
+        name: "adjust-consensus",
+        help_text: "Adjust the consensus threshold with Left/Right and watch the consensus row update; columns below it show an ambiguity code.",
+        aliases: &[],
+        run: run_adjust_consensus,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "set-consensus-scope",
+        help_text: "Compute consensus and conservation over all sequences or only the visible ones. Hiding rows updates visible stats in place.",
+        aliases: &[],
+        completer: None,
+        static_candidates: &["all", "visible"],
+        validator: None,
+        range: None,
+        run: run_consensus_scope,
+    }),
     PaletteCommand::Typable(TypableCommand {
         name: "set-translation-frame",
         help_text: "Set the translation frame used for translation view.",
         aliases: &[],
         completer: None,
         static_candidates: &["1", "2", "3"],
+        validator: None,
+        range: Some(ranges::frame),
         run: run_translation_frame,
     }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "set-translation-gap-mode",
+        help_text: "Choose how gaps inside codons are translated: read codons column by column, skip gaps and translate the ungapped sequence, or mark codons broken by a gap as frameshifts.",
+        aliases: &[],
+        completer: None,
+        static_candidates: &["columnwise", "skip-gaps", "gap-as-frameshift"],
+        validator: None,
+        range: None,
+        run: run_translation_gap_mode,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "set-genetic-code",
+        help_text: "Set the NCBI genetic code used for translation, ORFs and codon analyses, by table number or name.",
+        aliases: &[],
+        completer: None,
+        static_candidates: GENETIC_CODE_KEYS,
+        validator: Some(validators::genetic_code),
+        range: None,
+        run: run_set_genetic_code,
+    }),
     PaletteCommand::Typable(TypableCommand {
         name: "set-theme",
         help_text: "Set the active theme.",
@@ -150,14 +906,38 @@ pub(super) const COMMAND_SPECS: &[PaletteCommand] = &[
             "tokyo-night",
             "terminal-default",
         ],
+        validator: None,
+        range: None,
         run: run_theme,
     }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "set-sequence-palette",
+        help_text: "Set residue colours independently of the theme.",
+        aliases: &[],
+        completer: None,
+        static_candidates: &["theme", "colorblind", "high-contrast"],
+        validator: None,
+        range: None,
+        run: run_sequence_palette,
+    }),
+    PaletteCommand::Typable(TypableCommand {
+        name: "set-color-style",
+        help_text: "Paint residue colours as cell backgrounds, coloured letters or bold letters.",
+        aliases: &[],
+        completer: None,
+        static_candidates: &["background", "foreground", "bold"],
+        validator: None,
+        range: None,
+        run: run_color_style,
+    }),
     PaletteCommand::Typable(TypableCommand {
         name: "set-sequence-type",
         help_text: "Override sequence type detection for rendering.",
         aliases: &[],
         completer: None,
-        static_candidates: &["dna", "protein", "generic"],
+        static_candidates: &["dna", "rna", "protein", "generic"],
+        validator: None,
+        range: None,
         run: run_set_active_type,
     }),
 ];
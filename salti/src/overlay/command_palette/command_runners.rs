@@ -1,10 +1,20 @@
 use crate::command::Command;
+use crate::core::liftover::PositionSource;
+use crate::core::lookups::GeneticCode;
+use crate::core::orf::DEFAULT_MIN_ORF_CODONS;
+use crate::core::primer::DEFAULT_MAX_PRIMER_MISMATCHES;
+use crate::core::restriction;
+use crate::core::variability::DEFAULT_VARIABLE_COLUMNS;
+use crate::ui::sequence_id_pane::IdColorRule;
 use anyhow::format_err;
 use tracing::warn;
 
+use crate::app::{MAX_RENDER_FPS, MIN_RENDER_FPS};
+
 use super::input::CommandPaletteState;
 use super::input::VisibleSequence;
-use super::utils::parse_argument;
+use super::ranges::last_position;
+use super::utils::{expand_tilde, parse_argument, parse_arguments};
 
 fn ensure_no_argument(arguments: &str) -> anyhow::Result<()> {
     if parse_argument(arguments).is_some() {
@@ -56,15 +66,174 @@ pub(super) fn run_toggle_translation(
 ) -> anyhow::Result<Command> {
     run_command("toggle-translate", arguments, || {
         ensure_no_argument(arguments)?;
-        if state.active_type != libmsa::AlignmentType::Dna {
+        if !state.active_type.supports_translation() {
             return Err(format_err!(
-                "toggle-translate is only available for DNA sequences",
+                "toggle-translate is only available for DNA or RNA sequences",
             ));
         }
         Ok(Command::ToggleTranslationView)
     })
 }
 
+pub(super) fn run_toggle_inserts(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("toggle-inserts", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::CycleInsertColumns)
+    })
+}
+
+pub(super) fn run_toggle_variable_only(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("toggle-variable-only", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::ToggleVariableOnly)
+    })
+}
+
+pub(super) fn run_toggle_consensus_translation(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("toggle-consensus-translation", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::ToggleConsensusTranslation)
+    })
+}
+
+pub(super) fn run_list_marks(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("list-marks", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::ListMarks)
+    })
+}
+
+pub(super) fn run_toggle_follow_tail(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("toggle-follow-tail", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::ToggleFollowTail)
+    })
+}
+
+pub(super) fn run_toggle_quality(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("toggle-quality", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::ToggleQualityShading)
+    })
+}
+
+pub(super) fn run_toggle_position_gutter(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("toggle-position-gutter", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::TogglePositionGutter)
+    })
+}
+
+pub(super) fn run_jump_back(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("back", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::JumpBack)
+    })
+}
+
+pub(super) fn run_jump_forward(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("forward", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::JumpForward)
+    })
+}
+
+pub(super) fn run_toggle_depth_track(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("toggle-depth-track", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::ToggleDepthTrack)
+    })
+}
+
+pub(super) fn run_toggle_conservation_emphasis(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("toggle-conservation-emphasis", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::ToggleConservationEmphasis)
+    })
+}
+
+pub(super) fn run_toggle_perf_hud(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("toggle-perf-hud", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::TogglePerfHud)
+    })
+}
+
+pub(super) fn run_set_fps(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("set-fps", arguments, || {
+        let value = require_argument(arguments)?;
+        parse_fps(&value)
+    })
+}
+
+/// frame rate typed for `set-fps`, rejected outside the accepted range
+pub(super) fn parse_fps(value: &str) -> anyhow::Result<Command> {
+    let fps = value
+        .parse::<u16>()
+        .map_err(|_| format_err!("Invalid argument: expected a positive integer"))?;
+    if !(MIN_RENDER_FPS..=MAX_RENDER_FPS).contains(&fps) {
+        return Err(format_err!(
+            "frame rate must be {MIN_RENDER_FPS}..{MAX_RENDER_FPS}"
+        ));
+    }
+    Ok(Command::SetFps(fps))
+}
+
+pub(super) fn run_input_coalescing(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("set-input-coalescing", arguments, || {
+        let value = require_argument(arguments)?;
+        let enabled = match value.as_str() {
+            "on" => true,
+            "off" => false,
+            _ => return Err(format_err!("Invalid argument: expected on or off")),
+        };
+        Ok(Command::SetInputCoalescing(enabled))
+    })
+}
+
+pub(super) fn run_toggle_crosshair(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("toggle-crosshair", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::ToggleCrosshair)
+    })
+}
+
 fn next_visible_column_index(visible_columns: &[usize], absolute_target: usize) -> Option<usize> {
     match visible_columns.binary_search(&absolute_target) {
         Ok(visible_index) => Some(visible_index),
@@ -98,38 +267,197 @@ pub(super) fn run_filter_gaps(_: &CommandPaletteState, arguments: &str) -> anyho
     })
 }
 
+pub(super) fn run_next_gap_column(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("next-gap-column", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::JumpToNextGapColumn)
+    })
+}
+
+/// where `jump-position` goes, parsed from its argument
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum JumpTarget {
+    /// 1-based alignment position
+    Position(usize),
+    /// 1-based inclusive range, `100-200`, highlighted when followed by `highlight`
+    Range {
+        start: usize,
+        end: usize,
+        highlight: bool,
+    },
+    /// columns to scroll by, `+500` or `-500`
+    Offset(isize),
+    /// share of the visible columns, `50%`
+    Percent(f32),
+}
+
+fn parse_positive(value: &str) -> anyhow::Result<usize> {
+    value
+        .parse::<usize>()
+        .ok()
+        .filter(|&position| position > 0)
+        .ok_or_else(|| format_err!("Invalid argument: expected a positive integer"))
+}
+
+pub(super) fn parse_jump_target(arguments: &str) -> anyhow::Result<JumpTarget> {
+    let mut words = arguments.split_whitespace();
+    let value = words
+        .next()
+        .ok_or_else(|| format_err!("Missing argument"))?;
+    let highlight = match words.next() {
+        None => false,
+        Some("highlight" | "hl") => true,
+        Some(word) => return Err(format_err!("Invalid argument: unexpected '{word}'")),
+    };
+    if words.next().is_some() {
+        return Err(format_err!("Invalid argument: too many arguments"));
+    }
+
+    let target = if let Some(percent) = value.strip_suffix('%') {
+        match percent.parse::<f32>() {
+            Ok(percent) if percent.is_finite() && (0.0..=100.0).contains(&percent) => {
+                JumpTarget::Percent(percent)
+            }
+            _ => {
+                return Err(format_err!(
+                    "Invalid argument: expected a percentage (0-100)"
+                ));
+            }
+        }
+    } else if let Some(offset) = value.strip_prefix('+') {
+        JumpTarget::Offset(parse_positive(offset)?.try_into()?)
+    } else if let Some(offset) = value.strip_prefix('-') {
+        JumpTarget::Offset(-isize::try_from(parse_positive(offset)?)?)
+    } else if let Some((start, end)) = value.split_once('-') {
+        let (start, end) = (parse_positive(start)?, parse_positive(end)?);
+        if start > end {
+            return Err(format_err!(
+                "Invalid argument: range start is after its end"
+            ));
+        }
+        JumpTarget::Range {
+            start,
+            end,
+            highlight,
+        }
+    } else {
+        JumpTarget::Position(parse_positive(value)?)
+    };
+    if highlight && !matches!(target, JumpTarget::Range { .. }) {
+        return Err(format_err!(
+            "Invalid argument: only ranges can be highlighted"
+        ));
+    }
+    Ok(target)
+}
+
 pub(super) fn run_jump_position(
     state: &CommandPaletteState,
     arguments: &str,
 ) -> anyhow::Result<Command> {
     run_command("jump-position", arguments, || {
-        let value = require_argument(arguments)?;
+        require_argument(arguments)?;
 
-        let Ok(position) = value.parse::<usize>() else {
-            return Err(format_err!("Invalid argument: expected a positive integer",));
-        };
-        if position == 0 {
-            return Err(format_err!("Invalid argument: expected a positive integer",));
+        let last = last_position(state);
+        match parse_jump_target(arguments)? {
+            JumpTarget::Position(position) | JumpTarget::Range { end: position, .. }
+                if position > last =>
+            {
+                Err(format_err!("position must be 1..{last}"))
+            }
+            JumpTarget::Position(position) => {
+                let Some(visible_col) =
+                    next_visible_column_index(&state.visible_columns, position - 1)
+                else {
+                    return Err(format_err!(
+                        "No visible column at or after the requested position",
+                    ));
+                };
+                Ok(Command::JumpToPosition(visible_col))
+            }
+            JumpTarget::Range {
+                start,
+                end,
+                highlight,
+            } => Ok(Command::JumpToRange {
+                start: start - 1,
+                end: end - 1,
+                highlight,
+            }),
+            JumpTarget::Offset(offset) if offset < 0 => Ok(Command::ScrollLeft {
+                amount: offset.unsigned_abs(),
+            }),
+            JumpTarget::Offset(offset) => Ok(Command::ScrollRight {
+                amount: offset.unsigned_abs(),
+            }),
+            JumpTarget::Percent(percent) => {
+                let Some(last) = state.visible_columns.len().checked_sub(1) else {
+                    return Err(format_err!("No visible columns"));
+                };
+                let visible_col = (last as f32 * percent / 100.0).round() as usize;
+                Ok(Command::JumpToPosition(visible_col))
+            }
         }
+    })
+}
 
-        let absolute_target = position - 1;
-        let Some(visible_col) = next_visible_column_index(&state.visible_columns, absolute_target)
-        else {
-            return Err(format_err!(
-                "No visible column at or after the requested position",
-            ));
-        };
+pub(super) fn run_next_partition(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("next-partition", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::JumpToNextPartition)
+    })
+}
+
+pub(super) fn run_jump_partition(
+    state: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("jump-partition", arguments, || {
+        let name = require_argument(arguments)?;
+        let partition = state
+            .partitions
+            .iter()
+            .find(|partition| partition.name == name)
+            .or_else(|| {
+                state
+                    .partitions
+                    .iter()
+                    .find(|partition| partition.name.eq_ignore_ascii_case(&name))
+            })
+            .ok_or_else(|| format_err!("No partition named '{name}'"))?;
+        let visible_col =
+            next_visible_column_index(&state.visible_columns, partition.columns.start)
+                .filter(|&visible_col| {
+                    partition
+                        .columns
+                        .contains(&state.visible_columns[visible_col])
+                })
+                .ok_or_else(|| format_err!("Partition '{name}' has no visible columns"))?;
 
         Ok(Command::JumpToPosition(visible_col))
     })
 }
 
 // this searches through the visible sequences to get the seq id - in the future might want to
-// consider a hashmap? would changes behaviour to last seq wins rather than first
+// consider a hashmap? would changes behaviour to last seq wins rather than first.
+// names win over numbers, so a sequence called "12" is still found by name; otherwise a number
+// is the 1-based position in the input file, as shown in the sequence name pane.
 fn lookup_sequence_id(sequences: &[VisibleSequence], sequence_name: &str) -> Option<usize> {
     sequences
         .iter()
         .find(|sequence| sequence.sequence_name.as_ref() == sequence_name)
+        .or_else(|| {
+            let sequence_id = sequence_name.parse::<usize>().ok()?.checked_sub(1)?;
+            sequences
+                .iter()
+                .find(|sequence| sequence.sequence_id == sequence_id)
+        })
         .map(|sequence| sequence.sequence_id)
 }
 
@@ -148,13 +476,225 @@ fn resolve_argument_to_sequence_id(
         .ok_or_else(|| format_err!("Sequence not found: {sequence_name}"))
 }
 
+/// a regex matching the whole name for globs such as `sample_*`, or `pattern` as a regex
+fn sequence_pattern_regexes(pattern: &str) -> impl Iterator<Item = regex::Regex> {
+    let glob = pattern.contains(['*', '?']).then(|| {
+        let body: String = pattern
+            .chars()
+            .map(|character| match character {
+                '*' => ".*".to_string(),
+                '?' => ".".to_string(),
+                _ => regex::escape(&character.to_string()),
+            })
+            .collect();
+        format!("^{body}$")
+    });
+    glob.into_iter()
+        .chain(std::iter::once(pattern.to_string()))
+        .filter_map(|pattern| regex::Regex::new(&pattern).ok())
+}
+
+/// resolves `#42` by number, then an exact name or number, then the first name matching a glob
+/// or regex, in view order
+pub(super) fn find_sequence<'a>(
+    sequences: &'a [VisibleSequence],
+    argument: &str,
+) -> Option<&'a VisibleSequence> {
+    if let Some(number) = argument.strip_prefix('#') {
+        let sequence_id = number.parse::<usize>().ok()?.checked_sub(1)?;
+        return sequences
+            .iter()
+            .find(|sequence| sequence.sequence_id == sequence_id);
+    }
+    if let Some(sequence_id) = lookup_sequence_id(sequences, argument) {
+        return sequences
+            .iter()
+            .find(|sequence| sequence.sequence_id == sequence_id);
+    }
+    sequence_pattern_regexes(argument).find_map(|regex| {
+        sequences
+            .iter()
+            .find(|sequence| regex.is_match(&sequence.sequence_name))
+    })
+}
+
+/// every sequence an argument names: `#42`, an exact name or number, or all names matching a
+/// glob or regex
+fn match_sequences(sequences: &[VisibleSequence], argument: &str) -> Vec<usize> {
+    if argument.starts_with('#') || lookup_sequence_id(sequences, argument).is_some() {
+        return find_sequence(sequences, argument)
+            .map(|sequence| sequence.sequence_id)
+            .into_iter()
+            .collect();
+    }
+    sequence_pattern_regexes(argument)
+        .map(|regex| {
+            sequences
+                .iter()
+                .filter(|sequence| regex.is_match(&sequence.sequence_name))
+                .map(|sequence| sequence.sequence_id)
+                .collect::<Vec<_>>()
+        })
+        .find(|rows| !rows.is_empty())
+        .unwrap_or_default()
+}
+
+fn composition_rows(
+    sequences: &[VisibleSequence],
+    argument: Option<String>,
+) -> anyhow::Result<Option<Vec<usize>>> {
+    argument
+        .map(|argument| {
+            let rows = match_sequences(sequences, &argument);
+            if rows.is_empty() {
+                return Err(format_err!("Sequence not found: {argument}"));
+            }
+            Ok(rows)
+        })
+        .transpose()
+}
+
+pub(super) fn run_composition(
+    state: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("composition", arguments, || {
+        let argument = parse_argument(arguments).filter(|argument| !argument.is_empty());
+        Ok(Command::Composition(composition_rows(
+            &state.selectable_sequences,
+            argument,
+        )?))
+    })
+}
+
+pub(super) fn run_export_composition(
+    state: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("export-composition", arguments, || {
+        let mut arguments = parse_arguments(arguments).into_iter();
+        let path = arguments
+            .next()
+            .map(|path| expand_tilde(&path))
+            .ok_or_else(|| format_err!("Expected 1 argument, got 0"))?;
+        let rows = composition_rows(&state.selectable_sequences, arguments.next())?;
+        if arguments.next().is_some() {
+            return Err(format_err!("Expected at most 2 arguments"));
+        }
+
+        Ok(Command::ExportComposition { path, rows })
+    })
+}
+
+pub(super) fn run_dnds(state: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("dnds", arguments, || {
+        let mut arguments = parse_arguments(arguments);
+        let visible = arguments
+            .last()
+            .is_some_and(|argument| argument == "visible");
+        if visible {
+            arguments.pop();
+        }
+        let argument = match arguments.as_slice() {
+            [] => return Err(format_err!("Expected 1 argument, got 0")),
+            [argument] => argument,
+            _ => return Err(format_err!("Expected a sequence and optionally visible")),
+        };
+        let sequence = find_sequence(&state.selectable_sequences, argument)
+            .ok_or_else(|| format_err!("Sequence not found: {argument}"))?;
+        Ok(Command::EstimateDnDs {
+            sequence: sequence.sequence_id,
+            visible,
+        })
+    })
+}
+
 pub(super) fn run_jump_sequence(
     state: &CommandPaletteState,
     arguments: &str,
 ) -> anyhow::Result<Command> {
     run_command("jump-sequence", arguments, || {
+        let argument = require_argument(arguments)?;
+        let sequence = find_sequence(&state.selectable_sequences, &argument)
+            .ok_or_else(|| format_err!("Sequence not found: {argument}"))?;
+        Ok(Command::JumpToSequence(sequence.sequence_id))
+    })
+}
+
+pub(super) fn run_convert_position(
+    state: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("convert-position", arguments, || {
+        let mut arguments = parse_arguments(arguments).into_iter();
+        let position = arguments
+            .next()
+            .ok_or_else(|| format_err!("Expected 1 argument, got 0"))?
+            .parse::<usize>()
+            .ok()
+            .filter(|&position| position > 0)
+            .ok_or_else(|| format_err!("Invalid argument: expected a positive integer"))?;
+        let mut from = None;
+        let mut sequence = None;
+        for argument in arguments {
+            if from.is_none()
+                && let Ok(source) = argument.parse::<PositionSource>()
+            {
+                from = Some(source);
+            } else if sequence.is_none() {
+                sequence = Some(
+                    lookup_sequence_id(&state.selectable_sequences, &argument)
+                        .ok_or_else(|| format_err!("Sequence not found: {argument}"))?,
+                );
+            } else {
+                return Err(format_err!("Expected at most a source and a sequence"));
+            }
+        }
+        let from = from.unwrap_or_default();
+        if from == PositionSource::Sequence && sequence.is_none() {
+            return Err(format_err!(
+                "Converting from a sequence position needs a sequence"
+            ));
+        }
+
+        Ok(Command::ConvertPosition {
+            position,
+            from,
+            sequence,
+        })
+    })
+}
+
+pub(super) fn run_show_ungapped(
+    state: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("show-ungapped", arguments, || {
+        if parse_argument(arguments).is_none() {
+            return Ok(Command::ShowUngapped(None));
+        }
+        let sequence_id = resolve_argument_to_sequence_id(&state.selectable_sequences, arguments)?;
+        Ok(Command::ShowUngapped(Some(sequence_id)))
+    })
+}
+
+pub(super) fn run_track_sequence(
+    state: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("track-sequence", arguments, || {
+        if parse_argument(arguments).is_none() {
+            return Ok(Command::TrackSequence(None));
+        }
+        let sequence_id = resolve_argument_to_sequence_id(&state.selectable_sequences, arguments)?;
+        Ok(Command::TrackSequence(Some(sequence_id)))
+    })
+}
+
+pub(super) fn run_inspect(state: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("inspect", arguments, || {
         let sequence_id = resolve_argument_to_sequence_id(&state.selectable_sequences, arguments)?;
-        Ok(Command::JumpToSequence(sequence_id))
+        Ok(Command::InspectSequence(sequence_id))
     })
 }
 
@@ -178,130 +718,1335 @@ pub(super) fn run_unpin_sequence(
     })
 }
 
-pub(super) fn run_filter_rows(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
-    run_command("filter-rows", arguments, || {
-        if arguments.is_empty() {
-            Ok(Command::ClearFilter)
-        } else {
-            Ok(Command::SetFilter(arguments.to_string()))
-        }
+pub(super) fn run_pin_move_up(
+    state: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("pin-move-up", arguments, || {
+        let abs_row = resolve_argument_to_sequence_id(&state.pinned_sequences, arguments)?;
+        Ok(Command::MovePinned {
+            abs_row,
+            offset: -1,
+        })
     })
 }
 
-pub(super) fn run_set_reference(
+pub(super) fn run_pin_move_down(
     state: &CommandPaletteState,
     arguments: &str,
 ) -> anyhow::Result<Command> {
-    run_command("set-reference", arguments, || {
-        let arg = require_argument(arguments)?;
-
-        let sequence_id = lookup_sequence_id(&state.selectable_sequences, arg.as_str())
-            .ok_or_else(|| format_err!("Sequence not found: {arg}"))?;
-        Ok(Command::SetReference(sequence_id))
+    run_command("pin-move-down", arguments, || {
+        let abs_row = resolve_argument_to_sequence_id(&state.pinned_sequences, arguments)?;
+        Ok(Command::MovePinned { abs_row, offset: 1 })
     })
 }
 
-pub(super) fn run_load_alignment(
+pub(super) fn run_pin_matching(
     _: &CommandPaletteState,
     arguments: &str,
 ) -> anyhow::Result<Command> {
-    run_command("load-alignment", arguments, || {
-        let path = require_argument(arguments)?;
-
-        Ok(Command::LoadFile { input: path })
+    run_command("pin-matching", arguments, || {
+        if arguments.is_empty() {
+            return Err(format_err!("Expected 1 argument, got 0"));
+        }
+        Ok(Command::PinMatching(arguments.to_string()))
     })
 }
 
-pub(super) fn run_diff_mode(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
-    run_command("set-diff-mode", arguments, || {
-        let arg = require_argument(arguments)?;
-        let mode = arg.parse()?;
-        Ok(Command::SetDiffMode(mode))
+pub(super) fn run_unpin_all(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("unpin-all", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::UnpinAll)
     })
 }
 
-pub(super) fn run_consensus_method(
+pub(super) fn run_list_orf_issues(
     _: &CommandPaletteState,
     arguments: &str,
 ) -> anyhow::Result<Command> {
-    run_command("set-consensus-method", arguments, || {
-        let arg = require_argument(arguments)?;
-        let method = arg
-            .parse()
+    run_command("list-orf-issues", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::ListOrfIssues)
+    })
+}
+
+pub(super) fn run_find_orfs(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("find-orfs", arguments, || {
+        let Some(value) = parse_argument(arguments) else {
+            return Ok(Command::FindOrfs(DEFAULT_MIN_ORF_CODONS));
+        };
+        let min_codons = value
+            .parse::<usize>()
             .ok()
-            .ok_or_else(|| format_err!("Invalid argument for set-consensus-method: {arg}"))?;
-        Ok(Command::SetConsensusMethod(method))
+            .filter(|&codons| codons > 0)
+            .ok_or_else(|| format_err!("Invalid argument: expected a positive number of codons"))?;
+        Ok(Command::FindOrfs(min_codons))
     })
 }
 
-pub(super) fn run_translation_frame(
+pub(super) fn run_list_variable_columns(
     _: &CommandPaletteState,
     arguments: &str,
 ) -> anyhow::Result<Command> {
-    run_command("set-translation-frame", arguments, || {
-        let arg = require_argument(arguments)?;
-        let frame = arg
-            .parse()
+    run_command("list-variable-columns", arguments, || {
+        let Some(value) = parse_argument(arguments) else {
+            return Ok(Command::ListVariableColumns(DEFAULT_VARIABLE_COLUMNS));
+        };
+        let top_n = value
+            .parse::<usize>()
+            .ok()
+            .filter(|&count| count > 0)
+            .ok_or_else(|| {
+                format_err!("Invalid argument: expected a positive number of columns")
+            })?;
+        Ok(Command::ListVariableColumns(top_n))
+    })
+}
+
+pub(super) fn run_find_primer(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("find-primer", arguments, || {
+        let mut arguments = parse_arguments(arguments).into_iter();
+        let primer = arguments
+            .next()
+            .ok_or_else(|| format_err!("Expected 1 argument, got 0"))?;
+        let max_mismatches = arguments
+            .next()
+            .map(|value| {
+                value
+                    .parse::<usize>()
+                    .map_err(|_| format_err!("Invalid argument: expected a number of mismatches"))
+            })
+            .transpose()?
+            .unwrap_or(DEFAULT_MAX_PRIMER_MISMATCHES);
+        if arguments.next().is_some() {
+            return Err(format_err!("Expected at most 2 arguments"));
+        }
+
+        Ok(Command::FindPrimer {
+            primer,
+            max_mismatches,
+        })
+    })
+}
+
+pub(super) fn run_blast_lite(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("blast-lite", arguments, || {
+        let query = require_argument(arguments)?;
+        Ok(Command::BlastLite { query })
+    })
+}
+
+pub(super) fn run_show_restriction_sites(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("show-restriction-sites", arguments, || {
+        let names = arguments
+            .split([',', ' '])
+            .filter(|name| !name.is_empty())
+            .map(|name| restriction::enzyme(name).map(|enzyme| enzyme.name.to_string()))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(Command::ShowRestrictionSites(names))
+    })
+}
+
+pub(super) fn run_extract_region(
+    state: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("extract-region", arguments, || {
+        let (start, end) = parse_region(state, &require_argument(arguments)?)?;
+        Ok(Command::ExtractRegion {
+            start: start - 1,
+            end: end - 1,
+        })
+    })
+}
+
+/// 1-based inclusive `<start>-<end>` range of visible columns
+pub(super) fn parse_region(
+    state: &CommandPaletteState,
+    value: &str,
+) -> anyhow::Result<(usize, usize)> {
+    let invalid = || format_err!("Invalid argument: expected <start>-<end>, e.g. 100-250");
+    let (start, end) = value.split_once('-').ok_or_else(invalid)?;
+    let (Ok(start), Ok(end)) = (start.trim().parse::<usize>(), end.trim().parse::<usize>()) else {
+        return Err(invalid());
+    };
+    if start == 0 || end < start {
+        return Err(invalid());
+    }
+    let columns = state.visible_columns.len();
+    if end > columns {
+        return Err(format_err!("region must be within 1..{columns}"));
+    }
+    Ok((start, end))
+}
+
+pub(super) fn run_restore_alignment(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("restore-alignment", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::RestoreAlignment)
+    })
+}
+
+pub(super) fn run_filter_rows(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("filter-rows", arguments, || {
+        if arguments.is_empty() {
+            Ok(Command::ClearFilter)
+        } else {
+            Ok(Command::SetFilter(arguments.to_string()))
+        }
+    })
+}
+
+pub(super) fn run_set_reference(
+    state: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("set-reference", arguments, || {
+        let arg = require_argument(arguments)?;
+
+        let sequence_id = lookup_sequence_id(&state.selectable_sequences, arg.as_str())
+            .ok_or_else(|| format_err!("Sequence not found: {arg}"))?;
+        Ok(Command::SetReference(sequence_id))
+    })
+}
+
+pub(super) fn run_hide_sequence(
+    state: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("hide-sequence", arguments, || {
+        let sequence_id = resolve_argument_to_sequence_id(&state.selectable_sequences, arguments)?;
+        Ok(Command::HideSequence(sequence_id))
+    })
+}
+
+pub(super) fn run_show_hidden(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("show-hidden", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::ShowHiddenSequences)
+    })
+}
+
+pub(super) fn run_copy_selection(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("copy-selection", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::CopySelection)
+    })
+}
+
+pub(super) fn run_copy_locator(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("copy-locator", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::CopyLocator)
+    })
+}
+
+pub(super) fn run_selection_stats(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("selection-stats", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::ShowSelectionStats)
+    })
+}
+
+pub(super) fn run_highlight_selection(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("highlight-selection", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::HighlightSelection)
+    })
+}
+
+pub(super) fn run_clear_highlights(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("clear-highlights", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::ClearHighlights)
+    })
+}
+
+pub(super) fn run_browse_files(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("browse-files", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::OpenFilePicker)
+    })
+}
+
+pub(super) fn run_paste_alignment(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("paste-alignment", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::OpenPasteBuffer)
+    })
+}
+
+pub(super) fn run_adjust_consensus(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("adjust-consensus", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::OpenConsensusThreshold)
+    })
+}
+
+pub(super) fn run_load_alignment(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("load-alignment", arguments, || {
+        let path = expand_tilde(&require_argument(arguments)?);
+
+        Ok(Command::LoadFile { input: path })
+    })
+}
+
+pub(super) fn run_append_alignment(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("append-alignment", arguments, || {
+        let path = expand_tilde(&require_argument(arguments)?);
+
+        Ok(Command::AppendAlignment { input: path })
+    })
+}
+
+pub(super) fn run_realign_selection(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("realign-selection", arguments, || {
+        let aligner = require_argument(arguments)?.parse()?;
+        Ok(Command::RealignSelection(aligner))
+    })
+}
+
+pub(super) fn run_color_ids(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("color-ids", arguments, || {
+        let arguments = arguments.trim();
+        if arguments.is_empty() {
+            return Ok(Command::ClearIdColors);
+        }
+        let (pattern, color) = arguments.rsplit_once(char::is_whitespace).ok_or_else(|| {
+            format_err!("Invalid argument: expected <regex> <colour>, e.g. ^hCoV blue")
+        })?;
+        Ok(Command::AddIdColor(IdColorRule::new(
+            pattern.trim_end(),
+            color,
+        )?))
+    })
+}
+
+pub(super) fn run_tool(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("tool", arguments, || {
+        let name = require_argument(arguments)?;
+        Ok(Command::RunTool(name))
+    })
+}
+
+pub(super) fn run_save_mask(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("save-mask", arguments, || {
+        let path = expand_tilde(&require_argument(arguments)?);
+
+        Ok(Command::SaveMask { path })
+    })
+}
+
+pub(super) fn run_load_mask(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("load-mask", arguments, || {
+        let path = expand_tilde(&require_argument(arguments)?);
+
+        Ok(Command::LoadMask { input: path })
+    })
+}
+
+pub(super) fn run_clear_mask(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("clear-mask", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::ClearMask)
+    })
+}
+
+pub(super) fn run_cluster(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("cluster", arguments, || {
+        let value = require_argument(arguments)?;
+        match value.parse::<f32>() {
+            Ok(percent) if percent.is_finite() && percent > 0.0 && percent <= 100.0 => {
+                Ok(Command::ClusterRows(percent))
+            }
+            _ => Err(format_err!(
+                "Invalid argument: expected an identity percentage above 0 and up to 100"
+            )),
+        }
+    })
+}
+
+pub(super) fn run_group_by_indels(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("group-by-indels", arguments, || {
+        let visible_only = match parse_argument(arguments).as_deref() {
+            None | Some("" | "visible") => true,
+            Some("all") => false,
+            Some(other) => {
+                return Err(format_err!(
+                    "Invalid argument for group-by-indels: {other} (expected visible or all)"
+                ));
+            }
+        };
+        Ok(Command::GroupByIndels { visible_only })
+    })
+}
+
+pub(super) fn run_toggle_cluster(
+    state: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("toggle-cluster", arguments, || {
+        let sequence_id = resolve_argument_to_sequence_id(&state.selectable_sequences, arguments)?;
+        Ok(Command::ToggleCluster(sequence_id))
+    })
+}
+
+pub(super) fn run_uncluster(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("uncluster", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::ClearClusters)
+    })
+}
+
+pub(super) fn run_load_partitions(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("load-partitions", arguments, || {
+        let path = expand_tilde(&require_argument(arguments)?);
+
+        Ok(Command::LoadPartitions { input: path })
+    })
+}
+
+pub(super) fn run_export_consensus(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("export-consensus", arguments, || {
+        let mut arguments = parse_arguments(arguments).into_iter();
+        let path = arguments
+            .next()
+            .map(|path| expand_tilde(&path))
+            .ok_or_else(|| format_err!("Expected 1 argument, got 0"))?;
+        let mut method = None;
+        let mut threshold = None;
+        for option in arguments {
+            if let Ok(parsed) = option.parse() {
+                method = Some(parsed);
+                continue;
+            }
+            let percent = option
+                .parse::<f32>()
+                .ok()
+                .filter(|percent| percent.is_finite() && (0.0..=100.0).contains(percent))
+                .ok_or_else(|| {
+                    format_err!(
+                        "Invalid option for export-consensus: {option} (expected a consensus method or a threshold percentage in 0..=100)"
+                    )
+                })?;
+            threshold = Some(percent / 100.0);
+        }
+
+        Ok(Command::ExportConsensus {
+            path,
+            method,
+            threshold,
+        })
+    })
+}
+
+pub(super) fn run_export_conservation(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("export-conservation", arguments, || {
+        let mut arguments = parse_arguments(arguments).into_iter();
+        let path = arguments
+            .next()
+            .map(|path| expand_tilde(&path))
+            .ok_or_else(|| format_err!("Expected 1 argument, got 0"))?;
+        let format = arguments.next().map(|format| format.parse()).transpose()?;
+        if arguments.next().is_some() {
+            return Err(format_err!("Expected at most 2 arguments"));
+        }
+
+        Ok(Command::ExportConservation { path, format })
+    })
+}
+
+pub(super) fn run_save_as(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("save-as", arguments, || {
+        let mut arguments = parse_arguments(arguments).into_iter();
+        let path = arguments
+            .next()
+            .map(|path| expand_tilde(&path))
+            .ok_or_else(|| format_err!("Expected 1 argument, got 0"))?;
+        let mut format = None;
+        let mut full = false;
+        for argument in arguments {
+            match argument.as_str() {
+                "full" => full = true,
+                "visible" => full = false,
+                _ if format.is_none() => format = Some(argument.parse()?),
+                _ => return Err(format_err!("Expected at most a format and full or visible")),
+            }
+        }
+
+        Ok(Command::SaveAlignment { path, format, full })
+    })
+}
+
+pub(super) fn run_export_snp_dists(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("export-snp-dists", arguments, || {
+        let mut arguments = parse_arguments(arguments).into_iter();
+        let path = arguments
+            .next()
+            .map(|path| expand_tilde(&path))
+            .ok_or_else(|| format_err!("Expected 1 argument, got 0"))?;
+        let ignore = arguments
+            .next()
+            .map(|ignore| ignore.parse())
+            .transpose()?
+            .unwrap_or_default();
+        if arguments.next().is_some() {
+            return Err(format_err!("Expected at most 2 arguments"));
+        }
+
+        Ok(Command::ExportSnpDists { path, ignore })
+    })
+}
+
+pub(super) fn run_generate_report(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("generate-report", arguments, || {
+        let path = expand_tilde(&require_argument(arguments)?);
+        Ok(Command::GenerateReport { path })
+    })
+}
+
+pub(super) fn run_cancel_export(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("cancel-export", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::CancelExport)
+    })
+}
+
+pub(super) fn run_clear_cache(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("clear-cache", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::ClearCache)
+    })
+}
+
+pub(super) fn run_precompute_stats(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("precompute-stats", arguments, || {
+        match parse_argument(arguments).as_deref() {
+            None | Some("") => Ok(Command::PrecomputeStats),
+            Some("cancel") => Ok(Command::CancelPrecompute),
+            Some(other) => Err(format_err!("Invalid argument: {other} (expected cancel)")),
+        }
+    })
+}
+
+pub(super) fn run_diff_ambiguity(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("set-diff-ambiguity", arguments, || {
+        let arg = require_argument(arguments)?;
+        let ambiguity = arg.parse()?;
+        Ok(Command::SetDiffAmbiguity(ambiguity))
+    })
+}
+
+pub(super) fn run_diff_options(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("set-diff-options", arguments, || {
+        let arg = require_argument(arguments)?;
+        let options = arg.parse()?;
+        Ok(Command::SetDiffOptions(options))
+    })
+}
+
+pub(super) fn run_diff_mode(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("set-diff-mode", arguments, || {
+        let arg = require_argument(arguments)?;
+        let mode = arg.parse()?;
+        Ok(Command::SetDiffMode(mode))
+    })
+}
+
+pub(super) fn run_set_layout(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("set-layout", arguments, || {
+        let arg = require_argument(arguments)?;
+        let mode = arg.parse()?;
+        Ok(Command::SetLayoutMode(mode))
+    })
+}
+
+pub(super) fn run_set_mode(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("set-mode", arguments, || {
+        let arg = require_argument(arguments)?;
+        let mode = arg.parse()?;
+        Ok(Command::SetEditMode(mode))
+    })
+}
+
+pub(super) fn run_set_id_width(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("set-id-width", arguments, || {
+        let arg = require_argument(arguments)?;
+        let width = arg.parse()?;
+        Ok(Command::SetIdPaneWidth(width))
+    })
+}
+
+pub(super) fn run_set_row_numbers(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("set-row-numbers", arguments, || {
+        let arg = require_argument(arguments)?;
+        let row_numbers = arg.parse()?;
+        Ok(Command::SetRowNumbers(row_numbers))
+    })
+}
+
+pub(super) fn run_set_ruler(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("set-ruler", arguments, || {
+        let arguments = parse_arguments(arguments);
+        if arguments.is_empty() {
+            return Err(format_err!("Expected 1 argument, got 0"));
+        }
+        let (mut ticks, mut coordinates) = (None, None);
+        for arg in arguments {
+            if let Ok(value) = arg.parse() {
+                ticks = Some(value);
+            } else if let Ok(value) = arg.parse() {
+                coordinates = Some(value);
+            } else {
+                return Err(format_err!("Invalid argument for set-ruler: {arg}"));
+            }
+        }
+        Ok(Command::SetRuler { ticks, coordinates })
+    })
+}
+
+pub(super) fn run_set_fast_render(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("set-fast-render", arguments, || {
+        let arg = require_argument(arguments)?;
+        Ok(Command::SetFastRender(arg.parse()?))
+    })
+}
+
+pub(super) fn run_zoom_out(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("zoom-out", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::ZoomOut)
+    })
+}
+
+pub(super) fn run_zoom_in(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("zoom-in", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::ZoomIn)
+    })
+}
+
+pub(super) fn run_set_density(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("set-density", arguments, || {
+        let arg = require_argument(arguments)?;
+        Ok(Command::SetDensity(arg.parse()?))
+    })
+}
+
+pub(super) fn run_set_error_feedback(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("set-error-feedback", arguments, || {
+        let arg = require_argument(arguments)?;
+        Ok(Command::SetErrorFeedback(arg.parse()?))
+    })
+}
+
+pub(super) fn run_set_id_truncation(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("set-id-truncation", arguments, || {
+        let arg = require_argument(arguments)?;
+        let truncation = arg.parse()?;
+        Ok(Command::SetIdTruncation(truncation))
+    })
+}
+
+pub(super) fn run_consensus_method(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("set-consensus-method", arguments, || {
+        let arg = require_argument(arguments)?;
+        let method = arg
+            .parse()
+            .ok()
+            .ok_or_else(|| format_err!("Invalid argument for set-consensus-method: {arg}"))?;
+        Ok(Command::SetConsensusMethod(method))
+    })
+}
+
+pub(super) fn run_consensus_scope(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("set-consensus-scope", arguments, || {
+        let arg = require_argument(arguments)?;
+        let scope = arg
+            .parse()
+            .ok()
+            .ok_or_else(|| format_err!("Invalid argument for set-consensus-scope: {arg}"))?;
+        Ok(Command::SetConsensusScope(scope))
+    })
+}
+
+pub(super) fn run_translation_frame(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("set-translation-frame", arguments, || {
+        let arg = require_argument(arguments)?;
+        let frame = arg
+            .parse()
             .ok()
-            .ok_or_else(|| format_err!("Invalid argument for set-translation-frame: {arg}"))?;
+            .ok_or_else(|| format_err!("frame must be 1..3, got {arg}"))?;
         Ok(Command::SetTranslationFrame(frame))
     })
 }
 
-pub(super) fn run_theme(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
-    run_command("set-theme", arguments, || {
-        let arg = require_argument(arguments)?;
-        let theme = arg
-            .parse()
-            .ok()
-            .ok_or_else(|| format_err!("Invalid argument for set-theme: {arg}"))?;
-        Ok(Command::SetTheme(theme))
-    })
-}
+pub(super) fn parse_genetic_code(arguments: &str) -> anyhow::Result<&'static GeneticCode> {
+    let arg = require_argument(arguments)?;
+    GeneticCode::find(&arg).ok_or_else(|| format_err!("unknown genetic code: {arg}"))
+}
+
+pub(super) fn run_set_genetic_code(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("set-genetic-code", arguments, || {
+        Ok(Command::SetGeneticCode(parse_genetic_code(arguments)?))
+    })
+}
+
+pub(super) fn run_translation_gap_mode(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("set-translation-gap-mode", arguments, || {
+        let arg = require_argument(arguments)?;
+        let gap_mode = arg
+            .parse()
+            .ok()
+            .ok_or_else(|| format_err!("Invalid argument for set-translation-gap-mode: {arg}"))?;
+        Ok(Command::SetTranslationGapMode(gap_mode))
+    })
+}
+
+pub(super) fn run_theme(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("set-theme", arguments, || {
+        let arg = require_argument(arguments)?;
+        let theme = arg
+            .parse()
+            .ok()
+            .ok_or_else(|| format_err!("Invalid argument for set-theme: {arg}"))?;
+        Ok(Command::SetTheme(theme))
+    })
+}
+
+pub(super) fn run_sequence_palette(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("set-sequence-palette", arguments, || {
+        let arg = require_argument(arguments)?;
+        let palette = arg
+            .parse()
+            .ok()
+            .ok_or_else(|| format_err!("Invalid argument for set-sequence-palette: {arg}"))?;
+        Ok(Command::SetSequencePalette(palette))
+    })
+}
+
+pub(super) fn run_color_style(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("set-color-style", arguments, || {
+        let arg = require_argument(arguments)?;
+        let color_style = arg
+            .parse()
+            .ok()
+            .ok_or_else(|| format_err!("Invalid argument for set-color-style: {arg}"))?;
+        Ok(Command::SetColorStyle(color_style))
+    })
+}
+
+pub(super) fn run_set_active_type(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("set-sequence-type", arguments, || {
+        let arg = require_argument(arguments)?;
+        let kind = arg
+            .parse::<libmsa::AlignmentType>()
+            .map_err(|_| format_err!("Invalid argument for set-sequence-type: {arg}"))?;
+        Ok(Command::SetActiveType(kind))
+    })
+}
+
+pub(super) fn run_check_update(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("check-update", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::CheckForUpdate {
+            show_success_message: true,
+        })
+    })
+}
+
+pub(super) fn run_show_last_error(
+    _: &CommandPaletteState,
+    arguments: &str,
+) -> anyhow::Result<Command> {
+    run_command("show-last-error", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::ShowLastError)
+    })
+}
+
+pub(super) fn run_quit(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
+    run_command("quit", arguments, || {
+        ensure_no_argument(arguments)?;
+        Ok(Command::Quit)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::theme::{ColorStyle, SequencePaletteId};
+    use crate::core::export::{ConservationFormat, SnpIgnore};
+    use crate::core::partition::Partition;
+    use crate::core::serializer::AlignmentFormat;
+    use crate::ui::alignment_pane::{Coordinates, RulerTicks};
+    use crate::ui::layout::IdPaneWidth;
+    use crate::ui::sequence_id_pane::IdTruncation;
+
+    fn palette_state_with_columns(visible_columns: Vec<usize>) -> CommandPaletteState {
+        CommandPaletteState::new(
+            Vec::new(),
+            Vec::new(),
+            libmsa::AlignmentType::Dna,
+            visible_columns,
+        )
+    }
+
+    #[test]
+    fn sequences_can_be_named_or_numbered_in_file_order() {
+        let sequence = |sequence_id: usize, name: &str| VisibleSequence {
+            sequence_id,
+            sequence_name: name.into(),
+        };
+        let state = CommandPaletteState::new(
+            vec![sequence(0, "alpha"), sequence(4, "2"), sequence(1, "beta")],
+            Vec::new(),
+            libmsa::AlignmentType::Dna,
+            Vec::new(),
+        );
+
+        assert_eq!(
+            run_jump_sequence(&state, "beta").expect("name should resolve"),
+            Command::JumpToSequence(1)
+        );
+        assert_eq!(
+            run_pin_sequence(&state, "1").expect("number should resolve"),
+            Command::PinSequence(0)
+        );
+        assert_eq!(
+            run_set_reference(&state, "2").expect("a name wins over a number"),
+            Command::SetReference(4)
+        );
+        assert!(run_jump_sequence(&state, "4").is_err());
+    }
+
+    #[test]
+    fn pin_moves_only_resolve_pinned_sequences() {
+        let sequence = |sequence_id: usize, name: &str| VisibleSequence {
+            sequence_id,
+            sequence_name: name.into(),
+        };
+        let state = CommandPaletteState::new(
+            vec![sequence(0, "alpha")],
+            vec![sequence(3, "beta"), sequence(5, "gamma")],
+            libmsa::AlignmentType::Dna,
+            Vec::new(),
+        );
+
+        assert_eq!(
+            run_pin_move_up(&state, "gamma").expect("pinned name should resolve"),
+            Command::MovePinned {
+                abs_row: 5,
+                offset: -1
+            }
+        );
+        assert_eq!(
+            run_pin_move_down(&state, "beta").expect("pinned name should resolve"),
+            Command::MovePinned {
+                abs_row: 3,
+                offset: 1
+            }
+        );
+        assert!(run_pin_move_up(&state, "alpha").is_err());
+        assert!(run_pin_move_down(&state, "").is_err());
+    }
+
+    #[test]
+    fn color_ids_takes_a_pattern_and_a_colour() {
+        let state = palette_state_with_columns(Vec::new());
+
+        assert_eq!(
+            run_color_ids(&state, "^hCoV 19 lightblue").expect("rule should parse"),
+            Command::AddIdColor(IdColorRule::new("^hCoV 19", "lightblue").unwrap())
+        );
+        assert_eq!(
+            run_color_ids(&state, "").expect("empty clears"),
+            Command::ClearIdColors
+        );
+        assert!(run_color_ids(&state, "outgroup").is_err());
+        assert!(run_color_ids(&state, "outgroup chartreuse-ish").is_err());
+    }
+
+    #[test]
+    fn jump_sequence_accepts_numbers_and_patterns() {
+        let sequence = |sequence_id: usize, name: &str| VisibleSequence {
+            sequence_id,
+            sequence_name: name.into(),
+        };
+        let state = CommandPaletteState::new(
+            vec![
+                sequence(0, "ref"),
+                sequence(41, "sample_b.1"),
+                sequence(7, "sample_a.2"),
+            ],
+            Vec::new(),
+            libmsa::AlignmentType::Dna,
+            Vec::new(),
+        );
+
+        assert_eq!(
+            run_jump_sequence(&state, "#42 sample_b.1").expect("number should resolve"),
+            Command::JumpToSequence(41)
+        );
+        assert_eq!(
+            run_jump_sequence(&state, "sample_*.2").expect("glob should resolve"),
+            Command::JumpToSequence(7)
+        );
+        assert_eq!(
+            run_jump_sequence(&state, "_[ab]\\.").expect("regex should take the first match"),
+            Command::JumpToSequence(41)
+        );
+        assert!(run_jump_sequence(&state, "#5").is_err());
+    }
+
+    #[test]
+    fn composition_covers_all_rows_one_sequence_or_a_pattern() {
+        let sequence = |sequence_id: usize, name: &str| VisibleSequence {
+            sequence_id,
+            sequence_name: name.into(),
+        };
+        let state = CommandPaletteState::new(
+            vec![
+                sequence(0, "ref"),
+                sequence(41, "sample_b.1"),
+                sequence(7, "sample_a.2"),
+            ],
+            Vec::new(),
+            libmsa::AlignmentType::Dna,
+            Vec::new(),
+        );
+
+        assert_eq!(
+            run_composition(&state, "").expect("no argument should count every row"),
+            Command::Composition(None)
+        );
+        assert_eq!(
+            run_composition(&state, "ref").expect("a name should resolve"),
+            Command::Composition(Some(vec![0]))
+        );
+        assert_eq!(
+            run_export_composition(&state, "out.csv sample_*").expect("a glob should resolve"),
+            Command::ExportComposition {
+                path: "out.csv".to_string(),
+                rows: Some(vec![41, 7]),
+            }
+        );
+        assert!(run_composition(&state, "missing").is_err());
+    }
+
+    #[test]
+    fn dnds_takes_a_sequence_and_an_optional_visible_region() {
+        let state = CommandPaletteState::new(
+            vec![VisibleSequence {
+                sequence_id: 3,
+                sequence_name: "sample".into(),
+            }],
+            Vec::new(),
+            libmsa::AlignmentType::Dna,
+            Vec::new(),
+        );
+
+        assert_eq!(
+            run_dnds(&state, "sample").expect("a name should resolve"),
+            Command::EstimateDnDs {
+                sequence: 3,
+                visible: false,
+            }
+        );
+        assert_eq!(
+            run_dnds(&state, "sample visible").expect("visible should parse"),
+            Command::EstimateDnDs {
+                sequence: 3,
+                visible: true,
+            }
+        );
+        assert!(run_dnds(&state, "").is_err());
+        assert!(run_dnds(&state, "sample other").is_err());
+    }
 
-pub(super) fn run_set_active_type(
-    _: &CommandPaletteState,
-    arguments: &str,
-) -> anyhow::Result<Command> {
-    run_command("set-sequence-type", arguments, || {
-        let arg = require_argument(arguments)?;
-        let kind = arg
-            .parse::<libmsa::AlignmentType>()
-            .map_err(|_| format_err!("Invalid argument for set-sequence-type: {arg}"))?;
-        Ok(Command::SetActiveType(kind))
-    })
-}
+    #[test]
+    fn set_id_width_accepts_auto_or_columns() {
+        let state = palette_state_with_columns(Vec::new());
 
-pub(super) fn run_check_update(
-    _: &CommandPaletteState,
-    arguments: &str,
-) -> anyhow::Result<Command> {
-    run_command("check-update", arguments, || {
-        ensure_no_argument(arguments)?;
-        Ok(Command::CheckForUpdate {
-            show_success_message: true,
-        })
-    })
-}
+        assert_eq!(
+            run_set_id_width(&state, "auto").expect("auto should parse"),
+            Command::SetIdPaneWidth(IdPaneWidth::Auto)
+        );
+        assert_eq!(
+            run_set_id_width(&state, "32").expect("a width should parse"),
+            Command::SetIdPaneWidth(IdPaneWidth::Columns(32))
+        );
+        assert!(run_set_id_width(&state, "2").is_err());
+        assert_eq!(
+            run_set_id_truncation(&state, "start").expect("start should parse"),
+            Command::SetIdTruncation(IdTruncation::Start)
+        );
+    }
 
-pub(super) fn run_quit(_: &CommandPaletteState, arguments: &str) -> anyhow::Result<Command> {
-    run_command("quit", arguments, || {
-        ensure_no_argument(arguments)?;
-        Ok(Command::Quit)
-    })
-}
+    #[test]
+    fn export_consensus_parses_method_and_threshold() {
+        let state = palette_state_with_columns(Vec::new());
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let action = run_export_consensus(&state, "\"out dir/cons.fa\" majority 75")
+            .expect("export-consensus should parse its options");
 
-    fn palette_state_with_columns(visible_columns: Vec<usize>) -> CommandPaletteState {
-        CommandPaletteState::new(
+        assert_eq!(
+            action,
+            Command::ExportConsensus {
+                path: "out dir/cons.fa".to_string(),
+                method: Some(libmsa::ConsensusMethod::Majority),
+                threshold: Some(0.75),
+            }
+        );
+    }
+
+    #[test]
+    fn export_conservation_accepts_an_explicit_format() {
+        let state = palette_state_with_columns(Vec::new());
+
+        let action = run_export_conservation(&state, "scores.txt wig")
+            .expect("export-conservation should parse its format");
+
+        assert_eq!(
+            action,
+            Command::ExportConservation {
+                path: "scores.txt".to_string(),
+                format: Some(ConservationFormat::Wiggle),
+            }
+        );
+        assert!(run_export_conservation(&state, "scores.txt bed").is_err());
+    }
+
+    #[test]
+    fn export_snp_dists_defaults_to_ignoring_gaps_and_n() {
+        let state = palette_state_with_columns(Vec::new());
+
+        assert_eq!(
+            run_export_snp_dists(&state, "dists.tsv").expect("path alone should parse"),
+            Command::ExportSnpDists {
+                path: "dists.tsv".to_string(),
+                ignore: SnpIgnore::GapsAndN,
+            }
+        );
+        assert_eq!(
+            run_export_snp_dists(&state, "dists.tsv none").expect("ignore mode should parse"),
+            Command::ExportSnpDists {
+                path: "dists.tsv".to_string(),
+                ignore: SnpIgnore::None,
+            }
+        );
+        assert!(run_export_snp_dists(&state, "dists.tsv n").is_err());
+    }
+
+    #[test]
+    fn export_consensus_rejects_unknown_options() {
+        let state = palette_state_with_columns(Vec::new());
+
+        assert!(run_export_consensus(&state, "cons.fa strict").is_err());
+        assert!(run_export_consensus(&state, "").is_err());
+    }
+
+    #[test]
+    fn find_orfs_defaults_the_minimum_length() {
+        let state = palette_state_with_columns(Vec::new());
+
+        assert_eq!(
+            run_find_orfs(&state, "").expect("no argument should use the default"),
+            Command::FindOrfs(DEFAULT_MIN_ORF_CODONS)
+        );
+        assert_eq!(
+            run_find_orfs(&state, "30").expect("length should parse"),
+            Command::FindOrfs(30)
+        );
+        assert!(run_find_orfs(&state, "0").is_err());
+    }
+
+    #[test]
+    fn find_primer_defaults_the_mismatch_limit() {
+        let state = palette_state_with_columns(Vec::new());
+
+        assert_eq!(
+            run_find_primer(&state, "ACGTRY").expect("primer alone should parse"),
+            Command::FindPrimer {
+                primer: "ACGTRY".to_string(),
+                max_mismatches: DEFAULT_MAX_PRIMER_MISMATCHES,
+            }
+        );
+        assert_eq!(
+            run_find_primer(&state, "ACGT 0").expect("limit should parse"),
+            Command::FindPrimer {
+                primer: "ACGT".to_string(),
+                max_mismatches: 0,
+            }
+        );
+        assert!(run_find_primer(&state, "ACGT few").is_err());
+    }
+
+    #[test]
+    fn save_as_accepts_a_format_and_scope_in_any_order() {
+        let state = palette_state_with_columns(Vec::new());
+
+        assert_eq!(
+            run_save_as(&state, "out.txt full nexus").expect("arguments should parse"),
+            Command::SaveAlignment {
+                path: "out.txt".to_string(),
+                format: Some(AlignmentFormat::Nexus),
+                full: true,
+            }
+        );
+        assert_eq!(
+            run_save_as(&state, "out.aln").expect("path alone should parse"),
+            Command::SaveAlignment {
+                path: "out.aln".to_string(),
+                format: None,
+                full: false,
+            }
+        );
+        assert!(run_save_as(&state, "out.fa genbank").is_err());
+    }
+
+    #[test]
+    fn jump_partition_targets_the_first_visible_column() {
+        let mut state = palette_state_with_columns(vec![0, 1, 4, 5]);
+        state.partitions = vec![
+            Partition {
+                name: "gene1".to_string(),
+                columns: 0..3,
+            },
+            Partition {
+                name: "gene2".to_string(),
+                columns: 3..6,
+            },
+        ];
+
+        assert_eq!(
+            run_jump_partition(&state, "GENE2").expect("partition should resolve"),
+            Command::JumpToPosition(2)
+        );
+        assert!(run_jump_partition(&state, "gene3").is_err());
+    }
+
+    #[test]
+    fn show_restriction_sites_normalises_enzyme_names() {
+        let state = palette_state_with_columns(Vec::new());
+
+        assert_eq!(
+            run_show_restriction_sites(&state, "ecori, BAMHI").expect("names should parse"),
+            Command::ShowRestrictionSites(vec!["EcoRI".to_string(), "BamHI".to_string()])
+        );
+        assert_eq!(
+            run_show_restriction_sites(&state, "").expect("no names should clear"),
+            Command::ShowRestrictionSites(Vec::new())
+        );
+        assert!(run_show_restriction_sites(&state, "EcoRI,FooI").is_err());
+    }
+
+    #[test]
+    fn convert_position_takes_an_optional_source_and_sequence() {
+        let state = CommandPaletteState::new(
+            vec![VisibleSequence {
+                sequence_id: 2,
+                sequence_name: "seqC".into(),
+            }],
+            Vec::new(),
+            libmsa::AlignmentType::Dna,
             Vec::new(),
+        );
+
+        assert_eq!(
+            run_convert_position(&state, "120").expect("a column alone should parse"),
+            Command::ConvertPosition {
+                position: 120,
+                from: PositionSource::Column,
+                sequence: None,
+            }
+        );
+        assert_eq!(
+            run_convert_position(&state, "57 sequence seqC").expect("source should parse"),
+            Command::ConvertPosition {
+                position: 57,
+                from: PositionSource::Sequence,
+                sequence: Some(2),
+            }
+        );
+        assert!(run_convert_position(&state, "57 sequence").is_err());
+        assert!(run_convert_position(&state, "0").is_err());
+        assert!(run_convert_position(&state, "10 missing").is_err());
+    }
+
+    #[test]
+    fn show_ungapped_without_a_sequence_closes_the_pane() {
+        let state = CommandPaletteState::new(
+            vec![VisibleSequence {
+                sequence_id: 1,
+                sequence_name: "seqB".into(),
+            }],
             Vec::new(),
             libmsa::AlignmentType::Dna,
-            visible_columns,
-        )
+            Vec::new(),
+        );
+
+        assert_eq!(
+            run_show_ungapped(&state, "seqB").expect("sequence should resolve"),
+            Command::ShowUngapped(Some(1))
+        );
+        assert_eq!(
+            run_show_ungapped(&state, "").expect("no argument should close"),
+            Command::ShowUngapped(None)
+        );
+        assert!(run_show_ungapped(&state, "seqZ").is_err());
+    }
+
+    #[test]
+    fn track_sequence_without_a_sequence_stops_tracking() {
+        let state = CommandPaletteState::new(
+            vec![VisibleSequence {
+                sequence_id: 3,
+                sequence_name: "isolate".into(),
+            }],
+            Vec::new(),
+            libmsa::AlignmentType::Dna,
+            Vec::new(),
+        );
+
+        assert_eq!(
+            run_track_sequence(&state, "isolate").expect("sequence should resolve"),
+            Command::TrackSequence(Some(3))
+        );
+        assert_eq!(
+            run_track_sequence(&state, "").expect("no argument should stop tracking"),
+            Command::TrackSequence(None)
+        );
+    }
+
+    #[test]
+    fn extract_region_parses_one_based_inclusive_ranges() {
+        let state = palette_state_with_columns((0..300).collect());
+
+        assert_eq!(
+            run_extract_region(&state, "100-250").expect("range should parse"),
+            Command::ExtractRegion {
+                start: 99,
+                end: 249,
+            }
+        );
+        assert!(run_extract_region(&state, "250-100").is_err());
+        assert!(run_extract_region(&state, "0-10").is_err());
+        assert!(run_extract_region(&state, "100").is_err());
+        assert_eq!(
+            run_extract_region(&state, "100-301")
+                .expect_err("regions past the last column are rejected")
+                .to_string(),
+            "region must be within 1..300"
+        );
     }
 
     #[test]
@@ -321,10 +2066,53 @@ mod tests {
         let error = run_jump_position(&state, "10")
             .expect_err("jump-position should reject targets after the last visible column");
 
+        assert_eq!(error.to_string(), "position must be 1..5");
         assert_eq!(
-            error.to_string(),
-            "No visible column at or after the requested position"
+            run_jump_position(&state, "2-6")
+                .expect_err("ranges ending past the last column are rejected")
+                .to_string(),
+            "position must be 1..5"
+        );
+    }
+
+    #[test]
+    fn set_fps_names_the_accepted_range() {
+        let state = palette_state_with_columns(Vec::new());
+
+        assert_eq!(run_set_fps(&state, "60").unwrap(), Command::SetFps(60));
+        assert_eq!(
+            run_set_fps(&state, "500").unwrap_err().to_string(),
+            format!("frame rate must be {MIN_RENDER_FPS}..{MAX_RENDER_FPS}")
+        );
+    }
+
+    #[test]
+    fn jump_position_accepts_ranges_offsets_and_percentages() {
+        let state = palette_state_with_columns(vec![0, 1, 2, 3, 4]);
+
+        assert_eq!(
+            run_jump_position(&state, "2-4 highlight").expect("range should parse"),
+            Command::JumpToRange {
+                start: 1,
+                end: 3,
+                highlight: true,
+            }
+        );
+        assert_eq!(
+            run_jump_position(&state, "+500").expect("offset should parse"),
+            Command::ScrollRight { amount: 500 }
+        );
+        assert_eq!(
+            run_jump_position(&state, "-20").expect("offset should parse"),
+            Command::ScrollLeft { amount: 20 }
+        );
+        assert_eq!(
+            run_jump_position(&state, "50%").expect("percentage should parse"),
+            Command::JumpToPosition(2)
         );
+        assert!(run_jump_position(&state, "4-2").is_err());
+        assert!(run_jump_position(&state, "3 highlight").is_err());
+        assert!(run_jump_position(&state, "150%").is_err());
     }
 
     #[test]
@@ -375,6 +2163,81 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cluster_takes_an_identity_percentage() {
+        let state = palette_state_with_columns(Vec::new());
+
+        let action = run_cluster(&state, "97").expect("97 should be accepted");
+
+        assert!(matches!(
+            action,
+            Command::ClusterRows(value) if (value - 97.0).abs() < f32::EPSILON
+        ));
+        assert!(run_cluster(&state, "0").is_err());
+        assert!(run_cluster(&state, "101").is_err());
+    }
+
+    #[test]
+    fn set_ruler_takes_tick_spacing_and_coordinates_in_any_order() {
+        let state = palette_state_with_columns(Vec::new());
+
+        assert_eq!(
+            run_set_ruler(&state, "0-based codon").unwrap(),
+            Command::SetRuler {
+                ticks: Some(RulerTicks::Codon),
+                coordinates: Some(Coordinates::ZeroBased),
+            }
+        );
+        assert_eq!(
+            run_set_ruler(&state, "20").unwrap(),
+            Command::SetRuler {
+                ticks: Some(RulerTicks::Twenty),
+                coordinates: None,
+            }
+        );
+        assert!(run_set_ruler(&state, "").is_err());
+        assert!(run_set_ruler(&state, "7").is_err());
+    }
+
+    #[test]
+    fn group_by_indels_defaults_to_the_visible_columns() {
+        let state = palette_state_with_columns(Vec::new());
+
+        assert_eq!(
+            run_group_by_indels(&state, "").unwrap(),
+            Command::GroupByIndels { visible_only: true }
+        );
+        assert_eq!(
+            run_group_by_indels(&state, "all").unwrap(),
+            Command::GroupByIndels {
+                visible_only: false
+            }
+        );
+        assert!(run_group_by_indels(&state, "some").is_err());
+    }
+
+    #[test]
+    fn sequence_palette_accepts_known_names() {
+        let state = palette_state_with_columns(Vec::new());
+
+        assert_eq!(
+            run_sequence_palette(&state, "colorblind").expect("palette should parse"),
+            Command::SetSequencePalette(SequencePaletteId::Colorblind)
+        );
+        assert!(run_sequence_palette(&state, "rainbow").is_err());
+    }
+
+    #[test]
+    fn color_style_accepts_known_styles() {
+        let state = palette_state_with_columns(Vec::new());
+
+        assert_eq!(
+            run_color_style(&state, "foreground").expect("style should parse"),
+            Command::SetColorStyle(ColorStyle::Foreground)
+        );
+        assert!(run_color_style(&state, "italic").is_err());
+    }
+
     #[test]
     fn set_active_type_accepts_alignment_type_name() {
         let state = palette_state_with_columns(Vec::new());
@@ -392,12 +2255,53 @@ mod tests {
     fn set_active_type_rejects_unknown_argument() {
         let state = palette_state_with_columns(Vec::new());
 
-        let error = run_set_active_type(&state, "rna")
+        let error = run_set_active_type(&state, "cdna")
             .expect_err("unknown sequence type should be rejected");
 
         assert_eq!(
             error.to_string(),
-            "Invalid argument for set-sequence-type: rna"
+            "Invalid argument for set-sequence-type: cdna"
+        );
+    }
+
+    #[test]
+    fn set_genetic_code_accepts_numbers_and_names() {
+        let state = palette_state_with_columns(Vec::new());
+
+        for argument in ["2", "vertebrate-mito"] {
+            assert!(matches!(
+                run_set_genetic_code(&state, argument),
+                Ok(Command::SetGeneticCode(code)) if code.id == 2
+            ));
+        }
+        let error = run_set_genetic_code(&state, "8").expect_err("there is no table 8");
+        assert_eq!(error.to_string(), "unknown genetic code: 8");
+    }
+
+    #[test]
+    fn translation_gap_mode_parses_named_modes() {
+        let state = palette_state_with_columns(Vec::new());
+
+        assert!(matches!(
+            run_translation_gap_mode(&state, "skip-gaps"),
+            Ok(Command::SetTranslationGapMode(
+                libmsa::TranslationGapMode::SkipGaps
+            ))
+        ));
+        let error = run_translation_gap_mode(&state, "skip").expect_err("unknown mode");
+        assert_eq!(
+            error.to_string(),
+            "Invalid argument for set-translation-gap-mode: skip"
         );
     }
+
+    #[test]
+    fn set_active_type_accepts_rna() {
+        let state = palette_state_with_columns(Vec::new());
+
+        assert!(matches!(
+            run_set_active_type(&state, "rna"),
+            Ok(Command::SetActiveType(libmsa::AlignmentType::Rna))
+        ));
+    }
 }
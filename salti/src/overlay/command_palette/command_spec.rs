@@ -4,6 +4,10 @@ use super::input::CommandPaletteState;
 
 pub(super) type CompleterFunc = fn(&CommandPaletteState, &str) -> Vec<String>;
 pub(super) type RunnerFunc = fn(&CommandPaletteState, &str) -> anyhow::Result<Command>;
+/// checks arguments while they are typed, returning a short description of what they resolve to
+pub(super) type ValidatorFunc = fn(&CommandPaletteState, &str) -> anyhow::Result<String>;
+/// describes the values a numeric argument accepts, shown under the help text
+pub(super) type RangeFunc = fn(&CommandPaletteState) -> String;
 
 #[derive(Debug, Clone, Copy)]
 pub(super) struct StaticCommand {
@@ -20,6 +24,8 @@ pub(super) struct TypableCommand {
     pub(super) aliases: &'static [&'static str],
     pub(super) completer: Option<CompleterFunc>,
     pub(super) static_candidates: &'static [&'static str],
+    pub(super) validator: Option<ValidatorFunc>,
+    pub(super) range: Option<RangeFunc>,
     pub(super) run: RunnerFunc,
 }
 
@@ -34,6 +40,20 @@ impl TypableCommand {
                 .collect()
         }
     }
+
+    /// live hint for partially typed arguments; `None` until there is something to check
+    pub(super) fn validate(
+        self,
+        state: &CommandPaletteState,
+        arguments: &str,
+    ) -> Option<Result<String, String>> {
+        let validator = self.validator?;
+        let arguments = arguments.trim();
+        if arguments.is_empty() {
+            return None;
+        }
+        Some(validator(state, arguments).map_err(|error| error.to_string()))
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -75,6 +95,14 @@ impl PaletteCommand {
         }
     }
 
+    /// valid values of the argument for the loaded alignment, when it takes a number
+    pub(super) fn range_hint(self, state: &CommandPaletteState) -> Option<String> {
+        match self {
+            Self::Static(_) => None,
+            Self::Typable(spec) => spec.range.map(|range| range(state)),
+        }
+    }
+
     pub(super) fn typable(self) -> Option<TypableCommand> {
         match self {
             Self::Static(_) => None,
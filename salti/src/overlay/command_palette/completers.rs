@@ -1,7 +1,8 @@
-use std::fs;
 use std::path::PathBuf;
 
 use super::input::CommandPaletteState;
+use super::utils::expand_tilde;
+use crate::core::restriction;
 
 fn sequence_names_from(sequences: &[super::input::VisibleSequence]) -> Vec<String> {
     sequences
@@ -14,6 +15,15 @@ pub(super) fn sequences(state: &CommandPaletteState, _: &str) -> Vec<String> {
     sequence_names_from(&state.selectable_sequences)
 }
 
+/// `#42 name`, so rows with opaque IDs can be picked by number
+pub(super) fn numbered_sequences(state: &CommandPaletteState, _: &str) -> Vec<String> {
+    state
+        .selectable_sequences
+        .iter()
+        .map(|sequence| format!("#{} {}", sequence.sequence_id + 1, sequence.sequence_name))
+        .collect()
+}
+
 pub(super) fn pinned_sequences(state: &CommandPaletteState, _: &str) -> Vec<String> {
     sequence_names_from(&state.pinned_sequences)
 }
@@ -37,6 +47,36 @@ pub(super) fn filter_matches(state: &CommandPaletteState, arguments: &str) -> Ve
         .collect()
 }
 
+/// completes the last name in a comma separated enzyme list, skipping names already listed
+pub(super) fn restriction_enzymes(_: &CommandPaletteState, arguments: &str) -> Vec<String> {
+    let arguments = arguments.trim_start();
+    let prefix = arguments
+        .rfind(',')
+        .map_or("", |index| &arguments[..=index]);
+    let listed: Vec<&str> = prefix.split(',').map(str::trim).collect();
+    restriction::ENZYMES
+        .iter()
+        .filter(|enzyme| {
+            !listed
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(enzyme.name))
+        })
+        .map(|enzyme| format!("{prefix}{}", enzyme.name))
+        .collect()
+}
+
+pub(super) fn tools(state: &CommandPaletteState, _: &str) -> Vec<String> {
+    state.tools.clone()
+}
+
+pub(super) fn partitions(state: &CommandPaletteState, _: &str) -> Vec<String> {
+    state
+        .partitions
+        .iter()
+        .map(|partition| partition.name.clone())
+        .collect()
+}
+
 fn split_dir_and_prefix(query: &str) -> (&str, &str) {
     let trimmed = query.trim();
     if trimmed.is_empty() {
@@ -62,38 +102,96 @@ fn join_display_path(dir_prefix: &str, name: &str) -> String {
     joined
 }
 
-pub(super) fn filename(_: &CommandPaletteState, arguments: &str) -> Vec<String> {
-    let (dir_prefix, name_prefix) = split_dir_and_prefix(arguments);
+/// schemes the loader fetches remotely, offered while they are being typed
+const REMOTE_PREFIXES: &[&str] = &["http://", "https://", "ssh://"];
+
+pub(super) fn filename(state: &CommandPaletteState, arguments: &str) -> Vec<String> {
+    let query = arguments.trim();
+    // remote inputs cannot be listed, so keep what was typed rather than showing no matches
+    if query.contains("://") {
+        return vec![query.to_string()];
+    }
+
+    let (dir_prefix, name_prefix) = split_dir_and_prefix(query);
     let base_dir = if dir_prefix.is_empty() {
         PathBuf::from(".")
     } else {
-        PathBuf::from(dir_prefix)
+        PathBuf::from(expand_tilde(dir_prefix))
     };
+    // like a shell, dotfiles are only offered once the name starts with a dot
+    let show_hidden = name_prefix.starts_with('.');
+
+    let mut matches: Vec<(bool, String)> = state
+        .listed_entries(base_dir)
+        .into_iter()
+        .filter(|entry| entry.name.starts_with(name_prefix))
+        .filter(|entry| show_hidden || !entry.name.starts_with('.'))
+        .map(|entry| {
+            let mut label = join_display_path(dir_prefix, &entry.name);
+            if entry.is_dir {
+                label.push('/');
+            }
+            (!entry.is_dir, label)
+        })
+        .collect();
+    matches.sort();
 
-    let Ok(entries) = fs::read_dir(base_dir.as_path()) else {
-        return Vec::new();
-    };
+    let remote = REMOTE_PREFIXES
+        .iter()
+        .filter(|prefix| !query.is_empty() && dir_prefix.is_empty() && prefix.starts_with(query))
+        .map(|prefix| (*prefix).to_string());
+    matches
+        .into_iter()
+        .map(|(_, label)| label)
+        .chain(remote)
+        .collect()
+}
 
-    let mut matches = Vec::new();
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
 
-    for entry in entries.flatten() {
-        let entry_name = entry.file_name();
-        let Some(entry_name) = entry_name.to_str() else {
-            continue;
-        };
+    use std::fs;
 
-        if !entry_name.starts_with(name_prefix) {
-            continue;
-        }
+    use super::*;
 
-        let is_dir = entry.path().is_dir();
-        let mut label = join_display_path(dir_prefix, entry_name);
-        if is_dir {
-            label.push('/');
+    fn complete_when_listed(state: &CommandPaletteState, arguments: &str) -> Vec<String> {
+        filename(state, arguments);
+        let started = Instant::now();
+        while !state.listing_complete() && started.elapsed() < Duration::from_secs(5) {
+            std::thread::sleep(Duration::from_millis(5));
         }
-        matches.push((!is_dir, label));
+        filename(state, arguments)
     }
 
-    matches.sort();
-    matches.into_iter().map(|(_, label)| label).collect()
+    #[test]
+    fn filename_lists_directories_first_and_hides_dotfiles() {
+        let dir = std::env::temp_dir().join(format!("salti-complete-{}", std::process::id()));
+        fs::create_dir_all(dir.join("zdir")).unwrap();
+        fs::write(dir.join("a.fa"), "").unwrap();
+        fs::write(dir.join(".hidden.fa"), "").unwrap();
+        let state = CommandPaletteState::empty();
+        let prefix = format!("{}/", dir.display());
+
+        assert_eq!(
+            complete_when_listed(&state, &prefix),
+            [format!("{prefix}zdir/"), format!("{prefix}a.fa")]
+        );
+        assert_eq!(
+            complete_when_listed(&state, &format!("{prefix}.")),
+            [format!("{prefix}.hidden.fa")]
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn filename_keeps_remote_inputs_and_offers_schemes() {
+        let state = CommandPaletteState::empty();
+
+        assert_eq!(
+            filename(&state, "https://example.com/a.fa"),
+            ["https://example.com/a.fa"]
+        );
+        assert!(filename(&state, "ss").contains(&"ssh://".to_string()));
+    }
 }
@@ -1,11 +1,15 @@
 use anyhow::format_err;
+use std::cell::RefCell;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use crossterm::event::{KeyCode, KeyEvent};
 use libmsa::AlignmentType;
 
 use crate::command::Command;
+use crate::core::dir_listing::{DirListing, ListedEntry};
 use crate::core::model::AlignmentModel;
+use crate::core::partition::Partition;
 use crate::core::search::{Direction, FilterMode, SearchableList};
 use crate::ui::notification::{Notification, NotificationLevel};
 
@@ -36,6 +40,13 @@ pub struct CommandPaletteState {
     pub(super) pinned_sequences: Vec<VisibleSequence>,
     pub(super) active_type: AlignmentType,
     pub(super) visible_columns: Vec<usize>,
+    pub(super) partitions: Vec<Partition>,
+    /// names of the external tools configured under `[tools]`
+    pub(super) tools: Vec<String>,
+    /// live validation of the typed argument: a description when valid, the error otherwise
+    pub(super) argument_hint: Option<Result<String, String>>,
+    /// background listing behind the filename completer, replaced when the directory changes
+    directory_listing: RefCell<Option<DirListing>>,
 }
 impl CommandPaletteState {
     pub fn empty() -> Self {
@@ -80,12 +91,14 @@ impl CommandPaletteState {
             })
             .collect();
 
-        Self::new(
+        let mut state = Self::new(
             selectable_sequences,
             pinned_sequences,
             alignment.base().active_type(),
             alignment.view().absolute_column_ids().collect(),
-        )
+        );
+        state.partitions.clone_from(&alignment.partitions);
+        state
     }
 
     pub fn new(
@@ -108,7 +121,51 @@ impl CommandPaletteState {
             pinned_sequences,
             active_type,
             visible_columns,
+            partitions: Vec::new(),
+            tools: Vec::new(),
+            argument_hint: None,
+            directory_listing: RefCell::new(None),
+        }
+    }
+
+    /// offers `tools` as completions for `tool`
+    pub fn with_tools(mut self, tools: Vec<String>) -> Self {
+        self.tools = tools;
+        self
+    }
+
+    /// entries of `dir` listed so far, starting a background listing when `dir` is new
+    pub(super) fn listed_entries(&self, dir: PathBuf) -> Vec<ListedEntry> {
+        let mut listing = self.directory_listing.borrow_mut();
+        if listing.as_ref().is_none_or(|listing| listing.dir() != dir) {
+            *listing = Some(DirListing::start(dir));
         }
+        listing
+            .as_ref()
+            .map(DirListing::entries)
+            .unwrap_or_default()
+    }
+
+    #[cfg(test)]
+    pub(super) fn listing_complete(&self) -> bool {
+        self.directory_listing
+            .borrow()
+            .as_ref()
+            .is_some_and(DirListing::is_complete)
+    }
+
+    /// refreshes completions when the background directory listing has grown.
+    /// returns whether anything changed.
+    pub fn poll_directory_listing(&mut self) -> bool {
+        let changed = self
+            .directory_listing
+            .get_mut()
+            .as_ref()
+            .is_some_and(DirListing::take_changed);
+        if changed && self.current_typable_command().is_some() {
+            self.update_argument_completion();
+        }
+        changed
     }
 
     fn current_typable_command(&self) -> Option<TypableCommand> {
@@ -150,6 +207,13 @@ impl CommandPaletteState {
             self.argument_input.clear();
             self.argument_input.push_str(label);
         }
+        self.refresh_argument_hint();
+    }
+
+    fn refresh_argument_hint(&mut self) {
+        self.argument_hint = self
+            .current_typable_command()
+            .and_then(|spec| spec.validate(self, &self.argument_input));
     }
 
     fn update_command_filter(&mut self) {
@@ -169,6 +233,7 @@ impl CommandPaletteState {
             query.as_deref().unwrap_or_default(),
         );
         self.completion_list.reset_selection();
+        self.refresh_argument_hint();
     }
 
     fn update_active_list(&mut self) {
@@ -182,6 +247,7 @@ impl CommandPaletteState {
         self.phase = PaletteState::Command;
         self.command_input.clear();
         self.argument_input.clear();
+        self.argument_hint = None;
         self.completion_list.set_items(Vec::new());
         self.update_command_filter();
     }
@@ -386,6 +452,20 @@ impl CommandPaletteState {
     }
 }
 
+fn display_command_names() -> Vec<String> {
+    COMMAND_SPECS
+        .iter()
+        .map(|spec| spec.name().to_string())
+        .collect()
+}
+
+fn resolve_command(name: &str) -> Option<PaletteCommand> {
+    COMMAND_SPECS
+        .iter()
+        .copied()
+        .find(|spec| spec.name() == name || spec.aliases().contains(&name))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -412,6 +492,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn typed_arguments_are_validated_live() {
+        let mut palette = CommandPaletteState::new(
+            Vec::new(),
+            Vec::new(),
+            libmsa::AlignmentType::Dna,
+            vec![0, 1, 2],
+        );
+        for character in "jump-position 9".chars() {
+            palette.handle_key_event(key(KeyCode::Char(character)));
+        }
+
+        assert!(matches!(palette.argument_hint, Some(Err(_))));
+
+        palette.handle_key_event(key(KeyCode::Backspace));
+        palette.handle_key_event(key(KeyCode::Char('2')));
+
+        assert_eq!(palette.argument_hint, Some(Ok("Column 2 of 3".to_string())));
+    }
+
     #[test]
     fn submit_success_appends_close_command_palette() {
         let mut palette = CommandPaletteState::empty();
@@ -441,17 +541,3 @@ mod tests {
         );
     }
 }
-
-fn display_command_names() -> Vec<String> {
-    COMMAND_SPECS
-        .iter()
-        .map(|spec| spec.name().to_string())
-        .collect()
-}
-
-fn resolve_command(name: &str) -> Option<PaletteCommand> {
-    COMMAND_SPECS
-        .iter()
-        .copied()
-        .find(|spec| spec.name() == name || spec.aliases().contains(&name))
-}
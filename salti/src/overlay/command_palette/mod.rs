@@ -3,7 +3,9 @@ mod command_runners;
 mod command_spec;
 mod completers;
 mod input;
+mod ranges;
 mod ui;
 mod utils;
+mod validators;
 
 pub use input::CommandPaletteState;
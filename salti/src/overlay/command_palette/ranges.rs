@@ -0,0 +1,67 @@
+use crate::app::{MAX_RENDER_FPS, MIN_RENDER_FPS};
+use crate::core::orf::DEFAULT_MIN_ORF_CODONS;
+use crate::core::primer::DEFAULT_MAX_PRIMER_MISMATCHES;
+use crate::core::variability::DEFAULT_VARIABLE_COLUMNS;
+use crate::ui::utils::format_thousands;
+
+use super::input::CommandPaletteState;
+
+/// last alignment position `jump-position` accepts, the 1-based index of the last visible column
+pub(super) fn last_position(state: &CommandPaletteState) -> usize {
+    state.visible_columns.last().map_or(0, |&col| col + 1)
+}
+
+pub(super) fn position(state: &CommandPaletteState) -> String {
+    match last_position(state) {
+        0 => "Position: no visible columns".to_string(),
+        last => format!(
+            "Position: 1..{} (alignment columns)",
+            format_thousands(last)
+        ),
+    }
+}
+
+pub(super) fn region(state: &CommandPaletteState) -> String {
+    match state.visible_columns.len() {
+        0 => "Columns: no visible columns".to_string(),
+        columns => format!(
+            "Columns: 1..{} (visible columns)",
+            format_thousands(columns)
+        ),
+    }
+}
+
+pub(super) fn gap_percentage(_: &CommandPaletteState) -> String {
+    "Gap percentage: 0..100, 0 turns the filter off".to_string()
+}
+
+pub(super) fn identity(_: &CommandPaletteState) -> String {
+    "Identity: above 0 up to 100 %".to_string()
+}
+
+pub(super) fn fps(_: &CommandPaletteState) -> String {
+    format!("Frame rate: {MIN_RENDER_FPS}..{MAX_RENDER_FPS} fps")
+}
+
+pub(super) fn orf_codons(_: &CommandPaletteState) -> String {
+    format!("Minimum length: 1 or more codons, default {DEFAULT_MIN_ORF_CODONS}")
+}
+
+pub(super) fn variable_columns(state: &CommandPaletteState) -> String {
+    format!(
+        "Columns: 1..{}, default {DEFAULT_VARIABLE_COLUMNS}",
+        format_thousands(state.visible_columns.len().max(1))
+    )
+}
+
+pub(super) fn primer_mismatches(_: &CommandPaletteState) -> String {
+    format!("Mismatches: 0 or more, default {DEFAULT_MAX_PRIMER_MISMATCHES}")
+}
+
+pub(super) fn frame(_: &CommandPaletteState) -> String {
+    "Frame: 1..3".to_string()
+}
+
+pub(super) fn consensus_threshold(_: &CommandPaletteState) -> String {
+    "Minimum support: 0..100 %, default 0".to_string()
+}
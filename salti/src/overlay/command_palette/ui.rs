@@ -5,10 +5,11 @@ use ratatui::text::Line;
 use ratatui::widgets::{Block, Clear, Paragraph, Widget};
 
 use crate::core::search::SearchableList;
+use crate::ui::utils::wrap_text;
 
 use super::command_spec::PaletteCommand;
 use super::input::{CommandPaletteState, PaletteState};
-use super::utils::{pad_label, wrap_text};
+use super::utils::pad_label;
 
 /// maximum number of rows shown in the command/preview grid at once.
 const COMMAND_GRID_MAX_VISIBLE_ROWS: usize = 6;
@@ -21,6 +22,7 @@ const COMMAND_GRID_COLUMN_GAP_TEXT: &str = "  ";
 
 impl CommandPaletteState {
     fn help_overlay_lines(
+        &self,
         spec: PaletteCommand,
         width: usize,
         theme: &crate::config::theme::ThemeStyles,
@@ -29,22 +31,29 @@ impl CommandPaletteState {
             .into_iter()
             .map(|line| Line::from(line.set_style(theme.text)));
 
+        let range_lines = spec
+            .range_hint(self)
+            .into_iter()
+            .flat_map(|line| wrap_text(line.as_str(), width).into_iter())
+            .map(|line| Line::from(line.set_style(theme.accent)));
+
         let alias_lines = (!spec.aliases().is_empty())
             .then(|| format!("Aliases: {}", spec.aliases().join(", ")))
             .into_iter()
             .flat_map(|line| wrap_text(line.as_str(), width).into_iter())
             .map(|line| Line::from(line.set_style(theme.text_muted)));
 
-        help_lines.chain(alias_lines).collect()
+        help_lines.chain(range_lines).chain(alias_lines).collect()
     }
 
     fn render_command_help_overlay(
+        &self,
         f: &mut Frame,
         area: Rect,
         theme: &crate::config::theme::ThemeStyles,
         spec: PaletteCommand,
     ) {
-        let lines = Self::help_overlay_lines(spec, area.width.saturating_sub(2) as usize, theme);
+        let lines = self.help_overlay_lines(spec, area.width.saturating_sub(2) as usize, theme);
         let height = (lines.len().saturating_add(2) as u16).min(area.height);
         if height < 2 {
             return;
@@ -64,11 +73,12 @@ impl CommandPaletteState {
     }
 
     fn help_overlay_required_rows(
+        &self,
         spec: PaletteCommand,
         width: usize,
         theme: &crate::config::theme::ThemeStyles,
     ) -> usize {
-        Self::help_overlay_lines(
+        self.help_overlay_lines(
             spec,
             width.saturating_div(3).max(3).saturating_sub(2),
             theme,
@@ -175,6 +185,17 @@ impl CommandPaletteState {
             .min(COMMAND_GRID_MAX_VISIBLE_ROWS)
     }
 
+    fn argument_hint_line(
+        &self,
+        theme: &crate::config::theme::ThemeStyles,
+    ) -> Option<Line<'static>> {
+        let line = match self.argument_hint.as_ref()? {
+            Ok(message) => Line::from(format!("✓ {message}").set_style(theme.success)),
+            Err(message) => Line::from(format!("✗ {message}").set_style(theme.error)),
+        };
+        Some(line)
+    }
+
     fn render_help_and_preview(
         &self,
         f: &mut Frame,
@@ -182,7 +203,10 @@ impl CommandPaletteState {
         theme: &crate::config::theme::ThemeStyles,
         spec: PaletteCommand,
     ) {
-        let lines = self.preview_lines(spec, area.width as usize, area.height as usize, theme);
+        let hint = self.argument_hint_line(theme);
+        let preview_rows = (area.height as usize).saturating_sub(usize::from(hint.is_some()));
+        let mut lines: Vec<Line<'_>> = hint.into_iter().collect();
+        lines.extend(self.preview_lines(spec, area.width as usize, preview_rows, theme));
         f.render_widget(Paragraph::new(lines).style(theme.panel_block), area);
     }
 
@@ -259,7 +283,8 @@ impl CommandPaletteState {
         let area_height = area.height as usize;
         let help_spec = self.help_overlay_spec();
         let help_height = help_spec.map_or(0, |spec| {
-            Self::help_overlay_required_rows(spec, area_width, theme).min(area_height) as u16
+            self.help_overlay_required_rows(spec, area_width, theme)
+                .min(area_height) as u16
         });
 
         let (help_area, content_area) = if help_height == 0 {
@@ -293,7 +318,7 @@ impl CommandPaletteState {
         }
 
         if let (Some(help_area), Some(spec)) = (help_area, help_spec) {
-            Self::render_command_help_overlay(f, help_area, theme, spec);
+            self.render_command_help_overlay(f, help_area, theme, spec);
         }
     }
 
@@ -302,7 +327,7 @@ impl CommandPaletteState {
         let max_height = content_area.height as usize;
 
         let rows = if let Some(spec) = self.current_command_context() {
-            self.preview_required_rows(spec, width)
+            self.preview_required_rows(spec, width) + usize::from(self.argument_hint.is_some())
         } else {
             self.command_list
                 .visible_len()
@@ -311,7 +336,7 @@ impl CommandPaletteState {
         };
 
         let help_rows = self.help_overlay_spec().map_or(0, |spec| {
-            Self::help_overlay_required_rows(spec, width, theme)
+            self.help_overlay_required_rows(spec, width, theme)
         });
 
         rows.saturating_add(help_rows).min(max_height) as u16
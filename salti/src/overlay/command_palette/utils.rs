@@ -1,6 +1,12 @@
 use crate::ui::utils::truncate_label;
 
 pub(super) fn parse_argument(input: &str) -> Option<String> {
+    parse_arguments(input).into_iter().next()
+}
+
+/// splits palette input on whitespace, keeping quoted text together
+pub(super) fn parse_arguments(input: &str) -> Vec<String> {
+    let mut arguments = Vec::new();
     let mut current = String::new();
     let mut quote: Option<char> = None;
 
@@ -18,14 +24,31 @@ pub(super) fn parse_argument(input: &str) -> Option<String> {
             quote = Some(character);
         } else if character.is_whitespace() {
             if !current.is_empty() {
-                break;
+                arguments.push(std::mem::take(&mut current));
             }
         } else {
             current.push(character);
         }
     }
 
-    (!current.is_empty()).then_some(current)
+    if !current.is_empty() {
+        arguments.push(current);
+    }
+    arguments
+}
+
+/// expands a leading `~` to the home directory, as a shell would
+pub(super) fn expand_tilde(path: &str) -> String {
+    let Some(rest) = path.strip_prefix('~') else {
+        return path.to_string();
+    };
+    if !(rest.is_empty() || rest.starts_with('/')) {
+        return path.to_string();
+    }
+    match std::env::var_os("HOME") {
+        Some(home) => format!("{}{rest}", home.to_string_lossy()),
+        None => path.to_string(),
+    }
 }
 
 pub(super) fn pad_label(label: &str, width: usize) -> (String, String) {
@@ -33,35 +56,3 @@ pub(super) fn pad_label(label: &str, width: usize) -> (String, String) {
     let padding = width.saturating_sub(text.chars().count());
     (text, " ".repeat(padding))
 }
-
-pub(super) fn wrap_text(text: &str, width: usize) -> Vec<String> {
-    if width == 0 {
-        return Vec::new();
-    }
-
-    let mut lines = Vec::new();
-    for raw_line in text.split('\n') {
-        let mut current = String::new();
-        for word in raw_line.split_whitespace() {
-            if current.is_empty() {
-                current.push_str(word);
-                continue;
-            }
-            if current.len() + 1 + word.len() > width {
-                lines.push(current);
-                current = word.to_string();
-            } else {
-                current.push(' ');
-                current.push_str(word);
-            }
-        }
-        if !current.is_empty() {
-            lines.push(current);
-        }
-    }
-
-    if lines.is_empty() {
-        lines.push(String::new());
-    }
-    lines
-}
@@ -0,0 +1,188 @@
+use anyhow::{Result, format_err};
+
+use super::command_runners::{
+    JumpTarget, find_sequence, parse_fps, parse_genetic_code, parse_jump_target, parse_region,
+};
+use super::input::CommandPaletteState;
+use super::ranges::last_position;
+
+pub(super) fn regex(state: &CommandPaletteState, arguments: &str) -> Result<String> {
+    let regex = regex::Regex::new(arguments).map_err(|_| format_err!("Invalid regex"))?;
+    let matches = state
+        .selectable_sequences
+        .iter()
+        .filter(|sequence| regex.is_match(&sequence.sequence_name))
+        .count();
+    Ok(format!(
+        "Matches {matches} of {} sequences",
+        state.selectable_sequences.len()
+    ))
+}
+
+pub(super) fn position(state: &CommandPaletteState, arguments: &str) -> Result<String> {
+    let last_column = last_position(state);
+    let position = match parse_jump_target(arguments)? {
+        JumpTarget::Position(position) | JumpTarget::Range { end: position, .. }
+            if position > last_column =>
+        {
+            return Err(format_err!("position must be 1..{last_column}"));
+        }
+        JumpTarget::Position(position) => position,
+        JumpTarget::Range {
+            start,
+            end,
+            highlight,
+        } => {
+            let action = if highlight { "Highlight" } else { "Centre" };
+            return Ok(format!("{action} columns {start}-{end}"));
+        }
+        JumpTarget::Offset(offset) if offset < 0 => {
+            return Ok(format!("Scroll left {} columns", offset.unsigned_abs()));
+        }
+        JumpTarget::Offset(offset) => return Ok(format!("Scroll right {offset} columns")),
+        JumpTarget::Percent(percent) => {
+            return Ok(format!("{percent}% through the visible columns"));
+        }
+    };
+    let target = state
+        .visible_columns
+        .partition_point(|&col| col < position - 1);
+    match state.visible_columns.get(target) {
+        Some(&col) if col + 1 != position => {
+            Ok(format!("Column {position} is hidden, jumps to {}", col + 1))
+        }
+        _ => Ok(format!("Column {position} of {last_column}")),
+    }
+}
+
+pub(super) fn genetic_code(_: &CommandPaletteState, arguments: &str) -> Result<String> {
+    parse_genetic_code(arguments).map(ToString::to_string)
+}
+
+pub(super) fn percentage(_: &CommandPaletteState, arguments: &str) -> Result<String> {
+    match arguments.parse::<f32>() {
+        Ok(percent) if percent.is_finite() && (0.0..=100.0).contains(&percent) => {
+            Ok(format!("{percent}%"))
+        }
+        _ => Err(format_err!("Expected a percentage in 0..=100")),
+    }
+}
+
+pub(super) fn region(state: &CommandPaletteState, arguments: &str) -> Result<String> {
+    let (start, end) = parse_region(state, arguments)?;
+    Ok(format!("Keep {} columns", end - start + 1))
+}
+
+pub(super) fn fps(_: &CommandPaletteState, arguments: &str) -> Result<String> {
+    parse_fps(arguments).map(|_| format!("{arguments} fps"))
+}
+
+/// mirrors the name-then-number lookup of the sequence commands
+pub(super) fn sequence(state: &CommandPaletteState, arguments: &str) -> Result<String> {
+    let by_name = state
+        .selectable_sequences
+        .iter()
+        .find(|sequence| sequence.sequence_name.as_ref() == arguments);
+    let by_number = || {
+        let sequence_id = arguments.parse::<usize>().ok()?.checked_sub(1)?;
+        state
+            .selectable_sequences
+            .iter()
+            .find(|sequence| sequence.sequence_id == sequence_id)
+    };
+    by_name
+        .or_else(by_number)
+        .map(|sequence| {
+            format!(
+                "Sequence {}: {}",
+                sequence.sequence_id + 1,
+                sequence.sequence_name
+            )
+        })
+        .ok_or_else(|| format_err!("Sequence not found"))
+}
+
+/// like `sequence`, but also resolves `#42` and patterns the way `jump-sequence` does
+pub(super) fn jump_sequence(state: &CommandPaletteState, arguments: &str) -> Result<String> {
+    let argument = arguments.split_whitespace().next().unwrap_or_default();
+    find_sequence(&state.selectable_sequences, argument)
+        .map(|sequence| {
+            format!(
+                "Sequence {}: {}",
+                sequence.sequence_id + 1,
+                sequence.sequence_name
+            )
+        })
+        .ok_or_else(|| format_err!("Sequence not found"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::overlay::command_palette::input::VisibleSequence;
+
+    fn state() -> CommandPaletteState {
+        let sequence = |sequence_id: usize, name: &str| VisibleSequence {
+            sequence_id,
+            sequence_name: name.into(),
+        };
+        CommandPaletteState::new(
+            vec![sequence(0, "alpha"), sequence(2, "beta")],
+            Vec::new(),
+            libmsa::AlignmentType::Dna,
+            vec![0, 1, 4, 5],
+        )
+    }
+
+    #[test]
+    fn filter_and_position_hints_describe_the_result() {
+        let state = state();
+
+        assert_eq!(regex(&state, "^a").unwrap(), "Matches 1 of 2 sequences");
+        assert!(regex(&state, "(").is_err());
+        assert_eq!(position(&state, "2").unwrap(), "Column 2 of 6");
+        assert_eq!(
+            position(&state, "3").unwrap(),
+            "Column 3 is hidden, jumps to 5"
+        );
+        assert_eq!(
+            position(&state, "7").unwrap_err().to_string(),
+            "position must be 1..6"
+        );
+        assert!(position(&state, "0").is_err());
+        assert_eq!(position(&state, "+10").unwrap(), "Scroll right 10 columns");
+        assert_eq!(position(&state, "2-4 hl").unwrap(), "Highlight columns 2-4");
+    }
+
+    #[test]
+    fn sequence_hint_accepts_names_and_numbers() {
+        let state = state();
+
+        assert_eq!(sequence(&state, "3").unwrap(), "Sequence 3: beta");
+        assert_eq!(
+            jump_sequence(&state, "#3 beta").unwrap(),
+            "Sequence 3: beta"
+        );
+        assert_eq!(jump_sequence(&state, "b*").unwrap(), "Sequence 3: beta");
+        assert_eq!(sequence(&state, "alpha").unwrap(), "Sequence 1: alpha");
+        assert!(sequence(&state, "2").is_err());
+        assert!(percentage(&state, "120").is_err());
+    }
+
+    #[test]
+    fn numeric_hints_check_the_accepted_range() {
+        let state = state();
+
+        assert_eq!(region(&state, "2-4").unwrap(), "Keep 3 columns");
+        assert_eq!(
+            region(&state, "2-5").unwrap_err().to_string(),
+            "region must be within 1..4"
+        );
+        assert_eq!(fps(&state, "60").unwrap(), "60 fps");
+        assert!(fps(&state, "0").is_err());
+        assert_eq!(
+            crate::overlay::command_palette::ranges::position(&state),
+            "Position: 1..6 (alignment columns)"
+        );
+    }
+}
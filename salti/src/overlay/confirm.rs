@@ -0,0 +1,81 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::Styled;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+
+use crate::command::Command;
+use crate::config::theme::ThemeStyles;
+use crate::ui::notification::{Notification, NotificationLevel};
+
+/// a command held back until the user answers a y/n prompt on the input line
+#[derive(Debug)]
+pub struct ConfirmState {
+    prompt: String,
+    command: Command,
+}
+
+impl ConfirmState {
+    pub const fn new(prompt: String, command: Command) -> Self {
+        Self { prompt, command }
+    }
+
+    /// `y` runs the pending command and any other key abandons it
+    pub fn handle_key_event(&self, key: KeyEvent) -> Vec<Command> {
+        match key.code {
+            KeyCode::Char('y' | 'Y') => vec![
+                Command::CloseOverlay,
+                Command::Confirmed(Box::new(self.command.clone())),
+            ],
+            _ => vec![
+                Command::CloseOverlay,
+                Command::ShowNotification(Notification {
+                    level: NotificationLevel::Info,
+                    message: "Cancelled".to_string(),
+                }),
+            ],
+        }
+    }
+
+    pub fn render(&self, f: &mut Frame, input_area: Rect, styles: &ThemeStyles) {
+        let line = Line::from(vec![
+            self.prompt.as_str().set_style(styles.warning),
+            " (y/n)".set_style(styles.text_muted),
+        ]);
+        f.render_widget(Paragraph::new(line).style(styles.base_block), input_area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn confirm() -> ConfirmState {
+        ConfirmState::new("Overwrite out.fa?".to_string(), Command::ClearMask)
+    }
+
+    #[test]
+    fn yes_runs_the_pending_command() {
+        assert_eq!(
+            confirm().handle_key_event(KeyEvent::from(KeyCode::Char('y'))),
+            vec![
+                Command::CloseOverlay,
+                Command::Confirmed(Box::new(Command::ClearMask))
+            ]
+        );
+    }
+
+    #[test]
+    fn any_other_key_cancels() {
+        for code in [KeyCode::Char('n'), KeyCode::Esc, KeyCode::Enter] {
+            let commands = confirm().handle_key_event(KeyEvent::from(code));
+            assert_eq!(commands.first(), Some(&Command::CloseOverlay));
+            assert!(
+                !commands
+                    .iter()
+                    .any(|command| matches!(command, Command::Confirmed(_)))
+            );
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::Styled;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+
+use crate::command::Command;
+use crate::config::theme::ThemeStyles;
+
+/// percentage points the threshold moves per key press
+const THRESHOLD_STEP: u8 = 5;
+/// percentage points the threshold moves per key press with Shift held
+const FINE_THRESHOLD_STEP: u8 = 1;
+/// cells in the slider track
+const SLIDER_WIDTH: usize = 20;
+
+/// slider on the input line for the consensus threshold. every change is applied at once, so
+/// the consensus pane redraws from the cached column stats; Esc puts back the threshold the
+/// slider opened with.
+#[derive(Debug)]
+pub struct ConsensusThresholdState {
+    original: u8,
+    percent: u8,
+}
+
+impl ConsensusThresholdState {
+    pub fn new(threshold: f32) -> Self {
+        let percent = (threshold * 100.0).round().clamp(0.0, 100.0) as u8;
+        Self {
+            original: percent,
+            percent,
+        }
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent) -> Vec<Command> {
+        let step = if key.modifiers.contains(KeyModifiers::SHIFT) {
+            FINE_THRESHOLD_STEP
+        } else {
+            THRESHOLD_STEP
+        };
+        let percent = match key.code {
+            KeyCode::Left | KeyCode::Char('h') => self.percent.saturating_sub(step),
+            KeyCode::Right | KeyCode::Char('l') => (self.percent + step).min(100),
+            KeyCode::Home => 0,
+            KeyCode::End => 100,
+            KeyCode::Enter => return vec![Command::CloseOverlay],
+            KeyCode::Esc => {
+                let mut commands = vec![Command::CloseOverlay];
+                if self.percent != self.original {
+                    commands.push(Command::SetConsensusThreshold(
+                        f32::from(self.original) / 100.0,
+                    ));
+                }
+                return commands;
+            }
+            _ => return Vec::new(),
+        };
+        if percent == self.percent {
+            return Vec::new();
+        }
+        self.percent = percent;
+        vec![Command::SetConsensusThreshold(f32::from(percent) / 100.0)]
+    }
+
+    pub fn render(&self, f: &mut Frame, input_area: Rect, styles: &ThemeStyles) {
+        let filled = usize::from(self.percent) * SLIDER_WIDTH / 100;
+        let line = Line::from(vec![
+            "Consensus threshold ".set_style(styles.accent),
+            "█".repeat(filled).set_style(styles.accent),
+            "░".repeat(SLIDER_WIDTH - filled).set_style(styles.text_dim),
+            format!(" {:>3}%", self.percent).set_style(styles.text),
+            " (←/→ adjust, Shift for 1%, Enter to keep, Esc to revert)"
+                .set_style(styles.text_muted),
+        ]);
+        f.render_widget(Paragraph::new(line).style(styles.base_block), input_area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arrows_apply_the_threshold_as_it_changes() {
+        let mut slider = ConsensusThresholdState::new(0.5);
+
+        assert_eq!(
+            slider.handle_key_event(KeyEvent::from(KeyCode::Right)),
+            vec![Command::SetConsensusThreshold(0.55)]
+        );
+        assert_eq!(
+            slider.handle_key_event(KeyEvent::new(KeyCode::Left, KeyModifiers::SHIFT)),
+            vec![Command::SetConsensusThreshold(0.54)]
+        );
+        assert_eq!(
+            slider.handle_key_event(KeyEvent::from(KeyCode::End)),
+            vec![Command::SetConsensusThreshold(1.0)]
+        );
+        assert!(
+            slider
+                .handle_key_event(KeyEvent::from(KeyCode::Right))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn escape_restores_the_opening_threshold() {
+        let mut slider = ConsensusThresholdState::new(0.6);
+        slider.handle_key_event(KeyEvent::from(KeyCode::Home));
+
+        assert_eq!(
+            slider.handle_key_event(KeyEvent::from(KeyCode::Esc)),
+            vec![Command::CloseOverlay, Command::SetConsensusThreshold(0.6)]
+        );
+        assert_eq!(
+            ConsensusThresholdState::new(0.6).handle_key_event(KeyEvent::from(KeyCode::Enter)),
+            vec![Command::CloseOverlay]
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::Styled;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Clear, Paragraph, Widget};
+
+use crate::command::Command;
+use crate::config::theme::ThemeStyles;
+use crate::ui::utils::truncate_label;
+
+/// width of the sequence context menu in columns, including borders
+const CONTEXT_MENU_WIDTH: u16 = 26;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SequenceAction {
+    TogglePin,
+    Hide,
+    SetReference,
+    CopyName,
+    ShowUngapped,
+}
+
+const SEQUENCE_ACTIONS: [SequenceAction; 5] = [
+    SequenceAction::TogglePin,
+    SequenceAction::Hide,
+    SequenceAction::SetReference,
+    SequenceAction::CopyName,
+    SequenceAction::ShowUngapped,
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextMenuState {
+    sequence_id: usize,
+    sequence_name: String,
+    pinned: bool,
+    anchor: (u16, u16),
+    selected: usize,
+}
+
+impl ContextMenuState {
+    pub fn new(
+        sequence_id: usize,
+        sequence_name: String,
+        pinned: bool,
+        anchor: (u16, u16),
+    ) -> Self {
+        Self {
+            sequence_id,
+            sequence_name,
+            pinned,
+            anchor,
+            selected: 0,
+        }
+    }
+
+    fn label(&self, action: SequenceAction) -> &'static str {
+        match action {
+            SequenceAction::TogglePin if self.pinned => "Unpin",
+            SequenceAction::TogglePin => "Pin",
+            SequenceAction::Hide => "Hide",
+            SequenceAction::SetReference => "Set as reference",
+            SequenceAction::CopyName => "Copy name",
+            SequenceAction::ShowUngapped => "Show without gaps",
+        }
+    }
+
+    fn command_for(&self, action: SequenceAction) -> Command {
+        match action {
+            SequenceAction::TogglePin if self.pinned => Command::UnpinSequence(self.sequence_id),
+            SequenceAction::TogglePin => Command::PinSequence(self.sequence_id),
+            SequenceAction::Hide => Command::HideSequence(self.sequence_id),
+            SequenceAction::SetReference => Command::SetReference(self.sequence_id),
+            SequenceAction::CopyName => Command::CopyToClipboard(self.sequence_name.clone()),
+            SequenceAction::ShowUngapped => Command::ShowUngapped(Some(self.sequence_id)),
+        }
+    }
+
+    fn run_selected(&self) -> Vec<Command> {
+        vec![
+            self.command_for(SEQUENCE_ACTIONS[self.selected]),
+            Command::CloseOverlay,
+        ]
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent) -> Vec<Command> {
+        match key.code {
+            KeyCode::Esc => vec![Command::CloseOverlay],
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+                Vec::new()
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(SEQUENCE_ACTIONS.len() - 1);
+                Vec::new()
+            }
+            KeyCode::Enter => self.run_selected(),
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn handle_mouse(&mut self, mouse: MouseEvent, bounds: Rect) -> Vec<Command> {
+        let entries_area = Block::bordered().inner(self.area(bounds));
+        let hovered = entries_area
+            .contains((mouse.column, mouse.row).into())
+            .then(|| usize::from(mouse.row - entries_area.y));
+
+        match (mouse.kind, hovered) {
+            (MouseEventKind::Moved, Some(index)) => {
+                self.selected = index;
+                Vec::new()
+            }
+            (MouseEventKind::Down(MouseButton::Left), Some(index)) => {
+                self.selected = index;
+                self.run_selected()
+            }
+            (MouseEventKind::Down(_), None) => vec![Command::CloseOverlay],
+            _ => Vec::new(),
+        }
+    }
+
+    /// places the menu at its anchor, shifted so it stays inside `bounds`
+    pub fn area(&self, bounds: Rect) -> Rect {
+        let width = CONTEXT_MENU_WIDTH.min(bounds.width);
+        let height = (SEQUENCE_ACTIONS.len() as u16 + 2).min(bounds.height);
+        let max_x = bounds.right().saturating_sub(width);
+        let max_y = bounds.bottom().saturating_sub(height);
+        let (anchor_x, anchor_y) = self.anchor;
+        Rect::new(
+            anchor_x.clamp(bounds.x, max_x.max(bounds.x)),
+            anchor_y.clamp(bounds.y, max_y.max(bounds.y)),
+            width,
+            height,
+        )
+    }
+
+    pub fn render(&self, f: &mut Frame, bounds: Rect, styles: &ThemeStyles) {
+        let area = self.area(bounds);
+        let title_width = usize::from(area.width.saturating_sub(4));
+        let lines: Vec<Line> = SEQUENCE_ACTIONS
+            .iter()
+            .enumerate()
+            .map(|(index, &action)| {
+                let style = if index == self.selected {
+                    styles.selection
+                } else {
+                    styles.text
+                };
+                Line::from(format!(" {}", self.label(action)).set_style(style))
+            })
+            .collect();
+
+        Clear.render(area, f.buffer_mut());
+        f.render_widget(
+            Paragraph::new(lines).block(
+                Block::bordered()
+                    .title(Line::from(
+                        truncate_label(&self.sequence_name, title_width).set_style(styles.accent),
+                    ))
+                    .border_style(styles.border)
+                    .style(styles.panel_block),
+            ),
+            area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+
+    use super::*;
+
+    fn menu(pinned: bool) -> ContextMenuState {
+        ContextMenuState::new(3, "seq3".to_string(), pinned, (10, 5))
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::empty(),
+        }
+    }
+
+    #[test]
+    fn enter_runs_selected_action_and_closes() {
+        let mut state = menu(true);
+
+        let commands = state.handle_key_event(KeyEvent::from(KeyCode::Enter));
+
+        assert_eq!(
+            commands,
+            vec![Command::UnpinSequence(3), Command::CloseOverlay]
+        );
+    }
+
+    #[test]
+    fn clicking_an_entry_runs_it() {
+        let mut state = menu(false);
+        let bounds = Rect::new(0, 0, 80, 24);
+        let entries = Block::bordered().inner(state.area(bounds));
+
+        let commands = state.handle_mouse(
+            mouse(
+                MouseEventKind::Down(MouseButton::Left),
+                entries.x,
+                entries.y + 3,
+            ),
+            bounds,
+        );
+
+        assert_eq!(
+            commands,
+            vec![
+                Command::CopyToClipboard("seq3".to_string()),
+                Command::CloseOverlay
+            ]
+        );
+    }
+
+    #[test]
+    fn clicking_outside_closes_the_menu() {
+        let mut state = menu(false);
+
+        let commands = state.handle_mouse(
+            mouse(MouseEventKind::Down(MouseButton::Right), 0, 0),
+            Rect::new(0, 0, 80, 24),
+        );
+
+        assert_eq!(commands, vec![Command::CloseOverlay]);
+    }
+
+    #[test]
+    fn menu_is_shifted_to_stay_inside_bounds() {
+        let state = ContextMenuState::new(0, "seq".to_string(), false, (78, 23));
+
+        let area = state.area(Rect::new(0, 0, 80, 24));
+
+        assert_eq!(area.right(), 80);
+        assert_eq!(area.bottom(), 24);
+    }
+}
@@ -0,0 +1,339 @@
+use std::path::{Path, PathBuf};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::Styled;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Clear, Paragraph, Widget};
+
+use crate::command::Command;
+use crate::config::theme::ThemeStyles;
+use crate::core::dir_listing::DirListing;
+use crate::core::search::{Direction, FilterMode, SearchableList};
+use crate::ui::utils::truncate_label;
+
+/// widest the picker grows, in columns including borders
+const PICKER_MAX_WIDTH: u16 = 80;
+/// entry that moves to the parent directory
+const PARENT_ENTRY: &str = "../";
+/// extensions offered unless every file is shown. compressed copies of these are offered too.
+const ALIGNMENT_EXTENSIONS: &[&str] = &[
+    "fa", "fasta", "fas", "fna", "faa", "ffn", "afa", "mfa", "a2m", "nex", "nexus", "nxs",
+];
+const COMPRESSED_EXTENSIONS: &[&str] = &["gz", "bgz", "bz2", "xz", "zst"];
+
+/// whether `name` looks like an alignment file salti can open
+fn is_alignment_file(name: &str) -> bool {
+    let path = Path::new(name);
+    let path = match path.extension().and_then(|extension| extension.to_str()) {
+        Some(extension)
+            if COMPRESSED_EXTENSIONS
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(extension)) =>
+        {
+            Path::new(path.file_stem().unwrap_or_default())
+        }
+        _ => path,
+    };
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            ALIGNMENT_EXTENSIONS
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(extension))
+        })
+}
+
+/// fuzzy file browser for choosing an alignment without leaving the TUI. directories are listed
+/// in the background, so entries appear while a large directory is still being read.
+#[derive(Debug)]
+pub struct FilePickerState {
+    dir: PathBuf,
+    listing: DirListing,
+    list: SearchableList,
+    query: String,
+    all_files: bool,
+}
+
+impl FilePickerState {
+    pub fn new(dir: PathBuf) -> Self {
+        let dir = dir.canonicalize().unwrap_or(dir);
+        let mut picker = Self {
+            listing: DirListing::start(dir.clone()),
+            dir,
+            list: SearchableList::new(FilterMode::Fuzzy, Some(PARENT_ENTRY.to_string())),
+            query: String::new(),
+            all_files: false,
+        };
+        picker.refresh_items();
+        picker
+    }
+
+    fn refresh_items(&mut self) {
+        let show_hidden = self.query.starts_with('.');
+        let mut entries: Vec<(bool, String)> = self
+            .listing
+            .entries()
+            .into_iter()
+            .filter(|entry| show_hidden || !entry.name.starts_with('.'))
+            .filter(|entry| entry.is_dir || self.all_files || is_alignment_file(&entry.name))
+            .map(|entry| {
+                if entry.is_dir {
+                    (false, format!("{}/", entry.name))
+                } else {
+                    (true, entry.name)
+                }
+            })
+            .collect();
+        entries.sort();
+
+        self.list.set_items_and_query(
+            entries.into_iter().map(|(_, label)| label).collect(),
+            &self.query,
+        );
+        if self.list.selected_display_index().is_none() {
+            self.list.move_selection_wrapped(Direction::Forward);
+        }
+    }
+
+    /// picks up entries read since the last poll. returns whether anything changed.
+    pub fn poll_listing(&mut self) -> bool {
+        let changed = self.listing.take_changed();
+        if changed {
+            self.refresh_items();
+        }
+        changed
+    }
+
+    fn enter_dir(&mut self, dir: PathBuf) {
+        self.listing = DirListing::start(dir.clone());
+        self.dir = dir;
+        self.query.clear();
+        self.list.reset_selection();
+        self.refresh_items();
+    }
+
+    fn enter_parent(&mut self) {
+        if let Some(parent) = self.dir.parent() {
+            self.enter_dir(parent.to_path_buf());
+        }
+    }
+
+    fn open_selected(&mut self) -> Vec<Command> {
+        let Some(label) = self.list.selected_label().map(str::to_string) else {
+            return Vec::new();
+        };
+        if label == PARENT_ENTRY {
+            self.enter_parent();
+        } else if let Some(name) = label.strip_suffix('/') {
+            self.enter_dir(self.dir.join(name));
+        } else {
+            return vec![
+                Command::CloseOverlay,
+                Command::LoadFile {
+                    input: self.dir.join(label).display().to_string(),
+                },
+            ];
+        }
+        Vec::new()
+    }
+
+    fn set_query(&mut self, query: String) {
+        self.query = query;
+        self.list.reset_selection();
+        self.refresh_items();
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent) -> Vec<Command> {
+        match key.code {
+            KeyCode::Esc => return vec![Command::CloseOverlay],
+            KeyCode::Enter => return self.open_selected(),
+            KeyCode::Up | KeyCode::BackTab => self.list.move_selection_wrapped(Direction::Backward),
+            KeyCode::Down | KeyCode::Tab => self.list.move_selection_wrapped(Direction::Forward),
+            KeyCode::Backspace if self.query.is_empty() => self.enter_parent(),
+            KeyCode::Backspace => {
+                let mut query = self.query.clone();
+                query.pop();
+                self.set_query(query);
+            }
+            KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.all_files = !self.all_files;
+                self.refresh_items();
+            }
+            KeyCode::Char(character) => self.set_query(format!("{}{character}", self.query)),
+            _ => (),
+        }
+        Vec::new()
+    }
+
+    pub fn handle_mouse(&mut self, mouse: MouseEvent, bounds: Rect) -> Vec<Command> {
+        let area = Self::area(bounds);
+        let entries_area = Self::entries_area(area);
+        let clicked = entries_area
+            .contains((mouse.column, mouse.row).into())
+            .then(|| {
+                self.scroll_offset(entries_area.height) + usize::from(mouse.row - entries_area.y)
+            })
+            .filter(|&index| index < self.list.visible_len());
+
+        match (mouse.kind, clicked) {
+            (MouseEventKind::ScrollUp, _) => self.list.move_selection_wrapped(Direction::Backward),
+            (MouseEventKind::ScrollDown, _) => self.list.move_selection_wrapped(Direction::Forward),
+            (MouseEventKind::Down(MouseButton::Left), Some(index)) => {
+                while self.list.selected_display_index() != Some(index) {
+                    self.list.move_selection_wrapped(Direction::Forward);
+                }
+                return self.open_selected();
+            }
+            (MouseEventKind::Down(_), _) if !area.contains((mouse.column, mouse.row).into()) => {
+                return vec![Command::CloseOverlay];
+            }
+            _ => (),
+        }
+        Vec::new()
+    }
+
+    /// first entry shown so that the selection stays in view
+    fn scroll_offset(&self, visible_rows: u16) -> usize {
+        let visible_rows = usize::from(visible_rows).max(1);
+        let selected = self.list.selected_display_index().unwrap_or_default();
+        (selected + 1).saturating_sub(visible_rows)
+    }
+
+    /// centres the picker inside `bounds`, taking two thirds of the height
+    fn area(bounds: Rect) -> Rect {
+        let width = PICKER_MAX_WIDTH.min(bounds.width);
+        let height = (bounds.height.saturating_mul(2) / 3)
+            .max(5)
+            .min(bounds.height);
+        Rect::new(
+            bounds.x + (bounds.width - width) / 2,
+            bounds.y + (bounds.height - height) / 2,
+            width,
+            height,
+        )
+    }
+
+    /// rows below the query line
+    fn entries_area(area: Rect) -> Rect {
+        let inner = Block::bordered().inner(area);
+        Rect {
+            y: inner.y.saturating_add(1),
+            height: inner.height.saturating_sub(1),
+            ..inner
+        }
+    }
+
+    pub fn render(&self, f: &mut Frame, bounds: Rect, styles: &ThemeStyles) {
+        let area = Self::area(bounds);
+        let label_width = usize::from(area.width.saturating_sub(4));
+        let title = format!(
+            " {} ",
+            truncate_label(&self.dir.display().to_string(), label_width)
+        );
+        let keys = if self.all_files {
+            " Enter open · Backspace up · Ctrl+A alignments only "
+        } else {
+            " Enter open · Backspace up · Ctrl+A all files "
+        };
+        let block = Block::bordered()
+            .title(Line::from(title.set_style(styles.accent)))
+            .title_bottom(Line::from(keys.set_style(styles.text_dim)))
+            .border_style(styles.border)
+            .style(styles.panel_block);
+        let entries_area = Self::entries_area(area);
+
+        let mut lines = vec![Line::from(
+            format!("> {}█", self.query).set_style(styles.warning),
+        )];
+        let selected = self.list.selected_display_index();
+        lines.extend(
+            (0..self.list.visible_len())
+                .skip(self.scroll_offset(entries_area.height))
+                .take(usize::from(entries_area.height))
+                .filter_map(|index| {
+                    let label = self.list.visible_item_at(index)?;
+                    let style = if selected == Some(index) {
+                        styles.selection
+                    } else if label.ends_with('/') {
+                        styles.accent_alt
+                    } else {
+                        styles.text
+                    };
+                    Some(Line::from(
+                        format!(" {}", truncate_label(label, label_width)).set_style(style),
+                    ))
+                }),
+        );
+
+        Clear.render(area, f.buffer_mut());
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    fn picker_for(dir: &Path) -> FilePickerState {
+        let mut picker = FilePickerState::new(dir.to_path_buf());
+        let started = Instant::now();
+        while picker.list.visible_len() < 3 && started.elapsed() < Duration::from_secs(5) {
+            picker.poll_listing();
+            thread::sleep(Duration::from_millis(5));
+        }
+        picker
+    }
+
+    fn labels(picker: &FilePickerState) -> Vec<&str> {
+        (0..picker.list.visible_len())
+            .filter_map(|index| picker.list.visible_item_at(index))
+            .collect()
+    }
+
+    #[test]
+    fn alignment_extensions_include_compressed_files() {
+        assert!(is_alignment_file("genes.fasta"));
+        assert!(is_alignment_file("genes.FA.gz"));
+        assert!(is_alignment_file("tree.nex"));
+        assert!(!is_alignment_file("notes.txt"));
+        assert!(!is_alignment_file("archive.gz"));
+    }
+
+    #[test]
+    fn picker_lists_directories_then_alignments_and_opens_files() {
+        let dir = std::env::temp_dir().join(format!("salti-picker-{}", std::process::id()));
+        fs::create_dir_all(dir.join("runs")).unwrap();
+        fs::write(dir.join("a.fa"), ">a\nA\n").unwrap();
+        fs::write(dir.join("notes.txt"), "").unwrap();
+
+        let mut picker = picker_for(&dir);
+        assert_eq!(labels(&picker), ["../", "runs/", "a.fa"]);
+
+        picker.handle_key_event(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL));
+        assert_eq!(labels(&picker), ["../", "runs/", "a.fa", "notes.txt"]);
+
+        for character in "a.f".chars() {
+            picker.handle_key_event(KeyEvent::from(KeyCode::Char(character)));
+        }
+        picker.handle_key_event(KeyEvent::from(KeyCode::Down));
+        let commands = picker.handle_key_event(KeyEvent::from(KeyCode::Enter));
+
+        let expected = dir.canonicalize().unwrap().join("a.fa");
+        assert_eq!(
+            commands,
+            vec![
+                Command::CloseOverlay,
+                Command::LoadFile {
+                    input: expected.display().to_string()
+                }
+            ]
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,6 +1,6 @@
 use std::ops::Range;
 
-use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::Frame;
 use ratatui::layout::Rect;
 use ratatui::style::Color;
@@ -10,6 +10,8 @@ use ratatui::widgets::{Block, Clear, Paragraph, Widget};
 use crate::command::Command;
 use crate::config::theme::Theme;
 use crate::core::model::AlignmentModel;
+use crate::core::partition;
+use crate::core::stats_cache::ColumnStatsCache;
 use crate::ui::ui_state::UiState;
 
 /// maximum height of the minimap in rows
@@ -21,18 +23,95 @@ const MINIMAP_COLUMN_SAMPLES_PER_CELL: usize = 8;
 /// number of sampled sequences per minimap cell when estimating colour.
 const MINIMAP_ROW_SAMPLES_PER_CELL: usize = 10;
 
+/// `Left` and `Right` move the highlight box by this fraction of the alignment
+const MINIMAP_KEY_STEPS: usize = 100;
+
+/// bars for the consensus difference row, from a few differing rows up to all of them
+const DIFFERENCE_BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
 #[derive(Debug, Clone, Copy)]
 pub struct MinimapLayout {
     pub area: Rect,
     pub track_area: Rect,
+    /// top row of the track, showing how much each block differs from the consensus
+    pub difference_area: Rect,
+    /// rest of the track, coloured by the sampled majority residue
+    pub colour_area: Rect,
+    /// column left of the track marking which rows are on screen
+    pub row_gauge_area: Rect,
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct MinimapState {
     anchor_columns: Option<usize>,
+    /// first column of the highlight box moved by the keyboard, jumped to on `Enter`
+    target: Option<usize>,
+    /// 1-based alignment position being typed
+    typed: String,
 }
 
 impl MinimapState {
+    /// view column range covered by the highlight box
+    pub fn highlight_window(&self, viewport_column_range: &Range<usize>) -> Range<usize> {
+        match self.target {
+            Some(start) => start..start + viewport_column_range.len(),
+            None => viewport_column_range.clone(),
+        }
+    }
+
+    /// moves the highlight box or edits a typed position. returns `None` for keys the minimap
+    /// leaves to the usual bindings.
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        viewport_column_range: &Range<usize>,
+        total_columns: usize,
+    ) -> Option<Vec<Command>> {
+        if total_columns == 0 {
+            return None;
+        }
+
+        let viewport_cols = viewport_column_range.len().max(1);
+        let last_start = total_columns.saturating_sub(viewport_cols);
+        let start = self.target.unwrap_or(viewport_column_range.start);
+        let step = (total_columns / MINIMAP_KEY_STEPS).max(1);
+
+        match key.code {
+            KeyCode::Left | KeyCode::Char('h') => self.target = Some(start.saturating_sub(step)),
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.target = Some(start.saturating_add(step).min(last_start));
+            }
+            KeyCode::PageUp => self.target = Some(start.saturating_sub(viewport_cols)),
+            KeyCode::PageDown => {
+                self.target = Some(start.saturating_add(viewport_cols).min(last_start));
+            }
+            KeyCode::Home => self.target = Some(0),
+            KeyCode::End => self.target = Some(last_start),
+            KeyCode::Char(digit) if digit.is_ascii_digit() => self.typed.push(digit),
+            KeyCode::Backspace => {
+                self.typed.pop();
+            }
+            KeyCode::Enter => {
+                self.target = None;
+                if !self.typed.is_empty() {
+                    let typed = std::mem::take(&mut self.typed);
+                    let position = typed.parse::<usize>().unwrap_or(usize::MAX);
+                    return Some(vec![Command::JumpToAlignmentPosition(
+                        position.saturating_sub(1),
+                    )]);
+                }
+                return Some(vec![Command::JumpToPosition(start)]);
+            }
+            KeyCode::Esc if self.target.is_some() || !self.typed.is_empty() => {
+                self.target = None;
+                self.typed.clear();
+            }
+            KeyCode::Esc => return Some(vec![Command::CloseOverlay]),
+            _ => return None,
+        }
+        Some(Vec::new())
+    }
+
     pub fn is_dragging(&self) -> bool {
         self.anchor_columns.is_some()
     }
@@ -79,6 +158,7 @@ impl MinimapState {
 
         match mouse.kind {
             MouseEventKind::Down(MouseButton::Left) if in_track => {
+                self.target = None;
                 let column = Self::position_from_mouse(mouse.column, track_area, total_columns);
                 let drag_anchor = if viewport_column_range.contains(&column) {
                     column - viewport_column_range.start
@@ -202,10 +282,101 @@ pub fn highlight_box(track_area: Rect, window: Range<usize>, total_columns: usiz
     ))
 }
 
+/// cells of a gauge `height` cells tall covered by the visible `rows`, at least one cell
+fn row_gauge_thumb(height: u16, rows: &Range<usize>, total_rows: usize) -> Option<Range<u16>> {
+    if height == 0 || total_rows == 0 {
+        return None;
+    }
+    let height = usize::from(height);
+    let start = (rows.start * height / total_rows).min(height - 1);
+    let end = (rows.end * height)
+        .div_ceil(total_rows)
+        .max(start + 1)
+        .min(height);
+    Some(start as u16..end as u16)
+}
+
+/// vertical scroll position: a thumb over the rows on screen, on a dim track
+fn render_row_gauge(
+    f: &mut Frame,
+    area: Rect,
+    rows: &Range<usize>,
+    total_rows: usize,
+    theme: &Theme,
+) {
+    let thumb = row_gauge_thumb(area.height, rows, total_rows);
+    let buffer = f.buffer_mut();
+    for (offset, position) in area.positions().enumerate() {
+        let Some(cell) = buffer.cell_mut(position) else {
+            continue;
+        };
+        if thumb
+            .as_ref()
+            .is_some_and(|thumb| thumb.contains(&(offset as u16)))
+        {
+            cell.set_char('█');
+            cell.set_fg(theme.accent);
+        } else {
+            cell.set_char('│');
+            cell.set_fg(theme.text_dim);
+        }
+    }
+}
+
+/// column range of the alignment summarised by minimap cell `block_index`
+fn block_range(block_index: usize, total_width: usize, total_columns: usize) -> Range<usize> {
+    let block_start = block_index * total_columns / total_width;
+    let block_end = ((block_index + 1) * total_columns)
+        .div_ceil(total_width)
+        .max(block_start + 1)
+        .min(total_columns);
+    block_start..block_end
+}
+
+/// bar for the mean difference fraction of a block, blank where every row agrees
+fn difference_bar(fractions: &[f32]) -> char {
+    if fractions.is_empty() {
+        return ' ';
+    }
+    let mean = fractions.iter().sum::<f32>() / fractions.len() as f32;
+    let level = (mean.clamp(0.0, 1.0) * DIFFERENCE_BARS.len() as f32).ceil() as usize;
+    match level.checked_sub(1) {
+        Some(index) => DIFFERENCE_BARS[index.min(DIFFERENCE_BARS.len() - 1)],
+        None => ' ',
+    }
+}
+
+fn render_difference_row(
+    f: &mut Frame,
+    area: Rect,
+    differences: Option<&[f32]>,
+    theme: &Theme,
+    total_columns: usize,
+) {
+    let total_width = usize::from(area.width);
+    let buffer = f.buffer_mut();
+    for block_index in 0..total_width {
+        let bar = differences
+            .filter(|_| total_columns > 0)
+            .and_then(|fractions| {
+                fractions.get(block_range(block_index, total_width, total_columns))
+            })
+            .map_or(' ', difference_bar);
+        for position in Rect::new(area.x + block_index as u16, area.y, 1, area.height).positions() {
+            if let Some(cell) = buffer.cell_mut(position) {
+                cell.set_char(bar);
+                cell.set_fg(theme.warning);
+                cell.set_bg(theme.panel_bg_dim);
+            }
+        }
+    }
+}
+
 fn render_minimap_track(
     f: &mut Frame,
     area: Rect,
     alignment: &AlignmentModel,
+    gap_only: Option<&[bool]>,
     theme: &Theme,
     total_columns: usize,
 ) {
@@ -224,11 +395,10 @@ fn render_minimap_track(
     }
 
     for block_index in 0..total_width {
-        let block_start = block_index * total_columns / total_width;
-        let block_end = ((block_index + 1) * total_columns)
-            .div_ceil(total_width)
-            .max(block_start + 1)
-            .min(total_columns);
+        let Range {
+            start: block_start,
+            end: block_end,
+        } = block_range(block_index, total_width, total_columns);
         let block_colour = calculate_block_colour(alignment, theme, block_start, block_end);
         let block_x = area.x + block_index as u16;
         let block_area = Rect::new(block_x, area.y, 1, area.height);
@@ -238,6 +408,29 @@ fn render_minimap_track(
                 cell.set_bg(block_colour);
             }
         }
+        let has_gap_only = gap_only
+            .and_then(|columns| columns.get(block_start..block_end))
+            .is_some_and(|columns| columns.contains(&true));
+        if has_gap_only && let Some(cell) = buffer.cell_mut((block_x, area.y)) {
+            cell.set_char('▾');
+            cell.set_fg(theme.text_dim);
+        }
+        // alternate partitions are told apart by the colour of a bar along the bottom row
+        let partition = alignment
+            .view()
+            .absolute_column_id(block_start)
+            .and_then(|absolute_col| partition::partition_at(&alignment.partitions, absolute_col));
+        if let Some(index) = partition
+            && area.height > 0
+            && let Some(cell) = buffer.cell_mut((block_x, area.bottom() - 1))
+        {
+            cell.set_char('▁');
+            cell.set_fg(if index.is_multiple_of(2) {
+                theme.accent
+            } else {
+                theme.accent_alt
+            });
+        }
     }
 }
 
@@ -245,15 +438,43 @@ pub fn layout(overlay_area: Rect) -> MinimapLayout {
     let height = overlay_area.height.min(MINIMAP_HEIGHT_ROWS);
     let top = overlay_area.y.saturating_add(overlay_area.height - height);
     let area = Rect::new(overlay_area.x, top, overlay_area.width, height);
-    let track_area = Block::bordered().inner(area);
-    MinimapLayout { area, track_area }
+    let inner_area = Block::bordered().inner(area);
+    let gauge_width = u16::from(inner_area.width > 1);
+    let row_gauge_area = Rect {
+        width: gauge_width,
+        ..inner_area
+    };
+    let track_area = Rect {
+        x: inner_area.x + gauge_width,
+        width: inner_area.width - gauge_width,
+        ..inner_area
+    };
+    let difference_height = u16::from(track_area.height > 1);
+    let difference_area = Rect {
+        height: difference_height,
+        ..track_area
+    };
+    let colour_area = Rect {
+        y: track_area.y + difference_height,
+        height: track_area.height - difference_height,
+        ..track_area
+    };
+    MinimapLayout {
+        area,
+        track_area,
+        difference_area,
+        colour_area,
+        row_gauge_area,
+    }
 }
 
 pub fn render(
     f: &mut Frame,
     overlay_area: Rect,
     input_area: Rect,
+    minimap_state: &MinimapState,
     alignment: &AlignmentModel,
+    stats_cache: &ColumnStatsCache,
     ui: &UiState,
 ) {
     let minimap_layout = layout(overlay_area);
@@ -268,25 +489,82 @@ pub fn render(
             .style(styles.panel_block),
         minimap_layout.area,
     );
+    render_difference_row(
+        f,
+        minimap_layout.difference_area,
+        stats_cache.difference_fractions(),
+        theme,
+        total_columns,
+    );
     render_minimap_track(
         f,
-        minimap_layout.track_area,
+        minimap_layout.colour_area,
         alignment,
+        stats_cache.gap_only_columns(),
         theme,
         total_columns,
     );
 
+    render_row_gauge(
+        f,
+        minimap_layout.row_gauge_area,
+        &ui.viewport.window().row_range,
+        alignment.view().row_count(),
+        theme,
+    );
+
     if let Some(viewport_box) = highlight_box(
-        minimap_layout.track_area,
-        ui.viewport.window().col_range,
+        minimap_layout.colour_area,
+        minimap_state.highlight_window(&ui.viewport.window().col_range),
         total_columns,
     ) {
         shade_highlight_box(f, viewport_box, theme);
     }
 
+    let hint = if minimap_state.typed.is_empty() {
+        Span::styled(
+            "Drag or ←/→ PgUp/PgDn to pan, type a position, Enter to jump · top row: difference \
+             from consensus · left edge: rows on screen",
+            styles.text_dim,
+        )
+    } else {
+        Span::styled(
+            format!("Go to position: {}", minimap_state.typed),
+            styles.text,
+        )
+    };
     f.render_widget(
-        Paragraph::new(Line::from(Span::styled("Drag to pan", styles.text_dim)))
-            .style(styles.base_block),
+        Paragraph::new(Line::from(hint)).style(styles.base_block),
         input_area,
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn row_gauge_needs_rows_and_height() {
+        assert_eq!(row_gauge_thumb(10, &(0..0), 0), None);
+        assert_eq!(row_gauge_thumb(0, &(0..5), 10), None);
+    }
+
+    #[test]
+    fn row_gauge_fills_the_track_when_every_row_is_visible() {
+        assert_eq!(row_gauge_thumb(10, &(0..50), 50), Some(0..10));
+    }
+
+    #[test]
+    fn row_gauge_thumb_is_at_least_one_cell_and_stays_on_the_track() {
+        assert_eq!(row_gauge_thumb(10, &(0..1), 1000), Some(0..1));
+        assert_eq!(row_gauge_thumb(10, &(999..1000), 1000), Some(9..10));
+        // scrolled past the last row
+        assert_eq!(row_gauge_thumb(10, &(1000..1000), 1000), Some(9..10));
+    }
+
+    #[test]
+    fn one_cell_row_gauge_is_all_thumb() {
+        assert_eq!(row_gauge_thumb(1, &(10..20), 100), Some(0..1));
+        assert_eq!(row_gauge_thumb(1, &(90..100), 100), Some(0..1));
+    }
+}
@@ -1,4 +1,12 @@
 pub(crate) mod command_palette;
+pub(crate) mod confirm;
+pub(crate) mod consensus_threshold;
+pub(crate) mod context_menu;
+pub(crate) mod file_picker;
 pub(crate) mod minimap;
 pub(crate) mod overlay_state;
+pub(crate) mod pager;
+pub(crate) mod paste;
+pub(crate) mod perf_hud;
 pub(crate) mod render;
+pub(crate) mod report;
@@ -1,10 +1,24 @@
 use super::command_palette::CommandPaletteState;
+use super::confirm::ConfirmState;
+use super::consensus_threshold::ConsensusThresholdState;
+use super::context_menu::ContextMenuState;
+use super::file_picker::FilePickerState;
 use super::minimap::MinimapState;
+use super::pager::PagerState;
+use super::paste::PasteState;
+use super::report::ReportState;
 
 #[derive(Debug)]
 pub enum ActiveOverlay {
     Palette(Box<CommandPaletteState>),
     Minimap(MinimapState),
+    ContextMenu(ContextMenuState),
+    Report(ReportState),
+    Pager(PagerState),
+    FilePicker(Box<FilePickerState>),
+    Confirm(ConfirmState),
+    Paste(PasteState),
+    ConsensusThreshold(ConsensusThresholdState),
 }
 
 #[derive(Debug, Default)]
@@ -17,6 +31,36 @@ impl OverlayState {
         self.active_overlay = Some(ActiveOverlay::Palette(Box::new(palette)));
     }
 
+    pub fn open_context_menu(&mut self, menu: ContextMenuState) {
+        self.active_overlay = Some(ActiveOverlay::ContextMenu(menu));
+    }
+
+    pub fn open_report(&mut self, report: ReportState) {
+        self.active_overlay = Some(ActiveOverlay::Report(report));
+    }
+
+    pub fn open_pager(&mut self, pager: PagerState) {
+        self.active_overlay = Some(ActiveOverlay::Pager(pager));
+    }
+
+    pub fn open_file_picker(&mut self, picker: FilePickerState) {
+        self.active_overlay = Some(ActiveOverlay::FilePicker(Box::new(picker)));
+    }
+
+    pub fn open_confirm(&mut self, confirm: ConfirmState) {
+        self.active_overlay = Some(ActiveOverlay::Confirm(confirm));
+    }
+
+    pub fn open_paste(&mut self) {
+        self.active_overlay = Some(ActiveOverlay::Paste(PasteState::default()));
+    }
+
+    pub fn open_consensus_threshold(&mut self, threshold: f32) {
+        self.active_overlay = Some(ActiveOverlay::ConsensusThreshold(
+            ConsensusThresholdState::new(threshold),
+        ));
+    }
+
     pub fn toggle_minimap(&mut self) {
         self.active_overlay = match self.active_overlay.take() {
             Some(ActiveOverlay::Minimap(_)) => None,
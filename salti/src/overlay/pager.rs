@@ -0,0 +1,343 @@
+use std::cell::Cell;
+
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent, MouseEventKind};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::Styled;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Clear, Paragraph, Widget};
+
+use crate::command::Command;
+use crate::config::theme::ThemeStyles;
+
+/// widest the pager grows, in columns including borders
+const PAGER_MAX_WIDTH: u16 = 120;
+/// lines moved by one mouse wheel step
+const PAGER_SCROLL_ROWS: usize = 3;
+/// spaces a tab is expanded to, as the terminal cells cannot hold one
+const TAB_WIDTH: usize = 4;
+
+/// search within the pager: the query being typed after `/`, and the lines matching the last
+/// one entered
+#[derive(Debug, Clone, Default)]
+struct PagerSearch {
+    input: Option<String>,
+    query: String,
+    matches: Vec<usize>,
+    current: usize,
+}
+
+/// scrollable read-only text viewer for long output. `/` searches, `n`/`N` step through
+/// matches and `y` copies the whole text.
+#[derive(Debug, Clone)]
+pub struct PagerState {
+    title: String,
+    lines: Vec<Line<'static>>,
+    /// plain text of each line, searched and copied
+    text: Vec<String>,
+    offset: usize,
+    /// text rows shown by the last render, used to page and to keep the last page full
+    visible_rows: Cell<usize>,
+    search: PagerSearch,
+}
+
+impl PagerState {
+    pub fn new(title: impl Into<String>, lines: Vec<Line<'static>>) -> Self {
+        let text = lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect();
+        Self {
+            title: title.into(),
+            lines,
+            text,
+            offset: 0,
+            visible_rows: Cell::new(1),
+            search: PagerSearch::default(),
+        }
+    }
+
+    pub fn from_text(title: impl Into<String>, text: &str) -> Self {
+        let lines = text
+            .lines()
+            .map(|line| Line::from(line.replace('\t', &" ".repeat(TAB_WIDTH))))
+            .collect();
+        Self::new(title, lines)
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn last_offset(&self) -> usize {
+        self.lines.len().saturating_sub(self.visible_rows.get())
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        self.offset = self
+            .offset
+            .min(self.last_offset())
+            .saturating_add_signed(delta)
+            .min(self.last_offset());
+    }
+
+    fn scroll_to_line(&mut self, line: usize) {
+        let rows = self.visible_rows.get();
+        if line < self.offset || line >= self.offset + rows {
+            self.offset = line.min(self.last_offset());
+        }
+    }
+
+    /// finds the lines containing `query`, ignoring case, and shows the first one at or after
+    /// the top of the page
+    fn run_search(&mut self, query: String) {
+        let needle = query.to_lowercase();
+        self.search.matches = if needle.is_empty() {
+            Vec::new()
+        } else {
+            self.text
+                .iter()
+                .enumerate()
+                .filter(|(_, line)| line.to_lowercase().contains(&needle))
+                .map(|(index, _)| index)
+                .collect()
+        };
+        self.search.query = query;
+        self.search.current = self
+            .search
+            .matches
+            .iter()
+            .position(|&line| line >= self.offset)
+            .unwrap_or(0);
+        if let Some(&line) = self.search.matches.get(self.search.current) {
+            self.scroll_to_line(line);
+        }
+    }
+
+    fn step_match(&mut self, forward: bool) {
+        let count = self.search.matches.len();
+        if count == 0 {
+            return;
+        }
+        self.search.current = if forward {
+            (self.search.current + 1) % count
+        } else {
+            (self.search.current + count - 1) % count
+        };
+        self.scroll_to_line(self.search.matches[self.search.current]);
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent) -> Vec<Command> {
+        if let Some(input) = self.search.input.as_mut() {
+            match key.code {
+                KeyCode::Esc => self.search.input = None,
+                KeyCode::Enter => {
+                    let query = self.search.input.take().unwrap_or_default();
+                    self.run_search(query);
+                }
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => (),
+            }
+            return Vec::new();
+        }
+
+        let page = self.visible_rows.get().max(1) as isize;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return vec![Command::CloseOverlay],
+            KeyCode::Up | KeyCode::Char('k') => self.scroll(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll(1),
+            KeyCode::PageUp | KeyCode::Char('b') => self.scroll(-page),
+            KeyCode::PageDown | KeyCode::Char(' ') => self.scroll(page),
+            KeyCode::Home | KeyCode::Char('g') => self.offset = 0,
+            KeyCode::End | KeyCode::Char('G') => self.offset = self.last_offset(),
+            KeyCode::Char('/') => self.search.input = Some(String::new()),
+            KeyCode::Char('n') => self.step_match(true),
+            KeyCode::Char('N') => self.step_match(false),
+            KeyCode::Char('y') => {
+                return vec![Command::CopyToClipboard(self.text.join("\n"))];
+            }
+            _ => (),
+        }
+        Vec::new()
+    }
+
+    pub fn handle_mouse(&mut self, mouse: MouseEvent, bounds: Rect) -> Vec<Command> {
+        match mouse.kind {
+            MouseEventKind::ScrollUp => self.scroll(-(PAGER_SCROLL_ROWS as isize)),
+            MouseEventKind::ScrollDown => self.scroll(PAGER_SCROLL_ROWS as isize),
+            MouseEventKind::Down(_)
+                if !self.area(bounds).contains((mouse.column, mouse.row).into()) =>
+            {
+                return vec![Command::CloseOverlay];
+            }
+            _ => (),
+        }
+        Vec::new()
+    }
+
+    /// centres the pager inside `bounds`, sized to its text up to four fifths of the height
+    pub fn area(&self, bounds: Rect) -> Rect {
+        let width = PAGER_MAX_WIDTH.min(bounds.width);
+        let height = (self.lines.len().max(1) as u16)
+            .saturating_add(3)
+            .min(bounds.height.saturating_mul(4) / 5)
+            .max(4)
+            .min(bounds.height);
+        Rect::new(
+            bounds.x + (bounds.width - width) / 2,
+            bounds.y + (bounds.height - height) / 2,
+            width,
+            height,
+        )
+    }
+
+    fn status_line(&self, offset: usize, styles: &ThemeStyles) -> Line<'static> {
+        if let Some(input) = &self.search.input {
+            return Line::from(vec![
+                " /".set_style(styles.accent),
+                input.clone().set_style(styles.text),
+                "▏".set_style(styles.accent),
+            ]);
+        }
+        let first = (offset + 1).min(self.lines.len());
+        let last = (offset + self.visible_rows.get()).min(self.lines.len());
+        let position = format!(" {first}-{last} of {}", self.lines.len());
+        let search = if self.search.query.is_empty() {
+            String::new()
+        } else if self.search.matches.is_empty() {
+            format!(" | no match for '{}'", self.search.query)
+        } else {
+            format!(
+                " | match {}/{} for '{}'",
+                self.search.current + 1,
+                self.search.matches.len(),
+                self.search.query
+            )
+        };
+        Line::from(vec![
+            position.set_style(styles.text),
+            search.set_style(styles.accent),
+            " | / search, n/N next/prev, y copy, q close".set_style(styles.text_muted),
+        ])
+    }
+
+    pub fn render(&self, f: &mut Frame, bounds: Rect, styles: &ThemeStyles) {
+        let area = self.area(bounds);
+        let block = Block::bordered()
+            .title(Line::from(self.title.as_str().set_style(styles.accent)))
+            .border_style(styles.border)
+            .style(styles.panel_block);
+        let inner = block.inner(area);
+        let rows = usize::from(inner.height.saturating_sub(1)).max(1);
+        self.visible_rows.set(rows);
+        let offset = self.offset.min(self.last_offset());
+
+        let current = self.search.matches.get(self.search.current).copied();
+        let mut lines: Vec<Line> = if self.lines.is_empty() {
+            vec![Line::from(" Nothing to show".set_style(styles.text_dim))]
+        } else {
+            self.lines
+                .iter()
+                .enumerate()
+                .skip(offset)
+                .take(rows)
+                .map(|(index, line)| {
+                    let line = line.clone().style(styles.text);
+                    if Some(index) == current {
+                        line.patch_style(styles.selection)
+                    } else if self.search.matches.binary_search(&index).is_ok() {
+                        line.patch_style(styles.accent)
+                    } else {
+                        line
+                    }
+                })
+                .collect()
+        };
+        lines.resize(rows, Line::default());
+        lines.push(self.status_line(offset, styles));
+
+        Clear.render(area, f.buffer_mut());
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pager(rows: usize) -> PagerState {
+        let text: Vec<String> = (0..50).map(|index| format!("line {index}")).collect();
+        let pager = PagerState::from_text("Output", &text.join("\n"));
+        pager.visible_rows.set(rows);
+        pager
+    }
+
+    fn press(pager: &mut PagerState, codes: &[KeyCode]) {
+        for &code in codes {
+            pager.handle_key_event(KeyEvent::from(code));
+        }
+    }
+
+    #[test]
+    fn scrolling_stops_at_the_last_full_page() {
+        let mut pager = pager(10);
+
+        press(&mut pager, &[KeyCode::PageDown, KeyCode::Down]);
+        assert_eq!(pager.offset, 11);
+        press(&mut pager, &[KeyCode::End, KeyCode::Down]);
+        assert_eq!(pager.offset, 40);
+        press(&mut pager, &[KeyCode::Up, KeyCode::Home]);
+        assert_eq!(pager.offset, 0);
+    }
+
+    #[test]
+    fn search_jumps_between_matching_lines() {
+        let mut pager = pager(10);
+
+        press(
+            &mut pager,
+            &[
+                KeyCode::Char('/'),
+                KeyCode::Char('E'),
+                KeyCode::Char(' '),
+                KeyCode::Char('4'),
+                KeyCode::Enter,
+            ],
+        );
+        assert_eq!(
+            pager.search.matches,
+            [4, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49]
+        );
+        assert_eq!(pager.offset, 0);
+
+        press(&mut pager, &[KeyCode::Char('n')]);
+        assert_eq!(pager.offset, 40);
+        press(&mut pager, &[KeyCode::Char('N'), KeyCode::Char('N')]);
+        assert_eq!(pager.search.current, 10);
+    }
+
+    #[test]
+    fn copy_returns_the_plain_text() {
+        let mut pager = PagerState::new(
+            "Output",
+            vec![Line::from(vec!["a".into(), "b".into()]), Line::from("c\td")],
+        );
+
+        assert_eq!(
+            pager.handle_key_event(KeyEvent::from(KeyCode::Char('y'))),
+            vec![Command::CopyToClipboard("ab\nc\td".to_string())]
+        );
+        assert_eq!(
+            pager.handle_key_event(KeyEvent::from(KeyCode::Char('q'))),
+            vec![Command::CloseOverlay]
+        );
+    }
+}
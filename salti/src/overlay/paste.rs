@@ -0,0 +1,107 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::Styled;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+
+use crate::command::Command;
+use crate::config::theme::ThemeStyles;
+
+/// a blank buffer on the input line that collects a pasted alignment. a bracketed paste is
+/// loaded as soon as it arrives; text typed, or pasted by a terminal without bracketed paste,
+/// is loaded with `Ctrl+D`.
+#[derive(Debug, Default)]
+pub struct PasteState {
+    buffer: String,
+}
+
+impl PasteState {
+    pub fn handle_key_event(&mut self, key: KeyEvent) -> Vec<Command> {
+        match key.code {
+            KeyCode::Esc => return vec![Command::CloseOverlay],
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return self.load();
+            }
+            KeyCode::Enter => self.buffer.push('\n'),
+            KeyCode::Tab => self.buffer.push('\t'),
+            KeyCode::Backspace => {
+                self.buffer.pop();
+            }
+            KeyCode::Char(character) => self.buffer.push(character),
+            _ => (),
+        }
+        Vec::new()
+    }
+
+    pub fn handle_paste(&mut self, text: &str) -> Vec<Command> {
+        self.buffer.push_str(text);
+        self.load()
+    }
+
+    fn load(&mut self) -> Vec<Command> {
+        if self.buffer.trim().is_empty() {
+            return Vec::new();
+        }
+        vec![
+            Command::CloseOverlay,
+            Command::LoadPastedAlignment(std::mem::take(&mut self.buffer)),
+        ]
+    }
+
+    pub fn render(&self, f: &mut Frame, input_area: Rect, styles: &ThemeStyles) {
+        let line = if self.buffer.is_empty() {
+            Line::from(vec![
+                "Paste a FASTA or NEXUS alignment".set_style(styles.accent),
+                " (Esc to cancel)".set_style(styles.text_muted),
+            ])
+        } else {
+            Line::from(vec![
+                format!("{} line(s) entered", self.buffer.lines().count()).set_style(styles.text),
+                " (Ctrl+D to load, Esc to cancel)".set_style(styles.text_muted),
+            ])
+        };
+        f.render_widget(Paragraph::new(line).style(styles.base_block), input_area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bracketed_paste_loads_straight_away() {
+        let mut paste = PasteState::default();
+
+        assert_eq!(
+            paste.handle_paste(">a\nACGT\n"),
+            vec![
+                Command::CloseOverlay,
+                Command::LoadPastedAlignment(">a\nACGT\n".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn typed_lines_load_on_ctrl_d() {
+        let mut paste = PasteState::default();
+        for character in ">a".chars() {
+            paste.handle_key_event(KeyEvent::from(KeyCode::Char(character)));
+        }
+        paste.handle_key_event(KeyEvent::from(KeyCode::Enter));
+        paste.handle_key_event(KeyEvent::from(KeyCode::Char('A')));
+
+        assert_eq!(
+            paste.handle_key_event(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL)),
+            vec![
+                Command::CloseOverlay,
+                Command::LoadPastedAlignment(">a\nA".to_string())
+            ]
+        );
+        assert!(
+            paste
+                .handle_key_event(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL))
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use std::time::Duration;
+
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::Styled;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Clear, Paragraph, Widget};
+
+use crate::config::theme::ThemeStyles;
+use crate::core::memory::format_bytes;
+
+/// width of the performance HUD in columns, including borders
+const PERF_HUD_WIDTH: u16 = 32;
+/// frame render time above which the HUD flags a slow frame
+const SLOW_FRAME_THRESHOLD: Duration = Duration::from_millis(16);
+
+/// runtime measurements shown by the performance HUD
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PerfStats {
+    pub frame_time: Duration,
+    pub loop_latency: Duration,
+    pub pending_jobs: usize,
+    pub raw_cache: (usize, usize),
+    pub translated_cache: (usize, usize),
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.2} ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn format_coverage((filled, total): (usize, usize)) -> String {
+    if total == 0 {
+        return "-".to_string();
+    }
+    format!("{filled}/{total} ({}%)", filled * 100 / total)
+}
+
+/// resident memory, followed by the `--max-mem` limit when one is set
+fn format_memory((usage, limit): (Option<u64>, Option<u64>)) -> String {
+    let usage = usage.map_or_else(|| "-".to_string(), format_bytes);
+    match limit {
+        Some(limit) => format!("{usage} / {}", format_bytes(limit)),
+        None => usage,
+    }
+}
+
+fn hud_lines(
+    stats: &PerfStats,
+    memory: (Option<u64>, Option<u64>),
+    styles: &ThemeStyles,
+) -> Vec<Line<'static>> {
+    let frame_style = if stats.frame_time > SLOW_FRAME_THRESHOLD {
+        styles.warning
+    } else {
+        styles.text
+    };
+    let memory_style = match memory {
+        (Some(usage), Some(limit)) if usage > limit => styles.warning,
+        _ => styles.text,
+    };
+    let row = |label: &'static str, value: Span<'static>| {
+        Line::from(vec![
+            format!(" {label:<10}").set_style(styles.text_dim),
+            value,
+        ])
+    };
+
+    vec![
+        row(
+            "frame",
+            format_duration(stats.frame_time).set_style(frame_style),
+        ),
+        row(
+            "latency",
+            format_duration(stats.loop_latency).set_style(styles.text),
+        ),
+        row("memory", format_memory(memory).set_style(memory_style)),
+        row(
+            "jobs",
+            stats.pending_jobs.to_string().set_style(styles.text),
+        ),
+        row(
+            "cache nt",
+            format_coverage(stats.raw_cache).set_style(styles.text),
+        ),
+        row(
+            "cache aa",
+            format_coverage(stats.translated_cache).set_style(styles.text),
+        ),
+    ]
+}
+
+/// places the HUD in the top right corner of `bounds`
+pub fn area(bounds: Rect) -> Rect {
+    let width = PERF_HUD_WIDTH.min(bounds.width);
+    let height = 8.min(bounds.height);
+    Rect::new(
+        bounds.right().saturating_sub(width),
+        bounds.y,
+        width,
+        height,
+    )
+}
+
+pub fn render(
+    f: &mut Frame,
+    bounds: Rect,
+    stats: &PerfStats,
+    memory: (Option<u64>, Option<u64>),
+    styles: &ThemeStyles,
+) {
+    let area = area(bounds);
+    Clear.render(area, f.buffer_mut());
+    f.render_widget(
+        Paragraph::new(hud_lines(stats, memory, styles)).block(
+            Block::bordered()
+                .title(Line::from("Performance".set_style(styles.accent)))
+                .border_style(styles.border)
+                .style(styles.panel_block),
+        ),
+        area,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::theme::{EVERFOREST_DARK, build_theme_styles};
+
+    fn line_text(line: &Line<'_>) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn hud_lists_every_measurement() {
+        let stats = PerfStats {
+            frame_time: Duration::from_micros(1500),
+            loop_latency: Duration::from_micros(250),
+            pending_jobs: 3,
+            raw_cache: (1, 4),
+            translated_cache: (0, 0),
+        };
+
+        let lines = hud_lines(
+            &stats,
+            (Some(2048), None),
+            &build_theme_styles(EVERFOREST_DARK),
+        );
+        let text: Vec<String> = lines.iter().map(line_text).collect();
+
+        assert_eq!(
+            text,
+            vec![
+                " frame     1.50 ms",
+                " latency   0.25 ms",
+                " memory    2 KiB",
+                " jobs      3",
+                " cache nt  1/4 (25%)",
+                " cache aa  -",
+            ]
+        );
+    }
+
+    #[test]
+    fn memory_is_shown_against_the_limit() {
+        assert_eq!(format_memory((Some(2048), None)), "2 KiB");
+        assert_eq!(
+            format_memory((Some(2048), Some(8 * 1024 * 1024 * 1024))),
+            "2 KiB / 8.0 GiB"
+        );
+        assert_eq!(format_memory((None, Some(4096))), "- / 4 KiB");
+    }
+
+    #[test]
+    fn hud_is_anchored_to_the_top_right() {
+        let area = area(Rect::new(0, 1, 80, 20));
+
+        assert_eq!(area.right(), 80);
+        assert_eq!(area.y, 1);
+    }
+}
@@ -1,40 +1,94 @@
+use std::time::Instant;
+
 use crate::core::model::AlignmentModel;
-use crate::ui::notification::render_notification;
+use crate::core::stats_cache::ColumnStatsCache;
+use crate::ui::notification::{render_flash, render_notification};
 use crate::ui::ui_state::UiState;
 use ratatui::Frame;
 use ratatui::layout::Rect;
 use ratatui::widgets::Block;
 
-use super::minimap;
 use super::overlay_state::ActiveOverlay;
+use super::{minimap, perf_hud};
 
 pub fn render_overlays(
     f: &mut Frame,
     content_area: Rect,
     input_area: Rect,
     alignment: Option<&AlignmentModel>,
+    stats_cache: &ColumnStatsCache,
     ui: &UiState,
 ) {
+    if let Some(stats) = ui.perf_hud.as_ref() {
+        perf_hud::render(
+            f,
+            content_area,
+            stats,
+            (ui.meta.memory_usage, ui.meta.memory_limit),
+            &ui.theme.styles,
+        );
+    }
+
     match &ui.overlay.active_overlay {
-        Some(ActiveOverlay::Minimap(_)) => {
+        Some(ActiveOverlay::Minimap(minimap_state)) => {
             if let Some(alignment) = alignment {
-                minimap::render(f, content_area, input_area, alignment, ui);
+                minimap::render(
+                    f,
+                    content_area,
+                    input_area,
+                    minimap_state,
+                    alignment,
+                    stats_cache,
+                    ui,
+                );
             }
         }
         Some(ActiveOverlay::Palette(palette)) => {
             palette.render(f, content_area, input_area, &ui.theme.styles);
         }
+        Some(ActiveOverlay::ContextMenu(menu)) => {
+            menu.render(f, content_area, &ui.theme.styles);
+        }
+        Some(ActiveOverlay::Report(report)) => {
+            report.render(f, content_area, &ui.theme.styles);
+        }
+        Some(ActiveOverlay::Pager(pager)) => {
+            pager.render(f, content_area, &ui.theme.styles);
+        }
+        Some(ActiveOverlay::FilePicker(picker)) => {
+            picker.render(f, content_area, &ui.theme.styles);
+        }
+        Some(ActiveOverlay::Confirm(confirm)) => {
+            confirm.render(f, input_area, &ui.theme.styles);
+        }
+        Some(ActiveOverlay::Paste(paste)) => {
+            paste.render(f, input_area, &ui.theme.styles);
+        }
+        Some(ActiveOverlay::ConsensusThreshold(slider)) => {
+            slider.render(f, input_area, &ui.theme.styles);
+        }
         None => (),
     }
 
-    if ui.overlay.active_overlay.is_none() {
+    if matches!(
+        ui.overlay.active_overlay,
+        None | Some(
+            ActiveOverlay::ContextMenu(_)
+                | ActiveOverlay::Report(_)
+                | ActiveOverlay::Pager(_)
+                | ActiveOverlay::FilePicker(_)
+        )
+    ) {
         match ui.notification.as_ref() {
             Some(notification) => {
-                render_notification(f, input_area, notification, &ui.theme.styles);
+                render_notification(f, content_area, input_area, notification, &ui.theme.styles);
             }
             None => {
                 f.render_widget(Block::new().style(ui.theme.styles.base_block), input_area);
             }
         }
     }
+    if ui.flash_until.is_some_and(|until| Instant::now() < until) {
+        render_flash(f, input_area);
+    }
 }
@@ -0,0 +1,246 @@
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::Styled;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Clear, Paragraph, Widget};
+
+use crate::command::Command;
+use crate::config::theme::ThemeStyles;
+use crate::ui::utils::truncate_label;
+
+/// widest a report box grows, in columns including borders
+const REPORT_MAX_WIDTH: u16 = 80;
+/// rows moved by `PageUp` / `PageDown`
+const REPORT_PAGE_ROWS: usize = 10;
+
+/// alignment position an entry jumps to when chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReportTarget {
+    pub abs_row: Option<usize>,
+    /// column in the current view
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportEntry {
+    pub label: String,
+    pub target: Option<ReportTarget>,
+}
+
+impl ReportEntry {
+    pub fn text(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            target: None,
+        }
+    }
+
+    pub fn jump(label: impl Into<String>, target: ReportTarget) -> Self {
+        Self {
+            label: label.into(),
+            target: Some(target),
+        }
+    }
+}
+
+/// scrollable list of results produced by analysis commands. entries with a target jump the
+/// viewport there when chosen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportState {
+    title: String,
+    entries: Vec<ReportEntry>,
+    selected: usize,
+}
+
+impl ReportState {
+    pub fn new(title: impl Into<String>, entries: Vec<ReportEntry>) -> Self {
+        Self {
+            title: title.into(),
+            entries,
+            selected: 0,
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let last = self.entries.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+    }
+
+    fn run_selected(&self) -> Vec<Command> {
+        let Some(target) = self
+            .entries
+            .get(self.selected)
+            .and_then(|entry| entry.target)
+        else {
+            return Vec::new();
+        };
+        let mut commands = vec![Command::CloseOverlay];
+        if let Some(abs_row) = target.abs_row {
+            commands.push(Command::JumpToSequence(abs_row));
+        }
+        commands.push(Command::JumpToPosition(target.column));
+        commands
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent) -> Vec<Command> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return vec![Command::CloseOverlay],
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::PageUp => self.move_selection(-(REPORT_PAGE_ROWS as isize)),
+            KeyCode::PageDown => self.move_selection(REPORT_PAGE_ROWS as isize),
+            KeyCode::Home => self.selected = 0,
+            KeyCode::End => self.move_selection(isize::MAX),
+            KeyCode::Enter => return self.run_selected(),
+            _ => (),
+        }
+        Vec::new()
+    }
+
+    pub fn handle_mouse(&mut self, mouse: MouseEvent, bounds: Rect) -> Vec<Command> {
+        let area = self.area(bounds);
+        let entries_area = Block::bordered().inner(area);
+        let hovered = entries_area
+            .contains((mouse.column, mouse.row).into())
+            .then(|| {
+                self.scroll_offset(entries_area.height) + usize::from(mouse.row - entries_area.y)
+            })
+            .filter(|&index| index < self.entries.len());
+
+        match (mouse.kind, hovered) {
+            (MouseEventKind::ScrollUp, _) => self.move_selection(-1),
+            (MouseEventKind::ScrollDown, _) => self.move_selection(1),
+            (MouseEventKind::Down(MouseButton::Left), Some(index)) => {
+                self.selected = index;
+                return self.run_selected();
+            }
+            (MouseEventKind::Down(_), _) if !area.contains((mouse.column, mouse.row).into()) => {
+                return vec![Command::CloseOverlay];
+            }
+            _ => (),
+        }
+        Vec::new()
+    }
+
+    /// first entry shown so that the selection stays in view
+    fn scroll_offset(&self, visible_rows: u16) -> usize {
+        let visible_rows = usize::from(visible_rows).max(1);
+        (self.selected + 1).saturating_sub(visible_rows)
+    }
+
+    /// centres the report inside `bounds`
+    pub fn area(&self, bounds: Rect) -> Rect {
+        let width = REPORT_MAX_WIDTH.min(bounds.width);
+        let height = (self.entries.len().max(1) as u16)
+            .saturating_add(2)
+            .min(bounds.height.saturating_mul(2) / 3)
+            .max(3)
+            .min(bounds.height);
+        Rect::new(
+            bounds.x + (bounds.width - width) / 2,
+            bounds.y + (bounds.height - height) / 2,
+            width,
+            height,
+        )
+    }
+
+    pub fn render(&self, f: &mut Frame, bounds: Rect, styles: &ThemeStyles) {
+        let area = self.area(bounds);
+        let block = Block::bordered()
+            .title(Line::from(self.title.as_str().set_style(styles.accent)))
+            .border_style(styles.border)
+            .style(styles.panel_block);
+        let inner = block.inner(area);
+        let label_width = usize::from(inner.width.saturating_sub(1));
+
+        let lines: Vec<Line> = if self.entries.is_empty() {
+            vec![Line::from(" Nothing found".set_style(styles.text_dim))]
+        } else {
+            self.entries
+                .iter()
+                .enumerate()
+                .skip(self.scroll_offset(inner.height))
+                .take(usize::from(inner.height))
+                .map(|(index, entry)| {
+                    let style = if index == self.selected {
+                        styles.selection
+                    } else if entry.target.is_some() {
+                        styles.text
+                    } else {
+                        styles.text_dim
+                    };
+                    let label = format!(" {}", truncate_label(&entry.label, label_width));
+                    Line::from(label.set_style(style))
+                })
+                .collect()
+        };
+
+        Clear.render(area, f.buffer_mut());
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> ReportState {
+        ReportState::new(
+            "Results",
+            vec![
+                ReportEntry::text("summary"),
+                ReportEntry::jump(
+                    "seq1 at 10",
+                    ReportTarget {
+                        abs_row: Some(1),
+                        column: 9,
+                    },
+                ),
+            ],
+        )
+    }
+
+    #[test]
+    fn enter_jumps_to_the_selected_target() {
+        let mut state = report();
+        state.handle_key_event(KeyEvent::from(KeyCode::Down));
+
+        let commands = state.handle_key_event(KeyEvent::from(KeyCode::Enter));
+
+        assert_eq!(
+            commands,
+            vec![
+                Command::CloseOverlay,
+                Command::JumpToSequence(1),
+                Command::JumpToPosition(9)
+            ]
+        );
+    }
+
+    #[test]
+    fn entries_without_a_target_do_nothing() {
+        let mut state = report();
+
+        let commands = state.handle_key_event(KeyEvent::from(KeyCode::Enter));
+
+        assert!(commands.is_empty());
+    }
+
+    #[test]
+    fn selection_scrolls_into_view() {
+        let entries = (0..20)
+            .map(|index| ReportEntry::text(format!("entry {index}")))
+            .collect();
+        let mut state = ReportState::new("Results", entries);
+
+        state.handle_key_event(KeyEvent::from(KeyCode::End));
+
+        assert_eq!(state.selected, 19);
+        assert_eq!(state.scroll_offset(5), 15);
+    }
+}
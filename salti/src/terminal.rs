@@ -0,0 +1,346 @@
+use std::io::{self, Write};
+use std::ops::Range;
+use std::time::Duration;
+
+use crossterm::execute;
+use crossterm::terminal::SetTitle;
+use tracing::{debug, warn};
+
+use crate::config::theme::ThemeId;
+
+/// background jobs running at least this long send a desktop notification when they finish
+pub const NOTIFY_AFTER: Duration = Duration::from_secs(10);
+
+/// saves the terminal's own title so it can be put back on exit (xterm title stack)
+const PUSH_TITLE: &str = "\x1b[22;0t";
+const POP_TITLE: &str = "\x1b[23;0t";
+
+/// how long to wait for the terminal to report its background colour
+pub const BACKGROUND_QUERY_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// OSC 11 asks for the background colour. the device attributes request after it is answered
+/// by every terminal, so the reply can be read up to a known end.
+const BACKGROUND_QUERY: &[u8] = b"\x1b]11;?\x1b\\\x1b[c";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Background {
+    Light,
+    Dark,
+}
+
+impl Background {
+    /// built-in theme to start with on this background
+    pub fn default_theme(self) -> ThemeId {
+        match self {
+            Background::Light => ThemeId::SolarizedLight,
+            Background::Dark => ThemeId::EverforestDark,
+        }
+    }
+}
+
+/// a colour channel of 1 to 4 hex digits, scaled to 0..=1
+fn parse_channel(hex: &str) -> Option<f32> {
+    if hex.is_empty() || hex.len() > 4 {
+        return None;
+    }
+    let value = u32::from_str_radix(hex, 16).ok()?;
+    let max = (1u32 << (4 * hex.len())) - 1;
+    Some(value as f32 / max as f32)
+}
+
+/// reads `rgb:RRRR/GGGG/BBBB` from an OSC 11 reply and classifies it by its luminance
+fn parse_background_reply(reply: &[u8]) -> Option<Background> {
+    let reply = String::from_utf8_lossy(reply);
+    let colour = reply.split("]11;").nth(1)?.strip_prefix("rgb:")?;
+    let colour = colour.split(['\x07', '\x1b']).next()?;
+    let mut channels = colour.split('/').map(parse_channel);
+    let (Some(Some(red)), Some(Some(green)), Some(Some(blue)), None) = (
+        channels.next(),
+        channels.next(),
+        channels.next(),
+        channels.next(),
+    ) else {
+        return None;
+    };
+    let luminance = 0.2126 * red + 0.7152 * green + 0.0722 * blue;
+    Some(if luminance > 0.5 {
+        Background::Light
+    } else {
+        Background::Dark
+    })
+}
+
+/// the terminal to read the reply from: stdin when it is one, as crossterm reads it, otherwise
+/// `/dev/tty`. macOS cannot poll `/dev/tty` itself.
+#[cfg(unix)]
+fn open_tty() -> io::Result<std::fs::File> {
+    use std::io::IsTerminal;
+    use std::os::fd::AsFd;
+
+    let stdin = io::stdin();
+    if stdin.is_terminal() {
+        return Ok(stdin.as_fd().try_clone_to_owned()?.into());
+    }
+    std::fs::File::open("/dev/tty")
+}
+
+/// waits up to `timeout` for `tty` to have input
+#[cfg(unix)]
+fn wait_readable(tty: &impl std::os::fd::AsFd, timeout: Duration) -> io::Result<bool> {
+    use rustix::event::{PollFd, PollFlags, Timespec, poll};
+
+    let timeout = Timespec::try_from(timeout).map_err(io::Error::other)?;
+    let mut fds = [PollFd::new(tty, PollFlags::IN)];
+    loop {
+        match poll(&mut fds, Some(&timeout)) {
+            Ok(0) => return Ok(false),
+            Ok(_) if fds[0].revents().contains(PollFlags::NVAL) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "the terminal cannot be polled",
+                ));
+            }
+            Ok(_) => return Ok(true),
+            Err(rustix::io::Errno::INTR) => continue,
+            Err(error) => return Err(error.into()),
+        }
+    }
+}
+
+/// writes the query and reads the reply, giving up once `timeout` has passed. each read waits
+/// for input first, so nothing is read after the timeout and keys typed later reach the app.
+#[cfg(unix)]
+fn read_background_reply(timeout: Duration) -> io::Result<Vec<u8>> {
+    use std::io::Read;
+    use std::time::Instant;
+
+    let deadline = Instant::now() + timeout;
+    let mut tty = open_tty()?;
+    let mut out = std::fs::OpenOptions::new().write(true).open("/dev/tty")?;
+    out.write_all(BACKGROUND_QUERY)?;
+    out.flush()?;
+
+    // the device attributes reply, `ESC [ ? ... c`, always comes last
+    let mut reply = Vec::new();
+    let mut byte = [0u8; 1];
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() || !wait_readable(&tty, remaining)? {
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "the terminal did not answer",
+            ));
+        }
+        if tty.read(&mut byte)? == 0 {
+            break;
+        }
+        reply.push(byte[0]);
+        if byte[0] == b'c'
+            && let Some(start) = reply.windows(3).rposition(|window| window == b"\x1b[?")
+            && reply[start + 3..reply.len() - 1]
+                .iter()
+                .all(|&byte| byte.is_ascii_digit() || byte == b';')
+        {
+            break;
+        }
+    }
+    Ok(reply)
+}
+
+#[cfg(not(unix))]
+fn read_background_reply(_: Duration) -> io::Result<Vec<u8>> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "background queries need a unix terminal",
+    ))
+}
+
+/// asks the terminal for its background colour. must run in raw mode, before the first render.
+/// gives up after `timeout`, or straight away when the terminal does not know the query.
+pub async fn query_background(timeout: Duration) -> Option<Background> {
+    match tokio::task::spawn_blocking(move || read_background_reply(timeout)).await {
+        Ok(Ok(reply)) => {
+            let background = parse_background_reply(&reply);
+            debug!(?background, "Terminal background detected");
+            background
+        }
+        Ok(Err(error)) => {
+            debug!(error = ?error, "Terminal background query failed");
+            None
+        }
+        Err(error) => {
+            debug!(error = ?error, "Terminal background query did not finish");
+            None
+        }
+    }
+}
+
+/// escape sequence understood by the running terminal for desktop notifications
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NotificationProtocol {
+    /// iTerm2, WezTerm, Windows Terminal, kitty and friends
+    Osc9,
+    /// VTE based terminals, foot and urxvt
+    Osc777,
+}
+
+impl NotificationProtocol {
+    fn detect() -> Self {
+        let term = std::env::var("TERM").unwrap_or_default();
+        if std::env::var_os("VTE_VERSION").is_some()
+            || term.starts_with("foot")
+            || term.starts_with("rxvt")
+        {
+            Self::Osc777
+        } else {
+            Self::Osc9
+        }
+    }
+}
+
+/// control characters would end the escape sequence early
+fn sanitise(text: &str) -> String {
+    text.chars().filter(|c| !c.is_control()).collect()
+}
+
+fn notification_sequence(protocol: NotificationProtocol, title: &str, body: &str) -> String {
+    match protocol {
+        NotificationProtocol::Osc9 => format!("\x1b]9;{}: {}\x07", sanitise(title), sanitise(body)),
+        NotificationProtocol::Osc777 => format!(
+            "\x1b]777;notify;{};{}\x07",
+            sanitise(title).replace(';', ","),
+            sanitise(body)
+        ),
+    }
+}
+
+/// `salti - <file> [<first>-<last> of <total>]`, with 1-based visible columns
+pub fn window_title(input: Option<&str>, columns: Range<usize>, total_columns: usize) -> String {
+    let Some(input) = input else {
+        return "salti".to_string();
+    };
+    let name = input
+        .trim_end_matches('/')
+        .rsplit(['/', '\\', ':'])
+        .next()
+        .filter(|name| !name.is_empty())
+        .unwrap_or(input);
+    if columns.is_empty() {
+        return format!("salti - {name}");
+    }
+    format!(
+        "salti - {name} [{}-{} of {total_columns}]",
+        columns.start + 1,
+        columns.end
+    )
+}
+
+/// keeps the terminal title in step with the view, writing only when it changes
+#[derive(Debug, Default)]
+pub struct TerminalTitle {
+    current: Option<String>,
+}
+
+impl TerminalTitle {
+    pub fn update(&mut self, title: String) {
+        if self.current.as_ref() == Some(&title) {
+            return;
+        }
+        if let Err(error) = execute!(io::stdout(), SetTitle(&title)) {
+            warn!(error = ?error, "Failed to set terminal title");
+        }
+        self.current = Some(title);
+    }
+}
+
+fn write_sequence(sequence: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    stdout.write_all(sequence.as_bytes())?;
+    stdout.flush()
+}
+
+/// remembers the title in use before salti started
+pub fn push_title() {
+    if let Err(error) = write_sequence(PUSH_TITLE) {
+        warn!(error = ?error, "Failed to save terminal title");
+    }
+}
+
+/// puts back the title saved by `push_title`
+pub fn pop_title() {
+    if let Err(error) = write_sequence(POP_TITLE) {
+        warn!(error = ?error, "Failed to restore terminal title");
+    }
+}
+
+/// asks the terminal for a desktop notification. terminals without support ignore it.
+pub fn notify(title: &str, body: &str) {
+    let sequence = notification_sequence(NotificationProtocol::detect(), title, body);
+    if let Err(error) = write_sequence(&sequence) {
+        warn!(error = ?error, "Failed to send terminal notification");
+    }
+}
+
+/// rings the terminal bell
+pub fn bell() {
+    if let Err(error) = write_sequence("\x07") {
+        warn!(error = ?error, "Failed to ring the terminal bell");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_title_shows_file_name_and_visible_columns() {
+        assert_eq!(window_title(None, 0..0, 0), "salti");
+        assert_eq!(
+            window_title(Some("/data/run1/aln.fasta"), 0..120, 30000),
+            "salti - aln.fasta [1-120 of 30000]"
+        );
+        assert_eq!(
+            window_title(Some("host:/srv/aln.fa"), 0..0, 0),
+            "salti - aln.fa"
+        );
+    }
+
+    #[test]
+    fn background_replies_are_classified_by_luminance() {
+        assert_eq!(
+            parse_background_reply(b"\x1b]11;rgb:ffff/ffff/f0f0\x1b\\\x1b[?62;22c"),
+            Some(Background::Light)
+        );
+        assert_eq!(
+            parse_background_reply(b"\x1b]11;rgb:28/2c/34\x07\x1b[?1;2c"),
+            Some(Background::Dark)
+        );
+        assert_eq!(parse_background_reply(b"\x1b[?1;2c"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn waiting_for_input_stops_at_the_timeout() {
+        use std::os::unix::net::UnixStream;
+
+        let (mut writer, reader) = UnixStream::pair().unwrap();
+        let started = std::time::Instant::now();
+        assert!(!wait_readable(&reader, Duration::from_millis(20)).unwrap());
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        writer.write_all(b"x").unwrap();
+        assert!(wait_readable(&reader, Duration::from_millis(20)).unwrap());
+    }
+
+    #[test]
+    fn notifications_use_the_detected_protocol() {
+        assert_eq!(
+            notification_sequence(NotificationProtocol::Osc9, "salti", "Export\ndone"),
+            "\x1b]9;salti: Exportdone\x07"
+        );
+        assert_eq!(
+            notification_sequence(NotificationProtocol::Osc777, "salti; x", "done"),
+            "\x1b]777;notify;salti, x;done\x07"
+        );
+    }
+}
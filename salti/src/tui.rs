@@ -0,0 +1,89 @@
+use anyhow::Result;
+use clap::Parser;
+use crossterm::event::{
+    DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+};
+use crossterm::execute;
+use std::io::stdout;
+use tracing::{error, info};
+
+use crate::app::App;
+use crate::cli::Cli;
+use crate::{config, crash, logging, terminal};
+
+/// mouse reporting and bracketed paste, so pasted text arrives as one event rather than
+/// as keystrokes
+struct MouseCapture {
+    enabled: bool,
+}
+
+impl MouseCapture {
+    fn enable() -> std::io::Result<Self> {
+        execute!(stdout(), EnableMouseCapture, EnableBracketedPaste)?;
+        Ok(Self { enabled: true })
+    }
+
+    fn disable(&mut self) {
+        if !self.enabled {
+            return;
+        }
+
+        self.enabled = false;
+        if let Err(error_value) = execute!(stdout(), DisableMouseCapture, DisableBracketedPaste) {
+            error!(error = ?error_value, "Failed to disable mouse capture");
+        }
+    }
+}
+
+impl Drop for MouseCapture {
+    fn drop(&mut self) {
+        self.disable();
+    }
+}
+
+/// runs the terminal browser until the user quits
+pub async fn run() -> Result<()> {
+    crash::install_panic_hook();
+    let cli = Cli::parse();
+    let _logger = cli
+        .log_options()
+        .map(|options| logging::init_logging(&options))
+        .transpose()?;
+    let settings = config::settings::load_settings(cli.config.as_deref())?;
+    let mut startup = cli.load_startup_sate(&settings)?;
+    info!(
+        has_input_file = startup.file_path.is_some(),
+        initial_position = startup.initial_position,
+        threads = ?startup.threads,
+        "startup state: "
+    );
+
+    info!("Initialising terminal");
+    let terminal = ratatui::init();
+    let mut mouse_capture = match MouseCapture::enable() {
+        Ok(mouse_capture) => mouse_capture,
+        Err(error_value) => {
+            error!(error = ?error_value, "Failed to enable mouse capture");
+            ratatui::restore();
+            return Err(error_value.into());
+        }
+    };
+    if settings.theme.detect_background {
+        startup.theme = terminal::query_background(terminal::BACKGROUND_QUERY_TIMEOUT)
+            .await
+            .map(terminal::Background::default_theme);
+    }
+    terminal::push_title();
+    info!("Loading salti....");
+    let app_result = App::new(startup).run(terminal).await;
+    match &app_result {
+        Ok(()) => {}
+        Err(error_value) => error!(error = ?error_value, "salti exited with error"),
+    }
+
+    mouse_capture.disable();
+    terminal::pop_title();
+    info!("Restoring terminal");
+    ratatui::restore();
+    app_result
+}
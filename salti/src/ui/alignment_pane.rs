@@ -1,28 +1,234 @@
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
 use crate::{
     core::{
         model::{AlignmentModel, DiffMode},
+        orf,
+        restriction::RestrictionSite,
         stats_cache::ColumnStatsCache,
         viewport::{Viewport, ViewportWindow},
     },
     ui::{
-        layout::{AppLayout, RULER_HEIGHT_ROWS, pinned_section_layout},
+        layout::{
+            AppLayout, RULER_HEIGHT_ROWS, WrappedLayout, pinned_section_layout,
+            split_position_gutter,
+        },
+        overview::{OVERVIEW_CELL_COLS, OVERVIEW_CELL_ROWS, overview_lines, sample_cells},
+        row_cache::{RowCache, RowScope},
         rows::{
             RowRenderMode, TranslatedDiffRange, format_row_spans, format_translated_row_spans,
-            visible_bytes, visible_protein_range,
+            mark_orf_issues, visible_bytes, visible_protein_range, visible_qualities,
         },
-        ui_state::ThemeState,
+        ui_state::{ThemeState, UiState},
     },
 };
 use ratatui::Frame;
 use ratatui::layout::Rect;
 use ratatui::macros::vertical;
-use ratatui::style::Styled;
+use ratatui::style::{Color, Style, Styled};
 use ratatui::symbols::merge::MergeStrategy;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Paragraph};
+use serde::Deserialize;
+
+/// columns between the ruler's tick marks. every second tick is numbered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum RulerTicks {
+    #[default]
+    #[serde(rename = "5")]
+    Five,
+    #[serde(rename = "10")]
+    Ten,
+    #[serde(rename = "20")]
+    Twenty,
+    /// a tick on every codon boundary, numbered every ten codons
+    #[serde(rename = "codon")]
+    Codon,
+}
+
+impl RulerTicks {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Five => "5",
+            Self::Ten => "10",
+            Self::Twenty => "20",
+            Self::Codon => "codon",
+        }
+    }
+
+    pub const fn all() -> [Self; 4] {
+        [Self::Five, Self::Ten, Self::Twenty, Self::Codon]
+    }
+
+    /// columns between ticks, and between numbered ticks
+    const fn spacing(self) -> (usize, usize) {
+        match self {
+            Self::Five => (5, 10),
+            Self::Ten => (10, 20),
+            Self::Twenty => (20, 40),
+            Self::Codon => (3, 30),
+        }
+    }
+}
+
+impl fmt::Display for RulerTicks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for RulerTicks {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|ticks| ticks.name() == value)
+            .ok_or_else(|| anyhow::format_err!("invalid ruler ticks: {value}"))
+    }
+}
+
+/// whether ruler positions count from 1, like the rest of salti, or from 0 like BED files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum Coordinates {
+    #[default]
+    #[serde(rename = "1-based")]
+    OneBased,
+    #[serde(rename = "0-based")]
+    ZeroBased,
+}
+
+impl Coordinates {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::OneBased => "1-based",
+            Self::ZeroBased => "0-based",
+        }
+    }
+
+    pub const fn all() -> [Self; 2] {
+        [Self::OneBased, Self::ZeroBased]
+    }
+
+    /// position shown for the first column
+    const fn origin(self) -> usize {
+        match self {
+            Self::OneBased => 1,
+            Self::ZeroBased => 0,
+        }
+    }
+}
+
+impl fmt::Display for Coordinates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Coordinates {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|coordinates| coordinates.name() == value)
+            .ok_or_else(|| anyhow::format_err!("invalid coordinates: {value}"))
+    }
+}
+
+/// how the alignment ruler and position gutter number columns
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RulerOptions {
+    pub ticks: RulerTicks,
+    pub coordinates: Coordinates,
+}
+
+impl fmt::Display for RulerOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ticks every {}, {}", self.ticks, self.coordinates)
+    }
+}
+
+/// how many alignment rows and columns each terminal cell of the panned alignment pane shows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Density {
+    #[default]
+    Normal,
+    /// two rows per line drawn as coloured half-blocks, without residue letters
+    Compact,
+    /// 2x4 blocks of rows and columns per cell drawn as braille dots, coloured by how well the
+    /// residues agree with the consensus
+    Overview,
+}
+
+impl Density {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Compact => "compact",
+            Self::Overview => "overview",
+        }
+    }
+
+    pub const fn all() -> [Self; 3] {
+        [Self::Normal, Self::Compact, Self::Overview]
+    }
+
+    pub const fn rows_per_line(self) -> usize {
+        match self {
+            Self::Normal => 1,
+            Self::Compact => 2,
+            Self::Overview => OVERVIEW_CELL_ROWS,
+        }
+    }
+
+    pub const fn cols_per_cell(self) -> usize {
+        match self {
+            Self::Normal | Self::Compact => 1,
+            Self::Overview => OVERVIEW_CELL_COLS,
+        }
+    }
+
+    pub const fn zoomed_out(self) -> Option<Self> {
+        match self {
+            Self::Normal => Some(Self::Compact),
+            Self::Compact => Some(Self::Overview),
+            Self::Overview => None,
+        }
+    }
+
+    pub const fn zoomed_in(self) -> Option<Self> {
+        match self {
+            Self::Normal => None,
+            Self::Compact => Some(Self::Normal),
+            Self::Overview => Some(Self::Compact),
+        }
+    }
+}
+
+impl fmt::Display for Density {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Density {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|density| density.name() == value)
+            .ok_or_else(|| anyhow::format_err!("invalid density: {value}"))
+    }
+}
 
 const SCROLLBAR_THUMB_WIDTH: usize = 3;
 const SCROLLBAR_THUMB_MIN_WIDTH: usize = 1;
+const DEPTH_BARS: [&str; 8] = ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
 
 fn raw_render_mode<'a>(
     alignment: &AlignmentModel,
@@ -38,9 +244,54 @@ fn raw_render_mode<'a>(
     RowRenderMode {
         alignment_type: alignment.base().active_type(),
         diff_against,
+        diff_ambiguity: alignment.diff_ambiguity,
+        diff_options: alignment.diff_options,
+        diff_columns: None,
+        qualities: None,
+        conservation: None,
     }
 }
 
+/// columns from the first to the last residue of `sequence`
+fn residue_span(sequence: libmsa::SequenceView<'_>) -> Option<Range<usize>> {
+    let is_residue = |col| {
+        sequence
+            .byte_at(col)
+            .is_some_and(|byte| !matches!(byte, b'-' | b'.'))
+    };
+    let start = (0..sequence.len()).find(|&col| is_residue(col))?;
+    let end = (start..sequence.len()).rfind(|&col| is_residue(col))? + 1;
+    Some(start..end)
+}
+
+/// the visible columns, relative to the window, where both the row and what it is diffed against
+/// have residues. `None` when end gaps are not being ignored.
+fn visible_diff_columns(
+    alignment: &AlignmentModel,
+    sequence: libmsa::SequenceView<'_>,
+    reference_span: Option<&Range<usize>>,
+    col_range: &Range<usize>,
+) -> Option<Range<usize>> {
+    if !alignment.diff_options.ignore_end_gaps || alignment.diff_mode == DiffMode::Off {
+        return None;
+    }
+    let span = residue_span(sequence).map(|span| match reference_span {
+        Some(reference) if alignment.diff_mode == DiffMode::Reference => {
+            span.start.max(reference.start)..span.end.min(reference.end)
+        }
+        _ => span,
+    });
+    let Some(span) = span.filter(|span| !span.is_empty()) else {
+        return Some(0..0);
+    };
+    let start = span
+        .start
+        .max(col_range.start)
+        .saturating_sub(col_range.start);
+    let end = span.end.min(col_range.end).saturating_sub(col_range.start);
+    Some(start..end.max(start))
+}
+
 fn translated_diff_range<'a>(
     diff_mode: DiffMode,
     protein_range_start: usize,
@@ -58,17 +309,72 @@ fn translated_diff_range<'a>(
     }
 }
 
+fn mark_row_orf_issues(
+    spans: &mut [Span<'static>],
+    alignment: &AlignmentModel,
+    absolute_row: usize,
+    translated: libmsa::TranslatedSequenceView<'_>,
+    window: &ViewportWindow,
+    theme: &ThemeState,
+) {
+    let (Some(frame), Some(sequence)) = (
+        alignment.translation(),
+        alignment.view().project_absolute_row(absolute_row),
+    ) else {
+        return;
+    };
+    let scan = orf::scan_range(&window.col_range, alignment.view().column_count());
+    let issues = orf::row_issues(sequence, translated, frame, &scan);
+    mark_orf_issues(spans, &issues, &window.col_range, frame, theme.styles.error);
+}
+
+/// scope of the rows drawn in `window`, shared by every row formatted for it
+fn row_scope(
+    alignment: &AlignmentModel,
+    window: &ViewportWindow,
+    metrics: &ColumnStatsCache,
+    theme: &ThemeState,
+    diff_against: Option<&[u8]>,
+    conservation: Option<&[Option<f32>]>,
+) -> RowScope {
+    RowScope {
+        generation: metrics.generation,
+        col_range: window.col_range.clone(),
+        alignment_type: alignment.base().active_type(),
+        translation: alignment.translation(),
+        diff_mode: alignment.diff_mode,
+        diff_against: diff_against.map(<[u8]>::to_vec),
+        diff_ambiguity: alignment.diff_ambiguity,
+        diff_options: alignment.diff_options,
+        quality_shading: alignment.quality_shading,
+        conservation: conservation.map(<[Option<f32>]>::to_vec),
+        theme: (theme.id, theme.palette, theme.color_style),
+    }
+}
+
+/// formats the visible rows. with a `cache` rows whose bytes did not change since the last
+/// frame are reused, and the translated view skips marking ORF issues.
 fn build_sequence_row_lines(
     alignment: &AlignmentModel,
     window: &ViewportWindow,
     metrics: &ColumnStatsCache,
     area: Rect,
     theme: &ThemeState,
+    cache: Option<&RowCache>,
 ) -> Vec<Line<'static>> {
     let band_layout = pinned_section_layout(alignment.rows().pinned().len(), area.height as usize);
     let mut lines = Vec::with_capacity(
         band_layout.pinned_rendered + band_layout.divider_height + window.row_range.len(),
     );
+    let divider = |lines: &mut Vec<Line<'static>>| {
+        if band_layout.divider_height == 1 {
+            lines.push(Line::from(
+                "─"
+                    .repeat(area.width as usize)
+                    .set_style(theme.styles.border),
+            ));
+        }
+    };
 
     if let Some(translated) = alignment.translated_view() {
         let frame = alignment
@@ -107,6 +413,50 @@ fn build_sequence_row_lines(
                 consensus_bytes.as_deref(),
             )
         });
+        let mut cached = cache.map(|cache| {
+            let diff_bytes = match alignment.diff_mode {
+                DiffMode::Off => None,
+                DiffMode::Reference => reference_bytes.as_deref(),
+                DiffMode::Consensus => consensus_bytes.as_deref(),
+            };
+            cache.rows(row_scope(
+                alignment, window, metrics, theme, diff_bytes, None,
+            ))
+        });
+        let mut row_line = |absolute_row: usize, sequence| {
+            let format = || {
+                Line::from(format_translated_row_spans(
+                    sequence,
+                    &window.col_range,
+                    nucleotide_len,
+                    frame,
+                    &theme.theme.sequence,
+                    diff_against,
+                ))
+            };
+            match cached.as_mut() {
+                Some(cached) => {
+                    let bytes = alignment
+                        .view()
+                        .project_absolute_row(absolute_row)
+                        .map(|row| visible_bytes(row, &window.col_range))
+                        .unwrap_or_default();
+                    cached.line(absolute_row, &bytes, format)
+                }
+                None => {
+                    let mut line = format();
+                    mark_row_orf_issues(
+                        &mut line.spans,
+                        alignment,
+                        absolute_row,
+                        sequence,
+                        window,
+                        theme,
+                    );
+                    line
+                }
+            }
+        };
 
         for &absolute_row in alignment
             .rows()
@@ -117,24 +467,10 @@ fn build_sequence_row_lines(
             let Some(sequence) = translated.project_absolute_row(absolute_row) else {
                 continue;
             };
-            let spans = format_translated_row_spans(
-                sequence,
-                &window.col_range,
-                nucleotide_len,
-                frame,
-                &theme.theme.sequence,
-                diff_against,
-            );
-            lines.push(Line::from(spans));
+            lines.push(row_line(absolute_row, sequence));
         }
 
-        if band_layout.divider_height == 1 {
-            lines.push(Line::from(
-                "─"
-                    .repeat(area.width as usize)
-                    .set_style(theme.styles.border),
-            ));
-        }
+        divider(&mut lines);
 
         for relative_row in window.row_range.clone() {
             let Some(absolute_row) = alignment.view().absolute_row_id(relative_row) else {
@@ -143,25 +479,21 @@ fn build_sequence_row_lines(
             let Some(sequence) = translated.sequence_by_absolute(absolute_row) else {
                 continue;
             };
-            let spans = format_translated_row_spans(
-                sequence,
-                &window.col_range,
-                nucleotide_len,
-                frame,
-                &theme.theme.sequence,
-                diff_against,
-            );
-            lines.push(Line::from(spans));
+            lines.push(row_line(absolute_row, sequence));
         }
 
         return lines;
     }
 
-    let reference_bytes: Option<Vec<u8>> = alignment
+    let reference_row = alignment
         .rows()
         .reference()
-        .and_then(|abs_row| alignment.view().project_absolute_row(abs_row))
-        .map(|sequence| visible_bytes(sequence, &window.col_range));
+        .and_then(|abs_row| alignment.view().project_absolute_row(abs_row));
+    let reference_bytes: Option<Vec<u8>> =
+        reference_row.map(|sequence| visible_bytes(sequence, &window.col_range));
+    let reference_span = reference_row
+        .filter(|_| alignment.diff_options.ignore_end_gaps)
+        .and_then(residue_span);
     let consensus_bytes: Option<Vec<u8>> = window
         .col_range
         .clone()
@@ -171,11 +503,60 @@ fn build_sequence_row_lines(
                 .map(|summary| summary.consensus.unwrap_or(b' '))
         })
         .collect();
-    let render_mode = raw_render_mode(
-        alignment,
-        reference_bytes.as_deref(),
-        consensus_bytes.as_deref(),
-    );
+    let conservation: Option<Vec<Option<f32>>> = alignment.conservation_emphasis.then(|| {
+        window
+            .col_range
+            .clone()
+            .map(|relative_col| {
+                metrics
+                    .raw_summary_at(relative_col)
+                    .and_then(|summary| summary.conservation)
+            })
+            .collect()
+    });
+    let render_mode = RowRenderMode {
+        conservation: conservation.as_deref(),
+        ..raw_render_mode(
+            alignment,
+            reference_bytes.as_deref(),
+            consensus_bytes.as_deref(),
+        )
+    };
+    let mut cached = cache.map(|cache| {
+        cache.rows(row_scope(
+            alignment,
+            window,
+            metrics,
+            theme,
+            render_mode.diff_against,
+            render_mode.conservation,
+        ))
+    });
+    let mut row_line = |absolute_row: usize, sequence| {
+        let bytes = visible_bytes(sequence, &window.col_range);
+        let format = || {
+            let qualities = alignment
+                .quality_shading
+                .then(|| visible_qualities(sequence, &window.col_range))
+                .flatten();
+            let diff_columns = visible_diff_columns(
+                alignment,
+                sequence,
+                reference_span.as_ref(),
+                &window.col_range,
+            );
+            let render_mode = RowRenderMode {
+                qualities: qualities.as_deref(),
+                diff_columns: diff_columns.as_ref(),
+                ..render_mode
+            };
+            Line::from(format_row_spans(&bytes, &theme.theme.sequence, render_mode))
+        };
+        match cached.as_mut() {
+            Some(cached) => cached.line(absolute_row, &bytes, format),
+            None => format(),
+        }
+    };
 
     for &absolute_row in alignment
         .rows()
@@ -186,28 +567,79 @@ fn build_sequence_row_lines(
         let Some(projected_row) = alignment.view().project_absolute_row(absolute_row) else {
             continue;
         };
-        let bytes = visible_bytes(projected_row, &window.col_range);
-        let spans = format_row_spans(&bytes, &theme.theme.sequence, render_mode);
-        lines.push(Line::from(spans));
+        lines.push(row_line(absolute_row, projected_row));
     }
 
-    if band_layout.divider_height == 1 {
-        lines.push(Line::from(
-            "─"
-                .repeat(area.width as usize)
-                .set_style(theme.styles.border),
-        ));
-    }
+    divider(&mut lines);
 
     for relative_row in window.row_range.clone() {
+        let Some(absolute_row) = alignment.view().absolute_row_id(relative_row) else {
+            continue;
+        };
         let Some(sequence) = alignment.view().sequence(relative_row) else {
             continue;
         };
-        let bytes = visible_bytes(sequence, &window.col_range);
-        let spans = format_row_spans(&bytes, &theme.theme.sequence, render_mode);
-        lines.push(Line::from(spans));
+        lines.push(row_line(absolute_row, sequence));
+    }
+
+    lines
+}
+
+/// colour a residue cell is drawn in when packed into a half-block. blanks and gaps are left
+/// empty so they still read as gaps.
+fn cell_colour(symbol: char, style: Style) -> Option<Color> {
+    if matches!(symbol, ' ' | '-') {
+        return style.bg;
     }
+    style.bg.or(style.fg)
+}
 
+/// the cells of a formatted row as a colour each
+fn line_colours(line: &Line<'_>) -> Vec<Option<Color>> {
+    line.spans
+        .iter()
+        .flat_map(|span| {
+            let style = line.style.patch(span.style);
+            span.content
+                .chars()
+                .map(move |symbol| cell_colour(symbol, style))
+        })
+        .collect()
+}
+
+/// packs two formatted rows into one line of half-blocks: the upper half takes the colour of
+/// `top` and the lower half the colour of `bottom`
+fn pack_row_pair(top: &Line<'_>, bottom: Option<&Line<'_>>) -> Line<'static> {
+    let top = line_colours(top);
+    let bottom = bottom.map(line_colours).unwrap_or_default();
+    let cells = top.len().max(bottom.len());
+    Line::from(
+        (0..cells)
+            .map(|index| {
+                let upper = top.get(index).copied().flatten();
+                let lower = bottom.get(index).copied().flatten();
+                match (upper, lower) {
+                    (Some(upper), Some(lower)) => {
+                        Span::styled("▀", Style::new().fg(upper).bg(lower))
+                    }
+                    (Some(upper), None) => Span::styled("▀", Style::new().fg(upper)),
+                    (None, Some(lower)) => Span::styled("▄", Style::new().fg(lower)),
+                    (None, None) => Span::raw(" "),
+                }
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// compact writer: keeps the pinned band and divider as they are and packs the scrollable rows
+/// after them two to a line
+fn pack_scrollable_rows(mut lines: Vec<Line<'static>>, header_lines: usize) -> Vec<Line<'static>> {
+    let scrollable = lines.split_off(header_lines.min(lines.len()));
+    lines.extend(
+        scrollable
+            .chunks(2)
+            .map(|pair| pack_row_pair(&pair[0], pair.get(1))),
+    );
     lines
 }
 
@@ -217,9 +649,36 @@ fn render_sequence_rows(
     window: &ViewportWindow,
     metrics: &ColumnStatsCache,
     area: Rect,
-    theme: &ThemeState,
+    ui: &UiState,
+    cache: Option<&RowCache>,
 ) {
-    let lines = build_sequence_row_lines(alignment, window, metrics, area, theme);
+    let theme = &ui.theme;
+    let band_layout = pinned_section_layout(alignment.rows().pinned().len(), area.height as usize);
+    let lines = match ui.effective_density() {
+        Density::Normal => build_sequence_row_lines(alignment, window, metrics, area, theme, cache),
+        Density::Compact => pack_scrollable_rows(
+            build_sequence_row_lines(alignment, window, metrics, area, theme, cache),
+            band_layout.pinned_rendered + band_layout.divider_height,
+        ),
+        Density::Overview => {
+            // pinned rows keep their letters, sampled down to the overview's columns
+            let pinned_window = ViewportWindow {
+                row_range: window.row_range.start..window.row_range.start,
+                ..window.clone()
+            };
+            let mut lines =
+                build_sequence_row_lines(alignment, &pinned_window, metrics, area, theme, cache);
+            for line in lines.iter_mut().take(band_layout.pinned_rendered) {
+                *line = sample_cells(
+                    std::mem::take(line),
+                    window.col_range.len(),
+                    OVERVIEW_CELL_COLS,
+                );
+            }
+            lines.extend(overview_lines(alignment, window, metrics, &theme.theme));
+            lines
+        }
+    };
     f.render_widget(Paragraph::new(lines).style(theme.styles.base_block), area);
 }
 
@@ -383,8 +842,11 @@ fn dense_break_spans(breaks: &[(usize, BreakMarker)], width: usize) -> Vec<(usiz
 
 fn build_ruler(
     absolute_columns: &[usize],
-    filtered_leading: bool,
-    filtered_trailing: bool,
+    gap_only: &[bool],
+    restriction_sites: &[RestrictionSite],
+    partition_starts: &[usize],
+    breaks: &[(usize, BreakMarker)],
+    options: RulerOptions,
     theme: &ThemeState,
 ) -> (Line<'static>, Line<'static>) {
     let width = absolute_columns.len();
@@ -394,27 +856,40 @@ fn build_ruler(
 
     let mut number_line = vec![Span::raw(" "); width];
     let mut marker_line = vec![Span::raw(" "); width];
+    let (tick, major_tick) = options.ticks.spacing();
+    let origin = options.coordinates.origin();
 
     for (index, marker_span) in marker_line.iter_mut().enumerate() {
-        let display_pos = absolute_columns[index] + 1;
-        if display_pos == 1 || display_pos.is_multiple_of(5) {
-            let is_major_tick = display_pos.is_multiple_of(10);
+        let display_pos = absolute_columns[index] + origin;
+        if display_pos == origin || display_pos.is_multiple_of(tick) {
+            let is_major_tick = display_pos.is_multiple_of(major_tick);
             *marker_span = if is_major_tick {
                 "|".set_style(theme.styles.accent)
             } else {
                 ".".set_style(theme.styles.text_dim)
             };
 
-            if is_major_tick || display_pos == 1 {
+            if is_major_tick || display_pos == origin {
                 add_number_to_ruler(&mut number_line, index, display_pos, theme);
             }
         }
+        if gap_only.get(index).copied().unwrap_or(false) {
+            *marker_span = "-".set_style(theme.styles.text_dim);
+        }
+        if restriction_sites
+            .iter()
+            .any(|site| (site.first_col..=site.last_col).contains(&absolute_columns[index]))
+        {
+            *marker_span = "^".set_style(theme.styles.accent_alt);
+        }
+        if partition_starts.contains(&absolute_columns[index]) {
+            *marker_span = "‖".set_style(theme.styles.accent);
+        }
     }
 
-    let breaks = break_positions(absolute_columns, filtered_leading, filtered_trailing);
-    let dense_spans = dense_break_spans(&breaks, width);
+    let dense_spans = dense_break_spans(breaks, width);
 
-    for (position, marker) in breaks {
+    for &(position, marker) in breaks {
         let marker_position = dense_break_marker_position(position, marker, width);
         if dense_spans
             .iter()
@@ -439,18 +914,74 @@ fn build_ruler(
     (Line::from(number_line), Line::from(marker_line))
 }
 
+/// shrinks a ruler built one cell per column to one cell per `step` columns. each cell keeps the
+/// first marker of its columns, and numbers are re-centred on their tick, dropping any that
+/// would run into the number before.
+fn sample_ruler(
+    number_line: Line<'static>,
+    marker_line: Line<'static>,
+    step: usize,
+) -> (Line<'static>, Line<'static>) {
+    if step <= 1 {
+        return (number_line, marker_line);
+    }
+    let is_blank = |span: &Span<'_>| span.content.trim().is_empty();
+    let markers: Vec<Span<'static>> = marker_line
+        .spans
+        .chunks(step)
+        .map(|cell| {
+            cell.iter()
+                .find(|span| !is_blank(span))
+                .unwrap_or(&cell[0])
+                .clone()
+        })
+        .collect();
+
+    let width = markers.len();
+    let mut numbers = vec![Span::raw(" "); width];
+    let mut next_free = 0;
+    let mut index = 0;
+    while index < number_line.spans.len() {
+        if is_blank(&number_line.spans[index]) {
+            index += 1;
+            continue;
+        }
+        let end = (index..number_line.spans.len())
+            .find(|&end| is_blank(&number_line.spans[end]))
+            .unwrap_or(number_line.spans.len());
+        let digits = &number_line.spans[index..end];
+        let centre = (index + digits.len() / 2) / step;
+        let start = centre
+            .saturating_sub(digits.len() / 2)
+            .min(width.saturating_sub(digits.len()));
+        if start >= next_free && start + digits.len() <= width {
+            numbers[start..start + digits.len()].clone_from_slice(digits);
+            next_free = start + digits.len() + 1;
+        }
+        index = end;
+    }
+    (Line::from(numbers), Line::from(markers))
+}
+
 fn render_ruler(
     f: &mut Frame,
     alignment: &AlignmentModel,
     window: &ViewportWindow,
+    metrics: &ColumnStatsCache,
     area: Rect,
-    theme: &ThemeState,
+    ui: &UiState,
 ) {
+    let (options, theme) = (ui.ruler, &ui.theme);
     let absolute_columns: Vec<usize> = window
         .col_range
         .clone()
         .filter_map(|relative_col| alignment.view().absolute_column_id(relative_col))
         .collect();
+    let gap_only: Vec<bool> = window
+        .col_range
+        .clone()
+        .map(|relative_col| metrics.is_gap_only(relative_col))
+        .collect();
     let filtered_leading = window.col_range.start == 0
         && alignment
             .view()
@@ -462,39 +993,236 @@ fn render_ruler(
             .view()
             .absolute_column_id(alignment.view().column_count().saturating_sub(1))
             .is_some_and(|last| last < alignment.base().column_count() - 1);
+    let partition_starts: Vec<usize> = alignment
+        .partitions
+        .iter()
+        .skip(1)
+        .map(|partition| partition.columns.start)
+        .collect();
     let (number_line, marker_line) = build_ruler(
         &absolute_columns,
-        filtered_leading,
-        filtered_trailing,
+        &gap_only,
+        &alignment.restriction_sites,
+        &partition_starts,
+        &break_positions(&absolute_columns, filtered_leading, filtered_trailing),
+        options,
         theme,
     );
+    let (number_line, marker_line) = sample_ruler(
+        number_line,
+        marker_line,
+        ui.effective_density().cols_per_cell(),
+    );
     f.render_widget(
         Paragraph::new(vec![number_line, marker_line]).style(theme.styles.base_block),
         area,
     );
 }
 
-pub fn render_alignment_pane(
+fn depth_to_bar(fraction: f32) -> &'static str {
+    if fraction <= 0.0 {
+        return " ";
+    }
+    let level = (fraction.min(1.0) * DEPTH_BARS.len() as f32).ceil() as usize;
+    DEPTH_BARS[level.clamp(1, DEPTH_BARS.len()) - 1]
+}
+
+/// a bar per visible column for the share of visible rows with a residue there, so ragged
+/// ends and low coverage stand out
+fn build_depth_line(
+    alignment: &AlignmentModel,
+    window: &ViewportWindow,
+    metrics: &ColumnStatsCache,
+    theme: &ThemeState,
+) -> Line<'static> {
+    if alignment.view().row_count() == 0 {
+        return Line::default();
+    }
+    let mut bars = String::with_capacity(window.col_range.len() * 3);
+    for relative_col in window.col_range.clone() {
+        let Some(summary) = metrics.raw_summary_at(relative_col) else {
+            return Line::from("Calculating depth...".set_style(theme.styles.text_dim));
+        };
+        bars.push_str(depth_to_bar(1.0 - summary.gap_fraction));
+    }
+    Line::from(bars.set_style(theme.styles.accent_alt))
+}
+
+fn render_depth_track(
     f: &mut Frame,
-    layout: &AppLayout,
     alignment: &AlignmentModel,
-    viewport: &Viewport,
+    window: &ViewportWindow,
     metrics: &ColumnStatsCache,
+    area: Rect,
+    ui: &UiState,
+) {
+    if area.height == 0 {
+        return;
+    }
+    let line = build_depth_line(alignment, window, metrics, &ui.theme);
+    f.render_widget(
+        Paragraph::new(sample_cells(
+            line,
+            window.col_range.len(),
+            ui.effective_density().cols_per_cell(),
+        ))
+        .style(ui.theme.styles.base_block),
+        area,
+    );
+}
+
+/// repeats the position of the first column of `window` on every row, so copied text keeps
+/// its coordinates
+fn render_position_gutter(
+    f: &mut Frame,
+    alignment: &AlignmentModel,
+    window: &ViewportWindow,
+    area: Rect,
+    ruler_height: u16,
+    coordinates: Coordinates,
     theme: &ThemeState,
 ) {
+    if area.width == 0 {
+        return;
+    }
+    let Some(start) = alignment.view().absolute_column_id(window.col_range.start) else {
+        return;
+    };
+    let width = usize::from(area.width);
+    let label = format!("{:>width$}", format!("{} ", start + coordinates.origin()));
+    let band_layout = pinned_section_layout(
+        alignment.rows().pinned().len(),
+        usize::from(area.height.saturating_sub(ruler_height)),
+    );
+    let mut lines = vec![Line::from(""); usize::from(ruler_height)];
+    for _ in 0..band_layout.pinned_rendered {
+        lines.push(Line::from(label.clone().set_style(theme.styles.text_dim)));
+    }
+    if band_layout.divider_height == 1 {
+        lines.push(Line::from("─".repeat(width).set_style(theme.styles.border)));
+    }
+    for _ in window.row_range.clone() {
+        lines.push(Line::from(label.clone().set_style(theme.styles.text_dim)));
+    }
+    f.render_widget(Paragraph::new(lines).style(theme.styles.base_block), area);
+}
+
+fn render_pane_block(f: &mut Frame, area: Rect, theme: &ThemeState) -> Rect {
     let block = Block::bordered()
         .title(Line::from("Alignment".set_style(theme.styles.accent)))
         .border_style(theme.styles.border)
         .style(theme.styles.base_block)
         .merge_borders(MergeStrategy::Exact);
-    let inner_area = block.inner(layout.alignment_pane);
-    f.render_widget(block, layout.alignment_pane);
+    let inner_area = block.inner(area);
+    f.render_widget(block, area);
+    inner_area
+}
+
+pub fn render_alignment_pane(
+    f: &mut Frame,
+    layout: &AppLayout,
+    alignment: &AlignmentModel,
+    metrics: &ColumnStatsCache,
+    ui: &UiState,
+) {
+    let (viewport, ruler, theme) = (&ui.viewport, ui.ruler, &ui.theme);
+    let inner_area = render_pane_block(f, layout.alignment_pane, theme);
+    let [gutter_area, inner_area] = split_position_gutter(inner_area, layout.position_gutter);
+
+    let [ruler_area, sequence_rows_area] =
+        inner_area.layout(&vertical![==layout.ruler_height, *=1]);
+    let [ruler_area, depth_area] = ruler_area.layout(&vertical![==RULER_HEIGHT_ROWS, *=1]);
+    let window = viewport.window();
+
+    render_position_gutter(
+        f,
+        alignment,
+        &window,
+        gutter_area,
+        layout.ruler_height,
+        ruler.coordinates,
+        theme,
+    );
+    render_ruler(f, alignment, &window, metrics, ruler_area, ui);
+    render_depth_track(f, alignment, &window, metrics, depth_area, ui);
+    render_sequence_rows(
+        f,
+        alignment,
+        &window,
+        metrics,
+        sequence_rows_area,
+        ui,
+        ui.fast_row_cache(layout),
+    );
+    render_scrollbar(
+        f,
+        alignment,
+        viewport,
+        &window,
+        theme,
+        layout.alignment_pane,
+    );
+}
+
+/// column range shown by the wrapped block at `index`, split from the full viewport window
+pub fn wrapped_block_window(
+    window: &ViewportWindow,
+    index: usize,
+    block_width: usize,
+) -> ViewportWindow {
+    let start = window
+        .col_range
+        .start
+        .saturating_add(index * block_width)
+        .min(window.col_range.end);
+    let end = start.saturating_add(block_width).min(window.col_range.end);
 
-    let [ruler_area, sequence_rows_area] = inner_area.layout(&vertical![==RULER_HEIGHT_ROWS, *=1]);
+    ViewportWindow {
+        col_range: start..end,
+        ..window.clone()
+    }
+}
+
+pub fn render_wrapped_alignment_pane(
+    f: &mut Frame,
+    layout: &AppLayout,
+    alignment: &AlignmentModel,
+    wrapped: WrappedLayout,
+    metrics: &ColumnStatsCache,
+    ui: &UiState,
+) {
+    let (viewport, ruler, theme) = (&ui.viewport, ui.ruler, &ui.theme);
+    let inner_area = render_pane_block(f, layout.alignment_pane, theme);
     let window = viewport.window();
+    let block_width = layout.alignment_content_width();
 
-    render_ruler(f, alignment, &window, ruler_area, theme);
-    render_sequence_rows(f, alignment, &window, metrics, sequence_rows_area, theme);
+    for (index, block_area) in wrapped.block_areas(inner_area).enumerate() {
+        let block_window = wrapped_block_window(&window, index, block_width);
+        let [gutter_area, block_area] = split_position_gutter(block_area, layout.position_gutter);
+        render_position_gutter(
+            f,
+            alignment,
+            &block_window,
+            gutter_area,
+            wrapped.ruler_height,
+            ruler.coordinates,
+            theme,
+        );
+        let [ruler_area, sequence_rows_area] =
+            block_area.layout(&vertical![==wrapped.ruler_height, *=1]);
+        let [ruler_area, depth_area] = ruler_area.layout(&vertical![==RULER_HEIGHT_ROWS, *=1]);
+        render_ruler(f, alignment, &block_window, metrics, ruler_area, ui);
+        render_depth_track(f, alignment, &block_window, metrics, depth_area, ui);
+        render_sequence_rows(
+            f,
+            alignment,
+            &block_window,
+            metrics,
+            sequence_rows_area,
+            ui,
+            ui.fast_row_cache(layout),
+        );
+    }
     render_scrollbar(
         f,
         alignment,
@@ -504,3 +1232,106 @@ pub fn render_alignment_pane(
         layout.alignment_pane,
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ruler_text(options: RulerOptions) -> (String, String) {
+        let columns: Vec<usize> = (0..12).collect();
+        let (numbers, markers) = build_ruler(
+            &columns,
+            &[],
+            &[],
+            &[],
+            &[],
+            options,
+            &ThemeState::default(),
+        );
+        (numbers.to_string(), markers.to_string())
+    }
+
+    #[test]
+    fn compact_rows_pack_into_half_blocks() {
+        let top = Line::from(vec![
+            Span::styled("A", Style::new().fg(Color::Red)),
+            Span::styled("-", Style::new().fg(Color::Gray)),
+            Span::raw("C"),
+        ]);
+        let bottom = Line::from(vec![
+            Span::styled("G", Style::new().bg(Color::Green)),
+            Span::styled("T", Style::new().fg(Color::Blue)),
+        ]);
+
+        let packed = pack_row_pair(&top, Some(&bottom));
+        let cells: Vec<(&str, Style)> = packed
+            .spans
+            .iter()
+            .map(|span| (span.content.as_ref(), span.style))
+            .collect();
+        assert_eq!(
+            cells,
+            vec![
+                ("▀", Style::new().fg(Color::Red).bg(Color::Green)),
+                ("▄", Style::new().fg(Color::Blue)),
+                (" ", Style::new()),
+            ]
+        );
+
+        let lines = pack_scrollable_rows(vec![Line::from("pinned"), top.clone(), bottom, top], 1);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], Line::from("pinned"));
+    }
+
+    #[test]
+    fn depth_bars_never_hide_a_covered_column() {
+        assert_eq!(depth_to_bar(0.0), " ");
+        assert_eq!(depth_to_bar(0.01), "▁");
+        assert_eq!(depth_to_bar(0.5), "▄");
+        assert_eq!(depth_to_bar(1.0), "█");
+    }
+
+    #[test]
+    fn ruler_ticks_follow_spacing_and_coordinates() {
+        assert_eq!(
+            ruler_text(RulerOptions::default()),
+            ("1       10  ".to_string(), ".   .    |  ".to_string())
+        );
+        assert_eq!(
+            ruler_text(RulerOptions {
+                ticks: RulerTicks::Codon,
+                coordinates: Coordinates::ZeroBased,
+            }),
+            ("0           ".to_string(), "|  .  .  .  ".to_string())
+        );
+    }
+
+    #[test]
+    fn overview_ruler_keeps_ticks_over_their_cells() {
+        let columns: Vec<usize> = (0..12).collect();
+        let (numbers, markers) = build_ruler(
+            &columns,
+            &[],
+            &[],
+            &[],
+            &[],
+            RulerOptions::default(),
+            &ThemeState::default(),
+        );
+        let (numbers, markers) = sample_ruler(numbers, markers, 2);
+        assert_eq!(
+            (numbers.to_string(), markers.to_string()),
+            ("1  10 ".to_string(), ". . | ".to_string())
+        );
+    }
+
+    #[test]
+    fn zooming_steps_through_the_densities() {
+        assert_eq!(Density::Normal.zoomed_out(), Some(Density::Compact));
+        assert_eq!(Density::Compact.zoomed_out(), Some(Density::Overview));
+        assert_eq!(Density::Overview.zoomed_out(), None);
+        assert_eq!(Density::Overview.zoomed_in(), Some(Density::Compact));
+        assert_eq!(Density::Normal.zoomed_in(), None);
+        assert_eq!("overview".parse::<Density>().unwrap(), Density::Overview);
+    }
+}
@@ -1,20 +1,26 @@
 use crate::{
-    core::{model::AlignmentModel, stats_cache::ColumnStatsCache, viewport::ViewportWindow},
+    core::{
+        export::thresholded_consensus,
+        model::{AlignmentModel, ConsensusScope, DiffAmbiguity, DiffOptions},
+        stats_cache::ColumnStatsCache,
+        viewport::ViewportWindow,
+    },
     ui::{
-        layout::AppLayout,
+        layout::{AppLayout, split_position_gutter},
+        overview::sample_cells,
         rows::{
             RowRenderMode, TranslatedByteRange, format_row_spans,
-            format_translated_byte_range_spans, format_translated_row_spans, visible_bytes,
-            visible_protein_range,
+            format_translated_byte_range_spans, format_translated_row_spans,
+            translated_window_spans, visible_bytes, visible_protein_range,
         },
-        ui_state::ThemeState,
+        ui_state::{ThemeState, UiState},
     },
 };
 use ratatui::Frame;
 use ratatui::layout::Rect;
 use ratatui::style::{Styled, Stylize};
 use ratatui::symbols::merge::MergeStrategy;
-use ratatui::text::Line;
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Paragraph};
 
 const CONSERVATION_SPARK_STRS: [&str; 8] = ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
@@ -89,7 +95,14 @@ fn translated_consensus_line(
         .map(|protein_col| {
             metrics
                 .translated_summary_at(frame, protein_col)
-                .map(|summary| summary.consensus.unwrap_or(b' '))
+                .map(|summary| {
+                    thresholded_consensus(
+                        summary,
+                        alignment.consensus_threshold,
+                        libmsa::AlignmentType::Protein,
+                    )
+                    .unwrap_or(b' ')
+                })
         })
         .collect();
     let Some(consensus_bytes) = consensus_bytes else {
@@ -112,48 +125,90 @@ fn translated_conservation_line(
     metrics: &ColumnStatsCache,
     theme: &ThemeState,
 ) -> Line<'static> {
+    let calculating = || {
+        Line::from(
+            "Calculating conservation..."
+                .fg(theme.theme.text_dim)
+                .italic(),
+        )
+    };
     let Some(frame) = alignment.translation() else {
+        return calculating();
+    };
+    let spans = translated_window_spans(
+        &window.col_range,
+        alignment.view().column_count(),
+        frame,
+        |protein_col| {
+            let summary = metrics.translated_summary_at(frame, protein_col)?;
+            let spark = summary
+                .conservation
+                .filter(|value| value.is_finite())
+                .map_or(" ", conservation_to_spark);
+            Some([spark, spark, spark].map(Span::raw))
+        },
+    );
+    spans.map_or_else(calculating, |spans| {
+        Line::from(spans).set_style(theme.styles.accent_alt)
+    })
+}
+
+/// consensus codons in the current frame translated to amino acids, each spread over its codon
+fn consensus_translation_line(
+    alignment: &AlignmentModel,
+    window: &ViewportWindow,
+    metrics: &ColumnStatsCache,
+    theme: &ThemeState,
+) -> Line<'static> {
+    if alignment.translation().is_some() {
         return Line::from(
-            "Calculating conservation..."
+            "Consensus is already translated"
                 .fg(theme.theme.text_dim)
                 .italic(),
         );
-    };
+    }
+    let frame = alignment.translation_frame();
     let nucleotide_len = alignment.view().column_count();
-    let width = window.col_range.len();
-    let mut spans = vec![ratatui::text::Span::styled(" ", theme.styles.accent_alt); width];
-
     let Some(protein_range) = visible_protein_range(&window.col_range, frame, nucleotide_len)
     else {
-        return Line::from(spans);
+        return blank_line(window.col_range.len());
     };
 
-    for protein_col in protein_range {
-        let Some(summary) = metrics.translated_summary_at(frame, protein_col) else {
-            return Line::from(
-                "Calculating conservation..."
-                    .fg(theme.theme.text_dim)
-                    .italic(),
-            );
-        };
-        let spark = summary
-            .conservation
-            .filter(|value| value.is_finite())
-            .map_or(" ", conservation_to_spark);
-        let nuc_start = frame.offset() + protein_col * 3;
-
-        for absolute_col in nuc_start..=nuc_start + 2 {
-            let Some(window_offset) = absolute_col.checked_sub(window.col_range.start) else {
-                continue;
+    let table = alignment.genetic_code().table();
+    let mut amino_acids = Vec::with_capacity(protein_range.len());
+    for protein_col in protein_range.clone() {
+        let codon_start = frame.offset() + protein_col * 3;
+        let mut codon = [b'-'; 3];
+        for (base, col) in codon.iter_mut().zip(codon_start..codon_start + 3) {
+            *base = match metrics.raw_summary_at(col) {
+                Some(summary) => match summary.consensus.map(|byte| byte.to_ascii_uppercase()) {
+                    Some(b'U') => b'T',
+                    Some(byte) => byte,
+                    None => b'-',
+                },
+                // codons cut by the window edge may not have stats yet
+                None if window.col_range.contains(&col) => {
+                    return Line::from(
+                        "Calculating consensus...".fg(theme.theme.text_dim).italic(),
+                    );
+                }
+                None => b'N',
             };
-            if window_offset >= width {
-                continue;
-            }
-
-            spans[window_offset] = ratatui::text::Span::styled(spark, theme.styles.accent_alt);
         }
+        amino_acids.push(if codon.iter().all(|&base| matches!(base, b'-' | b'.')) {
+            b'-'
+        } else {
+            table.translate_codon(codon)
+        });
     }
-
+    let spans = format_translated_byte_range_spans(
+        TranslatedByteRange::new(protein_range.start, &amino_acids),
+        &window.col_range,
+        nucleotide_len,
+        frame,
+        &theme.theme.sequence,
+        None,
+    );
     Line::from(spans)
 }
 
@@ -174,6 +229,11 @@ fn consensus_alignment_lines(
     let no_diff_mode = RowRenderMode {
         alignment_type: alignment.base().active_type(),
         diff_against: None,
+        diff_ambiguity: DiffAmbiguity::Strict,
+        diff_options: DiffOptions::default(),
+        diff_columns: None,
+        qualities: None,
+        conservation: None,
     };
 
     let reference_line = alignment.rows().reference().map_or_else(
@@ -192,9 +252,14 @@ fn consensus_alignment_lines(
         .col_range
         .clone()
         .map(|rel_col| {
-            metrics
-                .raw_summary_at(rel_col)
-                .map(|summary| summary.consensus.unwrap_or(b' '))
+            metrics.raw_summary_at(rel_col).map(|summary| {
+                thresholded_consensus(
+                    summary,
+                    alignment.consensus_threshold,
+                    alignment.base().active_type(),
+                )
+                .unwrap_or(b' ')
+            })
         })
         .collect();
 
@@ -216,20 +281,33 @@ fn consensus_alignment_lines(
 
 fn render_consensus_alignment_pane(
     f: &mut Frame,
-    area: Rect,
+    layout: &AppLayout,
     alignment: &AlignmentModel,
     window: &ViewportWindow,
     metrics: &ColumnStatsCache,
-    theme: &ThemeState,
+    ui: &UiState,
 ) {
+    let theme = &ui.theme;
     let block = Block::bordered()
         .border_style(theme.styles.border)
         .style(theme.styles.base_block)
         .merge_borders(MergeStrategy::Exact);
-    let inner_area = block.inner(area);
+    let area = layout.consensus_alignment_pane;
+    let [_, inner_area] = split_position_gutter(block.inner(area), layout.position_gutter);
     f.render_widget(block, area);
 
-    let lines = consensus_alignment_lines(alignment, window, metrics, theme);
+    let mut lines = consensus_alignment_lines(alignment, window, metrics, theme);
+    if ui.consensus_translation {
+        lines.insert(
+            2,
+            consensus_translation_line(alignment, window, metrics, theme),
+        );
+    }
+    let cols_per_cell = ui.effective_density().cols_per_cell();
+    let lines: Vec<Line<'static>> = lines
+        .into_iter()
+        .map(|line| sample_cells(line, window.col_range.len(), cols_per_cell))
+        .collect();
     f.render_widget(
         Paragraph::new(lines).style(theme.styles.base_block),
         inner_area,
@@ -266,6 +344,7 @@ fn render_consensus_sequence_id_pane(
     area: Rect,
     alignment: &AlignmentModel,
     theme: &ThemeState,
+    show_translation: bool,
 ) {
     let block = Block::bordered()
         .border_style(theme.styles.border)
@@ -274,18 +353,29 @@ fn render_consensus_sequence_id_pane(
     let inner_area = block.inner(area);
     f.render_widget(block, area);
 
-    let lines = if shows_conservation_line(alignment) {
+    // stats over every row can disagree with the rows on screen, so say so
+    let consensus_label = match alignment.consensus_scope() {
+        ConsensusScope::Visible => "Consensus Sequence:",
+        ConsensusScope::All => "Consensus (all rows):",
+    };
+    let mut lines = if shows_conservation_line(alignment) {
         vec![
             Line::from("Reference Sequence:".set_style(theme.styles.accent)),
-            Line::from("Consensus Sequence:".set_style(theme.styles.accent)),
+            Line::from(consensus_label.set_style(theme.styles.accent)),
             Line::from("Conservation:".set_style(theme.styles.accent)),
         ]
     } else {
         vec![
             Line::from("Reference Sequence:".set_style(theme.styles.accent)),
-            Line::from("Consensus Sequence:".set_style(theme.styles.accent)),
+            Line::from(consensus_label.set_style(theme.styles.accent)),
         ]
     };
+    if show_translation {
+        lines.insert(
+            2,
+            Line::from("Translated Consensus:".set_style(theme.styles.accent)),
+        );
+    }
 
     f.render_widget(
         Paragraph::new(lines).style(theme.styles.base_block),
@@ -299,17 +389,16 @@ pub fn render_consensus_pane(
     alignment: &AlignmentModel,
     window: &ViewportWindow,
     metrics: &ColumnStatsCache,
-    theme: &ThemeState,
+    ui: &UiState,
 ) {
-    render_consensus_sequence_id_pane(f, layout.consensus_sequence_id_pane, alignment, theme);
-    render_consensus_alignment_pane(
+    render_consensus_sequence_id_pane(
         f,
-        layout.consensus_alignment_pane,
+        layout.consensus_sequence_id_pane,
         alignment,
-        window,
-        metrics,
-        theme,
+        &ui.theme,
+        ui.consensus_translation,
     );
+    render_consensus_alignment_pane(f, layout, alignment, window, metrics, ui);
 }
 
 #[cfg(test)]
@@ -322,6 +411,7 @@ mod tests {
         libmsa::RawSequence {
             id: id.to_string(),
             sequence: sequence.to_vec(),
+            quality: None,
         }
     }
 
@@ -353,11 +443,12 @@ mod tests {
             .map(|(position, &byte)| libmsa::ColumnSummary {
                 position,
                 consensus: Some(byte),
+                consensus_support: 1.0,
                 conservation,
                 gap_fraction: 0.0,
+                gap_only: false,
             })
             .collect();
-        let view = view;
         let generation = cache.generation;
         let chunk_idx = 0;
         let stored = cache.store(StatsJobResult {
@@ -365,6 +456,7 @@ mod tests {
             chunk_idx,
             view,
             summaries: Ok(summaries),
+            counts: None,
         });
         assert!(stored);
         cache
@@ -404,6 +496,41 @@ mod tests {
         assert_eq!(line_text(&lines[2]), "█████████");
     }
 
+    #[test]
+    fn translated_lines_stay_column_aligned_at_window_edges() {
+        let alignment = libmsa::Alignment::new(vec![
+            raw("ref", b"ATGAAATTTGGG"),
+            raw("row", b"ATGAAATTTGGG"),
+        ])
+        .expect("alignment should be valid");
+        let mut alignment =
+            AlignmentModel::new(alignment).expect("alignment model should be created");
+        alignment.set_reference(0).expect("reference should be set");
+        alignment
+            .set_translation(Some(libmsa::ReadingFrame::Frame1))
+            .expect("translation should succeed");
+
+        let window = ViewportWindow {
+            row_range: 0..alignment.view().row_count(),
+            col_range: 2..10,
+            name_range: 0..0,
+        };
+        let lines = consensus_alignment_lines(
+            &alignment,
+            &window,
+            &metrics_with(
+                StatsView::Translated(libmsa::ReadingFrame::Frame1),
+                b"MKFG",
+                Some(1.0),
+            ),
+            &ThemeState::default(),
+        );
+
+        assert_eq!(line_text(&lines[0]), "  K  F  ");
+        assert_eq!(line_text(&lines[1]), line_text(&lines[0]));
+        assert_eq!(line_text(&lines[2]), "████████");
+    }
+
     #[test]
     fn translated_mode_keeps_conservation_label() {
         let alignment =
@@ -442,4 +569,30 @@ mod tests {
         assert_eq!(line_text(&lines[0]), "ACGT");
         assert_eq!(line_text(&lines[1]), "ACGT");
     }
+
+    #[test]
+    fn consensus_translation_spreads_amino_acids_over_codons() {
+        let alignment =
+            libmsa::Alignment::new(vec![raw("a", b"AATGAAA---"), raw("b", b"AATGAAA---")])
+                .expect("alignment should be valid");
+        let mut alignment =
+            AlignmentModel::new(alignment).expect("alignment model should be created");
+        alignment
+            .set_translation_frame(libmsa::ReadingFrame::Frame2)
+            .expect("frame should be set");
+
+        let window = ViewportWindow {
+            row_range: 0..alignment.view().row_count(),
+            col_range: 0..alignment.view().column_count(),
+            name_range: 0..0,
+        };
+        let line = consensus_translation_line(
+            &alignment,
+            &window,
+            &metrics_with(StatsView::Raw, b"AATGAAA---", Some(1.0)),
+            &ThemeState::default(),
+        );
+
+        assert_eq!(line_text(&line), "  M  K  - ");
+    }
 }
@@ -1,9 +1,20 @@
+use std::str::FromStr;
+
+use anyhow::Context;
+
 use crate::{
-    core::model::AlignmentModel,
+    config::settings::StatusBarSettings,
+    core::{
+        liftover::{ResidueIndex, SequencePosition},
+        memory::format_bytes,
+        model::{AlignmentModel, DiffAmbiguity, DiffMode},
+        partition,
+        stats_cache::ColumnStatsCache,
+    },
     ui::{
         selection::selection_row_bounds,
-        ui_state::{LoadingState, UiState},
-        utils::truncate_label,
+        ui_state::{EditMode, LoadingState, UiState},
+        utils::{format_thousands, truncate_label},
     },
 };
 use ratatui::Frame;
@@ -14,6 +25,12 @@ use ratatui::widgets::Paragraph;
 
 /// maximum displayed character count for a selected sequence name in the status bar before truncation
 const STATUS_BAR_SELECTED_NAME_MAX_CHARS: usize = 25;
+/// built-in layout of the top status bar
+pub const DEFAULT_TOP_STATUS_TEMPLATE: &str =
+    "{file} | {status} | {mode} | {sequences} | {length} | {column} | {rows} | {partition}";
+/// built-in layout of the bottom status bar
+pub const DEFAULT_BOTTOM_STATUS_TEMPLATE: &str =
+    "{reference} | {tracked} | {filter} | {hidden} | {selection} | {crosshair} | {agreement}";
 
 fn format_gap_percent(max_gap_fraction: f32) -> String {
     let mut text = format!("{:.2}", max_gap_fraction * 100.0);
@@ -26,64 +43,7 @@ fn format_gap_percent(max_gap_fraction: f32) -> String {
     text
 }
 
-fn build_bottom_status_bar(alignment: Option<&AlignmentModel>, ui: &UiState) -> Vec<Span<'static>> {
-    let theme = &ui.theme.styles;
-    let mut parts = Vec::new();
-
-    if let Some(alignment) = alignment.filter(|alignment| alignment.filter().is_active()) {
-        let visible_rows = alignment.view().row_count();
-        let mut filter_text = String::from("Filters:");
-        let mut counts = format!(" ({visible_rows} rows)");
-        if let Some(pattern) = alignment.filter().pattern() {
-            filter_text.push_str(&format!(" [rows: {pattern}]"));
-        }
-        if let Some(max_gap_fraction) = alignment.filter().max_gap_fraction() {
-            filter_text.push_str(&format!(
-                " [gaps: <= {}%]",
-                format_gap_percent(max_gap_fraction)
-            ));
-            let visible_cols = alignment.view().column_count();
-            counts.push_str(&format!(" ({visible_cols} cols)"));
-        }
-        parts.push(format!("{filter_text}{counts}").set_style(theme.warning));
-    }
-
-    // optional selection info building
-    if let Some(selection) = ui.selection {
-        let (row_min, row_max) = selection_row_bounds(selection);
-        let selected_sequence_count = row_max - row_min + 1;
-        let col_start = selection.column.min(selection.end_column) + 1;
-        let col_end = selection.column.max(selection.end_column) + 1;
-
-        if !parts.is_empty() {
-            parts.push(Span::raw(" | "));
-        }
-
-        if selected_sequence_count == 1 && col_start == col_end {
-            let sequence_name = if let Some(alignment) = alignment {
-                if let Some(sequence) = alignment.base().project_absolute_row(selection.sequence_id)
-                {
-                    truncate_label(sequence.id(), STATUS_BAR_SELECTED_NAME_MAX_CHARS)
-                } else {
-                    "Unknown".to_string()
-                }
-            } else {
-                "Unknown".to_string()
-            };
-            parts.push(format!("Selected: {sequence_name} @ {col_start}").set_style(theme.text));
-        } else {
-            parts.push(
-                format!("{selected_sequence_count} sequence(s) selected @ {col_start}-{col_end}")
-                    .set_style(theme.text),
-            );
-        }
-    }
-
-    parts
-}
-
-fn build_top_status_bar(alignment: Option<&AlignmentModel>, ui: &UiState) -> Vec<Span<'static>> {
-    let theme = &ui.theme.styles;
+fn file_segment(ui: &UiState) -> Span<'static> {
     let file_name = ui
         .meta
         .input_path
@@ -96,21 +56,23 @@ fn build_top_status_bar(alignment: Option<&AlignmentModel>, ui: &UiState) -> Vec
                 .unwrap_or(input)
         })
         .unwrap_or("Unknown");
+    format!("File: {file_name}").set_style(ui.theme.styles.text_dim)
+}
 
-    let loading_text = ui.meta.loading_state.to_string();
+fn loading_segment(ui: &UiState) -> Span<'static> {
+    let theme = &ui.theme.styles;
+    if let Some((label, percent)) = ui.meta.export_progress {
+        return format!("{label} {percent}%").set_style(theme.accent);
+    }
     let loading_style = match &ui.meta.loading_state {
         LoadingState::Idle | LoadingState::Loading => theme.text_dim,
         LoadingState::Loaded => theme.success,
         LoadingState::Failed(_) => theme.error,
     };
-    let loading_status = loading_text.set_style(loading_style);
-
-    let alignment_count = alignment
-        .map(|alignment| alignment.view().row_count())
-        .unwrap_or(0);
-    let alignment_length = alignment
-        .map(|alignment| alignment.base().column_count())
-        .unwrap_or(0);
+    ui.meta.loading_state.to_string().set_style(loading_style)
+}
+
+fn position_segment(alignment: Option<&AlignmentModel>, ui: &UiState) -> Span<'static> {
     let position_range = alignment.map_or_else(
         || "Positions: 0-0".to_string(),
         |alignment| {
@@ -123,23 +85,519 @@ fn build_top_status_bar(alignment: Option<&AlignmentModel>, ui: &UiState) -> Vec
                     .checked_sub(1)
                     .and_then(|end| alignment.view().absolute_column_id(end)),
             ) {
-                (Some(start), Some(end)) => format!("Positions: {}-{}", start + 1, end + 1),
+                (Some(start), Some(end)) => format!(
+                    "Positions: {}-{}",
+                    format_thousands(start + 1),
+                    format_thousands(end + 1)
+                ),
                 _ => "Positions: 0-0".to_string(),
             }
         },
     );
+    position_range.set_style(ui.theme.styles.text)
+}
+
+/// `col 12,345 / 29,903 (41%)` for the crosshair column, or the first visible column
+fn column_segment(alignment: &AlignmentModel, ui: &UiState) -> Option<Span<'static>> {
+    let column = ui
+        .crosshair
+        .map_or(ui.viewport.window().col_range.start, |crosshair| {
+            crosshair.column
+        });
+    let absolute_col = alignment.view().absolute_column_id(column)?;
+    let total = alignment.base().column_count();
+    let percent = (absolute_col + 1) * 100 / total.max(1);
+    Some(
+        format!(
+            "col {} / {} ({percent}%)",
+            format_thousands(absolute_col + 1),
+            format_thousands(total)
+        )
+        .set_style(ui.theme.styles.text),
+    )
+}
+
+/// `rows 1-40 / 1,234` for the visible rows of the view
+fn rows_segment(alignment: &AlignmentModel, ui: &UiState) -> Option<Span<'static>> {
+    let rows = ui.viewport.window().row_range;
+    if rows.is_empty() {
+        return None;
+    }
+    Some(
+        format!(
+            "rows {}-{} / {}",
+            format_thousands(rows.start + 1),
+            format_thousands(rows.end),
+            format_thousands(alignment.view().row_count())
+        )
+        .set_style(ui.theme.styles.text),
+    )
+}
+
+fn reference_segment(alignment: &AlignmentModel, ui: &UiState) -> Option<Span<'static>> {
+    let sequence = alignment
+        .base()
+        .project_absolute_row(alignment.rows().reference()?)?;
+    Some(
+        format!(
+            "Ref: {}",
+            truncate_label(sequence.id(), STATUS_BAR_SELECTED_NAME_MAX_CHARS)
+        )
+        .set_style(ui.theme.styles.accent),
+    )
+}
+
+fn partition_segment(alignment: &AlignmentModel, ui: &UiState) -> Option<Span<'static>> {
+    let column = ui
+        .crosshair
+        .map_or(ui.viewport.window().col_range.start, |crosshair| {
+            crosshair.column
+        });
+    let absolute_col = alignment.view().absolute_column_id(column)?;
+    let index = partition::partition_at(&alignment.partitions, absolute_col)?;
+    Some(
+        format!(
+            "Partition: {} ({}/{})",
+            alignment.partitions[index].name,
+            index + 1,
+            alignment.partitions.len()
+        )
+        .set_style(ui.theme.styles.accent),
+    )
+}
+
+fn filter_segment(alignment: &AlignmentModel, ui: &UiState) -> Option<Span<'static>> {
+    if !alignment.filter().is_active() {
+        return None;
+    }
+
+    let visible_rows = format_thousands(alignment.view().row_count());
+    let mut filter_text = String::from("Filters:");
+    let mut counts = format!(" ({visible_rows} rows)");
+    if let Some(pattern) = alignment.filter().pattern() {
+        filter_text.push_str(&format!(" [rows: {pattern}]"));
+    }
+    if let Some(max_gap_fraction) = alignment.filter().max_gap_fraction() {
+        filter_text.push_str(&format!(
+            " [gaps: <= {}%]",
+            format_gap_percent(max_gap_fraction)
+        ));
+    }
+    let masked = alignment.filter().masked_columns().len();
+    if masked > 0 {
+        filter_text.push_str(&format!(" [mask: {masked} cols]"));
+    }
+    if alignment.filter().max_gap_fraction().is_some() || masked > 0 {
+        let visible_cols = format_thousands(alignment.view().column_count());
+        counts.push_str(&format!(" ({visible_cols} cols)"));
+    }
+    Some(format!("{filter_text}{counts}").set_style(ui.theme.styles.warning))
+}
+
+fn selection_segment(alignment: Option<&AlignmentModel>, ui: &UiState) -> Option<Span<'static>> {
+    let selection = ui.selection?;
+    let (row_min, row_max) = selection_row_bounds(selection);
+    let selected_sequence_count = row_max - row_min + 1;
+    let col_start = selection.column.min(selection.end_column) + 1;
+    let col_end = selection.column.max(selection.end_column) + 1;
+
+    if selected_sequence_count == 1 && col_start == col_end {
+        let sequence_name = alignment
+            .and_then(|alignment| alignment.base().project_absolute_row(selection.sequence_id))
+            .map_or_else(
+                || "Unknown".to_string(),
+                |sequence| truncate_label(sequence.id(), STATUS_BAR_SELECTED_NAME_MAX_CHARS),
+            );
+        Some(format!("Selected: {sequence_name} @ {col_start}").set_style(ui.theme.styles.text))
+    } else {
+        Some(
+            format!("{selected_sequence_count} sequence(s) selected @ {col_start}-{col_end}")
+                .set_style(ui.theme.styles.text),
+        )
+    }
+}
+
+/// sequence chosen with `track-sequence`, whose ungapped position at the current column is
+/// shown by the `tracked` status segment
+#[derive(Debug, Clone)]
+pub struct TrackedSequence {
+    id: String,
+    index: ResidueIndex,
+}
+
+impl TrackedSequence {
+    pub fn from_model(alignment: &mut AlignmentModel, abs_row: usize) -> Option<Self> {
+        let index = alignment.residue_index(abs_row)?.clone();
+        let sequence = alignment.base().project_absolute_row(abs_row)?;
+        Some(Self {
+            id: sequence.id().to_string(),
+            index,
+        })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+/// `Track: isolate-7 @ 1,234` for the crosshair column, or the first visible column
+fn tracked_segment(alignment: &AlignmentModel, ui: &UiState) -> Option<Span<'static>> {
+    let tracked = ui.tracked.as_ref()?;
+    let column = ui
+        .crosshair
+        .map_or(ui.viewport.window().col_range.start, |crosshair| {
+            crosshair.column
+        });
+    let absolute_col = alignment.view().absolute_column_id(column)?;
+    let position = match tracked.index.position_at(absolute_col) {
+        SequencePosition::Residue(position) => format_thousands(position),
+        SequencePosition::Gap { after: 0 } => "gap before 1".to_string(),
+        SequencePosition::Gap { after } => format!("gap after {}", format_thousands(after)),
+    };
+    Some(
+        format!(
+            "Track: {} @ {position}",
+            truncate_label(tracked.id(), STATUS_BAR_SELECTED_NAME_MAX_CHARS)
+        )
+        .set_style(ui.theme.styles.accent),
+    )
+}
+
+fn crosshair_segment(alignment: &AlignmentModel, ui: &UiState) -> Option<Span<'static>> {
+    let crosshair = ui.crosshair?;
+    let view = alignment.view();
+    let sequence = view.sequence(crosshair.row)?;
+    let absolute_col = view.absolute_column_id(crosshair.column)?;
+    let sequence_name = truncate_label(sequence.id(), STATUS_BAR_SELECTED_NAME_MAX_CHARS);
+    Some(
+        format!(
+            "Crosshair: {sequence_name} (row {}) @ {}",
+            format_thousands(sequence.absolute_row_id() + 1),
+            format_thousands(absolute_col + 1)
+        )
+        .set_style(ui.theme.styles.accent),
+    )
+}
+
+/// `Agree: 87.5% consensus` for the crosshair column, read from the cached column stats. in
+/// reference diff mode the share of rows carrying the reference byte is shown instead.
+fn agreement_segment(
+    alignment: &AlignmentModel,
+    ui: &UiState,
+    stats: &ColumnStatsCache,
+) -> Option<Span<'static>> {
+    let column = ui.crosshair?.column;
+    let reference_byte = (alignment.diff_mode == DiffMode::Reference)
+        .then(|| alignment.rows().reference())
+        .flatten()
+        .and_then(|absolute_row| alignment.view().project_absolute_row(absolute_row))
+        .and_then(|reference| reference.byte_at(column));
+    let (fraction, against) = match reference_byte {
+        Some(byte) => {
+            let counts = stats.raw_counts_at(column)?;
+            let fraction = counts.count(byte) as f32 / counts.total().max(1) as f32;
+            (fraction, "reference")
+        }
+        None => {
+            let summary = stats.raw_summary_at(column)?;
+            summary.consensus?;
+            (summary.consensus_support, "consensus")
+        }
+    };
+    Some(format!("Agree: {:.1}% {against}", fraction * 100.0).set_style(ui.theme.styles.text))
+}
+
+fn segment_span(
+    segment: StatusSegment,
+    alignment: Option<&AlignmentModel>,
+    ui: &UiState,
+    stats: &ColumnStatsCache,
+) -> Option<Span<'static>> {
+    let theme = &ui.theme.styles;
+    match segment {
+        StatusSegment::File => Some(file_segment(ui)),
+        StatusSegment::Status => Some(loading_segment(ui)),
+        StatusSegment::Sequences => {
+            let alignment_count = alignment.map_or(0, |alignment| alignment.view().row_count());
+            Some(format!("{} alignments", format_thousands(alignment_count)).set_style(theme.text))
+        }
+        StatusSegment::Length => {
+            let alignment_length = alignment.map_or(0, |alignment| alignment.base().column_count());
+            Some(format!("Length: {}", format_thousands(alignment_length)).set_style(theme.text))
+        }
+        StatusSegment::Position => Some(position_segment(alignment, ui)),
+        StatusSegment::Column => alignment.and_then(|alignment| column_segment(alignment, ui)),
+        StatusSegment::Rows => alignment.and_then(|alignment| rows_segment(alignment, ui)),
+        StatusSegment::Reference => {
+            alignment.and_then(|alignment| reference_segment(alignment, ui))
+        }
+        StatusSegment::Tracked => alignment.and_then(|alignment| tracked_segment(alignment, ui)),
+        StatusSegment::Partition => {
+            alignment.and_then(|alignment| partition_segment(alignment, ui))
+        }
+        StatusSegment::Filter => alignment.and_then(|alignment| filter_segment(alignment, ui)),
+        StatusSegment::Hidden => alignment
+            .map(|alignment| alignment.rows().hidden().len())
+            .filter(|&count| count > 0)
+            .map(|count| format!("{} hidden", format_thousands(count)).set_style(theme.warning)),
+        StatusSegment::Selection => selection_segment(alignment, ui),
+        StatusSegment::Crosshair => {
+            alignment.and_then(|alignment| crosshair_segment(alignment, ui))
+        }
+        StatusSegment::Agreement => {
+            alignment.and_then(|alignment| agreement_segment(alignment, ui, stats))
+        }
+        StatusSegment::Diff => alignment
+            .filter(|alignment| alignment.diff_mode != DiffMode::Off)
+            .map(|alignment| {
+                let ambiguity = match alignment.diff_ambiguity {
+                    DiffAmbiguity::Strict => "",
+                    DiffAmbiguity::Iupac => " (IUPAC)",
+                };
+                format!("Diff: {}{ambiguity}", alignment.diff_mode).set_style(theme.text)
+            }),
+        StatusSegment::Theme => Some(format!("Theme: {}", ui.theme.id).set_style(theme.text_dim)),
+        StatusSegment::Layout => {
+            Some(format!("Layout: {}", ui.layout_mode).set_style(theme.text_dim))
+        }
+        StatusSegment::Mode => Some(match (ui.meta.edit_mode, ui.meta.unsaved_edits) {
+            (EditMode::ReadOnly, _) => "Read-only".set_style(theme.text_dim),
+            (EditMode::Edit, false) => "Edit".set_style(theme.accent),
+            (EditMode::Edit, true) => "Edit (unsaved)".set_style(theme.warning),
+        }),
+        StatusSegment::Memory => ui
+            .meta
+            .memory_usage
+            .map(|bytes| format!("Mem: {}", format_bytes(bytes)).set_style(theme.text_dim)),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusSegment {
+    File,
+    Status,
+    Sequences,
+    Length,
+    Position,
+    Column,
+    Rows,
+    Reference,
+    Tracked,
+    Partition,
+    Filter,
+    Hidden,
+    Selection,
+    Crosshair,
+    Agreement,
+    Diff,
+    Theme,
+    Layout,
+    Mode,
+    Memory,
+}
+
+impl StatusSegment {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::File => "file",
+            Self::Status => "status",
+            Self::Sequences => "sequences",
+            Self::Length => "length",
+            Self::Position => "position",
+            Self::Column => "column",
+            Self::Rows => "rows",
+            Self::Reference => "reference",
+            Self::Tracked => "tracked",
+            Self::Partition => "partition",
+            Self::Filter => "filter",
+            Self::Hidden => "hidden",
+            Self::Selection => "selection",
+            Self::Crosshair => "crosshair",
+            Self::Agreement => "agreement",
+            Self::Diff => "diff",
+            Self::Theme => "theme",
+            Self::Layout => "layout",
+            Self::Mode => "mode",
+            Self::Memory => "memory",
+        }
+    }
+
+    pub const fn all() -> [Self; 20] {
+        [
+            Self::File,
+            Self::Status,
+            Self::Sequences,
+            Self::Length,
+            Self::Position,
+            Self::Column,
+            Self::Rows,
+            Self::Reference,
+            Self::Tracked,
+            Self::Partition,
+            Self::Filter,
+            Self::Hidden,
+            Self::Selection,
+            Self::Crosshair,
+            Self::Agreement,
+            Self::Diff,
+            Self::Theme,
+            Self::Layout,
+            Self::Mode,
+            Self::Memory,
+        ]
+    }
+}
+
+impl FromStr for StatusSegment {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|segment| segment.name() == value)
+            .ok_or_else(|| anyhow::format_err!("unknown status bar segment: {value}"))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TemplatePart {
+    Text(String),
+    Segment(StatusSegment),
+}
+
+/// a status bar layout such as `{file} | {position}`. segments are written in braces and
+/// any other text is kept as-is. text between two segments is treated as a separator and
+/// is dropped when either neighbour has nothing to show.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusTemplate {
+    parts: Vec<TemplatePart>,
+}
+
+impl FromStr for StatusTemplate {
+    type Err = anyhow::Error;
+
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        let mut parts = Vec::new();
+        let mut rest = template;
+        while let Some(open) = rest.find('{') {
+            if open > 0 {
+                parts.push(TemplatePart::Text(rest[..open].to_string()));
+            }
+            let close = rest[open..]
+                .find('}')
+                .ok_or_else(|| anyhow::format_err!("unclosed segment in status bar template"))?;
+            let name = rest[open + 1..open + close].trim();
+            parts.push(TemplatePart::Segment(name.parse()?));
+            rest = &rest[open + close + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(TemplatePart::Text(rest.to_string()));
+        }
 
-    vec![
-        format!("File: {file_name}").set_style(theme.text_dim),
-        Span::raw(" | "),
-        loading_status,
-        Span::raw(" | "),
-        format!("{alignment_count} alignments").set_style(theme.text),
-        Span::raw(" | "),
-        format!("Length: {alignment_length}").set_style(theme.text),
-        Span::raw(" | "),
-        position_range.set_style(theme.text),
-    ]
+        Ok(Self { parts })
+    }
+}
+
+impl StatusTemplate {
+    pub fn render(
+        &self,
+        alignment: Option<&AlignmentModel>,
+        ui: &UiState,
+        stats: &ColumnStatsCache,
+    ) -> Vec<Span<'static>> {
+        let first_segment = self
+            .parts
+            .iter()
+            .position(|part| matches!(part, TemplatePart::Segment(_)));
+        let mut spans = Vec::new();
+        let mut separator: Option<&str> = None;
+        let mut emitted_segment = false;
+
+        for (index, part) in self.parts.iter().enumerate() {
+            match part {
+                TemplatePart::Text(text) if first_segment.is_none_or(|first| index < first) => {
+                    spans.push(Span::raw(text.clone()));
+                }
+                TemplatePart::Text(text) => separator = Some(text),
+                TemplatePart::Segment(segment) => {
+                    let Some(span) = segment_span(*segment, alignment, ui, stats) else {
+                        continue;
+                    };
+                    if let Some(text) = separator.take()
+                        && emitted_segment
+                    {
+                        spans.push(Span::raw(text.to_string()));
+                    }
+                    spans.push(span);
+                    emitted_segment = true;
+                }
+            }
+        }
+        // text after the last segment is a suffix rather than a separator
+        if let Some(TemplatePart::Text(text)) = self.parts.last()
+            && self.parts.len() > 1
+            && emitted_segment
+        {
+            spans.push(Span::raw(text.clone()));
+        }
+
+        spans
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusBarTemplates {
+    pub top: StatusTemplate,
+    pub bottom: StatusTemplate,
+}
+
+impl Default for StatusBarTemplates {
+    fn default() -> Self {
+        Self {
+            top: DEFAULT_TOP_STATUS_TEMPLATE
+                .parse()
+                .expect("default top status template should parse"),
+            bottom: DEFAULT_BOTTOM_STATUS_TEMPLATE
+                .parse()
+                .expect("default bottom status template should parse"),
+        }
+    }
+}
+
+impl StatusBarTemplates {
+    pub fn from_settings(settings: &StatusBarSettings) -> anyhow::Result<Self> {
+        let parse = |template: Option<&str>, default: &str| {
+            template
+                .unwrap_or(default)
+                .parse::<StatusTemplate>()
+                .with_context(|| {
+                    format!(
+                        "invalid status bar template: {}",
+                        template.unwrap_or(default)
+                    )
+                })
+        };
+        Ok(Self {
+            top: parse(settings.top.as_deref(), DEFAULT_TOP_STATUS_TEMPLATE)?,
+            bottom: parse(settings.bottom.as_deref(), DEFAULT_BOTTOM_STATUS_TEMPLATE)?,
+        })
+    }
+}
+
+fn build_bottom_status_bar(
+    alignment: Option<&AlignmentModel>,
+    ui: &UiState,
+    stats: &ColumnStatsCache,
+) -> Vec<Span<'static>> {
+    ui.status_bar.bottom.render(alignment, ui, stats)
+}
+
+fn build_top_status_bar(
+    alignment: Option<&AlignmentModel>,
+    ui: &UiState,
+    stats: &ColumnStatsCache,
+) -> Vec<Span<'static>> {
+    ui.status_bar.top.render(alignment, ui, stats)
 }
 
 pub fn render_frame(
@@ -148,10 +606,11 @@ pub fn render_frame(
     bottom_status_area: Rect,
     alignment: Option<&AlignmentModel>,
     ui: &UiState,
+    stats: &ColumnStatsCache,
 ) {
     let theme = &ui.theme.styles;
-    let top_status_bar = build_top_status_bar(alignment, ui);
-    let bottom_status_bar = build_bottom_status_bar(alignment, ui);
+    let top_status_bar = build_top_status_bar(alignment, ui, stats);
+    let bottom_status_bar = build_bottom_status_bar(alignment, ui, stats);
 
     if top_status_area.height > 0 {
         let top_line = Line::from(top_status_bar).right_aligned();
@@ -175,11 +634,16 @@ mod tests {
     use super::*;
     use crate::cli::StartupState;
     use crate::core::model::AlignmentModel;
+    use crate::core::model::StatsView;
+    use crate::core::partition::Partition;
+    use crate::core::stats_cache::StatsJobResult;
+    use crate::ui::ui_state::Crosshair;
 
     fn raw(id: &str, sequence: &[u8]) -> libmsa::RawSequence {
         libmsa::RawSequence {
             id: id.to_string(),
             sequence: sequence.to_vec(),
+            quality: None,
         }
     }
 
@@ -188,7 +652,11 @@ mod tests {
     }
 
     fn top_status_text(alignment: Option<&AlignmentModel>, ui: &UiState) -> String {
-        status_text(&build_top_status_bar(alignment, ui))
+        status_text(&build_top_status_bar(
+            alignment,
+            ui,
+            &ColumnStatsCache::default(),
+        ))
     }
 
     fn ui_state() -> UiState {
@@ -212,7 +680,11 @@ mod tests {
         let ui = ui_state();
 
         assert_eq!(
-            status_text(&build_bottom_status_bar(Some(&alignment), &ui)),
+            status_text(&build_bottom_status_bar(
+                Some(&alignment),
+                &ui,
+                &ColumnStatsCache::default()
+            )),
             "Filters: [rows: alpha|beta] (2 rows)"
         );
     }
@@ -235,11 +707,33 @@ mod tests {
         let ui = ui_state();
 
         assert_eq!(
-            status_text(&build_bottom_status_bar(Some(&alignment), &ui)),
+            status_text(&build_bottom_status_bar(
+                Some(&alignment),
+                &ui,
+                &ColumnStatsCache::default()
+            )),
             "Filters: [rows: alpha|beta] [gaps: <= 0%] (2 rows) (2 cols)"
         );
     }
 
+    #[test]
+    fn bottom_status_bar_shows_crosshair_coordinates() {
+        let alignment = libmsa::Alignment::new(vec![raw("alpha", b"ACGT"), raw("beta", b"ACGT")])
+            .expect("alignment should be valid");
+        let alignment = AlignmentModel::new(alignment).expect("alignment model should build");
+        let mut ui = ui_state();
+        ui.crosshair = Some(Crosshair { row: 1, column: 2 });
+
+        assert_eq!(
+            status_text(&build_bottom_status_bar(
+                Some(&alignment),
+                &ui,
+                &ColumnStatsCache::default()
+            )),
+            "Crosshair: beta (row 2) @ 3"
+        );
+    }
+
     #[test]
     fn top_status_bar_shows_alignment_length() {
         let alignment = libmsa::Alignment::new(vec![
@@ -259,7 +753,180 @@ mod tests {
 
         assert_eq!(
             top_status_text(Some(&alignment), &ui),
-            "File: Unknown | Status: Loaded | 3 alignments | Length: 4 | Positions: 1-4"
+            "File: Unknown | Status: Loaded | Read-only | 3 alignments | Length: 4 | col 1 / 4 (25%) | rows 1-3 / 3"
+        );
+    }
+
+    #[test]
+    fn status_bar_readouts_use_thousands_separators() {
+        assert_eq!(format_thousands(7), "7");
+        assert_eq!(format_thousands(29_903), "29,903");
+        assert_eq!(format_thousands(1_234_567), "1,234,567");
+
+        let alignment = libmsa::Alignment::new(vec![
+            raw("alpha", &[b'A'; 12_000]),
+            raw("beta", &[b'C'; 12_000]),
+        ])
+        .expect("alignment should be valid");
+        let mut alignment = AlignmentModel::new(alignment).expect("alignment model should build");
+        alignment.set_reference(1).expect("reference should be set");
+        let mut ui = ui_state();
+        ui.status_bar.bottom = "{reference} | {column}"
+            .parse()
+            .expect("template should parse");
+        ui.crosshair = Some(Crosshair {
+            row: 0,
+            column: 4_999,
+        });
+
+        assert_eq!(
+            status_text(&build_bottom_status_bar(
+                Some(&alignment),
+                &ui,
+                &ColumnStatsCache::default()
+            )),
+            "Ref: beta | col 5,000 / 12,000 (41%)"
+        );
+    }
+
+    #[test]
+    fn agreement_segment_reads_the_crosshair_column_from_cached_counts() {
+        let alignment = libmsa::Alignment::new(vec![
+            raw("alpha", b"AC"),
+            raw("beta", b"AC"),
+            raw("gamma", b"AG"),
+            raw("delta", b"TC"),
+        ])
+        .expect("alignment should be valid");
+        let mut alignment = AlignmentModel::new(alignment).expect("alignment model should build");
+        let stats_view = alignment.stats_view();
+        let mut stats = ColumnStatsCache::default();
+        stats.init(2);
+        stats.store(StatsJobResult {
+            generation: stats.generation,
+            chunk_idx: 0,
+            view: StatsView::Raw,
+            summaries: stats_view
+                .column_summaries_range(0..2, alignment.consensus_method)
+                .map_err(|error| error.to_string()),
+            counts: stats_view.column_counts_range(0..2).ok(),
+        });
+        let mut ui = ui_state();
+        ui.status_bar.bottom = "{agreement}".parse().expect("template should parse");
+        let text = |alignment: &AlignmentModel, ui: &UiState, stats: &ColumnStatsCache| -> String {
+            status_text(&build_bottom_status_bar(Some(alignment), ui, stats))
+        };
+
+        assert_eq!(text(&alignment, &ui, &stats), "");
+        ui.crosshair = Some(Crosshair { row: 0, column: 0 });
+        assert_eq!(text(&alignment, &ui, &stats), "Agree: 75.0% consensus");
+        assert_eq!(text(&alignment, &ui, &ColumnStatsCache::default()), "");
+
+        alignment.set_reference(3).expect("reference should be set");
+        alignment.diff_mode = DiffMode::Reference;
+        ui.crosshair = Some(Crosshair { row: 0, column: 1 });
+        assert_eq!(text(&alignment, &ui, &stats), "Agree: 75.0% reference");
+    }
+
+    #[test]
+    fn tracked_segment_shows_the_ungapped_position_at_the_current_column() {
+        let alignment = libmsa::Alignment::new(vec![raw("alpha", b"ACGT"), raw("beta", b"A--T")])
+            .expect("alignment should be valid");
+        let mut alignment = AlignmentModel::new(alignment).expect("alignment model should build");
+        let mut ui = ui_state();
+        ui.status_bar.bottom = "{tracked}".parse().expect("template should parse");
+        let tracked = TrackedSequence::from_model(&mut alignment, 1);
+        let stats = ColumnStatsCache::default();
+        let text =
+            |ui: &UiState| status_text(&build_bottom_status_bar(Some(&alignment), ui, &stats));
+
+        assert_eq!(text(&ui), "");
+        ui.tracked = tracked;
+        assert_eq!(text(&ui), "Track: beta @ 1");
+        ui.crosshair = Some(Crosshair { row: 0, column: 2 });
+        assert_eq!(text(&ui), "Track: beta @ gap after 1");
+        ui.crosshair = Some(Crosshair { row: 0, column: 3 });
+        assert_eq!(text(&ui), "Track: beta @ 2");
+    }
+
+    #[test]
+    fn top_status_bar_names_the_current_partition() {
+        let alignment = libmsa::Alignment::new(vec![raw("alpha", b"ACGTAC")])
+            .expect("alignment should be valid");
+        let mut alignment = AlignmentModel::new(alignment).expect("alignment model should build");
+        alignment
+            .set_partitions(vec![
+                Partition {
+                    name: "gene1".to_string(),
+                    columns: 0..2,
+                },
+                Partition {
+                    name: "gene2".to_string(),
+                    columns: 2..6,
+                },
+            ])
+            .expect("partitions should fit");
+        let mut ui = ui_state();
+        ui.status_bar.top = "{partition}".parse().expect("template should parse");
+        ui.crosshair = Some(Crosshair { row: 0, column: 3 });
+
+        assert_eq!(
+            top_status_text(Some(&alignment), &ui),
+            "Partition: gene2 (2/2)"
+        );
+    }
+
+    #[test]
+    fn template_orders_segments_and_drops_empty_separators() {
+        let alignment = libmsa::Alignment::new(vec![raw("alpha", b"ACGT"), raw("beta", b"ACGT")])
+            .expect("alignment should be valid");
+        let mut alignment = AlignmentModel::new(alignment).expect("alignment model should build");
+        alignment.diff_mode = DiffMode::Reference;
+        let mut ui = ui_state();
+        ui.status_bar.bottom = "[{hidden} | {diff} | {theme}]"
+            .parse()
+            .expect("template should parse");
+
+        assert_eq!(
+            status_text(&build_bottom_status_bar(
+                Some(&alignment),
+                &ui,
+                &ColumnStatsCache::default()
+            )),
+            "[Diff: reference | Theme: everforest-dark]"
+        );
+
+        alignment.diff_ambiguity = DiffAmbiguity::Iupac;
+        assert_eq!(
+            status_text(&build_bottom_status_bar(
+                Some(&alignment),
+                &ui,
+                &ColumnStatsCache::default()
+            )),
+            "[Diff: reference (IUPAC) | Theme: everforest-dark]"
+        );
+    }
+
+    #[test]
+    fn template_rejects_unknown_and_unclosed_segments() {
+        assert!("{file} | {colour}".parse::<StatusTemplate>().is_err());
+        assert!("{file".parse::<StatusTemplate>().is_err());
+    }
+
+    #[test]
+    fn status_bar_settings_fall_back_to_defaults() {
+        let settings = StatusBarSettings {
+            top: None,
+            bottom: Some("{memory}".to_string()),
+        };
+
+        let templates =
+            StatusBarTemplates::from_settings(&settings).expect("templates should parse");
+
+        assert_eq!(templates.top, StatusBarTemplates::default().top);
+        assert_eq!(
+            templates.bottom,
+            "{memory}".parse().expect("template should parse")
         );
     }
 }
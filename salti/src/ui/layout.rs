@@ -1,14 +1,110 @@
-use ratatui::layout::{Rect, Spacing};
-use ratatui::macros::{horizontal, vertical};
+use std::{fmt, str::FromStr};
+
+use ratatui::layout::{Constraint, Layout, Rect, Spacing};
+use ratatui::macros::vertical;
 
 /// fixed height (rows) for the bottom consensus pane.
 /// the remaining vertical space is used for the alignment pane.
 const CONSENSUS_PANE_HEIGHT_ROWS: u16 = 5;
+/// fixed height (rows) for the ungapped sequence pane below the consensus pane, when shown.
+const UNGAPPED_PANE_HEIGHT_ROWS: u16 = 5;
 /// fixed height (rows) for the alignment ruler above sequence rows.
 pub const RULER_HEIGHT_ROWS: u16 = 2;
 /// width percentage for the left sequence ID pane (used in alignment and consensus panes).
 /// the remaining horizontal space is used for sequence content.
 const SEQUENCE_ID_PANE_WIDTH_PERCENT: u16 = 20;
+/// narrowest sequence ID pane (columns, including borders) a fixed width can shrink to.
+pub const MIN_SEQUENCE_ID_PANE_WIDTH: u16 = 8;
+/// columns always left to the alignment pane when the sequence ID pane has a fixed width.
+const MIN_ALIGNMENT_PANE_WIDTH: u16 = 10;
+
+/// width of the sequence ID pane: a share of the terminal, or a fixed number of columns
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IdPaneWidth {
+    #[default]
+    Auto,
+    Columns(u16),
+}
+
+impl IdPaneWidth {
+    /// constraint for the pane, keeping a fixed width within what `available` can hold
+    fn constraint(self, available: u16) -> Constraint {
+        match self {
+            Self::Auto => Constraint::Percentage(SEQUENCE_ID_PANE_WIDTH_PERCENT),
+            Self::Columns(width) => {
+                let max = available
+                    .saturating_sub(MIN_ALIGNMENT_PANE_WIDTH)
+                    .max(MIN_SEQUENCE_ID_PANE_WIDTH);
+                Constraint::Length(width.clamp(MIN_SEQUENCE_ID_PANE_WIDTH, max))
+            }
+        }
+    }
+}
+
+impl fmt::Display for IdPaneWidth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Auto => f.write_str("auto"),
+            Self::Columns(width) => write!(f, "{width}"),
+        }
+    }
+}
+
+impl FromStr for IdPaneWidth {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value == "auto" {
+            return Ok(Self::Auto);
+        }
+        match value.parse::<u16>() {
+            Ok(width) if width >= MIN_SEQUENCE_ID_PANE_WIDTH => Ok(Self::Columns(width)),
+            Ok(_) => Err(anyhow::format_err!(
+                "sequence ID pane width must be at least {MIN_SEQUENCE_ID_PANE_WIDTH}"
+            )),
+            Err(_) => Err(anyhow::format_err!(
+                "invalid sequence ID pane width: {value}"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LayoutMode {
+    #[default]
+    Panned,
+    Wrapped,
+}
+
+impl LayoutMode {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Panned => "panned",
+            Self::Wrapped => "wrapped",
+        }
+    }
+
+    pub const fn all() -> [Self; 2] {
+        [Self::Panned, Self::Wrapped]
+    }
+}
+
+impl fmt::Display for LayoutMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for LayoutMode {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|mode| mode.name() == value)
+            .ok_or_else(|| anyhow::format_err!("invalid layout: {value}"))
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct PinnedSectionLayout {
@@ -36,6 +132,52 @@ pub fn pinned_section_layout(pinned_count: usize, available_height: usize) -> Pi
         scrollable_height,
     }
 }
+
+/// stacked blocks used by the wrapped layout, each holding a ruler and every
+/// rendered row for one pane-width column range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WrappedLayout {
+    pub block_rows: usize,
+    pub block_count: usize,
+    /// rows of each block above its sequence rows
+    pub ruler_height: u16,
+}
+
+impl WrappedLayout {
+    pub fn new(available_height: usize, rows_needed: usize, ruler_height: u16) -> Self {
+        let header = usize::from(ruler_height);
+        let block_rows = rows_needed.clamp(1, available_height.saturating_sub(header).max(1));
+        let block_count = (available_height / (header + block_rows)).max(1);
+
+        Self {
+            block_rows,
+            block_count,
+            ruler_height,
+        }
+    }
+
+    /// drops trailing blocks that would have no columns to show
+    pub fn trimmed(self, visible_columns: usize, block_width: usize) -> Self {
+        let needed = visible_columns.div_ceil(block_width.max(1)).max(1);
+        Self {
+            block_count: self.block_count.min(needed),
+            ..self
+        }
+    }
+
+    /// splits `area` into one rect per block, top to bottom
+    pub fn block_areas(self, area: Rect) -> impl Iterator<Item = Rect> {
+        let block_height = self.ruler_height + self.block_rows as u16;
+        (0..self.block_count as u16)
+            .map(move |index| Rect {
+                y: area.y + index * block_height,
+                height: block_height,
+                ..area
+            })
+            .filter(move |block| block.bottom() <= area.bottom())
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct FrameLayout {
     pub top_status_area: Rect,
@@ -61,6 +203,33 @@ impl FrameLayout {
     }
 }
 
+/// optional panes and sizes the app layout is built around
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LayoutOptions {
+    pub id_pane_width: IdPaneWidth,
+    pub ungapped_pane: bool,
+    /// adds a row to the consensus pane for the translated consensus
+    pub consensus_translation: bool,
+    /// columns on the left of the alignment and consensus panes for the position gutter
+    pub position_gutter: u16,
+    /// adds a row under the ruler for the column depth track
+    pub depth_track: bool,
+}
+
+/// width of the position gutter for an alignment `column_count` columns long: its widest
+/// position and a space
+pub fn position_gutter_width(column_count: usize) -> u16 {
+    (column_count.max(1).ilog10() + 2) as u16
+}
+
+/// splits the position gutter off the left of a pane's inner area
+pub fn split_position_gutter(area: Rect, gutter: u16) -> [Rect; 2] {
+    area.layout(&Layout::horizontal([
+        Constraint::Length(gutter.min(area.width)),
+        Constraint::Fill(1),
+    ]))
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct AppLayout {
     pub sequence_id_pane: Rect,
@@ -68,25 +237,44 @@ pub struct AppLayout {
     pub alignment_pane_sequence_rows: Rect,
     pub consensus_sequence_id_pane: Rect,
     pub consensus_alignment_pane: Rect,
+    pub ungapped_sequence_id_pane: Option<Rect>,
+    pub ungapped_pane: Option<Rect>,
+    pub position_gutter: u16,
+    /// rows above the sequence rows: the ruler, and the depth track when shown
+    pub ruler_height: u16,
 }
 
 impl AppLayout {
-    pub fn new(content_area: Rect) -> Self {
-        let [alignment_area, consensus_area] = content_area
-            .layout(&vertical![*=1, ==CONSENSUS_PANE_HEIGHT_ROWS].spacing(Spacing::Overlap(1)));
+    pub fn new(content_area: Rect, options: LayoutOptions) -> Self {
+        let (panes_area, ungapped_area) = if options.ungapped_pane {
+            let [panes_area, ungapped_area] = content_area
+                .layout(&vertical![*=1, ==UNGAPPED_PANE_HEIGHT_ROWS].spacing(Spacing::Overlap(1)));
+            (panes_area, Some(ungapped_area))
+        } else {
+            (content_area, None)
+        };
+        let consensus_height =
+            CONSENSUS_PANE_HEIGHT_ROWS + u16::from(options.consensus_translation);
+        let [alignment_area, consensus_area] =
+            panes_area.layout(&vertical![*=1, ==consensus_height].spacing(Spacing::Overlap(1)));
 
-        let [sequence_id_pane_area, alignment_pane_area] = alignment_area.layout(
-            &horizontal![==SEQUENCE_ID_PANE_WIDTH_PERCENT%, *=1].spacing(Spacing::Overlap(1)),
-        );
+        let columns = Layout::horizontal([
+            options.id_pane_width.constraint(content_area.width),
+            Constraint::Fill(1),
+        ])
+        .spacing(Spacing::Overlap(1));
+        let [sequence_id_pane_area, alignment_pane_area] = alignment_area.layout(&columns);
         let [
             consensus_sequence_id_pane_area,
             consensus_alignment_pane_area,
-        ] = consensus_area.layout(
-            &horizontal![==SEQUENCE_ID_PANE_WIDTH_PERCENT%, *=1].spacing(Spacing::Overlap(1)),
+        ] = consensus_area.layout(&columns);
+        let ungapped_panes = ungapped_area.map(|area| area.layout::<2>(&columns));
+        let [_, content_area] = split_position_gutter(
+            ratatui::widgets::Block::bordered().inner(alignment_pane_area),
+            options.position_gutter,
         );
-        let [_, sequence_rows_area] = ratatui::widgets::Block::bordered()
-            .inner(alignment_pane_area)
-            .layout(&vertical![==RULER_HEIGHT_ROWS, *=1]);
+        let ruler_height = RULER_HEIGHT_ROWS + u16::from(options.depth_track);
+        let [_, sequence_rows_area] = content_area.layout(&vertical![==ruler_height, *=1]);
 
         Self {
             sequence_id_pane: sequence_id_pane_area,
@@ -94,6 +282,188 @@ impl AppLayout {
             alignment_pane_sequence_rows: sequence_rows_area,
             consensus_sequence_id_pane: consensus_sequence_id_pane_area,
             consensus_alignment_pane: consensus_alignment_pane_area,
+            ungapped_sequence_id_pane: ungapped_panes.map(|[id_pane, _]| id_pane),
+            ungapped_pane: ungapped_panes.map(|[_, pane]| pane),
+            position_gutter: options.position_gutter,
+            ruler_height,
         }
     }
+
+    /// width of the sequence content inside the alignment pane borders and position gutter
+    pub fn alignment_content_width(&self) -> usize {
+        self.alignment_pane
+            .width
+            .saturating_sub(2)
+            .saturating_sub(self.position_gutter) as usize
+    }
+
+    /// wrapped block geometry for the alignment pane, sized so each block shows
+    /// the pinned band and every scrollable row when they fit
+    pub fn wrapped(&self, pinned_count: usize, row_count: usize) -> WrappedLayout {
+        let available_height = self.alignment_pane.height.saturating_sub(2) as usize;
+        let rows_needed = pinned_count + usize::from(pinned_count > 0) + row_count;
+        WrappedLayout::new(available_height, rows_needed, self.ruler_height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapped_layout_stacks_as_many_blocks_as_fit() {
+        let wrapped = WrappedLayout::new(20, 3, RULER_HEIGHT_ROWS);
+
+        assert_eq!(wrapped.block_rows, 3);
+        assert_eq!(wrapped.block_count, 4);
+
+        let areas: Vec<Rect> = wrapped.block_areas(Rect::new(0, 0, 10, 20)).collect();
+        assert_eq!(areas.len(), 4);
+        assert_eq!(areas[1], Rect::new(0, 5, 10, 5));
+    }
+
+    #[test]
+    fn wrapped_layout_uses_single_block_when_rows_overflow() {
+        let wrapped = WrappedLayout::new(10, 50, RULER_HEIGHT_ROWS);
+
+        assert_eq!(wrapped.block_rows, 8);
+        assert_eq!(wrapped.block_count, 1);
+    }
+
+    #[test]
+    fn trimmed_drops_blocks_past_the_last_column() {
+        let wrapped = WrappedLayout::new(20, 3, RULER_HEIGHT_ROWS).trimmed(25, 10);
+
+        assert_eq!(wrapped.block_count, 3);
+    }
+
+    #[test]
+    fn position_gutter_narrows_the_sequence_rows() {
+        let area = Rect::new(0, 0, 100, 30);
+        let plain = AppLayout::new(area, LayoutOptions::default());
+        let gutter = position_gutter_width(29_903);
+
+        let layout = AppLayout::new(
+            area,
+            LayoutOptions {
+                position_gutter: gutter,
+                ..LayoutOptions::default()
+            },
+        );
+
+        assert_eq!(gutter, 6);
+        assert_eq!(
+            layout.alignment_pane_sequence_rows.x,
+            plain.alignment_pane_sequence_rows.x + gutter
+        );
+        assert_eq!(
+            layout.alignment_content_width(),
+            plain.alignment_content_width() - usize::from(gutter)
+        );
+    }
+
+    #[test]
+    fn depth_track_adds_a_row_under_the_ruler() {
+        let area = Rect::new(0, 0, 100, 30);
+        let plain = AppLayout::new(area, LayoutOptions::default());
+
+        let layout = AppLayout::new(
+            area,
+            LayoutOptions {
+                depth_track: true,
+                ..LayoutOptions::default()
+            },
+        );
+
+        assert_eq!(layout.ruler_height, RULER_HEIGHT_ROWS + 1);
+        assert_eq!(
+            layout.alignment_pane_sequence_rows.y,
+            plain.alignment_pane_sequence_rows.y + 1
+        );
+        assert_eq!(layout.wrapped(0, 3).block_areas(area).nth(1).unwrap().y, 6);
+    }
+
+    #[test]
+    fn fixed_id_pane_width_is_clamped_to_the_content_area() {
+        let area = Rect::new(0, 0, 100, 30);
+
+        let layout = AppLayout::new(
+            area,
+            LayoutOptions {
+                id_pane_width: IdPaneWidth::Columns(30),
+                ..LayoutOptions::default()
+            },
+        );
+        assert_eq!(layout.sequence_id_pane.width, 30);
+        assert_eq!(layout.consensus_sequence_id_pane.width, 30);
+
+        let layout = AppLayout::new(
+            area,
+            LayoutOptions {
+                id_pane_width: IdPaneWidth::Columns(500),
+                ..LayoutOptions::default()
+            },
+        );
+        assert_eq!(layout.sequence_id_pane.width, 90);
+
+        let layout = AppLayout::new(area, LayoutOptions::default());
+        assert_eq!(layout.sequence_id_pane.width, 20);
+    }
+
+    #[test]
+    fn ungapped_pane_takes_rows_from_the_bottom_of_the_content_area() {
+        let area = Rect::new(0, 0, 100, 30);
+
+        let hidden = AppLayout::new(area, LayoutOptions::default());
+        assert_eq!(hidden.ungapped_pane, None);
+
+        let shown = AppLayout::new(
+            area,
+            LayoutOptions {
+                ungapped_pane: true,
+                ..LayoutOptions::default()
+            },
+        );
+        let pane = shown.ungapped_pane.expect("pane should be laid out");
+        assert_eq!(pane.bottom(), area.bottom());
+        assert_eq!(pane.height, UNGAPPED_PANE_HEIGHT_ROWS);
+        assert_eq!(shown.consensus_alignment_pane.bottom(), pane.y + 1);
+        assert_eq!(shown.ungapped_sequence_id_pane.map(|id| id.width), Some(20));
+    }
+
+    #[test]
+    fn consensus_translation_adds_a_consensus_row() {
+        let area = Rect::new(0, 0, 100, 30);
+        let options = LayoutOptions {
+            consensus_translation: true,
+            ..LayoutOptions::default()
+        };
+
+        let layout = AppLayout::new(area, options);
+
+        assert_eq!(
+            layout.consensus_alignment_pane.height,
+            CONSENSUS_PANE_HEIGHT_ROWS + 1
+        );
+    }
+
+    #[test]
+    fn id_pane_width_parses_auto_and_columns() {
+        assert_eq!("auto".parse::<IdPaneWidth>().ok(), Some(IdPaneWidth::Auto));
+        assert_eq!(
+            "24".parse::<IdPaneWidth>().ok(),
+            Some(IdPaneWidth::Columns(24))
+        );
+        assert!("3".parse::<IdPaneWidth>().is_err());
+        assert!("wide".parse::<IdPaneWidth>().is_err());
+    }
+
+    #[test]
+    fn layout_mode_parses_names() {
+        assert_eq!(
+            "wrapped".parse::<LayoutMode>().ok(),
+            Some(LayoutMode::Wrapped)
+        );
+        assert!("stacked".parse::<LayoutMode>().is_err());
+    }
 }
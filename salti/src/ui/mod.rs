@@ -3,9 +3,13 @@ pub(crate) mod consensus_pane;
 pub(crate) mod frame;
 pub(crate) mod layout;
 pub(crate) mod notification;
+pub(crate) mod overview;
 pub(crate) mod render;
+pub(crate) mod row_cache;
 pub(crate) mod rows;
 pub(crate) mod selection;
 pub(crate) mod sequence_id_pane;
+pub(crate) mod snapshot;
 pub(crate) mod ui_state;
+pub(crate) mod ungapped_pane;
 pub(crate) mod utils;
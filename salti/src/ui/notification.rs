@@ -1,14 +1,73 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
 use crate::config::theme::ThemeStyles;
+use crate::ui::utils::wrap_text;
 use ratatui::Frame;
 use ratatui::layout::Rect;
-use ratatui::style::Style;
+use ratatui::style::{Modifier, Style, Styled};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::Paragraph;
+use ratatui::widgets::{Block, Clear, Paragraph, Widget};
+use serde::Deserialize;
+
+/// most message lines shown in the expanded box before the rest is elided
+const MAX_EXPANDED_LINES: usize = 8;
+/// how long the input line stays inverted after an error with `flash` feedback
+pub const FLASH_DURATION: Duration = Duration::from_millis(200);
+
+/// what happens besides the message when an action is rejected with an error
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorFeedback {
+    #[default]
+    Off,
+    /// rings the terminal bell
+    Bell,
+    /// briefly inverts the input line
+    Flash,
+}
+
+impl ErrorFeedback {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Bell => "bell",
+            Self::Flash => "flash",
+        }
+    }
+
+    pub const fn all() -> [Self; 3] {
+        [Self::Off, Self::Bell, Self::Flash]
+    }
+}
+
+impl fmt::Display for ErrorFeedback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ErrorFeedback {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|feedback| feedback.name() == value)
+            .ok_or_else(|| anyhow::format_err!("invalid error feedback: {value}"))
+    }
+}
+
+/// inverts the input line while an error flash is showing
+pub fn render_flash(f: &mut Frame, input_area: Rect) {
+    f.buffer_mut()
+        .set_style(input_area, Style::new().add_modifier(Modifier::REVERSED));
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NotificationLevel {
     Error,
-    #[allow(dead_code)]
     Warning,
     Info,
 }
@@ -27,16 +86,118 @@ fn notification_prefix(level: NotificationLevel, theme: &ThemeStyles) -> (&'stat
     }
 }
 
+const fn notification_title(level: NotificationLevel) -> &'static str {
+    match level {
+        NotificationLevel::Error => " Error ",
+        NotificationLevel::Warning => " Warning ",
+        NotificationLevel::Info => " Info ",
+    }
+}
+
+/// lines of a message too long for the input line, wrapped to fit inside a box `width` wide.
+/// messages that fit on the input line have none.
+fn expanded_lines(prefix: &str, message: &str, width: usize) -> Option<Vec<String>> {
+    let single_line = prefix.chars().count() + message.chars().count();
+    if !message.contains('\n') && single_line <= width {
+        return None;
+    }
+    let mut lines = wrap_text(message, width.saturating_sub(2));
+    if lines.len() > MAX_EXPANDED_LINES {
+        let hidden = lines.len() - MAX_EXPANDED_LINES + 1;
+        lines.truncate(MAX_EXPANDED_LINES - 1);
+        lines.push(format!("... {hidden} more lines, see the log for the rest"));
+    }
+    Some(lines)
+}
+
+/// draws the notification on the input line, expanding long messages into a box above it
 pub fn render_notification(
     f: &mut Frame,
+    content_area: Rect,
     input_area: Rect,
     notification: &Notification,
     theme: &ThemeStyles,
 ) {
     let (prefix, style) = notification_prefix(notification.level, theme);
-    let line = Line::from(vec![
-        Span::styled(prefix, style),
-        Span::styled(notification.message.as_str(), style),
-    ]);
-    f.render_widget(Paragraph::new(line).style(theme.base_block), input_area);
+    let Some(lines) = expanded_lines(prefix, &notification.message, usize::from(input_area.width))
+    else {
+        let line = Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(notification.message.as_str(), style),
+        ]);
+        f.render_widget(Paragraph::new(line).style(theme.base_block), input_area);
+        return;
+    };
+
+    let height = u16::try_from(lines.len() + 2)
+        .unwrap_or(u16::MAX)
+        .min(content_area.height);
+    let area = Rect::new(
+        input_area.x,
+        input_area.y.saturating_sub(height).max(content_area.y),
+        input_area.width,
+        height,
+    );
+    Clear.render(area, f.buffer_mut());
+    f.render_widget(
+        Paragraph::new(
+            lines
+                .into_iter()
+                .map(|line| Line::from(line.set_style(style)))
+                .collect::<Vec<_>>(),
+        )
+        .block(
+            Block::bordered()
+                .title(Line::from(
+                    notification_title(notification.level).set_style(style),
+                ))
+                .border_style(theme.border)
+                .style(theme.panel_block),
+        ),
+        area,
+    );
+    f.render_widget(
+        Paragraph::new(Line::from(
+            "Press any key to dismiss".set_style(theme.text_muted),
+        ))
+        .style(theme.base_block),
+        input_area,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_messages_stay_on_the_input_line() {
+        assert_eq!(expanded_lines("Error: ", "bad regex", 40), None);
+    }
+
+    #[test]
+    fn long_messages_wrap_and_split_long_paths() {
+        let lines = expanded_lines(
+            "Error: ",
+            "failed to open /a/very/long/path/to/alignment.fasta",
+            20,
+        )
+        .expect("message is wider than the input line");
+
+        assert_eq!(
+            lines,
+            vec!["failed to open", "/a/very/long/path/", "to/alignment.fasta"]
+        );
+    }
+
+    #[test]
+    fn very_long_messages_are_elided() {
+        let message = ["line"; MAX_EXPANDED_LINES + 4].join("\n");
+        let lines = expanded_lines("", &message, 40).expect("multi-line messages expand");
+
+        assert_eq!(lines.len(), MAX_EXPANDED_LINES);
+        assert_eq!(
+            lines.last().map(String::as_str),
+            Some("... 5 more lines, see the log for the rest")
+        );
+    }
 }
@@ -0,0 +1,177 @@
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+
+use crate::config::theme::Theme;
+use crate::core::model::AlignmentModel;
+use crate::core::stats_cache::ColumnStatsCache;
+use crate::core::viewport::ViewportWindow;
+
+/// alignment rows summarised by each line of the overview
+pub const OVERVIEW_CELL_ROWS: usize = 4;
+/// alignment columns summarised by each cell of the overview
+pub const OVERVIEW_CELL_COLS: usize = 2;
+/// agreement with the consensus at or above which a cell is drawn as conserved
+const CONSERVED_AGREEMENT: f32 = 0.9;
+/// agreement with the consensus below which a cell is drawn as divergent
+const DIVERGENT_AGREEMENT: f32 = 0.6;
+
+/// braille dot of each row and column of a cell
+const BRAILLE_DOTS: [[u8; OVERVIEW_CELL_COLS]; OVERVIEW_CELL_ROWS] =
+    [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+const BRAILLE_BASE: u32 = 0x2800;
+
+type CellBytes = [[Option<u8>; OVERVIEW_CELL_COLS]; OVERVIEW_CELL_ROWS];
+
+fn is_residue(byte: u8) -> bool {
+    !matches!(byte, b'-' | b'.' | b' ')
+}
+
+/// braille glyph with a dot for every residue of a cell, and the share of those residues that
+/// match the consensus of their column. `None` when the cell holds no residues.
+fn braille_cell(
+    bytes: &CellBytes,
+    consensus: [Option<u8>; OVERVIEW_CELL_COLS],
+) -> (char, Option<f32>) {
+    let mut dots = 0u8;
+    let mut residues = 0u32;
+    let mut agreeing = 0u32;
+    for (row, row_bytes) in bytes.iter().enumerate() {
+        for (col, byte) in row_bytes.iter().enumerate() {
+            let Some(byte) = byte.filter(|&byte| is_residue(byte)) else {
+                continue;
+            };
+            dots |= BRAILLE_DOTS[row][col];
+            residues += 1;
+            if consensus[col].is_some_and(|consensus| consensus.eq_ignore_ascii_case(&byte)) {
+                agreeing += 1;
+            }
+        }
+    }
+    if residues == 0 {
+        return (' ', None);
+    }
+    let glyph = char::from_u32(BRAILLE_BASE + u32::from(dots)).unwrap_or(' ');
+    (glyph, Some(agreeing as f32 / residues as f32))
+}
+
+fn agreement_colour(agreement: f32, theme: &Theme) -> Color {
+    if agreement >= CONSERVED_AGREEMENT {
+        theme.success
+    } else if agreement >= DIVERGENT_AGREEMENT {
+        theme.warning
+    } else {
+        theme.error
+    }
+}
+
+/// the scrollable rows of `window` as braille, each line summarising `OVERVIEW_CELL_ROWS` rows
+/// and each cell `OVERVIEW_CELL_COLS` columns. cells are dimmed until the consensus is known.
+pub fn overview_lines(
+    alignment: &AlignmentModel,
+    window: &ViewportWindow,
+    metrics: &ColumnStatsCache,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let consensus: Vec<Option<u8>> = window
+        .col_range
+        .clone()
+        .map(|relative_col| {
+            metrics
+                .raw_summary_at(relative_col)
+                .and_then(|summary| summary.consensus)
+        })
+        .collect();
+    let consensus_ready = window
+        .col_range
+        .clone()
+        .all(|relative_col| metrics.raw_summary_at(relative_col).is_some());
+    let sequences: Vec<_> = window
+        .row_range
+        .clone()
+        .filter_map(|relative_row| alignment.view().sequence(relative_row))
+        .collect();
+
+    sequences
+        .chunks(OVERVIEW_CELL_ROWS)
+        .map(|band| {
+            let cells = (0..consensus.len())
+                .step_by(OVERVIEW_CELL_COLS)
+                .map(|offset| {
+                    let mut bytes: CellBytes = [[None; OVERVIEW_CELL_COLS]; OVERVIEW_CELL_ROWS];
+                    let mut cell_consensus = [None; OVERVIEW_CELL_COLS];
+                    for col in 0..OVERVIEW_CELL_COLS {
+                        if offset + col >= consensus.len() {
+                            break;
+                        }
+                        cell_consensus[col] = consensus[offset + col];
+                        for (row, sequence) in band.iter().enumerate() {
+                            bytes[row][col] =
+                                sequence.byte_at(window.col_range.start + offset + col);
+                        }
+                    }
+                    let (glyph, agreement) = braille_cell(&bytes, cell_consensus);
+                    let colour = match agreement {
+                        Some(agreement) if consensus_ready => agreement_colour(agreement, theme),
+                        _ => theme.text_dim,
+                    };
+                    Span::styled(glyph.to_string(), Style::new().fg(colour))
+                });
+            Line::from(cells.collect::<Vec<_>>())
+        })
+        .collect()
+}
+
+/// keeps the first of every `step` cells of a line drawn one cell per column, so lines such as
+/// the consensus line out of the overview stay lined up with its cells. lines that are not
+/// `columns` wide, such as progress messages, are kept as they are.
+pub fn sample_cells(line: Line<'static>, columns: usize, step: usize) -> Line<'static> {
+    if step <= 1 || line.width() != columns {
+        return line;
+    }
+    let style = line.style;
+    let cells: Vec<Span<'static>> = line
+        .spans
+        .into_iter()
+        .flat_map(|span| {
+            let span_style = span.style;
+            span.content
+                .chars()
+                .map(|symbol| Span::styled(symbol.to_string(), span_style))
+                .collect::<Vec<_>>()
+        })
+        .step_by(step)
+        .collect();
+    Line::from(cells).style(style)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn braille_dots_follow_residues_and_agreement() {
+        let mut bytes: CellBytes = [[None; OVERVIEW_CELL_COLS]; OVERVIEW_CELL_ROWS];
+        bytes[0] = [Some(b'A'), Some(b'-')];
+        bytes[1] = [Some(b'A'), Some(b'C')];
+        bytes[3] = [Some(b'g'), Some(b'T')];
+
+        let (glyph, agreement) = braille_cell(&bytes, [Some(b'A'), Some(b'T')]);
+        assert_eq!(
+            glyph,
+            char::from_u32(BRAILLE_BASE + 0x01 + 0x02 + 0x10 + 0x40 + 0x80).unwrap()
+        );
+        assert_eq!(agreement, Some(3.0 / 5.0));
+
+        let gaps = [[Some(b'-'); OVERVIEW_CELL_COLS]; OVERVIEW_CELL_ROWS];
+        assert_eq!(braille_cell(&gaps, [None, None]), (' ', None));
+    }
+
+    #[test]
+    fn sampling_keeps_every_other_cell() {
+        let line = Line::from(vec![Span::raw("ACG"), Span::raw("TA")]);
+
+        assert_eq!(sample_cells(line.clone(), 5, 1), line);
+        assert_eq!(sample_cells(line.clone(), 5, 2).to_string(), "AGA");
+        assert_eq!(sample_cells(line.clone(), 8, 2), line);
+    }
+}
@@ -1,14 +1,17 @@
 use crate::{
-    core::{model::AlignmentModel, stats_cache::ColumnStatsCache},
+    core::{model::AlignmentModel, stats_cache::ColumnStatsCache, viewport::ViewportWindow},
     overlay::render::render_overlays,
     ui::{
-        alignment_pane::render_alignment_pane,
+        alignment_pane::{
+            render_alignment_pane, render_wrapped_alignment_pane, wrapped_block_window,
+        },
         consensus_pane::render_consensus_pane,
         frame::render_frame,
-        layout::{AppLayout, FrameLayout, RULER_HEIGHT_ROWS, pinned_section_layout},
+        layout::{AppLayout, FrameLayout, LayoutMode, PinnedSectionLayout, pinned_section_layout},
         selection::{selection_row_bounds, selection_visible_col_range},
-        sequence_id_pane::render_sequence_id_pane,
+        sequence_id_pane::{render_sequence_id_pane, render_wrapped_sequence_id_pane},
         ui_state::{LoadingState, UiState},
+        ungapped_pane::render_ungapped_pane,
     },
 };
 use ratatui::Frame;
@@ -21,6 +24,9 @@ use ratatui::widgets::{Block, Paragraph};
 const SELECTION_ROW_HIGHLIGHT_ALPHA: f32 = 0.3;
 const SELECTION_ROW_TINT_ALPHA: f32 = 0.22;
 const SELECTION_COL_HIGHLIGHT_ALPHA: f32 = 0.28;
+const HIGHLIGHT_ALPHA: f32 = 0.35;
+const CROSSHAIR_LINE_ALPHA: f32 = 0.18;
+const CROSSHAIR_CELL_ALPHA: f32 = 0.55;
 
 fn interpolate(from: u8, to: u8, alpha: f32) -> u8 {
     let from = f32::from(from);
@@ -78,6 +84,78 @@ fn shader(
     }
 }
 
+/// yields the row offset within the sequence rows area and the absolute row id of every rendered
+/// row. with compact density two scrollable rows share each offset.
+fn rendered_rows<'a>(
+    alignment: &'a AlignmentModel,
+    window: &'a ViewportWindow,
+    band_layout: PinnedSectionLayout,
+    rows_per_line: usize,
+) -> impl Iterator<Item = (u16, usize)> + 'a {
+    let pinned = alignment
+        .rows()
+        .pinned()
+        .iter()
+        .copied()
+        .take(band_layout.pinned_rendered)
+        .enumerate()
+        .map(|(row_offset, absolute_row)| (row_offset as u16, absolute_row));
+    let scroll_start = band_layout.pinned_rendered + band_layout.divider_height;
+    let scrollable =
+        window
+            .row_range
+            .clone()
+            .enumerate()
+            .filter_map(move |(row_offset, relative_row)| {
+                let absolute_row = alignment.view().absolute_row_id(relative_row)?;
+                Some((
+                    (scroll_start + row_offset / rows_per_line) as u16,
+                    absolute_row,
+                ))
+            });
+    pinned.chain(scrollable)
+}
+
+fn render_highlights(f: &mut Frame, layout: &AppLayout, alignment: &AlignmentModel, ui: &UiState) {
+    let window = ui.viewport.window();
+    let sequence_rows_area = layout.alignment_pane_sequence_rows;
+    let band_layout = pinned_section_layout(
+        alignment.rows().pinned().len(),
+        sequence_rows_area.height as usize,
+    );
+    let rows_per_line = ui.viewport.rows_per_line();
+
+    for &highlight in &ui.highlights {
+        let Some(visible_col_range) =
+            selection_visible_col_range(highlight, alignment, &window.col_range)
+        else {
+            continue;
+        };
+        let cells = ui.viewport.cells_of_columns(visible_col_range);
+        let start_x = sequence_rows_area.x + cells.start as u16;
+        let width = cells.len() as u16;
+        let (row_min, row_max) = selection_row_bounds(highlight);
+
+        let mut shaded_line = None;
+        for (row_offset, absolute_row) in
+            rendered_rows(alignment, &window, band_layout, rows_per_line)
+        {
+            // compact rows share a line, which is shaded once
+            if !(row_min..=row_max).contains(&absolute_row) || shaded_line == Some(row_offset) {
+                continue;
+            }
+            shaded_line = Some(row_offset);
+            shader(
+                f,
+                sequence_rows_area,
+                Rect::new(start_x, sequence_rows_area.y + row_offset, width, 1),
+                ui.theme.theme.accent_alt,
+                HIGHLIGHT_ALPHA,
+            );
+        }
+    }
+}
+
 fn render_mouse_selection(
     f: &mut Frame,
     layout: &AppLayout,
@@ -92,7 +170,7 @@ fn render_mouse_selection(
     let window = viewport.window();
     let id_inner_area = Block::bordered().inner(layout.sequence_id_pane);
     let sequence_rows_area = layout.alignment_pane_sequence_rows;
-    let id_content_y = id_inner_area.y + RULER_HEIGHT_ROWS;
+    let id_content_y = id_inner_area.y + layout.ruler_height;
     let id_end_x = id_inner_area.x.saturating_add(id_inner_area.width);
     let sequence_end_x = sequence_rows_area
         .x
@@ -103,24 +181,21 @@ fn render_mouse_selection(
     );
     let (row_min, row_max) = selection_row_bounds(selection);
 
-    for (row_offset, &absolute_row) in alignment
-        .rows()
-        .pinned()
-        .iter()
-        .take(band_layout.pinned_rendered)
-        .enumerate()
+    let mut shaded_line = None;
+    for (row_offset, absolute_row) in
+        rendered_rows(alignment, &window, band_layout, viewport.rows_per_line())
     {
-        if !(row_min..=row_max).contains(&absolute_row) {
+        if !(row_min..=row_max).contains(&absolute_row) || shaded_line == Some(row_offset) {
             continue;
         }
+        shaded_line = Some(row_offset);
 
-        let row_y = sequence_rows_area.y + row_offset as u16;
         shader(
             f,
             id_inner_area,
             Rect::new(
                 id_inner_area.x,
-                id_content_y + row_offset as u16,
+                id_content_y + row_offset,
                 id_end_x.saturating_sub(id_inner_area.x),
                 1,
             ),
@@ -132,7 +207,7 @@ fn render_mouse_selection(
             sequence_rows_area,
             Rect::new(
                 sequence_rows_area.x,
-                row_y,
+                sequence_rows_area.y + row_offset,
                 sequence_end_x.saturating_sub(sequence_rows_area.x),
                 1,
             ),
@@ -141,65 +216,97 @@ fn render_mouse_selection(
         );
     }
 
-    let scroll_start_y = sequence_rows_area.y
-        + band_layout.pinned_rendered as u16
-        + band_layout.divider_height as u16;
-    for (row_offset, relative_row) in window.row_range.clone().enumerate() {
-        let Some(absolute_row) = alignment.view().absolute_row_id(relative_row) else {
-            continue;
-        };
-        if !(row_min..=row_max).contains(&absolute_row) {
-            continue;
-        }
+    if let Some(visible_col_range) =
+        selection_visible_col_range(selection, alignment, &window.col_range)
+    {
+        let cells = viewport.cells_of_columns(visible_col_range);
+        let start_x = sequence_rows_area.x + cells.start as u16;
+        let end_x_exclusive = sequence_rows_area.x + cells.end as u16;
+        shader(
+            f,
+            sequence_rows_area,
+            Rect::new(
+                start_x,
+                sequence_rows_area.y,
+                end_x_exclusive.saturating_sub(start_x),
+                sequence_rows_area.height,
+            ),
+            ui.theme.theme.panel_bg,
+            SELECTION_COL_HIGHLIGHT_ALPHA,
+        );
+    }
+}
+
+fn render_crosshair(f: &mut Frame, layout: &AppLayout, alignment: &AlignmentModel, ui: &UiState) {
+    let Some(crosshair) = ui.crosshair else {
+        return;
+    };
 
-        let row_y = scroll_start_y + row_offset as u16;
+    let window = ui.viewport.window();
+    let id_inner_area = Block::bordered().inner(layout.sequence_id_pane);
+    let sequence_rows_area = layout.alignment_pane_sequence_rows;
+    let band_layout = pinned_section_layout(
+        alignment.rows().pinned().len(),
+        sequence_rows_area.height as usize,
+    );
+    let scroll_band_offset = (band_layout.pinned_rendered + band_layout.divider_height) as u16;
+    let accent = ui.theme.theme.accent;
+
+    let row_y = ui
+        .viewport
+        .line_of_row(crosshair.row)
+        .map(|line| scroll_band_offset + line as u16);
+    let col_x = window.col_range.contains(&crosshair.column).then(|| {
+        let cells = ui
+            .viewport
+            .cells_of_columns(crosshair.column..crosshair.column + 1);
+        sequence_rows_area.x + cells.start as u16
+    });
+
+    if let Some(row_offset) = row_y {
         shader(
             f,
             id_inner_area,
             Rect::new(
                 id_inner_area.x,
-                id_content_y
-                    + band_layout.pinned_rendered as u16
-                    + band_layout.divider_height as u16
-                    + row_offset as u16,
-                id_end_x.saturating_sub(id_inner_area.x),
+                id_inner_area.y + layout.ruler_height + row_offset,
+                id_inner_area.width,
                 1,
             ),
-            ui.theme.theme.accent,
-            SELECTION_ROW_HIGHLIGHT_ALPHA,
+            accent,
+            CROSSHAIR_LINE_ALPHA,
         );
         shader(
             f,
             sequence_rows_area,
             Rect::new(
                 sequence_rows_area.x,
-                row_y,
-                sequence_end_x.saturating_sub(sequence_rows_area.x),
+                sequence_rows_area.y + row_offset,
+                sequence_rows_area.width,
                 1,
             ),
-            ui.theme.theme.surface_bg,
-            SELECTION_ROW_TINT_ALPHA,
+            accent,
+            CROSSHAIR_LINE_ALPHA,
         );
     }
 
-    if let Some(visible_col_range) =
-        selection_visible_col_range(selection, alignment, &window.col_range)
-    {
-        let start_x =
-            sequence_rows_area.x + (visible_col_range.start - window.col_range.start) as u16;
-        let end_x_exclusive =
-            sequence_rows_area.x + (visible_col_range.end - window.col_range.start) as u16;
+    if let Some(x) = col_x {
         shader(
             f,
             sequence_rows_area,
-            Rect::new(
-                start_x,
-                sequence_rows_area.y,
-                end_x_exclusive.saturating_sub(start_x),
-                sequence_rows_area.height,
-            ),
-            ui.theme.theme.panel_bg,
-            SELECTION_COL_HIGHLIGHT_ALPHA,
+            Rect::new(x, sequence_rows_area.y, 1, sequence_rows_area.height),
+            accent,
+            CROSSHAIR_LINE_ALPHA,
+        );
+    }
+
+    if let (Some(row_offset), Some(x)) = (row_y, col_x) {
+        shader(
+            f,
+            sequence_rows_area,
+            Rect::new(x, sequence_rows_area.y + row_offset, 1, 1),
+            accent,
+            CROSSHAIR_CELL_ALPHA,
         );
     }
 }
@@ -236,7 +343,7 @@ fn render_empty_state_with_ui(f: &mut Frame, area: Rect, ui: &UiState) {
                 ),
                 Line::from(""),
                 Line::from(
-                    "Hint: use :load-alignment <alignment.fasta>"
+                    "Hint: use :browse-files or :load-alignment <alignment.fasta>"
                         .fg(theme.theme.text_dim)
                         .italic(),
                 ),
@@ -275,6 +382,7 @@ pub fn render(
         frame_layout.bottom_status_area,
         alignment,
         ui,
+        stats_cache,
     );
     let Some(alignment) = alignment else {
         render_empty_state_with_ui(f, frame_layout.content_area, ui);
@@ -283,24 +391,47 @@ pub fn render(
             frame_layout.overlay_area,
             frame_layout.input_area,
             None,
+            stats_cache,
             ui,
         );
         return;
     };
 
     let window = ui.viewport.window();
-    render_sequence_id_pane(f, layout, alignment, &window, &ui.theme);
-
-    render_alignment_pane(f, layout, alignment, &ui.viewport, stats_cache, &ui.theme);
-
-    render_consensus_pane(f, layout, alignment, &window, stats_cache, &ui.theme);
-    render_mouse_selection(f, layout, alignment, ui, &ui.viewport);
+    match ui.layout_mode {
+        LayoutMode::Panned => {
+            render_sequence_id_pane(f, layout, alignment, &window, ui);
+            render_alignment_pane(f, layout, alignment, stats_cache, ui);
+            render_consensus_pane(f, layout, alignment, &window, stats_cache, ui);
+            render_highlights(f, layout, alignment, ui);
+            render_mouse_selection(f, layout, alignment, ui, &ui.viewport);
+            render_crosshair(f, layout, alignment, ui);
+        }
+        LayoutMode::Wrapped => {
+            let block_width = layout.alignment_content_width();
+            let wrapped = layout
+                .wrapped(
+                    alignment.rows().pinned().len(),
+                    alignment.view().row_count(),
+                )
+                .trimmed(window.col_range.len(), block_width);
+            render_wrapped_sequence_id_pane(f, layout, alignment, &window, wrapped, ui);
+            render_wrapped_alignment_pane(f, layout, alignment, wrapped, stats_cache, ui);
+            let first_block = wrapped_block_window(&window, 0, block_width);
+            render_consensus_pane(f, layout, alignment, &first_block, stats_cache, ui);
+        }
+    }
+    ui.row_cache.finish_frame();
+    if let Some(ungapped) = &ui.ungapped {
+        render_ungapped_pane(f, layout, alignment, ungapped, &window, &ui.theme);
+    }
 
     render_overlays(
         f,
         frame_layout.overlay_area,
         frame_layout.input_area,
         Some(alignment),
+        stats_cache,
         ui,
     );
 }
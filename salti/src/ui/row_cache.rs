@@ -0,0 +1,247 @@
+use std::cell::{RefCell, RefMut};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+use ratatui::text::Line;
+use serde::Deserialize;
+
+use crate::config::theme::{ColorStyle, SequencePaletteId, ThemeId};
+use crate::core::model::{DiffAmbiguity, DiffMode, DiffOptions};
+
+/// alignment panes at least this wide switch to fast rendering under `auto`
+pub const FAST_RENDER_AUTO_WIDTH: u16 = 300;
+
+/// whether formatted alignment rows are kept between frames and only reformatted when their
+/// bytes or the window change. fast rendering leaves out the ORF issue marks of the
+/// translated view.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FastRender {
+    #[default]
+    Off,
+    /// on for alignment panes at least `FAST_RENDER_AUTO_WIDTH` columns wide
+    Auto,
+    On,
+}
+
+impl FastRender {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Auto => "auto",
+            Self::On => "on",
+        }
+    }
+
+    pub const fn all() -> [Self; 3] {
+        [Self::Off, Self::Auto, Self::On]
+    }
+
+    pub const fn enabled(self, pane_width: u16) -> bool {
+        match self {
+            Self::Off => false,
+            Self::Auto => pane_width >= FAST_RENDER_AUTO_WIDTH,
+            Self::On => true,
+        }
+    }
+}
+
+impl fmt::Display for FastRender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for FastRender {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|mode| mode.name() == value)
+            .ok_or_else(|| anyhow::format_err!("invalid fast render mode: {value}"))
+    }
+}
+
+/// everything besides a row's own bytes that its formatting depends on. rows cached under a
+/// different scope are never reused.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowScope {
+    /// stats generation, bumped whenever the alignment data or the visible rows change
+    pub generation: u64,
+    pub col_range: Range<usize>,
+    pub alignment_type: libmsa::AlignmentType,
+    pub translation: Option<libmsa::ReadingFrame>,
+    pub diff_mode: DiffMode,
+    /// visible bytes rows are compared against in diff mode
+    pub diff_against: Option<Vec<u8>>,
+    pub diff_ambiguity: DiffAmbiguity,
+    pub diff_options: DiffOptions,
+    pub quality_shading: bool,
+    pub conservation: Option<Vec<Option<f32>>>,
+    pub theme: (ThemeId, SequencePaletteId, ColorStyle),
+}
+
+#[derive(Debug)]
+struct CachedRow {
+    bytes: Vec<u8>,
+    line: Line<'static>,
+    used: bool,
+}
+
+/// rows formatted for one window of the alignment pane
+#[derive(Debug)]
+pub struct CachedRows {
+    scope: RowScope,
+    rows: HashMap<usize, CachedRow>,
+    used: bool,
+}
+
+impl CachedRows {
+    /// the line for `absolute_row`, reused when it was last formatted from the same `bytes`
+    pub fn line(
+        &mut self,
+        absolute_row: usize,
+        bytes: &[u8],
+        format: impl FnOnce() -> Line<'static>,
+    ) -> Line<'static> {
+        match self.rows.get_mut(&absolute_row) {
+            Some(row) if row.bytes == bytes => {
+                row.used = true;
+                row.line.clone()
+            }
+            _ => {
+                let line = format();
+                self.rows.insert(
+                    absolute_row,
+                    CachedRow {
+                        bytes: bytes.to_vec(),
+                        line: line.clone(),
+                        used: true,
+                    },
+                );
+                line
+            }
+        }
+    }
+}
+
+/// formatted alignment rows kept between frames by fast rendering. each window drawn in a
+/// frame (one per block when wrapped) keeps its own rows.
+#[derive(Debug, Default)]
+pub struct RowCache {
+    windows: RefCell<Vec<CachedRows>>,
+}
+
+impl RowCache {
+    /// the rows cached for `scope`, starting empty when nothing was drawn under it
+    pub fn rows(&self, scope: RowScope) -> RefMut<'_, CachedRows> {
+        let mut windows = self.windows.borrow_mut();
+        let index = match windows.iter().position(|window| window.scope == scope) {
+            Some(index) => index,
+            None => {
+                windows.push(CachedRows {
+                    scope,
+                    rows: HashMap::new(),
+                    used: false,
+                });
+                windows.len() - 1
+            }
+        };
+        RefMut::map(windows, |windows| {
+            let window = &mut windows[index];
+            window.used = true;
+            window
+        })
+    }
+
+    /// drops the windows and rows that were not drawn since the last call
+    pub fn finish_frame(&self) {
+        let mut windows = self.windows.borrow_mut();
+        windows.retain(|window| window.used);
+        for window in windows.iter_mut() {
+            window.used = false;
+            window.rows.retain(|_, row| std::mem::take(&mut row.used));
+        }
+    }
+
+    #[cfg(test)]
+    fn row_count(&self) -> usize {
+        self.windows
+            .borrow()
+            .iter()
+            .map(|window| window.rows.len())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scope(col_range: Range<usize>) -> RowScope {
+        RowScope {
+            generation: 1,
+            col_range,
+            alignment_type: libmsa::AlignmentType::Dna,
+            translation: None,
+            diff_mode: DiffMode::Off,
+            diff_against: None,
+            diff_ambiguity: DiffAmbiguity::default(),
+            diff_options: DiffOptions::default(),
+            quality_shading: false,
+            conservation: None,
+            theme: (
+                ThemeId::EverforestDark,
+                SequencePaletteId::default(),
+                ColorStyle::default(),
+            ),
+        }
+    }
+
+    #[test]
+    fn rows_are_reformatted_only_when_their_bytes_change() {
+        let cache = RowCache::default();
+        let mut formatted = 0;
+        let mut draw = |bytes: &[u8]| {
+            cache.rows(scope(0..4)).line(7, bytes, || {
+                formatted += 1;
+                Line::from(String::from_utf8_lossy(bytes).into_owned())
+            })
+        };
+
+        assert_eq!(draw(b"ACGT"), Line::from("ACGT"));
+        assert_eq!(draw(b"ACGT"), Line::from("ACGT"));
+        assert_eq!(draw(b"ACCT"), Line::from("ACCT"));
+        assert_eq!(formatted, 2);
+    }
+
+    #[test]
+    fn frames_drop_what_they_did_not_draw() {
+        let cache = RowCache::default();
+        cache.rows(scope(0..4)).line(0, b"ACGT", Line::default);
+        cache.rows(scope(0..4)).line(1, b"ACGT", Line::default);
+        cache.rows(scope(4..8)).line(0, b"ACGT", Line::default);
+        cache.finish_frame();
+        assert_eq!(cache.row_count(), 3);
+
+        cache.rows(scope(0..4)).line(1, b"ACGT", Line::default);
+        cache.finish_frame();
+        assert_eq!(cache.row_count(), 1);
+
+        let changed = RowScope {
+            diff_mode: DiffMode::Consensus,
+            ..scope(0..4)
+        };
+        let mut formatted = false;
+        cache.rows(changed).line(1, b"ACGT", || {
+            formatted = true;
+            Line::default()
+        });
+        cache.finish_frame();
+        assert!(formatted);
+        assert_eq!(cache.row_count(), 1);
+    }
+}
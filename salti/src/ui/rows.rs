@@ -1,9 +1,21 @@
 use std::ops::Range;
 
 use crate::config::theme::SequenceTheme;
-use ratatui::style::Stylize;
+use crate::core::model::{DiffAmbiguity, DiffOptions};
+use crate::core::orf::OrfIssue;
+use crate::core::primer::iupac_mask;
+use ratatui::style::{Color, Style, Stylize};
 use ratatui::text::Span;
 
+/// phred score at or above which residues keep their full background colour
+const FULL_SHADE_QUALITY: u8 = 40;
+/// brightness kept by the background of a residue with a phred score of 0
+const MIN_QUALITY_SHADE: f32 = 0.2;
+/// how far a fully conserved residue colour is pulled towards a dim grey
+const CONSERVED_FADE: f32 = 0.7;
+/// brightness of the grey that conserved residue colours fade towards
+const CONSERVED_GREY_SHADE: f32 = 0.6;
+
 /// Lookup table that maps each byte value (`0-255`) to a str for display.
 ///
 /// All printable ASCII bytes map to themselves.
@@ -31,6 +43,15 @@ const BYTE_TO_CHAR: [&str; 256] = [
 pub struct RowRenderMode<'a> {
     pub alignment_type: libmsa::AlignmentType,
     pub diff_against: Option<&'a [u8]>,
+    pub diff_ambiguity: DiffAmbiguity,
+    pub diff_options: DiffOptions,
+    /// visible columns where both rows have residues; with `ignore_end_gaps` every column outside
+    /// it is drawn as a match
+    pub diff_columns: Option<&'a Range<usize>>,
+    /// per-base phred scores for the visible bytes; residue backgrounds are darkened by quality
+    pub qualities: Option<&'a [u8]>,
+    /// conservation of the visible columns; residue colours fade as it rises
+    pub conservation: Option<&'a [Option<f32>]>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -56,7 +77,6 @@ pub type TranslatedDiffRange<'a> = TranslatedByteRange<'a>;
 struct VisibleCodon {
     protein_col: usize,
     nuc_start: usize,
-    centre: usize,
 }
 
 #[inline]
@@ -82,12 +102,31 @@ fn format_visible_bytes(
         .collect()
 }
 
+/// whether `byte` is shown as matching `diff_byte`. with IUPAC matching, nucleotide codes match
+/// when they share a base, so `R` matches `A`, `G`, `N` or `S`.
+#[inline]
+fn diff_matches(
+    byte: u8,
+    diff_byte: u8,
+    ambiguity: DiffAmbiguity,
+    options: DiffOptions,
+    alignment_type: libmsa::AlignmentType,
+) -> bool {
+    let is_gap = |byte: u8| matches!(byte, b'-' | b'.');
+    byte == diff_byte
+        || (options.ignore_case && byte.eq_ignore_ascii_case(&diff_byte))
+        || (options.ignore_end_gaps && is_gap(byte) && is_gap(diff_byte))
+        || (ambiguity == DiffAmbiguity::Iupac
+            && alignment_type.is_nucleotide()
+            && iupac_mask(byte) & iupac_mask(diff_byte) != 0)
+}
+
 #[inline]
 fn format_visible_bytes_with_diff(
     bytes: &[u8],
     diff_against: &[u8],
+    mode: RowRenderMode<'_>,
     sequence_theme: &SequenceTheme,
-    alignment_type: libmsa::AlignmentType,
 ) -> Vec<Span<'static>> {
     assert_eq!(
         bytes.len(),
@@ -95,31 +134,97 @@ fn format_visible_bytes_with_diff(
         "diff bytes must match the visible width"
     );
 
+    let end_gap = |col: usize| {
+        mode.diff_options.ignore_end_gaps
+            && mode
+                .diff_columns
+                .is_some_and(|columns| !columns.contains(&col))
+    };
     bytes
         .iter()
         .zip(diff_against.iter())
-        .map(|(&byte, &diff_byte)| {
-            if byte == diff_byte {
+        .enumerate()
+        .map(|(col, (&byte, &diff_byte))| {
+            if end_gap(col)
+                || diff_matches(
+                    byte,
+                    diff_byte,
+                    mode.diff_ambiguity,
+                    mode.diff_options,
+                    mode.alignment_type,
+                )
+            {
                 ".".fg(sequence_theme.diff_match)
             } else {
-                span_for_sequence_byte(byte, sequence_theme, alignment_type)
+                span_for_sequence_byte(byte, sequence_theme, mode.alignment_type)
             }
         })
         .collect()
 }
 
+/// applies `recolour` to the rgb residue colour of a span. the background is changed when
+/// residues are painted as cells, otherwise the letter itself.
+fn recolour_residue(style: Style, recolour: impl Fn(u8, u8, u8) -> Color) -> Style {
+    match (style.bg, style.fg) {
+        (Some(Color::Rgb(red, green, blue)), _) => style.bg(recolour(red, green, blue)),
+        (None, Some(Color::Rgb(red, green, blue))) => style.fg(recolour(red, green, blue)),
+        _ => style,
+    }
+}
+
+/// darkens an rgb residue colour towards black as the phred score drops
+fn shade_for_quality(style: Style, quality: u8) -> Style {
+    let fraction = f32::from(quality.min(FULL_SHADE_QUALITY)) / f32::from(FULL_SHADE_QUALITY);
+    let shade = MIN_QUALITY_SHADE + (1.0 - MIN_QUALITY_SHADE) * fraction;
+    let scale = |channel: u8| (f32::from(channel) * shade).round() as u8;
+    recolour_residue(style, |red, green, blue| {
+        Color::Rgb(scale(red), scale(green), scale(blue))
+    })
+}
+
+/// blends an rgb residue colour towards a dim grey of the same lightness, so conserved columns
+/// recede and variable ones keep their full colour
+fn fade_for_conservation(style: Style, conservation: f32) -> Style {
+    let amount = conservation.clamp(0.0, 1.0) * CONSERVED_FADE;
+    recolour_residue(style, |red, green, blue| {
+        let luma = 0.2126 * f32::from(red) + 0.7152 * f32::from(green) + 0.0722 * f32::from(blue);
+        let grey = luma * CONSERVED_GREY_SHADE;
+        let blend =
+            |channel: u8| (f32::from(channel) + (grey - f32::from(channel)) * amount).round() as u8;
+        Color::Rgb(blend(red), blend(green), blend(blue))
+    })
+}
+
 pub fn format_row_spans(
     visible_bytes: &[u8],
     sequence_theme: &SequenceTheme,
     mode: RowRenderMode<'_>,
+) -> Vec<Span<'static>> {
+    let mut spans = format_unshaded_row_spans(visible_bytes, sequence_theme, mode);
+    if let Some(qualities) = mode.qualities {
+        for (span, &quality) in spans.iter_mut().zip(qualities) {
+            span.style = shade_for_quality(span.style, quality);
+        }
+    }
+    if let Some(conservation) = mode.conservation {
+        for (span, &score) in spans.iter_mut().zip(conservation) {
+            if let Some(score) = score {
+                span.style = fade_for_conservation(span.style, score);
+            }
+        }
+    }
+    spans
+}
+
+fn format_unshaded_row_spans(
+    visible_bytes: &[u8],
+    sequence_theme: &SequenceTheme,
+    mode: RowRenderMode<'_>,
 ) -> Vec<Span<'static>> {
     match mode.diff_against {
-        Some(diff_against) => format_visible_bytes_with_diff(
-            visible_bytes,
-            diff_against,
-            sequence_theme,
-            mode.alignment_type,
-        ),
+        Some(diff_against) => {
+            format_visible_bytes_with_diff(visible_bytes, diff_against, mode, sequence_theme)
+        }
         None => format_visible_bytes(visible_bytes, sequence_theme, mode.alignment_type),
     }
 }
@@ -160,13 +265,9 @@ fn visible_codons(
     visible_protein_range(visible_nucleotide_range, frame, nucleotide_len)
         .into_iter()
         .flatten()
-        .map(move |protein_col| {
-            let nuc_start = frame.offset() + protein_col * 3;
-            VisibleCodon {
-                protein_col,
-                nuc_start,
-                centre: nuc_start + 1,
-            }
+        .map(move |protein_col| VisibleCodon {
+            protein_col,
+            nuc_start: frame.offset() + protein_col * 3,
         })
 }
 
@@ -178,13 +279,21 @@ pub fn format_translated_row_spans(
     sequence_theme: &SequenceTheme,
     diff_against: Option<TranslatedDiffRange<'_>>,
 ) -> Vec<Span<'static>> {
-    format_translated_spans(
+    // translated as one range, since skipping gaps has to read the bases before it
+    let protein_range = visible_protein_range(visible_nucleotide_range, frame, nucleotide_len);
+    let bytes: Vec<u8> = protein_range
+        .clone()
+        .and_then(|range| sequence.bytes_range(range).ok())
+        .map(|bytes| bytes.map(|(_, byte)| byte).collect())
+        .unwrap_or_default();
+    let start = protein_range.map_or(0, |range| range.start);
+    format_translated_byte_range_spans(
+        TranslatedByteRange::new(start, &bytes),
         visible_nucleotide_range,
         nucleotide_len,
         frame,
         sequence_theme,
         diff_against,
-        |protein_col| sequence.byte_at(protein_col),
     )
 }
 
@@ -214,39 +323,98 @@ fn format_translated_spans(
     diff_against: Option<TranslatedDiffRange<'_>>,
     mut byte_at: impl FnMut(usize) -> Option<u8>,
 ) -> Vec<Span<'static>> {
+    let spans = translated_window_spans(
+        visible_nucleotide_range,
+        nucleotide_len,
+        frame,
+        |protein_col| {
+            let residue = byte_at(protein_col).expect("visible codon must resolve");
+            let translated_style =
+                sequence_theme.style_for(residue, libmsa::AlignmentType::Protein);
+            let diff_matches =
+                diff_against.and_then(|diff| diff.byte_at(protein_col)) == Some(residue);
+            Some(if diff_matches {
+                [
+                    Span::raw(" "),
+                    ".".fg(sequence_theme.diff_match),
+                    Span::raw(" "),
+                ]
+            } else {
+                [
+                    Span::styled(" ", translated_style),
+                    Span::styled(BYTE_TO_CHAR[usize::from(residue)], translated_style),
+                    Span::styled(" ", translated_style),
+                ]
+            })
+        },
+    );
+    spans.expect("every visible codon is drawn")
+}
+
+/// one cell per column of `visible_nucleotide_range`, with each codon of `frame` that overlaps
+/// it spread over its three bases as drawn by `codon_cells`. codons cut by the window edge
+/// keep only their visible bases, and columns outside any complete codon are left blank. every
+/// translated line is laid out this way so the panes stay lined up with the rows. `None` when
+/// `codon_cells` has nothing to draw for a codon.
+pub fn translated_window_spans(
+    visible_nucleotide_range: &Range<usize>,
+    nucleotide_len: usize,
+    frame: libmsa::ReadingFrame,
+    mut codon_cells: impl FnMut(usize) -> Option<[Span<'static>; 3]>,
+) -> Option<Vec<Span<'static>>> {
     let width = visible_nucleotide_range.len();
     let mut spans = vec![Span::raw(" "); width];
 
     for codon in visible_codons(visible_nucleotide_range, frame, nucleotide_len) {
-        let residue = byte_at(codon.protein_col).expect("visible codon must resolve");
-        let translated_style = sequence_theme.style_for(residue, libmsa::AlignmentType::Protein);
-        let diff_matches =
-            diff_against.and_then(|diff| diff.byte_at(codon.protein_col)) == Some(residue);
-
-        for absolute_col in codon.nuc_start..=codon.nuc_start + 2 {
+        let cells = codon_cells(codon.protein_col)?;
+        for (absolute_col, cell) in (codon.nuc_start..).zip(cells) {
             let Some(window_offset) = absolute_col.checked_sub(visible_nucleotide_range.start)
             else {
                 continue;
             };
-            if window_offset >= width {
-                continue;
+            if let Some(span) = spans.get_mut(window_offset) {
+                *span = cell;
             }
+        }
+    }
 
-            spans[window_offset] = if diff_matches {
-                if absolute_col == codon.centre {
-                    ".".fg(sequence_theme.diff_match)
-                } else {
-                    Span::raw(" ")
-                }
-            } else if absolute_col == codon.centre {
-                Span::styled(BYTE_TO_CHAR[usize::from(residue)], translated_style)
-            } else {
-                Span::styled(" ", translated_style)
+    Some(spans)
+}
+
+/// Restyles translated row cells covered by ORF issues: stop codons keep their glyph, while
+/// frameshifting gaps are drawn as `-`.
+pub fn mark_orf_issues(
+    spans: &mut [Span<'static>],
+    issues: &[OrfIssue],
+    visible_nucleotide_range: &Range<usize>,
+    frame: libmsa::ReadingFrame,
+    style: Style,
+) {
+    for &issue in issues {
+        for absolute_col in issue.columns(frame) {
+            let Some(span) = absolute_col
+                .checked_sub(visible_nucleotide_range.start)
+                .and_then(|window_offset| spans.get_mut(window_offset))
+            else {
+                continue;
+            };
+            *span = match issue {
+                OrfIssue::PrematureStop { .. } => span.clone().patch_style(style),
+                OrfIssue::Frameshift { .. } => Span::styled("-", style),
             };
         }
     }
+}
 
-    spans
+/// Collects visible phred scores from a sequence view, or `None` if the row has no qualities.
+pub fn visible_qualities(
+    sequence: libmsa::SequenceView<'_>,
+    col_range: &Range<usize>,
+) -> Option<Vec<u8>> {
+    col_range
+        .clone()
+        .map(|relative_col| sequence.quality_at(relative_col))
+        .collect()
 }
 
 /// Collects visible bytes from a sequence view for the given relative column range.
@@ -270,6 +438,7 @@ mod tests {
         libmsa::RawSequence {
             id: id.to_string(),
             sequence: sequence.to_vec(),
+            quality: None,
         }
     }
 
@@ -277,6 +446,161 @@ mod tests {
         spans.iter().map(|span| span.content.as_ref()).collect()
     }
 
+    #[test]
+    fn iupac_diff_matches_ambiguity_codes_in_nucleotides_only() {
+        let theme = &crate::config::theme::EVERFOREST_DARK.sequence;
+        let mode = |alignment_type, diff_ambiguity| RowRenderMode {
+            alignment_type,
+            diff_against: Some(b"RANN-"),
+            diff_ambiguity,
+            diff_options: DiffOptions::default(),
+            diff_columns: None,
+            qualities: None,
+            conservation: None,
+        };
+        let text = |mode| spans_text(&format_row_spans(b"AGCT-", theme, mode));
+
+        assert_eq!(
+            text(mode(libmsa::AlignmentType::Dna, DiffAmbiguity::Iupac)),
+            ".G..."
+        );
+        assert_eq!(
+            text(mode(libmsa::AlignmentType::Dna, DiffAmbiguity::Strict)),
+            "AGCT."
+        );
+        assert_eq!(
+            text(mode(libmsa::AlignmentType::Protein, DiffAmbiguity::Iupac)),
+            "AGCT."
+        );
+    }
+
+    #[test]
+    fn diff_options_match_case_and_end_gap_differences() {
+        let theme = &crate::config::theme::EVERFOREST_DARK.sequence;
+        let compared = 2..5;
+        let mode = |diff_options, diff_columns| RowRenderMode {
+            alignment_type: libmsa::AlignmentType::Dna,
+            diff_against: Some(b"ACGTA--"),
+            diff_ambiguity: DiffAmbiguity::Strict,
+            diff_options,
+            diff_columns,
+            qualities: None,
+            conservation: None,
+        };
+        let text = |mode| spans_text(&format_row_spans(b"--gTC.A", theme, mode));
+        let options = |ignore_case, ignore_end_gaps| DiffOptions {
+            ignore_case,
+            ignore_end_gaps,
+        };
+
+        assert_eq!(text(mode(DiffOptions::default(), None)), "--g.C.A");
+        assert_eq!(text(mode(options(true, false), None)), "--..C.A");
+        assert_eq!(text(mode(options(false, true), Some(&compared))), "..g.C..");
+    }
+
+    #[test]
+    fn row_spans_darken_low_quality_backgrounds() {
+        let theme = &crate::config::theme::EVERFOREST_DARK.sequence;
+        let mode = RowRenderMode {
+            alignment_type: libmsa::AlignmentType::Dna,
+            diff_against: None,
+            diff_ambiguity: DiffAmbiguity::Strict,
+            diff_options: DiffOptions::default(),
+            diff_columns: None,
+            qualities: Some(&[40, 0]),
+            conservation: None,
+        };
+
+        let spans = format_row_spans(b"AA", theme, mode);
+
+        assert_eq!(
+            spans[0].style,
+            theme.style_for(b'A', libmsa::AlignmentType::Dna)
+        );
+        let Some(Color::Rgb(full, _, _)) = spans[0].style.bg else {
+            panic!("theme colours should be rgb");
+        };
+        let Some(Color::Rgb(shaded, _, _)) = spans[1].style.bg else {
+            panic!("shaded colours should be rgb");
+        };
+        assert!(shaded < full);
+    }
+
+    #[test]
+    fn foreground_style_colours_the_letter_only() {
+        let theme = crate::config::theme::SequenceTheme {
+            style: crate::config::theme::ColorStyle::Foreground,
+            ..crate::config::theme::EVERFOREST_DARK.sequence
+        };
+        let mode = RowRenderMode {
+            alignment_type: libmsa::AlignmentType::Dna,
+            diff_against: None,
+            diff_ambiguity: DiffAmbiguity::Strict,
+            diff_options: DiffOptions::default(),
+            diff_columns: None,
+            qualities: Some(&[40, 0]),
+            conservation: None,
+        };
+
+        let spans = format_row_spans(b"AA", &theme, mode);
+
+        assert_eq!(spans[0].style.bg, None);
+        assert_eq!(spans[0].style.fg, Some(theme.dna.a));
+        let Some(Color::Rgb(shaded, _, _)) = spans[1].style.fg else {
+            panic!("shaded colours should be rgb");
+        };
+        let Some(Color::Rgb(full, _, _)) = spans[0].style.fg else {
+            panic!("theme colours should be rgb");
+        };
+        assert!(shaded < full);
+    }
+
+    #[test]
+    fn conserved_columns_fade_towards_grey() {
+        let theme = &crate::config::theme::EVERFOREST_DARK.sequence;
+        let mode = RowRenderMode {
+            alignment_type: libmsa::AlignmentType::Dna,
+            diff_against: None,
+            diff_ambiguity: DiffAmbiguity::Strict,
+            diff_options: DiffOptions::default(),
+            diff_columns: None,
+            qualities: None,
+            conservation: Some(&[Some(0.0), Some(1.0), None]),
+        };
+
+        let spans = format_row_spans(b"AAA", theme, mode);
+
+        let full = theme.style_for(b'A', libmsa::AlignmentType::Dna);
+        assert_eq!(spans[0].style, full);
+        assert_eq!(spans[2].style, full);
+        let spread = |color: Option<Color>| {
+            let Some(Color::Rgb(red, green, blue)) = color else {
+                panic!("theme colours should be rgb");
+            };
+            red.max(green).max(blue) - red.min(green).min(blue)
+        };
+        assert!(spread(spans[1].style.bg) < spread(full.bg));
+    }
+
+    #[test]
+    fn visible_qualities_follow_the_row() {
+        let alignment = libmsa::Alignment::new(vec![
+            libmsa::RawSequence {
+                id: "read".to_string(),
+                sequence: b"ACGT".to_vec(),
+                quality: Some(vec![10, 20, 30, 40]),
+            },
+            raw("plain", b"ACGT"),
+        ])
+        .expect("test alignment should be valid");
+
+        let read = alignment.sequence(0).expect("row should resolve");
+        let plain = alignment.sequence(1).expect("row should resolve");
+
+        assert_eq!(visible_qualities(read, &(1..3)), Some(vec![20, 30]));
+        assert_eq!(visible_qualities(plain, &(1..3)), None);
+    }
+
     #[test]
     fn visible_protein_range_includes_complete_codons_overlapping_window() {
         let range = visible_protein_range(&(1..8), libmsa::ReadingFrame::Frame1, 9);
@@ -309,6 +633,21 @@ mod tests {
         assert_eq!(spans_text(&spans), " M  K  F ");
     }
 
+    #[test]
+    fn translated_window_spans_keep_the_visible_bases_of_cut_codons() {
+        let cells = |protein_col: usize| {
+            let label = ["a", "b", "c"][protein_col];
+            Some([label.to_string(), label.to_uppercase(), label.to_string()].map(Span::raw))
+        };
+
+        let spans =
+            translated_window_spans(&(2..8), 9, libmsa::ReadingFrame::Frame1, cells).unwrap();
+        assert_eq!(spans_text(&spans), "abBbcC");
+
+        let missing = translated_window_spans(&(0..9), 9, libmsa::ReadingFrame::Frame1, |_| None);
+        assert!(missing.is_none());
+    }
+
     #[test]
     fn translated_row_spans_render_diff_matches_in_centre_cells_only() {
         let alignment = libmsa::Alignment::new(vec![raw("seq1", b"ATGCCCTTT")])
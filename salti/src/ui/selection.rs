@@ -1,10 +1,15 @@
 use std::ops::Range;
 
 use ratatui::layout::Rect;
+use ratatui::macros::vertical;
+use ratatui::widgets::Block;
 
 use crate::{
     core::{Viewport, model::AlignmentModel},
-    ui::{layout::pinned_section_layout, ui_state::MouseSelection},
+    ui::{
+        layout::{AppLayout, pinned_section_layout},
+        ui_state::MouseSelection,
+    },
 };
 
 pub fn codon_span_for_absolute_column(
@@ -37,29 +42,80 @@ pub fn selection_point_crosshair(
     let col_offset = usize::from(mouse_x - sequence_rows_area.x);
 
     let window = viewport.window();
-    let relative_col = window.col_range.start + col_offset;
+    let relative_col = window.col_range.start + col_offset * viewport.cols_per_cell();
     let absolute_col = alignment.view().absolute_column_id(relative_col)?;
-    let band = pinned_section_layout(
-        alignment.rows().pinned().len(),
+    let absolute_row = absolute_row_at_offset(
+        alignment,
+        viewport,
         sequence_rows_area.height as usize,
-    );
+        row_offset,
+    )?;
 
-    let absolute_row = if row_offset < band.pinned_rendered {
-        let pinned = alignment.rows().pinned();
-        let pinned_index = row_offset;
-        if pinned_index >= pinned.len() {
-            return None;
-        }
-        pinned[pinned_index]
-    } else if row_offset < band.pinned_rendered + band.divider_height {
+    Some((absolute_row, absolute_col))
+}
+
+/// resolves the sequence whose name is drawn at the given position in the sequence id pane
+pub fn sequence_id_pane_row(
+    alignment: &AlignmentModel,
+    viewport: &Viewport,
+    layout: &AppLayout,
+    mouse_x: u16,
+    mouse_y: u16,
+) -> Option<usize> {
+    let [_, rows_area] = Block::bordered()
+        .inner(layout.sequence_id_pane)
+        .layout(&vertical![==layout.ruler_height, *=1]);
+    if !rows_area.contains((mouse_x, mouse_y).into()) {
         return None;
+    }
+
+    absolute_row_at_offset(
+        alignment,
+        viewport,
+        rows_area.height as usize,
+        usize::from(mouse_y - rows_area.y),
+    )
+}
+
+/// absolute row drawn at a terminal cell, over either the sequence ID pane or the sequence rows
+/// of the panned layout
+pub fn sequence_at_point(
+    alignment: &AlignmentModel,
+    viewport: &Viewport,
+    layout: &AppLayout,
+    x: u16,
+    y: u16,
+) -> Option<usize> {
+    sequence_id_pane_row(alignment, viewport, layout, x, y).or_else(|| {
+        selection_point_crosshair(
+            alignment,
+            viewport,
+            layout.alignment_pane_sequence_rows,
+            x,
+            y,
+        )
+        .map(|(sequence_id, _)| sequence_id)
+    })
+}
+
+fn absolute_row_at_offset(
+    alignment: &AlignmentModel,
+    viewport: &Viewport,
+    available_height: usize,
+    row_offset: usize,
+) -> Option<usize> {
+    let band = pinned_section_layout(alignment.rows().pinned().len(), available_height);
+
+    if row_offset < band.pinned_rendered {
+        alignment.rows().pinned().get(row_offset).copied()
+    } else if row_offset < band.pinned_rendered + band.divider_height {
+        None
     } else {
         let scroll_offset = row_offset - band.pinned_rendered - band.divider_height;
-        let relative_row = window.row_range.start + scroll_offset;
-        alignment.view().absolute_row_id(relative_row)?
-    };
-
-    Some((absolute_row, absolute_col))
+        let relative_row =
+            viewport.window().row_range.start + scroll_offset * viewport.rows_per_line();
+        alignment.view().absolute_row_id(relative_row)
+    }
 }
 
 pub fn selection_row_bounds(selection: MouseSelection) -> (usize, usize) {
@@ -165,6 +221,7 @@ mod tests {
         libmsa::RawSequence {
             id: id.to_string(),
             sequence: sequence.to_vec(),
+            quality: None,
         }
     }
 
@@ -349,6 +406,22 @@ mod tests {
         assert_eq!(result, Some((2, 3)));
     }
 
+    #[test]
+    fn compact_lines_map_to_the_upper_row_of_each_pair() {
+        let model = alignment_model(&["s1", "s2", "s3", "s4"]);
+        let mut viewport = Viewport::default();
+        viewport.set_cell_size(2, 1);
+        viewport.update_dimensions(4, 4, 2);
+        viewport.set_bounds(4, 4, 2);
+
+        let area = Rect::new(0, 0, 4, 2);
+        assert_eq!(
+            selection_point_crosshair(&model, &viewport, area, 1, 1),
+            Some((2, 1))
+        );
+        assert_eq!(viewport.line_of_row(3), Some(1));
+    }
+
     #[test]
     fn crosshair_handles_pinned_band() {
         let mut model = alignment_model(&["s1", "s2", "s3", "s4"]);
@@ -1,33 +1,261 @@
+use std::{fmt, str::FromStr};
+
 use crate::{
     core::{model::AlignmentModel, viewport::ViewportWindow},
     ui::{
-        layout::{AppLayout, RULER_HEIGHT_ROWS, pinned_section_layout},
-        ui_state::ThemeState,
+        layout::{AppLayout, RULER_HEIGHT_ROWS, WrappedLayout, pinned_section_layout},
+        ui_state::{ThemeState, UiState},
     },
 };
+use anyhow::format_err;
 use ratatui::Frame;
-use ratatui::style::{Style, Styled};
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style, Styled};
 use ratatui::symbols::merge::MergeStrategy;
-use ratatui::text::Line;
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Paragraph};
+use regex::Regex;
+
+/// how sequence IDs longer than the sequence ID pane are shown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IdTruncation {
+    /// cut at the pane edge; the rest is reached by scrolling the names
+    #[default]
+    Clip,
+    /// keep the end of the ID, eliding the start
+    Start,
+    /// keep both ends of the ID, eliding the middle
+    Middle,
+    /// keep the start of the ID, eliding the end
+    End,
+}
+
+impl IdTruncation {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Clip => "clip",
+            Self::Start => "start",
+            Self::Middle => "middle",
+            Self::End => "end",
+        }
+    }
+
+    pub const fn all() -> [Self; 4] {
+        [Self::Clip, Self::Start, Self::Middle, Self::End]
+    }
 
+    /// whether long IDs are scrolled rather than elided
+    pub const fn scrolls(self) -> bool {
+        matches!(self, Self::Clip)
+    }
+
+    /// fits `id`, skipping `offset` characters when scrolling, into `width` characters. a
+    /// scrolled ID shows `…` at each edge where more of it is hidden.
+    pub fn apply(self, id: &str, offset: usize, width: usize) -> String {
+        let char_count = id.chars().count();
+        if self.scrolls() {
+            let mut visible: Vec<char> = id.chars().skip(offset).take(width).collect();
+            if visible.len() > 1 {
+                if offset > 0 {
+                    visible[0] = '…';
+                }
+                if offset + width < char_count {
+                    *visible.last_mut().expect("visible has more than one char") = '…';
+                }
+            }
+            return visible.into_iter().collect();
+        }
+        if char_count <= width {
+            return id.to_string();
+        }
+        if width == 0 {
+            return String::new();
+        }
+
+        let kept = width - 1;
+        let (head, tail) = match self {
+            Self::Start => (0, kept),
+            Self::Middle => (kept.div_ceil(2), kept / 2),
+            Self::Clip | Self::End => (kept, 0),
+        };
+        let mut text: String = id.chars().take(head).collect();
+        text.push('…');
+        text.extend(id.chars().skip(char_count - tail));
+        text
+    }
+}
+
+impl fmt::Display for IdTruncation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for IdTruncation {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|mode| mode.name() == value)
+            .ok_or_else(|| anyhow::format_err!("invalid ID truncation: {value}"))
+    }
+}
+
+/// which 1-based row numbers are shown before sequence IDs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RowNumbers {
+    Off,
+    /// position in the input file
+    #[default]
+    File,
+    /// position on screen, counting pinned rows first
+    Display,
+    /// display position followed by file position
+    Both,
+}
+
+impl RowNumbers {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::File => "file",
+            Self::Display => "display",
+            Self::Both => "both",
+        }
+    }
+
+    pub const fn all() -> [Self; 4] {
+        [Self::Off, Self::File, Self::Display, Self::Both]
+    }
+
+    /// widest prefix the numbers take for an alignment of `row_count` rows
+    pub fn prefix_width(self, row_count: usize) -> usize {
+        let number_width = row_count.max(1).ilog10() as usize + 2;
+        match self {
+            Self::Off => 0,
+            Self::File | Self::Display => number_width,
+            Self::Both => number_width * 2,
+        }
+    }
+
+    fn spans(
+        self,
+        theme: &ThemeState,
+        display_row: usize,
+        absolute_row: usize,
+    ) -> Vec<Span<'static>> {
+        let display = format!("{} ", display_row + 1);
+        let file = format!("{} ", absolute_row + 1);
+        match self {
+            Self::Off => Vec::new(),
+            Self::File => vec![file.set_style(theme.styles.success)],
+            Self::Display => vec![display.set_style(theme.styles.success)],
+            Self::Both => vec![
+                display.set_style(theme.styles.success),
+                file.set_style(theme.styles.text_dim),
+            ],
+        }
+    }
+}
+
+impl fmt::Display for RowNumbers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for RowNumbers {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|mode| mode.name() == value)
+            .ok_or_else(|| anyhow::format_err!("invalid row numbers: {value}"))
+    }
+}
+
+/// colours the names in the sequence ID pane that match `pattern`
+#[derive(Debug, Clone)]
+pub struct IdColorRule {
+    pattern: Regex,
+    color: Color,
+}
+
+impl IdColorRule {
+    /// `color` is a colour name such as `blue` or `lightred`, a `#rrggbb` hex code or a 256
+    /// colour index
+    pub fn new(pattern: &str, color: &str) -> anyhow::Result<Self> {
+        let pattern = Regex::new(pattern)
+            .map_err(|error| format_err!("invalid ID pattern {pattern}: {error}"))?;
+        let color = color
+            .parse()
+            .map_err(|_| format_err!("invalid colour: {color}"))?;
+        Ok(Self { pattern, color })
+    }
+
+    pub fn pattern(&self) -> &str {
+        self.pattern.as_str()
+    }
+
+    pub fn matches(&self, id: &str) -> bool {
+        self.pattern.is_match(id)
+    }
+}
+
+impl PartialEq for IdColorRule {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern.as_str() == other.pattern.as_str() && self.color == other.color
+    }
+}
+
+impl Eq for IdColorRule {}
+
+/// colour of the first rule matching `id`
+fn id_color(rules: &[IdColorRule], id: &str) -> Option<Color> {
+    rules
+        .iter()
+        .find(|rule| rule.matches(id))
+        .map(|rule| rule.color)
+}
+
+/// `display_row` counts pinned rows first, then the scrollable rows of the view. cluster
+/// representatives lead with an expander badge, and IDs renamed as duplicates are highlighted
+/// ahead of any `color-ids` rule.
 fn build_sequence_id_line(
-    theme: &ThemeState,
+    ui: &UiState,
+    alignment: &AlignmentModel,
+    display_row: usize,
     absolute_row: usize,
     alignment_id: &str,
-    name_offset: usize,
-    name_width: usize,
+    window: &ViewportWindow,
     id_style: Style,
 ) -> Line<'static> {
-    let number_prefix = format!("{} ", absolute_row + 1).set_style(theme.styles.success);
+    let mut spans = ui.row_numbers.spans(&ui.theme, display_row, absolute_row);
     // sequence IDs can be longer than the visible sequence ID pane width.
-    let id_slice: String = alignment_id
-        .chars()
-        .skip(name_offset)
-        .take(name_width)
-        .collect();
+    let mut name_width = window
+        .name_range
+        .end
+        .saturating_sub(window.name_range.start);
+    if let Some(cluster) = alignment.cluster_of(absolute_row) {
+        let badge = cluster.badge();
+        name_width = name_width.saturating_sub(badge.chars().count());
+        spans.push(badge.set_style(ui.theme.styles.accent_alt));
+    }
+    let id_slice = ui
+        .id_truncation
+        .apply(alignment_id, window.name_range.start, name_width);
+    let id_style = if alignment.renamed_rows.contains(&absolute_row) {
+        ui.theme.styles.warning
+    } else if let Some(color) = id_color(&ui.id_colors, alignment_id) {
+        id_style.fg(color)
+    } else {
+        id_style
+    };
+    spans.push(id_slice.set_style(id_style));
 
-    Line::from(vec![number_prefix, id_slice.set_style(id_style)])
+    Line::from(spans)
 }
 
 fn build_pinned_divider_line(width: usize, style: Style) -> Line<'static> {
@@ -38,11 +266,13 @@ fn render_sequence_id_rows(
     f: &mut Frame,
     alignment: &AlignmentModel,
     window: &ViewportWindow,
-    theme: &ThemeState,
+    ui: &UiState,
     area: ratatui::layout::Rect,
+    ruler_height: u16,
 ) {
-    let ruler_height = usize::from(RULER_HEIGHT_ROWS);
-    let available_content_height = area.height.saturating_sub(RULER_HEIGHT_ROWS) as usize;
+    let theme = &ui.theme;
+    let available_content_height = area.height.saturating_sub(ruler_height) as usize;
+    let ruler_height = usize::from(ruler_height);
     let band_layout =
         pinned_section_layout(alignment.rows().pinned().len(), available_content_height);
     let mut lines = Vec::with_capacity(ruler_height + area.height as usize);
@@ -53,31 +283,31 @@ fn render_sequence_id_rows(
             lines.push(Line::from(
                 "Pinned sequences:".set_style(theme.styles.text_muted),
             ));
+        } else if ruler_row == usize::from(RULER_HEIGHT_ROWS) {
+            lines.push(Line::from("Depth".set_style(theme.styles.text_muted)));
         } else {
             lines.push(Line::from(" "));
         }
     }
 
-    let name_width = window
-        .name_range
-        .end
-        .saturating_sub(window.name_range.start);
-
-    for &absolute_row in alignment
+    let pinned_count = alignment.rows().pinned().len();
+    for (pinned_row, &absolute_row) in alignment
         .rows()
         .pinned()
         .iter()
         .take(band_layout.pinned_rendered)
+        .enumerate()
     {
         let Some(sequence) = alignment.base().project_absolute_row(absolute_row) else {
             continue;
         };
         lines.push(build_sequence_id_line(
-            theme,
+            ui,
+            alignment,
+            pinned_row,
             absolute_row,
             sequence.id(),
-            window.name_range.start,
-            name_width,
+            window,
             theme.styles.accent,
         ));
     }
@@ -89,16 +319,22 @@ fn render_sequence_id_rows(
         ));
     }
 
-    for relative_row in window.row_range.clone() {
+    // with compact density each line names the upper row of the pair drawn beside it
+    for relative_row in window
+        .row_range
+        .clone()
+        .step_by(ui.viewport.rows_per_line())
+    {
         let Some(sequence) = alignment.view().sequence(relative_row) else {
             continue;
         };
         lines.push(build_sequence_id_line(
-            theme,
+            ui,
+            alignment,
+            pinned_count + relative_row,
             sequence.absolute_row_id(),
             sequence.id(),
-            window.name_range.start,
-            name_width,
+            window,
             theme.styles.text,
         ));
     }
@@ -111,20 +347,138 @@ fn render_sequence_id_rows(
     );
 }
 
-pub fn render_sequence_id_pane(
+fn render_pane_block(
     f: &mut Frame,
-    layout: &AppLayout,
-    alignment: &AlignmentModel,
-    window: &ViewportWindow,
+    area: ratatui::layout::Rect,
     theme: &ThemeState,
-) {
+) -> ratatui::layout::Rect {
     let block = Block::bordered()
         .title(Line::from("Sequence Name".set_style(theme.styles.accent)))
         .border_style(theme.styles.border)
         .style(theme.styles.base_block)
         .merge_borders(MergeStrategy::Exact);
-    let inner_area = block.inner(layout.sequence_id_pane);
-    f.render_widget(block, layout.sequence_id_pane);
+    let inner_area = block.inner(area);
+    f.render_widget(block, area);
+    inner_area
+}
+
+/// start and length of the scrollbar thumb on a track `width` cells wide
+fn name_scrollbar_thumb(
+    width: usize,
+    visible: usize,
+    total: usize,
+    offset: usize,
+) -> (usize, usize) {
+    let length = (width * visible / total.max(1)).clamp(1, width);
+    let max_offset = total.saturating_sub(visible).max(1);
+    let start = (width - length) * offset.min(max_offset) / max_offset;
+    (start, length)
+}
+
+/// draws a thumb on the bottom border of the pane while names are wider than it
+fn render_name_scrollbar(
+    f: &mut Frame,
+    area: Rect,
+    alignment: &AlignmentModel,
+    window: &ViewportWindow,
+    ui: &UiState,
+) {
+    if !ui.id_truncation.scrolls() || area.width < 3 || area.height == 0 {
+        return;
+    }
+    let total = alignment.base().max_id_len();
+    let visible = window.name_range.len();
+    if total <= visible {
+        return;
+    }
 
-    render_sequence_id_rows(f, alignment, window, theme, inner_area);
+    let width = usize::from(area.width - 2);
+    let (start, length) = name_scrollbar_thumb(width, visible, total, window.name_range.start);
+    let y = area.y + area.height - 1;
+    for offset in start..start + length {
+        if let Some(cell) = f.buffer_mut().cell_mut((area.x + 1 + offset as u16, y)) {
+            cell.set_char('▬');
+            cell.set_fg(ui.theme.theme.accent_alt);
+        }
+    }
+}
+
+pub fn render_sequence_id_pane(
+    f: &mut Frame,
+    layout: &AppLayout,
+    alignment: &AlignmentModel,
+    window: &ViewportWindow,
+    ui: &UiState,
+) {
+    let inner_area = render_pane_block(f, layout.sequence_id_pane, &ui.theme);
+    render_sequence_id_rows(f, alignment, window, ui, inner_area, layout.ruler_height);
+    render_name_scrollbar(f, layout.sequence_id_pane, alignment, window, ui);
+}
+
+/// repeats the sequence names next to every block of the wrapped layout
+pub fn render_wrapped_sequence_id_pane(
+    f: &mut Frame,
+    layout: &AppLayout,
+    alignment: &AlignmentModel,
+    window: &ViewportWindow,
+    wrapped: WrappedLayout,
+    ui: &UiState,
+) {
+    let inner_area = render_pane_block(f, layout.sequence_id_pane, &ui.theme);
+    for block_area in wrapped.block_areas(inner_area) {
+        render_sequence_id_rows(f, alignment, window, ui, block_area, wrapped.ruler_height);
+    }
+    render_name_scrollbar(f, layout.sequence_id_pane, alignment, window, ui);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_ids_are_elided_at_the_chosen_end() {
+        let id = "sample_0123456789";
+
+        assert_eq!(IdTruncation::Clip.apply(id, 2, 8), "…ple_01…");
+        assert_eq!(IdTruncation::Clip.apply(id, 9, 8), "…3456789");
+        assert_eq!(IdTruncation::Clip.apply("short", 0, 8), "short");
+        assert_eq!(IdTruncation::Start.apply(id, 0, 8), "…3456789");
+        assert_eq!(IdTruncation::Middle.apply(id, 0, 8), "samp…789");
+        assert_eq!(IdTruncation::End.apply(id, 0, 8), "sample_…");
+        assert_eq!(IdTruncation::Middle.apply("short", 0, 8), "short");
+        assert!("left".parse::<IdTruncation>().is_err());
+    }
+
+    #[test]
+    fn the_first_matching_id_rule_wins() {
+        let rules = vec![
+            IdColorRule::new("^hCoV", "blue").unwrap(),
+            IdColorRule::new("outgroup", "#ff0000").unwrap(),
+        ];
+
+        assert_eq!(id_color(&rules, "hCoV-19/outgroup"), Some(Color::Blue));
+        assert_eq!(
+            id_color(&rules, "bat_outgroup"),
+            Some(Color::Rgb(255, 0, 0))
+        );
+        assert_eq!(id_color(&rules, "sample1"), None);
+        assert!(IdColorRule::new("(", "blue").is_err());
+        assert!(IdColorRule::new("x", "not-a-colour").is_err());
+    }
+
+    #[test]
+    fn name_scrollbar_thumb_tracks_the_offset() {
+        assert_eq!(name_scrollbar_thumb(10, 5, 20, 0), (0, 2));
+        assert_eq!(name_scrollbar_thumb(10, 5, 20, 15), (8, 2));
+        assert_eq!(name_scrollbar_thumb(10, 1, 1000, 500), (4, 1));
+    }
+
+    #[test]
+    fn row_number_prefix_fits_the_widest_number() {
+        assert_eq!(RowNumbers::Off.prefix_width(250), 0);
+        assert_eq!(RowNumbers::File.prefix_width(9), 2);
+        assert_eq!(RowNumbers::Display.prefix_width(250), 4);
+        assert_eq!(RowNumbers::Both.prefix_width(250), 8);
+        assert_eq!("both".parse::<RowNumbers>().ok(), Some(RowNumbers::Both));
+    }
 }
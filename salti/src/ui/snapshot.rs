@@ -0,0 +1,164 @@
+use std::ops::Range;
+
+use crossterm::Command as _;
+use crossterm::style::{Attribute, SetAttribute, SetBackgroundColor, SetForegroundColor};
+use ratatui::backend::IntoCrossterm;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+
+use crate::config::theme::{
+    ColorStyle, SequencePaletteId, ThemeId, build_theme_styles, sequence_theme_for, theme_from_id,
+};
+use crate::core::model::{DiffAmbiguity, DiffOptions};
+use crate::ui::rows::{RowRenderMode, format_row_spans, visible_bytes};
+use crate::ui::utils::truncate_label;
+
+/// what [`render_alignment_snapshot`] draws and how it is coloured
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotOptions {
+    /// alignment columns to draw, 0-based; the whole alignment when `None`
+    pub columns: Option<Range<usize>>,
+    /// rows to draw, 0-based; every row when `None`
+    pub rows: Option<Range<usize>>,
+    pub theme: ThemeId,
+    pub palette: SequencePaletteId,
+    pub color_style: ColorStyle,
+    /// width of the sequence ID drawn before each row, followed by a space. 0 leaves IDs out.
+    pub id_width: usize,
+}
+
+impl Default for SnapshotOptions {
+    fn default() -> Self {
+        Self {
+            columns: None,
+            rows: None,
+            theme: ThemeId::EverforestDark,
+            palette: SequencePaletteId::default(),
+            color_style: ColorStyle::default(),
+            id_width: 0,
+        }
+    }
+}
+
+fn clamp_range(range: Option<&Range<usize>>, len: usize) -> Range<usize> {
+    range.map_or(0..len, |range| range.start.min(len)..range.end.min(len))
+}
+
+/// draws rows of `alignment` the way the alignment pane colours them, one buffer line per row
+pub fn render_alignment_snapshot(
+    alignment: &libmsa::Alignment,
+    options: &SnapshotOptions,
+) -> Buffer {
+    let columns = clamp_range(options.columns.as_ref(), alignment.column_count());
+    let rows = clamp_range(options.rows.as_ref(), alignment.row_count());
+    let sequence_theme = sequence_theme_for(options.theme, options.palette, options.color_style);
+    let id_style = build_theme_styles(theme_from_id(options.theme)).base_block;
+    let mode = RowRenderMode {
+        alignment_type: alignment.active_type(),
+        diff_against: None,
+        diff_ambiguity: DiffAmbiguity::Strict,
+        diff_options: DiffOptions::default(),
+        diff_columns: None,
+        qualities: None,
+        conservation: None,
+    };
+    let id_columns = if options.id_width == 0 {
+        0
+    } else {
+        options.id_width + 1
+    };
+    let width = u16::try_from(id_columns + columns.len()).unwrap_or(u16::MAX);
+    let height = u16::try_from(rows.len()).unwrap_or(u16::MAX);
+    let mut buffer = Buffer::empty(Rect::new(0, 0, width, height));
+
+    for (y, row) in (0..height).zip(rows) {
+        let Some(sequence) = alignment.sequence(row) else {
+            continue;
+        };
+        let mut spans = Vec::new();
+        if options.id_width > 0 {
+            let id = truncate_label(sequence.id(), options.id_width);
+            spans.push(Span::styled(
+                format!("{id:<width$} ", width = options.id_width),
+                id_style,
+            ));
+        }
+        let bytes = visible_bytes(sequence, &columns);
+        spans.extend(format_row_spans(&bytes, &sequence_theme, mode));
+        buffer.set_line(0, y, &Line::from(spans), width);
+    }
+    buffer
+}
+
+fn write_style(output: &mut String, style: Style) {
+    let _ = SetAttribute(Attribute::Reset).write_ansi(output);
+    let _ =
+        SetForegroundColor(style.fg.unwrap_or(Color::Reset).into_crossterm()).write_ansi(output);
+    let _ =
+        SetBackgroundColor(style.bg.unwrap_or(Color::Reset).into_crossterm()).write_ansi(output);
+    if style.add_modifier.contains(Modifier::BOLD) {
+        let _ = SetAttribute(Attribute::Bold).write_ansi(output);
+    }
+}
+
+/// writes a snapshot as text with ANSI colour escapes, one line per buffer row
+pub fn snapshot_to_ansi(buffer: &Buffer) -> String {
+    let mut output = String::new();
+    for y in buffer.area.top()..buffer.area.bottom() {
+        let mut current = None;
+        for x in buffer.area.left()..buffer.area.right() {
+            let cell = &buffer[(x, y)];
+            let style = cell.style();
+            if current != Some(style) {
+                write_style(&mut output, style);
+                current = Some(style);
+            }
+            output.push_str(cell.symbol());
+        }
+        let _ = SetAttribute(Attribute::Reset).write_ansi(&mut output);
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::theme::TOKYO_NIGHT;
+
+    fn raw(id: &str, sequence: &[u8]) -> libmsa::RawSequence {
+        libmsa::RawSequence {
+            id: id.to_string(),
+            sequence: sequence.to_vec(),
+            quality: None,
+        }
+    }
+
+    #[test]
+    fn snapshot_draws_ids_and_themed_residues() {
+        let alignment =
+            libmsa::Alignment::new(vec![raw("reference", b"ACGT"), raw("sample", b"ACTT")])
+                .expect("alignment should be valid");
+        let options = SnapshotOptions {
+            columns: Some(1..10),
+            rows: Some(1..2),
+            theme: ThemeId::TokyoNight,
+            id_width: 4,
+            ..SnapshotOptions::default()
+        };
+
+        let buffer = render_alignment_snapshot(&alignment, &options);
+
+        assert_eq!(buffer.area, Rect::new(0, 0, 8, 1));
+        let text: String = (0..8).map(|x| buffer[(x, 0)].symbol()).collect();
+        assert_eq!(text, "s... CTT");
+        assert_eq!(buffer[(5, 0)].bg, TOKYO_NIGHT.sequence.dna.c);
+        let ansi = snapshot_to_ansi(&buffer);
+        assert_eq!(ansi.lines().count(), 1);
+        assert!(ansi.contains("s... "));
+        assert!(ansi.starts_with("\x1b["));
+        assert!(ansi.ends_with("\x1b[0m\n"));
+    }
+}
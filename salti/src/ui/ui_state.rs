@@ -1,13 +1,62 @@
+use std::time::Instant;
+
 use crate::{
     cli::StartupState,
     config::theme::{
-        EVERFOREST_DARK, Theme, ThemeId, ThemeStyles, build_theme_styles, theme_from_id,
+        ColorStyle, EVERFOREST_DARK, SequencePaletteId, Theme, ThemeId, ThemeStyles,
+        build_theme_styles, sequence_theme_for, theme_from_id,
+    },
+    core::{Viewport, jumps::JumpHistory, locator::Locator, marks::MarkState},
+    overlay::{overlay_state::OverlayState, perf_hud::PerfStats},
+    ui::{
+        alignment_pane::{Density, RulerOptions},
+        frame::{StatusBarTemplates, TrackedSequence},
+        layout::{AppLayout, IdPaneWidth, LayoutMode, LayoutOptions},
+        notification::{ErrorFeedback, FLASH_DURATION, Notification, NotificationLevel},
+        row_cache::{FastRender, RowCache},
+        sequence_id_pane::{IdColorRule, IdTruncation, RowNumbers},
+        ungapped_pane::UngappedSequence,
     },
-    core::Viewport,
-    overlay::overlay_state::OverlayState,
-    ui::notification::Notification,
 };
 
+/// whether commands that change the alignment are allowed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EditMode {
+    #[default]
+    ReadOnly,
+    Edit,
+}
+
+impl EditMode {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::ReadOnly => "read-only",
+            Self::Edit => "edit",
+        }
+    }
+
+    pub const fn all() -> [Self; 2] {
+        [Self::ReadOnly, Self::Edit]
+    }
+}
+
+impl std::fmt::Display for EditMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for EditMode {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|mode| mode.name() == value)
+            .ok_or_else(|| anyhow::format_err!("invalid mode: {value}"))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum LoadingState {
     #[default]
@@ -33,6 +82,21 @@ pub struct MetaState {
     pub loading_state: LoadingState,
     pub input_path: Option<String>,
     pub initial_position: usize,
+    /// whether records appended to the input are added after it loads
+    pub follow: bool,
+    /// whether consensus and conservation of the whole alignment are computed after each load
+    pub precompute: bool,
+    /// resident memory of the salti process in bytes, sampled periodically
+    pub memory_usage: Option<u64>,
+    /// the `--max-mem` limit in bytes, shown beside the memory usage
+    pub memory_limit: Option<u64>,
+    /// label and percentage of a running background export
+    pub export_progress: Option<(&'static str, usize)>,
+    /// view from a `salti://` locator, restored after the input loads
+    pub locator: Option<Locator>,
+    pub edit_mode: EditMode,
+    /// whether the alignment was edited since it was loaded or last saved
+    pub unsaved_edits: bool,
 }
 
 impl From<StartupState> for MetaState {
@@ -41,6 +105,14 @@ impl From<StartupState> for MetaState {
             loading_state: LoadingState::Idle,
             input_path: startup.file_path,
             initial_position: startup.initial_position,
+            follow: startup.follow,
+            precompute: startup.precompute,
+            memory_usage: None,
+            memory_limit: startup.max_memory.map(|limit| limit.bytes),
+            export_progress: None,
+            locator: startup.locator,
+            edit_mode: EditMode::default(),
+            unsaved_edits: false,
         }
     }
 }
@@ -53,9 +125,19 @@ pub struct MouseSelection {
     pub end_column: usize,
 }
 
+/// keyboard cursor position in view-relative row and column coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crosshair {
+    pub row: usize,
+    pub column: usize,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct ThemeState {
     pub id: ThemeId,
+    /// residue colours, kept when the theme changes
+    pub palette: SequencePaletteId,
+    pub color_style: ColorStyle,
     pub theme: Theme,
     pub styles: ThemeStyles,
 }
@@ -65,7 +147,13 @@ impl Default for ThemeState {
         let id = ThemeId::EverforestDark;
         let theme = EVERFOREST_DARK;
         let styles = build_theme_styles(theme);
-        Self { id, theme, styles }
+        Self {
+            id,
+            palette: SequencePaletteId::default(),
+            color_style: ColorStyle::default(),
+            theme,
+            styles,
+        }
     }
 }
 
@@ -73,7 +161,47 @@ impl Default for ThemeState {
 pub struct UiState {
     pub(crate) overlay: OverlayState,
     pub notification: Option<Notification>,
+    /// full text of the most recent error, reopened by `show-last-error`
+    pub last_error: Option<String>,
+    /// bell or flash that accompanies error notifications
+    pub error_feedback: ErrorFeedback,
+    /// end of the input line flash shown for the last error
+    pub flash_until: Option<Instant>,
+    /// the bell is rung once the next frame is drawn
+    pub bell_pending: bool,
     pub selection: Option<MouseSelection>,
+    pub crosshair: Option<Crosshair>,
+    pub highlights: Vec<MouseSelection>,
+    pub layout_mode: LayoutMode,
+    pub id_pane_width: IdPaneWidth,
+    pub id_truncation: IdTruncation,
+    pub row_numbers: RowNumbers,
+    /// colours for sequence IDs matching a pattern, first match wins
+    pub id_colors: Vec<IdColorRule>,
+    /// sequence shown without gaps in a pane under the consensus
+    pub ungapped: Option<UngappedSequence>,
+    /// sequence whose ungapped position is shown by the `tracked` status segment
+    pub tracked: Option<TrackedSequence>,
+    /// whether the consensus pane shows the consensus translated in the current frame
+    pub consensus_translation: bool,
+    /// whether each alignment row starts with the position of its first visible column
+    pub position_gutter: bool,
+    /// whether a bar chart of each column's residue count is drawn under the ruler
+    pub depth_track: bool,
+    /// tick spacing and coordinate origin of the alignment ruler
+    pub ruler: RulerOptions,
+    pub fast_render: FastRender,
+    /// alignment rows drawn per line of the panned layout
+    pub density: Density,
+    /// rows kept between frames while fast rendering
+    pub row_cache: RowCache,
+    pub marks: MarkState,
+    /// positions left by jump commands, revisited with `back` and `forward`
+    pub jumps: JumpHistory,
+    /// whether the viewport scrolls to rows added by `--follow`
+    pub follow_tail: bool,
+    pub status_bar: StatusBarTemplates,
+    pub perf_hud: Option<PerfStats>,
     pub theme: ThemeState,
     pub viewport: Viewport,
     pub meta: MetaState,
@@ -81,26 +209,120 @@ pub struct UiState {
 
 impl UiState {
     pub fn new(startup: StartupState) -> Self {
-        Self {
+        let theme_id = startup.theme;
+        let mut ui = Self {
             overlay: OverlayState::default(),
             notification: None,
+            last_error: None,
+            error_feedback: startup.error_feedback,
+            flash_until: None,
+            bell_pending: false,
             selection: None,
+            crosshair: None,
+            highlights: Vec::new(),
+            layout_mode: LayoutMode::default(),
+            id_pane_width: IdPaneWidth::default(),
+            id_truncation: IdTruncation::default(),
+            row_numbers: RowNumbers::default(),
+            id_colors: startup.id_colors.clone(),
+            ungapped: None,
+            tracked: None,
+            consensus_translation: false,
+            position_gutter: startup.position_gutter,
+            depth_track: startup.depth_track,
+            ruler: startup.ruler,
+            fast_render: startup.fast_render,
+            density: startup.density,
+            row_cache: RowCache::default(),
+            marks: MarkState {
+                path: startup.marks_path.clone(),
+                ..MarkState::default()
+            },
+            jumps: JumpHistory::default(),
+            follow_tail: false,
+            status_bar: startup.status_bar.clone(),
+            perf_hud: None,
             theme: ThemeState::default(),
             viewport: Viewport::default(),
             meta: MetaState::from(startup),
+        };
+        if let Some(theme_id) = theme_id {
+            ui.set_theme(theme_id);
         }
+        ui
     }
 
     pub fn set_theme(&mut self, theme_id: ThemeId) {
         if self.theme.id != theme_id {
             self.theme.id = theme_id;
             self.theme.theme = theme_from_id(theme_id);
+            self.refresh_sequence_theme();
             self.theme.styles = build_theme_styles(self.theme.theme);
         }
     }
 
+    pub fn set_sequence_palette(&mut self, palette: SequencePaletteId) {
+        self.theme.palette = palette;
+        self.refresh_sequence_theme();
+    }
+
+    pub fn set_color_style(&mut self, color_style: ColorStyle) {
+        self.theme.color_style = color_style;
+        self.refresh_sequence_theme();
+    }
+
+    /// residue colours of the theme with the chosen palette and colour style applied
+    fn refresh_sequence_theme(&mut self) {
+        self.theme.theme.sequence =
+            sequence_theme_for(self.theme.id, self.theme.palette, self.theme.color_style);
+    }
+
+    /// density the alignment rows are drawn at; the wrapped layout always draws them at normal
+    /// density
+    pub fn effective_density(&self) -> Density {
+        match self.layout_mode {
+            LayoutMode::Panned => self.density,
+            LayoutMode::Wrapped => Density::Normal,
+        }
+    }
+
+    /// the row cache when fast rendering is on for the alignment pane of `layout`
+    pub fn fast_row_cache(&self, layout: &AppLayout) -> Option<&RowCache> {
+        self.fast_render
+            .enabled(layout.alignment_pane.width)
+            .then_some(&self.row_cache)
+    }
+
+    pub fn layout_options(&self) -> LayoutOptions {
+        LayoutOptions {
+            id_pane_width: self.id_pane_width,
+            ungapped_pane: self.ungapped.is_some(),
+            consensus_translation: self.consensus_translation,
+            position_gutter: 0,
+            depth_track: self.depth_track,
+        }
+    }
+
+    /// shows `notification`, remembering it when it reports an error
+    pub fn notify(&mut self, notification: Notification) {
+        if notification.level == NotificationLevel::Error {
+            self.last_error = Some(notification.message.clone());
+            match self.error_feedback {
+                ErrorFeedback::Off => (),
+                ErrorFeedback::Bell => self.bell_pending = true,
+                ErrorFeedback::Flash => self.flash_until = Some(Instant::now() + FLASH_DURATION),
+            }
+        }
+        self.notification = Some(notification);
+    }
+
     pub fn clear_transient_state(&mut self) {
         self.selection = None;
+        self.crosshair = None;
+        self.highlights.clear();
+        self.ungapped = None;
+        self.tracked = None;
+        self.jumps.clear();
         self.overlay.close();
         self.notification = None;
     }
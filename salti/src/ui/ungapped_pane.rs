@@ -0,0 +1,216 @@
+use std::ops::Range;
+
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::Styled;
+use ratatui::symbols::merge::MergeStrategy;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph};
+
+use crate::core::liftover::ResidueIndex;
+use crate::core::model::{AlignmentModel, DiffAmbiguity, DiffOptions};
+use crate::core::viewport::ViewportWindow;
+use crate::ui::layout::AppLayout;
+use crate::ui::rows::{RowRenderMode, format_row_spans};
+use crate::ui::ui_state::ThemeState;
+use crate::ui::utils::truncate_label;
+
+/// one sequence with its gaps removed, shown under the consensus pane so that primers can be
+/// designed against it while panning the alignment
+#[derive(Debug, Clone)]
+pub struct UngappedSequence {
+    id: String,
+    residues: Vec<u8>,
+    index: ResidueIndex,
+}
+
+impl UngappedSequence {
+    pub fn from_model(alignment: &mut AlignmentModel, abs_row: usize) -> Option<Self> {
+        let index = alignment.residue_index(abs_row)?.clone();
+        let sequence = alignment.base().project_absolute_row(abs_row)?;
+        let residues = index
+            .columns()
+            .iter()
+            .filter_map(|&col| sequence.byte_at(col))
+            .collect();
+        Some(Self {
+            id: sequence.id().to_string(),
+            residues,
+            index,
+        })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// 0-based residues inside the columns shown by `window`
+    fn residues_in_view(
+        &self,
+        alignment: &AlignmentModel,
+        window: &ViewportWindow,
+    ) -> Range<usize> {
+        let view = alignment.view();
+        let first = view.absolute_column_id(window.col_range.start);
+        let last = window
+            .col_range
+            .end
+            .checked_sub(1)
+            .and_then(|col| view.absolute_column_id(col));
+        match (first, last) {
+            (Some(first), Some(last)) => self.index.residues_in(first..last + 1),
+            _ => 0..0,
+        }
+    }
+}
+
+/// first ungapped line shown, keeping the start of the highlighted residues in view
+fn first_line(
+    highlight_start: usize,
+    line_width: usize,
+    line_count: usize,
+    height: usize,
+) -> usize {
+    (highlight_start / line_width).min(line_count.saturating_sub(height))
+}
+
+fn ungapped_lines(
+    ungapped: &UngappedSequence,
+    highlight: &Range<usize>,
+    alignment_type: libmsa::AlignmentType,
+    area: Rect,
+    theme: &ThemeState,
+) -> Vec<Line<'static>> {
+    let number_width = ungapped.residues.len().max(1).to_string().len();
+    let line_width = usize::from(area.width)
+        .saturating_sub(number_width + 1)
+        .max(1);
+    let line_count = ungapped.residues.len().div_ceil(line_width);
+    let height = usize::from(area.height);
+    let mode = RowRenderMode {
+        alignment_type,
+        diff_against: None,
+        diff_ambiguity: DiffAmbiguity::Strict,
+        diff_options: DiffOptions::default(),
+        diff_columns: None,
+        qualities: None,
+        conservation: None,
+    };
+
+    ungapped
+        .residues
+        .chunks(line_width)
+        .enumerate()
+        .skip(first_line(highlight.start, line_width, line_count, height))
+        .take(height)
+        .map(|(line_index, chunk)| {
+            let start = line_index * line_width;
+            let mut spans = vec![Span::styled(
+                format!("{:>number_width$} ", start + 1),
+                theme.styles.text_dim,
+            )];
+            let coloured = format_row_spans(chunk, &theme.theme.sequence, mode);
+            spans.extend(coloured.into_iter().enumerate().map(|(offset, span)| {
+                if highlight.contains(&(start + offset)) {
+                    span
+                } else {
+                    Span::styled(span.content, theme.styles.text_dim)
+                }
+            }));
+            Line::from(spans)
+        })
+        .collect()
+}
+
+pub fn render_ungapped_pane(
+    f: &mut Frame,
+    layout: &AppLayout,
+    alignment: &AlignmentModel,
+    ungapped: &UngappedSequence,
+    window: &ViewportWindow,
+    theme: &ThemeState,
+) {
+    let (Some(id_area), Some(sequence_area)) =
+        (layout.ungapped_sequence_id_pane, layout.ungapped_pane)
+    else {
+        return;
+    };
+    let highlight = ungapped.residues_in_view(alignment, window);
+
+    let block = || {
+        Block::bordered()
+            .border_style(theme.styles.border)
+            .style(theme.styles.base_block)
+            .merge_borders(MergeStrategy::Exact)
+    };
+    let id_inner = block().inner(id_area);
+    f.render_widget(block(), id_area);
+    let in_view = if highlight.is_empty() {
+        "View: no residues".to_string()
+    } else {
+        format!("View: {}-{}", highlight.start + 1, highlight.end)
+    };
+    let id_lines = vec![
+        Line::from(
+            truncate_label(&ungapped.id, usize::from(id_inner.width))
+                .set_style(theme.styles.accent),
+        ),
+        Line::from(format!("Ungapped: {}", ungapped.residues.len()).set_style(theme.styles.text)),
+        Line::from(in_view.set_style(theme.styles.text)),
+    ];
+    f.render_widget(
+        Paragraph::new(id_lines).style(theme.styles.base_block),
+        id_inner,
+    );
+
+    let sequence_inner = block().inner(sequence_area);
+    f.render_widget(block(), sequence_area);
+    let lines = ungapped_lines(
+        ungapped,
+        &highlight,
+        alignment.base().active_type(),
+        sequence_inner,
+        theme,
+    );
+    f.render_widget(
+        Paragraph::new(lines).style(theme.styles.base_block),
+        sequence_inner,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw(id: &str, sequence: &[u8]) -> libmsa::RawSequence {
+        libmsa::RawSequence {
+            id: id.to_string(),
+            sequence: sequence.to_vec(),
+            quality: None,
+        }
+    }
+
+    #[test]
+    fn view_highlight_follows_the_visible_columns() {
+        let alignment = libmsa::Alignment::new(vec![raw("a", b"AC--GTAC"), raw("b", b"ACGTACGT")])
+            .expect("alignment should build");
+        let mut model = AlignmentModel::new(alignment).expect("model should build");
+        let ungapped = UngappedSequence::from_model(&mut model, 0).expect("row exists");
+        let window = |col_range: Range<usize>| ViewportWindow {
+            row_range: 0..2,
+            col_range,
+            name_range: 0..0,
+        };
+
+        assert_eq!(ungapped.residues, b"ACGTAC");
+        assert_eq!(ungapped.residues_in_view(&model, &window(1..5)), 1..3);
+        assert_eq!(ungapped.residues_in_view(&model, &window(2..4)), 2..2);
+    }
+
+    #[test]
+    fn first_line_keeps_the_highlight_visible_without_running_past_the_end() {
+        assert_eq!(first_line(25, 10, 10, 3), 2);
+        assert_eq!(first_line(95, 10, 10, 3), 7);
+        assert_eq!(first_line(0, 10, 2, 3), 0);
+    }
+}
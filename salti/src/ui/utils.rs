@@ -1,3 +1,56 @@
+/// writes a count with comma thousands separators, e.g. `29,903`
+pub fn format_thousands(value: usize) -> String {
+    let digits = value.to_string();
+    let mut text = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            text.push(',');
+        }
+        text.push(digit);
+    }
+    text
+}
+
+/// splits `text` into lines of at most `width` characters, breaking between words where it can
+/// and inside words longer than a line
+pub fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    if width == 0 {
+        return Vec::new();
+    }
+
+    let mut lines = Vec::new();
+    for raw_line in text.split('\n') {
+        let mut current = String::new();
+        for word in raw_line.split_whitespace() {
+            let word_len = word.chars().count();
+            let current_len = current.chars().count();
+            if !current.is_empty() && current_len + 1 + word_len <= width {
+                current.push(' ');
+                current.push_str(word);
+                continue;
+            }
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            let mut chars = word.chars().peekable();
+            while chars.peek().is_some() {
+                current = chars.by_ref().take(width).collect();
+                if chars.peek().is_some() {
+                    lines.push(std::mem::take(&mut current));
+                }
+            }
+        }
+        if !current.is_empty() {
+            lines.push(current);
+        }
+    }
+
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
+
 pub fn truncate_label(value: &str, width: usize) -> String {
     let char_count = value.chars().count();
     if char_count <= width {